@@ -0,0 +1,111 @@
+//! Blend slider and change overlay for comparing two file visualisations.
+
+use eframe::egui;
+use egui::Color32;
+
+/// Height of the comparison bar in points.
+pub const BAR_HEIGHT: f32 = 32.0;
+
+/// Second render of a file comparison and the pixels that differ.
+pub struct Diff {
+    /// Render of the second file, the same size as the first.
+    pub after: image::RgbaImage,
+    /// Whether each pixel, row by row, shows a different byte in the two files.
+    pub changed: Vec<bool>,
+    /// Color of changed pixels in the overlay, as unmultiplied RGBA.
+    pub accent: [u8; 4],
+}
+
+/// Comparison controls: blend position, overlay toggle and the texture
+/// showing them.
+pub struct DiffPanel {
+    /// Render of the first file.
+    before: egui::ColorImage,
+    /// Render of the second file.
+    after: egui::ColorImage,
+    /// Which pixels differ.
+    changed: Vec<bool>,
+    /// Overlay color for changed pixels.
+    accent: Color32,
+    /// Position between the first (0) and second (1) render.
+    blend: f32,
+    /// Whether changed pixels are painted in the accent color.
+    overlay: bool,
+    /// Settings the current texture was built for, with the texture.
+    shown: Option<((f32, bool), egui::TextureHandle)>,
+}
+
+impl DiffPanel {
+    /// Panel comparing `before` with the render in `diff`.
+    pub fn new(before: egui::ColorImage, diff: Diff) -> Self {
+        let size = [diff.after.width() as usize, diff.after.height() as usize];
+        let [r, g, b, a] = diff.accent;
+        Self {
+            after: egui::ColorImage::from_rgba_unmultiplied(size, diff.after.as_raw()),
+            before,
+            changed: diff.changed,
+            accent: Color32::from_rgba_unmultiplied(r, g, b, a),
+            blend: 0.0,
+            overlay: true,
+            shown: None,
+        }
+    }
+
+    /// Draw the blend slider, overlay toggle and change count.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("First");
+            ui.add(egui::Slider::new(&mut self.blend, 0.0..=1.0).show_value(false));
+            ui.label("Second");
+            ui.separator();
+            ui.checkbox(&mut self.overlay, "Highlight changes");
+            let changed = self.changed.iter().filter(|&&c| c).count();
+            let share = changed as f64 * 100.0 / self.changed.len().max(1) as f64;
+            ui.label(format!(
+                "{changed} of {} pixels differ ({share:.2}%)",
+                self.changed.len()
+            ));
+        });
+    }
+
+    /// Texture showing the current blend and overlay, rebuilt only when
+    /// either changes.
+    pub fn texture(&mut self, ctx: &egui::Context) -> &egui::TextureHandle {
+        let settings = (self.blend, self.overlay);
+        if self
+            .shown
+            .as_ref()
+            .is_none_or(|(shown, _)| *shown != settings)
+        {
+            let pixels = self
+                .before
+                .pixels
+                .iter()
+                .zip(&self.after.pixels)
+                .zip(&self.changed)
+                .map(|((&a, &b), &changed)| {
+                    if changed && self.overlay {
+                        self.accent
+                    } else {
+                        mix(a, b, self.blend)
+                    }
+                })
+                .collect();
+            let image = egui::ColorImage::new(self.before.size, pixels);
+            let texture = ctx.load_texture("diff-blend", image, egui::TextureOptions::NEAREST);
+            self.shown = Some((settings, texture));
+        }
+        &self.shown.as_ref().expect("texture was just set").1
+    }
+}
+
+/// Color a fraction `t` of the way from `a` to `b`.
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let lerp = |x: u8, y: u8| (f32::from(x) + (f32::from(y) - f32::from(x)) * t).round() as u8;
+    Color32::from_rgba_premultiplied(
+        lerp(a.r(), b.r()),
+        lerp(a.g(), b.g()),
+        lerp(a.b(), b.b()),
+        lerp(a.a(), b.a()),
+    )
+}
@@ -0,0 +1,169 @@
+//! Byte-frequency sidebar linked to a file visualisation.
+
+use eframe::egui;
+use egui::{Color32, Rect, Sense, Stroke, Vec2};
+
+/// Height of the bar chart in points.
+const CHART_HEIGHT: f32 = 160.0;
+/// Width of the sidebar in points.
+pub const PANEL_WIDTH: f32 = 280.0;
+/// Backdrop of the bar chart, mid-grey so black and white bars both show.
+const BACKDROP: Color32 = Color32::from_gray(128);
+/// How far pixels of other bytes fade toward the backdrop while one byte is
+/// highlighted.
+const FADE: f32 = 0.8;
+
+/// Byte frequencies of a visualised file and the byte behind each pixel.
+pub struct Histogram {
+    /// Occurrences of each byte value in the file.
+    pub counts: [u64; 256],
+    /// Color of each byte value's bar, as unmultiplied RGBA.
+    pub colors: [[u8; 4]; 256],
+    /// Byte drawn at each pixel of the image, row by row.
+    pub pixel_bytes: Vec<u8>,
+}
+
+/// Sidebar state: the histogram, its scale and the highlighted byte.
+pub struct HistogramPanel {
+    /// Data being shown.
+    histogram: Histogram,
+    /// Pixels of the image without highlighting.
+    pixels: egui::ColorImage,
+    /// Whether bar heights use a log scale.
+    log_scale: bool,
+    /// Byte whose pixels are highlighted, with the texture showing it.
+    highlight: Option<(u8, egui::TextureHandle)>,
+}
+
+impl HistogramPanel {
+    /// Panel for `histogram` over the unhighlighted `pixels`.
+    pub fn new(histogram: Histogram, pixels: egui::ColorImage) -> Self {
+        Self {
+            histogram,
+            pixels,
+            log_scale: true,
+            highlight: None,
+        }
+    }
+
+    /// Byte drawn at pixel (`x`, `y`), if the pixel is inside the image.
+    pub fn byte_at(&self, x: usize, y: usize) -> Option<u8> {
+        let [width, height] = self.pixels.size;
+        (x < width && y < height)
+            .then(|| self.histogram.pixel_bytes.get(y * width + x).copied())
+            .flatten()
+    }
+
+    /// Draw the sidebar, marking `pointed` (the byte under the pointer on the
+    /// canvas), and return the bucket under the pointer, if any.
+    pub fn show(&mut self, ui: &mut egui::Ui, pointed: Option<u8>) -> Option<u8> {
+        ui.set_width(PANEL_WIDTH);
+        ui.heading("Byte histogram");
+        ui.checkbox(&mut self.log_scale, "Log scale");
+        ui.add_space(4.0);
+
+        let (rect, response) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), CHART_HEIGHT),
+            Sense::hover(),
+        );
+        let bar_width = rect.width() / 256.0;
+        let hovered = response
+            .hover_pos()
+            .map(|pos| ((pos.x - rect.min.x) / bar_width).clamp(0.0, 255.0) as u8);
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, BACKDROP);
+        let max = self.histogram.counts.iter().copied().max().unwrap_or(0);
+        for (byte, &count) in self.histogram.counts.iter().enumerate() {
+            let left = rect.min.x + byte as f32 * bar_width;
+            let height = bar_fraction(count, max, self.log_scale) * rect.height();
+            let bar = Rect::from_min_max(
+                egui::pos2(left, rect.max.y - height),
+                egui::pos2(left + bar_width, rect.max.y),
+            );
+            let [r, g, b, a] = self.histogram.colors[byte];
+            painter.rect_filled(bar, 0.0, Color32::from_rgba_unmultiplied(r, g, b, a));
+        }
+        if let Some(byte) = hovered.or(pointed) {
+            let left = rect.min.x + f32::from(byte) * bar_width;
+            let column = Rect::from_min_max(
+                egui::pos2(left - 1.0, rect.min.y),
+                egui::pos2(left + bar_width + 1.0, rect.max.y),
+            );
+            painter.rect_stroke(
+                column,
+                0.0,
+                Stroke::new(1.0, ui.visuals().strong_text_color()),
+                egui::StrokeKind::Outside,
+            );
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("0x00");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label("0xff");
+            });
+        });
+        match hovered.or(pointed) {
+            Some(byte) => ui.label(self.describe(byte)),
+            None => ui.label("Hover a bar to highlight its pixels."),
+        };
+        hovered
+    }
+
+    /// One-line summary of `byte`'s frequency.
+    fn describe(&self, byte: u8) -> String {
+        let count = self.histogram.counts[usize::from(byte)];
+        let total: u64 = self.histogram.counts.iter().sum();
+        let share = if total == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / total as f64
+        };
+        let glyph = if byte.is_ascii_graphic() {
+            format!(" '{}'", char::from(byte))
+        } else {
+            String::new()
+        };
+        format!("0x{byte:02x}{glyph}: {count} bytes ({share:.2}%)")
+    }
+
+    /// Texture showing the pixels of `byte` with every other pixel faded,
+    /// rebuilt only when the byte changes.
+    pub fn highlight(&mut self, ctx: &egui::Context, byte: u8) -> &egui::TextureHandle {
+        if self
+            .highlight
+            .as_ref()
+            .is_none_or(|(shown, _)| *shown != byte)
+        {
+            let mut image = self.pixels.clone();
+            for (pixel, &b) in image.pixels.iter_mut().zip(&self.histogram.pixel_bytes) {
+                if b != byte {
+                    *pixel = fade(*pixel);
+                }
+            }
+            let texture =
+                ctx.load_texture("histogram-highlight", image, egui::TextureOptions::NEAREST);
+            self.highlight = Some((byte, texture));
+        }
+        &self.highlight.as_ref().expect("highlight was just set").1
+    }
+}
+
+/// Height of a bar as a fraction of the chart, relative to the largest count.
+fn bar_fraction(count: u64, max: u64, log_scale: bool) -> f32 {
+    if max == 0 {
+        return 0.0;
+    }
+    if log_scale {
+        ((count as f64).ln_1p() / (max as f64).ln_1p()) as f32
+    } else {
+        (count as f64 / max as f64) as f32
+    }
+}
+
+/// Blend `color` most of the way toward the chart backdrop.
+fn fade(color: Color32) -> Color32 {
+    let mix = |c: u8| (f32::from(c) * (1.0 - FADE) + f32::from(BACKDROP.r()) * FADE).round() as u8;
+    Color32::from_rgb(mix(color.r()), mix(color.g()), mix(color.b()))
+}
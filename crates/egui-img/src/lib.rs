@@ -2,6 +2,9 @@
 
 //! Tiny helper to show an RGBA image inside an egui window.
 
+mod diff;
+mod histogram;
+
 use std::{
     fs::File,
     io::BufWriter,
@@ -15,6 +18,9 @@ use egui::{Vec2, load::SizedTexture};
 use image::RgbaImage;
 use png::{BitDepth, ColorType, Encoder};
 
+pub use crate::{diff::Diff, histogram::Histogram};
+use crate::{diff::DiffPanel, histogram::HistogramPanel};
+
 /// Simple egui app that shows a single texture with a zoom slider.
 struct ImageViewer {
     /// Texture containing the displayed image.
@@ -29,6 +35,12 @@ struct ImageViewer {
     screenshot: Option<ScreenshotState>,
     /// Window title shown in the header.
     title: String,
+    /// Byte histogram sidebar, for file visualisations.
+    histogram: Option<HistogramPanel>,
+    /// Byte under the pointer on the canvas in the previous frame.
+    pointed: Option<u8>,
+    /// Comparison with a second render, for file diffs.
+    diff: Option<DiffPanel>,
 }
 
 /// Layout constants for the viewer window.
@@ -78,9 +90,14 @@ impl ImageViewer {
         title: String,
         color_image: egui::ColorImage,
         screenshot: Option<PathBuf>,
+        histogram: Option<Histogram>,
+        diff: Option<Diff>,
     ) -> Self {
         let image_size = color_image.size;
         let (base_zoom, _) = initial_view(image_size);
+        let histogram =
+            histogram.map(|histogram| HistogramPanel::new(histogram, color_image.clone()));
+        let diff = diff.map(|diff| DiffPanel::new(color_image.clone(), diff));
         let texture =
             cc.egui_ctx
                 .load_texture(title.clone(), color_image, egui::TextureOptions::NEAREST);
@@ -95,6 +112,9 @@ impl ImageViewer {
                 output_path,
             }),
             title,
+            histogram,
+            pointed: None,
+            diff,
         }
     }
 
@@ -106,15 +126,28 @@ impl ImageViewer {
         )
     }
 
-    /// Render the texture into the given `ui` at `display_size`.
-    fn paint_image(&self, ui: &mut egui::Ui, display_size: Vec2) {
-        let sized_texture = SizedTexture::from_handle(&self.texture);
-
-        ui.add(
+    /// Render `texture` into the given `ui` at `display_size`, noting the
+    /// byte under the pointer when a histogram is shown.
+    fn paint_image(
+        &mut self,
+        ui: &mut egui::Ui,
+        display_size: Vec2,
+        texture: &egui::TextureHandle,
+    ) {
+        let sized_texture = SizedTexture::from_handle(texture);
+
+        let response = ui.add(
             egui::Image::from_texture(sized_texture)
                 .texture_options(egui::TextureOptions::NEAREST)
-                .fit_to_exact_size(display_size),
+                .fit_to_exact_size(display_size)
+                .sense(egui::Sense::hover()),
         );
+        if let Some(panel) = &self.histogram {
+            self.pointed = response.hover_pos().and_then(|pos| {
+                let offset = (pos - response.rect.min) / self.zoom;
+                panel.byte_at(offset.x as usize, offset.y as usize)
+            });
+        }
     }
 
     /// Kick off and save a screenshot if configured. Returns true when capture completes.
@@ -156,6 +189,21 @@ impl ImageViewer {
 impl eframe::App for ImageViewer {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let title = self.title.clone();
+        let mut texture = self.texture.clone();
+        if let Some(panel) = &mut self.histogram {
+            let pointed = self.pointed;
+            let hovered = egui::SidePanel::right("histogram")
+                .resizable(false)
+                .show(ctx, |ui| panel.show(ui, pointed))
+                .inner;
+            if let Some(byte) = hovered {
+                texture = panel.highlight(ctx, byte).clone();
+            }
+        }
+        if let Some(panel) = &mut self.diff {
+            egui::TopBottomPanel::bottom("diff").show(ctx, |ui| panel.show(ui));
+            texture = panel.texture(ctx).clone();
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             if self.screenshot.is_none() {
                 ui.heading(&title);
@@ -198,7 +246,7 @@ impl eframe::App for ImageViewer {
                         ui.allocate_ui_with_layout(
                             padded_size,
                             egui::Layout::centered_and_justified(egui::Direction::TopDown),
-                            |ui| self.paint_image(ui, display_size),
+                            |ui| self.paint_image(ui, display_size, &texture),
                         );
                     },
                 );
@@ -219,7 +267,7 @@ impl eframe::App for ImageViewer {
                         ui.allocate_ui_with_layout(
                             container,
                             egui::Layout::centered_and_justified(egui::Direction::TopDown),
-                            |ui| self.paint_image(ui, display_size),
+                            |ui| self.paint_image(ui, display_size, &texture),
                         );
                     });
             }
@@ -240,7 +288,71 @@ fn initial_window_size(image_size: [usize; 2]) -> Vec2 {
 /// This function blocks until the window is closed by the user.
 /// The image is uploaded with nearest‑neighbour sampling to keep pixels crisp.
 pub fn view_image(title: &str, image: RgbaImage) -> Result<()> {
+    run_viewer(title, image, None, None)
+}
+
+/// Show a file visualisation with a byte histogram beside it.
+///
+/// Hovering a bar highlights the pixels drawn from that byte value, and
+/// hovering a pixel marks its byte's bar. Blocks until the window is closed.
+pub fn view_image_with_histogram(
+    title: &str,
+    image: RgbaImage,
+    histogram: Histogram,
+) -> Result<()> {
+    let size = [image.width() as usize, image.height() as usize];
+    if histogram.pixel_bytes.len() != size[0] * size[1] {
+        return Err(anyhow!(
+            "histogram covers {} pixels but the image has {}",
+            histogram.pixel_bytes.len(),
+            size[0] * size[1]
+        ));
+    }
+    run_viewer(title, image, Some(histogram), None)
+}
+
+/// Compare two file visualisations, `before` and the render in `diff`.
+///
+/// A slider blends from the first render to the second, and changed pixels
+/// can be painted in the diff's accent color. Blocks until the window is
+/// closed.
+pub fn view_image_diff(title: &str, before: RgbaImage, diff: Diff) -> Result<()> {
+    let size = [before.width() as usize, before.height() as usize];
+    if diff.after.dimensions() != before.dimensions() {
+        return Err(anyhow!(
+            "cannot compare a {}×{} render with a {}×{} one",
+            size[0],
+            size[1],
+            diff.after.width(),
+            diff.after.height()
+        ));
+    }
+    if diff.changed.len() != size[0] * size[1] {
+        return Err(anyhow!(
+            "diff covers {} pixels but the image has {}",
+            diff.changed.len(),
+            size[0] * size[1]
+        ));
+    }
+    run_viewer(title, before, None, Some(diff))
+}
+
+/// Open the viewer window on `image`, with an optional histogram sidebar or
+/// comparison bar.
+fn run_viewer(
+    title: &str,
+    image: RgbaImage,
+    mut histogram: Option<Histogram>,
+    mut diff: Option<Diff>,
+) -> Result<()> {
     let size = [image.width() as usize, image.height() as usize];
+    let mut window_size = initial_window_size(size);
+    if histogram.is_some() {
+        window_size.x += histogram::PANEL_WIDTH + UI_OVERHEAD_X_PX;
+    }
+    if diff.is_some() {
+        window_size.y += diff::BAR_HEIGHT;
+    }
     let mut color_image = Some(egui::ColorImage::from_rgba_unmultiplied(
         size,
         image.as_raw(),
@@ -251,7 +363,7 @@ pub fn view_image(title: &str, image: RgbaImage) -> Result<()> {
 
     let native_options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size(initial_window_size(size))
+            .with_inner_size(window_size)
             .with_title(window_title.clone()),
         ..Default::default()
     };
@@ -269,6 +381,8 @@ pub fn view_image(title: &str, image: RgbaImage) -> Result<()> {
                 window_title.clone(),
                 color_image,
                 None,
+                histogram.take(),
+                diff.take(),
             )))
         }),
     )
@@ -317,6 +431,8 @@ pub fn view_image_with_screenshot(title: &str, image: RgbaImage, output: &Path)
                 window_title.clone(),
                 color_image,
                 Some(output_path.clone()),
+                None,
+                None,
             )))
         }),
     )
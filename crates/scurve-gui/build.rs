@@ -0,0 +1,68 @@
+//! Build script that records build metadata for the About dialog.
+//!
+//! Emits the git commit hash and build profile as compile-time environment
+//! variables. The dependency license table is checked in and regenerated with
+//! `cargo xtask licenses`.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+    let workspace_root = manifest_dir
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(&manifest_dir)
+        .to_path_buf();
+
+    let git_dir = workspace_root.join(".git");
+    for watched in ["HEAD", "index"] {
+        let path = git_dir.join(watched);
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+
+    println!(
+        "cargo:rustc-env=SCURVE_GIT_HASH={}",
+        git_hash(&workspace_root)
+    );
+    println!(
+        "cargo:rustc-env=SCURVE_BUILD_PROFILE={}",
+        build_profile(&out_dir)
+    );
+}
+
+/// Short hash of the current git commit, or `unknown` outside a checkout.
+fn git_hash(root: &Path) -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short=10", "HEAD"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Name of the cargo profile directory this build writes into.
+///
+/// `OUT_DIR` is `<target>/<profile>/build/<pkg>-<hash>/out`, which preserves
+/// custom profile names such as `wasm-release` that `PROFILE` collapses.
+fn build_profile(out_dir: &Path) -> String {
+    out_dir
+        .ancestors()
+        .nth(3)
+        .and_then(Path::file_name)
+        .and_then(|s| s.to_str())
+        .map(str::to_string)
+        .or_else(|| env::var("PROFILE").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
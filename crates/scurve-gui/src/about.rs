@@ -1,16 +1,34 @@
-use std::sync::OnceLock;
-
 use egui::epaint::Shadow;
 use egui_commonmark::CommonMarkViewer;
+use spacecurve::{curve_from_name, registry};
+
+use crate::{
+    APP_NAME,
+    i18n::{Language, Msg},
+    theme,
+};
 
-use crate::{APP_NAME, theme};
+/// Tabs of the About dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AboutTab {
+    /// Introduction, licenses and build information.
+    #[default]
+    About,
+    /// Thumbnails of every available curve.
+    Gallery,
+}
 
 /// Show the modal About dialog overlay, handling open/close interactions.
+///
+/// Returns the curve picked in the gallery tab, which also closes the dialog.
 pub fn show_about_dialog(
     ctx: &egui::Context,
     about_open: &mut bool,
+    tab: &mut AboutTab,
     cache: &mut egui_commonmark::CommonMarkCache,
-) {
+    curves: &[&'static str],
+    lang: Language,
+) -> Option<&'static str> {
     let (was_just_opened, dialog_opened_id) = track_dialog_open(ctx);
     draw_dim_background(ctx);
 
@@ -22,7 +40,20 @@ pub fn show_about_dialog(
     let center_pos = screen_rect.center() - dialog_size * 0.5;
 
     let mut should_close = false;
-    let response = show_about_area(ctx, cache, dialog_size, center_pos, &mut should_close);
+    let mut picked = None;
+    let response = show_about_area(
+        ctx,
+        &mut AboutContent {
+            tab,
+            cache,
+            curves,
+            picked: &mut picked,
+        },
+        lang,
+        dialog_size,
+        center_pos,
+        &mut should_close,
+    );
 
     if !was_just_opened
         && ctx.input(|i| i.pointer.primary_clicked())
@@ -32,7 +63,7 @@ pub fn show_about_dialog(
         *about_open = false;
     }
 
-    if should_close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+    if should_close || picked.is_some() || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
         *about_open = false;
         clear_dialog_open(ctx, dialog_opened_id);
     }
@@ -40,6 +71,19 @@ pub fn show_about_dialog(
     if !*about_open {
         clear_dialog_open(ctx, dialog_opened_id);
     }
+    picked
+}
+
+/// Tab state and data for the dialog body.
+struct AboutContent<'a> {
+    /// Currently shown tab.
+    tab: &'a mut AboutTab,
+    /// Markdown cache for the About tab.
+    cache: &'a mut egui_commonmark::CommonMarkCache,
+    /// Curve keys shown in the gallery.
+    curves: &'a [&'static str],
+    /// Set to the curve clicked in the gallery.
+    picked: &'a mut Option<&'static str>,
 }
 
 /// Track the About dialog open flag; returns (just_opened, storage_id).
@@ -73,7 +117,8 @@ fn draw_dim_background(ctx: &egui::Context) {
 /// Create and render the About dialog window contents.
 fn show_about_area(
     ctx: &egui::Context,
-    cache: &mut egui_commonmark::CommonMarkCache,
+    content: &mut AboutContent<'_>,
+    lang: Language,
     dialog_size: egui::Vec2,
     center_pos: egui::Pos2,
     should_close: &mut bool,
@@ -129,14 +174,14 @@ fn show_about_area(
                                         ));
                                         ui.add_space(2.0);
                                         ui.add(egui::Label::new(
-                                            egui::RichText::new("Space-filling curve playground")
+                                            egui::RichText::new(lang.tr(Msg::Tagline))
                                                 .size(theme::font_size::LABEL)
                                                 .color(theme::TEXT_SECONDARY),
                                         ));
                                         ui.add_space(2.0);
                                         ui.horizontal(|ui| {
                                             ui.label(
-                                                egui::RichText::new("by")
+                                                egui::RichText::new(lang.tr(Msg::By))
                                                     .size(theme::font_size::INFO)
                                                     .color(theme::TEXT_DIM),
                                             );
@@ -165,16 +210,36 @@ fn show_about_area(
                                 });
 
                                 ui.add_space(theme::spacing::LARGE);
+                                ui.horizontal(|ui| {
+                                    for (tab, msg) in [
+                                        (AboutTab::About, Msg::About),
+                                        (AboutTab::Gallery, Msg::Gallery),
+                                    ] {
+                                        ui.selectable_value(content.tab, tab, lang.tr(msg));
+                                    }
+                                });
                                 ui.add(egui::Separator::default().spacing(12.0));
-                                ui.add_space(theme::spacing::LARGE);
+                                ui.add_space(theme::spacing::SMALL);
 
                                 egui::ScrollArea::vertical()
                                     .max_height(theme::window::ABOUT_SCROLL_HEIGHT)
-                                    .show(ui, |ui| {
-                                        // Override visuals for readable markdown content
-                                        ui.visuals_mut().override_text_color =
-                                            Some(theme::TEXT_BODY);
-                                        CommonMarkViewer::new().show(ui, cache, about_content());
+                                    .show(ui, |ui| match content.tab {
+                                        AboutTab::About => {
+                                            // Override visuals for readable markdown content
+                                            ui.visuals_mut().override_text_color =
+                                                Some(theme::TEXT_BODY);
+                                            CommonMarkViewer::new().show(
+                                                ui,
+                                                content.cache,
+                                                &about_content(lang),
+                                            );
+                                            ui.add_space(theme::spacing::MEDIUM);
+                                            show_licenses_and_build_info(ui, lang);
+                                        }
+                                        AboutTab::Gallery => {
+                                            *content.picked =
+                                                show_gallery(ui, content.curves, lang);
+                                        }
                                     });
                             });
                     });
@@ -182,22 +247,154 @@ fn show_about_area(
         })
 }
 
-/// Static markdown content shown in the About dialog.
-const ABOUT_CONTENT_BODY: &str = r#"
+/// Render a clickable thumbnail for each of `curves`; returns the clicked one.
+fn show_gallery(
+    ui: &mut egui::Ui,
+    curves: &[&'static str],
+    lang: Language,
+) -> Option<&'static str> {
+    ui.label(
+        egui::RichText::new(lang.tr(Msg::GalleryHint))
+            .size(theme::font_size::INFO)
+            .color(theme::TEXT_DIM),
+    );
+    ui.add_space(theme::spacing::SMALL);
+    let mut picked = None;
+    ui.horizontal_wrapped(|ui| {
+        for &name in curves {
+            if gallery_thumbnail(ui, name).clicked() {
+                picked = Some(name);
+            }
+        }
+    });
+    picked
+}
+
+/// Draw one gallery thumbnail: the 2D curve at [`theme::gallery::CURVE_SIZE`]
+/// above its display name.
+fn gallery_thumbnail(ui: &mut egui::Ui, name: &str) -> egui::Response {
+    let side = theme::gallery::THUMBNAIL_SIZE;
+    let label_height = theme::font_size::INFO + theme::spacing::SMALL * 2.0;
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(side, side + label_height), egui::Sense::click());
+    let painter = ui.painter_at(rect);
+    let thumb = egui::Rect::from_min_size(rect.min, egui::Vec2::splat(side));
+    let border = if response.hovered() {
+        theme::TEXT_LINK
+    } else {
+        theme::BORDER
+    };
+    painter.rect_filled(thumb, 3.0, theme::CANVAS_BACKGROUND);
+    painter.rect_stroke(
+        thumb,
+        3.0,
+        egui::Stroke::new(1.0, border),
+        egui::StrokeKind::Inside,
+    );
 
-This interactive playground lets you explore various **space-filling curves** in both 2D and 3D. Space-filling curves are continuous paths that visit every point in a space, providing fascinating mathematical and practical properties.
+    let size = theme::gallery::CURVE_SIZE;
+    if let Ok(curve) = curve_from_name(name, 2, size) {
+        let margin = theme::gallery::MARGIN;
+        let step = (side - margin * 2.0) / (size - 1) as f32;
+        let points = curve
+            .points()
+            .map(|p| {
+                thumb.min + egui::vec2(p[0] as f32, p[1] as f32) * step + egui::Vec2::splat(margin)
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(
+                theme::gallery::LINE_WIDTH,
+                theme::curve_color_with_brightness(1.0, 1.0),
+            ),
+        ));
+    }
 
----
+    let display = registry::find(name).map_or(name, |entry| entry.display);
+    painter.text(
+        egui::pos2(thumb.center().x, thumb.max.y + theme::spacing::SMALL),
+        egui::Align2::CENTER_TOP,
+        display,
+        egui::FontId::proportional(theme::font_size::INFO),
+        theme::TEXT_BODY,
+    );
+    response.on_hover_cursor(egui::CursorIcon::PointingHand)
+}
 
-*Built with Rust & egui.*
-"#;
+/// Orbitron font license text bundled alongside the embedded font files.
+const ORBITRON_LICENSE: &str = include_str!("../assets/fonts/OFL-ORBITRON.txt");
 
-/// Markdown content buffer built once for the About dialog.
-static ABOUT_CONTENT: OnceLock<String> = OnceLock::new();
+include!("dependency_licenses.rs");
+
+/// Render the collapsible licenses and build information section.
+fn show_licenses_and_build_info(ui: &mut egui::Ui, lang: Language) {
+    egui::CollapsingHeader::new(
+        egui::RichText::new(lang.tr(Msg::LicensesAndBuildInfo))
+            .size(theme::font_size::LABEL)
+            .color(theme::TEXT_HEADING),
+    )
+    .id_salt("about_licenses")
+    .show(ui, |ui| {
+        let body = |text: &str| {
+            egui::RichText::new(text)
+                .size(theme::font_size::INFO)
+                .color(theme::TEXT_BODY)
+        };
+        let dim = |text: &str| {
+            egui::RichText::new(text)
+                .size(theme::font_size::INFO)
+                .color(theme::TEXT_DIM)
+        };
+
+        egui::Grid::new("about_build_info")
+            .num_columns(2)
+            .spacing([theme::spacing::LARGE, theme::spacing::SMALL])
+            .show(ui, |ui| {
+                ui.label(dim(lang.tr(Msg::Version)));
+                ui.label(body(env!("CARGO_PKG_VERSION")));
+                ui.end_row();
+                ui.label(dim(lang.tr(Msg::Commit)));
+                ui.label(body(env!("SCURVE_GIT_HASH")));
+                ui.end_row();
+                ui.label(dim(lang.tr(Msg::Profile)));
+                ui.label(body(env!("SCURVE_BUILD_PROFILE")));
+                ui.end_row();
+                ui.label(dim(lang.tr(Msg::License)));
+                ui.label(body(env!("CARGO_PKG_LICENSE")));
+                ui.end_row();
+            });
+
+        ui.add_space(theme::spacing::MEDIUM);
+        ui.label(body(lang.tr(Msg::Fonts)).strong());
+        ui.label(body("Orbitron — SIL Open Font License 1.1"));
+        egui::CollapsingHeader::new(dim(lang.tr(Msg::ShowLicenseText)))
+            .id_salt("about_orbitron_license")
+            .show(ui, |ui| {
+                ui.label(dim(ORBITRON_LICENSE).monospace());
+            });
+
+        ui.add_space(theme::spacing::MEDIUM);
+        ui.label(body(lang.tr(Msg::Dependencies)).strong());
+        egui::Grid::new("about_dependency_licenses")
+            .num_columns(3)
+            .spacing([theme::spacing::LARGE, theme::spacing::SMALL])
+            .show(ui, |ui| {
+                for &(name, version, license) in DEPENDENCY_LICENSES {
+                    ui.label(body(name));
+                    ui.label(dim(version));
+                    ui.label(body(license));
+                    ui.end_row();
+                }
+            });
+    });
+}
 
-/// Return the rendered About markdown, initializing it on first use.
-fn about_content() -> &'static str {
-    ABOUT_CONTENT
-        .get_or_init(|| format!("## Welcome to {APP_NAME}{ABOUT_CONTENT_BODY}"))
-        .as_str()
+/// Markdown content shown at the top of the About dialog.
+fn about_content(lang: Language) -> String {
+    format!(
+        "## {} {APP_NAME}\n\n{}\n",
+        lang.tr(Msg::Welcome),
+        lang.tr(Msg::AboutIntro)
+    )
 }
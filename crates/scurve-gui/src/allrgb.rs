@@ -0,0 +1,372 @@
+//! AllRGB generator dialog: renders an image containing every RGB color once.
+//!
+//! Pixels are laid out along a 2D "layout" curve while colors are taken by
+//! walking a 3D "colormap" curve through the RGB cube, mirroring the CLI's
+//! `allrgb` command. Rendering runs on a background thread and reports
+//! progress; the full image is kept for saving and a downscaled copy is shown
+//! as a preview.
+
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
+
+use egui::{Color32, ColorImage, TextureHandle, TextureOptions};
+use spacecurve::{
+    SpaceCurve, error,
+    registry::{self, CurveId, CurveVisitor},
+};
+
+use crate::{
+    i18n::{Language, Msg},
+    theme,
+};
+
+/// Side length of the rendered image; `SIDE²` equals `CUBE³`.
+pub const SIDE: u32 = 4096;
+
+/// Side length of the RGB cube walked by the colormap curve.
+pub const CUBE: u32 = 256;
+
+/// Render an AllRGB-style image of `side × side` pixels.
+///
+/// `side²` must equal `cube³`. Colormap coordinates are scaled from
+/// `0..cube` to `0..=255`, so small sizes still span the full color range.
+/// `progress` is advanced to the number of pixels written so far.
+pub fn render(
+    layout: &str,
+    colormap: &str,
+    side: u32,
+    cube: u32,
+    progress: &AtomicUsize,
+) -> error::Result<ColorImage> {
+    let pattern: CurveId = layout.parse()?;
+    let stages = Layout {
+        colormap: colormap.parse()?,
+        side,
+        cube,
+        progress,
+    };
+    registry::with_curve(pattern, 2, side, stages)?
+}
+
+/// Visits the layout curve, then the colormap curve, so the pixel loop is
+/// monomorphized for both curve types.
+struct Layout<'a> {
+    /// Curve walking the RGB cube.
+    colormap: CurveId,
+    /// Side length of the image.
+    side: u32,
+    /// Side length of the RGB cube.
+    cube: u32,
+    /// Pixels written so far.
+    progress: &'a AtomicUsize,
+}
+
+impl CurveVisitor for Layout<'_> {
+    type Output = error::Result<ColorImage>;
+
+    fn visit<P: SpaceCurve + ?Sized>(self, pattern: &P) -> Self::Output {
+        let colors = Colors {
+            pattern,
+            side: self.side,
+            cube: self.cube,
+            progress: self.progress,
+        };
+        registry::with_curve(self.colormap, 3, self.cube, colors)
+    }
+}
+
+/// Second stage of [`Layout`], holding the already built layout curve.
+struct Colors<'a, P: ?Sized> {
+    /// Curve placing pixels in the image.
+    pattern: &'a P,
+    /// Side length of the image.
+    side: u32,
+    /// Side length of the RGB cube.
+    cube: u32,
+    /// Pixels written so far.
+    progress: &'a AtomicUsize,
+}
+
+impl<P: SpaceCurve + ?Sized> CurveVisitor for Colors<'_, P> {
+    type Output = ColorImage;
+
+    fn visit<C: SpaceCurve + ?Sized>(self, colors: &C) -> ColorImage {
+        let side_px = self.side as usize;
+        let mut image = ColorImage::filled([side_px, side_px], Color32::BLACK);
+        let step = 255.0 / (self.cube - 1) as f32;
+        let channel = |v: u32| (v as f32 * step).round() as u8;
+
+        let len = self.pattern.length().min(colors.length());
+        for i in 0..len {
+            let p = self.pattern.point(i);
+            let c = colors.point(i);
+            image.pixels[p[1] as usize * side_px + p[0] as usize] =
+                Color32::from_rgb(channel(c[0]), channel(c[1]), channel(c[2]));
+            if (i + 1).is_multiple_of(u64::from(self.side)) {
+                self.progress.store(i as usize + 1, Ordering::Relaxed);
+            }
+        }
+        self.progress.store(len as usize, Ordering::Relaxed);
+        image
+    }
+}
+
+/// Shrink `image` by an integer `factor`, averaging each `factor × factor` block.
+pub fn downscale(image: &ColorImage, factor: usize) -> ColorImage {
+    let [width, height] = image.size;
+    let (out_width, out_height) = (width / factor, height / factor);
+    let mut pixels = Vec::with_capacity(out_width * out_height);
+    let area = (factor * factor) as u32;
+    for block_y in 0..out_height {
+        for block_x in 0..out_width {
+            let mut sum = [0u32; 3];
+            for row in block_y * factor..(block_y + 1) * factor {
+                let start = row * width + block_x * factor;
+                for px in &image.pixels[start..start + factor] {
+                    sum[0] += u32::from(px.r());
+                    sum[1] += u32::from(px.g());
+                    sum[2] += u32::from(px.b());
+                }
+            }
+            let [r, g, b] = sum.map(|s| (s / area) as u8);
+            pixels.push(Color32::from_rgb(r, g, b));
+        }
+    }
+    ColorImage::new([out_width, out_height], pixels)
+}
+
+/// Something the caller must act on after showing the dialog.
+pub enum AllRgbEvent {
+    /// Save the full-resolution image to `path`.
+    Save {
+        /// Destination file.
+        path: PathBuf,
+        /// Rendered image.
+        image: Arc<ColorImage>,
+    },
+    /// Rendering failed or could not be started.
+    Failed(String),
+}
+
+/// A render running on the worker thread.
+struct Job {
+    /// File stem of the result, e.g. `allrgb-hilbert-zorder`.
+    name: String,
+    /// Pixels written so far.
+    progress: Arc<AtomicUsize>,
+    /// Delivers the finished image or an error message.
+    result: Receiver<Result<ColorImage, String>>,
+}
+
+/// A finished render.
+struct Rendered {
+    /// File stem used when saving.
+    name: String,
+    /// Full-resolution image.
+    image: Arc<ColorImage>,
+    /// Downscaled preview texture.
+    preview: TextureHandle,
+}
+
+/// State for the AllRGB generator window.
+#[derive(Default)]
+pub struct AllRgbDialog {
+    /// Whether the window is visible.
+    pub open: bool,
+    /// 2D curve that lays out the pixels.
+    layout: String,
+    /// 3D curve that walks the RGB cube.
+    colormap: String,
+    /// Render in progress, if any.
+    job: Option<Job>,
+    /// Most recent finished render.
+    rendered: Option<Rendered>,
+}
+
+impl AllRgbDialog {
+    /// Start a render of the current layout/colormap pair on a worker thread.
+    fn start(&mut self) -> Result<(), String> {
+        let (layout, colormap) = (self.layout.clone(), self.colormap.clone());
+        let name = format!("allrgb-{layout}-{colormap}");
+        let progress = Arc::new(AtomicUsize::new(0));
+        let (sender, result) = mpsc::channel();
+        let worker_progress = Arc::clone(&progress);
+        thread::Builder::new()
+            .name("allrgb".to_string())
+            .spawn(move || {
+                let outcome = render(&layout, &colormap, SIDE, CUBE, &worker_progress)
+                    .map_err(|e| format!("{layout}/{colormap}: {e}"));
+                // The dialog may have been dropped; nothing to report then.
+                sender.send(outcome).ok();
+            })
+            .map_err(|e| format!("could not start render thread: {e}"))?;
+        self.job = Some(Job {
+            name,
+            progress,
+            result,
+        });
+        Ok(())
+    }
+
+    /// Collect a finished render, turning it into a preview texture.
+    fn poll(&mut self, ctx: &egui::Context) -> Option<AllRgbEvent> {
+        let job = self.job.as_ref()?;
+        let outcome = match job.result.try_recv() {
+            Ok(outcome) => outcome,
+            Err(TryRecvError::Empty) => {
+                ctx.request_repaint();
+                return None;
+            }
+            Err(TryRecvError::Disconnected) => Err("render thread stopped".to_string()),
+        };
+        let name = job.name.clone();
+        self.job = None;
+        match outcome {
+            Ok(image) => {
+                let factor = SIDE as usize / theme::allrgb::PREVIEW_SIDE;
+                let preview = ctx.load_texture(
+                    "allrgb_preview",
+                    downscale(&image, factor),
+                    TextureOptions::LINEAR,
+                );
+                self.rendered = Some(Rendered {
+                    name,
+                    image: Arc::new(image),
+                    preview,
+                });
+                None
+            }
+            Err(err) => Some(AllRgbEvent::Failed(err)),
+        }
+    }
+
+    /// Show the window and return an event for the caller, if any.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        available_curves: &[&str],
+        lang: Language,
+    ) -> Option<AllRgbEvent> {
+        let mut event = self.poll(ctx);
+        if !self.open {
+            return event;
+        }
+        if let Some(&first) = available_curves.first() {
+            if self.layout.is_empty() {
+                self.layout = first.to_string();
+            }
+            if self.colormap.is_empty() {
+                self.colormap = first.to_string();
+            }
+        }
+
+        let mut open = self.open;
+        egui::Window::new("AllRGB")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let running = self.job.is_some();
+                egui::Grid::new("allrgb_curves")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(lang.tr(Msg::Layout));
+                        curve_combo(ui, "allrgb_layout", &mut self.layout, available_curves);
+                        ui.end_row();
+                        ui.label(lang.tr(Msg::Colormap));
+                        curve_combo(ui, "allrgb_colormap", &mut self.colormap, available_curves);
+                        ui.end_row();
+                    });
+                ui.add_space(theme::spacing::SMALL);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!running, egui::Button::new(lang.tr(Msg::Render)))
+                        .clicked()
+                        && let Err(err) = self.start()
+                    {
+                        event = Some(AllRgbEvent::Failed(err));
+                    }
+                    if let Some(rendered) = &self.rendered
+                        && ui.button(lang.tr(Msg::Save)).clicked()
+                    {
+                        event = Some(AllRgbEvent::Save {
+                            path: PathBuf::from(format!("{}.png", rendered.name)),
+                            image: Arc::clone(&rendered.image),
+                        });
+                    }
+                });
+
+                if let Some(job) = &self.job {
+                    let done = job.progress.load(Ordering::Relaxed) as f32;
+                    let total = (SIDE * SIDE) as f32;
+                    ui.add(egui::ProgressBar::new(done / total).show_percentage());
+                }
+                if let Some(rendered) = &self.rendered {
+                    ui.label(
+                        egui::RichText::new(&rendered.name)
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_DIM),
+                    );
+                    ui.image((
+                        rendered.preview.id(),
+                        egui::Vec2::splat(theme::allrgb::PREVIEW_DISPLAY_SIZE),
+                    ));
+                }
+            });
+        self.open = open;
+        event
+    }
+}
+
+/// Combo box listing `curves`, writing the choice into `selected`.
+fn curve_combo(ui: &mut egui::Ui, id_salt: &str, selected: &mut String, curves: &[&str]) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected.as_str())
+        .show_ui(ui, |ui| {
+            for &name in curves {
+                ui.selectable_value(selected, name.to_string(), name);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn small_render_uses_every_color_once() {
+        let progress = AtomicUsize::new(0);
+        let image = render("hilbert", "hilbert", 8, 4, &progress).unwrap();
+        assert_eq!(progress.load(Ordering::Relaxed), 64);
+        let unique: HashSet<Color32> = image.pixels.iter().copied().collect();
+        assert_eq!(unique.len(), 64);
+        assert!(unique.contains(&Color32::from_rgb(255, 255, 255)));
+    }
+
+    #[test]
+    fn render_reports_unknown_curves() {
+        let progress = AtomicUsize::new(0);
+        assert!(render("nope", "hilbert", 8, 4, &progress).is_err());
+    }
+
+    #[test]
+    fn downscale_averages_blocks() {
+        let mut image = ColorImage::filled([4, 2], Color32::BLACK);
+        image.pixels[0] = Color32::from_rgb(200, 100, 40);
+        image.pixels[1] = Color32::from_rgb(200, 100, 40);
+        let small = downscale(&image, 2);
+        assert_eq!(small.size, [2, 1]);
+        assert_eq!(small.pixels[0], Color32::from_rgb(100, 50, 20));
+        assert_eq!(small.pixels[1], Color32::BLACK);
+    }
+}
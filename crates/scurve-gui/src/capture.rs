@@ -0,0 +1,73 @@
+//! Helpers for saving the visible canvas: cropping window captures and
+//! naming the resulting files.
+
+use egui::{ColorImage, Rect};
+
+/// Cut `rect` (in points) out of a full-window capture.
+///
+/// The rectangle is scaled by `pixels_per_point` so the result keeps the
+/// capture's native resolution, and is clamped to the image bounds.
+pub fn crop(image: &ColorImage, rect: Rect, pixels_per_point: f32) -> ColorImage {
+    let [width, height] = image.size;
+    let to_px =
+        |v: f32, limit: usize| ((v * pixels_per_point).round().max(0.0) as usize).min(limit);
+    let (left, right) = (to_px(rect.min.x, width), to_px(rect.max.x, width));
+    let (top, bottom) = (to_px(rect.min.y, height), to_px(rect.max.y, height));
+    image.region_by_pixels(
+        [left, top],
+        [right.saturating_sub(left), bottom.saturating_sub(top)],
+    )
+}
+
+/// Format seconds since the Unix epoch as a UTC `YYYYMMDD-HHMMSS` stamp.
+pub fn timestamp(unix_secs: u64) -> String {
+    let (days, secs) = (unix_secs / 86_400, unix_secs % 86_400);
+    // Civil-from-days conversion with years starting on March 1st.
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{Color32, pos2};
+
+    use super::*;
+
+    #[test]
+    fn timestamps_are_utc_calendar_times() {
+        assert_eq!(timestamp(0), "19700101-000000");
+        assert_eq!(timestamp(951_782_400), "20000229-000000");
+        assert_eq!(timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn crop_scales_and_clamps_to_the_image() {
+        let mut image = ColorImage::filled([8, 6], Color32::BLACK);
+        image.pixels[2 * 8 + 2] = Color32::WHITE;
+        let cropped = crop(
+            &image,
+            Rect::from_min_max(pos2(1.0, 1.0), pos2(10.0, 2.5)),
+            2.0,
+        );
+        assert_eq!(cropped.size, [6, 3]);
+        assert_eq!(cropped.pixels[0], Color32::WHITE);
+    }
+}
@@ -0,0 +1,130 @@
+//! Unattended demo ("attract") mode that cycles curves, sizes and panes.
+
+use spacecurve::registry;
+
+use crate::{
+    Pane,
+    theme::demo::{FADE_SECONDS, SIZES_2D, SIZES_3D, STEP_SECONDS},
+};
+
+/// One stop in the demo cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DemoStep {
+    /// Pane to show.
+    pub pane: Pane,
+    /// Curve to select in that pane.
+    pub curve: &'static str,
+    /// Grid size to select in that pane.
+    pub size: u32,
+}
+
+/// Timer state for the demo cycle.
+#[derive(Debug, Default)]
+pub struct DemoMode {
+    /// Position in the cycle.
+    step: usize,
+    /// Seconds spent on the current step.
+    elapsed: f32,
+}
+
+impl DemoMode {
+    /// Step at position `step` of the cycle over `curves`.
+    ///
+    /// Each curve is shown in 2D and then in 3D (or 2D again when it has no
+    /// 3D form); every full pass over the curve list moves on to the next
+    /// size in the demo size lists.
+    pub fn step_at(step: usize, curves: &[&'static str]) -> DemoStep {
+        let curve = curves[(step / 2) % curves.len()];
+        let pass = step / (2 * curves.len());
+        let has_3d = registry::find(curve).is_some_and(|entry| entry.accepts_dimension(3));
+        if step.is_multiple_of(2) || !has_3d {
+            DemoStep {
+                pane: Pane::TwoD,
+                curve,
+                size: SIZES_2D[pass % SIZES_2D.len()],
+            }
+        } else {
+            DemoStep {
+                pane: Pane::ThreeD,
+                curve,
+                size: SIZES_3D[pass % SIZES_3D.len()],
+            }
+        }
+    }
+
+    /// The step currently on screen.
+    pub fn current(&self, curves: &[&'static str]) -> DemoStep {
+        Self::step_at(self.step, curves)
+    }
+
+    /// Advance the timer by `delta` seconds, returning the next step when it is time to switch.
+    pub fn advance(&mut self, delta: f32, curves: &[&'static str]) -> Option<DemoStep> {
+        self.elapsed += delta;
+        if self.elapsed < STEP_SECONDS {
+            return None;
+        }
+        self.elapsed %= STEP_SECONDS;
+        self.step += 1;
+        Some(self.current(curves))
+    }
+
+    /// Strength of the fade overlay (0 = clear, 1 = fully faded) for the current time.
+    ///
+    /// Fades in at the start of each step and out at the end, so switches
+    /// happen while the canvas is fully covered.
+    pub fn fade(&self) -> f32 {
+        let t = self.elapsed;
+        if t < FADE_SECONDS {
+            1.0 - t / FADE_SECONDS
+        } else if t > STEP_SECONDS - FADE_SECONDS {
+            (t - (STEP_SECONDS - FADE_SECONDS)) / FADE_SECONDS
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [&str; 2] = ["hilbert", "zorder"];
+
+    #[test]
+    fn cycles_panes_curves_then_sizes() {
+        let steps: Vec<DemoStep> = (0..5).map(|i| DemoMode::step_at(i, &CURVES)).collect();
+        assert_eq!(steps[0].pane, Pane::TwoD);
+        assert_eq!(steps[1].pane, Pane::ThreeD);
+        assert_eq!(steps[0].curve, "hilbert");
+        assert_eq!(steps[1].curve, "hilbert");
+        assert_eq!(steps[2].curve, "zorder");
+        assert_eq!(steps[0].size, SIZES_2D[0]);
+        assert_eq!(steps[4].size, SIZES_2D[1]);
+        assert_eq!(steps[4].curve, "hilbert");
+    }
+
+    #[test]
+    fn curves_without_3d_stay_in_2d() {
+        let step = DemoMode::step_at(1, &["gilbert"]);
+        assert_eq!(step.pane, Pane::TwoD);
+        assert_eq!(step.curve, "gilbert");
+    }
+
+    #[test]
+    fn advances_after_step_duration() {
+        let mut demo = DemoMode::default();
+        assert_eq!(demo.advance(STEP_SECONDS * 0.5, &CURVES), None);
+        let next = demo.advance(STEP_SECONDS * 0.5, &CURVES);
+        assert_eq!(next, Some(DemoMode::step_at(1, &CURVES)));
+    }
+
+    #[test]
+    fn fade_covers_step_edges() {
+        let mut demo = DemoMode::default();
+        assert_eq!(demo.fade(), 1.0);
+        demo.advance(STEP_SECONDS * 0.5, &CURVES);
+        assert_eq!(demo.fade(), 0.0);
+        demo.advance(STEP_SECONDS * 0.5 - 0.001, &CURVES);
+        assert!(demo.fade() > 0.99);
+    }
+}
@@ -0,0 +1,13 @@
+// Generated by `cargo xtask licenses`; do not edit by hand.
+
+/// Key dependencies as `(name, version, license)`.
+const DEPENDENCY_LICENSES: &[(&str, &str, &str)] = &[
+    ("egui", "0.33.3", "MIT OR Apache-2.0"),
+    ("eframe", "0.33.3", "MIT OR Apache-2.0"),
+    ("egui_commonmark", "0.22.0", "MIT OR Apache-2.0"),
+    ("webbrowser", "1.0.6", "MIT OR Apache-2.0"),
+    ("png", "0.18.0", "MIT OR Apache-2.0"),
+    ("anyhow", "1.0.100", "MIT OR Apache-2.0"),
+    ("smallvec", "1.15.1", "MIT OR Apache-2.0"),
+    ("thiserror", "2.0.18", "MIT OR Apache-2.0"),
+];
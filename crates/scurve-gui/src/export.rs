@@ -0,0 +1,87 @@
+//! Clipboard export of curve points.
+//!
+//! Points are written with their curve index so a pasted table keeps the
+//! traversal order, e.g. for plotting in a notebook.
+
+use std::fmt::Write;
+
+/// Axis names used as column headers and JSON keys.
+const AXES: [&str; 3] = ["x", "y", "z"];
+
+/// Text format for exported points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointFormat {
+    /// Comma-separated rows with an `index,x,y[,z]` header.
+    Csv,
+    /// A JSON array of `{"index": …, "x": …, …}` objects.
+    Json,
+}
+
+impl PointFormat {
+    /// All formats, in menu order.
+    pub const ALL: [Self; 2] = [Self::Csv, Self::Json];
+}
+
+/// Format `points` in `format`, numbering them from curve index `start`.
+pub fn format_points<const D: usize>(
+    points: &[[u32; D]],
+    start: usize,
+    format: PointFormat,
+) -> String {
+    let axes = &AXES[..D];
+    let mut out = String::new();
+    match format {
+        PointFormat::Csv => {
+            out.push_str("index");
+            for axis in axes {
+                write!(out, ",{axis}").unwrap();
+            }
+            out.push('\n');
+            for (i, p) in points.iter().enumerate() {
+                write!(out, "{}", start + i).unwrap();
+                for c in p {
+                    write!(out, ",{c}").unwrap();
+                }
+                out.push('\n');
+            }
+        }
+        PointFormat::Json => {
+            out.push('[');
+            for (i, p) in points.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write!(out, "\n  {{\"index\": {}", start + i).unwrap();
+                for (axis, c) in axes.iter().zip(p) {
+                    write!(out, ", \"{axis}\": {c}").unwrap();
+                }
+                out.push('}');
+            }
+            out.push_str(if points.is_empty() { "]\n" } else { "\n]\n" });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_header_and_indexed_rows() {
+        let text = format_points(&[[0, 0], [0, 1]], 4, PointFormat::Csv);
+        assert_eq!(text, "index,x,y\n4,0,0\n5,0,1\n");
+        let text = format_points(&[[1, 2, 3]], 0, PointFormat::Csv);
+        assert_eq!(text, "index,x,y,z\n0,1,2,3\n");
+    }
+
+    #[test]
+    fn json_is_an_array_of_objects() {
+        let text = format_points(&[[0, 0], [1, 0]], 0, PointFormat::Json);
+        assert_eq!(
+            text,
+            "[\n  {\"index\": 0, \"x\": 0, \"y\": 0},\n  {\"index\": 1, \"x\": 1, \"y\": 0}\n]\n"
+        );
+        assert_eq!(format_points::<2>(&[], 0, PointFormat::Json), "[]\n");
+    }
+}
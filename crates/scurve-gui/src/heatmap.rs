@@ -0,0 +1,76 @@
+//! Statistics-driven curve coloring.
+//!
+//! In [`ColorMode::Stretch`] each segment of the 2D curve is tinted by the
+//! local stretch of its endpoints (see [`spacecurve::metrics::dilation_map`]),
+//! so seams where the traversal tears spatial neighbours apart stand out.
+
+use egui::Color32;
+
+use crate::theme;
+
+/// How the main 2D curve strokes are colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// One color for the whole curve.
+    #[default]
+    Solid,
+    /// Color each segment by its neighbourhood stretch.
+    Stretch,
+}
+
+impl ColorMode {
+    /// All color modes, in selector order.
+    pub const ALL: [Self; 2] = [Self::Solid, Self::Stretch];
+}
+
+/// Fill `out` with a heat in `0.0..=1.0` for each segment between consecutive points.
+///
+/// A segment's stretch is the mean of its endpoints' values. Heat is the
+/// stretch on a log scale relative to the largest stretch on the curve, so
+/// perfectly local segments are cold regardless of curve size.
+pub fn segment_heat(stretch: &[f32], out: &mut Vec<f32>) {
+    out.clear();
+    let max = stretch.iter().copied().fold(1.0, f32::max);
+    let log_max = max.ln();
+    out.extend(stretch.windows(2).map(|w| {
+        let s = ((w[0] + w[1]) * 0.5).max(1.0);
+        if log_max > 0.0 { s.ln() / log_max } else { 0.0 }
+    }));
+}
+
+/// Gradient color for `heat` in `0.0..=1.0` at the given stroke opacity.
+pub fn heat_color(heat: f32, opacity: f32) -> Color32 {
+    let heat = heat.clamp(0.0, 1.0);
+    let color = if heat < 0.5 {
+        theme::heatmap::COLD.lerp_to_gamma(theme::heatmap::WARM, heat * 2.0)
+    } else {
+        theme::heatmap::WARM.lerp_to_gamma(theme::heatmap::HOT, heat * 2.0 - 1.0)
+    };
+    color.gamma_multiply(opacity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heat_is_log_scaled_to_the_maximum() {
+        let mut heat = Vec::new();
+        segment_heat(&[1.0, 1.0, 7.0, 9.0], &mut heat);
+        assert_eq!(heat.len(), 3);
+        assert_eq!(heat[0], 0.0);
+        assert!((heat[1] - 4f32.ln() / 9f32.ln()).abs() < 1e-6);
+        assert!((heat[2] - 8f32.ln() / 9f32.ln()).abs() < 1e-6);
+
+        segment_heat(&[1.0, 1.0], &mut heat);
+        assert_eq!(heat, vec![0.0]);
+    }
+
+    #[test]
+    fn gradient_spans_cold_to_hot() {
+        assert_eq!(heat_color(0.0, 1.0), theme::heatmap::COLD);
+        assert_eq!(heat_color(0.5, 1.0), theme::heatmap::WARM);
+        assert_eq!(heat_color(1.0, 1.0), theme::heatmap::HOT);
+        assert_eq!(heat_color(2.0, 1.0), theme::heatmap::HOT);
+    }
+}
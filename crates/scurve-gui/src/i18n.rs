@@ -0,0 +1,550 @@
+//! Translated user-facing strings.
+//!
+//! Every label shown in menus, settings and dialogs is a [`Msg`] that a
+//! [`Language`] resolves to text. Translations are exhaustive `match`es, so
+//! adding a message without translating it for every language fails to compile.
+
+/// Interface language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    /// English.
+    #[default]
+    English,
+    /// German.
+    German,
+}
+
+/// A translatable user-facing string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    /// Label in front of the curve selector.
+    Curve,
+    /// Label in front of the size selector.
+    Size,
+    /// Warning shown when the selected size is invalid for the curve.
+    UnsupportedSize,
+    /// Warning shown before building a very large curve.
+    LargeSize,
+    /// Unit label for a point count.
+    Points,
+    /// Suffix of the estimated curve build time.
+    BuildTime,
+    /// Hover text unit for the number of segments drawn per frame.
+    SegmentsPerFrame,
+    /// Prefix for pane entries in the command palette.
+    Pane,
+    /// Measure tool toggle.
+    Measure,
+    /// Hover text for the measure tool.
+    MeasureHint,
+    /// Toggle clipping the 2D curve to the canvas shape.
+    Fit,
+    /// Hover text for the fit toggle.
+    FitHint,
+    /// Heading of the curve info panel.
+    CurveInfo,
+    /// Curve opacity slider.
+    Opacity,
+    /// Checkbox enabling the curve glow pass.
+    Glow,
+    /// Label in front of the 2D color mode choices.
+    ColorMode,
+    /// Single-color curve option.
+    ColorSolid,
+    /// Stretch heatmap option.
+    ColorStretch,
+    /// Hover text explaining the stretch heatmap.
+    ColorStretchHint,
+    /// Checkbox caching the static 2D curve between frames.
+    CacheCurve,
+    /// Hover text for the curve cache checkbox.
+    CacheCurveHint,
+    /// Checkbox enabling alternating onion shell colors.
+    OnionShells,
+    /// Hover text for the onion shell checkbox.
+    OnionShellsHint,
+    /// Settings section for selection options.
+    Selection,
+    /// Checkbox that locks 2D and 3D selections together.
+    LockSelections,
+    /// Settings section for index range highlighting.
+    IndexRange,
+    /// Checkbox enabling the index range highlight.
+    HighlightRange,
+    /// Label before the range start.
+    RangeFrom,
+    /// Label before the range end.
+    RangeTo,
+    /// Settings section for long jumps.
+    LongJumps,
+    /// Checkbox showing long jumps on the curve.
+    ShowOnCurve,
+    /// Checkbox showing long jumps on the snake.
+    ShowOnSnake,
+    /// Heading of the picked long jump popup.
+    LongJump,
+    /// Popup line counting jumps with the same displacement.
+    SameJumps,
+    /// Button hiding jumps with the picked jump's displacement.
+    HideSimilarJumps,
+    /// Button drawing every hidden jump class again.
+    ShowAllJumps,
+    /// Hover text explaining long jump picking.
+    LongJumpHint,
+    /// Settings section for the snake overlay.
+    Snake,
+    /// Checkbox enabling the snake overlay.
+    EnableSnake,
+    /// Snake length slider.
+    Length,
+    /// Speed sliders.
+    Speed,
+    /// Settings section for 3D rotation.
+    Rotation3d,
+    /// Rotation axis selector.
+    Axis,
+    /// Two-axis tumbling rotation option.
+    Tumble,
+    /// Slider for the segment stride used while dragging.
+    DragDetail,
+    /// Hover text explaining the drag stride slider.
+    DragDetailHint,
+    /// Slider for the number of 3D depth bins.
+    DepthBins,
+    /// Hover text explaining the depth bin slider.
+    DepthBinsHint,
+    /// Checkbox enabling exact 3D depth sorting.
+    ExactDepthSort,
+    /// Hover text explaining exact depth sorting.
+    ExactDepthSortHint,
+    /// Settings section for the 3D camera.
+    Camera,
+    /// Camera tilt slider.
+    Tilt,
+    /// Camera distance (perspective strength) slider.
+    Distance,
+    /// Settings section and selector for the interface language.
+    Language,
+    /// About button and palette entry.
+    About,
+    /// Curve gallery tab of the About dialog.
+    Gallery,
+    /// Instructions above the curve gallery.
+    GalleryHint,
+    /// Copy state button.
+    CopyState,
+    /// Paste state button and dialog title.
+    PasteState,
+    /// Confirm button.
+    Apply,
+    /// Cancel button.
+    Cancel,
+    /// Palette entry resuming animation.
+    ResumeAnimation,
+    /// Palette entry pausing animation.
+    PauseAnimation,
+    /// Palette entry toggling the settings panel.
+    ToggleSettings,
+    /// Palette entry toggling the snake overlay.
+    ToggleSnake,
+    /// Palette entry toggling the About dialog.
+    ToggleAbout,
+    /// Palette entry exporting a screenshot.
+    ExportScreenshot,
+    /// Palette entry and menu button opening the AllRGB generator.
+    AllRgb,
+    /// AllRGB layout curve selector.
+    Layout,
+    /// AllRGB colormap curve selector.
+    Colormap,
+    /// Button starting an AllRGB render.
+    Render,
+    /// Button saving the rendered image.
+    Save,
+    /// Toast title when a rendered image cannot be saved.
+    FailedSaveImage,
+    /// Toast title when an AllRGB render fails.
+    FailedRender,
+    /// Placeholder in the command palette search field.
+    TypeCommand,
+    /// Shown when no palette entry matches the query.
+    NoMatchingCommands,
+    /// Toast title when a state spec fails to parse.
+    InvalidState,
+    /// Toast title when the browser cannot be opened.
+    FailedOpenBrowser,
+    /// Toast title when a screenshot cannot be saved.
+    FailedSaveScreenshot,
+    /// Toast title when a curve cannot be built.
+    FailedBuildCurve,
+    /// Hover text on the toast close button.
+    Dismiss,
+    /// Collapsible toast details header.
+    Details,
+    /// Canvas context menu entry copying points as CSV.
+    CopyCsv,
+    /// Canvas context menu entry copying points as JSON.
+    CopyJson,
+    /// Tagline under the app name in the About dialog.
+    Tagline,
+    /// Word before the author link in the About dialog.
+    By,
+    /// Heading before the app name in the About dialog.
+    Welcome,
+    /// Markdown introduction in the About dialog.
+    AboutIntro,
+    /// Collapsible licenses section in the About dialog.
+    LicensesAndBuildInfo,
+    /// Build info row: version.
+    Version,
+    /// Build info row: commit hash.
+    Commit,
+    /// Build info row: build profile.
+    Profile,
+    /// Build info row: license.
+    License,
+    /// Fonts heading.
+    Fonts,
+    /// Collapsible font license text.
+    ShowLicenseText,
+    /// Dependencies heading.
+    Dependencies,
+    /// Heading for a curve's asymptotic and measured costs.
+    Cost,
+    /// Button that measures per-call curve latency.
+    Calibrate,
+    /// Calibration column with the time per `point` call.
+    PointLatency,
+    /// Calibration column with the time per `index` call.
+    IndexLatency,
+    /// Shown in the info popup when the curve can't be built at this size.
+    CurveInfoUnavailable,
+    /// Progress bar label while a curve's points are being cached.
+    BuildingCurve,
+    /// Web startup status while the renderer is created.
+    StartingRenderer,
+    /// Web startup status while fonts and the theme are set up.
+    LoadingTheme,
+    /// Web startup message when the browser offers no WebGL.
+    WebGlUnavailable,
+    /// Prefix of the web startup message when the renderer fails to start.
+    StartFailed,
+}
+
+impl Language {
+    /// All supported languages, in selector order.
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    /// Name of the language in that language, for the selector.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    /// Short language code, as accepted by [`Self::from_locale`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// Language of a POSIX-style locale such as `de_DE.UTF-8`, `en-US` or `de`.
+    ///
+    /// Returns `None` for unsupported languages and the `C`/`POSIX` locales.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['_', '-', '.', '@']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Resolve `msg` to text in this language.
+    pub fn tr(self, msg: Msg) -> &'static str {
+        match self {
+            Self::English => english(msg),
+            Self::German => german(msg),
+        }
+    }
+}
+
+/// English strings.
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Curve => "Curve:",
+        Msg::Size => "Size:",
+        Msg::UnsupportedSize => "Unsupported size",
+        Msg::LargeSize => "is large",
+        Msg::Points => "points",
+        Msg::BuildTime => "to build",
+        Msg::SegmentsPerFrame => "segments drawn per frame",
+        Msg::Pane => "Pane:",
+        Msg::Measure => "Measure",
+        Msg::MeasureHint => "Click two cells to compare curve and spatial distance",
+        Msg::Fit => "Fit",
+        Msg::FitHint => "Fill wide or tall windows by clipping the curve to a rectangle",
+        Msg::CurveInfo => "Curve Info",
+        Msg::Opacity => "Opacity",
+        Msg::Glow => "Neon glow",
+        Msg::CacheCurve => "Cache curve",
+        Msg::CacheCurveHint => {
+            "Draw the 2D curve once and reuse it while only the snake moves; turn off to redraw every frame"
+        }
+        Msg::OnionShells => "Onion shells",
+        Msg::OnionShellsHint => "Alternate colors between the concentric shells of onion curves",
+        Msg::ColorMode => "Color",
+        Msg::ColorSolid => "Solid",
+        Msg::ColorStretch => "Stretch",
+        Msg::ColorStretchHint => {
+            "Color segments by how far apart along the curve their spatial neighbours are"
+        }
+        Msg::Selection => "Selection",
+        Msg::LockSelections => "Lock 2D/3D curves",
+        Msg::IndexRange => "Index Range",
+        Msg::HighlightRange => "Highlight range",
+        Msg::RangeFrom => "From",
+        Msg::RangeTo => "to",
+        Msg::LongJumps => "Long Jumps",
+        Msg::ShowOnCurve => "Show on curve",
+        Msg::LongJump => "Long jump",
+        Msg::SameJumps => "jumps like this",
+        Msg::HideSimilarJumps => "Hide jumps like this",
+        Msg::ShowAllJumps => "Show all jumps",
+        Msg::LongJumpHint => "Click a long jump in the 2D view to inspect it",
+        Msg::ShowOnSnake => "Show on snake",
+        Msg::Snake => "Snake",
+        Msg::EnableSnake => "Enable snake overlay",
+        Msg::Length => "Length",
+        Msg::Speed => "Speed",
+        Msg::Rotation3d => "3D rotation",
+        Msg::Axis => "Axis",
+        Msg::Tumble => "Tumble",
+        Msg::DragDetail => "Drag LOD",
+        Msg::DragDetailHint => {
+            "Draw every Nth segment of dense curves while rotating; full detail returns on release"
+        }
+        Msg::DepthBins => "Depth bins",
+        Msg::DepthBinsHint => {
+            "Depth slices the 3D curve is batched into; more slices shade depth more smoothly"
+        }
+        Msg::ExactDepthSort => "Exact depth sort",
+        Msg::ExactDepthSortHint => {
+            "Sort every segment by depth for curves up to 5,000 segments, so crossings overlap correctly"
+        }
+        Msg::Camera => "Camera",
+        Msg::Tilt => "Tilt",
+        Msg::Distance => "Distance",
+        Msg::Language => "Language",
+        Msg::About => "About",
+        Msg::Gallery => "Gallery",
+        Msg::GalleryHint => "Click a curve to explore it.",
+        Msg::CopyState => "Copy state",
+        Msg::PasteState => "Paste state",
+        Msg::Apply => "Apply",
+        Msg::Cancel => "Cancel",
+        Msg::ResumeAnimation => "Resume animation",
+        Msg::PauseAnimation => "Pause animation",
+        Msg::ToggleSettings => "Toggle settings",
+        Msg::ToggleSnake => "Toggle snake overlay",
+        Msg::ToggleAbout => "Toggle About",
+        Msg::ExportScreenshot => "Export screenshot (PNG)",
+        Msg::AllRgb => "AllRGB generator",
+        Msg::Layout => "Layout",
+        Msg::Colormap => "Colormap",
+        Msg::Render => "Render",
+        Msg::Save => "Save PNG",
+        Msg::FailedSaveImage => "Failed to save image",
+        Msg::FailedRender => "AllRGB render failed",
+        Msg::TypeCommand => "Type a command…",
+        Msg::NoMatchingCommands => "No matching commands",
+        Msg::InvalidState => "Invalid state",
+        Msg::FailedOpenBrowser => "Failed to open browser",
+        Msg::FailedSaveScreenshot => "Failed to save screenshot",
+        Msg::FailedBuildCurve => "Failed to build curve",
+        Msg::Dismiss => "Dismiss",
+        Msg::Details => "Details",
+        Msg::CopyCsv => "Copy coordinates as CSV",
+        Msg::CopyJson => "Copy coordinates as JSON",
+        Msg::Tagline => "Space-filling curve playground",
+        Msg::By => "by",
+        Msg::Welcome => "Welcome to",
+        Msg::AboutIntro => {
+            "This interactive playground lets you explore various **space-filling curves** \
+             in both 2D and 3D. Space-filling curves are continuous paths that visit every \
+             point in a space, providing fascinating mathematical and practical properties.\n\n\
+             ---\n\n*Built with Rust & egui.*"
+        }
+        Msg::LicensesAndBuildInfo => "Licenses & build info",
+        Msg::Version => "Version",
+        Msg::Commit => "Commit",
+        Msg::Profile => "Profile",
+        Msg::License => "License",
+        Msg::Fonts => "Fonts",
+        Msg::ShowLicenseText => "Show license text",
+        Msg::Dependencies => "Dependencies",
+        Msg::Cost => "Cost",
+        Msg::Calibrate => "Calibrate",
+        Msg::PointLatency => "point (ns)",
+        Msg::IndexLatency => "index (ns)",
+        Msg::CurveInfoUnavailable => "Unable to construct curve for info.",
+        Msg::BuildingCurve => "Building curve",
+        Msg::StartingRenderer => "Starting renderer…",
+        Msg::LoadingTheme => "Loading fonts and theme…",
+        Msg::WebGlUnavailable => {
+            "spacecurve needs WebGL, which this browser or device does not provide. \
+             Try enabling hardware acceleration or another browser."
+        }
+        Msg::StartFailed => "spacecurve failed to start",
+    }
+}
+
+/// German strings.
+fn german(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Curve => "Kurve:",
+        Msg::Size => "Größe:",
+        Msg::UnsupportedSize => "Größe nicht unterstützt",
+        Msg::LargeSize => "ist groß",
+        Msg::Points => "Punkte",
+        Msg::BuildTime => "zum Erzeugen",
+        Msg::SegmentsPerFrame => "Segmente pro Bild",
+        Msg::Pane => "Ansicht:",
+        Msg::Measure => "Messen",
+        Msg::MeasureHint => "Zwei Zellen anklicken, um Kurven- und Raumabstand zu vergleichen",
+        Msg::Fit => "Einpassen",
+        Msg::FitHint => {
+            "Breite oder hohe Fenster füllen, indem die Kurve rechteckig beschnitten wird"
+        }
+        Msg::CurveInfo => "Kurveninfo",
+        Msg::Opacity => "Deckkraft",
+        Msg::Glow => "Neon-Leuchten",
+        Msg::CacheCurve => "Kurve zwischenspeichern",
+        Msg::CacheCurveHint => {
+            "Die 2D-Kurve einmal zeichnen und wiederverwenden, solange sich nur die Schlange bewegt; aus, um jedes Bild neu zu zeichnen"
+        }
+        Msg::OnionShells => "Zwiebelschalen",
+        Msg::OnionShellsHint => "Konzentrische Schalen von Zwiebelkurven abwechselnd einfärben",
+        Msg::ColorMode => "Farbe",
+        Msg::ColorSolid => "Einfarbig",
+        Msg::ColorStretch => "Streckung",
+        Msg::ColorStretchHint => {
+            "Segmente danach färben, wie weit ihre räumlichen Nachbarn entlang der Kurve entfernt sind"
+        }
+        Msg::Selection => "Auswahl",
+        Msg::LockSelections => "2D/3D-Kurven koppeln",
+        Msg::IndexRange => "Indexbereich",
+        Msg::HighlightRange => "Bereich hervorheben",
+        Msg::RangeFrom => "Von",
+        Msg::RangeTo => "bis",
+        Msg::LongJumps => "Lange Sprünge",
+        Msg::ShowOnCurve => "Auf der Kurve zeigen",
+        Msg::LongJump => "Langer Sprung",
+        Msg::SameJumps => "gleiche Sprünge",
+        Msg::HideSimilarJumps => "Gleiche Sprünge ausblenden",
+        Msg::ShowAllJumps => "Alle Sprünge zeigen",
+        Msg::LongJumpHint => {
+            "Einen langen Sprung in der 2D-Ansicht anklicken, um ihn zu untersuchen"
+        }
+        Msg::ShowOnSnake => "Auf der Schlange zeigen",
+        Msg::Snake => "Schlange",
+        Msg::EnableSnake => "Schlangen-Overlay aktivieren",
+        Msg::Length => "Länge",
+        Msg::Speed => "Tempo",
+        Msg::Rotation3d => "3D-Rotation",
+        Msg::Axis => "Achse",
+        Msg::Tumble => "Taumeln",
+        Msg::DragDetail => "Zieh-LOD",
+        Msg::DragDetailHint => {
+            "Beim Drehen dichter Kurven nur jedes N-te Segment zeichnen; volle Details nach dem Loslassen"
+        }
+        Msg::DepthBins => "Tiefenstufen",
+        Msg::DepthBinsHint => {
+            "Tiefenschichten, in die die 3D-Kurve gebündelt wird; mehr Schichten schattieren weicher"
+        }
+        Msg::ExactDepthSort => "Exakte Tiefensortierung",
+        Msg::ExactDepthSortHint => {
+            "Bei Kurven bis 5.000 Segmente jedes Segment nach Tiefe sortieren, damit Kreuzungen richtig überlappen"
+        }
+        Msg::Camera => "Kamera",
+        Msg::Tilt => "Neigung",
+        Msg::Distance => "Abstand",
+        Msg::Language => "Sprache",
+        Msg::About => "Über",
+        Msg::Gallery => "Galerie",
+        Msg::GalleryHint => "Eine Kurve anklicken, um sie zu erkunden.",
+        Msg::CopyState => "Zustand kopieren",
+        Msg::PasteState => "Zustand einfügen",
+        Msg::Apply => "Übernehmen",
+        Msg::Cancel => "Abbrechen",
+        Msg::ResumeAnimation => "Animation fortsetzen",
+        Msg::PauseAnimation => "Animation anhalten",
+        Msg::ToggleSettings => "Einstellungen ein/aus",
+        Msg::ToggleSnake => "Schlangen-Overlay ein/aus",
+        Msg::ToggleAbout => "Info-Dialog ein/aus",
+        Msg::ExportScreenshot => "Bildschirmfoto exportieren (PNG)",
+        Msg::AllRgb => "AllRGB-Generator",
+        Msg::Layout => "Anordnung",
+        Msg::Colormap => "Farbkurve",
+        Msg::Render => "Berechnen",
+        Msg::Save => "PNG speichern",
+        Msg::FailedSaveImage => "Bild konnte nicht gespeichert werden",
+        Msg::FailedRender => "AllRGB-Berechnung fehlgeschlagen",
+        Msg::TypeCommand => "Befehl eingeben…",
+        Msg::NoMatchingCommands => "Keine passenden Befehle",
+        Msg::InvalidState => "Ungültiger Zustand",
+        Msg::FailedOpenBrowser => "Browser konnte nicht geöffnet werden",
+        Msg::FailedSaveScreenshot => "Bildschirmfoto konnte nicht gespeichert werden",
+        Msg::FailedBuildCurve => "Kurve konnte nicht erzeugt werden",
+        Msg::Dismiss => "Schließen",
+        Msg::Details => "Details",
+        Msg::CopyCsv => "Koordinaten als CSV kopieren",
+        Msg::CopyJson => "Koordinaten als JSON kopieren",
+        Msg::Tagline => "Spielwiese für raumfüllende Kurven",
+        Msg::By => "von",
+        Msg::Welcome => "Willkommen bei",
+        Msg::AboutIntro => {
+            "Diese interaktive Spielwiese zeigt verschiedene **raumfüllende Kurven** in 2D \
+             und 3D. Raumfüllende Kurven sind stetige Pfade, die jeden Punkt eines Raums \
+             besuchen, und haben faszinierende mathematische und praktische Eigenschaften.\n\n\
+             ---\n\n*Gebaut mit Rust & egui.*"
+        }
+        Msg::LicensesAndBuildInfo => "Lizenzen & Build-Info",
+        Msg::Version => "Version",
+        Msg::Commit => "Commit",
+        Msg::Profile => "Profil",
+        Msg::License => "Lizenz",
+        Msg::Fonts => "Schriften",
+        Msg::ShowLicenseText => "Lizenztext anzeigen",
+        Msg::Dependencies => "Abhängigkeiten",
+        Msg::Cost => "Kosten",
+        Msg::Calibrate => "Kalibrieren",
+        Msg::PointLatency => "point (ns)",
+        Msg::IndexLatency => "index (ns)",
+        Msg::CurveInfoUnavailable => "Kurve lässt sich für die Info nicht erzeugen.",
+        Msg::BuildingCurve => "Kurve wird erzeugt",
+        Msg::StartingRenderer => "Renderer wird gestartet …",
+        Msg::LoadingTheme => "Schriften und Design werden geladen …",
+        Msg::WebGlUnavailable => {
+            "spacecurve benötigt WebGL, das dieser Browser oder dieses Gerät nicht bietet. \
+             Aktiviere die Hardwarebeschleunigung oder versuche einen anderen Browser."
+        }
+        Msg::StartFailed => "spacecurve konnte nicht gestartet werden",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locales_resolve_to_languages() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Some(Language::German));
+        assert_eq!(Language::from_locale("en-GB"), Some(Language::English));
+        assert_eq!(Language::from_locale("DE"), Some(Language::German));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!(Language::from_locale("fr_FR"), None);
+        assert_eq!(Language::from_locale(""), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Picking and filtering of long-jump segments in the 2D pane.
+//!
+//! Curves like Z-order and Gray leave the grid in jumps whose shapes repeat
+//! at every level of the recursion. Clicking a jump shows where it starts and
+//! ends, and jumps can be hidden by shape, so the structure of the remaining
+//! ones stands out.
+
+/// Displacement `[dx, dy]` of a step; jumps with the same displacement form
+/// one class.
+pub type JumpClass = [i64; 2];
+
+/// Displacement from `a` to `b`.
+pub fn jump_class(a: [u32; 2], b: [u32; 2]) -> JumpClass {
+    [
+        i64::from(b[0]) - i64::from(a[0]),
+        i64::from(b[1]) - i64::from(a[1]),
+    ]
+}
+
+/// Picked jump and hidden jump classes.
+#[derive(Debug, Clone, Default)]
+pub struct JumpPicker {
+    /// Index of the picked jump's first point, with both endpoints so a pick
+    /// from another curve is not mistaken for one on this curve.
+    selected: Option<(usize, [[u32; 2]; 2])>,
+    /// Classes left out of the main curve.
+    hidden: Vec<JumpClass>,
+}
+
+impl JumpPicker {
+    /// Pick the jump starting at index `index` of `points`, or clear the
+    /// pick with `None`.
+    pub fn select(&mut self, points: &[[u32; 2]], index: Option<usize>) {
+        self.selected = index.map(|i| (i, [points[i], points[i + 1]]));
+    }
+
+    /// Index of the picked jump's first point, if it is still a step of
+    /// `points`.
+    pub fn selected(&self, points: &[[u32; 2]]) -> Option<usize> {
+        let (i, ends) = self.selected?;
+        (points.get(i..i + 2) == Some(&ends[..])).then_some(i)
+    }
+
+    /// Leave jumps of `class` out of the main curve.
+    pub fn hide(&mut self, class: JumpClass) {
+        if !self.hidden.contains(&class) {
+            self.hidden.push(class);
+        }
+    }
+
+    /// Draw every class again.
+    pub fn show_all(&mut self) {
+        self.hidden.clear();
+    }
+
+    /// Classes currently hidden.
+    pub fn hidden(&self) -> &[JumpClass] {
+        &self.hidden
+    }
+
+    /// Whether jumps of `class` are hidden.
+    pub fn is_hidden(&self, class: JumpClass) -> bool {
+        self.hidden.contains(&class)
+    }
+}
+
+/// Which long jumps the main curve draws.
+#[derive(Debug, Clone, Copy)]
+pub struct JumpVisibility<'a> {
+    /// Whether long jumps are drawn at all.
+    pub show: bool,
+    /// Classes left out even when they are.
+    pub hidden: &'a [JumpClass],
+}
+
+impl JumpVisibility<'_> {
+    /// Whether every segment is drawn, so the curve is one polyline.
+    pub fn draws_all(&self) -> bool {
+        self.show && self.hidden.is_empty()
+    }
+
+    /// Whether segment `i` of `points`, joining `adjacent` cells or not, is
+    /// drawn.
+    pub fn draws(&self, points: &[[u32; 2]], adjacent: bool, i: usize) -> bool {
+        adjacent || (self.show && !self.hidden.contains(&jump_class(points[i], points[i + 1])))
+    }
+}
+
+/// Number of long jumps in `points` with displacement `class`.
+pub fn class_count(points: &[[u32; 2]], adjacent: &[bool], class: JumpClass) -> usize {
+    adjacent
+        .iter()
+        .enumerate()
+        .filter(|&(i, &adj)| !adj && jump_class(points[i], points[i + 1]) == class)
+        .count()
+}
+
+/// Index of the first point of the long jump, outside the `hidden` classes,
+/// whose screen segment passes nearest `pos`, within `radius`.
+pub fn pick_jump(
+    points: &[[u32; 2]],
+    adjacent: &[bool],
+    screen_points: &[egui::Pos2],
+    hidden: &[JumpClass],
+    pos: egui::Pos2,
+    radius: f32,
+) -> Option<usize> {
+    adjacent
+        .iter()
+        .enumerate()
+        .filter(|&(i, &adj)| !adj && !hidden.contains(&jump_class(points[i], points[i + 1])))
+        .map(|(i, _)| {
+            let distance = distance_to_segment(pos, screen_points[i], screen_points[i + 1]);
+            (i, distance)
+        })
+        .filter(|&(_, distance)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+/// Distance from `p` to the segment from `a` to `b`.
+fn distance_to_segment(p: egui::Pos2, a: egui::Pos2, b: egui::Pos2) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_sq();
+    let t = if length_sq > 0.0 {
+        ((p - a).dot(ab) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Z-order over a 2×4 block: two quads joined by one jump.
+    const POINTS: [[u32; 2]; 8] = [
+        [0, 0],
+        [1, 0],
+        [0, 1],
+        [1, 1],
+        [0, 2],
+        [1, 2],
+        [0, 3],
+        [1, 3],
+    ];
+    const ADJACENT: [bool; 7] = [true, false, true, false, true, false, true];
+
+    fn screen() -> Vec<egui::Pos2> {
+        POINTS
+            .iter()
+            .map(|p| egui::pos2(p[0] as f32 * 10.0, p[1] as f32 * 10.0))
+            .collect()
+    }
+
+    #[test]
+    fn picks_the_nearest_jump_only() {
+        let screen = screen();
+        let pick =
+            |hidden: &[JumpClass], pos| pick_jump(&POINTS, &ADJACENT, &screen, hidden, pos, 2.0);
+        // Midway along the diagonal from (1, 0) to (0, 1).
+        assert_eq!(pick(&[], egui::pos2(5.0, 5.0)), Some(1));
+        assert_eq!(pick(&[[-1, 1]], egui::pos2(5.0, 5.0)), None);
+        // On the unit step from (0, 0) to (1, 0): not a jump.
+        assert_eq!(pick(&[], egui::pos2(5.0, 0.0)), None);
+    }
+
+    #[test]
+    fn hidden_classes_drop_matching_jumps() {
+        assert_eq!(class_count(&POINTS, &ADJACENT, [-1, 1]), 3);
+        let mut picker = JumpPicker::default();
+        picker.hide([-1, 1]);
+        picker.hide([-1, 1]);
+        assert_eq!(picker.hidden(), [[-1, 1]]);
+        let visibility = JumpVisibility {
+            show: true,
+            hidden: picker.hidden(),
+        };
+        assert!(!visibility.draws_all());
+        assert!(!visibility.draws(&POINTS, false, 1));
+        assert!(visibility.draws(&POINTS, true, 0));
+        picker.show_all();
+        assert!(!picker.is_hidden([-1, 1]));
+    }
+
+    #[test]
+    fn picks_do_not_carry_over_to_other_curves() {
+        let mut picker = JumpPicker::default();
+        picker.select(&POINTS, Some(3));
+        assert_eq!(picker.selected(&POINTS), Some(3));
+        let mut other = POINTS;
+        other.swap(3, 4);
+        assert_eq!(picker.selected(&other), None);
+        assert_eq!(picker.selected(&POINTS[..3]), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Cached tessellation of the static 2D curve.
+//!
+//! At large sizes, turning the full curve path into triangles every frame
+//! dominates frame time even when only the snake moves. The curve strokes are
+//! tessellated once into a mesh and re-added each frame until the curve, the
+//! view or a setting that affects them changes.
+
+use std::sync::Arc;
+
+use egui::epaint::{Mesh, Shape, Tessellator};
+
+use crate::{heatmap::ColorMode, jumps::JumpClass};
+
+/// Everything the static curve strokes depend on; any change redraws them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerKey {
+    /// Curve name.
+    pub name: String,
+    /// Grid side length.
+    pub size: u32,
+    /// Visible grid extent when the curve is clipped to the canvas.
+    pub clip: Option<[u32; 2]>,
+    /// Canvas rect the curve is drawn and clipped to.
+    pub rect: egui::Rect,
+    /// Screen position of grid cell (0, 0).
+    pub origin: egui::Pos2,
+    /// Spacing between neighbouring grid cells.
+    pub scale: f32,
+    /// Physical pixels per point, which sets the stroke feathering.
+    pub pixels_per_point: f32,
+    /// Curve opacity.
+    pub opacity: f32,
+    /// Whether the glow pass is drawn.
+    pub glow: bool,
+    /// How segments are colored.
+    pub color_mode: ColorMode,
+    /// Whether stretch values were available for the heatmap.
+    pub stretch: bool,
+    /// Whether onion shells are tinted.
+    pub shells: bool,
+    /// Whether long jumps are drawn.
+    pub long_jumps: bool,
+    /// Long jump classes left out.
+    pub hidden_jumps: Vec<JumpClass>,
+}
+
+/// Tessellated static curve, reused while its key matches.
+#[derive(Default)]
+pub struct CurveLayer {
+    /// Key and mesh of the last tessellation.
+    cached: Option<(LayerKey, Arc<Mesh>)>,
+}
+
+impl CurveLayer {
+    /// Shape drawing the layer for `key`, tessellating what `draw` emits only
+    /// when the key differs from the last call.
+    pub fn shape(
+        &mut self,
+        ctx: &egui::Context,
+        key: LayerKey,
+        draw: impl FnOnce(&mut Vec<Shape>),
+    ) -> Shape {
+        if let Some((cached, mesh)) = &self.cached
+            && *cached == key
+        {
+            return Shape::Mesh(mesh.clone());
+        }
+        let mut shapes = Vec::new();
+        draw(&mut shapes);
+        let mesh = Arc::new(tessellate(ctx, key.rect, shapes));
+        self.cached = Some((key, mesh.clone()));
+        Shape::Mesh(mesh)
+    }
+
+    /// Drop the cached mesh.
+    pub fn clear(&mut self) {
+        self.cached = None;
+    }
+}
+
+/// Tessellate `shapes` into one mesh the way egui would paint them inside
+/// `clip_rect`.
+fn tessellate(ctx: &egui::Context, clip_rect: egui::Rect, shapes: Vec<Shape>) -> Mesh {
+    // The layer has no text, so the font atlas size is never consulted.
+    let mut tessellator = Tessellator::new(
+        ctx.pixels_per_point(),
+        ctx.tessellation_options(|options| *options),
+        [1, 1],
+        Vec::new(),
+    );
+    tessellator.set_clip_rect(clip_rect);
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_shape(Shape::Vec(shapes), &mut mesh);
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Stroke;
+
+    use super::*;
+
+    fn key(opacity: f32) -> LayerKey {
+        LayerKey {
+            name: "hilbert".to_string(),
+            size: 4,
+            clip: None,
+            rect: egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(100.0, 100.0)),
+            origin: egui::pos2(10.0, 10.0),
+            scale: 20.0,
+            pixels_per_point: 1.0,
+            opacity,
+            glow: false,
+            color_mode: ColorMode::Solid,
+            stretch: false,
+            shells: false,
+            long_jumps: false,
+            hidden_jumps: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn tessellates_again_only_when_the_key_changes() {
+        let ctx = egui::Context::default();
+        let mut layer = CurveLayer::default();
+        let mut draws = 0;
+        let mut draw = |out: &mut Vec<Shape>| {
+            draws += 1;
+            out.push(Shape::line_segment(
+                [egui::pos2(10.0, 10.0), egui::pos2(30.0, 10.0)],
+                Stroke::new(2.0, egui::Color32::WHITE),
+            ));
+        };
+        let mut mesh = |layer: &mut CurveLayer, key| match layer.shape(&ctx, key, &mut draw) {
+            Shape::Mesh(mesh) => mesh,
+            _ => panic!("layer is a mesh"),
+        };
+        let first = mesh(&mut layer, key(0.5));
+        assert!(!first.vertices.is_empty());
+        assert!(Arc::ptr_eq(&first, &mesh(&mut layer, key(0.5))));
+        assert!(!Arc::ptr_eq(&first, &mesh(&mut layer, key(0.6))));
+        layer.clear();
+        mesh(&mut layer, key(0.6));
+        assert_eq!(draws, 3);
+    }
+}
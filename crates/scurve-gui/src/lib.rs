@@ -1,9 +1,10 @@
 //! GUI application for exploring space‑filling curves using egui/eframe.
 
-use std::{fs::File, io::BufWriter, path::PathBuf, sync::Arc};
+use std::{fs::File, io::BufWriter, ops::RangeInclusive, path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use spacecurve::registry;
+use web_time::{SystemTime, UNIX_EPOCH};
 
 /// Canonical application name used across the GUI.
 pub const APP_NAME: &str = "spacecurve";
@@ -43,6 +44,54 @@ pub struct ScreenshotConfig {
     pub target: ScreenshotTarget,
     /// Output file path for the PNG.
     pub output_path: PathBuf,
+    /// Curve to show; the default curve when `None`.
+    pub curve: Option<&'static registry::CurveEntry>,
+    /// Grid size of the captured pane; the pane's default when `None`.
+    pub size: Option<u32>,
+    /// Show or hide the snake overlay; the default setting when `None`.
+    pub snake: Option<bool>,
+    /// Main curve opacity (0.0–1.0); the default when `None`.
+    pub opacity: Option<f32>,
+    /// Rotation of the 3D view about its vertical axis, in degrees.
+    pub rotation: Option<f32>,
+}
+
+impl ScreenshotConfig {
+    /// Capture `target` to `output_path` with default curve settings.
+    pub fn new(target: ScreenshotTarget, output_path: PathBuf) -> Self {
+        Self {
+            target,
+            output_path,
+            curve: None,
+            size: None,
+            snake: None,
+            opacity: None,
+            rotation: None,
+        }
+    }
+
+    /// Pane shown by the capture target.
+    fn pane(&self) -> Pane {
+        match self.target {
+            ScreenshotTarget::TwoD | ScreenshotTarget::About | ScreenshotTarget::Settings => {
+                Pane::TwoD
+            }
+            ScreenshotTarget::ThreeD | ScreenshotTarget::Settings3D => Pane::ThreeD,
+        }
+    }
+
+    /// Curve settings to apply on top of the launch state.
+    fn state(&self) -> StateSpec {
+        let pane = self.pane();
+        StateSpec {
+            curve: self.curve.map(|entry| entry.key.to_string()),
+            size: self.size.filter(|_| pane == Pane::TwoD),
+            size3d: self.size.filter(|_| pane == Pane::ThreeD),
+            opacity: self.opacity,
+            snake: self.snake,
+            ..StateSpec::default()
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -52,6 +101,37 @@ struct ActiveScreenshot {
     output_path: PathBuf,
     /// Whether we've already requested a frame capture.
     requested: bool,
+    /// Close the window once the capture has been saved.
+    close_when_done: bool,
+    /// Window region to keep, in points; the whole window when `None`.
+    crop: Option<egui::Rect>,
+}
+
+/// Actions offered by the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PaletteAction {
+    /// Switch to the given pane.
+    SwitchPane(Pane),
+    /// Select a curve in the active pane.
+    SelectCurve(&'static str),
+    /// Set the grid size in the active pane.
+    SetSize(u32),
+    /// Open or close the settings dropdown.
+    ToggleSettings,
+    /// Enable or disable the snake overlay.
+    ToggleSnake,
+    /// Pause or resume animations.
+    TogglePause,
+    /// Open or close the About dialog.
+    ToggleAbout,
+    /// Save a PNG screenshot of the window to the working directory.
+    Export,
+    /// Copy the current state spec to the clipboard.
+    CopyState,
+    /// Open the dialog for pasting a state spec.
+    PasteState,
+    /// Open the AllRGB generator.
+    AllRgb,
 }
 
 /// Launch configuration for the GUI.
@@ -63,34 +143,97 @@ pub struct GuiOptions {
     pub screenshot: Option<ScreenshotConfig>,
     /// Enable developer overlay (frame timing, etc.).
     pub show_dev_overlay: bool,
+    /// Start in demo mode, cycling curves, sizes and panes unattended.
+    pub demo: bool,
+    /// Initial state to apply on startup (see [`share::StateSpec`]).
+    pub state: Option<StateSpec>,
+    /// Embedded mode for iframes: only the canvas is shown and interactive,
+    /// and stored settings are neither restored nor overwritten.
+    pub embed: bool,
+    /// Directory for Ctrl+S canvas captures; the working directory when `None`.
+    pub screenshot_dir: Option<PathBuf>,
+    /// Clock driving animations; screenshot mode switches a wall clock to a
+    /// fixed one at [`theme::animation::CAPTURE_FPS`].
+    pub clock: AnimationClock,
 }
 
 /// About dialog contents and helpers.
 pub mod about;
+/// AllRGB generator dialog.
+pub mod allrgb;
+/// Cropping and file naming for canvas captures.
+pub mod capture;
+/// Unattended demo mode.
+pub mod demo;
+/// Clipboard export of curve points.
+pub mod export;
+/// Statistics-driven curve coloring.
+pub mod heatmap;
+/// Translated user-facing strings.
+pub mod i18n;
+/// Picking and filtering of long jumps in the 2D pane.
+pub mod jumps;
+/// Cached tessellation of the static 2D curve.
+pub mod layer;
+/// Two-point measurement tool for the 2D pane.
+pub mod measure;
+/// Command palette widget with fuzzy search.
+pub mod palette;
+/// Viewer state kept across restarts.
+pub mod persist;
+/// Hover picking of curve vertices on the canvas.
+pub mod pick;
+/// Rotation matrices and axis choices for the 3D view.
+pub mod rotation;
 /// Shared selection/cache helpers for 2D and 3D panes.
 pub mod selection;
+/// Shareable text form of the viewer state.
+pub mod share;
+/// Alternating shell coloring for onion-family curves.
+pub mod shells;
 /// Shared helpers for snake overlays.
 pub mod snake;
 /// State management logic.
 pub mod state;
+/// Headless frame rendering for integration tests and recordings.
+pub mod test_harness;
 /// Centralized theme constants (colors, fonts, spacing).
 pub mod theme;
 /// 3D view and interactions.
 pub mod threed;
+/// Dismissible error notifications.
+pub mod toast;
 /// 2D view and interactions.
 pub mod twod;
 /// Reusable GUI widgets.
 pub mod widgets;
 
+use allrgb::{AllRgbDialog, AllRgbEvent};
+use demo::{DemoMode, DemoStep};
+use heatmap::ColorMode;
+use i18n::{Language, Msg};
+use palette::{CommandPalette, PaletteItem};
+use rotation::RotationAxis;
 pub use selection::{Selected3DCurve, SelectedCurve};
+use share::StateSpec;
+pub use state::AnimationClock;
 use state::AnimationController;
 use threed::show_3d_pane;
+use toast::Toasts;
 use twod::show_2d_pane;
 
 /// Settings shared between the 2D and 3D views.
 pub struct SharedSettings {
     /// Opacity of the main curve rendering (0.0–1.0).
     pub curve_opacity: f32,
+    /// Draw a wide translucent glow pass under the curve strokes.
+    pub curve_glow: bool,
+    /// How the main 2D curve strokes are colored.
+    pub color_mode: ColorMode,
+    /// Reuse the tessellated 2D curve across frames until it changes.
+    pub cache_static_2d: bool,
+    /// Tint alternate shells of onion-family curves in both panes.
+    pub onion_shells: bool,
     /// Whether to draw long-jump segments in the main curve.
     pub curve_long_jumps: bool,
     /// Whether to draw long-jump segments in the snake overlay.
@@ -103,18 +246,68 @@ pub struct SharedSettings {
     pub snake_speed: f32,
     /// Rotation speed of the 3D view (0–100 scale).
     pub spin_speed: f32,
+    /// Axis the 3D view spins around while animating.
+    pub rotation_axis: RotationAxis,
+    /// Camera tilt of the 3D view around the X axis, in radians.
+    pub camera_tilt: f32,
+    /// Camera distance for the 3D perspective projection; larger is flatter.
+    pub perspective_distance: f32,
+    /// Draw every Nth segment of dense 3D curves while dragging (1 disables).
+    pub drag_decimation: u32,
+    /// Number of depth buckets the 3D view batches segments into.
+    pub depth_bins: usize,
+    /// Sort 3D segments exactly instead of binning them, for curves of at
+    /// most [`theme::canvas_3d::EXACT_SORT_MAX_SEGMENTS`] segments.
+    pub exact_depth_sort: bool,
+    /// Keep the 2D and 3D panes on the same curve when true.
+    pub lock_selections: bool,
+    /// Shade the cells whose curve index falls in `range_min..=range_max`.
+    pub range_enabled: bool,
+    /// First curve index of the highlighted range.
+    pub range_min: u32,
+    /// Last curve index of the highlighted range.
+    pub range_max: u32,
+    /// Interface language for labels, menus and dialogs.
+    pub language: Language,
+}
+
+impl SharedSettings {
+    /// Highlighted index range clamped to a curve of `len` points, if enabled and non-empty.
+    pub fn highlight_range(&self, len: usize) -> Option<RangeInclusive<usize>> {
+        if !self.range_enabled || len == 0 {
+            return None;
+        }
+        let lo = self.range_min as usize;
+        let hi = (self.range_max as usize).min(len - 1);
+        (lo <= hi).then_some(lo..=hi)
+    }
 }
 
 impl Default for SharedSettings {
     fn default() -> Self {
         Self {
             curve_opacity: 0.35, // Default to 35% opacity
+            curve_glow: false,
+            color_mode: ColorMode::default(),
+            cache_static_2d: true,
+            onion_shells: false,
             curve_long_jumps: false,
             snake_long_jumps: false,
             snake_enabled: true,
             snake_length: 5.0, // Default to 5% of curve length
             snake_speed: 30.0, // Default snake speed (segments per second)
             spin_speed: 50.0,  // Default rotation speed (0-100 scale)
+            rotation_axis: RotationAxis::default(),
+            camera_tilt: theme::canvas_3d::CAMERA_TILT,
+            perspective_distance: theme::canvas_3d::PERSPECTIVE_DISTANCE,
+            drag_decimation: theme::canvas_3d::DRAG_DECIMATION,
+            depth_bins: theme::canvas_3d::DEPTH_BINS,
+            exact_depth_sort: false,
+            lock_selections: true,
+            range_enabled: false,
+            range_min: 0,
+            range_max: 255,
+            language: Language::default(),
         }
     }
 }
@@ -127,8 +320,8 @@ pub struct AppState {
     pub animation_time: f32,
     /// Global pause state for animations.
     pub paused: bool,
-    /// Current rotation angle for the 3D view (radians).
-    pub rotation_angle: f32,
+    /// Current rotation of the 3D view as `[x, y, z]` Euler angles (radians).
+    pub rotation_angles: [f32; 3],
     /// Whether the user is currently dragging in the 3D view.
     pub mouse_dragging: bool,
     /// Last X coordinate recorded during a drag gesture.
@@ -141,12 +334,24 @@ pub struct AppState {
     pub settings_dropdown_pos: Option<egui::Pos2>,
     /// Whether the About dialog is currently open.
     pub about_open: bool,
+    /// Tab shown in the About dialog.
+    pub about_tab: about::AboutTab,
     /// Smoothed frame time in milliseconds (for dev overlay).
     pub frame_time_ms: Option<f32>,
     /// Latched frame time used for the UI (updates slowly for readability).
     pub frame_time_display_ms: Option<f32>,
     /// Last time (seconds) the display value was latched.
     pub frame_time_last_display_s: Option<f64>,
+    /// Two-point measurement tool state for the 2D pane.
+    pub measurement: measure::Measurement,
+    /// Picked long jump and hidden jump classes in the 2D pane.
+    pub jumps: jumps::JumpPicker,
+    /// Grid cell the 2D canvas is zoomed in on, if any.
+    pub zoom_focus_2d: Option<[u32; 2]>,
+    /// Clip the 2D curve to the canvas shape in wide or tall windows.
+    pub fit_2d: bool,
+    /// Show only the canvas, without menu bar, control bars or app shortcuts.
+    pub embed: bool,
 }
 
 impl Default for AppState {
@@ -155,16 +360,22 @@ impl Default for AppState {
             current_pane: Pane::TwoD,
             animation_time: 0.0,
             paused: false,
-            rotation_angle: 0.0,
+            rotation_angles: [0.0; 3],
             mouse_dragging: false,
             last_mouse_x: 0.0,
             snake_time: 0.0,
             settings_dropdown_open: false,
             settings_dropdown_pos: None,
             about_open: false,
+            about_tab: about::AboutTab::default(),
             frame_time_ms: None,
             frame_time_display_ms: None,
             frame_time_last_display_s: None,
+            measurement: measure::Measurement::default(),
+            jumps: jumps::JumpPicker::default(),
+            zoom_focus_2d: None,
+            fit_2d: false,
+            embed: false,
         }
     }
 }
@@ -187,8 +398,6 @@ pub struct RenderCache {
     pub cache_3d_points: Vec<[f32; 3]>,
     /// Reusable buffer for 3D rendering (screen points).
     pub cache_3d_screen: Vec<egui::Pos2>,
-    /// Reusable buffer for 3D rendering (connectivity).
-    pub cache_connected: Vec<bool>,
     /// Reusable buffer for 3D rendering (shorten caps).
     pub cache_caps: Vec<(bool, bool)>,
     /// Reusable buffer for 3D rendering (depth sorting).
@@ -197,8 +406,18 @@ pub struct RenderCache {
     pub cache_2d_screen: Vec<egui::Pos2>,
     /// Reusable buffer for 2D line segments.
     pub cache_2d_run: Vec<egui::Pos2>,
+    /// Reusable buffer for the zoom overview inset's screen points.
+    pub cache_2d_minimap: Vec<egui::Pos2>,
+    /// Reusable buffer for 2D per-segment heatmap values.
+    pub cache_2d_heat: Vec<f32>,
+    /// Reusable buffer for per-point onion shell levels.
+    pub cache_shells: Vec<u32>,
+    /// Spatial lookup over the current pane's screen points for hover picking.
+    pub pick_grid: pick::ScreenGrid,
     /// Reusable buffer for depth binning (3D).
     pub cache_bins: Vec<Vec<usize>>,
+    /// Tessellated static 2D curve.
+    pub curve_layer_2d: layer::CurveLayer,
 }
 
 impl Default for RenderCache {
@@ -212,12 +431,16 @@ impl Default for RenderCache {
             last_canvas_rect: None,
             cache_3d_points: Vec::new(),
             cache_3d_screen: Vec::new(),
-            cache_connected: Vec::new(),
             cache_caps: Vec::new(),
             cache_depths: Vec::new(),
             cache_2d_screen: Vec::new(),
             cache_2d_run: Vec::new(),
-            cache_bins: vec![Vec::new(); 128],
+            cache_2d_minimap: Vec::new(),
+            cache_2d_heat: Vec::new(),
+            cache_shells: Vec::new(),
+            pick_grid: pick::ScreenGrid::default(),
+            cache_bins: vec![Vec::new(); theme::canvas_3d::DEPTH_BINS],
+            curve_layer_2d: layer::CurveLayer::default(),
         }
     }
 }
@@ -236,14 +459,28 @@ pub struct ScurveApp {
     render_cache: RenderCache,
     /// Settings shared between panes.
     shared_settings: SharedSettings,
-    /// Active screenshot request state (when running in screenshot mode).
+    /// Active screenshot request state (screenshot mode, export or Ctrl+S).
     screenshot: Option<ActiveScreenshot>,
+    /// Directory that Ctrl+S canvas captures are written to.
+    screenshot_dir: PathBuf,
     /// Last frame time used to compute deltas.
     last_time: Option<f64>,
+    /// Clock turning frame times into animation steps.
+    clock: AnimationClock,
     /// CommonMark cache for the About dialog.
     commonmark_cache: egui_commonmark::CommonMarkCache,
     /// Whether to show developer diagnostics overlay.
     show_dev_overlay: bool,
+    /// Error notifications awaiting dismissal.
+    toasts: Toasts,
+    /// Ctrl+K command palette state.
+    palette: CommandPalette,
+    /// Demo mode timer, when running unattended.
+    demo: Option<DemoMode>,
+    /// Text in the "Paste state" dialog, when it is open.
+    paste_state: Option<String>,
+    /// AllRGB generator window state.
+    allrgb: AllRgbDialog,
 }
 
 impl ScurveApp {
@@ -286,38 +523,47 @@ impl ScurveApp {
         let mut app_state = AppState::default();
         let render_cache = RenderCache::default();
         let screenshot_config = options.screenshot;
+        let clock = match options.clock {
+            AnimationClock::Wall if screenshot_config.is_some() => {
+                AnimationClock::Fixed(theme::animation::CAPTURE_FPS)
+            }
+            clock => clock,
+        };
         let mut screenshot_runtime = screenshot_config.as_ref().map(|cfg| ActiveScreenshot {
             output_path: cfg.output_path.clone(),
             requested: false,
+            close_when_done: true,
+            crop: None,
         });
 
+        // Screenshots and embeds start from defaults so they don't depend on past sessions.
+        app_state.embed = options.embed;
+        let saved = cc
+            .storage
+            .filter(|_| screenshot_config.is_none() && !options.embed)
+            .map(persist::load)
+            .unwrap_or_default();
+
         // Configure initial state based on screenshot target
+        let mut screenshot_state = None;
         if let Some(config) = screenshot_config {
+            app_state.current_pane = config.pane();
             match config.target {
-                ScreenshotTarget::TwoD => {
-                    app_state.current_pane = Pane::TwoD;
-                }
-                ScreenshotTarget::ThreeD => {
-                    app_state.current_pane = Pane::ThreeD;
-                }
-                ScreenshotTarget::About => {
-                    app_state.current_pane = Pane::TwoD;
-                    app_state.about_open = true;
-                }
-                ScreenshotTarget::Settings => {
-                    app_state.current_pane = Pane::TwoD;
-                    app_state.settings_dropdown_open = true;
-                }
-                ScreenshotTarget::Settings3D => {
-                    app_state.current_pane = Pane::ThreeD;
+                ScreenshotTarget::About => app_state.about_open = true,
+                ScreenshotTarget::Settings | ScreenshotTarget::Settings3D => {
                     app_state.settings_dropdown_open = true;
                 }
+                ScreenshotTarget::TwoD | ScreenshotTarget::ThreeD => {}
+            }
+            if let Some(degrees) = config.rotation {
+                app_state.rotation_angles[1] = degrees.to_radians();
             }
             // Pause animations for consistent screenshots
             app_state.paused = true;
+            screenshot_state = Some(config.state());
         }
 
-        Self {
+        let mut app = Self {
             selected_curve: SelectedCurve::with_name(default_curve),
             selected_3d_curve: Selected3DCurve::with_name(default_curve),
             available_curves,
@@ -325,9 +571,286 @@ impl ScurveApp {
             render_cache,
             shared_settings: Default::default(),
             screenshot: screenshot_runtime.take(),
+            screenshot_dir: options.screenshot_dir.unwrap_or_default(),
             last_time: None,
+            clock,
             commonmark_cache: Default::default(),
             show_dev_overlay: options.show_dev_overlay,
+            toasts: Toasts::default(),
+            palette: CommandPalette::default(),
+            demo: options.demo.then(DemoMode::default),
+            paste_state: None,
+            allrgb: AllRgbDialog::default(),
+        };
+        if let Some(language) = saved.language {
+            app.shared_settings.language = language;
+        }
+        for spec in saved
+            .state
+            .iter()
+            .chain(&options.state)
+            .chain(&screenshot_state)
+        {
+            spec.apply(
+                &mut app.app_state,
+                &mut app.shared_settings,
+                &mut app.selected_curve,
+                &mut app.selected_3d_curve,
+            );
+        }
+        if let Some(demo) = &app.demo {
+            let step = demo.current(&app.available_curves);
+            app.apply_demo_step(step);
+        }
+        app
+    }
+
+    /// Switch pane, curve and size to the given demo step.
+    fn apply_demo_step(&mut self, step: DemoStep) {
+        self.app_state.current_pane = step.pane;
+        self.app_state.paused = false;
+        self.shared_settings.snake_enabled = true;
+        self.selected_curve.name = step.curve.to_string();
+        self.selected_3d_curve.name = step.curve.to_string();
+        match step.pane {
+            Pane::TwoD => self.selected_curve.size = step.size,
+            Pane::ThreeD => self.selected_3d_curve.size = step.size,
+        }
+    }
+
+    /// Advance demo mode, leaving it when Escape is pressed.
+    fn update_demo(&mut self, ctx: &egui::Context, delta: f32) {
+        let Some(demo) = self.demo.as_mut() else {
+            return;
+        };
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.demo = None;
+            return;
+        }
+        if let Some(step) = demo.advance(delta, &self.available_curves) {
+            self.apply_demo_step(step);
+        }
+        ctx.request_repaint();
+    }
+
+    /// Cover `rect` with the demo fade for smooth transitions between steps.
+    fn show_demo_fade(&self, ctx: &egui::Context, rect: egui::Rect) {
+        let Some(demo) = &self.demo else {
+            return;
+        };
+        let fade = demo.fade();
+        if fade <= 0.0 {
+            return;
+        }
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Middle,
+            egui::Id::new("demo_fade"),
+        ));
+        painter.rect_filled(rect, 0.0, theme::CANVAS_BACKGROUND.gamma_multiply(fade));
+    }
+
+    /// Build the command palette entries for the current state.
+    fn palette_items(&self) -> Vec<PaletteItem<PaletteAction>> {
+        let lang = self.shared_settings.language;
+        let (pane, curve, size) = (lang.tr(Msg::Pane), lang.tr(Msg::Curve), lang.tr(Msg::Size));
+        let mut items = vec![
+            PaletteItem::new(format!("{pane} 2D"), PaletteAction::SwitchPane(Pane::TwoD)),
+            PaletteItem::new(
+                format!("{pane} 3D"),
+                PaletteAction::SwitchPane(Pane::ThreeD),
+            ),
+        ];
+        for &name in &self.available_curves {
+            items.push(PaletteItem::new(
+                format!("{curve} {name}"),
+                PaletteAction::SelectCurve(name),
+            ));
+        }
+        match self.app_state.current_pane {
+            Pane::TwoD => items.extend(
+                widgets::size_choices::<2>(&self.selected_curve.name)
+                    .into_iter()
+                    .map(|s| {
+                        PaletteItem::new(format!("{size} {s}×{s}"), PaletteAction::SetSize(s))
+                    }),
+            ),
+            Pane::ThreeD => items.extend(
+                widgets::size_choices::<3>(&self.selected_3d_curve.name)
+                    .into_iter()
+                    .map(|s| {
+                        PaletteItem::new(format!("{size} {s}×{s}×{s}"), PaletteAction::SetSize(s))
+                    }),
+            ),
+        }
+        let pause_label = if self.app_state.paused {
+            Msg::ResumeAnimation
+        } else {
+            Msg::PauseAnimation
+        };
+        items.extend(
+            [
+                (Msg::ToggleSettings, PaletteAction::ToggleSettings),
+                (Msg::ToggleSnake, PaletteAction::ToggleSnake),
+                (pause_label, PaletteAction::TogglePause),
+                (Msg::ToggleAbout, PaletteAction::ToggleAbout),
+                (Msg::ExportScreenshot, PaletteAction::Export),
+                (Msg::CopyState, PaletteAction::CopyState),
+                (Msg::PasteState, PaletteAction::PasteState),
+                (Msg::AllRgb, PaletteAction::AllRgb),
+            ]
+            .map(|(msg, action)| PaletteItem::new(lang.tr(msg), action)),
+        );
+        items
+    }
+
+    /// Select the curve called `name` in the current pane.
+    fn select_curve(&mut self, name: &str) {
+        match self.app_state.current_pane {
+            Pane::TwoD => self.selected_curve.name = name.to_string(),
+            Pane::ThreeD => self.selected_3d_curve.name = name.to_string(),
+        }
+    }
+
+    /// Apply an action chosen from the command palette.
+    fn apply_palette_action(&mut self, ctx: &egui::Context, action: PaletteAction) {
+        let pane = self.app_state.current_pane;
+        match action {
+            PaletteAction::SwitchPane(target) => self.app_state.current_pane = target,
+            PaletteAction::SelectCurve(name) => self.select_curve(name),
+            PaletteAction::SetSize(size) => match pane {
+                Pane::TwoD => self.selected_curve.request_size(size),
+                Pane::ThreeD => self.selected_3d_curve.request_size(size),
+            },
+            PaletteAction::ToggleSettings => {
+                self.app_state.settings_dropdown_open = !self.app_state.settings_dropdown_open;
+                self.app_state.settings_dropdown_pos = None;
+            }
+            PaletteAction::ToggleSnake => {
+                self.shared_settings.snake_enabled = !self.shared_settings.snake_enabled;
+            }
+            PaletteAction::TogglePause => self.app_state.paused = !self.app_state.paused,
+            PaletteAction::ToggleAbout => self.app_state.about_open = !self.app_state.about_open,
+            PaletteAction::Export => {
+                self.screenshot = Some(ActiveScreenshot {
+                    output_path: PathBuf::from(format!("{}.png", self.pane_file_stem())),
+                    requested: false,
+                    close_when_done: false,
+                    crop: None,
+                });
+            }
+            PaletteAction::CopyState => self.copy_state(ctx),
+            PaletteAction::PasteState => self.paste_state = Some(String::new()),
+            PaletteAction::AllRgb => self.allrgb.open = true,
+        }
+    }
+
+    /// File name stem describing the active pane, e.g. `hilbert-64-2d`.
+    fn pane_file_stem(&self) -> String {
+        let (name, size, tag) = match self.app_state.current_pane {
+            Pane::TwoD => (&self.selected_curve.name, self.selected_curve.size, "2d"),
+            Pane::ThreeD => (
+                &self.selected_3d_curve.name,
+                self.selected_3d_curve.size,
+                "3d",
+            ),
+        };
+        format!("{name}-{size}-{tag}")
+    }
+
+    /// Capture the active pane's canvas to a timestamped PNG in the screenshot directory.
+    fn capture_canvas(&mut self) {
+        if self.screenshot.is_some() {
+            return;
+        }
+        let unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let file_name = format!(
+            "{}-{}.png",
+            self.pane_file_stem(),
+            capture::timestamp(unix_secs)
+        );
+        self.screenshot = Some(ActiveScreenshot {
+            output_path: self.screenshot_dir.join(file_name),
+            requested: false,
+            close_when_done: false,
+            crop: self.render_cache.last_canvas_rect,
+        });
+    }
+
+    /// Copy the current state spec to the clipboard.
+    fn copy_state(&self, ctx: &egui::Context) {
+        let spec = StateSpec::capture(
+            &self.app_state,
+            &self.shared_settings,
+            &self.selected_curve,
+            &self.selected_3d_curve,
+        );
+        ctx.copy_text(spec.to_string());
+    }
+
+    /// Render the AllRGB generator and handle its save and failure events.
+    fn show_allrgb(&mut self, ctx: &egui::Context) {
+        let lang = self.shared_settings.language;
+        match self.allrgb.show(ctx, &self.available_curves, lang) {
+            Some(AllRgbEvent::Save { path, image }) => {
+                if let Err(err) = save_color_image(&path, &image) {
+                    self.toasts.error(
+                        lang.tr(Msg::FailedSaveImage),
+                        format!("{}: {err:#}", path.display()),
+                    );
+                }
+            }
+            Some(AllRgbEvent::Failed(err)) => self.toasts.error(lang.tr(Msg::FailedRender), err),
+            None => {}
+        }
+    }
+
+    /// Render the "Paste state" dialog and apply the spec when confirmed.
+    ///
+    /// egui can only read the clipboard through paste events, so the spec is
+    /// pasted into a text field rather than read directly.
+    fn show_paste_state(&mut self, ctx: &egui::Context) {
+        let lang = self.shared_settings.language;
+        let Some(text) = self.paste_state.as_mut() else {
+            return;
+        };
+        let mut apply = false;
+        let mut cancel = false;
+        egui::Window::new(lang.tr(Msg::PasteState))
+            .id(egui::Id::new("paste_state"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text("curve=hilbert&size=64…")
+                        .desired_width(theme::palette::WIDTH),
+                );
+                edit.request_focus();
+                apply = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.horizontal(|ui| {
+                    apply |= ui.button(lang.tr(Msg::Apply)).clicked();
+                    cancel = ui.button(lang.tr(Msg::Cancel)).clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape));
+                });
+            });
+        if cancel {
+            self.paste_state = None;
+        } else if apply {
+            match text.parse::<StateSpec>() {
+                Ok(spec) => {
+                    spec.apply(
+                        &mut self.app_state,
+                        &mut self.shared_settings,
+                        &mut self.selected_curve,
+                        &mut self.selected_3d_curve,
+                    );
+                    self.paste_state = None;
+                }
+                Err(err) => self.toasts.error(lang.tr(Msg::InvalidState), err),
+            }
         }
     }
 
@@ -353,7 +876,10 @@ impl ScurveApp {
                         .clicked()
                         && let Err(e) = webbrowser::open(APP_REPO_URL)
                     {
-                        eprintln!("Failed to open browser: {e}");
+                        self.toasts.error(
+                            self.shared_settings.language.tr(Msg::FailedOpenBrowser),
+                            format!("{APP_REPO_URL}: {e}"),
+                        );
                     }
 
                     ui.add_space(theme::menu_bar::TITLE_SPACING);
@@ -383,9 +909,16 @@ impl ScurveApp {
                     // Right-aligned About button with padding
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.add_space(theme::menu_bar::BUTTON_PADDING);
-                        if ui.button("About").clicked() {
+                        let lang = self.shared_settings.language;
+                        if ui.button(lang.tr(Msg::About)).clicked() {
                             self.app_state.about_open = !self.app_state.about_open;
                         }
+                        if ui.button(lang.tr(Msg::PasteState)).clicked() {
+                            self.paste_state = Some(String::new());
+                        }
+                        if ui.button(lang.tr(Msg::CopyState)).clicked() {
+                            self.copy_state(ctx);
+                        }
                     });
                 });
             });
@@ -415,11 +948,23 @@ impl ScurveApp {
             }
         });
 
-        if let Some(image) = captured {
+        if let Some(mut image) = captured {
+            if let Some(rect) = screenshot.crop {
+                image = Arc::new(capture::crop(&image, rect, ctx.pixels_per_point()));
+            }
             if let Err(err) = save_color_image(&screenshot.output_path, &image) {
-                eprintln!("Failed to save screenshot: {err}");
+                // Keep the window open so the failure stays visible.
+                self.toasts.error(
+                    self.shared_settings.language.tr(Msg::FailedSaveScreenshot),
+                    format!("{}: {err:#}", screenshot.output_path.display()),
+                );
+                self.screenshot = None;
+                return;
             }
-            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            if screenshot.close_when_done {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            self.screenshot = None;
         } else {
             // Keep driving frames until the platform delivers the screenshot event.
             ctx.request_repaint();
@@ -509,8 +1054,10 @@ impl eframe::App for ScurveApp {
             let delta = (now - prev) as f32;
             let clamped_delta = delta.max(0.0);
             self.update_frame_time(clamped_delta, now);
+            let step = self.clock.step(clamped_delta);
+            self.update_demo(ctx, step);
             AnimationController::update(
-                clamped_delta,
+                step,
                 &mut self.app_state,
                 &self.shared_settings,
                 &mut self.selected_curve,
@@ -527,54 +1074,120 @@ impl eframe::App for ScurveApp {
             ctx.request_repaint();
         }
 
-        self.show_menu_bar(ctx);
+        // Embedded views leave the host page in charge of everything but the canvas.
+        if !self.app_state.embed {
+            self.show_menu_bar(ctx);
+
+            let palette_shortcut =
+                egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::K);
+            if ctx.input_mut(|i| i.consume_shortcut(&palette_shortcut)) {
+                self.palette.toggle();
+            }
+            let capture_shortcut =
+                egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S);
+            if ctx.input_mut(|i| i.consume_shortcut(&capture_shortcut)) {
+                self.capture_canvas();
+            }
+        }
 
         // Show About dialog if open
-        if self.app_state.about_open {
-            about::show_about_dialog(
+        if self.app_state.about_open
+            && let Some(name) = about::show_about_dialog(
                 ctx,
                 &mut self.app_state.about_open,
+                &mut self.app_state.about_tab,
                 &mut self.commonmark_cache,
-            );
+                &self.available_curves,
+                self.shared_settings.language,
+            )
+        {
+            self.select_curve(name);
         }
 
-        egui::CentralPanel::default().show(ctx, |ui| match self.app_state.current_pane {
-            Pane::TwoD => {
-                show_2d_pane(
-                    ui,
-                    &mut self.app_state,
-                    &mut self.render_cache,
-                    &mut self.selected_curve,
-                    &self.available_curves,
-                    &mut self.shared_settings,
-                );
-            }
-            Pane::ThreeD => {
-                show_3d_pane(
-                    ui,
-                    &mut self.app_state,
-                    &mut self.render_cache,
-                    &mut self.selected_3d_curve,
-                    &self.available_curves,
-                    &mut self.shared_settings,
-                );
-            }
-        });
+        let central =
+            egui::CentralPanel::default().show(ctx, |ui| match self.app_state.current_pane {
+                Pane::TwoD => {
+                    show_2d_pane(
+                        ui,
+                        &mut self.app_state,
+                        &mut self.render_cache,
+                        &mut self.selected_curve,
+                        &self.available_curves,
+                        &mut self.shared_settings,
+                    );
+                }
+                Pane::ThreeD => {
+                    show_3d_pane(
+                        ui,
+                        &mut self.app_state,
+                        &mut self.render_cache,
+                        &mut self.selected_3d_curve,
+                        &self.available_curves,
+                        &mut self.shared_settings,
+                    );
+                }
+            });
+
+        self.show_demo_fade(ctx, central.response.rect);
 
         // Synchronize selection between panes based on the active pane
         AnimationController::sync_panes(
             self.app_state.current_pane,
+            self.shared_settings.lock_selections,
             &mut self.selected_curve,
             &mut self.selected_3d_curve,
             &self.available_curves,
         );
 
+        for err in [
+            self.selected_curve.take_error(),
+            self.selected_3d_curve.take_error(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.toasts
+                .error(self.shared_settings.language.tr(Msg::FailedBuildCurve), err);
+        }
+        self.show_paste_state(ctx);
+        self.show_allrgb(ctx);
+        self.toasts.show(ctx, self.shared_settings.language);
+
         self.handle_screenshot(ctx, frame);
 
+        // Run after screenshot handling so an export starts on a frame without the palette.
+        if self.palette.open {
+            let items = self.palette_items();
+            if let Some(action) = self
+                .palette
+                .show(ctx, &items, self.shared_settings.language)
+            {
+                self.apply_palette_action(ctx, action);
+                ctx.request_repaint();
+            }
+        }
+
         if self.show_dev_overlay {
             self.show_frame_time_overlay(ctx);
         }
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Demo, embedded and screenshot runs drive the state themselves; keep the user's.
+        if self.demo.is_some()
+            || self.app_state.embed
+            || self.screenshot.as_ref().is_some_and(|s| s.close_when_done)
+        {
+            return;
+        }
+        let state = StateSpec::capture(
+            &self.app_state,
+            &self.shared_settings,
+            &self.selected_curve,
+            &self.selected_3d_curve,
+        );
+        persist::store(storage, &state, self.shared_settings.language);
+    }
 }
 
 /// Persist an egui `ColorImage` to disk as a PNG file.
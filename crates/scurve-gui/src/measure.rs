@@ -0,0 +1,109 @@
+//! Two-point measurement tool for the 2D pane.
+//!
+//! Compares how far apart two cells are along the curve with how far apart
+//! they are in space, which makes a curve's locality tangible.
+
+/// Measurement state: whether the tool is active and which cells are picked.
+///
+/// Cells are stored as grid coordinates rather than curve indices so the same
+/// pair can be compared across curves of the same size.
+#[derive(Debug, Clone, Default)]
+pub struct Measurement {
+    /// Whether canvas clicks place measurement points.
+    pub active: bool,
+    /// Picked grid cells, filled in click order.
+    cells: [Option<[u32; 2]>; 2],
+}
+
+impl Measurement {
+    /// Record a click on `cell`: set the second endpoint if only the first is
+    /// placed, otherwise start a new measurement from `cell`.
+    pub fn click(&mut self, cell: [u32; 2]) {
+        self.cells = match self.cells {
+            [Some(first), None] => [Some(first), Some(cell)],
+            _ => [Some(cell), None],
+        };
+    }
+
+    /// Remove both endpoints.
+    pub fn clear(&mut self) {
+        self.cells = [None, None];
+    }
+
+    /// Resolve the picked cells to curve indices in `points`.
+    ///
+    /// Cells that are not on the current grid (e.g. after shrinking the size)
+    /// resolve to `None`.
+    pub fn indices(&self, points: &[[u32; 2]]) -> [Option<usize>; 2] {
+        self.cells
+            .map(|cell| cell.and_then(|c| points.iter().position(|p| *p == c)))
+    }
+}
+
+/// Distances between two measured curve indices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasureStats {
+    /// Number of curve steps between the two indices.
+    pub index_distance: u32,
+    /// Straight-line distance between the two cells, in grid units.
+    pub euclidean: f32,
+    /// Ratio of index distance to Euclidean distance; `None` for a single cell.
+    pub stretch: Option<f32>,
+}
+
+impl MeasureStats {
+    /// Compute the stats for indices `a` and `b` into `points`.
+    pub fn between(points: &[[u32; 2]], a: usize, b: usize) -> Self {
+        let (pa, pb) = (points[a], points[b]);
+        let dx = pa[0] as f32 - pb[0] as f32;
+        let dy = pa[1] as f32 - pb[1] as f32;
+        let euclidean = (dx * dx + dy * dy).sqrt();
+        let index_distance = a.abs_diff(b) as u32;
+        let stretch = (euclidean > 0.0).then(|| index_distance as f32 / euclidean);
+        Self {
+            index_distance,
+            euclidean,
+            stretch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2×2 "U" path: (0,0) → (0,1) → (1,1) → (1,0).
+    const U_PATH: [[u32; 2]; 4] = [[0, 0], [0, 1], [1, 1], [1, 0]];
+
+    #[test]
+    fn clicks_alternate_between_endpoints() {
+        let mut m = Measurement::default();
+        m.click([0, 0]);
+        assert_eq!(m.indices(&U_PATH), [Some(0), None]);
+        m.click([1, 0]);
+        assert_eq!(m.indices(&U_PATH), [Some(0), Some(3)]);
+        m.click([1, 1]);
+        assert_eq!(m.indices(&U_PATH), [Some(2), None]);
+        m.clear();
+        assert_eq!(m.indices(&U_PATH), [None, None]);
+    }
+
+    #[test]
+    fn cells_off_grid_resolve_to_none() {
+        let mut m = Measurement::default();
+        m.click([5, 5]);
+        assert_eq!(m.indices(&U_PATH), [None, None]);
+    }
+
+    #[test]
+    fn stats_compare_index_and_space() {
+        let stats = MeasureStats::between(&U_PATH, 3, 0);
+        assert_eq!(stats.index_distance, 3);
+        assert_eq!(stats.euclidean, 1.0);
+        assert_eq!(stats.stretch, Some(3.0));
+
+        let same = MeasureStats::between(&U_PATH, 1, 1);
+        assert_eq!(same.index_distance, 0);
+        assert_eq!(same.stretch, None);
+    }
+}
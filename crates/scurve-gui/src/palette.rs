@@ -0,0 +1,241 @@
+//! Keyboard-driven command palette with fuzzy search.
+
+use std::cmp::Reverse;
+
+use egui::{Key, Modifiers, epaint::Shadow};
+
+use crate::{
+    i18n::{Language, Msg},
+    theme,
+};
+
+/// A selectable entry in the command palette.
+#[derive(Debug, Clone)]
+pub struct PaletteItem<A> {
+    /// Text shown in the list and matched against the query.
+    pub label: String,
+    /// Action returned when the entry is chosen.
+    pub action: A,
+}
+
+impl<A> PaletteItem<A> {
+    /// Create an entry with the given label and action.
+    pub fn new(label: impl Into<String>, action: A) -> Self {
+        Self {
+            label: label.into(),
+            action,
+        }
+    }
+}
+
+/// State for a modal command palette.
+///
+/// The palette is generic over the action type so callers decide what entries
+/// mean; [`CommandPalette::show`] only handles filtering, navigation and choice.
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    /// Whether the palette is currently visible.
+    pub open: bool,
+    /// Current search text.
+    query: String,
+    /// Index of the highlighted entry within the filtered list.
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Open the palette with an empty query, or close it if already open.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Render the palette and return the chosen action, if any.
+    ///
+    /// Arrow keys move the highlight, Enter chooses it and Escape closes the
+    /// palette. Entries are ranked by [`fuzzy_score`] against the query.
+    pub fn show<A: Clone>(
+        &mut self,
+        ctx: &egui::Context,
+        items: &[PaletteItem<A>],
+        lang: Language,
+    ) -> Option<A> {
+        if !self.open {
+            return None;
+        }
+
+        let mut ranked: Vec<(i32, &PaletteItem<A>)> = items
+            .iter()
+            .filter_map(|item| fuzzy_score(&self.query, &item.label).map(|s| (s, item)))
+            .collect();
+        // Stable sort keeps registration order among equal scores.
+        ranked.sort_by_key(|(score, _)| Reverse(*score));
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if escape {
+            self.toggle();
+            return None;
+        }
+        if down && self.selected + 1 < ranked.len() {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(ranked.len().saturating_sub(1));
+
+        let mut chosen = enter
+            .then(|| {
+                ranked
+                    .get(self.selected)
+                    .map(|(_, item)| item.action.clone())
+            })
+            .flatten();
+
+        let screen_rect = ctx.content_rect();
+        let pos = egui::pos2(
+            screen_rect.center().x - theme::palette::WIDTH * 0.5,
+            screen_rect.min.y + theme::palette::TOP_OFFSET,
+        );
+        let area_response = egui::Area::new(egui::Id::new("command_palette"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(theme::SETTINGS_PANEL_BG)
+                    .stroke(egui::Stroke::new(1.0, theme::BORDER))
+                    .inner_margin(egui::Margin::same(theme::popup::INNER_MARGIN))
+                    .corner_radius(egui::CornerRadius::same(theme::popup::CORNER_RADIUS))
+                    .shadow(Shadow {
+                        offset: theme::shadow::OFFSET,
+                        blur: theme::shadow::BLUR,
+                        spread: theme::shadow::SPREAD,
+                        color: egui::Color32::from_black_alpha(theme::DIALOG_SHADOW_ALPHA),
+                    })
+                    .show(ui, |ui| {
+                        ui.set_width(theme::palette::WIDTH);
+                        let edit = ui.add(
+                            egui::TextEdit::singleline(&mut self.query)
+                                .hint_text(lang.tr(Msg::TypeCommand))
+                                .desired_width(f32::INFINITY),
+                        );
+                        edit.request_focus();
+                        if edit.changed() {
+                            self.selected = 0;
+                        }
+                        ui.add_space(theme::spacing::SMALL);
+
+                        if ranked.is_empty() {
+                            ui.label(
+                                egui::RichText::new(lang.tr(Msg::NoMatchingCommands))
+                                    .size(theme::font_size::INFO)
+                                    .color(theme::TEXT_DIM),
+                            );
+                            return;
+                        }
+
+                        egui::ScrollArea::vertical()
+                            .max_height(theme::palette::MAX_LIST_HEIGHT)
+                            .show(ui, |ui| {
+                                for (idx, (_, item)) in ranked.iter().enumerate() {
+                                    let is_selected = idx == self.selected;
+                                    let response = ui.add(
+                                        egui::Button::selectable(
+                                            is_selected,
+                                            egui::RichText::new(&item.label)
+                                                .size(theme::font_size::LABEL),
+                                        )
+                                        .min_size(egui::vec2(ui.available_width(), 0.0)),
+                                    );
+                                    if is_selected && (up || down) {
+                                        response.scroll_to_me(None);
+                                    }
+                                    if response.clicked() {
+                                        chosen = Some(item.action.clone());
+                                    }
+                                }
+                            });
+                    });
+            });
+
+        let clicked_outside = ctx.input(|i| i.pointer.primary_clicked())
+            && ctx
+                .input(|i| i.pointer.interact_pos())
+                .is_some_and(|p| !area_response.response.rect.contains(p));
+        if chosen.is_some() || clicked_outside {
+            self.toggle();
+        }
+        chosen
+    }
+}
+
+/// Score how well `query` fuzzily matches `candidate`, case-insensitively.
+///
+/// Every non-whitespace query character must appear in `candidate` in order.
+/// Consecutive matches and matches at word starts score higher, while skipped
+/// characters cost a point each. Returns `None` when the query does not match;
+/// an empty query matches everything with a score of zero.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let haystack: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0i32;
+    let mut pos = 0usize;
+    let mut last_match: Option<usize> = None;
+
+    for qc in query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+    {
+        let skipped = haystack.get(pos..)?.iter().position(|&c| c == qc)?;
+        let idx = pos + skipped;
+        score += 1;
+        if last_match.is_some_and(|last| last + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !haystack[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= skipped as i32;
+        last_match = Some(idx);
+        pos = idx + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "Curve: hilbert"), Some(0));
+        assert_eq!(fuzzy_score("   ", ""), Some(0));
+    }
+
+    #[test]
+    fn requires_ordered_subsequence() {
+        assert!(fuzzy_score("hlb", "Curve: hilbert").is_some());
+        assert!(fuzzy_score("HILB", "curve: hilbert").is_some());
+        assert_eq!(fuzzy_score("blh", "Curve: hilbert"), None);
+        assert_eq!(fuzzy_score("xyz", "Curve: hilbert"), None);
+    }
+
+    #[test]
+    fn prefers_contiguous_word_start_matches() {
+        let exact = fuzzy_score("size", "Size: 64").unwrap();
+        let scattered = fuzzy_score("size", "Switch pane: 3D (ize)").unwrap();
+        assert!(exact > scattered);
+
+        let word_start = fuzzy_score("3d", "Pane: 3D").unwrap();
+        let mid_word = fuzzy_score("3d", "Size: 32×32×32 (3D)").unwrap();
+        assert!(word_start >= mid_word);
+    }
+}
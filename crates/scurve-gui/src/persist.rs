@@ -0,0 +1,93 @@
+//! Viewer state kept across restarts in eframe's [`Storage`](eframe::Storage).
+//!
+//! On the web the storage is the page's `localStorage`, so a reload restores
+//! the last curve, pane, settings and interface language. The viewer state is
+//! stored in its [`StateSpec`] text form; state given at launch (e.g. the page
+//! query) is applied on top of it. Stored values that no longer parse are
+//! ignored rather than reported.
+
+use eframe::Storage;
+
+use crate::{i18n::Language, share::StateSpec};
+
+/// Storage key for the viewer state spec.
+const STATE_KEY: &str = "scurve.state";
+/// Storage key for the interface language code.
+const LANGUAGE_KEY: &str = "scurve.language";
+
+/// Settings read back from storage.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Saved {
+    /// Last viewer state, if one was stored.
+    pub state: Option<StateSpec>,
+    /// Last interface language, if one was stored.
+    pub language: Option<Language>,
+}
+
+/// Read the saved settings from `storage`.
+pub fn load(storage: &dyn Storage) -> Saved {
+    Saved {
+        state: storage
+            .get_string(STATE_KEY)
+            .and_then(|text| text.parse().ok()),
+        language: storage
+            .get_string(LANGUAGE_KEY)
+            .and_then(|code| Language::from_locale(&code)),
+    }
+}
+
+/// Write the current settings to `storage`.
+pub fn store(storage: &mut dyn Storage, state: &StateSpec, language: Language) {
+    storage.set_string(STATE_KEY, state.to_string());
+    storage.set_string(LANGUAGE_KEY, language.code().to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::Pane;
+
+    /// In-memory stand-in for the web's `localStorage`.
+    #[derive(Default)]
+    struct MemoryStorage(HashMap<String, String>);
+
+    impl Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let mut storage = MemoryStorage::default();
+        assert_eq!(load(&storage), Saved::default());
+
+        let state = StateSpec {
+            curve: Some("zorder".to_string()),
+            pane: Some(Pane::ThreeD),
+            size3d: Some(16),
+            snake: Some(false),
+            ..StateSpec::default()
+        };
+        store(&mut storage, &state, Language::German);
+        let saved = load(&storage);
+        assert_eq!(saved.state, Some(state));
+        assert_eq!(saved.language, Some(Language::German));
+    }
+
+    #[test]
+    fn unreadable_values_are_ignored() {
+        let mut storage = MemoryStorage::default();
+        storage.set_string(STATE_KEY, "curve=nope".to_string());
+        storage.set_string(LANGUAGE_KEY, "xx".to_string());
+        assert_eq!(load(&storage), Saved::default());
+    }
+}
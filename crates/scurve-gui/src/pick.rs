@@ -0,0 +1,207 @@
+//! Hover picking of curve vertices on the canvas.
+//!
+//! Dense curves put many vertices under a single pixel, so scanning every
+//! point per frame is wasteful. [`ScreenGrid`] buckets screen positions into a
+//! uniform grid; a lookup only inspects the buckets around the pointer.
+//!
+//! [`ScreenGrid`]: crate::pick::ScreenGrid
+
+use std::fmt::Write;
+
+use crate::theme;
+
+/// Uniform bucket grid over screen-space points.
+///
+/// Buckets are stored compactly: `starts[b]..starts[b + 1]` indexes the
+/// points of bucket `b` in `items`.
+#[derive(Debug, Default)]
+pub struct ScreenGrid {
+    /// Screen position of the grid's top-left corner.
+    origin: egui::Pos2,
+    /// Side length of a bucket in points.
+    bucket: f32,
+    /// Number of bucket columns.
+    cols: usize,
+    /// Number of bucket rows.
+    rows: usize,
+    /// Offsets into `items` for each bucket, plus a final end offset.
+    starts: Vec<u32>,
+    /// Point indices grouped by bucket.
+    items: Vec<u32>,
+}
+
+impl ScreenGrid {
+    /// Rebuild the grid over `points` that fall inside `rect`.
+    pub fn rebuild(&mut self, rect: egui::Rect, points: &[egui::Pos2]) {
+        self.origin = rect.min;
+        self.bucket = theme::hover::BUCKET_SIZE;
+        self.cols = (rect.width() / self.bucket).ceil().max(1.0) as usize;
+        self.rows = (rect.height() / self.bucket).ceil().max(1.0) as usize;
+        self.starts.clear();
+        self.starts.resize(self.cols * self.rows + 1, 0);
+        for &p in points {
+            if let Some(b) = self.bucket_of(p) {
+                self.starts[b + 1] += 1;
+            }
+        }
+        for b in 1..self.starts.len() {
+            self.starts[b] += self.starts[b - 1];
+        }
+        self.items.clear();
+        self.items
+            .resize(self.starts[self.starts.len() - 1] as usize, 0);
+        let mut fill = self.starts.clone();
+        for (i, &p) in points.iter().enumerate() {
+            if let Some(b) = self.bucket_of(p) {
+                self.items[fill[b] as usize] = i as u32;
+                fill[b] += 1;
+            }
+        }
+    }
+
+    /// Index of the point nearest to `pos` within `radius`, if any.
+    ///
+    /// Points within [`theme::hover::TIE_DISTANCE`] of the nearest distance
+    /// count as equally near; among those the one with the largest `front`
+    /// value wins, so overlapping 3D vertices resolve to the one in front.
+    pub fn nearest(
+        &self,
+        points: &[egui::Pos2],
+        pos: egui::Pos2,
+        radius: f32,
+        front: impl Fn(usize) -> f32,
+    ) -> Option<usize> {
+        let reach = (radius / self.bucket).ceil() as isize;
+        let col = ((pos.x - self.origin.x) / self.bucket).floor() as isize;
+        let row = ((pos.y - self.origin.y) / self.bucket).floor() as isize;
+        let mut best: Option<(usize, f32)> = None;
+        for r in row - reach..=row + reach {
+            for c in col - reach..=col + reach {
+                if r < 0 || c < 0 || r as usize >= self.rows || c as usize >= self.cols {
+                    continue;
+                }
+                let b = r as usize * self.cols + c as usize;
+                for &i in &self.items[self.starts[b] as usize..self.starts[b + 1] as usize] {
+                    let i = i as usize;
+                    let d = points[i].distance(pos);
+                    if d > radius {
+                        continue;
+                    }
+                    best = match best {
+                        Some((j, bd)) if d > bd + theme::hover::TIE_DISTANCE => Some((j, bd)),
+                        Some((j, bd)) if d >= bd - theme::hover::TIE_DISTANCE => {
+                            if front(i) > front(j) {
+                                Some((i, d.min(bd)))
+                            } else {
+                                Some((j, bd))
+                            }
+                        }
+                        _ => Some((i, d)),
+                    };
+                }
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Bucket containing `p`, if it lies inside the grid.
+    fn bucket_of(&self, p: egui::Pos2) -> Option<usize> {
+        let col = ((p.x - self.origin.x) / self.bucket).floor();
+        let row = ((p.y - self.origin.y) / self.bucket).floor();
+        if col < 0.0 || row < 0.0 || col as usize >= self.cols || row as usize >= self.rows {
+            return None;
+        }
+        Some(row as usize * self.cols + col as usize)
+    }
+}
+
+/// Mark the vertex `index` at screen position `at` and label it with its
+/// curve index and grid coordinates.
+pub fn draw_hover_label<const D: usize>(
+    painter: &egui::Painter,
+    at: egui::Pos2,
+    index: usize,
+    point: &[u32; D],
+) {
+    painter.circle_stroke(
+        at,
+        theme::hover::RING_RADIUS,
+        egui::Stroke::new(1.5, theme::TEXT_PRIMARY),
+    );
+    let mut text = format!("#{index} (");
+    for (axis, c) in point.iter().enumerate() {
+        if axis > 0 {
+            text.push_str(", ");
+        }
+        write!(text, "{c}").unwrap();
+    }
+    text.push(')');
+    painter.text(
+        at + egui::vec2(
+            theme::hover::RING_RADIUS + 4.0,
+            -theme::hover::RING_RADIUS - 4.0,
+        ),
+        egui::Align2::LEFT_BOTTOM,
+        text,
+        egui::FontId::monospace(theme::font_size::INFO),
+        theme::TEXT_PRIMARY,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(points: &[egui::Pos2]) -> ScreenGrid {
+        let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(100.0, 100.0));
+        let mut grid = ScreenGrid::default();
+        grid.rebuild(rect, points);
+        grid
+    }
+
+    #[test]
+    fn snaps_to_the_nearest_vertex_across_buckets() {
+        let points = [
+            egui::pos2(5.0, 5.0),
+            egui::pos2(15.0, 5.0),
+            egui::pos2(90.0, 90.0),
+            egui::pos2(150.0, 5.0),
+        ];
+        let grid = grid(&points);
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(11.0, 6.0), 12.0, |_| 0.0),
+            Some(1)
+        );
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(9.0, 6.0), 12.0, |_| 0.0),
+            Some(0)
+        );
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(50.0, 50.0), 12.0, |_| 0.0),
+            None
+        );
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(99.0, 5.0), 60.0, |_| 0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn overlapping_vertices_prefer_the_front() {
+        let points = [
+            egui::pos2(20.0, 20.0),
+            egui::pos2(20.0, 20.0),
+            egui::pos2(20.2, 20.0),
+        ];
+        let grid = grid(&points);
+        let depth = [0.1, 0.5, 0.9];
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(20.0, 20.0), 5.0, |i| depth[i]),
+            Some(2)
+        );
+        assert_eq!(
+            grid.nearest(&points, egui::pos2(20.0, 20.0), 5.0, |_| 0.0),
+            Some(0)
+        );
+    }
+}
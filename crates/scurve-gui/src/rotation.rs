@@ -0,0 +1,129 @@
+//! Rotation matrices for the 3D view.
+//!
+//! The scene orientation is kept as three Euler angles and turned into a
+//! matrix each frame, so accumulated rotation never drifts away from a proper
+//! rotation the way a repeatedly multiplied matrix would.
+
+/// Row-major 3×3 matrix.
+pub type Mat3 = [[f32; 3]; 3];
+
+/// Axis (or axes) the 3D view spins around while animating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RotationAxis {
+    /// Spin around the horizontal X axis.
+    X,
+    /// Spin around the vertical Y axis.
+    #[default]
+    Y,
+    /// Spin around the depth Z axis.
+    Z,
+    /// Spin around Y while slowly turning around X, tumbling the curve.
+    Tumble,
+}
+
+impl RotationAxis {
+    /// All axis choices, in selector order.
+    pub const ALL: [Self; 4] = [Self::X, Self::Y, Self::Z, Self::Tumble];
+
+    /// Per-axis angular velocity weights for `[x, y, z]`.
+    ///
+    /// The tumble's secondary X rate is irrational relative to Y, so the
+    /// combined motion does not repeat quickly.
+    pub fn weights(self) -> [f32; 3] {
+        match self {
+            Self::X => [1.0, 0.0, 0.0],
+            Self::Y => [0.0, 1.0, 0.0],
+            Self::Z => [0.0, 0.0, 1.0],
+            Self::Tumble => [0.618, 1.0, 0.0],
+        }
+    }
+}
+
+/// Rotation by `angle` radians around the X axis.
+pub fn rotation_x(angle: f32) -> Mat3 {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, -s], [0.0, s, c]]
+}
+
+/// Rotation by `angle` radians around the Y axis.
+pub fn rotation_y(angle: f32) -> Mat3 {
+    let (s, c) = angle.sin_cos();
+    [[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]]
+}
+
+/// Rotation by `angle` radians around the Z axis.
+pub fn rotation_z(angle: f32) -> Mat3 {
+    let (s, c) = angle.sin_cos();
+    [[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+/// Matrix product `a · b` (apply `b` first, then `a`).
+pub fn mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut out = [[0.0; 3]; 3];
+    for (r, row) in out.iter_mut().enumerate() {
+        for (c, cell) in row.iter_mut().enumerate() {
+            *cell = (0..3).map(|k| a[r][k] * b[k][c]).sum();
+        }
+    }
+    out
+}
+
+/// Apply `m` to the vector `v`.
+pub fn apply(m: &Mat3, v: [f32; 3]) -> [f32; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Full view rotation: object spin by `angles` (`[x, y, z]`), then camera `tilt` around X.
+pub fn view_rotation(angles: [f32; 3], tilt: f32) -> Mat3 {
+    let spin = mul(
+        &rotation_y(angles[1]),
+        &mul(&rotation_x(angles[0]), &rotation_z(angles[2])),
+    );
+    mul(&rotation_x(tilt), &spin)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    /// Assert two vectors are equal within floating point tolerance.
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-5, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn quarter_turns_move_basis_vectors() {
+        assert_close(
+            apply(&rotation_x(FRAC_PI_2), [0.0, 1.0, 0.0]),
+            [0.0, 0.0, 1.0],
+        );
+        assert_close(
+            apply(&rotation_y(FRAC_PI_2), [1.0, 0.0, 0.0]),
+            [0.0, 0.0, -1.0],
+        );
+        assert_close(
+            apply(&rotation_z(FRAC_PI_2), [1.0, 0.0, 0.0]),
+            [0.0, 1.0, 0.0],
+        );
+    }
+
+    #[test]
+    fn view_rotation_matches_yaw_then_tilt() {
+        let (yaw, tilt) = (0.7, 0.3);
+        let v = [0.2, -0.5, 0.9];
+        let expected = apply(&rotation_x(tilt), apply(&rotation_y(yaw), v));
+        assert_close(apply(&view_rotation([0.0, yaw, 0.0], tilt), v), expected);
+    }
+
+    #[test]
+    fn rotations_preserve_length() {
+        let m = view_rotation([1.1, -2.3, 0.4], 0.5);
+        let v = [0.3, 0.4, 1.2];
+        let len = |v: [f32; 3]| v.iter().map(|c| c * c).sum::<f32>().sqrt();
+        assert!((len(apply(&m, v)) - len(v)).abs() < 1e-5);
+    }
+}
@@ -1,4 +1,35 @@
-use spacecurve::{curve_from_name, registry};
+use std::{array, sync::Arc, time::Duration};
+
+use spacecurve::{SpaceCurve, clip::Clipped, curve_from_name, metrics, registry};
+use web_time::Instant;
+
+/// Grids with at least this many cells need confirmation before they are built.
+pub const CONFIRM_POINTS: u64 = 32 * 32 * 32;
+
+/// Points cached between checks of the build budget.
+const BUILD_CHUNK: u64 = 4096;
+
+/// Time spent caching points per frame before yielding to the browser.
+///
+/// The web build has no threads, so large curves are cached over several
+/// frames to keep the tab responsive. Native builds cache in one go.
+#[cfg(target_arch = "wasm32")]
+const BUILD_BUDGET: Option<Duration> = Some(Duration::from_millis(8));
+/// Native builds cache every point in one go.
+#[cfg(not(target_arch = "wasm32"))]
+const BUILD_BUDGET: Option<Duration> = None;
+
+/// Cached points of the selected curve with per-segment adjacency.
+#[derive(Clone, Copy)]
+pub struct CachedCurve<'a, const D: usize> {
+    /// Points in curve order.
+    pub points: &'a [[u32; D]],
+    /// Whether segment `i` (point `i` to `i + 1`) joins neighbouring cells.
+    pub adjacent: &'a [bool],
+    /// Neighbourhood stretch per point, or empty until
+    /// [`CurveSelection::ensure_stretch`] has run for this curve.
+    pub stretch: &'a [f32],
+}
 
 /// Shared cache and selection state for 2D/3D curve panes.
 #[derive(Clone)]
@@ -7,6 +38,10 @@ pub struct CurveSelection<const D: usize> {
     pub name: String,
     /// The side length of the grid per axis.
     pub size: u32,
+    /// Large size picked by the user and awaiting confirmation.
+    pub pending_size: Option<u32>,
+    /// Rectangular extent the curve is clipped to, or the full grid when `None`.
+    pub clip: Option<[u32; D]>,
     /// Current offset for the animated snake overlay, in segments.
     pub snake_offset: f32,
     /// Whether the info pane for this curve is open.
@@ -15,10 +50,22 @@ pub struct CurveSelection<const D: usize> {
     cached_name: String,
     /// Cache key: last grid size used to generate `cached_points`.
     cached_size: u32,
+    /// Cache key: last clip extent used to generate `cached_points`.
+    cached_clip: Option<[u32; D]>,
     /// Cached integer points for the currently selected curve and size.
     cached_points: Vec<[u32; D]>,
+    /// Adjacency of each segment of `cached_points`.
+    cached_adjacent: Vec<bool>,
+    /// Neighbourhood stretch of each point in `cached_points`, computed on demand.
+    cached_stretch: Vec<f32>,
     /// Cached curve length for the currently selected curve and size.
-    cached_length: Option<u32>,
+    cached_length: Option<u64>,
+    /// Curve whose points are still being cached, while a build spans frames.
+    building: Option<Arc<dyn SpaceCurve>>,
+    /// Name and size of the last selection whose construction failed.
+    failed: Option<(String, u32)>,
+    /// Construction error not yet collected by [`Self::take_error`].
+    pending_error: Option<String>,
 }
 
 impl<const D: usize> Default for CurveSelection<D> {
@@ -38,73 +85,196 @@ impl<const D: usize> CurveSelection<D> {
         Self {
             name: name.to_string(),
             size: if D == 2 { 64 } else { 8 },
+            pending_size: None,
+            clip: None,
             snake_offset: 0.0,
             info_open: false,
             cached_name: String::new(),
             cached_size: 0,
+            cached_clip: None,
             cached_points: Vec::new(),
+            cached_adjacent: Vec::new(),
+            cached_stretch: Vec::new(),
             cached_length: None,
+            building: None,
+            failed: None,
+            pending_error: None,
+        }
+    }
+
+    /// Switch to `size`, or hold it in [`Self::pending_size`] if the grid
+    /// would have at least [`CONFIRM_POINTS`] cells so the UI can ask first.
+    pub fn request_size(&mut self, size: u32) {
+        let points = u64::from(size).saturating_pow(D as u32);
+        if size != self.size && points >= CONFIRM_POINTS {
+            self.pending_size = Some(size);
+        } else {
+            self.size = size;
+            self.pending_size = None;
         }
     }
 
     /// Reset cached data when the selected curve or size changes.
     fn invalidate_if_changed(&mut self) {
-        if self.cached_name != self.name || self.cached_size != self.size {
+        if self.cached_name != self.name
+            || self.cached_size != self.size
+            || self.cached_clip != self.clip
+        {
             self.cached_points.clear();
+            self.cached_adjacent.clear();
+            self.cached_stretch.clear();
             self.cached_length = None;
+            self.building = None;
         }
     }
 
     /// Ensure the cached curve length is available for the current selection.
-    pub fn ensure_curve_length(&mut self) -> Option<u32> {
+    pub fn ensure_curve_length(&mut self) -> Option<u64> {
         self.invalidate_if_changed();
         if let Some(len) = self.cached_length {
             return Some(len);
         }
         if !self.cached_points.is_empty() {
-            let len = self.cached_points.len() as u32;
+            let len = self.cached_points.len() as u64;
             self.cached_length = Some(len);
             return Some(len);
         }
-        match curve_from_name(&self.name, D as u32, self.size) {
-            Ok(pattern) => {
-                let len = pattern.length();
-                self.cached_length = Some(len);
-                self.cached_name = self.name.clone();
-                self.cached_size = self.size;
-                Some(len)
-            }
-            Err(_) => None,
-        }
+        let pattern = self.build_curve()?;
+        let len = pattern.length();
+        self.cached_length = Some(len);
+        self.cached_name = self.name.clone();
+        self.cached_size = self.size;
+        self.cached_clip = self.clip;
+        Some(len)
     }
 
     /// Ensure the cached points are computed for the current name and size.
-    /// Returns a slice of cached points if successful.
-    pub fn ensure_cached_points(&mut self) -> Option<&[[u32; D]]> {
+    /// Returns the cached points and segment adjacency if successful.
+    ///
+    /// On the web a large curve is cached over several calls; until it is
+    /// complete this returns `None` and [`Self::build_progress`] reports how
+    /// far it got.
+    pub fn ensure_cached_points(&mut self) -> Option<CachedCurve<'_, D>> {
+        self.ensure_cached_points_within(BUILD_BUDGET)
+    }
+
+    /// Fraction of the points cached so far while a build spans frames.
+    pub fn build_progress(&self) -> Option<f32> {
+        self.building
+            .as_ref()
+            .map(|pattern| self.cached_points.len() as f32 / pattern.length() as f32)
+    }
+
+    /// [`Self::ensure_cached_points`], caching for at most `budget` per call
+    /// (without limit when `None`).
+    fn ensure_cached_points_within(
+        &mut self,
+        budget: Option<Duration>,
+    ) -> Option<CachedCurve<'_, D>> {
         self.invalidate_if_changed();
-        if self.cached_name != self.name
-            || self.cached_size != self.size
-            || self.cached_points.is_empty()
+        if self.building.is_none()
+            && (self.cached_name != self.name
+                || self.cached_size != self.size
+                || self.cached_clip != self.clip
+                || self.cached_points.is_empty())
+        {
+            let pattern = self.build_curve()?;
+            let len = pattern.length() as usize;
+            self.cached_points = Vec::with_capacity(len);
+            self.cached_adjacent = Vec::with_capacity(len.saturating_sub(1));
+            self.cached_stretch.clear();
+            self.cached_name = self.name.clone();
+            self.cached_size = self.size;
+            self.cached_clip = self.clip;
+            self.cached_length = Some(pattern.length());
+            self.building = Some(Arc::from(pattern));
+        }
+        if !self.advance_build(budget) {
+            return None;
+        }
+        Some(CachedCurve {
+            points: &self.cached_points,
+            adjacent: &self.cached_adjacent,
+            stretch: &self.cached_stretch,
+        })
+    }
+
+    /// Cache further points of the curve being built, in chunks of
+    /// [`BUILD_CHUNK`], until it is complete or `budget` has elapsed.
+    ///
+    /// Returns whether every point is cached.
+    fn advance_build(&mut self, budget: Option<Duration>) -> bool {
+        let Some(pattern) = self.building.clone() else {
+            return true;
+        };
+        let started = Instant::now();
+        let len = pattern.length();
+        loop {
+            let cached = self.cached_points.len() as u64;
+            if cached == 0 {
+                let first = pattern.point(0);
+                self.cached_points.push(array::from_fn(|d| first[d]));
+                continue;
+            }
+            let end = (cached + BUILD_CHUNK).min(len);
+            for segment in pattern.segments_in(cached - 1..end) {
+                self.cached_points.push(array::from_fn(|d| segment.b[d]));
+                self.cached_adjacent.push(segment.adjacent);
+            }
+            if end >= len {
+                self.building = None;
+                return true;
+            }
+            if budget.is_some_and(|budget| started.elapsed() >= budget) {
+                return false;
+            }
+        }
+    }
+
+    /// Compute the neighbourhood stretch of the cached points if it is missing.
+    ///
+    /// Stretch is costly for large grids, so it is only computed for views that
+    /// color by it and then kept until the curve changes.
+    pub fn ensure_stretch(&mut self) {
+        let Some(curve) = self.ensure_cached_points() else {
+            return;
+        };
+        if curve.stretch.is_empty()
+            && let Some(pattern) = self.build_curve()
         {
-            if let Ok(pattern) = curve_from_name(&self.name, D as u32, self.size) {
-                let mut pts = Vec::with_capacity(pattern.length() as usize);
-                for i in 0..pattern.length() {
-                    let p = pattern.point(i);
-                    let mut arr = [0u32; D];
-                    for d in 0..D {
-                        arr[d] = p[d];
-                    }
-                    pts.push(arr);
+            self.cached_stretch = metrics::dilation_map(&*pattern);
+        }
+    }
+
+    /// Take the most recent curve construction error, if one has not been reported yet.
+    pub fn take_error(&mut self) -> Option<String> {
+        self.pending_error.take()
+    }
+
+    /// Construct the selected curve, clipped to [`Self::clip`] when set,
+    /// recording a failure once per name and size.
+    fn build_curve(&mut self) -> Option<Box<dyn SpaceCurve>> {
+        let built = curve_from_name(&self.name, D as u32, self.size).and_then(|pattern| {
+            Ok(match self.clip {
+                None => pattern,
+                Some(extent) => Box::new(Clipped::new(pattern, &extent)?),
+            })
+        });
+        match built {
+            Ok(pattern) => {
+                self.failed = None;
+                Some(pattern)
+            }
+            Err(err) => {
+                let key = (self.name.clone(), self.size);
+                if self.failed.as_ref() != Some(&key) {
+                    self.pending_error =
+                        Some(format!("{} ({D}D, size {}): {err}", self.name, self.size));
+                    self.failed = Some(key);
                 }
-                self.cached_points = pts;
-                self.cached_name = self.name.clone();
-                self.cached_size = self.size;
-                self.cached_length = Some(pattern.length());
-            } else {
-                return None;
+                None
             }
         }
-        Some(&self.cached_points)
     }
 }
 
@@ -112,3 +282,47 @@ impl<const D: usize> CurveSelection<D> {
 pub type SelectedCurve = CurveSelection<2>;
 /// 3D selection state.
 pub type Selected3DCurve = CurveSelection<3>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_sizes_wait_for_confirmation() {
+        let mut selection = Selected3DCurve::with_name("hilbert");
+        selection.request_size(16);
+        assert_eq!((selection.size, selection.pending_size), (16, None));
+        selection.request_size(32);
+        assert_eq!((selection.size, selection.pending_size), (16, Some(32)));
+        selection.request_size(4);
+        assert_eq!((selection.size, selection.pending_size), (4, None));
+
+        let mut flat = SelectedCurve::with_name("hilbert");
+        flat.request_size(128);
+        assert_eq!((flat.size, flat.pending_size), (128, None));
+    }
+
+    #[test]
+    fn builds_spread_over_calls_match_a_full_build() {
+        let mut chunked = Selected3DCurve::with_name("hilbert");
+        chunked.size = 32;
+        let mut calls = 0;
+        while chunked
+            .ensure_cached_points_within(Some(Duration::ZERO))
+            .is_none()
+        {
+            let progress = chunked.build_progress().unwrap();
+            assert!(progress > 0.0 && progress < 1.0, "{progress}");
+            calls += 1;
+        }
+        assert_eq!(calls, 7);
+        assert_eq!(chunked.build_progress(), None);
+
+        let mut full = Selected3DCurve::with_name("hilbert");
+        full.size = 32;
+        let expected = full.ensure_cached_points_within(None).unwrap();
+        let curve = chunked.ensure_cached_points_within(None).unwrap();
+        assert_eq!(curve.points, expected.points);
+        assert_eq!(curve.adjacent, expected.adjacent);
+    }
+}
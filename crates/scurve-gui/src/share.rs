@@ -0,0 +1,250 @@
+//! Compact, shareable text form of the viewer state.
+//!
+//! The spec is a URL query string such as
+//! `curve=hilbert&pane=2d&size=64&size3d=8&snake=1`, so the same text works on
+//! the clipboard, as `scurve gui --state <spec>` and as the web page query.
+//! Every key is optional when parsing; missing keys leave the current value
+//! untouched and unknown keys (e.g. `dev`) are ignored.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use spacecurve::registry;
+
+use crate::{AppState, Pane, Selected3DCurve, SelectedCurve, SharedSettings};
+
+/// Viewer state that can be captured, shared and re-applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateSpec {
+    /// Curve key from the registry.
+    pub curve: Option<String>,
+    /// Active pane.
+    pub pane: Option<Pane>,
+    /// 2D grid size.
+    pub size: Option<u32>,
+    /// 3D grid size.
+    pub size3d: Option<u32>,
+    /// Main curve opacity (0.0–1.0).
+    pub opacity: Option<f32>,
+    /// Draw the glow pass under curve strokes.
+    pub glow: Option<bool>,
+    /// Draw long jumps on the main curve.
+    pub long_jumps: Option<bool>,
+    /// Snake overlay enabled.
+    pub snake: Option<bool>,
+    /// Snake length as a percentage of curve length.
+    pub snake_length: Option<f32>,
+    /// Snake speed in segments per second.
+    pub snake_speed: Option<f32>,
+    /// Draw long jumps on the snake.
+    pub snake_long_jumps: Option<bool>,
+    /// 3D rotation speed (0–100).
+    pub spin: Option<f32>,
+}
+
+impl StateSpec {
+    /// Capture the complete current state.
+    pub fn capture(
+        app_state: &AppState,
+        shared: &SharedSettings,
+        selected_curve: &SelectedCurve,
+        selected_3d_curve: &Selected3DCurve,
+    ) -> Self {
+        let curve = match app_state.current_pane {
+            Pane::TwoD => &selected_curve.name,
+            Pane::ThreeD => &selected_3d_curve.name,
+        };
+        Self {
+            curve: Some(curve.clone()),
+            pane: Some(app_state.current_pane),
+            size: Some(selected_curve.size),
+            size3d: Some(selected_3d_curve.size),
+            opacity: Some(shared.curve_opacity),
+            glow: Some(shared.curve_glow),
+            long_jumps: Some(shared.curve_long_jumps),
+            snake: Some(shared.snake_enabled),
+            snake_length: Some(shared.snake_length),
+            snake_speed: Some(shared.snake_speed),
+            snake_long_jumps: Some(shared.snake_long_jumps),
+            spin: Some(shared.spin_speed),
+        }
+    }
+
+    /// Apply every field present in the spec.
+    pub fn apply(
+        &self,
+        app_state: &mut AppState,
+        shared: &mut SharedSettings,
+        selected_curve: &mut SelectedCurve,
+        selected_3d_curve: &mut Selected3DCurve,
+    ) {
+        if let Some(curve) = &self.curve {
+            selected_curve.name = curve.clone();
+            selected_3d_curve.name = curve.clone();
+        }
+        if let Some(pane) = self.pane {
+            app_state.current_pane = pane;
+        }
+        if let Some(size) = self.size {
+            selected_curve.size = size;
+        }
+        if let Some(size) = self.size3d {
+            selected_3d_curve.size = size;
+        }
+        if let Some(v) = self.opacity {
+            shared.curve_opacity = v.clamp(0.0, 1.0);
+        }
+        if let Some(v) = self.glow {
+            shared.curve_glow = v;
+        }
+        if let Some(v) = self.long_jumps {
+            shared.curve_long_jumps = v;
+        }
+        if let Some(v) = self.snake {
+            shared.snake_enabled = v;
+        }
+        if let Some(v) = self.snake_length {
+            shared.snake_length = v.clamp(0.0, 50.0);
+        }
+        if let Some(v) = self.snake_speed {
+            shared.snake_speed = v.clamp(1.0, 200.0);
+        }
+        if let Some(v) = self.snake_long_jumps {
+            shared.snake_long_jumps = v;
+        }
+        if let Some(v) = self.spin {
+            shared.spin_speed = v.clamp(0.0, 100.0);
+        }
+    }
+}
+
+impl Display for StateSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pane = self.pane.map(|p| match p {
+            Pane::TwoD => "2d",
+            Pane::ThreeD => "3d",
+        });
+        let flag = |b: Option<bool>| b.map(|b| if b { "1" } else { "0" });
+        let pairs: [(&str, Option<String>); 12] = [
+            ("curve", self.curve.clone()),
+            ("pane", pane.map(str::to_string)),
+            ("size", self.size.map(|v| v.to_string())),
+            ("size3d", self.size3d.map(|v| v.to_string())),
+            ("opacity", self.opacity.map(|v| format!("{v:.2}"))),
+            ("glow", flag(self.glow).map(str::to_string)),
+            ("jumps", flag(self.long_jumps).map(str::to_string)),
+            ("snake", flag(self.snake).map(str::to_string)),
+            ("snake_len", self.snake_length.map(|v| format!("{v}"))),
+            ("snake_speed", self.snake_speed.map(|v| format!("{v}"))),
+            (
+                "snake_jumps",
+                flag(self.snake_long_jumps).map(str::to_string),
+            ),
+            ("spin", self.spin.map(|v| format!("{v}"))),
+        ];
+        let mut first = true;
+        for (key, value) in pairs {
+            let Some(value) = value else {
+                continue;
+            };
+            if !first {
+                f.write_str("&")?;
+            }
+            write!(f, "{key}={value}")?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StateSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut spec = Self::default();
+        let query = s.trim().trim_start_matches('?');
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "curve" => {
+                    if registry::find(value).is_none() {
+                        return Err(format!("unknown curve '{value}'"));
+                    }
+                    spec.curve = Some(value.to_string());
+                }
+                "pane" => {
+                    spec.pane = Some(match value {
+                        "2d" => Pane::TwoD,
+                        "3d" => Pane::ThreeD,
+                        _ => return Err(format!("invalid pane '{value}', expected 2d or 3d")),
+                    });
+                }
+                "size" => spec.size = Some(parse_value(key, value)?),
+                "size3d" => spec.size3d = Some(parse_value(key, value)?),
+                "opacity" => spec.opacity = Some(parse_value(key, value)?),
+                "glow" => spec.glow = Some(parse_flag(key, value)?),
+                "jumps" => spec.long_jumps = Some(parse_flag(key, value)?),
+                "snake" => spec.snake = Some(parse_flag(key, value)?),
+                "snake_len" => spec.snake_length = Some(parse_value(key, value)?),
+                "snake_speed" => spec.snake_speed = Some(parse_value(key, value)?),
+                "snake_jumps" => spec.snake_long_jumps = Some(parse_flag(key, value)?),
+                "spin" => spec.spin = Some(parse_value(key, value)?),
+                _ => {}
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// Parse a numeric value, naming the key in the error.
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for '{key}'"))
+}
+
+/// Parse a boolean flag written as `1`/`0`, `true`/`false`, `on`/`off` or empty (true).
+fn parse_flag(key: &str, value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("invalid flag '{value}' for '{key}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_full_state() {
+        let spec = StateSpec::capture(
+            &AppState::default(),
+            &SharedSettings::default(),
+            &SelectedCurve::with_name("hilbert"),
+            &Selected3DCurve::with_name("hilbert"),
+        );
+        let text = spec.to_string();
+        assert!(text.starts_with("curve=hilbert&pane=2d&size=64&size3d=8"));
+        assert_eq!(text.parse::<StateSpec>().unwrap(), spec);
+    }
+
+    #[test]
+    fn partial_specs_and_url_queries_parse() {
+        let spec: StateSpec = "?curve=zorder&pane=3d&dev&snake=off".parse().unwrap();
+        assert_eq!(spec.curve.as_deref(), Some("zorder"));
+        assert_eq!(spec.pane, Some(Pane::ThreeD));
+        assert_eq!(spec.snake, Some(false));
+        assert_eq!(spec.size, None);
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!("curve=nope".parse::<StateSpec>().is_err());
+        assert!("pane=4d".parse::<StateSpec>().is_err());
+        assert!("size=big".parse::<StateSpec>().is_err());
+        assert!("snake=maybe".parse::<StateSpec>().is_err());
+    }
+}
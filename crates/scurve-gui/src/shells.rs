@@ -0,0 +1,48 @@
+//! Alternating shell coloring for onion-family curves.
+//!
+//! Onion curves peel the grid in concentric shells (see
+//! [`spacecurve::curves::onion::shell_level`]); tinting every other shell makes
+//! that layering visible in both panes.
+
+use spacecurve::{
+    curves::{hairyonion, onion},
+    registry::CurveId,
+};
+
+/// Maps a grid side and a point on that grid to the point's shell level.
+pub type ShellLevel = fn(u32, &[u32]) -> u32;
+
+/// Shell level function for the curve called `name`, if it is built from shells.
+pub fn shell_level_fn(name: &str) -> Option<ShellLevel> {
+    match name.parse::<CurveId>().ok()? {
+        CurveId::Onion => Some(onion::shell_level),
+        CurveId::HairyOnion => Some(hairyonion::shell_level),
+        _ => None,
+    }
+}
+
+/// Fill `out` with the shell level of each of `points` on a grid of side `size`.
+pub fn shell_levels<const D: usize>(
+    level: ShellLevel,
+    size: u32,
+    points: &[[u32; D]],
+    out: &mut Vec<u32>,
+) {
+    out.clear();
+    out.extend(points.iter().map(|p| level(size, p)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_onion_curves_have_shells() {
+        let level = shell_level_fn("onion").unwrap();
+        let mut levels = vec![7];
+        shell_levels(level, 4, &[[0, 0], [1, 1], [2, 1]], &mut levels);
+        assert_eq!(levels, vec![0, 1, 1]);
+        assert!(shell_level_fn("hairyonion").is_some());
+        assert!(shell_level_fn("hilbert").is_none());
+    }
+}
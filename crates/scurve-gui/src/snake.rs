@@ -1,26 +1,9 @@
-// --- Adjacency helpers ---
-
-/// Check if two 2D points are adjacent (Manhattan distance <= 1).
-#[inline]
-pub fn is_adjacent_2d(a: &[u32; 2], b: &[u32; 2]) -> bool {
-    let dx = (a[0] as i32 - b[0] as i32).abs();
-    let dy = (a[1] as i32 - b[1] as i32).abs();
-    dx + dy <= 1
-}
-
-/// Check if two 3D points are adjacent (Manhattan distance <= 1).
-#[inline]
-pub fn is_adjacent_3d(a: &[u32; 3], b: &[u32; 3]) -> bool {
-    let dx = (a[0] as i32 - b[0] as i32).abs();
-    let dy = (a[1] as i32 - b[1] as i32).abs();
-    let dz = (a[2] as i32 - b[2] as i32).abs();
-    dx + dy + dz <= 1
-}
+use spacecurve::wrap::CyclicRange;
 
 /// Advance the snake offset by `increment`, wrapping at `curve_length`.
 ///
 /// Returns the new offset value. If `curve_length` is zero or None, returns 0.0.
-pub fn advance_snake_offset(offset: f32, increment: f32, curve_length: Option<u32>) -> f32 {
+pub fn advance_snake_offset(offset: f32, increment: f32, curve_length: Option<u64>) -> f32 {
     let Some(len) = curve_length else {
         return offset + increment;
     };
@@ -40,7 +23,7 @@ pub fn advance_snake_offset(offset: f32, increment: f32, curve_length: Option<u3
 pub fn calculate_snake_segments(
     snake_offset: f32,
     snake_length_percent: f32,
-    curve_length: u32,
+    curve_length: u64,
 ) -> Vec<usize> {
     let mut segments = Vec::new();
     fill_snake_segments(
@@ -57,7 +40,7 @@ pub fn fill_snake_segments(
     out: &mut Vec<usize>,
     snake_offset: f32,
     snake_length_percent: f32,
-    curve_length: u32,
+    curve_length: u64,
 ) {
     out.clear();
 
@@ -65,18 +48,15 @@ pub fn fill_snake_segments(
         return;
     }
 
-    let start_offset = snake_offset as u32;
-    let snake_length = ((snake_length_percent / 100.0) * curve_length as f32).round() as u32;
+    let start_offset = snake_offset as u64;
+    let snake_length = ((snake_length_percent / 100.0) * curve_length as f32).round() as u64;
     let snake_length = snake_length.max(1);
 
-    if out.capacity() < snake_length as usize {
-        out.reserve(snake_length as usize - out.capacity());
-    }
-
-    for i in 0..snake_length {
-        let segment_index = (start_offset + i) % curve_length;
-        out.push(segment_index as usize);
-    }
+    out.extend(
+        CyclicRange::new(curve_length, start_offset, snake_length)
+            .into_iter()
+            .map(|i| i as usize),
+    );
 }
 
 /// Build an O(1) membership mask for fast neighbour lookups without allocation.
@@ -1,9 +1,32 @@
 //! State management for the GUI application.
 
+use spacecurve::registry;
+
 use crate::{
     Pane, Selected3DCurve, SelectedCurve, SharedSettings, snake::advance_snake_offset, theme,
 };
 
+/// Source of the per-frame time step that drives animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationClock {
+    /// Elapsed wall-clock time between frames.
+    #[default]
+    Wall,
+    /// A synthetic clock advancing exactly `1 / fps` seconds per frame, so
+    /// captured frames are reproducible regardless of rendering speed.
+    Fixed(u32),
+}
+
+impl AnimationClock {
+    /// Animation time step for a frame that took `wall_delta` seconds.
+    pub fn step(self, wall_delta: f32) -> f32 {
+        match self {
+            Self::Wall => wall_delta,
+            Self::Fixed(fps) => 1.0 / fps.max(1) as f32,
+        }
+    }
+}
+
 /// Logic controller for updating application state.
 pub struct AnimationController;
 
@@ -26,7 +49,10 @@ impl AnimationController {
         // Convert 0-100 scale to actual rotation speed using base speed
         let actual_rotation_speed =
             theme::animation::BASE_ROTATION_SPEED * (shared_settings.spin_speed / 100.0);
-        app_state.rotation_angle += delta * actual_rotation_speed;
+        let weights = shared_settings.rotation_axis.weights();
+        for (angle, weight) in app_state.rotation_angles.iter_mut().zip(weights) {
+            *angle += delta * actual_rotation_speed * weight;
+        }
 
         // Update snake animation timing
         app_state.snake_time += delta;
@@ -52,20 +78,29 @@ impl AnimationController {
     /// Synchronize selection between 2D and 3D panes.
     ///
     /// Propagates the selection from the active pane to the inactive pane,
-    /// provided the curve name is valid in the target context.
+    /// provided the curve name is valid in the target context. When
+    /// `lock_selections` is false each pane keeps its own last-used curve.
     pub fn sync_panes(
         current_pane: Pane,
+        lock_selections: bool,
         selected_curve: &mut SelectedCurve,
         selected_3d_curve: &mut Selected3DCurve,
         available_curves: &[&str],
     ) {
-        let is_supported = |name: &str| available_curves.contains(&name);
+        if !lock_selections {
+            return;
+        }
+
+        let is_supported = |name: &str, dimension: u32| {
+            available_curves.contains(&name)
+                && registry::find(name).is_some_and(|entry| entry.accepts_dimension(dimension))
+        };
 
         match current_pane {
             Pane::TwoD => {
                 if selected_3d_curve.name != selected_curve.name {
                     // Ensure name is valid for 3D
-                    if is_supported(&selected_curve.name) {
+                    if is_supported(&selected_curve.name, 3) {
                         selected_3d_curve.name = selected_curve.name.clone();
                     }
                 }
@@ -73,7 +108,7 @@ impl AnimationController {
             Pane::ThreeD => {
                 if selected_curve.name != selected_3d_curve.name {
                     // Ensure name is valid for 2D
-                    if is_supported(&selected_3d_curve.name) {
+                    if is_supported(&selected_3d_curve.name, 2) {
                         selected_curve.name = selected_3d_curve.name.clone();
                     }
                 }
@@ -81,3 +116,16 @@ impl AnimationController {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_clock_ignores_wall_time() {
+        assert_eq!(AnimationClock::Wall.step(0.25), 0.25);
+        assert_eq!(AnimationClock::Fixed(50).step(0.25), 0.02);
+        assert_eq!(AnimationClock::Fixed(50).step(0.0), 0.02);
+        assert_eq!(AnimationClock::Fixed(0).step(0.1), 1.0);
+    }
+}
@@ -0,0 +1,234 @@
+//! Headless driver that renders app frames to images for integration tests
+//! and recordings.
+//!
+//! The harness feeds synthetic input to [`ScurveApp`], tessellates the output
+//! and rasterizes it on the CPU, so golden-image tests and `scurve record` run
+//! without a window or GPU. Time advances by a fixed step per frame, keeping
+//! captures deterministic.
+
+use std::{collections::HashMap, mem, ops::Range, sync::Arc};
+
+use eframe::App as _;
+use egui::{
+    Color32, ColorImage, Context, Event, Pos2, RawInput, Rect, TextureId, Vec2, ViewportCommand,
+    ViewportId,
+    epaint::{ClippedPrimitive, ImageData, Primitive, Vertex},
+};
+
+use crate::{AnimationClock, GuiOptions, ScurveApp, theme};
+
+/// Runs a [`ScurveApp`] headlessly and returns each rendered frame.
+pub struct Harness {
+    /// egui context shared by all frames.
+    ctx: Context,
+    /// Application under test.
+    app: ScurveApp,
+    /// Integration stub handed to `App::update`.
+    frame: eframe::Frame,
+    /// Window size in points.
+    size: Vec2,
+    /// Seconds between frames.
+    dt: f32,
+    /// Number of frames rendered so far.
+    frames: u64,
+    /// Textures uploaded by egui, keyed by id.
+    textures: HashMap<TextureId, ColorImage>,
+    /// Events queued for the next frame (e.g. screenshot replies).
+    pending: Vec<Event>,
+}
+
+impl Harness {
+    /// Create a harness for an app built from `options` with a window of
+    /// `size` points.
+    ///
+    /// Frames are spaced by the options' fixed clock, or by
+    /// [`theme::animation::CAPTURE_FPS`] when it is the wall clock.
+    pub fn new(options: GuiOptions, size: impl Into<Vec2>) -> Self {
+        let fps = match options.clock {
+            AnimationClock::Fixed(fps) => fps.max(1),
+            AnimationClock::Wall => theme::animation::CAPTURE_FPS,
+        };
+        let ctx = Context::default();
+        let app =
+            ScurveApp::with_options(&eframe::CreationContext::_new_kittest(ctx.clone()), options);
+        Self {
+            ctx,
+            app,
+            frame: eframe::Frame::_new_kittest(),
+            size: size.into(),
+            dt: 1.0 / fps as f32,
+            frames: 0,
+            textures: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Run `frames` frames, delivering `events` with the first, and return the
+    /// rendered images in order.
+    pub fn run(&mut self, frames: usize, events: Vec<Event>) -> Vec<ColorImage> {
+        let mut events = Some(events);
+        (0..frames)
+            .map(|_| self.step(events.take().unwrap_or_default()))
+            .collect()
+    }
+
+    /// Render a single frame with the given input events.
+    pub fn step(&mut self, events: Vec<Event>) -> ColorImage {
+        let dt = self.dt;
+        let mut queued = mem::take(&mut self.pending);
+        queued.extend(events);
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.size)),
+            time: Some(self.frames as f64 * f64::from(dt)),
+            predicted_dt: dt,
+            events: queued,
+            ..RawInput::default()
+        };
+        self.frames += 1;
+
+        let (app, frame) = (&mut self.app, &mut self.frame);
+        let output = self.ctx.run(input, |ctx| app.update(ctx, frame));
+
+        for (id, delta) in output.textures_delta.set {
+            self.apply_texture(id, &delta.image, delta.pos);
+        }
+        let primitives = self.ctx.tessellate(output.shapes, output.pixels_per_point);
+        let image = Arc::new(self.rasterize(&primitives, output.pixels_per_point));
+        for id in output.textures_delta.free {
+            self.textures.remove(&id);
+        }
+
+        let commands = output
+            .viewport_output
+            .get(&ViewportId::ROOT)
+            .map(|viewport| viewport.commands.as_slice())
+            .unwrap_or_default();
+        for command in commands {
+            if let ViewportCommand::Screenshot(user_data) = command {
+                self.pending.push(Event::Screenshot {
+                    viewport_id: ViewportId::ROOT,
+                    user_data: user_data.clone(),
+                    image: image.clone(),
+                });
+            }
+        }
+        Arc::unwrap_or_clone(image)
+    }
+
+    /// Apply a full or partial texture upload.
+    fn apply_texture(&mut self, id: TextureId, data: &ImageData, pos: Option<[usize; 2]>) {
+        let ImageData::Color(patch) = data;
+        let Some([x0, y0]) = pos else {
+            self.textures.insert(id, (**patch).clone());
+            return;
+        };
+        let Some(texture) = self.textures.get_mut(&id) else {
+            return;
+        };
+        let [width, height] = patch.size;
+        for y in 0..height {
+            let dst = (y0 + y) * texture.size[0] + x0;
+            texture.pixels[dst..dst + width]
+                .copy_from_slice(&patch.pixels[y * width..(y + 1) * width]);
+        }
+    }
+
+    /// Rasterize tessellated primitives over the app's clear color.
+    fn rasterize(&self, primitives: &[ClippedPrimitive], pixels_per_point: f32) -> ColorImage {
+        let clear = self.app.clear_color(&self.ctx.style().visuals);
+        let [red, green, blue, alpha] = clear.map(|c| (c * 255.0).round() as u8);
+        let size_px = self.size * pixels_per_point;
+        let mut image = ColorImage::filled(
+            [size_px.x.round() as usize, size_px.y.round() as usize],
+            Color32::from_rgba_unmultiplied(red, green, blue, alpha),
+        );
+        for primitive in primitives {
+            let Primitive::Mesh(mesh) = &primitive.primitive else {
+                continue;
+            };
+            let texture = self.textures.get(&mesh.texture_id);
+            let clip = primitive.clip_rect * pixels_per_point;
+            for tri in mesh.indices.chunks_exact(3) {
+                let vertices = [0, 1, 2].map(|k| &mesh.vertices[tri[k] as usize]);
+                fill_triangle(&mut image, texture, clip, vertices, pixels_per_point);
+            }
+        }
+        image
+    }
+}
+
+/// Fill one triangle, modulating vertex colors by the texture and blending
+/// premultiplied colors over `image` within `clip` (in pixels).
+fn fill_triangle(
+    image: &mut ColorImage,
+    texture: Option<&ColorImage>,
+    clip: Rect,
+    vertices: [&Vertex; 3],
+    pixels_per_point: f32,
+) {
+    let [a, b, c] = vertices.map(|v| v.pos.to_vec2() * pixels_per_point);
+    let area = edge(a, b, c);
+    if area == 0.0 {
+        return;
+    }
+    let bounds = Rect::from_points(&[a.to_pos2(), b.to_pos2(), c.to_pos2()]).intersect(clip);
+    let x_range = pixel_span(bounds.min.x, bounds.max.x, image.size[0]);
+    let y_range = pixel_span(bounds.min.y, bounds.max.y, image.size[1]);
+    for y in y_range {
+        for x in x_range.clone() {
+            let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let weights = [edge(b, c, p), edge(c, a, p), edge(a, b, p)].map(|w| w / area);
+            if weights.iter().any(|&w| w < 0.0) {
+                continue;
+            }
+            let src = shade(vertices, weights, texture);
+            let dst = &mut image.pixels[y * image.size[0] + x];
+            *dst = blend(src, *dst);
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`.
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Pixel indices whose centers may fall in `min..max`, clamped to `len`.
+fn pixel_span(min: f32, max: f32, len: usize) -> Range<usize> {
+    let start = (min - 0.5).ceil().max(0.0) as usize;
+    let end = ((max - 0.5).floor() + 1.0).clamp(0.0, len as f32) as usize;
+    start..end.max(start)
+}
+
+/// Interpolated vertex color times the nearest texel, premultiplied.
+fn shade(vertices: [&Vertex; 3], weights: [f32; 3], texture: Option<&ColorImage>) -> [f32; 4] {
+    let mut color = [0.0; 4];
+    let mut uv = Vec2::ZERO;
+    for (vertex, weight) in vertices.iter().zip(weights) {
+        for (channel, value) in color.iter_mut().zip(vertex.color.to_array()) {
+            *channel += f32::from(value) * weight;
+        }
+        uv += vertex.uv.to_vec2() * weight;
+    }
+    if let Some(texture) = texture {
+        let [width, height] = texture.size;
+        let tx = ((uv.x * width as f32) as usize).min(width.saturating_sub(1));
+        let ty = ((uv.y * height as f32) as usize).min(height.saturating_sub(1));
+        let texel = texture.pixels[ty * width + tx].to_array();
+        for (channel, value) in color.iter_mut().zip(texel) {
+            *channel *= f32::from(value) / 255.0;
+        }
+    }
+    color
+}
+
+/// Composite a premultiplied source color over `dst`.
+fn blend(src: [f32; 4], dst: Color32) -> Color32 {
+    let keep = 1.0 - src[3] / 255.0;
+    let [r, g, b, a] = [0, 1, 2, 3].map(|k| {
+        (src[k] + f32::from(dst.to_array()[k]) * keep)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    });
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
@@ -44,6 +44,16 @@ pub mod accent_color {
     pub const B: u8 = 0xf6;
 }
 
+/// Color (solar amber) of alternate shells in onion shell coloring.
+pub mod shell_color {
+    /// Red component.
+    pub const R: u8 = 0xff;
+    /// Green component.
+    pub const G: u8 = 0xc8;
+    /// Blue component.
+    pub const B: u8 = 0x3d;
+}
+
 /// Primary text color - crisp cool white.
 pub const TEXT_PRIMARY: Color32 = Color32::from_rgb(0xe6, 0xed, 0xff);
 
@@ -98,6 +108,18 @@ pub const BUTTON_PLAY: Color32 = Color32::from_rgb(0x0f, 0x6e, 0xa8);
 /// Pause button color - ultraviolet violet fill for strong contrast.
 pub const BUTTON_PAUSE: Color32 = Color32::from_rgb(0x4a, 0x14, 0x63);
 
+/// Error accent for toast borders and titles - hot neon red.
+pub const TOAST_ERROR: Color32 = Color32::from_rgb(0xff, 0x4d, 0x6d);
+
+/// Measurement tool path and markers - electric amber.
+pub const MEASURE: Color32 = Color32::from_rgb(0xff, 0xc8, 0x3d);
+
+/// Picked long jump and its endpoint markers - hot neon red.
+pub const JUMP_PICK: Color32 = Color32::from_rgb(0xff, 0x4d, 0x6d);
+
+/// Index range highlight fill - accent magenta at ~28% alpha (premultiplied).
+pub const RANGE_HIGHLIGHT: Color32 = Color32::from_rgba_premultiplied(0x48, 0x16, 0x45, 0x48);
+
 /// Dimming overlay for modal backgrounds.
 pub const MODAL_DIM_ALPHA: u8 = 180;
 
@@ -107,15 +129,68 @@ pub const POPUP_SHADOW_ALPHA: u8 = 140;
 /// Shadow color alpha for the About dialog.
 pub const DIALOG_SHADOW_ALPHA: u8 = 160;
 
+/// Canvas color presets shared with the CLI so rendered maps match GUI screenshots.
+pub mod preset {
+    use egui::Color32;
+
+    use super::{CANVAS_BACKGROUND, accent_color, curve_color};
+
+    /// Coordinated canvas colors for one theme.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Preset {
+        /// Name used on the command line.
+        pub key: &'static str,
+        /// Canvas background.
+        pub background: Color32,
+        /// Curve stroke.
+        pub curve: Color32,
+        /// Snake and highlight color.
+        pub accent: Color32,
+    }
+
+    /// The GUI's own Neon Grid canvas.
+    pub const NEON: Preset = Preset {
+        key: "neon",
+        background: CANVAS_BACKGROUND,
+        curve: Color32::from_rgb(curve_color::R, curve_color::G, curve_color::B),
+        accent: Color32::from_rgb(accent_color::R, accent_color::G, accent_color::B),
+    };
+
+    /// Navy ink on warm off-white, for printed or light-mode documents.
+    pub const PAPER: Preset = Preset {
+        key: "paper",
+        background: Color32::from_rgb(0xfb, 0xf8, 0xf1),
+        curve: Color32::from_rgb(0x1f, 0x2a, 0x44),
+        accent: Color32::from_rgb(0xd1, 0x49, 0x5b),
+    };
+
+    /// Black on white with a grey accent.
+    pub const MONO: Preset = Preset {
+        key: "mono",
+        background: Color32::WHITE,
+        curve: Color32::BLACK,
+        accent: Color32::from_gray(0x80),
+    };
+
+    /// Every preset, in presentation order.
+    pub const ALL: [Preset; 3] = [NEON, PAPER, MONO];
+
+    /// Look up a preset by key, ignoring ASCII case.
+    pub fn find(key: &str) -> Option<Preset> {
+        ALL.into_iter()
+            .find(|preset| preset.key.eq_ignore_ascii_case(key))
+    }
+}
+
 // =============================================================================
 // FONTS
 // =============================================================================
 
 /// Embedded Orbitron Regular font bytes (OFL licensed).
-const FONT_ORBITRON_REGULAR: &[u8] = include_bytes!("../assets/fonts/Orbitron-Regular.ttf");
+pub const FONT_ORBITRON_REGULAR: &[u8] = include_bytes!("../assets/fonts/Orbitron-Regular.ttf");
 
 /// Embedded Orbitron Bold font bytes (OFL licensed).
-const FONT_ORBITRON_BOLD: &[u8] = include_bytes!("../assets/fonts/Orbitron-Bold.ttf");
+pub const FONT_ORBITRON_BOLD: &[u8] = include_bytes!("../assets/fonts/Orbitron-Bold.ttf");
 
 // =============================================================================
 // FONTS & TEXT
@@ -197,6 +272,21 @@ pub mod window {
     pub const ABOUT_SCROLL_HEIGHT: f32 = 300.0;
 }
 
+/// Curve gallery tab of the About dialog.
+pub mod gallery {
+    /// Side length of each thumbnail in points.
+    pub const THUMBNAIL_SIZE: f32 = 96.0;
+
+    /// Grid size the thumbnail curves are drawn at.
+    pub const CURVE_SIZE: u32 = 8;
+
+    /// Inset between the thumbnail border and the curve.
+    pub const MARGIN: f32 = 10.0;
+
+    /// Stroke width of thumbnail curves.
+    pub const LINE_WIDTH: f32 = 1.5;
+}
+
 /// Popup and dropdown dimensions.
 pub mod popup {
     /// Inner margin for popup frames.
@@ -224,6 +314,30 @@ pub mod popup {
     pub const SETTINGS_OFFSET_Y: f32 = 4.0;
 }
 
+/// Toast notification layout.
+pub mod toast {
+    /// Width of each toast.
+    pub const WIDTH: f32 = 300.0;
+
+    /// Distance from the window's bottom-right corner.
+    pub const MARGIN: f32 = 12.0;
+
+    /// Vertical gap between stacked toasts.
+    pub const SPACING: f32 = 6.0;
+}
+
+/// Command palette layout.
+pub mod palette {
+    /// Width of the palette panel.
+    pub const WIDTH: f32 = 360.0;
+
+    /// Distance from the top of the window.
+    pub const TOP_OFFSET: f32 = 80.0;
+
+    /// Maximum height of the scrolling result list.
+    pub const MAX_LIST_HEIGHT: f32 = 280.0;
+}
+
 /// Shadow parameters for UI elements.
 pub mod shadow {
     /// Shadow offset (x, y) - subtle, technical.
@@ -236,6 +350,44 @@ pub mod shadow {
     pub const SPREAD: u8 = 0;
 }
 
+/// AllRGB generator dialog.
+pub mod allrgb {
+    /// Side length in pixels of the downscaled preview texture.
+    pub const PREVIEW_SIDE: usize = 512;
+
+    /// On-screen size of the preview image.
+    pub const PREVIEW_DISPLAY_SIZE: f32 = 384.0;
+}
+
+/// Progress bar shown while a large curve is cached over several frames.
+pub mod build_progress {
+    /// Width of the progress bar.
+    pub const WIDTH: f32 = 240.0;
+}
+
+/// Optional glow pass drawn under curve strokes in both panes.
+pub mod glow {
+    /// Glow stroke width relative to the core stroke.
+    pub const WIDTH_MULTIPLIER: f32 = 3.0;
+
+    /// Glow alpha at full curve opacity; scaled down with the opacity slider.
+    pub const ALPHA: u8 = 70;
+}
+
+/// Gradient stops for the stretch heatmap color mode.
+pub mod heatmap {
+    use egui::Color32;
+
+    /// Color of perfectly local segments.
+    pub const COLD: Color32 = Color32::from_rgb(0x1f, 0x4f, 0xff);
+
+    /// Color halfway up the log stretch scale.
+    pub const WARM: Color32 = Color32::from_rgb(0xff, 0x4d, 0xf6);
+
+    /// Color of the most stretched segments.
+    pub const HOT: Color32 = Color32::from_rgb(0xff, 0xc8, 0x3d);
+}
+
 // =============================================================================
 // 2D RENDERING
 // =============================================================================
@@ -256,6 +408,62 @@ pub mod canvas_2d {
 
     /// Snake overlay width multiplier (relative to line width).
     pub const SNAKE_WIDTH_MULTIPLIER: f32 = 1.8;
+
+    /// Line width of the measured path.
+    pub const MEASURE_WIDTH: f32 = 3.0;
+
+    /// Radius of the measurement endpoint markers.
+    pub const MEASURE_MARKER_RADIUS: f32 = 6.0;
+
+    /// Line width of a picked long jump.
+    pub const JUMP_WIDTH: f32 = 3.5;
+
+    /// Maximum pointer distance at which a click picks a long jump.
+    pub const JUMP_PICK_RADIUS: f32 = 6.0;
+
+    /// Magnification applied when double-clicking to focus on a cell.
+    pub const ZOOM_FACTOR: f32 = 4.0;
+
+    /// Canvas aspect ratio from which fit mode clips the grid to a rectangle.
+    pub const FIT_MIN_ASPECT: f32 = 1.25;
+}
+
+/// Overview inset shown while the 2D canvas is zoomed.
+pub mod minimap {
+    use egui::Color32;
+
+    /// Side length of the inset.
+    pub const SIZE: f32 = 120.0;
+
+    /// Gap between the inset and the canvas corner.
+    pub const MARGIN: f32 = 8.0;
+
+    /// Inset between the border and the drawn curve.
+    pub const PADDING: f32 = 6.0;
+
+    /// Stroke width of the overview curve.
+    pub const LINE_WIDTH: f32 = 1.0;
+
+    /// Opacity of the overview curve.
+    pub const CURVE_OPACITY: f32 = 0.6;
+
+    /// Inset background, opaque enough to hide the zoomed curve beneath.
+    pub const BACKGROUND: Color32 = Color32::from_rgba_premultiplied(0x08, 0x07, 0x14, 0xe6);
+}
+
+/// Hover picking of curve vertices.
+pub mod hover {
+    /// Side length of the screen-space buckets used for lookups.
+    pub const BUCKET_SIZE: f32 = 8.0;
+
+    /// Maximum pointer distance at which a vertex is picked.
+    pub const SNAP_RADIUS: f32 = 12.0;
+
+    /// Distance difference below which two vertices count as equally near.
+    pub const TIE_DISTANCE: f32 = 0.5;
+
+    /// Radius of the ring marking the hovered vertex.
+    pub const RING_RADIUS: f32 = 6.0;
 }
 
 // =============================================================================
@@ -278,18 +486,62 @@ pub mod canvas_3d {
     /// Mouse drag rotation sensitivity.
     pub const DRAG_SENSITIVITY: f32 = 0.01;
 
-    /// Distance from camera to scene center in normalized coordinates.
+    /// Default distance from camera to scene center in normalized coordinates.
     ///
     /// A value of 4.0 with a scene spanning [-1, 1] provides moderate perspective
     /// distortion that adds depth without excessive foreshortening.
     pub const PERSPECTIVE_DISTANCE: f32 = 4.0;
 
-    /// Fixed tilt angle (radians) for X-axis rotation, giving a slight top-down view.
+    /// Closest allowed camera distance.
+    ///
+    /// Must stay above √3, the farthest a rotated point can be from the scene
+    /// center, so every point remains in front of the camera.
+    pub const PERSPECTIVE_DISTANCE_MIN: f32 = 2.0;
+
+    /// Farthest allowed camera distance, where the projection is nearly orthographic.
+    pub const PERSPECTIVE_DISTANCE_MAX: f32 = 20.0;
+
+    /// Default tilt angle (radians) for X-axis rotation, giving a slight top-down view.
     ///
     /// PI/6 (30°) tilts the scene so the top face is partially visible while keeping
     /// the front face prominent.
     pub const CAMERA_TILT: f32 = PI / 6.0;
 
+    /// Largest tilt angle (radians) in either direction; PI/2 looks straight down.
+    pub const CAMERA_TILT_MAX: f32 = PI / 2.0;
+
+    /// Default segment stride while dragging dense curves (every Nth segment is drawn).
+    pub const DRAG_DECIMATION: u32 = 4;
+
+    /// Largest selectable drag decimation stride.
+    pub const DRAG_DECIMATION_MAX: u32 = 8;
+
+    /// Segment count above which drag decimation kicks in.
+    ///
+    /// 8192 leaves 16³ curves (4095 segments) at full detail and thins out 32³
+    /// curves, which are the ones that drop frames while rotating.
+    pub const DRAG_DECIMATION_MIN_SEGMENTS: usize = 8192;
+
+    /// Default number of depth buckets for O(N) "sorting".
+    ///
+    /// Instead of fully sorting 32k+ segments (O(N log N)), we bucket them into fixed depth
+    /// slices. All segments in a bucket share the same Z-depth for styling purposes, allowing
+    /// us to batch them into a single mesh. 128 bins provides smooth enough depth gradation
+    /// that the discrete steps are not noticeable.
+    pub const DEPTH_BINS: usize = 128;
+
+    /// Smallest selectable depth bin count.
+    pub const DEPTH_BINS_MIN: usize = 8;
+
+    /// Largest selectable depth bin count.
+    pub const DEPTH_BINS_MAX: usize = 1024;
+
+    /// Segment count up to which exact depth sorting is used when enabled.
+    ///
+    /// Below this, sorting every segment costs less than a frame's slack and
+    /// fixes crossings that share a bin; above it, binning takes over again.
+    pub const EXACT_SORT_MAX_SEGMENTS: usize = 5000;
+
     /// Minimum depth value (front of scene) for brightness mapping.
     pub const DEPTH_MIN: f32 = -2.0;
 
@@ -326,6 +578,24 @@ pub mod animation {
     /// At this rate, a full 360° rotation takes approximately 18 seconds, which
     /// provides a comfortable viewing speed for examining 3D curve structure.
     pub const BASE_ROTATION_SPEED: f32 = 0.35;
+
+    /// Frame rate of the fixed animation clock used in screenshot mode.
+    pub const CAPTURE_FPS: u32 = 60;
+}
+
+/// Demo (attract) mode timing and content.
+pub mod demo {
+    /// Seconds each demo step stays on screen, including fades.
+    pub const STEP_SECONDS: f32 = 10.0;
+
+    /// Duration of the fade at each end of a step.
+    pub const FADE_SECONDS: f32 = 0.8;
+
+    /// 2D sizes cycled through on successive passes.
+    pub const SIZES_2D: [u32; 3] = [16, 32, 64];
+
+    /// 3D sizes cycled through on successive passes.
+    pub const SIZES_3D: [u32; 3] = [4, 8, 16];
 }
 
 // =============================================================================
@@ -363,6 +633,17 @@ pub fn snake_color_with_brightness(brightness: f32) -> Color32 {
     )
 }
 
+/// Create the alternate onion shell color with brightness scaling and opacity.
+#[inline]
+pub fn shell_color_with_brightness(brightness: f32, opacity: f32) -> Color32 {
+    Color32::from_rgba_unmultiplied(
+        (shell_color::R as f32 * brightness) as u8,
+        (shell_color::G as f32 * brightness) as u8,
+        (shell_color::B as f32 * brightness) as u8,
+        (255.0 * opacity) as u8,
+    )
+}
+
 /// Create a lighter "glow" version of the curve color.
 ///
 /// Blends the curve color toward white for a glowing/bloom effect.
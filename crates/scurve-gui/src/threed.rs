@@ -2,12 +2,17 @@ use egui::{
     self,
     epaint::{PathShape, Stroke, Vertex},
 };
+use spacecurve::segment::is_adjacent;
 
 // pattern_from_name used in caching method only; no direct use here
 use super::{AppState, widgets};
 use crate::{
-    selection::Selected3DCurve,
-    snake::{fill_snake_segments, is_adjacent_3d, snake_mask_contains, snake_membership_mask},
+    i18n::Msg,
+    pick::draw_hover_label,
+    rotation::{Mat3, apply, view_rotation},
+    selection::{CachedCurve, Selected3DCurve},
+    shells::{shell_level_fn, shell_levels},
+    snake::{fill_snake_segments, snake_mask_contains, snake_membership_mask},
     theme::{
         self, canvas_3d::CAP_SHORTEN_FACTOR, curve_color_opaque, curve_glow_color,
         curve_glow_color_alpha, isolated_point_brightness, isolated_point_line_width,
@@ -15,14 +20,6 @@ use crate::{
     },
 };
 
-/// Number of depth buckets for O(N) "sorting".
-///
-/// Instead of fully sorting 32k+ segments (O(N log N)), we bucket them into fixed depth slices.
-/// All segments in a bucket share the same Z-depth for styling purposes, allowing us to
-/// batch them into a single mesh. 128 bins provides smooth enough depth gradation that
-/// the discrete steps are not noticeable.
-const NUM_DEPTH_BINS: usize = 128;
-
 /// Helper to tessellate a line segment into a mesh (as a simple quad).
 ///
 /// We do this manually rather than using `painter.line_segment` to allow batching.
@@ -128,57 +125,59 @@ pub fn show_3d_pane(
 ) {
     // Repaints are requested conditionally from the app loop
 
-    // Secondary control bar with lighter visual weight
-    egui::Frame::new()
-        .inner_margin(egui::Margin {
-            left: theme::control_bar::PADDING_HORIZONTAL as i8,
-            right: theme::control_bar::PADDING_HORIZONTAL as i8,
-            top: theme::control_bar::PADDING_VERTICAL as i8,
-            bottom: theme::control_bar::PADDING_VERTICAL as i8,
-        })
-        .show(ui, |ui| {
-            ui.horizontal(|ui| {
-                // Use smaller, dimmer text for control labels
-                ui.label(
-                    egui::RichText::new("Curve:")
-                        .size(theme::font_size::INFO)
-                        .color(theme::TEXT_DIM),
-                );
-                widgets::curve_selector_combo(
-                    ui,
-                    &mut selected_3d_curve.name,
-                    available_curves,
-                    "3d_curve_selector",
-                    &mut selected_3d_curve.info_open,
-                    3,
-                    selected_3d_curve.size,
-                );
-
-                ui.separator();
+    // Embedded views show the canvas alone
+    if !app_state.embed {
+        // Secondary control bar with lighter visual weight
+        egui::Frame::new()
+            .inner_margin(egui::Margin {
+                left: theme::control_bar::PADDING_HORIZONTAL as i8,
+                right: theme::control_bar::PADDING_HORIZONTAL as i8,
+                top: theme::control_bar::PADDING_VERTICAL as i8,
+                bottom: theme::control_bar::PADDING_VERTICAL as i8,
+            })
+            .show(ui, |ui| {
+                let lang = shared_settings.language;
+                ui.horizontal(|ui| {
+                    // Use smaller, dimmer text for control labels
+                    ui.label(
+                        egui::RichText::new(lang.tr(Msg::Curve))
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_DIM),
+                    );
+                    widgets::curve_selector_combo(
+                        ui,
+                        selected_3d_curve,
+                        available_curves,
+                        "3d_curve_selector",
+                        lang,
+                    );
 
-                ui.label(
-                    egui::RichText::new("Size:")
-                        .size(theme::font_size::INFO)
-                        .color(theme::TEXT_DIM),
-                );
-                widgets::size_selector_3d(ui, &mut selected_3d_curve.size, "3d_size_selector");
+                    ui.separator();
 
-                // Add pause button and settings on the right side of the controls
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    widgets::settings_dropdown(
-                        ui,
-                        &mut app_state.settings_dropdown_open,
-                        &mut app_state.settings_dropdown_pos,
-                        shared_settings,
-                        true, // Include spin speed for 3D view
+                    ui.label(
+                        egui::RichText::new(lang.tr(Msg::Size))
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_DIM),
                     );
-                    ui.add_space(theme::spacing::SMALL);
-                    widgets::pause_play_button(ui, &mut app_state.paused);
+                    widgets::size_selector(ui, selected_3d_curve, "3d_size_selector", lang);
+
+                    // Add pause button and settings on the right side of the controls
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        widgets::settings_dropdown(
+                            ui,
+                            &mut app_state.settings_dropdown_open,
+                            &mut app_state.settings_dropdown_pos,
+                            shared_settings,
+                            true, // Include spin speed for 3D view
+                        );
+                        ui.add_space(theme::spacing::SMALL);
+                        widgets::pause_play_button(ui, &mut app_state.paused);
+                    });
                 });
             });
-        });
 
-    ui.separator();
+        ui.separator();
+    }
 
     let available_rect = ui.available_rect_before_wrap();
     render_cache.last_canvas_rect = Some(available_rect);
@@ -190,21 +189,47 @@ pub fn show_3d_pane(
     // Capture values that will be needed while we hold a borrow during caching
     let curve_size = selected_3d_curve.size;
     let snake_offset = selected_3d_curve.snake_offset;
-    if let Some(points3d) = selected_3d_curve.ensure_cached_points() {
+    let shell_level = shared_settings
+        .onion_shells
+        .then(|| shell_level_fn(&selected_3d_curve.name))
+        .flatten();
+    if let Some(curve) = selected_3d_curve.ensure_cached_points() {
+        let shells = &mut render_cache.cache_shells;
+        match shell_level {
+            Some(level) => shell_levels(level, curve_size, curve.points, shells),
+            None => shells.clear(),
+        }
         draw_3d_space_curve(
             &painter,
             available_rect,
             app_state,
             render_cache,
             shared_settings,
-            points3d,
+            curve,
             curve_size,
             snake_offset,
         );
+        if !app_state.mouse_dragging
+            && ui.rect_contains_pointer(available_rect)
+            && let Some(hover) = ui.ctx().pointer_hover_pos()
+        {
+            let screen = &render_cache.cache_3d_screen;
+            let depth = &render_cache.cache_3d_points;
+            let grid = &mut render_cache.pick_grid;
+            grid.rebuild(available_rect, screen);
+            if let Some(i) = grid.nearest(screen, hover, theme::hover::SNAP_RADIUS, |i| depth[i][2])
+            {
+                draw_hover_label(&painter, screen[i], i, &curve.points[i]);
+            }
+        }
+    }
+    if let Some(progress) = selected_3d_curve.build_progress() {
+        widgets::build_progress(ui, available_rect, progress, shared_settings.language);
     }
 
     // Handle mouse interaction for manual rotation control
     let response = ui.allocate_rect(available_rect, egui::Sense::click_and_drag());
+    widgets::copy_points_menu(&response, selected_3d_curve, shared_settings);
 
     if response.hovered() && ui.input(|i| i.pointer.primary_down()) {
         // Mouse button is down - pause rotation immediately
@@ -219,7 +244,7 @@ pub fn show_3d_pane(
             let delta_x = current_mouse_x - app_state.last_mouse_x;
 
             // Apply manual rotation (scale the mouse movement)
-            app_state.rotation_angle += delta_x * theme::canvas_3d::DRAG_SENSITIVITY;
+            app_state.rotation_angles[1] += delta_x * theme::canvas_3d::DRAG_SENSITIVITY;
             app_state.last_mouse_x = current_mouse_x;
         }
     } else if app_state.mouse_dragging {
@@ -236,10 +261,12 @@ fn draw_3d_space_curve(
     app_state: &AppState,
     render_cache: &mut crate::RenderCache,
     shared_settings: &crate::SharedSettings,
-    original_curve_points: &[[u32; 3]],
+    curve: CachedCurve<'_, 3>,
     curve_size: u32,
     snake_offset: f32,
 ) {
+    let original_curve_points = curve.points;
+    let connected = curve.adjacent;
     let center = rect.center();
     let margin = theme::canvas_3d::MARGIN;
     let available_width = rect.width() - margin * 2.0;
@@ -251,42 +278,78 @@ fn draw_3d_space_curve(
         return;
     }
 
-    let rotation_y = app_state.rotation_angle;
-    let rotation_x = theme::canvas_3d::CAMERA_TILT;
+    let rotation = view_rotation(app_state.rotation_angles, shared_settings.camera_tilt);
+    let distance = shared_settings.perspective_distance;
 
     // Use cached buffers
     project_points(
         original_curve_points,
         curve_size,
-        rotation_x,
-        rotation_y,
+        &rotation,
+        distance,
         center,
         scale,
         &mut render_cache.cache_3d_points,
         &mut render_cache.cache_3d_screen,
     );
 
-    compute_connected(original_curve_points, &mut render_cache.cache_connected);
-    compute_shorten_caps(
-        &render_cache.cache_connected,
-        &mut render_cache.cache_caps,
-    );
+    if let Some(range) = shared_settings.highlight_range(original_curve_points.len()) {
+        let cell_size = scale * 2.0 / (curve_size - 1) as f32;
+        draw_range_highlight(
+            painter,
+            &render_cache.cache_3d_screen[range.clone()],
+            &render_cache.cache_3d_points[range],
+            cell_size,
+            distance,
+        );
+    }
+
+    compute_shorten_caps(connected, &mut render_cache.cache_caps);
+    // Thin out dense curves while dragging; full detail returns on release.
+    let stride = if app_state.mouse_dragging
+        && connected.len() > theme::canvas_3d::DRAG_DECIMATION_MIN_SEGMENTS
+    {
+        shared_settings.drag_decimation.max(1) as usize
+    } else {
+        1
+    };
     build_segment_depths(
         &render_cache.cache_3d_points,
-        &render_cache.cache_connected,
+        connected,
         shared_settings.curve_long_jumps,
+        stride,
         &mut render_cache.cache_depths,
     );
 
-    // Sorted by depth binning inside draw_curve_segments
-    draw_curve_segments(
-        painter,
-        &render_cache.cache_3d_screen,
-        &render_cache.cache_depths,
-        &render_cache.cache_caps,
-        shared_settings.curve_opacity,
-        &mut render_cache.cache_bins,
-    );
+    // Sorted exactly for small curves when asked, by depth binning otherwise
+    render_cache
+        .cache_bins
+        .resize_with(shared_settings.depth_bins.max(2), Vec::new);
+    if shared_settings.exact_depth_sort
+        && render_cache.cache_depths.len() <= theme::canvas_3d::EXACT_SORT_MAX_SEGMENTS
+    {
+        sort_back_to_front(&mut render_cache.cache_depths);
+        draw_sorted_curve_segments(
+            painter,
+            &render_cache.cache_3d_screen,
+            &render_cache.cache_depths,
+            &render_cache.cache_caps,
+            &render_cache.cache_shells,
+            shared_settings.curve_opacity,
+            shared_settings.curve_glow,
+        );
+    } else {
+        draw_curve_segments(
+            painter,
+            &render_cache.cache_3d_screen,
+            &render_cache.cache_depths,
+            &render_cache.cache_caps,
+            &render_cache.cache_shells,
+            shared_settings.curve_opacity,
+            shared_settings.curve_glow,
+            &mut render_cache.cache_bins,
+        );
+    }
 
     if shared_settings.snake_enabled && render_cache.cache_3d_screen.len() > 1 {
         let curve_len = original_curve_points.len() as f32;
@@ -300,7 +363,7 @@ fn draw_3d_space_curve(
         let raw_tail_segment = tail_pos.floor() as usize % original_curve_points.len();
         let raw_tail_frac = tail_pos.fract();
         let tail_next = (raw_tail_segment + 1) % original_curve_points.len();
-        let tail_adjacent = is_adjacent_3d(
+        let tail_adjacent = is_adjacent(
             &original_curve_points[raw_tail_segment],
             &original_curve_points[tail_next],
         );
@@ -343,7 +406,7 @@ fn draw_3d_space_curve(
         let raw_head_segment = head_pos.floor() as usize % original_curve_points.len();
         let raw_head_frac = head_pos.fract();
         let head_next = (raw_head_segment + 1) % original_curve_points.len();
-        let head_adjacent = is_adjacent_3d(
+        let head_adjacent = is_adjacent(
             &original_curve_points[raw_head_segment],
             &original_curve_points[head_next],
         );
@@ -385,7 +448,7 @@ fn draw_3d_space_curve(
             &mut render_cache.snake_segments_3d,
             snake_offset,
             shared_settings.snake_length,
-            original_curve_points.len() as u32,
+            original_curve_points.len() as u64,
         );
         let snake_segments = &render_cache.snake_segments_3d;
 
@@ -400,14 +463,14 @@ fn draw_3d_space_curve(
         };
         let snake_included = snake_included_mask(
             snake_segments,
-            &render_cache.cache_connected,
+            connected,
             shared_settings.snake_long_jumps,
             &mut render_cache.snake_included_3d,
         );
         let draws = collect_snake_draws(
             &render_cache.cache_3d_screen,
             &render_cache.cache_3d_points,
-            &render_cache.cache_connected,
+            connected,
             snake_included,
             &render_cache.cache_caps,
             snake_segments,
@@ -454,8 +517,8 @@ fn draw_3d_space_curve(
 fn project_points(
     original: &[[u32; 3]],
     curve_size: u32,
-    rotation_x: f32,
-    rotation_y: f32,
+    rotation: &Mat3,
+    distance: f32,
     center: egui::Pos2,
     scale: f32,
     pts3d: &mut Vec<[f32; 3]>,
@@ -470,30 +533,32 @@ fn project_points(
         let x = (p[0] as f32 / (curve_size - 1) as f32) * 2.0 - 1.0;
         let y = (p[1] as f32 / (curve_size - 1) as f32) * 2.0 - 1.0;
         let z = (p[2] as f32 / (curve_size - 1) as f32) * 2.0 - 1.0;
-        let x_rot = x * rotation_y.cos() + z * rotation_y.sin();
-        let z_rot = -x * rotation_y.sin() + z * rotation_y.cos();
-        let y_tilt = y * rotation_x.cos() - z_rot * rotation_x.sin();
-        let z_tilt = y * rotation_x.sin() + z_rot * rotation_x.cos();
-        pts3d.push([x_rot, y_tilt, z_tilt]);
-        let depth = theme::canvas_3d::PERSPECTIVE_DISTANCE - z_tilt;
-        let perspective_scale = theme::canvas_3d::PERSPECTIVE_DISTANCE / depth;
+        let [x_rot, y_rot, z_rot] = apply(rotation, [x, y, z]);
+        pts3d.push([x_rot, y_rot, z_rot]);
+        let perspective_scale = distance / (distance - z_rot);
         let screen_x = center.x + x_rot * scale * perspective_scale;
-        let screen_y = center.y - y_tilt * scale * perspective_scale;
+        let screen_y = center.y - y_rot * scale * perspective_scale;
         pts2d.push(egui::Pos2::new(screen_x, screen_y));
     }
 }
 
-/// Compute whether successive 3D points are adjacent (Manhattan distance <= 1).
-fn compute_connected(original: &[[u32; 3]], connected: &mut Vec<bool>) {
-    connected.clear();
-    if original.len() < 2 {
-        return;
-    }
-    let last_seg_idx = original.len() - 2;
-    connected.reserve(last_seg_idx + 1);
-    for i in 0..=last_seg_idx {
-        connected.push(is_adjacent_3d(&original[i], &original[i + 1]));
+/// Shade a perspective-scaled square around each highlighted point, batched into one mesh.
+fn draw_range_highlight(
+    painter: &egui::Painter,
+    pts2d: &[egui::Pos2],
+    pts3d: &[[f32; 3]],
+    cell_size: f32,
+    distance: f32,
+) {
+    let mut mesh = egui::Mesh::default();
+    for (&p, depth) in pts2d.iter().zip(pts3d) {
+        let perspective_scale = distance / (distance - depth[2]);
+        mesh.add_colored_rect(
+            egui::Rect::from_center_size(p, egui::Vec2::splat(cell_size * perspective_scale)),
+            theme::RANGE_HIGHLIGHT,
+        );
     }
+    painter.add(egui::Shape::mesh(mesh));
 }
 
 /// For each segment, decide whether to shorten start/end caps at exposed ends.
@@ -512,15 +577,18 @@ fn compute_shorten_caps(connected: &[bool], caps: &mut Vec<(bool, bool)>) {
 }
 
 /// Build a list of segment indices with their average depth for painter sorting.
+///
+/// Only every `stride`th segment is kept, which thins dense curves while dragging.
 fn build_segment_depths(
     pts3d: &[[f32; 3]],
     connected: &[bool],
     show_long_jumps: bool,
+    stride: usize,
     segs: &mut Vec<(usize, f32)>,
 ) {
     segs.clear();
-    segs.reserve(connected.len());
-    for i in 0..connected.len() {
+    segs.reserve(connected.len() / stride + 1);
+    for i in (0..connected.len()).step_by(stride) {
         let start_depth = pts3d[i][2];
         let end_depth = pts3d[i + 1][2];
         let avg_depth = (start_depth + end_depth) / 2.0;
@@ -530,20 +598,39 @@ fn build_segment_depths(
     }
 }
 
+/// Index of the bucket `depth` falls into when `bins` buckets span the scene.
+fn depth_bin(depth: f32, bins: usize) -> usize {
+    (theme::normalize_depth(depth) * (bins as f32 - 1.0)).round() as usize
+}
+
+/// Order `(segment, depth)` pairs by depth, in the order bins are drawn.
+fn sort_back_to_front(segments_with_depth: &mut [(usize, f32)]) {
+    segments_with_depth.sort_by(|a, b| a.1.total_cmp(&b.1));
+}
+
 /// Draw depth‑sorted curve segments using depth binning.
 ///
 /// This function implements the core optimization:
-/// 1. **Binning**: Distribute segments into `NUM_DEPTH_BINS` buckets based on depth.
+/// 1. **Binning**: Distribute segments into `bins.len()` buckets based on depth.
 /// 2. **Batching**: For each bin, generate a single `egui::Mesh` containing all segments.
 ///
 /// This reduces the number of draw calls from O(N) (e.g., 32,000) to O(BINS) (128),
 /// providing a massive performance boost.
+///
+/// With `glow`, each bin's mesh first gets a wide translucent copy of its
+/// segments, so the glow stays depth-sorted along with the core strokes.
+///
+/// Segments starting on an odd level in `shells` (per point, empty when shell
+/// coloring is off) use the alternate shell color.
+#[allow(clippy::too_many_arguments)]
 fn draw_curve_segments(
     painter: &egui::Painter,
     pts2d: &[egui::Pos2],
     segments_with_depth: &[(usize, f32)],
     shorten_caps: &[(bool, bool)],
+    shells: &[u32],
     opacity: f32,
+    glow: bool,
     bins: &mut [Vec<usize>],
 ) {
     if opacity <= 0.0 {
@@ -554,10 +641,10 @@ fn draw_curve_segments(
         bin.clear();
     }
 
+    let num_bins = bins.len();
     for (i, depth) in segments_with_depth {
-        let normalized = theme::normalize_depth(*depth);
-        let bin_idx = (normalized * (NUM_DEPTH_BINS as f32 - 1.0)).round() as usize;
-        if bin_idx < NUM_DEPTH_BINS {
+        let bin_idx = depth_bin(*depth, num_bins);
+        if bin_idx < num_bins {
             bins[bin_idx].push(*i);
         }
     }
@@ -567,34 +654,102 @@ fn draw_curve_segments(
             continue;
         }
         // Use the bin center to determine style for all segments in this bin
-        let normalized_depth = bin_idx as f32 / (NUM_DEPTH_BINS as f32 - 1.0);
+        let normalized_depth = bin_idx as f32 / (num_bins as f32 - 1.0);
         let depth = theme::canvas_3d::DEPTH_MIN
             + normalized_depth * (theme::canvas_3d::DEPTH_MAX - theme::canvas_3d::DEPTH_MIN);
         let brightness = theme::segment_brightness(depth);
         let line_width = theme::segment_line_width(brightness);
         let color = theme::curve_color_with_brightness(brightness, opacity);
+        let shell_color = theme::shell_color_with_brightness(brightness, opacity);
         // Stroke not needed for mesh, just width and color
 
         let mut mesh = egui::Mesh::default();
 
-        for &i in bin {
-            let start_pos = pts2d[i];
-            let end_pos = pts2d[i + 1];
-            let (shorten_start, shorten_end) = shorten_caps[i];
+        let glow_pass = glow.then(|| {
+            let alpha = (theme::glow::ALPHA as f32 * opacity) as u8;
+            (
+                line_width * theme::glow::WIDTH_MULTIPLIER,
+                curve_glow_color_alpha(brightness, alpha),
+                false,
+            )
+        });
+        for (width, color, tint_shells) in glow_pass.into_iter().chain([(line_width, color, true)])
+        {
+            for &i in bin {
+                let (shorten_start, shorten_end) = shorten_caps[i];
+                let alternate = tint_shells && shells.get(i).is_some_and(|level| level % 2 == 1);
+                add_segment_to_mesh(
+                    &mut mesh,
+                    pts2d[i],
+                    pts2d[i + 1],
+                    width,
+                    if alternate { shell_color } else { color },
+                    shorten_start,
+                    shorten_end,
+                );
+            }
+        }
+
+        if !mesh.vertices.is_empty() {
+            painter.add(egui::Shape::Mesh(mesh.into()));
+        }
+    }
+}
+
+/// Draw curve segments already sorted back to front, one at a time.
+///
+/// Unlike [`draw_curve_segments`], every segment is styled by its own depth
+/// and drawn after everything behind it, glow included, so crossings overlap
+/// correctly. The whole curve still goes into one mesh, which keeps the
+/// order.
+fn draw_sorted_curve_segments(
+    painter: &egui::Painter,
+    pts2d: &[egui::Pos2],
+    sorted_segments: &[(usize, f32)],
+    shorten_caps: &[(bool, bool)],
+    shells: &[u32],
+    opacity: f32,
+    glow: bool,
+) {
+    if opacity <= 0.0 {
+        return;
+    }
+
+    let glow_alpha = (theme::glow::ALPHA as f32 * opacity) as u8;
+    let mut mesh = egui::Mesh::default();
+    for &(i, depth) in sorted_segments {
+        let brightness = theme::segment_brightness(depth);
+        let line_width = theme::segment_line_width(brightness);
+        let (shorten_start, shorten_end) = shorten_caps[i];
+        if glow {
             add_segment_to_mesh(
                 &mut mesh,
-                start_pos,
-                end_pos,
-                line_width,
-                color,
+                pts2d[i],
+                pts2d[i + 1],
+                line_width * theme::glow::WIDTH_MULTIPLIER,
+                curve_glow_color_alpha(brightness, glow_alpha),
                 shorten_start,
                 shorten_end,
             );
         }
+        let color = if shells.get(i).is_some_and(|level| level % 2 == 1) {
+            theme::shell_color_with_brightness(brightness, opacity)
+        } else {
+            theme::curve_color_with_brightness(brightness, opacity)
+        };
+        add_segment_to_mesh(
+            &mut mesh,
+            pts2d[i],
+            pts2d[i + 1],
+            line_width,
+            color,
+            shorten_start,
+            shorten_end,
+        );
+    }
 
-        if !mesh.vertices.is_empty() {
-            painter.add(egui::Shape::Mesh(mesh.into()));
-        }
+    if !mesh.vertices.is_empty() {
+        painter.add(egui::Shape::Mesh(mesh.into()));
     }
 }
 
@@ -809,10 +964,10 @@ fn draw_snake_draws(painter: &egui::Painter, draws: &[SnakeDraw], bins: &mut [Ve
         bin.clear();
     }
 
+    let num_bins = bins.len();
     for (i, d) in draws.iter().enumerate() {
-        let normalized = theme::normalize_depth(d.depth);
-        let bin_idx = (normalized * (NUM_DEPTH_BINS as f32 - 1.0)).round() as usize;
-        if bin_idx < NUM_DEPTH_BINS {
+        let bin_idx = depth_bin(d.depth, num_bins);
+        if bin_idx < num_bins {
             bins[bin_idx].push(i);
         }
     }
@@ -861,10 +1016,10 @@ fn draw_isolated_snake_points(
         if idx < original.len() {
             let has_adjacent_prev = idx > 0
                 && snake_mask_contains(snake_mask, idx - 1)
-                && is_adjacent_3d(&original[idx - 1], &original[idx]);
+                && is_adjacent(&original[idx - 1], &original[idx]);
             let has_adjacent_next = idx < original.len() - 1
                 && snake_mask_contains(snake_mask, idx + 1)
-                && is_adjacent_3d(&original[idx], &original[idx + 1]);
+                && is_adjacent(&original[idx], &original[idx + 1]);
             if !has_adjacent_prev && !has_adjacent_next {
                 isolated.push((idx, pts3d[idx][2]));
             }
@@ -904,9 +1059,9 @@ fn draw_isolated_points(
 ) {
     let mut iso = Vec::new();
     for i in 0..original.len() {
-        let has_adjacent_prev = i > 0 && is_adjacent_3d(&original[i - 1], &original[i]);
+        let has_adjacent_prev = i > 0 && is_adjacent(&original[i - 1], &original[i]);
         let has_adjacent_next =
-            i < original.len() - 1 && is_adjacent_3d(&original[i], &original[i + 1]);
+            i < original.len() - 1 && is_adjacent(&original[i], &original[i + 1]);
         if !has_adjacent_prev && !has_adjacent_next {
             iso.push((i, pts3d[i][2]));
         }
@@ -950,3 +1105,30 @@ fn draw_head_marker_at(painter: &egui::Painter, pos: egui::Pos2, depth: f32) {
     let core_color = curve_glow_color(brightness);
     painter.circle_filled(pos, core_radius, core_color);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_bins_span_the_scene() {
+        let (near, far) = (theme::canvas_3d::DEPTH_MIN, theme::canvas_3d::DEPTH_MAX);
+        for bins in [8, 128, 1024] {
+            assert_eq!(depth_bin(near, bins), 0);
+            assert_eq!(depth_bin(far, bins), bins - 1);
+            assert_eq!(depth_bin(far + 1.0, bins), bins - 1);
+        }
+        // Nearby depths share a coarse bin but not a fine one.
+        let (a, b) = (0.01, 0.02);
+        assert_eq!(depth_bin(a, 8), depth_bin(b, 8));
+        assert_ne!(depth_bin(a, 1024), depth_bin(b, 1024));
+    }
+
+    #[test]
+    fn exact_sort_orders_by_depth() {
+        let mut segments = vec![(0, 0.5), (1, -1.0), (2, 0.01), (3, 0.02)];
+        sort_back_to_front(&mut segments);
+        let order: Vec<usize> = segments.iter().map(|&(i, _)| i).collect();
+        assert_eq!(order, [1, 2, 3, 0]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Dismissible in-app notifications used to surface runtime errors.
+
+use std::fmt::Display;
+
+use egui::epaint::Shadow;
+
+use crate::{
+    i18n::{Language, Msg},
+    theme,
+};
+
+/// A single notification in the toast stack.
+#[derive(Debug, Clone)]
+struct Toast {
+    /// Stable identifier used to salt widget ids across frames.
+    id: u64,
+    /// One-line summary shown in the toast header.
+    title: String,
+    /// Full error text, revealed in the collapsible details section.
+    detail: String,
+}
+
+/// Stack of active toasts, rendered in the bottom-right corner of the window.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    /// Toasts in the order they were raised.
+    items: Vec<Toast>,
+    /// Identifier assigned to the next toast.
+    next_id: u64,
+}
+
+impl Toasts {
+    /// Queue an error toast with a short `title` and detailed description.
+    ///
+    /// Identical messages that are already visible are not duplicated.
+    pub fn error(&mut self, title: impl Into<String>, detail: impl Display) {
+        let title = title.into();
+        let detail = detail.to_string();
+        if self
+            .items
+            .iter()
+            .any(|t| t.title == title && t.detail == detail)
+        {
+            return;
+        }
+        self.items.push(Toast {
+            id: self.next_id,
+            title,
+            detail,
+        });
+        self.next_id += 1;
+    }
+
+    /// Render all active toasts and drop any the user dismissed.
+    pub fn show(&mut self, ctx: &egui::Context, lang: Language) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let mut dismissed = Vec::new();
+        egui::Area::new(egui::Id::new("toast_stack"))
+            .order(egui::Order::Foreground)
+            .anchor(
+                egui::Align2::RIGHT_BOTTOM,
+                egui::vec2(-theme::toast::MARGIN, -theme::toast::MARGIN),
+            )
+            .show(ctx, |ui| {
+                ui.set_max_width(theme::toast::WIDTH);
+                for toast in &self.items {
+                    if show_toast(ui, toast, lang) {
+                        dismissed.push(toast.id);
+                    }
+                    ui.add_space(theme::toast::SPACING);
+                }
+            });
+
+        self.items.retain(|t| !dismissed.contains(&t.id));
+    }
+}
+
+/// Draw a single toast; returns true when its dismiss button was clicked.
+fn show_toast(ui: &mut egui::Ui, toast: &Toast, lang: Language) -> bool {
+    let mut dismiss = false;
+    egui::Frame::new()
+        .fill(theme::SETTINGS_PANEL_BG)
+        .stroke(egui::Stroke::new(1.0, theme::TOAST_ERROR))
+        .corner_radius(egui::CornerRadius::same(theme::popup::CORNER_RADIUS))
+        .inner_margin(egui::Margin::same(theme::popup::INNER_MARGIN))
+        .shadow(Shadow {
+            offset: theme::shadow::OFFSET,
+            blur: theme::shadow::BLUR,
+            spread: theme::shadow::SPREAD,
+            color: egui::Color32::from_black_alpha(theme::POPUP_SHADOW_ALPHA),
+        })
+        .show(ui, |ui| {
+            ui.set_width(theme::toast::WIDTH);
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(&toast.title)
+                        .size(theme::font_size::LABEL)
+                        .strong()
+                        .color(theme::TOAST_ERROR),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(egui::Button::new("×").frame(false))
+                        .on_hover_text(lang.tr(Msg::Dismiss))
+                        .clicked()
+                    {
+                        dismiss = true;
+                    }
+                });
+            });
+            egui::CollapsingHeader::new(
+                egui::RichText::new(lang.tr(Msg::Details))
+                    .size(theme::font_size::INFO)
+                    .color(theme::TEXT_SECONDARY),
+            )
+            .id_salt(("toast_details", toast.id))
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(&toast.detail)
+                        .size(theme::font_size::INFO)
+                        .color(theme::TEXT_BODY),
+                );
+            });
+        });
+    dismiss
+}
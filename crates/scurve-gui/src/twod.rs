@@ -2,12 +2,20 @@ use egui::{
     self,
     epaint::{PathShape, Stroke},
 };
+use spacecurve::segment::is_adjacent;
 
 use super::widgets;
 use crate::{
     AppState,
-    selection::SelectedCurve,
-    snake::{fill_snake_segments, is_adjacent_2d, snake_membership_mask},
+    heatmap::{ColorMode, heat_color, segment_heat},
+    i18n::{Language, Msg},
+    jumps::{JumpPicker, JumpVisibility, class_count, jump_class, pick_jump},
+    layer::LayerKey,
+    measure::{MeasureStats, Measurement},
+    pick::{ScreenGrid, draw_hover_label},
+    selection::{CachedCurve, SelectedCurve},
+    shells::{shell_level_fn, shell_levels},
+    snake::{fill_snake_segments, snake_membership_mask},
     theme::{self, curve_glow_color, curve_glow_color_alpha},
 };
 
@@ -20,111 +28,225 @@ pub fn show_2d_pane(
     available_curves: &[&str],
     shared_settings: &mut crate::SharedSettings,
 ) {
-    // Secondary control bar with lighter visual weight
-    egui::Frame::new()
-        .inner_margin(egui::Margin {
-            left: theme::control_bar::PADDING_HORIZONTAL as i8,
-            right: theme::control_bar::PADDING_HORIZONTAL as i8,
-            top: theme::control_bar::PADDING_VERTICAL as i8,
-            bottom: theme::control_bar::PADDING_VERTICAL as i8,
-        })
-        .show(ui, |ui| {
-            ui.horizontal(|ui| {
-                // Use smaller, dimmer text for control labels
-                ui.label(
-                    egui::RichText::new("Curve:")
-                        .size(theme::font_size::INFO)
-                        .color(theme::TEXT_DIM),
-                );
-                widgets::curve_selector_combo(
-                    ui,
-                    &mut selected_curve.name,
-                    available_curves,
-                    "curve_selector",
-                    &mut selected_curve.info_open,
-                    2,
-                    selected_curve.size,
-                );
-
-                ui.separator();
-
-                ui.label(
-                    egui::RichText::new("Size:")
-                        .size(theme::font_size::INFO)
-                        .color(theme::TEXT_DIM),
-                );
-                widgets::size_selector_2d(ui, &mut selected_curve.size, "size_selector");
-
-                // Push pause and settings buttons to the far right
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    widgets::settings_dropdown(
+    // Embedded views show the canvas alone
+    if !app_state.embed {
+        // Secondary control bar with lighter visual weight
+        egui::Frame::new()
+            .inner_margin(egui::Margin {
+                left: theme::control_bar::PADDING_HORIZONTAL as i8,
+                right: theme::control_bar::PADDING_HORIZONTAL as i8,
+                top: theme::control_bar::PADDING_VERTICAL as i8,
+                bottom: theme::control_bar::PADDING_VERTICAL as i8,
+            })
+            .show(ui, |ui| {
+                let lang = shared_settings.language;
+                ui.horizontal(|ui| {
+                    // Use smaller, dimmer text for control labels
+                    ui.label(
+                        egui::RichText::new(lang.tr(Msg::Curve))
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_DIM),
+                    );
+                    widgets::curve_selector_combo(
                         ui,
-                        &mut app_state.settings_dropdown_open,
-                        &mut app_state.settings_dropdown_pos,
-                        shared_settings,
-                        false,
+                        selected_curve,
+                        available_curves,
+                        "curve_selector",
+                        lang,
+                    );
+
+                    ui.separator();
+
+                    ui.label(
+                        egui::RichText::new(lang.tr(Msg::Size))
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_DIM),
                     );
-                    ui.add_space(theme::spacing::SMALL);
-                    widgets::pause_play_button(ui, &mut app_state.paused);
+                    widgets::size_selector(ui, selected_curve, "size_selector", lang);
+
+                    ui.separator();
+
+                    let measurement = &mut app_state.measurement;
+                    if ui
+                        .selectable_label(measurement.active, lang.tr(Msg::Measure))
+                        .on_hover_text(lang.tr(Msg::MeasureHint))
+                        .clicked()
+                    {
+                        measurement.active = !measurement.active;
+                        if !measurement.active {
+                            measurement.clear();
+                        }
+                    }
+
+                    if ui
+                        .selectable_label(app_state.fit_2d, lang.tr(Msg::Fit))
+                        .on_hover_text(lang.tr(Msg::FitHint))
+                        .clicked()
+                    {
+                        app_state.fit_2d = !app_state.fit_2d;
+                    }
+
+                    let hidden_jumps = app_state.jumps.hidden().len();
+                    if hidden_jumps > 0
+                        && ui
+                            .button(format!("{} ({hidden_jumps})", lang.tr(Msg::ShowAllJumps)))
+                            .clicked()
+                    {
+                        app_state.jumps.show_all();
+                    }
+
+                    // Push pause and settings buttons to the far right
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        widgets::settings_dropdown(
+                            ui,
+                            &mut app_state.settings_dropdown_open,
+                            &mut app_state.settings_dropdown_pos,
+                            shared_settings,
+                            false,
+                        );
+                        ui.add_space(theme::spacing::SMALL);
+                        widgets::pause_play_button(ui, &mut app_state.paused);
+                    });
                 });
             });
-        });
 
-    ui.separator();
+        ui.separator();
+    }
 
-    draw_2d_canvas(ui, render_cache, selected_curve, shared_settings);
+    draw_2d_canvas(
+        ui,
+        render_cache,
+        selected_curve,
+        shared_settings,
+        &mut app_state.measurement,
+        &mut app_state.jumps,
+        &mut app_state.zoom_focus_2d,
+        app_state.fit_2d,
+    );
 }
 
 /// Render the 2D drawing canvas and overlays.
+///
+/// Double-clicking the canvas zooms in around the clicked cell by
+/// `ZOOM_FACTOR`; double-clicking again restores the full view. Zooming is
+/// disabled while the measure tool is active, since its clicks pick cells.
+/// With `fit` set, wide or tall canvases show the curve clipped to a
+/// rectangle instead of a letterboxed square. While long jumps are shown, a
+/// single click on one picks it and opens a popup describing it.
+#[allow(clippy::too_many_arguments)]
 fn draw_2d_canvas(
     ui: &mut egui::Ui,
     render_cache: &mut crate::RenderCache,
     selected_curve: &mut SelectedCurve,
     shared_settings: &crate::SharedSettings,
+    measurement: &mut Measurement,
+    jumps: &mut JumpPicker,
+    zoom_focus: &mut Option<[u32; 2]>,
+    fit: bool,
 ) {
     let bg = theme::CANVAS_BACKGROUND;
     let available_rect = ui.available_rect_before_wrap();
-    let drawing_size = (available_rect.width().min(available_rect.height())
-        * theme::canvas_2d::SIZE_FRACTION)
-        .max(theme::canvas_2d::MIN_SIZE);
-    let drawing_rect =
-        egui::Rect::from_center_size(available_rect.center(), egui::Vec2::splat(drawing_size));
+    let curve_size = selected_curve.size;
+    let (drawing_rect, extent) = canvas_layout(available_rect, curve_size, fit);
+    selected_curve.clip = (extent != [curve_size; 2]).then_some(extent);
     render_cache.last_canvas_rect = Some(drawing_rect);
     let painter = ui.painter_at(available_rect);
     painter.rect_filled(available_rect, 0.0, bg);
 
-    let curve_size = selected_curve.size;
     let snake_offset = selected_curve.snake_offset;
-    if let Some(curve_points) = selected_curve.ensure_cached_points() {
+    let margin = theme::canvas_2d::MARGIN;
+    let base_scale =
+        (drawing_rect.width().max(drawing_rect.height()) - margin * 2.0) / (curve_size - 1) as f32;
+    if zoom_focus.is_some_and(|c| c[0] >= extent[0] || c[1] >= extent[1]) {
+        *zoom_focus = None;
+    }
+    // Grid cells map to `origin + cell * scale`, centered on the focus cell when zoomed.
+    let (origin, scale) = match *zoom_focus {
+        Some([fx, fy]) => {
+            let scale = base_scale * theme::canvas_2d::ZOOM_FACTOR;
+            (
+                drawing_rect.center() - egui::vec2(fx as f32, fy as f32) * scale,
+                scale,
+            )
+        }
+        None => (drawing_rect.min + egui::Vec2::splat(margin), base_scale),
+    };
+    let minimap = zoom_focus.map(|_| minimap_layout(drawing_rect, extent));
+    if shared_settings.color_mode == ColorMode::Stretch {
+        selected_curve.ensure_stretch();
+    }
+    let shell_level = shared_settings
+        .onion_shells
+        .then(|| shell_level_fn(&selected_curve.name))
+        .flatten();
+    let (layer_name, layer_clip) = (selected_curve.name.clone(), selected_curve.clip);
+    if let Some(curve) = selected_curve.ensure_cached_points() {
+        let curve_points = curve.points;
         let painter = ui.painter_at(drawing_rect);
         painter.rect_filled(drawing_rect, 5.0, bg);
-
-        let margin = theme::canvas_2d::MARGIN;
-        let inner_size = drawing_size - margin * 2.0;
-        let scale = inner_size / (curve_size - 1) as f32;
+        let shells = &mut render_cache.cache_shells;
+        match shell_level {
+            Some(level) => shell_levels(level, curve_size, curve_points, shells),
+            None => shells.clear(),
+        }
 
         build_screen_points(
             curve_points,
-            drawing_rect,
+            origin,
             scale,
-            margin,
             &mut render_cache.cache_2d_screen,
         );
         let screen_points = &render_cache.cache_2d_screen;
 
-        let line_color = theme::curve_color_with_brightness(1.0, shared_settings.curve_opacity);
-        let line_width = theme::canvas_2d::LINE_WIDTH;
+        if let Some(range) = shared_settings.highlight_range(screen_points.len()) {
+            draw_range_highlight(&painter, &screen_points[range], scale);
+        }
 
-        if shared_settings.curve_opacity > 0.0 && screen_points.len() > 1 {
-            draw_main_curve_segments(
-                &painter,
-                curve_points,
+        let visibility = JumpVisibility {
+            show: shared_settings.curve_long_jumps,
+            hidden: jumps.hidden(),
+        };
+        let line_width = theme::canvas_2d::LINE_WIDTH;
+        let (shells, heat, run) = (
+            &render_cache.cache_shells,
+            &mut render_cache.cache_2d_heat,
+            &mut render_cache.cache_2d_run,
+        );
+        let mut draw = |out: &mut Vec<egui::Shape>| {
+            draw_static_curve(
+                out,
+                curve,
                 screen_points,
-                line_width,
-                line_color,
-                shared_settings.curve_long_jumps,
-                &mut render_cache.cache_2d_run,
+                shells,
+                shared_settings,
+                visibility,
+                heat,
+                run,
             );
+        };
+        if shared_settings.cache_static_2d {
+            let key = LayerKey {
+                name: layer_name,
+                size: curve_size,
+                clip: layer_clip,
+                rect: drawing_rect,
+                origin,
+                scale,
+                pixels_per_point: ui.ctx().pixels_per_point(),
+                opacity: shared_settings.curve_opacity,
+                glow: shared_settings.curve_glow,
+                color_mode: shared_settings.color_mode,
+                stretch: !curve.stretch.is_empty(),
+                shells: !shells.is_empty(),
+                long_jumps: shared_settings.curve_long_jumps,
+                hidden_jumps: jumps.hidden().to_vec(),
+            };
+            painter.add(render_cache.curve_layer_2d.shape(ui.ctx(), key, draw));
+        } else {
+            render_cache.curve_layer_2d.clear();
+            let mut shapes = Vec::new();
+            draw(&mut shapes);
+            painter.extend(shapes);
         }
 
         if shared_settings.snake_enabled && curve_points.len() > 1 {
@@ -140,7 +262,7 @@ fn draw_2d_canvas(
             let raw_tail_frac = tail_pos.fract();
             let tail_next = (raw_tail_segment + 1) % curve_points.len();
             let tail_adjacent =
-                is_adjacent_2d(&curve_points[raw_tail_segment], &curve_points[tail_next]);
+                is_adjacent(&curve_points[raw_tail_segment], &curve_points[tail_next]);
 
             // Effective tail position: either interpolated or snapped to next point
             // When PARTWAY through a long jump with snake_long_jumps=false, skip to the end
@@ -168,7 +290,7 @@ fn draw_2d_canvas(
             let raw_head_frac = head_pos.fract();
             let head_next = (raw_head_segment + 1) % curve_points.len();
             let head_adjacent =
-                is_adjacent_2d(&curve_points[raw_head_segment], &curve_points[head_next]);
+                is_adjacent(&curve_points[raw_head_segment], &curve_points[head_next]);
 
             // Effective head position: either interpolated or snapped to next point
             // When PARTWAY through a long jump with snake_long_jumps=false, skip to the end
@@ -194,7 +316,7 @@ fn draw_2d_canvas(
                 &mut render_cache.snake_segments_2d,
                 snake_offset,
                 shared_settings.snake_length,
-                curve_points.len() as u32,
+                curve_points.len() as u64,
             );
             let snake_segments = &render_cache.snake_segments_2d;
 
@@ -232,70 +354,586 @@ fn draw_2d_canvas(
             // Draw glowing head marker at the front of the snake
             draw_head_marker_at(&painter, head_screen);
         }
+
+        if let Some(minimap) = minimap {
+            draw_minimap(
+                &painter,
+                curve,
+                &minimap,
+                minimap.visible_rect(drawing_rect, origin, scale),
+                &mut render_cache.cache_2d_minimap,
+                &mut render_cache.cache_2d_run,
+            );
+        }
+
+        if measurement.active {
+            draw_measurement(
+                &painter,
+                curve_points,
+                screen_points,
+                measurement,
+                drawing_rect,
+            );
+        } else {
+            draw_vertex_hover(
+                ui,
+                &painter,
+                &mut render_cache.pick_grid,
+                drawing_rect,
+                curve_points,
+                screen_points,
+                minimap,
+            );
+            draw_picked_jump(
+                ui.ctx(),
+                &painter,
+                curve,
+                screen_points,
+                jumps,
+                shared_settings,
+            );
+        }
+    }
+    if let Some(progress) = selected_curve.build_progress() {
+        widgets::build_progress(ui, drawing_rect, progress, shared_settings.language);
     }
 
-    ui.allocate_rect(drawing_rect, egui::Sense::hover());
+    let response = ui.allocate_rect(drawing_rect, egui::Sense::click());
+    widgets::copy_points_menu(&response, selected_curve, shared_settings);
+    if minimap.is_some_and(|minimap| minimap_click(&response, &minimap, extent, zoom_focus)) {
+        return;
+    }
+    let clicked_cell = response
+        .interact_pointer_pos()
+        .and_then(|pos| cell_at(pos, origin, scale, extent));
+    if measurement.active {
+        if response.clicked()
+            && let Some(cell) = clicked_cell
+        {
+            measurement.click(cell);
+            ui.ctx().request_repaint();
+        }
+    } else if response.double_clicked() {
+        *zoom_focus = match zoom_focus {
+            Some(_) => None,
+            None => clicked_cell,
+        };
+        ui.ctx().request_repaint();
+    } else {
+        pick_clicked_jump(
+            ui.ctx(),
+            &response,
+            selected_curve,
+            &render_cache.cache_2d_screen,
+            jumps,
+            shared_settings.curve_long_jumps,
+        );
+    }
 }
 
-/// Convert integer curve points to screen positions within the drawing rect.
-fn build_screen_points(
+/// Pick the long jump under a single click on the canvas, or clear the pick
+/// when the click misses every jump. Does nothing unless `long_jumps` are
+/// shown.
+fn pick_clicked_jump(
+    ctx: &egui::Context,
+    response: &egui::Response,
+    selected_curve: &mut SelectedCurve,
+    screen_points: &[egui::Pos2],
+    jumps: &mut JumpPicker,
+    long_jumps: bool,
+) {
+    if long_jumps
+        && response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+        && let Some(curve) = selected_curve.ensure_cached_points()
+    {
+        let picked = pick_jump(
+            curve.points,
+            curve.adjacent,
+            screen_points,
+            jumps.hidden(),
+            pos,
+            theme::canvas_2d::JUMP_PICK_RADIUS,
+        );
+        jumps.select(curve.points, picked);
+        ctx.request_repaint();
+    }
+}
+
+/// Highlight the picked long jump, if any, and show its popup while long
+/// jumps are shown.
+fn draw_picked_jump(
+    ctx: &egui::Context,
+    painter: &egui::Painter,
+    curve: CachedCurve<'_, 2>,
+    screen_points: &[egui::Pos2],
+    jumps: &mut JumpPicker,
+    shared_settings: &crate::SharedSettings,
+) {
+    if !shared_settings.curve_long_jumps {
+        return;
+    }
+    let Some(i) = jumps.selected(curve.points) else {
+        return;
+    };
+    let ends = [screen_points[i], screen_points[i + 1]];
+    painter.line_segment(
+        ends,
+        Stroke::new(theme::canvas_2d::JUMP_WIDTH, theme::JUMP_PICK),
+    );
+    for end in ends {
+        painter.circle_stroke(
+            end,
+            theme::canvas_2d::MEASURE_MARKER_RADIUS,
+            Stroke::new(2.0, theme::JUMP_PICK),
+        );
+    }
+    jump_popup(ctx, curve, ends, i, jumps, shared_settings.language);
+}
+
+/// Show the popup describing the long jump from point `i` to `i + 1`, drawn
+/// on screen between `ends`, with buttons hiding jumps like it.
+fn jump_popup(
+    ctx: &egui::Context,
+    curve: CachedCurve<'_, 2>,
+    ends: [egui::Pos2; 2],
+    i: usize,
+    jumps: &mut JumpPicker,
+    lang: Language,
+) {
+    let (a, b) = (curve.points[i], curve.points[i + 1]);
+    let class = jump_class(a, b);
+    let length = (class[0] as f64).hypot(class[1] as f64);
+    let text = format!(
+        "A  #{i} ({}, {})\nB  #{} ({}, {})\nΔ        ({}, {})\nLength   {length:.2}\n{} {}",
+        a[0],
+        a[1],
+        i + 1,
+        b[0],
+        b[1],
+        class[0],
+        class[1],
+        class_count(curve.points, curve.adjacent, class),
+        lang.tr(Msg::SameJumps),
+    );
+    let anchor = ends[0].lerp(ends[1], 0.5) + egui::Vec2::splat(theme::popup::ANCHOR_OFFSET);
+    egui::Area::new(egui::Id::new("long_jump_popup"))
+        .movable(false)
+        .order(egui::Order::Foreground)
+        .pivot(egui::Align2::LEFT_TOP)
+        .constrain_to(ctx.content_rect())
+        .fixed_pos(anchor)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .fill(theme::SETTINGS_PANEL_BG)
+                .stroke(Stroke::new(1.0, theme::JUMP_PICK))
+                .inner_margin(egui::Margin::same(theme::popup::INNER_MARGIN))
+                .corner_radius(egui::CornerRadius::same(theme::popup::CORNER_RADIUS))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(lang.tr(Msg::LongJump))
+                                .strong()
+                                .color(theme::TEXT_HEADING),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✕").clicked() {
+                                jumps.select(curve.points, None);
+                            }
+                        });
+                    });
+                    ui.label(
+                        egui::RichText::new(text)
+                            .monospace()
+                            .size(theme::font_size::INFO)
+                            .color(theme::TEXT_PRIMARY),
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button(lang.tr(Msg::HideSimilarJumps)).clicked() {
+                            jumps.hide(class);
+                            jumps.select(curve.points, None);
+                        }
+                        if !jumps.hidden().is_empty()
+                            && ui.button(lang.tr(Msg::ShowAllJumps)).clicked()
+                        {
+                            jumps.show_all();
+                        }
+                    });
+                });
+        });
+}
+
+/// Place the drawing rect inside `available` and pick the visible grid extent.
+///
+/// The square grid is letterboxed unless `fit` is set and the canvas is
+/// further from square than [`theme::canvas_2d::FIT_MIN_ASPECT`]; then the
+/// long axis shows all `curve_size` cells and the short axis as many as fit at
+/// the same spacing.
+fn canvas_layout(available: egui::Rect, curve_size: u32, fit: bool) -> (egui::Rect, [u32; 2]) {
+    let area = available.size() * theme::canvas_2d::SIZE_FRACTION;
+    let (long, short) = (area.max_elem(), area.min_elem());
+    if !fit || long < short * theme::canvas_2d::FIT_MIN_ASPECT {
+        let side = short.max(theme::canvas_2d::MIN_SIZE);
+        let rect = egui::Rect::from_center_size(available.center(), egui::Vec2::splat(side));
+        return (rect, [curve_size; 2]);
+    }
+
+    let long = long.max(theme::canvas_2d::MIN_SIZE);
+    let margin = theme::canvas_2d::MARGIN;
+    let spacing = (long - margin * 2.0) / (curve_size - 1) as f32;
+    let short_cells = (((short - margin * 2.0) / spacing).floor() as u32 + 1).clamp(1, curve_size);
+    let short = (short_cells - 1) as f32 * spacing + margin * 2.0;
+    let (size, extent) = if area.x >= area.y {
+        (egui::vec2(long, short), [curve_size, short_cells])
+    } else {
+        (egui::vec2(short, long), [short_cells, curve_size])
+    };
+    (
+        egui::Rect::from_center_size(available.center(), size),
+        extent,
+    )
+}
+
+/// Label the curve vertex nearest the pointer, unless the pointer is off the
+/// canvas or over the overview inset.
+fn draw_vertex_hover(
+    ui: &egui::Ui,
+    painter: &egui::Painter,
+    grid: &mut ScreenGrid,
+    drawing_rect: egui::Rect,
     curve_points: &[[u32; 2]],
+    screen_points: &[egui::Pos2],
+    minimap: Option<MinimapLayout>,
+) {
+    let Some(hover) = ui.ctx().pointer_hover_pos() else {
+        return;
+    };
+    if !ui.rect_contains_pointer(drawing_rect) || minimap.is_some_and(|m| m.rect.contains(hover)) {
+        return;
+    }
+    grid.rebuild(drawing_rect, screen_points);
+    if let Some(i) = grid.nearest(screen_points, hover, theme::hover::SNAP_RADIUS, |_| 0.0) {
+        draw_hover_label(painter, screen_points[i], i, &curve_points[i]);
+    }
+}
+
+/// Placement of the overview inset shown while zoomed.
+#[derive(Debug, Clone, Copy)]
+struct MinimapLayout {
+    /// Screen rect of the inset.
+    rect: egui::Rect,
+    /// Screen position of grid cell (0, 0) inside the inset.
+    origin: egui::Pos2,
+    /// Inset spacing between neighbouring grid cells.
+    scale: f32,
+}
+
+impl MinimapLayout {
+    /// Inset rect covering the part of the grid visible in `drawing_rect`
+    /// when cell (0, 0) is drawn at `origin` with spacing `scale`.
+    fn visible_rect(&self, drawing_rect: egui::Rect, origin: egui::Pos2, scale: f32) -> egui::Rect {
+        egui::Rect::from_min_max(
+            self.origin + (drawing_rect.min - origin) / scale * self.scale,
+            self.origin + (drawing_rect.max - origin) / scale * self.scale,
+        )
+    }
+}
+
+/// Refocus the zoom on the cell clicked in the overview inset.
+///
+/// Returns whether the pointer interaction was inside the inset, in which case
+/// the canvas ignores it.
+fn minimap_click(
+    response: &egui::Response,
+    minimap: &MinimapLayout,
+    extent: [u32; 2],
+    zoom_focus: &mut Option<[u32; 2]>,
+) -> bool {
+    let Some(pos) = response
+        .interact_pointer_pos()
+        .filter(|pos| minimap.rect.contains(*pos))
+    else {
+        return false;
+    };
+    if response.clicked()
+        && let Some(cell) = cell_at(pos, minimap.origin, minimap.scale, extent)
+    {
+        *zoom_focus = Some(cell);
+        response.ctx.request_repaint();
+    }
+    true
+}
+
+/// Place the overview inset in the bottom-right corner of `drawing_rect`,
+/// scaled so a grid of `extent` cells fits inside it.
+fn minimap_layout(drawing_rect: egui::Rect, extent: [u32; 2]) -> MinimapLayout {
+    let size = theme::minimap::SIZE;
+    let rect = egui::Rect::from_min_size(
+        drawing_rect.max - egui::Vec2::splat(size + theme::minimap::MARGIN),
+        egui::Vec2::splat(size),
+    );
+    let padding = theme::minimap::PADDING;
+    let cells = extent[0].max(extent[1]).max(2) - 1;
+    MinimapLayout {
+        rect,
+        origin: rect.min + egui::Vec2::splat(padding),
+        scale: (size - padding * 2.0) / cells as f32,
+    }
+}
+
+/// Draw the whole curve into the overview inset and outline the `visible`
+/// part of the grid, given in inset coordinates.
+fn draw_minimap(
+    painter: &egui::Painter,
+    curve: CachedCurve<'_, 2>,
+    minimap: &MinimapLayout,
+    visible: egui::Rect,
+    screen_buf: &mut Vec<egui::Pos2>,
+    run_buf: &mut Vec<egui::Pos2>,
+) {
+    painter.rect_filled(minimap.rect, 3.0, theme::minimap::BACKGROUND);
+    painter.rect_stroke(
+        minimap.rect,
+        3.0,
+        Stroke::new(1.0, theme::BORDER),
+        egui::StrokeKind::Inside,
+    );
+    build_screen_points(curve.points, minimap.origin, minimap.scale, screen_buf);
+    let mut shapes = Vec::new();
+    draw_main_curve_segments(
+        &mut shapes,
+        curve,
+        screen_buf,
+        theme::minimap::LINE_WIDTH,
+        theme::curve_color_with_brightness(1.0, theme::minimap::CURVE_OPACITY),
+        JumpVisibility {
+            show: false,
+            hidden: &[],
+        },
+        run_buf,
+    );
+    painter.with_clip_rect(minimap.rect).extend(shapes);
+    painter.with_clip_rect(minimap.rect).rect_stroke(
+        visible,
+        0.0,
+        Stroke::new(1.5, theme::TEXT_HEADING),
+        egui::StrokeKind::Middle,
+    );
+}
+
+/// Shade the grid cell around each of `points`, batched into a single mesh.
+fn draw_range_highlight(painter: &egui::Painter, points: &[egui::Pos2], cell_size: f32) {
+    let mut mesh = egui::Mesh::default();
+    for &p in points {
+        mesh.add_colored_rect(
+            egui::Rect::from_center_size(p, egui::Vec2::splat(cell_size)),
+            theme::RANGE_HIGHLIGHT,
+        );
+    }
+    painter.add(egui::Shape::mesh(mesh));
+}
+
+/// Map a screen position on the canvas to the nearest grid cell, if inside `extent`.
+fn cell_at(pos: egui::Pos2, origin: egui::Pos2, scale: f32, extent: [u32; 2]) -> Option<[u32; 2]> {
+    let gx = ((pos.x - origin.x) / scale).round();
+    let gy = ((pos.y - origin.y) / scale).round();
+    if !(0.0..=(extent[0] - 1) as f32).contains(&gx)
+        || !(0.0..=(extent[1] - 1) as f32).contains(&gy)
+    {
+        return None;
+    }
+    Some([gx as u32, gy as u32])
+}
+
+/// Draw the measured sub-path, endpoint markers and the distance readout.
+fn draw_measurement(
+    painter: &egui::Painter,
+    curve_points: &[[u32; 2]],
+    screen_points: &[egui::Pos2],
+    measurement: &Measurement,
     drawing_rect: egui::Rect,
+) {
+    let [a, b] = measurement.indices(curve_points);
+    if let (Some(a), Some(b)) = (a, b) {
+        let (lo, hi) = (a.min(b), a.max(b));
+        if hi > lo {
+            painter.add(PathShape::line(
+                screen_points[lo..=hi].to_vec(),
+                Stroke::new(theme::canvas_2d::MEASURE_WIDTH, theme::MEASURE),
+            ));
+        }
+
+        let stats = MeasureStats::between(curve_points, a, b);
+        let stretch = stats
+            .stretch
+            .map_or_else(|| "—".to_string(), |s| format!("{s:.2}"));
+        let text = format!(
+            "A  #{a} ({}, {})\nB  #{b} ({}, {})\nΔindex   {}\nEuclid   {:.2}\nStretch  {stretch}",
+            curve_points[a][0],
+            curve_points[a][1],
+            curve_points[b][0],
+            curve_points[b][1],
+            stats.index_distance,
+            stats.euclidean,
+        );
+        let galley = painter.layout_no_wrap(
+            text,
+            egui::FontId::monospace(theme::font_size::INFO),
+            theme::TEXT_PRIMARY,
+        );
+        let padding = theme::spacing::MEDIUM;
+        let bg_rect = egui::Rect::from_min_size(
+            drawing_rect.min + egui::vec2(padding, padding),
+            galley.size() + egui::vec2(padding, padding) * 2.0,
+        );
+        painter.rect(
+            bg_rect,
+            theme::popup::CORNER_RADIUS,
+            theme::SETTINGS_PANEL_BG,
+            Stroke::new(1.0, theme::MEASURE),
+            egui::StrokeKind::Inside,
+        );
+        painter.galley(
+            bg_rect.min + egui::vec2(padding, padding),
+            galley,
+            theme::TEXT_PRIMARY,
+        );
+    }
+
+    for idx in [a, b].into_iter().flatten() {
+        painter.circle_stroke(
+            screen_points[idx],
+            theme::canvas_2d::MEASURE_MARKER_RADIUS,
+            Stroke::new(2.0, theme::MEASURE),
+        );
+    }
+}
+
+/// Convert integer curve points to screen positions at `origin + point * scale`.
+fn build_screen_points(
+    curve_points: &[[u32; 2]],
+    origin: egui::Pos2,
     scale: f32,
-    margin: f32,
     out: &mut Vec<egui::Pos2>,
 ) {
     out.clear();
     out.reserve(curve_points.len());
     for p in curve_points {
         out.push(egui::Pos2 {
-            x: drawing_rect.min.x + margin + p[0] as f32 * scale,
-            y: drawing_rect.min.y + margin + p[1] as f32 * scale,
+            x: origin.x + p[0] as f32 * scale,
+            y: origin.y + p[1] as f32 * scale,
+        });
+    }
+}
+
+/// Emit the glow pass and curve strokes into `out`.
+///
+/// These depend only on the curve, the view and the settings, so the result
+/// can be cached across frames; see [`crate::layer`].
+#[allow(clippy::too_many_arguments)]
+fn draw_static_curve(
+    out: &mut Vec<egui::Shape>,
+    curve: CachedCurve<'_, 2>,
+    screen_points: &[egui::Pos2],
+    shells: &[u32],
+    shared_settings: &crate::SharedSettings,
+    visibility: JumpVisibility<'_>,
+    heat: &mut Vec<f32>,
+    run: &mut Vec<egui::Pos2>,
+) {
+    if shared_settings.curve_opacity <= 0.0 || screen_points.len() < 2 {
+        return;
+    }
+    let line_color = theme::curve_color_with_brightness(1.0, shared_settings.curve_opacity);
+    let line_width = theme::canvas_2d::LINE_WIDTH;
+
+    if shared_settings.curve_glow {
+        let glow_alpha = (theme::glow::ALPHA as f32 * shared_settings.curve_opacity) as u8;
+        draw_main_curve_segments(
+            out,
+            curve,
+            screen_points,
+            line_width * theme::glow::WIDTH_MULTIPLIER,
+            curve_glow_color_alpha(1.0, glow_alpha),
+            visibility,
+            run,
+        );
+    }
+
+    let heat_mode = shared_settings.color_mode == ColorMode::Stretch && !curve.stretch.is_empty();
+    if !shells.is_empty() {
+        let shell_color = theme::shell_color_with_brightness(1.0, shared_settings.curve_opacity);
+        draw_colored_segments(out, curve, screen_points, line_width, visibility, |i| {
+            if shells[i] % 2 == 1 {
+                shell_color
+            } else {
+                line_color
+            }
+        });
+    } else if heat_mode {
+        segment_heat(curve.stretch, heat);
+        draw_colored_segments(out, curve, screen_points, line_width, visibility, |i| {
+            heat_color(heat[i], shared_settings.curve_opacity)
         });
+    } else {
+        draw_main_curve_segments(
+            out,
+            curve,
+            screen_points,
+            line_width,
+            line_color,
+            visibility,
+            run,
+        );
     }
 }
 
 /// Draw the main curve segments and half‑segments for isolated nodes.
 fn draw_main_curve_segments(
-    painter: &egui::Painter,
-    curve_points: &[[u32; 2]],
+    out: &mut Vec<egui::Shape>,
+    curve: CachedCurve<'_, 2>,
     screen_points: &[egui::Pos2],
     line_width: f32,
     line_color: egui::Color32,
-    show_long_jumps: bool,
+    jumps: JumpVisibility<'_>,
     run: &mut Vec<egui::Pos2>,
 ) {
-    if show_long_jumps {
-        painter.add(PathShape::line(
-            screen_points.to_vec(),
-            Stroke::new(line_width, line_color),
-        ));
+    if jumps.draws_all() {
+        out.push(
+            PathShape::line(screen_points.to_vec(), Stroke::new(line_width, line_color)).into(),
+        );
         return;
     }
 
     run.clear();
     let stroke = Stroke::new(line_width, line_color);
+    let curve_points = curve.points;
+    let drawn: Vec<bool> = curve
+        .adjacent
+        .iter()
+        .enumerate()
+        .map(|(i, &adjacent)| jumps.draws(curve_points, adjacent, i))
+        .collect();
     for i in 0..curve_points.len() - 1 {
-        if is_adjacent_2d(&curve_points[i], &curve_points[i + 1]) {
+        if drawn[i] {
             if run.is_empty() {
                 run.push(screen_points[i]);
             }
             run.push(screen_points[i + 1]);
         } else if !run.is_empty() {
             if run.len() >= 2 {
-                painter.add(PathShape::line(run.clone(), stroke));
+                out.push(PathShape::line(run.clone(), stroke).into());
             }
             run.clear();
         }
     }
     if !run.is_empty() && run.len() >= 2 {
-        painter.add(PathShape::line(run.clone(), stroke));
+        out.push(PathShape::line(run.clone(), stroke).into());
     }
 
     for i in 0..curve_points.len() {
-        let has_adjacent_prev = i > 0 && is_adjacent_2d(&curve_points[i - 1], &curve_points[i]);
-        let has_adjacent_next =
-            i < curve_points.len() - 1 && is_adjacent_2d(&curve_points[i], &curve_points[i + 1]);
+        let has_adjacent_prev = i > 0 && drawn[i - 1];
+        let has_adjacent_next = i < curve_points.len() - 1 && drawn[i];
         if !has_adjacent_prev && !has_adjacent_next {
             let current_pos = screen_points[i];
             let segment_end = if i == curve_points.len() - 1 && i > 0 {
@@ -313,10 +951,31 @@ fn draw_main_curve_segments(
             } else {
                 continue;
             };
-            painter.line_segment(
+            out.push(egui::Shape::line_segment(
                 [current_pos, segment_end],
                 Stroke::new(line_width, line_color),
-            );
+            ));
+        }
+    }
+}
+
+/// Draw each curve segment in the color `color_of` picks for its index.
+///
+/// Long jumps are drawn only where `jumps` allows.
+fn draw_colored_segments(
+    out: &mut Vec<egui::Shape>,
+    curve: CachedCurve<'_, 2>,
+    screen_points: &[egui::Pos2],
+    line_width: f32,
+    jumps: JumpVisibility<'_>,
+    color_of: impl Fn(usize) -> egui::Color32,
+) {
+    for (i, &adjacent) in curve.adjacent.iter().enumerate() {
+        if jumps.draws(curve.points, adjacent, i) {
+            out.push(egui::Shape::line_segment(
+                [screen_points[i], screen_points[i + 1]],
+                Stroke::new(line_width, color_of(i)),
+            ));
         }
     }
 }
@@ -408,7 +1067,7 @@ fn draw_snake_overlay(
     if tail_frac > 0.0 {
         // Check if tail segment is adjacent (for interpolation decision)
         let tail_next = (tail_segment + 1) % n;
-        let tail_adjacent = is_adjacent_2d(&curve_points[tail_segment], &curve_points[tail_next]);
+        let tail_adjacent = is_adjacent(&curve_points[tail_segment], &curve_points[tail_next]);
         if tail_adjacent {
             current_run.push(tail_screen);
         }
@@ -428,7 +1087,7 @@ fn draw_snake_overlay(
         };
 
         let is_adjacent_to_prev =
-            prev_i.is_some_and(|p| is_adjacent_2d(&curve_points[p], &curve_points[i]));
+            prev_i.is_some_and(|p| is_adjacent(&curve_points[p], &curve_points[i]));
 
         if !is_adjacent_to_prev && !current_run.is_empty() {
             // End current run and start a new one
@@ -444,7 +1103,7 @@ fn draw_snake_overlay(
     // Finish with interpolated head
     if head_frac > 0.0 {
         let head_next = (head_segment + 1) % n;
-        let head_adjacent = is_adjacent_2d(&curve_points[head_segment], &curve_points[head_next]);
+        let head_adjacent = is_adjacent(&curve_points[head_segment], &curve_points[head_next]);
         if head_adjacent && !current_run.is_empty() {
             current_run.push(head_screen);
         } else if !current_run.is_empty() {
@@ -472,3 +1131,47 @@ fn draw_head_marker_at(painter: &egui::Painter, pos: egui::Pos2) {
     let core_color = curve_glow_color(brightness);
     painter.circle_filled(pos, core_radius, core_color);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_clips_the_short_axis_of_wide_canvases() {
+        let square = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(800.0, 800.0));
+        let wide = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(1600.0, 600.0));
+
+        assert_eq!(canvas_layout(square, 64, true).1, [64, 64]);
+        assert_eq!(canvas_layout(wide, 64, false).1, [64, 64]);
+
+        let (rect, extent) = canvas_layout(wide, 64, true);
+        assert_eq!(extent[0], 64);
+        assert!(extent[1] > 1 && extent[1] < 64, "{extent:?}");
+        assert!(rect.width() > rect.height() && rect.height() <= 600.0);
+
+        let tall = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(600.0, 1600.0));
+        let (_, extent) = canvas_layout(tall, 64, true);
+        assert_eq!(extent, [extent[0], 64]);
+        assert!(extent[0] < 64);
+    }
+
+    #[test]
+    fn minimap_fits_the_grid_in_the_canvas_corner() {
+        let canvas = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 400.0));
+        let minimap = minimap_layout(canvas, [64, 32]);
+        assert!(canvas.contains_rect(minimap.rect));
+        assert!(minimap.rect.min.x > canvas.center().x && minimap.rect.min.y > canvas.center().y);
+        let far = minimap.origin + egui::vec2(63.0, 31.0) * minimap.scale;
+        assert!(minimap.rect.contains(far));
+        assert_eq!(
+            cell_at(far, minimap.origin, minimap.scale, [64, 32]),
+            Some([63, 31])
+        );
+
+        // Zoomed 4x onto the grid center, a quarter of each axis is visible.
+        let scale = 8.0;
+        let origin = canvas.center() - egui::vec2(32.0, 16.0) * scale;
+        let visible = minimap.visible_rect(canvas, origin, scale);
+        assert!((visible.width() - 50.0 * minimap.scale).abs() < 1e-3);
+    }
+}
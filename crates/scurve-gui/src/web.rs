@@ -1,4 +1,11 @@
 //! Web entrypoint and glue for the spacecurve GUI compiled to WebAssembly.
+//!
+//! The page loader reports download progress in the `#loading` element; from
+//! there the entrypoint reports the remaining startup steps in the same
+//! element, in the browser's language, and leaves an explanation in it when
+//! the browser cannot render.
+#[cfg(target_arch = "wasm32")]
+use scurve_gui::i18n::{Language, Msg};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsCast;
 #[cfg(target_arch = "wasm32")]
@@ -6,12 +13,23 @@ use wasm_bindgen::prelude::*;
 #[cfg(target_arch = "wasm32")]
 use web_sys::{HtmlCanvasElement, UrlSearchParams, window};
 
+/// Id of the page element showing startup progress and errors.
+#[cfg(target_arch = "wasm32")]
+const STATUS_ID: &str = "loading";
+
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen(start)]
 /// Launch the spacecurve GUI inside the existing canvas element when running on the web.
 pub async fn run() {
     console_error_panic_hook::set_once();
 
+    let lang = browser_language();
+    if !webgl_available() {
+        show_status(lang.tr(Msg::WebGlUnavailable));
+        return;
+    }
+    show_status(lang.tr(Msg::StartingRenderer));
+
     let dev_mode = query_flag("dev");
     let include_experimental = dev_mode || query_flag("experimental");
 
@@ -29,22 +47,92 @@ pub async fn run() {
     let gui_options = scurve_gui::GuiOptions {
         include_experimental_curves: include_experimental,
         show_dev_overlay: dev_mode,
+        demo: query_flag("demo"),
+        state: query_state(),
+        embed: query_flag("embed"),
         ..Default::default()
     };
 
-    eframe::WebRunner::new()
+    let started = eframe::WebRunner::new()
         .start(
             canvas,
             web_options,
             Box::new(move |cc| {
+                show_status(lang.tr(Msg::LoadingTheme));
                 Ok(Box::new(scurve_gui::ScurveApp::with_options(
                     cc,
                     gui_options.clone(),
                 )))
             }),
         )
-        .await
-        .expect("failed to start eframe web app");
+        .await;
+    match started {
+        Ok(()) => hide_status(),
+        Err(err) => show_status(&format!("{}: {err:?}", lang.tr(Msg::StartFailed))),
+    }
+}
+
+/// Interface language from the browser's preferred locale.
+#[cfg(target_arch = "wasm32")]
+fn browser_language() -> Language {
+    window()
+        .and_then(|w| w.navigator().language())
+        .and_then(|locale| Language::from_locale(&locale))
+        .unwrap_or_default()
+}
+
+/// Whether the browser can create a WebGL context, probed on a scratch canvas
+/// so the page canvas stays free for eframe.
+#[cfg(target_arch = "wasm32")]
+fn webgl_available() -> bool {
+    let Some(canvas) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("canvas").ok())
+        .and_then(|e| e.dyn_into::<HtmlCanvasElement>().ok())
+    else {
+        return false;
+    };
+    ["webgl2", "webgl"]
+        .into_iter()
+        .any(|kind| matches!(canvas.get_context(kind), Ok(Some(_))))
+}
+
+/// Show `text` in the status element, creating it if the page has none.
+#[cfg(target_arch = "wasm32")]
+fn show_status(text: &str) {
+    let Some(document) = window().and_then(|w| w.document()) else {
+        return;
+    };
+    let status = document.get_element_by_id(STATUS_ID).or_else(|| {
+        let status = document.create_element("div").ok()?;
+        status.set_id(STATUS_ID);
+        status.set_class_name("loading");
+        document.body()?.append_child(&status).ok()?;
+        Some(status)
+    });
+    if let Some(status) = status {
+        status.set_text_content(Some(text));
+    }
+}
+
+/// Remove the status element once the app is running.
+#[cfg(target_arch = "wasm32")]
+fn hide_status() {
+    if let Some(status) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(STATUS_ID))
+    {
+        status.remove();
+    }
+}
+
+/// State spec from the page query string; invalid specs are ignored.
+#[cfg(target_arch = "wasm32")]
+fn query_state() -> Option<scurve_gui::share::StateSpec> {
+    window()
+        .map(|w| w.location())
+        .and_then(|loc| loc.search().ok())
+        .and_then(|search| search.parse().ok())
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -2,9 +2,26 @@ use egui::{
     self, Response, Slider,
     epaint::{Shadow, Stroke},
 };
-use spacecurve::curve_from_name;
+use spacecurve::{
+    curve_from_name,
+    metrics::{self, CALIBRATION_SIZES, Calibration},
+    registry,
+};
 
-use crate::theme;
+use crate::{
+    export::{PointFormat, format_points},
+    heatmap::ColorMode,
+    i18n::{Language, Msg},
+    rotation::RotationAxis,
+    selection::CurveSelection,
+    theme::{
+        self,
+        canvas_3d::{
+            CAMERA_TILT_MAX, DEPTH_BINS_MAX, DEPTH_BINS_MIN, DRAG_DECIMATION_MAX,
+            PERSPECTIVE_DISTANCE_MAX, PERSPECTIVE_DISTANCE_MIN,
+        },
+    },
+};
 
 /// Add a slider with themed rail and fill colors for better visibility.
 pub fn themed_slider(ui: &mut egui::Ui, slider: Slider<'_>) -> Response {
@@ -112,38 +129,34 @@ fn slider_row_with_value(
 }
 
 /// Common curve selector widget with label included.
-pub fn curve_selector(
+pub fn curve_selector<const D: usize>(
     ui: &mut egui::Ui,
-    curve_name: &mut String,
+    selection: &mut CurveSelection<D>,
     available_curves: &[&str],
     id_salt: &str,
-    info_open: &mut bool,
-    dim: u32,
-    size: u32,
+    lang: Language,
 ) {
-    ui.label("Curve:");
-    curve_selector_combo(
-        ui,
-        curve_name,
-        available_curves,
-        id_salt,
-        info_open,
-        dim,
-        size,
-    );
+    ui.label(lang.tr(Msg::Curve));
+    curve_selector_combo(ui, selection, available_curves, id_salt, lang);
 }
 
 /// Curve selector combo box only (without label).
 /// Use this when you want to style the label separately.
-pub fn curve_selector_combo(
+pub fn curve_selector_combo<const D: usize>(
     ui: &mut egui::Ui,
-    curve_name: &mut String,
+    selection: &mut CurveSelection<D>,
     available_curves: &[&str],
     id_salt: &str,
-    info_open: &mut bool,
-    dim: u32,
-    size: u32,
+    lang: Language,
 ) {
+    let CurveSelection {
+        name: curve_name,
+        info_open,
+        size,
+        ..
+    } = selection;
+    let (dim, size) = (D as u32, *size);
+
     // Track if any curve was selected
     let mut curve_was_selected = false;
 
@@ -151,6 +164,9 @@ pub fn curve_selector_combo(
         .selected_text(&*curve_name)
         .show_ui(ui, |ui| {
             for &name in available_curves {
+                if !registry::find(name).is_some_and(|entry| entry.accepts_dimension(dim)) {
+                    continue;
+                }
                 if ui
                     .selectable_value(curve_name, name.to_string(), name)
                     .clicked()
@@ -183,6 +199,7 @@ pub fn curve_selector_combo(
                 curve_name,
                 dim,
                 size,
+                lang,
                 curve_was_selected,
                 combo_response: &combo_response.response,
                 info_button: &info_button,
@@ -203,6 +220,8 @@ struct InfoPaneArgs<'a> {
     dim: u32,
     /// Grid size used when querying pattern info.
     size: u32,
+    /// Language for the pane's labels.
+    lang: Language,
     /// Whether a selection just occurred in the combo box.
     curve_was_selected: bool,
     /// Response for the combo box area (used for outside‑click detection).
@@ -219,6 +238,7 @@ fn draw_curve_info_pane(ctx: &egui::Context, args: InfoPaneArgs<'_>) {
         curve_name,
         dim,
         size,
+        lang,
         curve_was_selected,
         combo_response,
         info_button,
@@ -247,7 +267,7 @@ fn draw_curve_info_pane(ctx: &egui::Context, args: InfoPaneArgs<'_>) {
                 .corner_radius(egui::CornerRadius::same(theme::popup::CORNER_RADIUS))
                 .show(ui, |ui| {
                     ui.set_width(theme::popup::INFO_PANE_WIDTH);
-                    render_info_popup_contents(ui, curve_name, dim, size, info_open);
+                    render_info_popup_contents(ui, curve_name, dim, size, lang, info_open);
                 });
         });
 
@@ -277,6 +297,7 @@ fn render_info_popup_contents(
     curve_name: &str,
     dim: u32,
     size: u32,
+    lang: Language,
     info_open: &mut bool,
 ) {
     if let Ok(curve) = curve_from_name(curve_name, dim, size) {
@@ -313,9 +334,17 @@ fn render_info_popup_contents(
                         .color(ui.visuals().text_color().gamma_multiply(0.9)),
                 );
             });
+        if let Some(entry) = registry::find(curve_name) {
+            ui.add_space(theme::spacing::SMALL);
+            render_cost_section(ui, entry, dim, lang);
+        }
     } else {
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Curve Info").heading().strong());
+            ui.label(
+                egui::RichText::new(lang.tr(Msg::CurveInfo))
+                    .heading()
+                    .strong(),
+            );
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui
                     .add(
@@ -333,34 +362,236 @@ fn render_info_popup_contents(
         ui.add(egui::Separator::default().spacing(theme::spacing::MEDIUM));
         ui.add_space(theme::spacing::SMALL + 2.0);
         ui.label(
-            egui::RichText::new("Unable to construct curve for info.")
+            egui::RichText::new(lang.tr(Msg::CurveInfoUnavailable))
                 .italics()
                 .color(ui.visuals().warn_fg_color),
         );
     }
 }
 
-/// Common size selector widget for 2D curves
-pub fn size_selector_2d(ui: &mut egui::Ui, size: &mut u32, id_salt: &str) {
-    egui::ComboBox::from_id_salt(id_salt)
-        .selected_text(format!("{size}×{size}"))
-        .show_ui(ui, |ui| {
-            for &s in &[4, 8, 16, 32, 64, 128] {
-                ui.selectable_value(size, s, format!("{s}×{s}"));
+/// Complexity note plus on-demand latency calibration for a curve.
+///
+/// Results are kept in egui's temporary memory per curve and dimension, so
+/// they survive closing and reopening the popup.
+fn render_cost_section(ui: &mut egui::Ui, entry: &registry::CurveEntry, dim: u32, lang: Language) {
+    let results_id = egui::Id::new(("curve_calibration", entry.key, dim));
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(lang.tr(Msg::Cost)).strong());
+        ui.label(
+            egui::RichText::new(entry.complexity)
+                .size(theme::font_size::INFO)
+                .color(theme::TEXT_DIM),
+        );
+    });
+    if ui.small_button(lang.tr(Msg::Calibrate)).clicked() {
+        let results = metrics::calibrate_entry(entry, dim, &CALIBRATION_SIZES);
+        ui.data_mut(|d| d.insert_temp(results_id, results));
+    }
+    let Some(results) = ui.data(|d| d.get_temp::<Vec<Calibration>>(results_id)) else {
+        return;
+    };
+    egui::Grid::new(results_id)
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(lang.tr(Msg::Size).trim_end_matches(':'));
+            ui.label(lang.tr(Msg::PointLatency));
+            ui.label(lang.tr(Msg::IndexLatency));
+            ui.end_row();
+            for c in &results {
+                ui.label(format!("{}^{dim}", c.size));
+                ui.label(format!("{:.1}", c.point_ns));
+                ui.label(format!("{:.1}", c.index_ns));
+                ui.end_row();
             }
         });
 }
 
-/// Common size selector widget for 3D curves
-pub fn size_selector_3d(ui: &mut egui::Ui, size: &mut u32, id_salt: &str) {
+/// Smallest grid side offered in the size selectors.
+const MIN_SIZE: u32 = 4;
+
+/// Largest grid side offered for 2D curves.
+const MAX_SIZE_2D: u32 = 128;
+
+/// Largest grid side offered for 3D curves (smaller due to cubic growth).
+const MAX_SIZE_3D: u32 = 32;
+
+/// Grid sizes offered for the curve called `name` in `D` dimensions.
+///
+/// Powers of two, plus the sizes halfway between them (6, 12, 24, …) for
+/// curves that take any size, up to the pane's maximum. Sizes the curve
+/// rejects are left out.
+pub fn size_choices<const D: usize>(name: &str) -> Vec<u32> {
+    let max = if D == 3 { MAX_SIZE_3D } else { MAX_SIZE_2D };
+    let Some(entry) = registry::find(name) else {
+        return Vec::new();
+    };
+    entry
+        .valid_sizes(D as u32)
+        .sizes_in(MIN_SIZE..=max)
+        .into_iter()
+        .filter(|&size| matches!(size >> size.trailing_zeros(), 1 | 3))
+        .collect()
+}
+
+/// Common size selector widget for 2D and 3D curves.
+///
+/// Offers the [`size_choices`] of the selected curve. If the current size is
+/// invalid (for example from a pasted state), the reason is shown inline next
+/// to quick buttons for the nearest valid sizes.
+pub fn size_selector<const D: usize>(
+    ui: &mut egui::Ui,
+    selection: &mut CurveSelection<D>,
+    id_salt: &str,
+    lang: Language,
+) {
     egui::ComboBox::from_id_salt(id_salt)
-        .selected_text(format!("{size}×{size}×{size}"))
+        .selected_text(size_label::<D>(selection.size))
         .show_ui(ui, |ui| {
-            for &s in &[4, 8, 16, 32] {
-                // Smaller max size for 3D due to cubic growth
-                ui.selectable_value(size, s, format!("{s}×{s}×{s}"));
+            for s in size_choices::<D>(&selection.name) {
+                if ui
+                    .selectable_label(selection.size == s, size_label::<D>(s))
+                    .clicked()
+                {
+                    selection.request_size(s);
+                }
             }
         });
+
+    if let Some(pending) = selection.pending_size {
+        large_size_prompt(ui, selection, pending, lang);
+        return;
+    }
+
+    let Some(entry) = registry::find(&selection.name) else {
+        return;
+    };
+    let Err(error) = (entry.build_spec)(&[selection.size; D]) else {
+        return;
+    };
+    ui.label(
+        egui::RichText::new(format!("⚠ {}", lang.tr(Msg::UnsupportedSize)))
+            .color(theme::TOAST_ERROR)
+            .size(theme::font_size::INFO),
+    )
+    .on_hover_text(format!("{error}\n{}", entry.constraints));
+    let (below, above) = entry.nearest_valid_sizes(D as u32, selection.size);
+    for s in [below, above].into_iter().flatten() {
+        if ui.small_button(size_label::<D>(s)).clicked() {
+            selection.size = s;
+        }
+    }
+}
+
+/// Grid size probed to estimate how long a large curve takes to build.
+const ESTIMATE_PROBE_SIZE: u32 = 8;
+
+/// Inline point count and build estimate for a pending large size, with
+/// buttons to apply or discard it.
+fn large_size_prompt<const D: usize>(
+    ui: &mut egui::Ui,
+    selection: &mut CurveSelection<D>,
+    pending: u32,
+    lang: Language,
+) {
+    let points = u64::from(pending).saturating_pow(D as u32);
+    let mut text = format!(
+        "⚠ {} {}: {points} {}",
+        size_label::<D>(pending),
+        lang.tr(Msg::LargeSize),
+        lang.tr(Msg::Points)
+    );
+    if let Some(ns) = point_cost_ns::<D>(ui, &selection.name) {
+        let ms = ns * points as f64 / 1e6;
+        text.push_str(&format!(", ~{ms:.0} ms {}", lang.tr(Msg::BuildTime)));
+    }
+    ui.label(
+        egui::RichText::new(text)
+            .color(theme::TOAST_ERROR)
+            .size(theme::font_size::INFO),
+    )
+    .on_hover_text(format!(
+        "{} {}",
+        points.saturating_sub(1),
+        lang.tr(Msg::SegmentsPerFrame)
+    ));
+    if ui.small_button(lang.tr(Msg::Apply)).clicked() {
+        selection.size = pending;
+        selection.pending_size = None;
+    }
+    if ui.small_button(lang.tr(Msg::Cancel)).clicked() {
+        selection.pending_size = None;
+    }
+}
+
+/// Mean `point` latency of the curve called `name`, calibrated once at
+/// [`ESTIMATE_PROBE_SIZE`] and kept in egui's temporary data.
+fn point_cost_ns<const D: usize>(ui: &egui::Ui, name: &str) -> Option<f64> {
+    let entry = registry::find(name)?;
+    let id = egui::Id::new(("size_estimate", entry.key, D));
+    if let Some(ns) = ui.data(|d| d.get_temp::<Option<f64>>(id)) {
+        return ns;
+    }
+    let ns = metrics::calibrate_entry(entry, D as u32, &[ESTIMATE_PROBE_SIZE])
+        .first()
+        .map(|c| c.point_ns);
+    ui.data_mut(|d| d.insert_temp(id, ns));
+    ns
+}
+
+/// Display label for a grid of side `size` in `D` dimensions, e.g. `8×8×8`.
+fn size_label<const D: usize>(size: u32) -> String {
+    vec![size.to_string(); D].join("×")
+}
+
+/// Attach a context menu to a curve canvas that copies the rendered points.
+///
+/// When the index range highlight is enabled only that range is copied.
+pub fn copy_points_menu<const D: usize>(
+    response: &Response,
+    selection: &mut CurveSelection<D>,
+    shared: &crate::SharedSettings,
+) {
+    let lang = shared.language;
+    response.context_menu(|ui| {
+        for format in PointFormat::ALL {
+            let label = match format {
+                PointFormat::Csv => Msg::CopyCsv,
+                PointFormat::Json => Msg::CopyJson,
+            };
+            if !ui.button(lang.tr(label)).clicked() {
+                continue;
+            }
+            if let Some(curve) = selection.ensure_cached_points() {
+                let range = shared
+                    .highlight_range(curve.points.len())
+                    .unwrap_or(0..=curve.points.len().saturating_sub(1));
+                let start = *range.start();
+                let points = curve.points.get(range).unwrap_or_default();
+                ui.ctx().copy_text(format_points(points, start, format));
+            }
+            ui.close();
+        }
+    });
+}
+
+/// Centered progress bar shown in `rect` while a curve's points are cached
+/// over several frames, keeping frames coming until the build finishes.
+pub fn build_progress(ui: &mut egui::Ui, rect: egui::Rect, fraction: f32, lang: Language) {
+    let width = theme::build_progress::WIDTH.min(rect.width());
+    let bar = egui::Rect::from_center_size(
+        rect.center(),
+        egui::vec2(width, ui.spacing().interact_size.y),
+    );
+    ui.put(
+        bar,
+        egui::ProgressBar::new(fraction).text(format!(
+            "{} {:.0}%",
+            lang.tr(Msg::BuildingCurve),
+            fraction * 100.0
+        )),
+    );
+    ui.ctx().request_repaint();
 }
 
 /// Common pause/play button widget
@@ -400,6 +631,7 @@ fn settings_panel_content(
     // Logarithmic opacity slider constant - maps opacity (0.01 to 1.0) to log scale (0 to 100)
     const LOG_MIN: f32 = -4.605;
 
+    let lang = shared.language;
     ui.spacing_mut().item_spacing.y = theme::spacing::MEDIUM - 2.0;
 
     // Curve controls (no top-level heading per request)
@@ -412,7 +644,7 @@ fn settings_panel_content(
 
     let response = slider_row(
         ui,
-        "Opacity",
+        lang.tr(Msg::Opacity),
         egui::Slider::new(&mut log_value, 0.0..=100.0)
             .step_by(1.0)
             .suffix("%")
@@ -433,32 +665,90 @@ fn settings_panel_content(
             (LOG_MIN + (log_value / 100.0) * (0.0 - LOG_MIN)).exp()
         };
     }
+    neon_checkbox(ui, &mut shared.curve_glow, lang.tr(Msg::Glow));
+    neon_checkbox(ui, &mut shared.onion_shells, lang.tr(Msg::OnionShells))
+        .on_hover_text(lang.tr(Msg::OnionShellsHint));
+    if !show_spin_speed {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(lang.tr(Msg::ColorMode))
+                    .color(theme::TEXT_BODY)
+                    .size(theme::font_size::LABEL),
+            );
+            for mode in ColorMode::ALL {
+                let (label, hint) = match mode {
+                    ColorMode::Solid => (Msg::ColorSolid, None),
+                    ColorMode::Stretch => (Msg::ColorStretch, Some(Msg::ColorStretchHint)),
+                };
+                let response = ui.selectable_value(&mut shared.color_mode, mode, lang.tr(label));
+                if let Some(hint) = hint {
+                    response.on_hover_text(lang.tr(hint));
+                }
+            }
+        });
+        neon_checkbox(ui, &mut shared.cache_static_2d, lang.tr(Msg::CacheCurve))
+            .on_hover_text(lang.tr(Msg::CacheCurveHint));
+    }
+
+    ui.add_space(theme::spacing::MEDIUM - 2.0);
+    ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
+
+    section_header(ui, lang.tr(Msg::Selection));
+    neon_checkbox(
+        ui,
+        &mut shared.lock_selections,
+        lang.tr(Msg::LockSelections),
+    );
+
+    ui.add_space(theme::spacing::MEDIUM - 2.0);
+    ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
+
+    section_header(ui, lang.tr(Msg::IndexRange));
+    neon_checkbox(ui, &mut shared.range_enabled, lang.tr(Msg::HighlightRange));
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(lang.tr(Msg::RangeFrom))
+                .size(theme::font_size::INFO)
+                .color(theme::TEXT_SECONDARY),
+        );
+        ui.add(egui::DragValue::new(&mut shared.range_min).speed(1.0));
+        ui.label(
+            egui::RichText::new(lang.tr(Msg::RangeTo))
+                .size(theme::font_size::INFO)
+                .color(theme::TEXT_SECONDARY),
+        );
+        ui.add(egui::DragValue::new(&mut shared.range_max).speed(1.0));
+    });
+    if shared.range_min > shared.range_max {
+        shared.range_max = shared.range_min;
+    }
 
     ui.add_space(theme::spacing::MEDIUM - 2.0);
     ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
 
-    section_header(ui, "Long Jumps");
-    neon_checkbox(ui, &mut shared.curve_long_jumps, "Show on curve");
-    neon_checkbox(ui, &mut shared.snake_long_jumps, "Show on snake");
+    section_header(ui, lang.tr(Msg::LongJumps));
+    neon_checkbox(ui, &mut shared.curve_long_jumps, lang.tr(Msg::ShowOnCurve))
+        .on_hover_text(lang.tr(Msg::LongJumpHint));
+    neon_checkbox(ui, &mut shared.snake_long_jumps, lang.tr(Msg::ShowOnSnake));
 
     ui.add_space(theme::spacing::MEDIUM - 2.0);
     ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
 
-    section_header(ui, "Snake");
+    section_header(ui, lang.tr(Msg::Snake));
 
-    neon_checkbox(ui, &mut shared.snake_enabled, "Enable snake overlay");
+    neon_checkbox(ui, &mut shared.snake_enabled, lang.tr(Msg::EnableSnake));
 
     let snake_length_value = shared.snake_length;
     slider_row_with_value(
         ui,
-        "Length",
+        lang.tr(Msg::Length),
         egui::Slider::new(&mut shared.snake_length, 0.0..=50.0).step_by(0.5),
         format!("{:>6.1}%", snake_length_value),
     );
     let snake_value = shared.snake_speed;
     slider_row_with_value(
         ui,
-        "Speed",
+        lang.tr(Msg::Speed),
         egui::Slider::new(&mut shared.snake_speed, 1.0..=200.0).step_by(1.0),
         format!("{:>6.0} seg/s", snake_value.round()),
     );
@@ -466,15 +756,86 @@ fn settings_panel_content(
     if show_spin_speed {
         ui.add_space(theme::spacing::MEDIUM - 2.0);
         ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
-        section_header(ui, "3D rotation");
+        section_header(ui, lang.tr(Msg::Rotation3d));
         let spin_value = shared.spin_speed;
         slider_row_with_value(
             ui,
-            "Speed",
+            lang.tr(Msg::Speed),
             egui::Slider::new(&mut shared.spin_speed, 0.0..=100.0).step_by(1.0),
             format!("{:>5.0}%", spin_value.round()),
         );
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(lang.tr(Msg::Axis))
+                    .color(theme::TEXT_BODY)
+                    .size(theme::font_size::LABEL),
+            );
+            for axis in RotationAxis::ALL {
+                let label = match axis {
+                    RotationAxis::X => "X",
+                    RotationAxis::Y => "Y",
+                    RotationAxis::Z => "Z",
+                    RotationAxis::Tumble => lang.tr(Msg::Tumble),
+                };
+                ui.selectable_value(&mut shared.rotation_axis, axis, label);
+            }
+        });
+
+        ui.add_space(theme::spacing::MEDIUM - 2.0);
+        ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
+        section_header(ui, lang.tr(Msg::Camera));
+        let tilt_value = shared.camera_tilt.to_degrees();
+        slider_row_with_value(
+            ui,
+            lang.tr(Msg::Tilt),
+            egui::Slider::new(&mut shared.camera_tilt, -CAMERA_TILT_MAX..=CAMERA_TILT_MAX),
+            format!("{:>5.0}°", tilt_value.round()),
+        );
+        let distance_value = shared.perspective_distance;
+        slider_row_with_value(
+            ui,
+            lang.tr(Msg::Distance),
+            egui::Slider::new(
+                &mut shared.perspective_distance,
+                PERSPECTIVE_DISTANCE_MIN..=PERSPECTIVE_DISTANCE_MAX,
+            )
+            .logarithmic(true),
+            format!("{distance_value:>6.1}"),
+        );
+        let decimation_value = shared.drag_decimation;
+        slider_row_with_value(
+            ui,
+            lang.tr(Msg::DragDetail),
+            egui::Slider::new(&mut shared.drag_decimation, 1..=DRAG_DECIMATION_MAX),
+            format!("1/{decimation_value}"),
+        )
+        .on_hover_text(lang.tr(Msg::DragDetailHint));
+        let bins_value = shared.depth_bins;
+        slider_row_with_value(
+            ui,
+            lang.tr(Msg::DepthBins),
+            egui::Slider::new(&mut shared.depth_bins, DEPTH_BINS_MIN..=DEPTH_BINS_MAX)
+                .logarithmic(true),
+            format!("{bins_value:>5}"),
+        )
+        .on_hover_text(lang.tr(Msg::DepthBinsHint));
+        neon_checkbox(
+            ui,
+            &mut shared.exact_depth_sort,
+            lang.tr(Msg::ExactDepthSort),
+        )
+        .on_hover_text(lang.tr(Msg::ExactDepthSortHint));
     }
+
+    ui.add_space(theme::spacing::MEDIUM - 2.0);
+    ui.add(egui::Separator::default().spacing(theme::spacing::SMALL));
+
+    section_header(ui, lang.tr(Msg::Language));
+    ui.horizontal(|ui| {
+        for option in Language::ALL {
+            ui.selectable_value(&mut shared.language, option, option.native_name());
+        }
+    });
 }
 
 /// Settings dropdown widget that appears as an overlay.
@@ -558,3 +919,20 @@ pub fn settings_dropdown(
         *settings_pos = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_choices_follow_the_curve() {
+        assert_eq!(size_choices::<2>("hilbert"), [4, 8, 16, 32, 64, 128]);
+        assert_eq!(
+            size_choices::<2>("scan"),
+            [4, 6, 8, 12, 16, 24, 32, 48, 64, 96, 128]
+        );
+        assert_eq!(size_choices::<3>("hilbert"), [4, 8, 16, 32]);
+        assert!(size_choices::<3>("gilbert").is_empty());
+        assert!(size_choices::<2>("no-such-curve").is_empty());
+    }
+}
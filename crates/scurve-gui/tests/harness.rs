@@ -0,0 +1,68 @@
+//! Integration tests that render the app headlessly.
+
+#[cfg(test)]
+mod tests {
+    use egui::{Event, Key, Modifiers, Vec2};
+    use scurve_gui::{GuiOptions, test_harness::Harness};
+
+    /// Window size used by the tests, in points.
+    const SIZE: Vec2 = Vec2::new(400.0, 300.0);
+
+    // Every frame covers the window and the 2D pane draws more than a flat fill.
+    #[test]
+    fn frames_render_the_window() {
+        let frames = Harness::new(GuiOptions::default(), SIZE).run(2, Vec::new());
+        assert_eq!(frames.len(), 2);
+        let image = &frames[1];
+        assert_eq!(image.size, [400, 300]);
+        assert!(image.pixels.iter().any(|p| *p != image.pixels[0]));
+    }
+
+    // Identical inputs produce identical images, so goldens stay stable.
+    #[test]
+    fn frames_are_deterministic() {
+        let render = || Harness::new(GuiOptions::default(), SIZE).run(3, Vec::new());
+        assert_eq!(render(), render());
+    }
+
+    // Injected input reaches the app: Ctrl+K opens the command palette.
+    #[test]
+    fn injected_events_change_the_frame() {
+        let mut harness = Harness::new(GuiOptions::default(), SIZE);
+        let before = harness.run(2, Vec::new()).pop().unwrap();
+        let shortcut = Event::Key {
+            key: Key::K,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::COMMAND,
+        };
+        let after = harness.run(2, vec![shortcut]).pop().unwrap();
+        assert_ne!(before, after);
+    }
+
+    // Embedded views drop the menu bar and ignore app shortcuts like Ctrl+K.
+    #[test]
+    fn embedded_views_show_only_the_canvas() {
+        let embed = || GuiOptions {
+            embed: true,
+            ..GuiOptions::default()
+        };
+        let shortcut = Event::Key {
+            key: Key::K,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::COMMAND,
+        };
+        let plain = Harness::new(embed(), SIZE).run(3, Vec::new()).pop();
+        let mut harness = Harness::new(embed(), SIZE);
+        harness.run(1, Vec::new());
+        let pressed = harness.run(2, vec![shortcut]).pop();
+        assert_eq!(plain, pressed);
+        let full = Harness::new(GuiOptions::default(), SIZE)
+            .run(3, Vec::new())
+            .pop();
+        assert_ne!(plain, full);
+    }
+}
@@ -0,0 +1,114 @@
+//! Resumable batch rendering.
+//!
+//! Commands that write one output per item, such as a `sweep` into a
+//! directory, decide up front which outputs to render. A batch refuses to
+//! overwrite existing files unless told how to treat them: `--skip-existing`
+//! keeps them and renders only the missing ones, so an interrupted job picks up
+//! where it stopped, and `--force` renders everything again.
+
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::Args;
+use scurve_gui::i18n::Language;
+
+use crate::i18n::{Msg, tr};
+
+/// How a batch treats outputs that already exist.
+#[derive(Args, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchArgs {
+    /// Keep existing outputs and render only the missing ones.
+    #[arg(
+        long = "skip-existing",
+        conflicts_with = "force",
+        help = "Batch outputs: keep files that already exist and render only the missing ones"
+    )]
+    pub skip_existing: bool,
+
+    /// Overwrite existing outputs.
+    #[arg(
+        long = "force",
+        help = "Batch outputs: overwrite files that already exist"
+    )]
+    pub force: bool,
+}
+
+impl BatchArgs {
+    /// Whether either flag was given.
+    pub fn any(self) -> bool {
+        self.skip_existing || self.force
+    }
+
+    /// Split `items` into those to render, in order, and the number skipped
+    /// because their output exists.
+    ///
+    /// Without either flag, an existing output is an error.
+    pub fn select<T>(self, items: Vec<(T, PathBuf)>) -> Result<(Vec<(T, PathBuf)>, usize)> {
+        if self.force {
+            return Ok((items, 0));
+        }
+        let total = items.len();
+        let (existing, pending): (Vec<_>, Vec<_>) =
+            items.into_iter().partition(|(_, path)| path.exists());
+        if let Some((_, first)) = existing.first()
+            && !self.skip_existing
+        {
+            bail!(
+                "{} of {total} outputs already exist (first: {}); pass --skip-existing to render only the missing ones or --force to overwrite them",
+                existing.len(),
+                first.display()
+            );
+        }
+        Ok((pending, existing.len()))
+    }
+}
+
+/// One line counting the outputs a batch rendered and skipped, in `lang`.
+pub fn summary(lang: Language, rendered: usize, skipped: usize) -> String {
+    format!(
+        "{}: {rendered}, {}: {skipped}",
+        tr(lang, Msg::Rendered),
+        tr(lang, Msg::SkippedExisting)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn existing_outputs_need_a_flag() {
+        let td = tempdir().unwrap();
+        let [a, b, c] = ["a.png", "b.png", "c.png"].map(|name| td.path().join(name));
+        fs::write(&b, []).unwrap();
+        let items = || vec![(1, a.clone()), (2, b.clone()), (3, c.clone())];
+
+        let err = BatchArgs::default().select(items()).unwrap_err();
+        assert!(err.to_string().starts_with("1 of 3 outputs already exist"));
+
+        let skip = BatchArgs {
+            skip_existing: true,
+            force: false,
+        };
+        assert_eq!(
+            skip.select(items()).unwrap(),
+            (vec![(1, a.clone()), (3, c.clone())], 1)
+        );
+        let force = BatchArgs {
+            skip_existing: false,
+            force: true,
+        };
+        assert_eq!(force.select(items()).unwrap(), (items(), 0));
+
+        fs::remove_file(&b).unwrap();
+        assert_eq!(BatchArgs::default().select(items()).unwrap(), (items(), 0));
+        assert_eq!(
+            summary(Language::English, 2, 1),
+            "Rendered: 2, skipped (already existed): 1"
+        );
+    }
+}
@@ -3,16 +3,38 @@
 //! These functions implement the top‑level subcommands and write the resulting
 //! images to disk.
 
-use std::{fs::File, ops::Range, path::Path};
+use std::{
+    array,
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    iter,
+    ops::Range,
+    path::Path,
+};
 
 use anyhow::{Result, anyhow, bail};
 use gif::{Encoder, Frame, Repeat};
-use spacecurve::{curve_from_name, registry};
+use image::imageops;
+use spacecurve::{
+    SpaceCurve,
+    curves::zorder::ZOrder,
+    error,
+    mapping::{self, Fit},
+    point::Point,
+    raster,
+    registry::{self, CurveEntry, CurveId, CurveVisitor},
+    table::{self, PointTable},
+};
 
 use crate::map::{
-    MapPalette, StrokeOptions, draw_chunk_overlay, render_chunk_image, render_map_image,
+    MapPalette, StrokeOptions, draw_chunk_overlay, draw_partial_step, draw_trail, render_map_image,
+    render_morph_image,
 };
 
+/// Number of curve points computed per batch when rendering `vis` images.
+const VIS_BATCH: u64 = 4096;
+
 /// Black color for 0x00.
 const COLOR_BLACK: image::Rgba<u8> = image::Rgba([0, 0, 0, 0xff]);
 /// White color for 0xFF.
@@ -24,20 +46,258 @@ const COLOR_BLUE: image::Rgba<u8> = image::Rgba([0x10, 0x72, 0xb8, 0xff]);
 /// Red color for extended/other characters.
 const COLOR_RED: image::Rgba<u8> = image::Rgba([0xe4, 0x1a, 0x1c, 0xff]);
 
+/// Byte classes drawn in distinct colors by `vis`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteClass {
+    /// 0x00.
+    Zero,
+    /// 0xff.
+    Ones,
+    /// Low ASCII control characters.
+    Control,
+    /// Printable ASCII.
+    Printable,
+    /// Extended ASCII and everything else.
+    Other,
+}
+
+impl ByteClass {
+    /// Every class, in the order of [`ByteStats::classes`].
+    pub const ALL: [Self; 5] = [
+        Self::Zero,
+        Self::Ones,
+        Self::Control,
+        Self::Printable,
+        Self::Other,
+    ];
+
+    /// Class of `byte`.
+    pub fn of(byte: u8) -> Self {
+        match byte {
+            0x00 => Self::Zero,
+            0xff => Self::Ones,
+            // Low ASCII control chars approx range
+            b if b < 31 => Self::Control,
+            // Printable ASCII approx range
+            b if (32..127).contains(&b) => Self::Printable,
+            // Extended ASCII / unprintable
+            _ => Self::Other,
+        }
+    }
+
+    /// Lowercase name, as used in `vis` reports.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::Ones => "ff",
+            Self::Control => "control",
+            Self::Printable => "printable",
+            Self::Other => "other",
+        }
+    }
+}
+
 /// Map a byte value to a representative RGBA color used by `vis`.
 fn byte_to_color(byte: u8) -> image::Rgba<u8> {
-    match byte {
-        0x00 => COLOR_BLACK,
-        0xff => COLOR_WHITE,
-        // Low ASCII control chars approx range
-        b if b < 31 => COLOR_GREEN,
-        // Printable ASCII approx range
-        b if (32..127).contains(&b) => COLOR_BLUE,
-        // Extended ASCII / unprintable
-        _ => COLOR_RED,
+    match ByteClass::of(byte) {
+        ByteClass::Zero => COLOR_BLACK,
+        ByteClass::Ones => COLOR_WHITE,
+        ByteClass::Control => COLOR_GREEN,
+        ByteClass::Printable => COLOR_BLUE,
+        ByteClass::Other => COLOR_RED,
+    }
+}
+
+/// Entropy, in bits per byte, at and above which a region counts as high
+/// entropy; compressed and encrypted data typically lands here.
+pub const HIGH_ENTROPY: f64 = 7.0;
+
+/// Composition of a run of bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ByteStats {
+    /// Offset of the first byte in the file.
+    pub offset: u64,
+    /// Number of bytes.
+    pub len: u64,
+    /// Shannon entropy in bits per byte, from 0 (one repeated value) to 8.
+    pub entropy: f64,
+    /// Fraction of bytes in each class, in [`ByteClass::ALL`] order.
+    pub classes: [f64; 5],
+}
+
+impl ByteStats {
+    /// Statistics of the bytes starting at `offset` whose values occur
+    /// `counts` times.
+    fn from_counts(offset: u64, counts: &[u64; 256]) -> Self {
+        let len: u64 = counts.iter().sum();
+        let total = len.max(1) as f64;
+        let entropy = counts
+            .iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| n as f64 / total * (total / n as f64).log2())
+            .sum();
+        let mut classes = [0.0; 5];
+        for (byte, &n) in counts.iter().enumerate() {
+            classes[ByteClass::of(byte as u8) as usize] += n as f64 / total;
+        }
+        Self {
+            offset,
+            len,
+            entropy,
+            classes,
+        }
     }
 }
 
+/// Statistics gathered by [`vis`] when a report is requested.
+pub struct VisReport {
+    /// The whole file.
+    pub file: ByteStats,
+    /// Bytes per region; the last region may be shorter.
+    pub region_len: u64,
+    /// Consecutive regions of `region_len` bytes, in file order.
+    pub regions: Vec<ByteStats>,
+}
+
+impl VisReport {
+    /// Byte ranges covered by consecutive regions with an entropy of at
+    /// least [`HIGH_ENTROPY`].
+    pub fn high_entropy_runs(&self) -> Vec<Range<u64>> {
+        let mut runs: Vec<Range<u64>> = Vec::new();
+        for region in self.regions.iter().filter(|r| r.entropy >= HIGH_ENTROPY) {
+            let end = region.offset + region.len;
+            match runs.last_mut() {
+                Some(run) if run.end == region.offset => run.end = end,
+                _ => runs.push(region.offset..end),
+            }
+        }
+        runs
+    }
+}
+
+/// Color blended over bytes whose block repeats elsewhere in the file.
+const DUPLICATE_TINT: image::Rgba<u8> = image::Rgba([0xff, 0x00, 0xff, 0xff]);
+
+/// Continuous colormap for coloring bytes by value in `vis`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Colormap {
+    /// Perceptually uniform dark blue through green to yellow.
+    Viridis,
+    /// Perceptually uniform black through purple and orange to pale yellow.
+    Magma,
+    /// Perceptually uniform black through red and orange to light yellow.
+    Inferno,
+    /// Black to white.
+    Gray,
+}
+
+impl Colormap {
+    /// Lowercase name, as accepted by `--colormap`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Viridis => "viridis",
+            Self::Magma => "magma",
+            Self::Inferno => "inferno",
+            Self::Gray => "gray",
+        }
+    }
+
+    /// Evenly spaced sRGB stops from the low end of the map to the high end.
+    fn stops(self) -> &'static [[u8; 3]] {
+        match self {
+            Self::Viridis => &[
+                [0x44, 0x01, 0x54],
+                [0x48, 0x28, 0x78],
+                [0x3e, 0x4a, 0x89],
+                [0x31, 0x68, 0x8e],
+                [0x26, 0x82, 0x8e],
+                [0x1f, 0x9e, 0x89],
+                [0x35, 0xb7, 0x79],
+                [0x6d, 0xcd, 0x59],
+                [0xb4, 0xde, 0x2c],
+                [0xfd, 0xe7, 0x25],
+            ],
+            Self::Magma => &[
+                [0x00, 0x00, 0x04],
+                [0x18, 0x0f, 0x3e],
+                [0x45, 0x10, 0x77],
+                [0x72, 0x1f, 0x81],
+                [0x9f, 0x2f, 0x7f],
+                [0xcd, 0x40, 0x71],
+                [0xf1, 0x60, 0x5d],
+                [0xfd, 0x95, 0x67],
+                [0xfe, 0xc9, 0x8d],
+                [0xfc, 0xfd, 0xbf],
+            ],
+            Self::Inferno => &[
+                [0x00, 0x00, 0x04],
+                [0x1b, 0x0c, 0x42],
+                [0x4b, 0x0c, 0x6b],
+                [0x78, 0x1c, 0x6d],
+                [0xa5, 0x2c, 0x60],
+                [0xcf, 0x44, 0x46],
+                [0xed, 0x69, 0x25],
+                [0xfb, 0x9a, 0x06],
+                [0xf7, 0xd0, 0x3c],
+                [0xfc, 0xff, 0xa4],
+            ],
+            Self::Gray => &[[0x00, 0x00, 0x00], [0xff, 0xff, 0xff]],
+        }
+    }
+
+    /// Color at `t` in `0.0..=1.0`, interpolated linearly between stops.
+    pub fn color(self, t: f32) -> image::Rgba<u8> {
+        let stops = self.stops();
+        let pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let low = (pos.floor() as usize).min(stops.len() - 2);
+        let frac = pos - low as f32;
+        let [r, g, b] = [0, 1, 2].map(|k| {
+            let (from, to) = (f32::from(stops[low][k]), f32::from(stops[low + 1][k]));
+            (from + (to - from) * frac).round() as u8
+        });
+        image::Rgba([r, g, b, 0xff])
+    }
+}
+
+/// How `vis` colors each byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ByteColoring {
+    /// Five classes: 0x00, 0xff, control, printable ASCII and other bytes.
+    #[default]
+    Class,
+    /// The byte value through a continuous colormap, 0x00 at its low end.
+    Value(Colormap),
+}
+
+impl ByteColoring {
+    /// Color of `byte` under this scheme.
+    pub fn color(self, byte: u8) -> image::Rgba<u8> {
+        match self {
+            Self::Class => byte_to_color(byte),
+            Self::Value(colormap) => colormap.color(f32::from(byte) / 255.0),
+        }
+    }
+}
+
+/// Whether each `block`-byte block of `data` also occurs elsewhere in `data`.
+///
+/// Blocks are aligned to multiples of `block`; a shorter final block only
+/// matches itself.
+fn repeated_blocks(data: &[u8], block: usize) -> Vec<bool> {
+    let mut counts: HashMap<&[u8], u32> = HashMap::new();
+    for chunk in data.chunks(block) {
+        *counts.entry(chunk).or_default() += 1;
+    }
+    data.chunks(block).map(|chunk| counts[chunk] > 1).collect()
+}
+
+/// Blend `color` halfway toward [`DUPLICATE_TINT`].
+fn tint_duplicate(color: image::Rgba<u8>) -> image::Rgba<u8> {
+    let [r, g, b, a] =
+        [0, 1, 2, 3].map(|k| ((u16::from(color.0[k]) + u16::from(DUPLICATE_TINT.0[k])) / 2) as u8);
+    image::Rgba([r, g, b, a])
+}
+
 /// Map a file into memory for read‑only access.
 ///
 /// Safety rationale: the mapping is read‑only and the `File` is not mutated
@@ -48,10 +308,52 @@ fn mmap_readonly(file: &File) -> Result<memmap2::Mmap> {
     Ok(map)
 }
 
+/// Result of visualizing a file with [`vis`].
+pub struct VisOutput {
+    /// The rendered image.
+    pub image: image::RgbaImage,
+    /// Byte drawn at each pixel, row by row.
+    pub pixel_bytes: Vec<u8>,
+    /// How often each byte value occurs in the whole file.
+    pub counts: [u64; 256],
+    /// Per-region statistics, when requested.
+    pub report: Option<VisReport>,
+}
+
+impl VisOutput {
+    /// Whether each pixel, row by row, shows a different byte than in
+    /// `before`, the pixel bytes of another render at the same size and curve.
+    pub fn changed_pixels_from(&self, before: &[u8]) -> Vec<bool> {
+        self.pixel_bytes
+            .iter()
+            .zip(before)
+            .map(|(a, b)| a != b)
+            .collect()
+    }
+}
+
+/// Paint the pixels of `image` flagged in `changed` with `accent`.
+pub fn mark_changes(image: &mut image::RgbaImage, changed: &[bool], accent: image::Rgba<u8>) {
+    for (pixel, _) in image.pixels_mut().zip(changed).filter(|(_, c)| **c) {
+        *pixel = accent;
+    }
+}
+
 /// Visualize a file by mapping each byte through a space‑filling curve.
 ///
-/// The returned image is square with the requested `width`.
-pub fn vis(input: &Path, width: u32, pattern_name: &str) -> Result<image::RgbaImage> {
+/// The returned image is square with the requested `width`, and each byte is
+/// colored by `coloring`. With `mark_duplicates`, the file is cut into blocks
+/// of that many bytes and bytes in blocks that repeat elsewhere are tinted.
+/// With `report`, the byte histogram pass also gathers a [`VisReport`] over
+/// regions of that many bytes.
+pub fn vis(
+    input: &Path,
+    width: u32,
+    pattern_entry: &'static CurveEntry,
+    coloring: ByteColoring,
+    mark_duplicates: Option<u32>,
+    report: Option<u32>,
+) -> Result<VisOutput> {
     let file = File::open(input)?;
     let mmap = mmap_readonly(&file)?;
 
@@ -59,20 +361,269 @@ pub fn vis(input: &Path, width: u32, pattern_name: &str) -> Result<image::RgbaIm
         bail!("input file is empty");
     }
 
-    let pattern = curve_from_name(pattern_name, 2, width)?;
+    let pattern = registry::construct_entry(pattern_entry, 2, width)?;
+    // Z-order has a batch decoder; other curves are evaluated point by point.
+    let zorder = if pattern_entry.id == Some(CurveId::ZOrder) {
+        Some(ZOrder::from_dimensions(2, width)?)
+    } else {
+        None
+    };
 
     let mut imgbuf = image::ImageBuffer::new(width, width);
+    let mut pixel_bytes = vec![0; width as usize * width as usize];
+    let mut counts = [0u64; 256];
+    let mut regions = Vec::new();
+    let region_len = report.map_or(mmap.len(), |len| len as usize);
+    for (offset, chunk) in (0u64..).step_by(region_len).zip(mmap.chunks(region_len)) {
+        let mut region = [0u64; 256];
+        for &byte in chunk {
+            region[usize::from(byte)] += 1;
+        }
+        for (total, n) in counts.iter_mut().zip(region) {
+            *total += n;
+        }
+        if report.is_some() {
+            regions.push(ByteStats::from_counts(offset, &region));
+        }
+    }
+    let report = report.map(|region_len| VisReport {
+        file: ByteStats::from_counts(0, &counts),
+        region_len: u64::from(region_len),
+        regions,
+    });
+    let colors: [image::Rgba<u8>; 256] = array::from_fn(|byte| coloring.color(byte as u8));
+    let duplicates =
+        mark_duplicates.map(|block| (block as usize, repeated_blocks(&mmap, block as usize)));
 
-    let plen = pattern.length() as u128;
+    let plen = u128::from(pattern.length());
     let mlen = mmap.len() as u128;
-    for i in 0..pattern.length() {
-        let p = pattern.point(i);
-        // Integer scaling avoids float rounding that could produce idx == mlen.
-        let idx = ((i as u128) * mlen / plen) as usize;
-        let byte = mmap[idx.min(mmap.len() - 1)];
-        imgbuf.put_pixel(p[0], p[1], byte_to_color(byte));
+    let mut batch: Vec<[u32; 2]> = Vec::with_capacity(VIS_BATCH as usize);
+    for start in (0..pattern.length()).step_by(VIS_BATCH as usize) {
+        let indices = start..start.saturating_add(VIS_BATCH).min(pattern.length());
+        batch.clear();
+        match &zorder {
+            Some(curve) => curve.points_into(indices.clone(), &mut batch),
+            None => batch.extend(pattern.points_in(indices.clone()).map(|p| [p[0], p[1]])),
+        }
+        for (i, &[x, y]) in indices.zip(&batch) {
+            // Integer scaling avoids float rounding that could produce idx == mlen.
+            let idx = (u128::from(i) * mlen / plen) as usize;
+            let idx = idx.min(mmap.len() - 1);
+            pixel_bytes[y as usize * width as usize + x as usize] = mmap[idx];
+            let color = colors[usize::from(mmap[idx])];
+            let repeated = duplicates
+                .as_ref()
+                .is_some_and(|(block, repeated)| repeated[idx / block]);
+            imgbuf.put_pixel(
+                x,
+                y,
+                if repeated {
+                    tint_duplicate(color)
+                } else {
+                    color
+                },
+            );
+        }
+    }
+    Ok(VisOutput {
+        image: imgbuf,
+        pixel_bytes,
+        counts,
+        report,
+    })
+}
+
+/// Write every point of `pattern_entry` on a grid with `axes` axes of side
+/// `side` to `out`, in curve order.
+///
+/// Binary records are a little-endian `u64` index followed by `u32`
+/// coordinates `x, y[, z]`, with no header or padding; text records hold the same values separated by spaces,
+/// one point per line.
+pub fn stream(
+    pattern_entry: &'static CurveEntry,
+    axes: u32,
+    side: u32,
+    binary: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    let curve = registry::construct_entry(pattern_entry, axes, side)?;
+    write_records(curve.points(), binary, out)
+}
+
+/// Write every point of the point table stored at `path` to `out`, in the
+/// record format of [`stream`].
+///
+/// The table is memory-mapped and decoded in place, one keyframe interval at
+/// a time, so only the pages being decoded are read.
+pub fn stream_table(path: &Path, binary: bool, out: &mut impl Write) -> Result<()> {
+    let file = File::open(path)?;
+    let data = mmap_readonly(&file)?;
+    let table = PointTable::parse(&data)?;
+    let chunk = u64::from(table::KEYFRAME_INTERVAL);
+    let points = (0..table.length())
+        .step_by(chunk as usize)
+        .flat_map(|start| {
+            let mut batch = Vec::new();
+            table.points_range(start..start + chunk, &mut batch);
+            batch
+        });
+    write_records(points, binary, out)
+}
+
+/// Write `points` as numbered [`stream`] records and flush `out`.
+fn write_records(
+    points: impl Iterator<Item = Point>,
+    binary: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    for (index, point) in (0u64..).zip(points) {
+        if binary {
+            out.write_all(&index.to_le_bytes())?;
+            for coord in point.iter() {
+                out.write_all(&coord.to_le_bytes())?;
+            }
+        } else {
+            write!(out, "{index}")?;
+            for coord in point.iter() {
+                write!(out, " {coord}")?;
+            }
+            writeln!(out)?;
+        }
     }
-    Ok(imgbuf)
+    out.flush()?;
+    Ok(())
+}
+
+/// Block-wise comparison of two files produced by [`vis_similarity`].
+pub struct Similarity {
+    /// Length of the first file in bytes.
+    pub len_a: usize,
+    /// Length of the second file in bytes.
+    pub len_b: usize,
+    /// Bytes compared per block (the last block may be shorter).
+    pub block_len: usize,
+    /// Similarity of each block in file order, from 0 (every bit differs) to 1 (identical).
+    pub blocks: Vec<f64>,
+    /// Similarity over the longer file as a whole.
+    pub overall: f64,
+    /// Heat map placing block `i` at curve point `i`; cells past the last block stay transparent.
+    pub heat: image::RgbaImage,
+}
+
+/// Compare two files block by block, laying the blocks out along a curve.
+///
+/// The longer file is split into at most `width × width` equal blocks. Each
+/// block scores one minus its normalised Hamming distance, with bytes past the
+/// end of the shorter file counting as entirely different, so shifted or
+/// patched regions of otherwise similar files show up as hot spots.
+pub fn vis_similarity(
+    a: &Path,
+    b: &Path,
+    width: u32,
+    pattern_entry: &'static CurveEntry,
+) -> Result<Similarity> {
+    let (file_a, file_b) = (File::open(a)?, File::open(b)?);
+    let (map_a, map_b) = (mmap_readonly(&file_a)?, mmap_readonly(&file_b)?);
+    let len = map_a.len().max(map_b.len());
+    if len == 0 {
+        bail!("both input files are empty");
+    }
+
+    let pattern = registry::construct_entry(pattern_entry, 2, width)?;
+    let block_len = len.div_ceil(pattern.length() as usize);
+    let mut blocks = Vec::with_capacity(len.div_ceil(block_len));
+    let mut differing_bits = 0u64;
+    for start in (0..len).step_by(block_len) {
+        let end = (start + block_len).min(len);
+        let bits = differing_bits_in(&map_a, &map_b, start..end);
+        differing_bits += bits;
+        blocks.push(1.0 - bits as f64 / (8 * (end - start)) as f64);
+    }
+
+    let mut heat = image::RgbaImage::new(width, width);
+    for (p, &score) in pattern.points().zip(&blocks) {
+        heat.put_pixel(p[0], p[1], heat_color(score));
+    }
+
+    Ok(Similarity {
+        len_a: map_a.len(),
+        len_b: map_b.len(),
+        block_len,
+        overall: 1.0 - differing_bits as f64 / (8 * len) as f64,
+        blocks,
+        heat,
+    })
+}
+
+/// Count differing bits between `a` and `b` over `range`; bytes present in
+/// only one input count as eight.
+fn differing_bits_in(a: &[u8], b: &[u8], range: Range<usize>) -> u64 {
+    range
+        .map(|i| match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => u64::from((x ^ y).count_ones()),
+            _ => 8,
+        })
+        .sum()
+}
+
+/// Shade a block score from white (identical) to red (every bit differs).
+fn heat_color(score: f64) -> image::Rgba<u8> {
+    let shade = |channel: u8| (255.0 - (255.0 - f64::from(channel)) * (1.0 - score)).round() as u8;
+    let [r, g, b, _] = COLOR_RED.0;
+    image::Rgba([shade(r), shade(g), shade(b), 0xff])
+}
+
+/// Result of reordering a file with [`permute`].
+pub struct PermuteOutput {
+    /// Reordered (or restored) bytes.
+    pub bytes: Vec<u8>,
+    /// Side length of the 2D grid the bytes were laid out on.
+    pub side: u32,
+    /// Zero bytes appended so the input filled the grid.
+    pub padded: usize,
+}
+
+/// Reorder a file's bytes along a 2D curve, or restore them when `inverse` is set.
+///
+/// Forward, the file is laid out row by row on the smallest square grid the
+/// curve accepts and zero-padded to fill it. Inverse, the input must fill a
+/// square grid exactly and `restore_len` (defaulting to the input length)
+/// selects how many restored bytes to keep, dropping that padding again.
+pub fn permute(
+    input: &Path,
+    pattern_entry: &'static CurveEntry,
+    inverse: bool,
+    restore_len: Option<usize>,
+) -> Result<PermuteOutput> {
+    let data = fs::read(input)?;
+    let len = u32::try_from(data.len()).map_err(|_| anyhow!("input is too large"))?;
+
+    if inverse {
+        let side = len.isqrt();
+        if side * side != len {
+            bail!("permuted input of {len} bytes does not fill a square grid");
+        }
+        let curve = registry::construct_entry(pattern_entry, 2, side)?;
+        let bytes = mapping::unpermute_bytes(&*curve, &data, restore_len.unwrap_or(data.len()))?;
+        return Ok(PermuteOutput {
+            bytes,
+            side,
+            padded: 0,
+        });
+    }
+
+    if len == 0 {
+        bail!("input file is empty");
+    }
+    let requested = len.isqrt() + u32::from(len.isqrt().pow(2) != len);
+    let (side, _) = resolve_curve_dimension(pattern_entry, requested)?;
+    let curve = registry::construct_entry(pattern_entry, 2, side)?;
+    let bytes = mapping::permute_bytes(&*curve, &data, Fit::Pad(0))?;
+    Ok(PermuteOutput {
+        padded: bytes.len() - data.len(),
+        bytes,
+        side,
+    })
 }
 
 /// Result of rendering a map image.
@@ -99,10 +650,10 @@ pub struct SnakeOptions<'a> {
     pub size: u32,
     /// Requested logical curve dimension (side length).
     pub curve_dimension: u32,
-    /// Pattern name for the curve.
-    pub pattern_name: &'a str,
+    /// Curve to animate.
+    pub pattern_entry: &'static CurveEntry,
     /// Segment range to animate.
-    pub chunk: Range<u32>,
+    pub chunk: Range<u64>,
     /// Frames per second for the GIF.
     pub fps: u16,
     /// Stroke styling used for the snake overlay.
@@ -111,97 +662,147 @@ pub struct SnakeOptions<'a> {
     pub output: &'a Path,
     /// Optional color for rendering the full curve beneath the snake overlay.
     pub full_curve: Option<image::Rgba<u8>>,
+    /// Number of steps behind the snake drawn as a fading trail (0 disables it).
+    pub trail: u32,
+    /// Frames per curve step; above 1 the head and tail glide between cells.
+    pub substeps: u32,
+}
+
+/// Result of rendering a morph animation.
+pub struct MorphRender {
+    /// Grid side length shared by both curves.
+    pub side: u32,
+    /// Whether the requested dimension had to be adjusted upward to satisfy curve constraints.
+    pub adjusted: bool,
+}
+
+/// Parameters controlling morph animation rendering.
+#[derive(Clone, Copy)]
+pub struct MorphOptions<'a> {
+    /// Output image size in pixels.
+    pub size: u32,
+    /// Requested logical curve dimension (side length).
+    pub curve_dimension: u32,
+    /// Curve the animation starts from.
+    pub from: &'static CurveEntry,
+    /// Curve the animation ends on.
+    pub to: &'static CurveEntry,
+    /// Number of frames, including both ends.
+    pub frames: u32,
+    /// Frames per second for the GIF.
+    pub fps: u16,
+    /// Stroke styling for the moving curve.
+    pub stroke: StrokeOptions,
+    /// Output GIF path.
+    pub output: &'a Path,
 }
 
 /// Find the smallest curve dimension ≥ `requested_side` that satisfies the pattern constraints.
-fn resolve_curve_dimension(pattern_name: &str, requested_side: u32) -> Result<(u32, bool)> {
+pub fn resolve_curve_dimension(
+    pattern_entry: &'static CurveEntry,
+    requested_side: u32,
+) -> Result<(u32, bool)> {
     const DIMENSION: u32 = 2;
 
     if requested_side == 0 {
         bail!("curve dimension must be >= 1");
     }
 
-    let initial_validation = registry::validate(pattern_name, DIMENSION, requested_side);
-    if initial_validation.is_ok() {
-        return Ok((requested_side, false));
+    let rule = pattern_entry.valid_sizes(DIMENSION);
+    if let Some(side) = rule.at_least(requested_side) {
+        return Ok((side, side != requested_side));
     }
+    let reason = registry::validate_entry(pattern_entry, DIMENSION, requested_side)
+        .err()
+        .map(|err| format!(": {err}"))
+        .unwrap_or_default();
+    Err(anyhow!(
+        "could not find a valid curve dimension >= {requested_side} for '{pattern_entry}'{reason}"
+    ))
+}
 
-    let mut last_err = initial_validation.unwrap_err();
+/// Find the smallest curve dimension ≥ `requested_side` valid for both `a` and `b`.
+pub fn resolve_shared_dimension(
+    a: &'static CurveEntry,
+    b: &'static CurveEntry,
+    requested_side: u32,
+) -> Result<(u32, bool)> {
+    let mut side = requested_side;
+    loop {
+        let (side_a, _) = resolve_curve_dimension(a, side)?;
+        let (side_b, _) = resolve_curve_dimension(b, side_a)?;
+        if side_b == side_a {
+            return Ok((side_a, side_a != requested_side));
+        }
+        side = side_b;
+    }
+}
 
-    let mut candidate = requested_side
-        .checked_next_power_of_two()
-        .and_then(|p| {
-            if p > requested_side {
-                Some(p)
-            } else {
-                p.checked_mul(2)
-            }
-        })
-        .ok_or_else(|| {
-            anyhow!(
-                "could not find a valid curve dimension >= {} for '{}': {}",
-                requested_side,
-                pattern_name,
-                last_err
-            )
-        })?;
+/// Check that `chunk` is a non-empty range within a curve of `length` points.
+pub fn check_chunk(
+    chunk: &Range<u64>,
+    length: u64,
+    pattern_entry: &'static CurveEntry,
+) -> Result<()> {
+    if chunk.start >= chunk.end {
+        bail!("chunk start must be less than chunk end");
+    }
 
-    while candidate > requested_side {
-        match registry::validate(pattern_name, DIMENSION, candidate) {
-            Ok(()) => return Ok((candidate, true)),
-            Err(err) => {
-                last_err = err;
-                candidate = match candidate.checked_mul(2) {
-                    Some(next) if next > candidate => next,
-                    _ => break,
-                };
-            }
-        }
+    if chunk.end > length {
+        bail!(
+            "chunk end {} exceeds curve length {} for pattern '{}'",
+            chunk.end,
+            length,
+            pattern_entry
+        );
     }
+    Ok(())
+}
 
-    Err(anyhow!(
-        "could not find a valid curve dimension >= {} for '{}': {}",
-        requested_side,
-        pattern_name,
-        last_err
-    ))
+/// Check a snake chunk: in bounds and spanning at least two points.
+pub fn check_snake_chunk(
+    chunk: &Range<u64>,
+    length: u64,
+    pattern_entry: &'static CurveEntry,
+) -> Result<()> {
+    check_chunk(chunk, length, pattern_entry)?;
+    if chunk.end - chunk.start < 2 {
+        bail!("chunk must span at least two points for animation");
+    }
+    Ok(())
+}
+
+/// Check that a square frame of `size` pixels fits GIF dimensions.
+pub fn check_gif_size(size: u32) -> Result<()> {
+    if size > u32::from(u16::MAX) {
+        bail!("size {} exceeds GIF limits ({}).", size, u16::MAX);
+    }
+    Ok(())
 }
 
 /// Render a map of a curve using a requested grid dimension.
 ///
 /// - `size`: Output image width/height in pixels.
 /// - `curve_dimension`: Requested side length for the curve grid (renders `dimension×dimension` points).
-/// - `pattern_name`: Curve name.
+/// - `pattern_entry`: Curve to draw.
 /// - `chunk`: Optional [start, end) offsets limiting which part of the curve is drawn.
 /// - `stroke`: Stroke rendering options.
 pub fn map(
     size: u32,
     curve_dimension: u32,
-    pattern_name: &str,
-    chunk: Option<Range<u32>>,
+    pattern_entry: &'static CurveEntry,
+    chunk: Option<Range<u64>>,
     stroke: StrokeOptions,
 ) -> Result<MapRender> {
     if stroke.line_width == 0 {
         bail!("line width must be >= 1");
     }
 
-    let (side, adjusted) = resolve_curve_dimension(pattern_name, curve_dimension)?;
-    let pattern = curve_from_name(pattern_name, 2, side)?;
+    let (side, adjusted) = resolve_curve_dimension(pattern_entry, curve_dimension)?;
+    let pattern = registry::construct_entry(pattern_entry, 2, side)?;
     let length = pattern.length();
     let chunk = chunk.unwrap_or(0..length);
-
-    if chunk.start >= chunk.end {
-        bail!("chunk start must be less than chunk end");
-    }
-
-    if chunk.end > length {
-        bail!(
-            "chunk end {} exceeds curve length {} for pattern '{}'",
-            chunk.end,
-            length,
-            pattern_name
-        );
-    }
+    check_chunk(&chunk, length, pattern_entry)?;
 
     let imgbuf = render_map_image(size, side, chunk, stroke, &*pattern);
     Ok(MapRender {
@@ -211,48 +812,102 @@ pub fn map(
     })
 }
 
+/// Write the curve index of every cell of a `side`×`side` grid to `path`.
+///
+/// Row `y`, column `x` holds the index of the cell drawn at that grid position
+/// by [`map`], so downstream tools can correlate a render back to indices.
+/// `.npy` files hold a NumPy array of little-endian `u16`, widening to `u32`
+/// or `u64` when the indices do not fit; `.png` (or `.png16`) files hold 16-bit greyscale and
+/// need a curve of at most 65536 points.
+pub fn write_index_map(pattern_entry: &'static CurveEntry, side: u32, path: &Path) -> Result<()> {
+    let curve = registry::construct_entry(pattern_entry, 2, side)?;
+    let grid = mapping::index_grid(&*curve)?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("npy") => fs::write(path, encode_npy(&grid, side))?,
+        Some("png" | "png16") => {
+            if curve.length() > 1 << 16 {
+                bail!(
+                    "{} indices do not fit a 16-bit PNG; use a .npy index map",
+                    curve.length()
+                );
+            }
+            let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), side, side);
+            encoder.set_color(png::ColorType::Grayscale);
+            encoder.set_depth(png::BitDepth::Sixteen);
+            let mut writer = encoder.write_header()?;
+            // PNG samples are big-endian.
+            let data: Vec<u8> = grid
+                .iter()
+                .flat_map(|&i| (i as u16).to_be_bytes())
+                .collect();
+            writer.write_image_data(&data)?;
+            writer.finish()?;
+        }
+        _ => bail!("index map must be a .npy or .png file: {}", path.display()),
+    }
+    Ok(())
+}
+
+/// Encode a square grid of indices as a version 1.0 `.npy` file.
+fn encode_npy(grid: &[u64], side: u32) -> Vec<u8> {
+    let max = grid.iter().copied().max().unwrap_or(0);
+    let width: usize = if max > u64::from(u32::MAX) {
+        8
+    } else if max > u64::from(u16::MAX) {
+        4
+    } else {
+        2
+    };
+    let mut header =
+        format!("{{'descr': '<u{width}', 'fortran_order': False, 'shape': ({side}, {side}), }}");
+    // Magic, version and length take 10 bytes; pad so the data starts 64-byte aligned.
+    let padded = (10 + header.len() + 1).next_multiple_of(64) - 10;
+    header.extend(iter::repeat_n(' ', padded - header.len() - 1));
+    header.push('\n');
+
+    let mut out = Vec::with_capacity(10 + header.len() + grid.len() * width);
+    out.extend_from_slice(b"\x93NUMPY\x01\x00");
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    for &index in grid {
+        out.extend_from_slice(&index.to_le_bytes()[..width]);
+    }
+    out
+}
+
 /// Generate an animated snake GIF where a chunk of the curve marches across all offsets.
 pub fn snake(options: SnakeOptions<'_>) -> Result<SnakeRender> {
     let SnakeOptions {
         size,
         curve_dimension,
-        pattern_name,
+        pattern_entry,
         chunk,
         fps,
         stroke,
         output,
         full_curve,
+        trail,
+        substeps,
     } = options;
 
     if stroke.line_width == 0 {
         bail!("line width must be >= 1");
     }
-
-    if size > u16::MAX as u32 {
-        bail!("size {} exceeds GIF limits ({}).", size, u16::MAX);
+    if substeps == 0 {
+        bail!("a snake needs at least one frame per step");
     }
 
-    let (side, adjusted) = resolve_curve_dimension(pattern_name, curve_dimension)?;
-    let pattern = curve_from_name(pattern_name, 2, side)?;
-    let length = pattern.length();
-
-    if chunk.start >= chunk.end {
-        bail!("chunk start must be less than chunk end");
-    }
-
-    if chunk.end > length {
-        bail!(
-            "chunk end {} exceeds curve length {} for pattern '{}'",
-            chunk.end,
-            length,
-            pattern_name
-        );
-    }
+    check_gif_size(size)?;
 
+    let (side, adjusted) = resolve_curve_dimension(pattern_entry, curve_dimension)?;
+    let pattern = registry::construct_entry(pattern_entry, 2, side)?;
+    let length = pattern.length();
+    check_snake_chunk(&chunk, length, pattern_entry)?;
     let chunk_len = chunk.end - chunk.start;
-    if chunk_len < 2 {
-        bail!("chunk must span at least two points for animation");
-    }
 
     let mut file = File::create(output)?;
     let mut encoder = Encoder::new(&mut file, size as u16, size as u16, &[])?;
@@ -271,13 +926,28 @@ pub fn snake(options: SnakeOptions<'_>) -> Result<SnakeRender> {
         render_map_image(size, side, 0..length, palette, &*pattern)
     });
 
-    for offset in 0..length {
-        let start = (chunk.start + offset) % length;
-        let mut frame_image = base_frame
-            .clone()
-            .unwrap_or_else(|| render_chunk_image(size, side, start, chunk_len, stroke, &*pattern));
-
-        if base_frame.is_some() {
+    // The trail never reaches round to the snake's own head.
+    let trail = u64::from(trail).min(length - chunk_len);
+    let frames = pattern
+        .cyclic_range(chunk.start, length)
+        .into_iter()
+        .flat_map(|start| (0..substeps).map(move |step| (start, step)));
+    for (start, step) in frames {
+        let mut frame_image = base_frame.clone().unwrap_or_else(|| {
+            image::ImageBuffer::from_pixel(size, size, stroke.palette.background)
+        });
+        if trail > 0 {
+            draw_trail(
+                &mut frame_image,
+                size,
+                side,
+                start,
+                trail,
+                stroke,
+                &*pattern,
+            );
+        }
+        if step == 0 {
             draw_chunk_overlay(
                 &mut frame_image,
                 size,
@@ -287,6 +957,38 @@ pub fn snake(options: SnakeOptions<'_>) -> Result<SnakeRender> {
                 stroke,
                 &*pattern,
             );
+        } else {
+            // Between cells the tail has left `start` and the head is part
+            // way along the step past the chunk's last point.
+            let frac = f64::from(step) / f64::from(substeps);
+            let head = pattern.advance(start, chunk_len as i64 - 1);
+            draw_chunk_overlay(
+                &mut frame_image,
+                size,
+                side,
+                pattern.advance(start, 1),
+                chunk_len - 1,
+                stroke,
+                &*pattern,
+            );
+            draw_partial_step(
+                &mut frame_image,
+                size,
+                side,
+                start,
+                frac..1.0,
+                stroke,
+                &*pattern,
+            );
+            draw_partial_step(
+                &mut frame_image,
+                size,
+                side,
+                head,
+                0.0..frac,
+                stroke,
+                &*pattern,
+            );
         }
 
         let mut raw = frame_image.into_raw();
@@ -298,8 +1000,243 @@ pub fn snake(options: SnakeOptions<'_>) -> Result<SnakeRender> {
     Ok(SnakeRender { side, adjusted })
 }
 
+/// Generate an animated GIF in which curve `from` morphs into curve `to`.
+///
+/// Each curve index moves in a straight line from its cell in `from` to its
+/// cell in `to`, eased so the motion starts and ends at rest.
+pub fn morph(options: MorphOptions<'_>) -> Result<MorphRender> {
+    let MorphOptions {
+        size,
+        curve_dimension,
+        from,
+        to,
+        frames,
+        fps,
+        stroke,
+        output,
+    } = options;
+
+    if stroke.line_width == 0 {
+        bail!("line width must be >= 1");
+    }
+    if frames < 2 {
+        bail!("a morph needs at least two frames");
+    }
+    check_gif_size(size)?;
+
+    let (side, adjusted) = resolve_shared_dimension(from, to, curve_dimension)?;
+    let from_curve = registry::construct_entry(from, 2, side)?;
+    let to_curve = registry::construct_entry(to, 2, side)?;
+
+    let mut file = File::create(output)?;
+    let mut encoder = Encoder::new(&mut file, size as u16, size as u16, &[])?;
+    encoder.set_repeat(Repeat::Infinite)?;
+    let frame_delay = frame_delay_from_fps(fps);
+
+    for index in 0..frames {
+        let t = f64::from(index) / f64::from(frames - 1);
+        let eased = t * t * (3.0 - 2.0 * t);
+        let frame_image = render_morph_image(size, side, eased, stroke, &*from_curve, &*to_curve);
+
+        let mut raw = frame_image.into_raw();
+        let mut frame = Frame::from_rgba_speed(size as u16, size as u16, &mut raw, 10);
+        frame.delay = frame_delay;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(MorphRender { side, adjusted })
+}
+
+/// Result of rendering a size sweep.
+pub struct SweepRender {
+    /// One map per requested grid side, in order.
+    pub tiles: Vec<image::RgbaImage>,
+    /// Grid side actually used for each tile.
+    pub sides: Vec<u32>,
+}
+
+/// Render a `size`×`size` map of the whole curve at each grid side in `sides`.
+///
+/// Each side is rounded up to the nearest one the curve accepts, as in [`map`].
+pub fn sweep(
+    size: u32,
+    sides: &[u32],
+    pattern_entry: &'static CurveEntry,
+    stroke: StrokeOptions,
+) -> Result<SweepRender> {
+    if stroke.line_width == 0 {
+        bail!("line width must be >= 1");
+    }
+    if sides.is_empty() {
+        bail!("a sweep needs at least one size");
+    }
+
+    let mut render = SweepRender {
+        tiles: Vec::with_capacity(sides.len()),
+        sides: Vec::with_capacity(sides.len()),
+    };
+    for &requested in sides {
+        let (side, _) = resolve_curve_dimension(pattern_entry, requested)?;
+        let pattern = registry::construct_entry(pattern_entry, 2, side)?;
+        render.tiles.push(render_map_image(
+            size,
+            side,
+            0..pattern.length(),
+            stroke,
+            &*pattern,
+        ));
+        render.sides.push(side);
+    }
+    Ok(render)
+}
+
+/// Lay `tiles` out left to right on `background`, separated by a gap of a
+/// sixteenth of the tallest tile.
+pub fn montage(tiles: &[image::RgbaImage], background: image::Rgba<u8>) -> image::RgbaImage {
+    let height = tiles
+        .iter()
+        .map(image::RgbaImage::height)
+        .max()
+        .unwrap_or(0);
+    let gap = height / 16;
+    let width = tiles.iter().map(image::RgbaImage::width).sum::<u32>()
+        + gap * (tiles.len() as u32).saturating_sub(1);
+    let mut canvas = image::ImageBuffer::from_pixel(width, height, background);
+    let mut x = 0;
+    for tile in tiles {
+        imageops::overlay(&mut canvas, tile, i64::from(x), 0);
+        x += tile.width() + gap;
+    }
+    canvas
+}
+
+/// How `wallpaper` arranges its curve tiles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum WallpaperLayout {
+    /// One tile per column, left to right (one per monitor on a wide span).
+    Row,
+    /// One tile per row, top to bottom.
+    Column,
+    /// As many columns as make the tiles largest.
+    Grid,
+}
+
+impl WallpaperLayout {
+    /// Lowercase name, as accepted by `--layout`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Row => "row",
+            Self::Column => "column",
+            Self::Grid => "grid",
+        }
+    }
+}
+
+/// Result of rendering a wallpaper.
+pub struct WallpaperRender {
+    /// The composed canvas.
+    pub image: image::RgbaImage,
+    /// Grid side actually used for each curve.
+    pub sides: Vec<u32>,
+}
+
+/// Square tiles for `count` curves on a `width`×`height` canvas, as
+/// `(x, y, size)` in reading order.
+///
+/// The canvas is split into equal cells with `padding` pixels around and
+/// between them, and each tile is the largest square centred in its cell.
+pub fn wallpaper_tiles(
+    width: u32,
+    height: u32,
+    count: u32,
+    layout: WallpaperLayout,
+    padding: u32,
+) -> Result<Vec<(u32, u32, u32)>> {
+    if count == 0 {
+        bail!("a wallpaper needs at least one curve");
+    }
+    let cells = |columns: u32| {
+        let rows = count.div_ceil(columns);
+        let cell_w = width.saturating_sub(padding.saturating_mul(columns + 1)) / columns;
+        let cell_h = height.saturating_sub(padding.saturating_mul(rows + 1)) / rows;
+        (columns, cell_w, cell_h)
+    };
+    let (columns, cell_w, cell_h) = match layout {
+        WallpaperLayout::Row => cells(count),
+        WallpaperLayout::Column => cells(1),
+        WallpaperLayout::Grid => (1..=count)
+            .map(cells)
+            .max_by_key(|&(_, w, h)| w.min(h))
+            .expect("count is at least 1"),
+    };
+    let tile = cell_w.min(cell_h);
+    if tile == 0 {
+        bail!("{width}×{height} leaves no room for {count} tiles with {padding}px padding");
+    }
+    Ok((0..count)
+        .map(|i| {
+            let (column, row) = (i % columns, i / columns);
+            let x = padding + column * (cell_w + padding) + (cell_w - tile) / 2;
+            let y = padding + row * (cell_h + padding) + (cell_h - tile) / 2;
+            (x, y, tile)
+        })
+        .collect())
+}
+
+/// Compose maps of `curves` onto a `width`×`height` canvas filled with the
+/// stroke background, arranged by `layout`.
+///
+/// Each curve's side is rounded up to one it accepts, as in [`map`].
+pub fn wallpaper(
+    width: u32,
+    height: u32,
+    curves: &[&'static CurveEntry],
+    curve_dimension: u32,
+    layout: WallpaperLayout,
+    padding: u32,
+    stroke: StrokeOptions,
+) -> Result<WallpaperRender> {
+    if stroke.line_width == 0 {
+        bail!("line width must be >= 1");
+    }
+    let tiles = wallpaper_tiles(width, height, curves.len() as u32, layout, padding)?;
+    let mut render = WallpaperRender {
+        image: image::ImageBuffer::from_pixel(width, height, stroke.palette.background),
+        sides: Vec::with_capacity(curves.len()),
+    };
+    for (&pattern_entry, (x, y, size)) in curves.iter().zip(tiles) {
+        let (side, _) = resolve_curve_dimension(pattern_entry, curve_dimension)?;
+        let pattern = registry::construct_entry(pattern_entry, 2, side)?;
+        let tile = render_map_image(size, side, 0..pattern.length(), stroke, &*pattern);
+        imageops::overlay(&mut render.image, &tile, i64::from(x), i64::from(y));
+        render.sides.push(side);
+    }
+    Ok(render)
+}
+
+/// Write `frames` to `output` as a looping GIF at `fps` frames per second.
+pub fn write_gif(frames: Vec<image::RgbaImage>, fps: u16, output: &Path) -> Result<()> {
+    let Some(first) = frames.first() else {
+        bail!("an animation needs at least one frame");
+    };
+    let (width, height) = first.dimensions();
+    check_gif_size(width.max(height))?;
+
+    let mut file = File::create(output)?;
+    let mut encoder = Encoder::new(&mut file, width as u16, height as u16, &[])?;
+    encoder.set_repeat(Repeat::Infinite)?;
+    let frame_delay = frame_delay_from_fps(fps);
+    for frame_image in frames {
+        let mut raw = frame_image.into_raw();
+        let mut frame = Frame::from_rgba_speed(width as u16, height as u16, &mut raw, 10);
+        frame.delay = frame_delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
 /// Convert frames-per-second into a GIF frame delay (hundredths of a second).
-fn frame_delay_from_fps(fps: u16) -> u16 {
+pub fn frame_delay_from_fps(fps: u16) -> u16 {
     // GIF delays are centiseconds; clamp to at least 1cs to avoid zero-delay frames.
     let fps = fps.max(1);
     ((100 + (fps / 2)) / fps).max(1)
@@ -307,30 +1244,65 @@ fn frame_delay_from_fps(fps: u16) -> u16 {
 
 /// Generate a 4096×4096 image containing every RGB color exactly once.
 ///
-/// The pixels are laid out following `pattern_name`; the colors are chosen by
-/// walking `colormap_name` in RGB space.
-pub fn allrgb(pattern_name: &str, colormap_name: &str) -> Result<image::RgbaImage> {
+/// The pixels are laid out following `pattern_entry`; the colors are chosen by
+/// walking `colormap_entry` in RGB space.
+pub fn allrgb(
+    pattern_entry: &'static CurveEntry,
+    colormap_entry: &'static CurveEntry,
+) -> Result<image::RgbaImage> {
     let width = 4096;
-    let pattern = curve_from_name(pattern_name, 2, width)?;
-    let mut imgbuf: image::RgbaImage = image::ImageBuffer::new(width, width);
-    let colormap = curve_from_name(colormap_name, 3, 256)?;
-
-    let mut pb = pbr::ProgressBar::new(4096);
-    pb.format("╢▌▌░╟");
-
-    for i in 0..pattern.length() {
-        let p = pattern.point(i);
-        let c = colormap.point(i);
-        if i % 4096 == 0 {
-            pb.inc();
-        }
-        imgbuf.put_pixel(
-            p[0],
-            p[1],
-            image::Rgba([c[0] as u8, c[1] as u8, c[2] as u8, 255]),
-        );
+    let stages = AllRgbLayout {
+        colormap: colormap_entry,
+        width,
+    };
+    Ok(registry::with_entry(pattern_entry, 2, width, stages)??)
+}
+
+/// First stage of [`allrgb`]: builds the colormap curve for a concrete layout
+/// curve, so the pixel loop is monomorphized for both.
+struct AllRgbLayout {
+    /// Curve walking the RGB cube.
+    colormap: &'static CurveEntry,
+    /// Side length of the image.
+    width: u32,
+}
+
+impl CurveVisitor for AllRgbLayout {
+    type Output = error::Result<image::RgbaImage>;
+
+    fn visit<P: SpaceCurve + ?Sized>(self, pattern: &P) -> Self::Output {
+        let colors = AllRgbColors {
+            pattern,
+            width: self.width,
+        };
+        registry::with_entry(self.colormap, 3, 256, colors)?
     }
+}
+
+/// Second stage of [`allrgb`], holding the layout curve.
+struct AllRgbColors<'a, P: ?Sized> {
+    /// Curve placing pixels in the image.
+    pattern: &'a P,
+    /// Side length of the image.
+    width: u32,
+}
+
+impl<P: SpaceCurve + ?Sized> CurveVisitor for AllRgbColors<'_, P> {
+    type Output = error::Result<image::RgbaImage>;
 
-    pb.finish();
-    Ok(imgbuf)
+    fn visit<C: SpaceCurve + ?Sized>(self, colormap: &C) -> Self::Output {
+        let mut pb = pbr::ProgressBar::new(u64::from(self.width));
+        pb.format("╢▌▌░╟");
+
+        let imgbuf = raster::paint_along(self.pattern, |i| {
+            if i % u64::from(self.width) == 0 {
+                pb.inc();
+            }
+            let c = colormap.point(i);
+            image::Rgba([c[0] as u8, c[1] as u8, c[2] as u8, 255])
+        })?;
+
+        pb.finish();
+        Ok(imgbuf)
+    }
 }
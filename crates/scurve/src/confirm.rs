@@ -0,0 +1,147 @@
+//! Safeguards against renders large enough to exhaust memory or run for hours.
+//!
+//! Commands estimate their output before allocating it. Anything above the
+//! thresholds needs `--yes`, or an explicit answer when stdin is a terminal.
+
+use std::io::{self, IsTerminal, Write};
+
+use anyhow::{Result, bail};
+
+/// Largest image rendered without confirmation (8192×8192 pixels).
+const MAX_PIXELS: u64 = 8192 * 8192;
+
+/// Most animation frames rendered without confirmation.
+const MAX_FRAMES: u64 = 2000;
+
+/// Bytes per RGBA pixel.
+const BYTES_PER_PIXEL: u64 = 4;
+
+/// Rough rendering plus encoding throughput, for order-of-magnitude time estimates.
+const PIXELS_PER_SECOND: f64 = 20_000_000.0;
+
+/// Planned size of a render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Estimate {
+    /// Pixels in each frame.
+    pub pixels: u64,
+    /// Number of frames produced.
+    pub frames: u64,
+    /// Frame buffers held in memory at once.
+    pub buffers: u64,
+}
+
+impl Estimate {
+    /// A single `width`×`height` image.
+    pub fn image(width: u32, height: u32) -> Self {
+        Self {
+            pixels: u64::from(width) * u64::from(height),
+            frames: 1,
+            buffers: 1,
+        }
+    }
+
+    /// An animation of `frames` square frames of `size` pixels.
+    ///
+    /// The snake renderer keeps a base frame, the frame being drawn and its
+    /// encoding buffer alive together.
+    pub fn animation(size: u32, frames: u64) -> Self {
+        Self {
+            frames,
+            buffers: 3,
+            ..Self::image(size, size)
+        }
+    }
+
+    /// Peak memory for the frame buffers, in bytes.
+    pub fn memory_bytes(&self) -> u64 {
+        self.pixels
+            .saturating_mul(BYTES_PER_PIXEL)
+            .saturating_mul(self.buffers)
+    }
+
+    /// Rough wall-clock time, in seconds.
+    pub fn seconds(&self) -> f64 {
+        self.pixels as f64 * self.frames as f64 / PIXELS_PER_SECOND
+    }
+
+    /// Whether the render exceeds the pixel or frame threshold.
+    pub fn is_large(&self) -> bool {
+        self.pixels > MAX_PIXELS || self.frames > MAX_FRAMES
+    }
+}
+
+/// Proceed when the render is small or `yes` is set; otherwise ask on an
+/// interactive terminal and refuse elsewhere.
+pub fn confirm(estimate: Estimate, yes: bool) -> Result<()> {
+    if yes || !estimate.is_large() {
+        return Ok(());
+    }
+    let summary = format!(
+        "This render produces {} frame(s) of {} pixels, needing about {} and roughly {}.",
+        estimate.frames,
+        estimate.pixels,
+        format_bytes(estimate.memory_bytes()),
+        format_duration(estimate.seconds()),
+    );
+    if !io::stdin().is_terminal() {
+        bail!("{summary} Pass --yes to proceed.");
+    }
+
+    eprint!("{summary} Continue? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if matches!(answer.trim(), "y" | "Y" | "yes") {
+        Ok(())
+    } else {
+        bail!("render cancelled");
+    }
+}
+
+/// Format `bytes` in MiB or, from 1 GiB up, GiB.
+pub fn format_bytes(bytes: u64) -> String {
+    let mib = bytes as f64 / f64::from(1u32 << 20);
+    if mib < 1024.0 {
+        format!("{mib:.0} MiB")
+    } else {
+        format!("{:.1} GiB", mib / 1024.0)
+    }
+}
+
+/// Format `seconds` with the coarsest sensible unit.
+pub fn format_duration(seconds: f64) -> String {
+    if seconds < 90.0 {
+        format!("{seconds:.0} s")
+    } else if seconds < 90.0 * 60.0 {
+        format!("{:.0} min", seconds / 60.0)
+    } else {
+        format!("{:.1} h", seconds / 3600.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds_flag_large_renders() {
+        assert!(!Estimate::image(8192, 8192).is_large());
+        assert!(Estimate::image(8193, 8192).is_large());
+        assert!(!Estimate::animation(512, 2000).is_large());
+        assert!(Estimate::animation(512, 2001).is_large());
+
+        let big = Estimate::image(16384, 16384);
+        assert_eq!(big.memory_bytes(), 1 << 30);
+        assert!(confirm(big, true).is_ok());
+        assert!(confirm(Estimate::image(64, 64), false).is_ok());
+    }
+
+    #[test]
+    fn sizes_and_durations_pick_a_unit() {
+        assert_eq!(format_bytes(3 << 20), "3 MiB");
+        assert_eq!(format_bytes(3 << 29), "1.5 GiB");
+        assert_eq!(format_duration(12.4), "12 s");
+        assert_eq!(format_duration(600.0), "10 min");
+        assert_eq!(format_duration(7200.0), "2.0 h");
+    }
+}
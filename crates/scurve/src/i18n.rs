@@ -0,0 +1,351 @@
+//! Translated CLI help and status messages.
+//!
+//! Languages come from the GUI's [`Language`], so both front ends support the
+//! same set. The language is picked by `--locale`, else the `LC_ALL`,
+//! `LC_MESSAGES` and `LANG` environment variables, else English. Errors raised
+//! deep inside commands stay in English.
+
+use std::{env, ffi::OsString};
+
+use clap::Command;
+use scurve_gui::i18n::Language;
+
+/// Environment variables consulted for the locale, in POSIX precedence order.
+const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// A translatable CLI string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    /// Help for `-v`.
+    Verbosity,
+    /// Help for `--yes`.
+    Yes,
+    /// Help for `--no-metadata`.
+    NoMetadata,
+    /// Help for `--dry-run`.
+    DryRun,
+    /// Help for `--locale`.
+    Locale,
+    /// About text of `map`.
+    MapAbout,
+    /// About text of `snake`.
+    SnakeAbout,
+    /// About text of `morph`.
+    MorphAbout,
+    /// About text of `sweep`.
+    SweepAbout,
+    /// About text of `wallpaper`.
+    WallpaperAbout,
+    /// About text of `allrgb`.
+    AllrgbAbout,
+    /// About text of `vis`.
+    VisAbout,
+    /// About text of `vis-similarity`.
+    VisSimilarityAbout,
+    /// About text of `stream`.
+    StreamAbout,
+    /// About text of `table`.
+    TableAbout,
+    /// About text of `verify`.
+    VerifyAbout,
+    /// About text of `permute`.
+    PermuteAbout,
+    /// About text of `gui`.
+    GuiAbout,
+    /// About text of `screenshot`.
+    ScreenshotAbout,
+    /// About text of `record`.
+    RecordAbout,
+    /// About text of `list-curves`.
+    ListCurvesAbout,
+    /// About text of `bench`.
+    BenchAbout,
+    /// About text of `tui`.
+    TuiAbout,
+    /// Printed after a command succeeds.
+    Ok,
+    /// Printed after a snake GIF is written.
+    SavedSnake,
+    /// Printed after a morph GIF is written.
+    SavedMorph,
+    /// Printed after a sweep is written.
+    SavedSweep,
+    /// Printed after a wallpaper is written.
+    SavedWallpaper,
+    /// Printed after a GUI recording is written.
+    SavedRecording,
+    /// Printed after a GUI screenshot is written.
+    #[cfg(feature = "screenshot")]
+    SavedScreenshot,
+    /// Label of the number of outputs a batch rendered.
+    Rendered,
+    /// Label of the number of batch outputs kept because they existed.
+    SkippedExisting,
+    /// Prefix of an error message.
+    Error,
+    /// Printed before opening the image viewer.
+    OpeningViewer,
+    /// Heading of a `--dry-run` summary.
+    NothingRendered,
+}
+
+/// Subcommands and the message holding their about text.
+const SUBCOMMANDS: [(&str, Msg); 18] = [
+    ("map", Msg::MapAbout),
+    ("snake", Msg::SnakeAbout),
+    ("morph", Msg::MorphAbout),
+    ("sweep", Msg::SweepAbout),
+    ("wallpaper", Msg::WallpaperAbout),
+    ("allrgb", Msg::AllrgbAbout),
+    ("vis", Msg::VisAbout),
+    ("vis-similarity", Msg::VisSimilarityAbout),
+    ("stream", Msg::StreamAbout),
+    ("table", Msg::TableAbout),
+    ("verify", Msg::VerifyAbout),
+    ("permute", Msg::PermuteAbout),
+    ("gui", Msg::GuiAbout),
+    ("screenshot", Msg::ScreenshotAbout),
+    ("record", Msg::RecordAbout),
+    ("list-curves", Msg::ListCurvesAbout),
+    ("bench", Msg::BenchAbout),
+    ("tui", Msg::TuiAbout),
+];
+
+/// Top-level arguments and the message holding their help text.
+const ARGS: [(&str, Msg); 2] = [("v", Msg::Verbosity), ("locale", Msg::Locale)];
+
+/// Rendering flags, present on the subcommands that honor them, and the
+/// message holding their help text.
+const RENDER_ARGS: [(&str, Msg); 3] = [
+    ("yes", Msg::Yes),
+    ("no_metadata", Msg::NoMetadata),
+    ("dry_run", Msg::DryRun),
+];
+
+/// Resolve `msg` to text in `lang`.
+pub fn tr(lang: Language, msg: Msg) -> &'static str {
+    match lang {
+        Language::English => english(msg),
+        Language::German => german(msg),
+    }
+}
+
+/// Parse a `--locale` value such as `de` or `de_DE.UTF-8`.
+pub fn parse_locale(s: &str) -> Result<Language, String> {
+    Language::from_locale(s).ok_or_else(|| {
+        let codes: Vec<_> = Language::ALL.iter().map(|l| l.code()).collect();
+        format!(
+            "Unsupported locale '{s}'. Valid options: {}",
+            codes.join(", ")
+        )
+    })
+}
+
+/// Language selected by the environment, or English.
+pub fn env_language() -> Language {
+    locale_language(None, |var| env::var(var).ok())
+}
+
+/// Language for a command line: its `--locale` value, else the environment.
+///
+/// Scans the raw arguments so help output can be translated before clap
+/// parses them; an invalid `--locale` falls back here and is reported by clap.
+pub fn cli_language(args: &[OsString]) -> Language {
+    let mut flag = None;
+    let mut iter = args.iter().filter_map(|a| a.to_str());
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            break;
+        } else if arg == "--locale" {
+            flag = iter.next();
+        } else if let Some(value) = arg.strip_prefix("--locale=") {
+            flag = Some(value);
+        }
+    }
+    locale_language(flag, |var| env::var(var).ok())
+}
+
+/// Language from an explicit locale, else the first non-empty locale variable.
+fn locale_language(flag: Option<&str>, var: impl Fn(&str) -> Option<String>) -> Language {
+    flag.and_then(Language::from_locale)
+        .or_else(|| {
+            LOCALE_VARS
+                .into_iter()
+                .filter_map(var)
+                .find(|value| !value.is_empty())
+                .and_then(|value| Language::from_locale(&value))
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the subcommand about texts and flag help of `cmd` with their
+/// translations.
+pub fn localize(cmd: Command, lang: Language) -> Command {
+    let cmd = SUBCOMMANDS.into_iter().fold(cmd, |cmd, (name, msg)| {
+        cmd.mut_subcommand(name, |sub| {
+            let flags: Vec<_> = RENDER_ARGS
+                .into_iter()
+                .filter(|(id, _)| sub.get_arguments().any(|arg| arg.get_id() == id))
+                .collect();
+            flags
+                .into_iter()
+                .fold(sub.about(tr(lang, msg)), |sub, (id, msg)| {
+                    sub.mut_arg(id, |arg| arg.help(tr(lang, msg)))
+                })
+        })
+    });
+    ARGS.into_iter().fold(cmd, |cmd, (id, msg)| {
+        cmd.mut_arg(id, |arg| arg.help(tr(lang, msg)))
+    })
+}
+
+/// English strings.
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Verbosity => "Sets the level of verbosity",
+        Msg::Yes => "Render without confirmation even above 8192×8192 pixels or 2000 frames",
+        Msg::NoMetadata => "Do not embed the command line, curve and version in PNG outputs",
+        Msg::DryRun => {
+            "Validate curves, chunks and paths and print the expected output size without rendering"
+        }
+        Msg::Locale => "Language of help and messages (en, de) [default: from LANG]",
+        Msg::MapAbout => "Generate a map of a pattern",
+        Msg::SnakeAbout => "Generate an animated snake GIF for a pattern",
+        Msg::MorphAbout => "Generate an animated GIF morphing one curve into another",
+        Msg::SweepAbout => "Render a curve at several grid sizes as an animation or montage",
+        Msg::WallpaperAbout => "Compose curve maps into a wallpaper of any resolution",
+        Msg::AllrgbAbout => {
+            "Generate a dense map of a pattern that contains one pixel for each RGB colour"
+        }
+        Msg::VisAbout => "visualise a file",
+        Msg::VisSimilarityAbout => "Score how similar two files are, block by block along a curve",
+        Msg::StreamAbout => "Write a curve's points to stdout for other tools",
+        Msg::TableAbout => "Cache a curve's points in a compact point table file",
+        Msg::VerifyAbout => "Check that a curve visits every grid cell once and inverts its index",
+        Msg::PermuteAbout => "Reorder a file's bytes along a curve, or restore them",
+        Msg::GuiAbout => "Open GUI window",
+        Msg::ScreenshotAbout => "Take a screenshot of the GUI (requires --features screenshot)",
+        Msg::RecordAbout => "Record the GUI on a fixed clock to a video or GIF",
+        Msg::ListCurvesAbout => "List supported curve names and constraints",
+        Msg::BenchAbout => "Show complexity notes and measured per-call latency of curves",
+        Msg::TuiAbout => "Explore curves in the terminal (no display needed, e.g. over SSH)",
+        Msg::Ok => "OK!",
+        Msg::SavedSnake => "Saved snake GIF!",
+        Msg::SavedMorph => "Saved morph GIF!",
+        Msg::SavedSweep => "Saved sweep!",
+        Msg::SavedWallpaper => "Saved wallpaper!",
+        Msg::SavedRecording => "Saved recording!",
+        #[cfg(feature = "screenshot")]
+        Msg::SavedScreenshot => "Screenshot saved!",
+        Msg::Rendered => "Rendered",
+        Msg::SkippedExisting => "skipped (already existed)",
+        Msg::Error => "Error",
+        Msg::OpeningViewer => {
+            "No output file provided; opening viewer (close the window to finish)..."
+        }
+        Msg::NothingRendered => "Dry run, nothing rendered.",
+    }
+}
+
+/// German strings.
+fn german(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Verbosity => "Legt die Ausführlichkeit fest",
+        Msg::Yes => "Ohne Rückfrage rendern, auch über 8192×8192 Pixeln oder 2000 Bildern",
+        Msg::NoMetadata => "Befehlszeile, Kurve und Version nicht in PNG-Ausgaben einbetten",
+        Msg::DryRun => {
+            "Kurven, Abschnitte und Pfade prüfen und die erwartete Ausgabegröße ausgeben, \
+             ohne zu rendern"
+        }
+        Msg::Locale => "Sprache von Hilfe und Meldungen (en, de) [Standard: aus LANG]",
+        Msg::MapAbout => "Karte eines Musters erzeugen",
+        Msg::SnakeAbout => "Animiertes Schlangen-GIF eines Musters erzeugen",
+        Msg::MorphAbout => "Animiertes GIF erzeugen, das eine Kurve in eine andere überführt",
+        Msg::SweepAbout => "Eine Kurve in mehreren Rastergrößen als Animation oder Montage rendern",
+        Msg::WallpaperAbout => {
+            "Kurvenkarten zu einem Hintergrundbild beliebiger Auflösung anordnen"
+        }
+        Msg::AllrgbAbout => {
+            "Dichte Karte eines Musters mit genau einem Pixel pro RGB-Farbe erzeugen"
+        }
+        Msg::VisAbout => "Eine Datei visualisieren",
+        Msg::VisSimilarityAbout => {
+            "Ähnlichkeit zweier Dateien blockweise entlang einer Kurve bewerten"
+        }
+        Msg::StreamAbout => "Punkte einer Kurve für andere Werkzeuge auf stdout schreiben",
+        Msg::TableAbout => {
+            "Punkte einer Kurve in einer kompakten Punkttabellen-Datei zwischenspeichern"
+        }
+        Msg::VerifyAbout => {
+            "Prüfen, dass eine Kurve jede Gitterzelle einmal besucht und ihren Index umkehrt"
+        }
+        Msg::PermuteAbout => "Bytes einer Datei entlang einer Kurve umordnen oder wiederherstellen",
+        Msg::GuiAbout => "GUI-Fenster öffnen",
+        Msg::ScreenshotAbout => {
+            "Bildschirmfoto der GUI aufnehmen (erfordert --features screenshot)"
+        }
+        Msg::RecordAbout => "Die GUI mit fester Taktung als Video oder GIF aufzeichnen",
+        Msg::ListCurvesAbout => "Unterstützte Kurvennamen und Einschränkungen auflisten",
+        Msg::BenchAbout => "Komplexität und gemessene Laufzeit pro Aufruf der Kurven anzeigen",
+        Msg::TuiAbout => "Kurven im Terminal erkunden (ohne Anzeige, z. B. über SSH)",
+        Msg::Ok => "OK!",
+        Msg::SavedSnake => "Schlangen-GIF gespeichert!",
+        Msg::SavedMorph => "Morph-GIF gespeichert!",
+        Msg::SavedSweep => "Durchlauf gespeichert!",
+        Msg::SavedWallpaper => "Hintergrundbild gespeichert!",
+        Msg::SavedRecording => "Aufzeichnung gespeichert!",
+        #[cfg(feature = "screenshot")]
+        Msg::SavedScreenshot => "Bildschirmfoto gespeichert!",
+        Msg::Rendered => "Gerendert",
+        Msg::SkippedExisting => "übersprungen (bereits vorhanden)",
+        Msg::Error => "Fehler",
+        Msg::OpeningViewer => {
+            "Keine Ausgabedatei angegeben; Betrachter wird geöffnet \
+             (Fenster schließen zum Beenden)..."
+        }
+        Msg::NothingRendered => "Probelauf, nichts gerendert.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn flag_overrides_environment() {
+        let env = |var: &str| (var == "LANG").then(|| "de_DE.UTF-8".to_string());
+        assert_eq!(locale_language(None, env), Language::German);
+        assert_eq!(locale_language(Some("en"), env), Language::English);
+        assert_eq!(locale_language(Some("fr"), env), Language::German);
+    }
+
+    #[test]
+    fn environment_follows_posix_precedence() {
+        let env = |var: &str| match var {
+            "LC_ALL" => Some(String::new()),
+            "LC_MESSAGES" => Some("de_AT".to_string()),
+            _ => Some("en_US".to_string()),
+        };
+        assert_eq!(locale_language(None, env), Language::German);
+        assert_eq!(locale_language(None, |_| None), Language::English);
+    }
+
+    #[test]
+    fn localizes_subcommands_and_flags() {
+        let cmd = localize(crate::Cli::command(), Language::German);
+        let map = cmd.find_subcommand("map").unwrap();
+        assert_eq!(
+            map.get_about().unwrap().to_string(),
+            tr(Language::German, Msg::MapAbout)
+        );
+        let yes = map.get_arguments().find(|a| a.get_id() == "yes").unwrap();
+        assert_eq!(
+            yes.get_help().unwrap().to_string(),
+            tr(Language::German, Msg::Yes)
+        );
+        let verify = cmd.find_subcommand("verify").unwrap();
+        assert!(!verify.get_arguments().any(|a| a.get_id() == "dry_run"));
+    }
+}
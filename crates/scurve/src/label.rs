@@ -0,0 +1,237 @@
+//! Title and caption text drawn onto rendered images.
+//!
+//! Labels use the Orbitron faces bundled with the GUI, so annotated CLI output
+//! matches GUI screenshots. Text size follows the image width and the color is
+//! picked for contrast with what lies underneath.
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+use anyhow::{Result, anyhow};
+use clap::{Args, ValueEnum};
+use image::{Pixel, Rgba, RgbaImage};
+use scurve_gui::theme::{FONT_ORBITRON_BOLD, FONT_ORBITRON_REGULAR};
+
+/// Image corner that labels are anchored to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Corner {
+    /// Upper left.
+    TopLeft,
+    /// Upper right.
+    TopRight,
+    /// Lower left.
+    #[default]
+    BottomLeft,
+    /// Lower right.
+    BottomRight,
+}
+
+/// Label options shared by the image-producing subcommands.
+#[derive(Args, Clone, Debug, Default)]
+pub struct LabelArgs {
+    /// Title text, drawn in bold.
+    #[arg(
+        long = "title",
+        value_name = "TEXT",
+        help = "Title drawn onto the image"
+    )]
+    pub title: Option<String>,
+
+    /// Caption text, drawn smaller beneath the title.
+    #[arg(
+        long = "caption",
+        value_name = "TEXT",
+        help = "Caption drawn beneath the title"
+    )]
+    pub caption: Option<String>,
+
+    /// Corner the labels are anchored to.
+    #[arg(
+        long = "label-corner",
+        value_enum,
+        default_value = "bottom-left",
+        help = "Corner for the title and caption"
+    )]
+    pub corner: Corner,
+}
+
+/// One line of text to lay out.
+struct Line<'a> {
+    /// Face to draw with.
+    font: FontRef<'static>,
+    /// Text content.
+    text: &'a str,
+    /// Pixel height of the face.
+    scale: PxScale,
+}
+
+impl Line<'_> {
+    /// Width and height of the laid-out line, in pixels.
+    fn size(&self) -> (f32, f32) {
+        let font = self.font.as_scaled(self.scale);
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in self.text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                width += font.kern(previous, id);
+            }
+            width += font.h_advance(id);
+            previous = Some(id);
+        }
+        (width, font.height())
+    }
+
+    /// Draw the line with its top-left corner at `(x, y)`.
+    fn draw(&self, img: &mut RgbaImage, (x, y): (f32, f32), color: Rgba<u8>) {
+        let font = self.font.as_scaled(self.scale);
+        let (width, height) = img.dimensions();
+        let mut caret = x;
+        let mut previous = None;
+        for c in self.text.chars() {
+            let id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                caret += font.kern(previous, id);
+            }
+            let glyph = id.with_scale_and_position(self.scale, point(caret, y + font.ascent()));
+            caret += font.h_advance(id);
+            previous = Some(id);
+
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + i64::from(gx);
+                let py = bounds.min.y as i64 + i64::from(gy);
+                if let (Ok(px), Ok(py)) = (u32::try_from(px), u32::try_from(py))
+                    && px < width
+                    && py < height
+                {
+                    let mut ink = color;
+                    ink.0[3] = (f32::from(color.0[3]) * coverage.min(1.0)).round() as u8;
+                    img.get_pixel_mut(px, py).blend(&ink);
+                }
+            });
+        }
+    }
+}
+
+/// Draw the title and caption from `labels` onto `img`; does nothing without text.
+pub fn draw_labels(img: &mut RgbaImage, labels: &LabelArgs) -> Result<()> {
+    let title_px = (img.width() as f32 / 18.0).max(12.0);
+    let mut lines = Vec::new();
+    if let Some(text) = labels.title.as_deref() {
+        lines.push(Line {
+            font: face(FONT_ORBITRON_BOLD)?,
+            text,
+            scale: PxScale::from(title_px),
+        });
+    }
+    if let Some(text) = labels.caption.as_deref() {
+        lines.push(Line {
+            font: face(FONT_ORBITRON_REGULAR)?,
+            text,
+            scale: PxScale::from((title_px * 0.6).max(10.0)),
+        });
+    }
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let margin = (img.width() as f32 / 40.0).max(4.0);
+    let sizes: Vec<(f32, f32)> = lines.iter().map(Line::size).collect();
+    let block_height: f32 = sizes.iter().map(|&(_, h)| h).sum();
+    let (img_w, img_h) = (img.width() as f32, img.height() as f32);
+    let top = match labels.corner {
+        Corner::TopLeft | Corner::TopRight => margin,
+        Corner::BottomLeft | Corner::BottomRight => img_h - margin - block_height,
+    };
+    let right_aligned = matches!(labels.corner, Corner::TopRight | Corner::BottomRight);
+    let block_width = sizes.iter().map(|&(w, _)| w).fold(0.0, f32::max);
+    let left = if right_aligned {
+        img_w - margin - block_width
+    } else {
+        margin
+    };
+    let color = contrasting_color(img, (left, top), (block_width, block_height));
+
+    let mut y = top;
+    for (line, (line_width, line_height)) in lines.iter().zip(sizes) {
+        let x = if right_aligned {
+            img_w - margin - line_width
+        } else {
+            margin
+        };
+        line.draw(img, (x, y), color);
+        y += line_height;
+    }
+    Ok(())
+}
+
+/// Parse one of the bundled font faces.
+fn face(bytes: &'static [u8]) -> Result<FontRef<'static>> {
+    FontRef::try_from_slice(bytes).map_err(|e| anyhow!("bundled font is invalid: {e}"))
+}
+
+/// Black or white, whichever stands out more against the average of the
+/// pixels in the `size` box at `origin`.
+fn contrasting_color(img: &RgbaImage, origin: (f32, f32), size: (f32, f32)) -> Rgba<u8> {
+    let clamp_x = |v: f32| (v.max(0.0) as u32).min(img.width());
+    let clamp_y = |v: f32| (v.max(0.0) as u32).min(img.height());
+    let (x0, x1) = (clamp_x(origin.0), clamp_x(origin.0 + size.0));
+    let (y0, y1) = (clamp_y(origin.1), clamp_y(origin.1 + size.1));
+
+    let (mut total, mut count) = (0.0, 0u32);
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let [r, g, b, _] = img.get_pixel(x, y).0;
+            total += 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+            count += 1;
+        }
+    }
+    if count > 0 && total / f64::from(count) < 128.0 {
+        Rgba([0xff, 0xff, 0xff, 0xff])
+    } else {
+        Rgba([0, 0, 0, 0xff])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count pixels in the given quadrant that differ from `background`.
+    fn inked(img: &RgbaImage, background: Rgba<u8>, right: bool, bottom: bool) -> usize {
+        let (w, h) = (img.width() / 2, img.height() / 2);
+        let (x0, y0) = (if right { w } else { 0 }, if bottom { h } else { 0 });
+        (y0..y0 + h)
+            .flat_map(|y| (x0..x0 + w).map(move |x| (x, y)))
+            .filter(|&(x, y)| *img.get_pixel(x, y) != background)
+            .count()
+    }
+
+    #[test]
+    fn labels_land_in_the_chosen_corner_with_contrast() {
+        let dark = Rgba([0x06, 0x08, 0x14, 0xff]);
+        let mut img = RgbaImage::from_pixel(256, 256, dark);
+        let labels = LabelArgs {
+            title: Some("Hilbert".to_string()),
+            caption: Some("order 4".to_string()),
+            corner: Corner::TopRight,
+        };
+        draw_labels(&mut img, &labels).unwrap();
+
+        assert!(inked(&img, dark, true, false) > 0);
+        assert_eq!(inked(&img, dark, false, false), 0);
+        assert_eq!(inked(&img, dark, false, true), 0);
+        assert_eq!(inked(&img, dark, true, true), 0);
+        assert!(img.pixels().any(|p| *p == Rgba([0xff, 0xff, 0xff, 0xff])));
+    }
+
+    #[test]
+    fn no_text_leaves_the_image_alone() {
+        let white = Rgba([0xff, 0xff, 0xff, 0xff]);
+        let mut img = RgbaImage::from_pixel(64, 64, white);
+        draw_labels(&mut img, &LabelArgs::default()).unwrap();
+        assert!(img.pixels().all(|p| *p == white));
+    }
+}
@@ -4,38 +4,87 @@
 //! GUI.
 
 use std::{
+    array, env,
     fmt::Display,
+    fs,
+    io::{self, BufWriter, Write},
     ops::Range,
-    path::{Path, PathBuf},
+    path::{self, Path, PathBuf},
     process,
     str::FromStr,
 };
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{Result, bail};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
 use colornames::Color;
 use image::{Rgba, RgbaImage};
-use spacecurve::registry;
+use scurve_gui::{
+    Pane,
+    i18n::Language,
+    share::StateSpec,
+    theme::preset::{self, Preset},
+};
+#[cfg(feature = "screenshot")]
+use scurve_gui::{ScreenshotConfig, ScreenshotTarget};
+use spacecurve::{
+    metrics,
+    registry::{self, CurveEntry, CurveId},
+    segment::{Adjacency, Metric},
+    table,
+    verify::{self, Tally},
+};
 
+/// Skip and overwrite rules for commands writing many outputs.
+mod batch;
 /// CLI command implementations.
 mod cmd;
+/// Size estimates and confirmation before huge renders.
+mod confirm;
+/// Translated help and status messages.
+mod i18n;
+/// Title and caption overlays for rendered images.
+mod label;
 /// Rendering helpers shared by the CLI.
 mod map;
+/// Reproduction metadata embedded in PNG outputs.
+mod metadata;
+/// Validation summaries for `--dry-run`.
+mod plan;
+/// Scripted GUI recordings for the `record` subcommand.
+mod record;
+/// Terminal curve explorer for the `tui` subcommand.
+mod tui;
+
+use crate::{
+    batch::BatchArgs,
+    cmd::{ByteColoring, WallpaperLayout},
+    confirm::{Estimate, confirm},
+    i18n::{Msg, tr},
+    label::{LabelArgs, draw_labels},
+    map::MapPalette,
+    metadata::{PngMetadata, save_image},
+    plan::Plan,
+    record::RecordOptions,
+};
 
-use crate::map::MapPalette;
+/// Stroke color used when neither `--fg` nor `--theme` is given.
+const DEFAULT_FOREGROUND: Rgba<u8> = Rgba([0x80, 0x80, 0xff, 0xff]);
+
+/// Background color used when neither `--bg` nor `--theme` is given.
+const DEFAULT_BACKGROUND: Rgba<u8> = Rgba([0xff, 0xff, 0xff, 0xff]);
 
 /// Half-open range of curve offsets parsed from `--chunk`.
 #[derive(Clone, Copy, Debug)]
 struct ChunkOffsets {
     /// Inclusive start offset for rendering.
-    start: u32,
+    start: u64,
     /// Exclusive end offset for rendering.
-    end: u32,
+    end: u64,
 }
 
 impl ChunkOffsets {
     /// Convert the offsets into a standard half-open range.
-    fn into_range(self) -> Range<u32> {
+    fn into_range(self) -> Range<u64> {
         self.start..self.end
     }
 }
@@ -48,8 +97,8 @@ impl FromStr for ChunkOffsets {
             .split_once(':')
             .ok_or_else(|| "chunk must be in START:END form".to_string())?;
 
-        let parse_bound = |label: &str, bound: &str| -> Result<u32, String> {
-            bound.trim().parse::<u32>().map_err(|_| {
+        let parse_bound = |label: &str, bound: &str| -> Result<u64, String> {
+            bound.trim().parse::<u64>().map_err(|_| {
                 format!("invalid {label} offset '{bound}': expected a non-negative integer")
             })
         };
@@ -67,17 +116,70 @@ impl FromStr for ChunkOffsets {
     }
 }
 
-/// Validate a curve name against the known set.
-fn parse_curve_name(s: &str) -> Result<String, String> {
-    if registry::CURVE_NAMES.contains(&s) {
-        Ok(s.to_string())
-    } else {
-        Err(format!(
+/// Look up a curve by name, including curves added with
+/// [`registry::register`].
+fn parse_curve_name(s: &str) -> Result<&'static CurveEntry, String> {
+    registry::find(s).ok_or_else(|| {
+        format!(
             "Invalid curve name '{}'. Valid options: {}",
             s,
-            registry::CURVE_NAMES.join(", ")
-        ))
+            registry::curve_names(true).join(", ")
+        )
+    })
+}
+
+/// Parse a `WIDTHxHEIGHT` pixel resolution such as `5120x1440`.
+fn parse_resolution(s: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid resolution '{s}': expected WIDTHxHEIGHT, e.g. 5120x1440");
+    let (width, height) = s.split_once(['x', 'X', '×']).ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(format!("resolution {width}x{height} must be at least 1x1"));
+    }
+    Ok((width, height))
+}
+
+/// Parse an opacity between 0 and 1.
+fn parse_opacity(s: &str) -> Result<f32, String> {
+    let value: f32 = s.parse().map_err(|_| format!("Invalid opacity '{s}'"))?;
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("Opacity {value} is outside 0..=1"))
+    }
+}
+
+/// Parse a `--theme` name into one of the presets shared with the GUI.
+fn parse_theme(s: &str) -> Result<Preset, String> {
+    preset::find(s).ok_or_else(|| {
+        let keys: Vec<_> = preset::ALL.iter().map(|p| p.key).collect();
+        format!("Invalid theme '{s}'. Valid options: {}", keys.join(", "))
+    })
+}
+
+/// Pick the color for one role: the explicit flag, else the theme's, else `default`.
+fn themed(explicit: Option<Rgba<u8>>, theme: Option<[u8; 4]>, default: Rgba<u8>) -> Rgba<u8> {
+    explicit.or(theme.map(Rgba)).unwrap_or(default)
+}
+
+/// Parse a `--long` value: `any` for every edge, or `METRIC[:MAX]` for edges
+/// within MAX (default 1) under the named metric.
+fn parse_long_edges(s: &str) -> Result<Adjacency, String> {
+    if s.eq_ignore_ascii_case("any") {
+        return Ok(Adjacency::ANY);
     }
+    let (metric, threshold) = s.split_once(':').unwrap_or((s, "1"));
+    let metric: Metric = metric.trim().parse().map_err(|_| {
+        format!("invalid metric '{metric}': expected manhattan, chebyshev or euclidean")
+    })?;
+    let threshold = threshold
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|t| *t >= 0.0)
+        .ok_or_else(|| format!("invalid distance '{threshold}': expected a non-negative number"))?;
+    Ok(Adjacency::new(metric, threshold))
 }
 
 /// Parse a named or hex color into an `Rgba` value (alpha defaults to 0xff).
@@ -153,11 +255,113 @@ struct Cli {
     #[arg(short, action = clap::ArgAction::Count, help = "Sets the level of verbosity")]
     v: u8,
 
+    /// Flags shared by every subcommand.
+    #[command(flatten)]
+    global: GlobalArgs,
+
     /// Command to execute.
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Flags accepted by every subcommand.
+#[derive(Args, Clone, Copy, Debug)]
+struct GlobalArgs {
+    /// Language of help and messages, overriding the environment.
+    #[arg(
+        long = "locale",
+        value_name = "LANG",
+        global = true,
+        value_parser = i18n::parse_locale,
+        help = "Language of help and messages (en, de) [default: from LANG]"
+    )]
+    locale: Option<Language>,
+}
+
+impl GlobalArgs {
+    /// Language for messages: `--locale`, else the environment.
+    fn language(self) -> Language {
+        self.locale.unwrap_or_else(i18n::env_language)
+    }
+
+    /// Options for a subcommand that renders without writing PNG metadata.
+    fn render(self, args: RenderArgs) -> RenderOptions {
+        RenderOptions {
+            yes: args.yes,
+            dry_run: args.dry_run,
+            metadata: false,
+            lang: self.language(),
+        }
+    }
+
+    /// Options for a subcommand that can write PNGs.
+    fn png(self, args: PngArgs) -> RenderOptions {
+        RenderOptions {
+            metadata: !args.no_metadata,
+            ..self.render(args.render)
+        }
+    }
+}
+
+/// Flags of the subcommands that render images, GIFs or videos.
+#[derive(Args, Clone, Copy, Debug)]
+struct RenderArgs {
+    /// Skip the confirmation required for very large renders.
+    #[arg(
+        short = 'y',
+        long = "yes",
+        help = "Render without confirmation even above 8192×8192 pixels or 2000 frames"
+    )]
+    yes: bool,
+
+    /// Validate and summarise renders without performing them.
+    #[arg(
+        long = "dry-run",
+        help = "Validate curves, chunks and paths and print the expected output size without rendering"
+    )]
+    dry_run: bool,
+}
+
+/// Flags of the subcommands that can write PNGs.
+#[derive(Args, Clone, Copy, Debug)]
+struct PngArgs {
+    /// Confirmation and dry-run flags.
+    #[command(flatten)]
+    render: RenderArgs,
+
+    /// Omit reproduction metadata from PNG outputs.
+    #[arg(
+        long = "no-metadata",
+        help = "Do not embed the command line, curve and version in PNG outputs"
+    )]
+    no_metadata: bool,
+}
+
+/// What a rendering subcommand runs with, gathered from its flags.
+#[derive(Clone, Copy, Debug)]
+struct RenderOptions {
+    /// Skip the confirmation for very large renders.
+    yes: bool,
+    /// Print the plan instead of rendering.
+    dry_run: bool,
+    /// Embed reproduction metadata in PNG outputs.
+    metadata: bool,
+    /// Language for messages.
+    lang: Language,
+}
+
+impl RenderOptions {
+    /// Metadata to embed in PNG outputs, unless `--no-metadata` was given.
+    fn metadata(self) -> Option<PngMetadata> {
+        self.metadata.then(PngMetadata::new)
+    }
+
+    /// Language for messages.
+    fn language(self) -> Language {
+        self.lang
+    }
+}
+
 /// Screenshot target for the GUI.
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 enum ScreenshotPane {
@@ -176,6 +380,74 @@ enum ScreenshotPane {
     Settings3D,
 }
 
+/// Byte coloring scheme for `vis`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum VisMode {
+    /// One color per byte class.
+    Class,
+    /// The byte value through a continuous colormap.
+    Value,
+}
+
+/// Pane shown in a GUI recording.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum RecordPane {
+    /// The 2D curve visualization pane.
+    #[value(name = "2d")]
+    TwoD,
+    /// The 3D curve visualization pane.
+    #[value(name = "3d")]
+    ThreeD,
+}
+
+/// Curve and display settings shown in a GUI screenshot.
+#[derive(Args, Clone, Copy, Debug)]
+struct ScreenshotView {
+    #[arg(
+        short = 'c',
+        long = "curve",
+        value_parser = parse_curve_name,
+        help = "Curve to show (defaults to the GUI's first curve)"
+    )]
+    /// Curve to show.
+    curve: Option<&'static CurveEntry>,
+
+    #[arg(
+        short = 's',
+        long = "size",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Grid size of the captured pane"
+    )]
+    /// Grid size of the captured pane.
+    size: Option<u32>,
+
+    #[arg(
+        long = "snake",
+        value_name = "BOOL",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        help = "Show the snake overlay (on/off)"
+    )]
+    /// Snake overlay visibility.
+    snake: Option<bool>,
+
+    #[arg(
+        long = "opacity",
+        value_parser = parse_opacity,
+        help = "Main curve opacity between 0 and 1"
+    )]
+    /// Main curve opacity.
+    opacity: Option<f32>,
+
+    #[arg(
+        long = "rotation",
+        value_name = "DEGREES",
+        allow_negative_numbers = true,
+        help = "Rotation of the 3D view about its vertical axis"
+    )]
+    /// 3D view rotation in degrees.
+    rotation: Option<f32>,
+}
+
 #[derive(Subcommand)]
 /// Subcommands supported by the `scurve` tool.
 enum Commands {
@@ -210,31 +482,42 @@ enum Commands {
             long = "fg",
             visible_alias = "foreground",
             value_parser = parse_rgba_color,
-            default_value = "#8080ff",
             value_name = "HEX",
-            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional)"
+            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #8080ff or the theme's]"
         )]
-        /// Stroke color for the curve.
-        foreground: Rgba<u8>,
+        /// Stroke color for the curve, overriding the theme.
+        foreground: Option<Rgba<u8>>,
 
         #[arg(
             long = "bg",
             visible_alias = "background",
             value_parser = parse_rgba_color,
-            default_value = "#ffffff",
             value_name = "HEX",
-            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional)"
+            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #ffffff or the theme's]"
         )]
-        /// Background color for the map.
-        background: Rgba<u8>,
+        /// Background color for the map, overriding the theme.
+        background: Option<Rgba<u8>>,
+
+        #[arg(
+            long = "theme",
+            value_name = "THEME",
+            value_parser = parse_theme,
+            help = "Color theme shared with the GUI (neon, paper, mono)"
+        )]
+        /// Coordinated colors applied before `--fg`/`--bg`.
+        theme: Option<Preset>,
 
         #[arg(
             long = "long",
-            default_value_t = false,
-            help = "Include long edges (segments longer than 1 unit)"
+            value_name = "METRIC[:MAX]",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "any",
+            value_parser = parse_long_edges,
+            help = "Include long edges; with =METRIC[:MAX] (manhattan, chebyshev, euclidean) only those within MAX (default 1)"
         )]
-        /// Render long edges between non-adjacent points.
-        long_edges: bool,
+        /// Which long edges to render; unit steps only when omitted.
+        long_edges: Option<Adjacency>,
 
         #[arg(
             long = "chunk",
@@ -244,13 +527,29 @@ enum Commands {
         /// Optional start/end offsets (START:END) for the rendered curve segment.
         chunk: Option<ChunkOffsets>,
 
-        #[arg(help = &format!("Pattern name (options: {})", registry::CURVE_NAMES.join(", ")), value_parser = parse_curve_name)]
+        #[arg(
+            long = "index-map",
+            value_name = "PATH",
+            help = "Also write the curve index of every grid cell (.npy, or 16-bit .png/.png16)"
+        )]
+        /// Optional companion file mapping grid cells back to curve indices.
+        index_map: Option<PathBuf>,
+
+        /// Title and caption overlay.
+        #[command(flatten)]
+        labels: LabelArgs,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
         /// Pattern name.
-        pattern: String,
+        pattern: &'static CurveEntry,
 
         #[arg(help = "Optional output file path; opens a viewer when omitted")]
         /// Optional output file path (launches a viewer when not provided).
         output: Option<PathBuf>,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
     },
 
     #[command(about = "Generate an animated snake GIF for a pattern")]
@@ -284,40 +583,51 @@ enum Commands {
             long = "fg",
             visible_alias = "foreground",
             value_parser = parse_rgba_color,
-            default_value = "#8080ff",
             value_name = "HEX",
-            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional)"
+            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #8080ff or the theme's]"
         )]
-        /// Stroke color for the curve.
-        foreground: Rgba<u8>,
+        /// Stroke color for the curve, overriding the theme.
+        foreground: Option<Rgba<u8>>,
 
         #[arg(
             long = "bg",
             visible_alias = "background",
             value_parser = parse_rgba_color,
-            default_value = "#ffffff",
             value_name = "HEX",
-            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional)"
+            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #ffffff or the theme's]"
         )]
-        /// Background color for the map.
-        background: Rgba<u8>,
+        /// Background color for the map, overriding the theme.
+        background: Option<Rgba<u8>>,
+
+        #[arg(
+            long = "theme",
+            value_name = "THEME",
+            value_parser = parse_theme,
+            help = "Color theme shared with the GUI (neon, paper, mono)"
+        )]
+        /// Coordinated colors applied before `--fg`/`--bg`.
+        theme: Option<Preset>,
 
         #[arg(
             long = "full",
             value_name = "COLOR",
             value_parser = parse_rgba_color,
-            help = "Draw the full curve in COLOR beneath the animated snake"
+            help = "Draw the full curve in COLOR beneath the animated snake (implied by --theme)"
         )]
         /// Optional full-curve color to render behind the snake overlay.
         full: Option<Rgba<u8>>,
 
         #[arg(
             long = "long",
-            default_value_t = false,
-            help = "Include long edges (segments longer than 1 unit)"
+            value_name = "METRIC[:MAX]",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "any",
+            value_parser = parse_long_edges,
+            help = "Include long edges; with =METRIC[:MAX] (manhattan, chebyshev, euclidean) only those within MAX (default 1)"
         )]
-        /// Render long edges between non-adjacent points.
-        long_edges: bool,
+        /// Which long edges to render; unit steps only when omitted.
+        long_edges: Option<Adjacency>,
 
         #[arg(
             long = "chunk",
@@ -337,152 +647,1311 @@ enum Commands {
         /// Frames per second for the animation (1-120).
         fps: u16,
 
-        #[arg(help = &format!("Pattern name (options: {})", registry::CURVE_NAMES.join(", ")), value_parser = parse_curve_name)]
+        #[arg(
+            long = "trail",
+            value_name = "N",
+            default_value_t = 0,
+            help = "Draw the N steps behind the snake as a fading trail"
+        )]
+        /// Length of the fading trail behind the snake.
+        trail: u32,
+
+        #[arg(
+            long = "substeps",
+            value_name = "N",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u32).range(1..=16),
+            help = "Frames per curve step; above 1 the snake glides between cells"
+        )]
+        /// Frames rendered for each step of the snake (1-16).
+        substeps: u32,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
         /// Pattern name.
-        pattern: String,
+        pattern: &'static CurveEntry,
 
         #[arg(help = "Output GIF file path")]
         /// Output GIF path (required).
         output: PathBuf,
+
+        /// Confirmation and dry-run flags.
+        #[command(flatten)]
+        render: RenderArgs,
     },
 
-    #[command(
-        about = "Generate a dense map of a pattern that contains one pixel for each RGB colour"
-    )]
-    /// Generate a dense map that contains one pixel for each RGB colour.
-    Allrgb {
-        #[arg(short = 'c', help = &format!("Pattern name for color map (options: {})", registry::CURVE_NAMES.join(", ")), value_parser = parse_curve_name)]
-        /// Optional pattern name for the color map (defaults to `pattern`).
-        colormap: Option<String>,
+    #[command(about = "Generate an animated GIF morphing one curve into another")]
+    /// Generate an animated GIF in which each index travels from its cell on one curve to its cell on another.
+    Morph {
+        #[arg(short = 's', long = "size", help = "Square image size in pixels")]
+        /// Image size in pixels (square output).
+        size: Option<u32>,
 
-        #[arg(help = &format!("Pattern name (options: {})", registry::CURVE_NAMES.join(", ")), value_parser = parse_curve_name)]
-        /// Pattern name for pixel layout.
-        pattern: String,
+        #[arg(
+            short = 'd',
+            long = "dimension",
+            value_name = "SIDE",
+            help = "Logical curve dimension shared by both curves (renders a SIDE×SIDE grid)"
+        )]
+        /// Side length of the curve grid (SIDE×SIDE points).
+        curve_dimension: Option<u32>,
 
-        #[arg(help = "Optional output file path; opens a viewer when omitted")]
-        /// Optional output file path (launches a viewer when not provided).
-        output: Option<PathBuf>,
-    },
+        #[arg(
+            short = 'w',
+            long = "line-width",
+            value_name = "PIXELS",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Line width in pixels for the curve stroke"
+        )]
+        /// Stroke width for the rendered curve.
+        line_width: u32,
 
-    #[command(about = "visualise a file")]
-    /// Visualise a file using a space‑filling curve.
-    Vis {
-        #[arg(short = 'p', help = &format!("Pattern name (options: {})", registry::CURVE_NAMES.join(", ")), value_parser = parse_curve_name)]
-        /// Optional pattern name (defaults to `hilbert`).
-        pattern: Option<String>,
+        #[arg(
+            long = "fg",
+            visible_alias = "foreground",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #8080ff or the theme's]"
+        )]
+        /// Stroke color for the curve, overriding the theme.
+        foreground: Option<Rgba<u8>>,
 
-        #[arg(short = 'w', help = "Image width")]
-        /// Output image width/height in pixels.
-        width: Option<u32>,
+        #[arg(
+            long = "bg",
+            visible_alias = "background",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #ffffff or the theme's]"
+        )]
+        /// Background color, overriding the theme.
+        background: Option<Rgba<u8>>,
 
-        #[arg(help = "File to visualise")]
-        /// Input file to visualise.
-        input: PathBuf,
+        #[arg(
+            long = "theme",
+            value_name = "THEME",
+            value_parser = parse_theme,
+            help = "Color theme shared with the GUI (neon, paper, mono)"
+        )]
+        /// Coordinated colors applied before `--fg`/`--bg`.
+        theme: Option<Preset>,
 
-        #[arg(help = "Optional output file path; opens a viewer when omitted")]
-        /// Optional output file path (launches a viewer when not provided).
-        output: Option<PathBuf>,
-    },
+        #[arg(
+            long = "long",
+            value_name = "METRIC[:MAX]",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "any",
+            value_parser = parse_long_edges,
+            help = "Include long edges; with =METRIC[:MAX] (manhattan, chebyshev, euclidean) only those within MAX (default 1)"
+        )]
+        /// Which long edges to render; unit steps only when omitted.
+        long_edges: Option<Adjacency>,
 
-    #[command(about = "Open GUI window")]
-    /// Launch the interactive GUI.
-    Gui {
         #[arg(
-            long = "dev",
-            help = "Show experimental curves (e.g. Hairy Onion) in the GUI"
+            long = "frames",
+            default_value_t = 48,
+            value_parser = clap::value_parser!(u32).range(2..),
+            help = "Number of frames from the first curve to the second"
         )]
-        /// Enable experimental curves in the GUI selectors.
-        dev: bool,
-    },
+        /// Frame count, including both end frames (at least 2).
+        frames: u32,
 
-    #[command(about = "Take a screenshot of the GUI (requires --features screenshot)")]
-    /// Capture a screenshot of a specific GUI pane.
-    Screenshot {
         #[arg(
-            short = 'p',
-            long = "pane",
-            value_enum,
-            default_value = "2d",
-            help = "Which pane to screenshot"
+            long = "fps",
+            default_value_t = 20,
+            value_parser = clap::value_parser!(u16).range(1..=120),
+            help = "Frames per second for the animated GIF"
         )]
-        /// Which pane to capture.
-        pane: ScreenshotPane,
+        /// Frames per second for the animation (1-120).
+        fps: u16,
 
-        #[arg(help = "Output PNG file path")]
-        /// Output file path for the screenshot.
+        #[arg(help = &format!("Curve to start from (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Curve shown in the first frame.
+        from: &'static CurveEntry,
+
+        #[arg(help = &format!("Curve to end on (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Curve shown in the last frame.
+        to: &'static CurveEntry,
+
+        #[arg(help = "Output GIF file path")]
+        /// Output GIF path (required).
         output: PathBuf,
+
+        /// Confirmation and dry-run flags.
+        #[command(flatten)]
+        render: RenderArgs,
     },
 
-    #[command(
-        name = "list-curves",
-        about = "List supported curve names and constraints"
-    )]
-    /// List supported curves and their constraints.
-    ListCurves,
-}
+    #[command(about = "Render a curve at several grid sizes as an animation or montage")]
+    /// Show a curve's self-similar structure by rendering it at increasing grid sizes.
+    Sweep {
+        #[arg(
+            short = 's',
+            long = "size",
+            default_value_t = 256,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Size of each square map in pixels"
+        )]
+        /// Size of each map in pixels.
+        size: u32,
 
-/// Print a success message or exit with an error.
-fn report_ok<E: Display>(result: Result<(), E>, ok_msg: &str) {
-    match result {
-        Ok(()) => println!("{ok_msg}"),
-        Err(e) => {
-            eprintln!("{e}");
-            process::exit(1);
-        }
-    }
-}
+        #[arg(
+            long = "sizes",
+            value_name = "SIDES",
+            value_delimiter = ',',
+            default_value = "2,4,8,16,32",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Comma-separated grid sides, each rounded up to one the curve accepts"
+        )]
+        /// Grid sides to render, in order.
+        sides: Vec<u32>,
 
-/// Save an image to disk or show it in an egui viewer when no path is given.
-fn deliver_image(image: RgbaImage, output: Option<&Path>, window_title: &str) -> Result<()> {
-    if let Some(path) = output {
-        image.save(path)?;
-    } else {
-        println!("No output file provided; opening viewer (close the window to finish)...");
-        egui_img::view_image(window_title, image)?;
-    }
+        #[arg(
+            short = 'w',
+            long = "line-width",
+            value_name = "PIXELS",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Line width in pixels for the curve stroke"
+        )]
+        /// Stroke width for the rendered curve.
+        line_width: u32,
 
-    Ok(())
-}
+        #[arg(
+            long = "fg",
+            visible_alias = "foreground",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #8080ff or the theme's]"
+        )]
+        /// Stroke color for the curve, overriding the theme.
+        foreground: Option<Rgba<u8>>,
 
-/// Handle the `vis` subcommand.
-fn handle_vis(
-    input: &Path,
-    output: Option<&Path>,
-    width: Option<u32>,
-    pattern: Option<&str>,
-) -> Result<()> {
-    let width = width.unwrap_or(256);
-    let pattern_name = pattern.unwrap_or("hilbert");
-    let image = cmd::vis(input, width, pattern_name)?;
-    deliver_image(image, output, &format!("vis: {pattern_name}"))
-}
+        #[arg(
+            long = "bg",
+            visible_alias = "background",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #ffffff or the theme's]"
+        )]
+        /// Background color, overriding the theme.
+        background: Option<Rgba<u8>>,
 
-/// Handle the `map` subcommand.
-fn handle_map(
-    size: Option<u32>,
-    curve_dimension: Option<u32>,
-    pattern: &str,
-    output: Option<&Path>,
-    chunk: Option<ChunkOffsets>,
-    stroke: map::StrokeOptions,
-) -> Result<()> {
-    let size = size.unwrap_or(512);
-    // Default keeps behaviour similar to the previous 16×16 grid (256 points).
-    let requested_dimension = curve_dimension.unwrap_or(16);
-    let render = cmd::map(
-        size,
-        requested_dimension,
-        pattern,
+        #[arg(
+            long = "theme",
+            value_name = "THEME",
+            value_parser = parse_theme,
+            help = "Color theme shared with the GUI (neon, paper, mono)"
+        )]
+        /// Coordinated colors applied before `--fg`/`--bg`.
+        theme: Option<Preset>,
+
+        #[arg(
+            long = "long",
+            value_name = "METRIC[:MAX]",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "any",
+            value_parser = parse_long_edges,
+            help = "Include long edges; with =METRIC[:MAX] (manhattan, chebyshev, euclidean) only those within MAX (default 1)"
+        )]
+        /// Which long edges to render; unit steps only when omitted.
+        long_edges: Option<Adjacency>,
+
+        #[arg(
+            long = "fps",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u16).range(1..=120),
+            help = "Frames per second when writing an animated GIF"
+        )]
+        /// Frames per second for the animation (1-120).
+        fps: u16,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Pattern name.
+        pattern: &'static CurveEntry,
+
+        #[arg(
+            help = "Output path; .gif steps through the sizes, a directory (existing or ending in '/') gets one PNG per size, other formats get a side-by-side montage"
+        )]
+        /// Output GIF, image or directory path.
+        output: PathBuf,
+
+        /// Treatment of existing files when writing into a directory.
+        #[command(flatten)]
+        batch: BatchArgs,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
+    },
+
+    #[command(about = "Compose curve maps into a wallpaper of any resolution")]
+    /// Lay one or more curve maps out on a canvas of any size, e.g. a
+    /// multi-monitor wallpaper.
+    Wallpaper {
+        #[arg(
+            short = 'r',
+            long = "resolution",
+            value_name = "WIDTHxHEIGHT",
+            default_value = "1920x1080",
+            value_parser = parse_resolution,
+            help = "Canvas size in pixels"
+        )]
+        /// Canvas width and height in pixels.
+        resolution: (u32, u32),
+
+        #[arg(
+            long = "curves",
+            value_name = "PATTERNS",
+            value_delimiter = ',',
+            default_value = "hilbert",
+            value_parser = parse_curve_name,
+            help = "Comma-separated curves, one tile each"
+        )]
+        /// Curves to draw, in tile order.
+        curves: Vec<&'static CurveEntry>,
+
+        #[arg(
+            short = 'd',
+            long = "dimension",
+            value_name = "SIDE",
+            default_value_t = 32,
+            help = "Logical curve dimension, rounded up to one each curve accepts"
+        )]
+        /// Requested side length of every curve grid.
+        curve_dimension: u32,
+
+        #[arg(
+            long = "layout",
+            value_enum,
+            default_value_t = WallpaperLayout::Row,
+            help = "Tile arrangement: row, column, or grid"
+        )]
+        /// How tiles are arranged on the canvas.
+        layout: WallpaperLayout,
+
+        #[arg(
+            long = "padding",
+            value_name = "PIXELS",
+            default_value_t = 40,
+            help = "Pixels around and between tiles"
+        )]
+        /// Padding around and between tiles.
+        padding: u32,
+
+        #[arg(
+            short = 'w',
+            long = "line-width",
+            value_name = "PIXELS",
+            default_value_t = 2,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Line width in pixels for the curve stroke"
+        )]
+        /// Stroke width for the rendered curves.
+        line_width: u32,
+
+        #[arg(
+            long = "fg",
+            visible_alias = "foreground",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Foreground color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #8080ff or the theme's]"
+        )]
+        /// Stroke color for the curves, overriding the theme.
+        foreground: Option<Rgba<u8>>,
+
+        #[arg(
+            long = "bg",
+            visible_alias = "background",
+            value_parser = parse_rgba_color,
+            value_name = "HEX",
+            help = "Background color (name or hex; RGB/RRGGBB with optional alpha, '#' optional) [default: #ffffff or the theme's]"
+        )]
+        /// Canvas color, overriding the theme.
+        background: Option<Rgba<u8>>,
+
+        #[arg(
+            long = "theme",
+            value_name = "THEME",
+            value_parser = parse_theme,
+            help = "Color theme shared with the GUI (neon, paper, mono)"
+        )]
+        /// Coordinated colors applied before `--fg`/`--bg`.
+        theme: Option<Preset>,
+
+        #[arg(help = "Output image path")]
+        /// Output image path.
+        output: PathBuf,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
+    },
+
+    #[command(
+        about = "Generate a dense map of a pattern that contains one pixel for each RGB colour"
+    )]
+    /// Generate a dense map that contains one pixel for each RGB colour.
+    Allrgb {
+        #[arg(short = 'c', help = &format!("Pattern name for color map (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Optional pattern name for the color map (defaults to `pattern`).
+        colormap: Option<&'static CurveEntry>,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Pattern name for pixel layout.
+        pattern: &'static CurveEntry,
+
+        #[arg(help = "Optional output file path; opens a viewer when omitted")]
+        /// Optional output file path (launches a viewer when not provided).
+        output: Option<PathBuf>,
+
+        /// Title and caption overlay.
+        #[command(flatten)]
+        labels: LabelArgs,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
+    },
+
+    #[command(about = "visualise a file")]
+    /// Visualise a file using a space‑filling curve.
+    Vis {
+        #[arg(short = 'p', help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Optional pattern name (defaults to `hilbert`).
+        pattern: Option<&'static CurveEntry>,
+
+        #[arg(short = 'w', help = "Image width")]
+        /// Output image width/height in pixels.
+        width: Option<u32>,
+
+        #[arg(
+            long = "mode",
+            value_enum,
+            default_value = "class",
+            help = "Color bytes by class (zero, 0xff, control, printable, other) or by value through a colormap"
+        )]
+        /// How each byte is colored.
+        mode: VisMode,
+
+        #[arg(
+            long = "colormap",
+            value_enum,
+            help = "Colormap for --mode value [default: viridis]"
+        )]
+        /// Colormap used in value mode.
+        colormap: Option<cmd::Colormap>,
+
+        #[arg(
+            long = "mark-duplicates",
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Tint N-byte blocks whose content repeats elsewhere in the file"
+        )]
+        /// Block size for duplicate detection, or `None` to skip it.
+        mark_duplicates: Option<u32>,
+
+        #[arg(
+            long = "diff",
+            value_name = "OTHER",
+            help = "Compare with OTHER: the viewer blends between the two renders and highlights changed bytes; with an output path, changed pixels are painted in the accent color"
+        )]
+        /// Second file to compare against.
+        diff: Option<PathBuf>,
+
+        #[arg(
+            long = "report",
+            value_name = "PATH",
+            help = "Also write per-region byte statistics (entropy, byte-class ratios, high-entropy runs) to PATH as JSON"
+        )]
+        /// JSON report path, if requested.
+        report: Option<PathBuf>,
+
+        #[arg(
+            long = "report-region",
+            value_name = "BYTES",
+            default_value_t = 4096,
+            value_parser = clap::value_parser!(u32).range(1..),
+            requires = "report",
+            help = "Bytes per region in the --report statistics"
+        )]
+        /// Region size for the report.
+        report_region: u32,
+
+        #[arg(help = "File to visualise")]
+        /// Input file to visualise.
+        input: PathBuf,
+
+        #[arg(help = "Optional output file path; opens a viewer when omitted")]
+        /// Optional output file path (launches a viewer when not provided).
+        output: Option<PathBuf>,
+
+        /// Title and caption overlay.
+        #[command(flatten)]
+        labels: LabelArgs,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
+    },
+
+    #[command(about = "Score how similar two files are, block by block along a curve")]
+    /// Compare two files block-wise and optionally render a heat map.
+    VisSimilarity {
+        #[arg(short = 'p', help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Optional pattern name (defaults to `hilbert`).
+        pattern: Option<&'static CurveEntry>,
+
+        #[arg(
+            short = 'w',
+            help = "Heat map width; the files are split into at most width×width blocks"
+        )]
+        /// Heat map width/height in pixels, which also sets the block count.
+        width: Option<u32>,
+
+        #[arg(long = "json", help = "Print the scores as JSON")]
+        /// Emit machine-readable output.
+        json: bool,
+
+        #[arg(
+            long = "heat",
+            value_name = "PATH",
+            help = "Write a heat map (white = identical, red = different)"
+        )]
+        /// Optional heat map output path.
+        heat: Option<PathBuf>,
+
+        #[arg(help = "First file")]
+        /// First input file.
+        a: PathBuf,
+
+        #[arg(help = "Second file")]
+        /// Second input file.
+        b: PathBuf,
+
+        /// Confirmation, dry-run and metadata flags.
+        #[command(flatten)]
+        render: PngArgs,
+    },
+
+    #[command(about = "Write a curve's points to stdout for other tools")]
+    /// Stream `index x y[ z]` records in curve order.
+    Stream {
+        #[arg(
+            short = 'd',
+            long = "dimension",
+            value_name = "SIDE",
+            default_value_t = 16,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Side length of the curve grid"
+        )]
+        /// Side length of the curve grid.
+        side: u32,
+
+        #[arg(
+            long = "axes",
+            value_name = "N",
+            default_value_t = 2,
+            value_parser = clap::value_parser!(u32).range(2..=3),
+            help = "Number of axes per point: 2 (x, y) or 3 (x, y, z)"
+        )]
+        /// Number of coordinates per point.
+        axes: u32,
+
+        #[arg(
+            long = "binary",
+            help = "Emit little-endian records (u64 index, u32 x, y[, z]) instead of text lines"
+        )]
+        /// Emit raw binary records.
+        binary: bool,
+
+        #[arg(
+            long = "table",
+            value_name = "FILE",
+            conflicts_with_all = ["side", "axes", "pattern"],
+            help = "Read the points from a point table written by `scurve table` instead of a curve"
+        )]
+        /// Cached point table to stream instead of building a curve.
+        table: Option<PathBuf>,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name, required_unless_present = "table")]
+        /// Curve pattern.
+        pattern: Option<&'static CurveEntry>,
+    },
+
+    #[command(about = "Cache a curve's points in a compact point table file")]
+    /// Write a curve's points as a point table.
+    Table {
+        #[arg(
+            short = 'd',
+            long = "dimension",
+            value_name = "SIDE",
+            default_value_t = 16,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Side length of the curve grid"
+        )]
+        /// Side length of the curve grid.
+        side: u32,
+
+        #[arg(
+            long = "axes",
+            value_name = "N",
+            default_value_t = 2,
+            value_parser = clap::value_parser!(u32).range(2..=3),
+            help = "Number of axes per point: 2 (x, y) or 3 (x, y, z)"
+        )]
+        /// Number of coordinates per point.
+        axes: u32,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Curve pattern.
+        pattern: &'static CurveEntry,
+
+        #[arg(help = "Output table file")]
+        /// Output path.
+        output: PathBuf,
+    },
+
+    #[command(about = "Check that a curve visits every grid cell once and inverts its index")]
+    /// Run every validity check over one curve.
+    Verify {
+        #[arg(
+            short = 'd',
+            long = "dimension",
+            value_name = "SIDE",
+            default_value_t = 16,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Side length of the curve grid"
+        )]
+        /// Side length of the curve grid.
+        side: u32,
+
+        #[arg(
+            long = "axes",
+            value_name = "N",
+            default_value_t = 2,
+            value_parser = clap::value_parser!(u32).range(2..=3),
+            help = "Number of curve axes: 2 or 3"
+        )]
+        /// Number of curve axes.
+        axes: u32,
+
+        #[arg(
+            long = "per-index",
+            help = "Also print the local stretch of every index as `index stretch` lines"
+        )]
+        /// Print the dilation map after the report.
+        per_index: bool,
+
+        #[arg(help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Curve pattern.
+        pattern: &'static CurveEntry,
+    },
+
+    #[command(about = "Reorder a file's bytes along a curve, or restore them")]
+    /// Reorder a file's bytes into curve order (or back with `--inverse`).
+    Permute {
+        #[arg(short = 'p', help = &format!("Pattern name (options: {})", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Optional pattern name (defaults to `hilbert`).
+        pattern: Option<&'static CurveEntry>,
+
+        #[arg(
+            long = "inverse",
+            help = "Restore the original byte order of a permuted file"
+        )]
+        /// Undo a previous permutation.
+        inverse: bool,
+
+        #[arg(
+            long = "length",
+            value_name = "BYTES",
+            help = "Original file length to keep when restoring (drops padding)"
+        )]
+        /// Number of restored bytes to keep.
+        length: Option<usize>,
+
+        #[arg(help = "Input file")]
+        /// Input file.
+        input: PathBuf,
+
+        #[arg(help = "Output file")]
+        /// Output file.
+        output: PathBuf,
+    },
+
+    #[command(about = "Open GUI window")]
+    /// Launch the interactive GUI.
+    Gui {
+        #[arg(
+            long = "dev",
+            help = "Show experimental curves (e.g. Hairy Onion) in the GUI"
+        )]
+        /// Enable experimental curves in the GUI selectors.
+        dev: bool,
+        #[arg(
+            long = "demo",
+            help = "Cycle through curves, sizes and panes unattended (Esc to stop)"
+        )]
+        /// Start in demo mode.
+        demo: bool,
+        #[arg(
+            long = "state",
+            value_name = "SPEC",
+            help = "Initial state as copied from the GUI, e.g. 'curve=hilbert&size=32&snake=0'"
+        )]
+        /// Initial viewer state spec.
+        state: Option<StateSpec>,
+        #[arg(
+            long = "screenshot-dir",
+            value_name = "DIR",
+            help = "Directory for Ctrl+S canvas captures (defaults to the working directory)"
+        )]
+        /// Directory for canvas captures.
+        screenshot_dir: Option<PathBuf>,
+        #[arg(
+            long = "fixed-fps",
+            value_name = "FPS",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Advance animations by exactly 1/FPS seconds per frame for reproducible recordings"
+        )]
+        /// Frame rate of the fixed animation clock, if any.
+        fixed_fps: Option<u32>,
+    },
+
+    #[command(about = "Take a screenshot of the GUI (requires --features screenshot)")]
+    /// Capture a screenshot of a specific GUI pane.
+    Screenshot {
+        #[arg(
+            short = 'p',
+            long = "pane",
+            value_enum,
+            default_value = "2d",
+            help = "Which pane to screenshot"
+        )]
+        /// Which pane to capture.
+        pane: ScreenshotPane,
+
+        /// Curve, size and display settings for the capture.
+        #[command(flatten)]
+        view: ScreenshotView,
+
+        #[arg(help = "Output PNG file path")]
+        /// Output file path for the screenshot.
+        output: PathBuf,
+    },
+
+    #[command(about = "Record the GUI on a fixed clock to a video or GIF")]
+    /// Render a scripted GUI session frame by frame and encode it.
+    Record {
+        #[arg(
+            short = 'p',
+            long = "pane",
+            value_enum,
+            default_value = "3d",
+            help = "Which pane to record"
+        )]
+        /// Which pane to record.
+        pane: RecordPane,
+
+        #[arg(
+            long = "seconds",
+            default_value_t = 10,
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Length of the recording"
+        )]
+        /// Length of the recording in seconds.
+        seconds: u32,
+
+        #[arg(
+            long = "fps",
+            default_value_t = 30,
+            value_parser = clap::value_parser!(u16).range(1..=120),
+            help = "Frames per second of the animation clock and the output"
+        )]
+        /// Frames per second (1-120).
+        fps: u16,
+
+        #[arg(
+            short = 'c',
+            long = "curve",
+            value_parser = parse_curve_name,
+            help = "Curve to show (defaults to the GUI's first curve)"
+        )]
+        /// Curve to show.
+        curve: Option<&'static CurveEntry>,
+
+        #[arg(
+            short = 's',
+            long = "size",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Grid size of the recorded pane"
+        )]
+        /// Grid size of the recorded pane.
+        size: Option<u32>,
+
+        #[arg(
+            long = "spin",
+            value_name = "SPEED",
+            help = "3D rotation speed from 0 to 100 [default: the GUI's]"
+        )]
+        /// 3D rotation speed.
+        spin: Option<f32>,
+
+        #[arg(
+            long = "snake",
+            value_name = "BOOL",
+            value_parser = clap::builder::BoolishValueParser::new(),
+            help = "Show the snake overlay (on/off)"
+        )]
+        /// Snake overlay visibility.
+        snake: Option<bool>,
+
+        #[arg(
+            help = "Output path; .gif is encoded directly, other formats (e.g. .mp4) via ffmpeg"
+        )]
+        /// Output video or GIF path.
+        output: PathBuf,
+
+        /// Confirmation and dry-run flags.
+        #[command(flatten)]
+        render: RenderArgs,
+    },
+
+    #[command(
+        name = "list-curves",
+        about = "List supported curve names and constraints"
+    )]
+    /// List supported curves and their constraints.
+    ListCurves,
+
+    #[command(about = "Show complexity notes and measured per-call latency of curves")]
+    /// Calibrate curve operations at several grid sizes.
+    Bench {
+        #[arg(help = &format!("Pattern name (options: {}); all curves when omitted", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Optional pattern to measure (defaults to every curve).
+        pattern: Option<&'static CurveEntry>,
+    },
+
+    #[command(about = "Explore curves in the terminal (no display needed, e.g. over SSH)")]
+    /// Browse curves interactively with a braille rendering.
+    Tui {
+        #[arg(help = &format!("Initial pattern (options: {}); defaults to hilbert", registry::curve_names(true).join(", ")), value_parser = parse_curve_name)]
+        /// Curve shown first.
+        pattern: Option<&'static CurveEntry>,
+    },
+}
+
+/// Print a success message or exit with an error.
+fn report_ok<E: Display>(result: Result<(), E>, ok_msg: &str) {
+    match result {
+        Ok(()) => println!("{ok_msg}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Like [`report_ok`], except that a dry run has already printed its plan and
+/// reports nothing more.
+fn report_rendered<E: Display>(result: Result<(), E>, opts: RenderOptions, ok_msg: Msg) {
+    match result {
+        Ok(()) if opts.dry_run => {}
+        result => report_ok(result, tr(opts.language(), ok_msg)),
+    }
+}
+
+/// Save an image to disk or show it in an egui viewer when no path is given.
+fn deliver_image(
+    image: RgbaImage,
+    output: Option<&Path>,
+    window_title: &str,
+    metadata: Option<&PngMetadata>,
+    lang: Language,
+) -> Result<()> {
+    if let Some(path) = output {
+        save_image(&image, path, metadata)?;
+    } else {
+        println!("{}", tr(lang, Msg::OpeningViewer));
+        egui_img::view_image(window_title, image)?;
+    }
+
+    Ok(())
+}
+
+/// Parameters supplied by the CLI for the `vis` subcommand.
+#[derive(Clone, Copy)]
+struct VisInput<'a> {
+    /// File whose bytes are drawn.
+    input: &'a Path,
+    /// Optional output path; a viewer opens when `None`.
+    output: Option<&'a Path>,
+    /// Requested image width (defaults to 256 when `None`).
+    width: Option<u32>,
+    /// Curve pattern (defaults to Hilbert when `None`).
+    pattern: Option<&'static CurveEntry>,
+    /// How each byte is colored.
+    coloring: ByteColoring,
+    /// Block size for tinting repeated blocks, if requested.
+    mark_duplicates: Option<u32>,
+    /// Second file to compare against, if any.
+    diff: Option<&'a Path>,
+    /// JSON report path and region size, if requested.
+    report: Option<(&'a Path, u32)>,
+    /// Title and caption overlay.
+    labels: &'a LabelArgs,
+}
+
+/// Combine `--mode` and `--colormap` into a byte coloring scheme.
+fn vis_coloring(mode: VisMode, colormap: Option<cmd::Colormap>) -> Result<ByteColoring> {
+    match (mode, colormap) {
+        (VisMode::Class, None) => Ok(ByteColoring::Class),
+        (VisMode::Class, Some(_)) => bail!("--colormap only applies with --mode value"),
+        (VisMode::Value, colormap) => Ok(ByteColoring::Value(
+            colormap.unwrap_or(cmd::Colormap::Viridis),
+        )),
+    }
+}
+
+/// Handle the `vis` subcommand.
+fn handle_vis(input: VisInput<'_>, opts: RenderOptions) -> Result<()> {
+    let VisInput {
+        input,
+        output,
+        width,
+        pattern,
+        coloring,
+        mark_duplicates,
+        diff,
+        report,
+        labels,
+    } = input;
+    let width = width.unwrap_or(256);
+    let pattern = pattern.unwrap_or(CurveId::Hilbert.entry());
+    let estimate = Estimate::image(width, width);
+    if opts.dry_run {
+        let mut plan = Plan::new("vis", estimate)
+            .curve(pattern, 2, width)
+            .input(input);
+        if let Some(other) = diff {
+            plan = plan.input(other);
+        }
+        return plan
+            .output(output)
+            .output(report.map(|(path, _)| path))
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let cmd::VisOutput {
+        mut image,
+        pixel_bytes,
+        counts,
+        report: stats,
+    } = cmd::vis(
+        input,
+        width,
+        pattern,
+        coloring,
+        mark_duplicates,
+        report.map(|(_, region)| region),
+    )?;
+    if let (Some((path, _)), Some(stats)) = (report, &stats) {
+        fs::write(path, vis_report_json(pattern, stats) + "\n")?;
+    }
+    let colors = match coloring {
+        ByteColoring::Class => "class",
+        ByteColoring::Value(colormap) => colormap.key(),
+    };
+    let metadata = opts.metadata().map(|m| {
+        let m = m
+            .with("Curve", pattern.key)
+            .with("Size", width)
+            .with("Colors", colors)
+            .with("Input", input.display());
+        let m = match mark_duplicates {
+            Some(block) => m.with("Duplicates", block),
+            None => m,
+        };
+        match diff {
+            Some(other) => m.with("Diff", other.display()),
+            None => m,
+        }
+    });
+    if let Some(other) = diff {
+        let after = cmd::vis(other, width, pattern, coloring, mark_duplicates, None)?;
+        let changed = after.changed_pixels_from(&pixel_bytes);
+        let accent = Rgba(preset::NEON.accent.to_array());
+        if output.is_none() {
+            println!("{}", tr(opts.language(), Msg::OpeningViewer));
+            let mut after = after.image;
+            draw_labels(&mut image, labels)?;
+            draw_labels(&mut after, labels)?;
+            let diff = egui_img::Diff {
+                after,
+                changed,
+                accent: accent.0,
+            };
+            return egui_img::view_image_diff(&format!("vis diff: {pattern}"), image, diff);
+        }
+        cmd::mark_changes(&mut image, &changed, accent);
+    }
+    draw_labels(&mut image, labels)?;
+    if output.is_none() {
+        println!("{}", tr(opts.language(), Msg::OpeningViewer));
+        let histogram = egui_img::Histogram {
+            counts,
+            colors: array::from_fn(|byte| coloring.color(byte as u8).0),
+            pixel_bytes,
+        };
+        return egui_img::view_image_with_histogram(&format!("vis: {pattern}"), image, histogram);
+    }
+    deliver_image(
+        image,
+        output,
+        &format!("vis: {pattern}"),
+        metadata.as_ref(),
+        opts.language(),
+    )
+}
+
+/// Render byte statistics as a single-line JSON object.
+fn vis_report_json(pattern: &'static CurveEntry, report: &cmd::VisReport) -> String {
+    let stats = |s: &cmd::ByteStats| {
+        let classes = cmd::ByteClass::ALL
+            .iter()
+            .zip(s.classes)
+            .map(|(class, ratio)| format!(r#""{}":{ratio:.6}"#, class.key()))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#""offset":{},"len":{},"entropy":{:.6},"classes":{{{classes}}}"#,
+            s.offset, s.len, s.entropy
+        )
+    };
+    let runs = report
+        .high_entropy_runs()
+        .iter()
+        .map(|run| format!("[{},{}]", run.start, run.end))
+        .collect::<Vec<_>>()
+        .join(",");
+    let regions = report
+        .regions
+        .iter()
+        .map(|region| format!("{{{}}}", stats(region)))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"pattern":"{}",{},"region_len":{},"high_entropy":{:.1},"high_entropy_runs":[{runs}],"regions":[{regions}]}}"#,
+        pattern.key,
+        stats(&report.file),
+        report.region_len,
+        cmd::HIGH_ENTROPY
+    )
+}
+
+/// Handle the `vis-similarity` subcommand.
+fn handle_vis_similarity(
+    a: &Path,
+    b: &Path,
+    pattern: Option<&'static CurveEntry>,
+    width: Option<u32>,
+    json: bool,
+    heat: Option<&Path>,
+    opts: RenderOptions,
+) -> Result<()> {
+    let pattern = pattern.unwrap_or(CurveId::Hilbert.entry());
+    let width = width.unwrap_or(256);
+    let estimate = Estimate::image(width, width);
+    if opts.dry_run {
+        return Plan::new("vis-similarity", estimate)
+            .curve(pattern, 2, width)
+            .input(a)
+            .input(b)
+            .output(heat)
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let similarity = cmd::vis_similarity(a, b, width, pattern)?;
+    if let Some(path) = heat {
+        let metadata = opts.metadata().map(|m| {
+            m.with("Curve", pattern.key)
+                .with("Size", width)
+                .with("Block", similarity.block_len)
+        });
+        save_image(&similarity.heat, path, metadata.as_ref())?;
+    }
+    if json {
+        println!("{}", similarity_json(pattern, &similarity));
+    } else {
+        let identical = similarity.blocks.iter().filter(|&&s| s == 1.0).count();
+        println!(
+            "Similarity {:.2}% ({identical} of {} blocks of {} bytes identical)",
+            similarity.overall * 100.0,
+            similarity.blocks.len(),
+            similarity.block_len
+        );
+    }
+    Ok(())
+}
+
+/// Render a similarity report as a single-line JSON object.
+fn similarity_json(pattern: &'static CurveEntry, similarity: &cmd::Similarity) -> String {
+    let blocks = similarity
+        .blocks
+        .iter()
+        .map(|s| format!("{s:.4}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"pattern":"{}","len_a":{},"len_b":{},"block_len":{},"similarity":{:.6},"blocks":[{blocks}]}}"#,
+        pattern.key, similarity.len_a, similarity.len_b, similarity.block_len, similarity.overall
+    )
+}
+
+/// Handle the `stream` subcommand.
+///
+/// A reader that stops early (e.g. `head`) closes the pipe; that ends the
+/// stream quietly rather than as an error.
+fn handle_stream(pattern: &'static CurveEntry, axes: u32, side: u32, binary: bool) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    ignore_broken_pipe(cmd::stream(pattern, axes, side, binary, &mut out))
+}
+
+/// Handle `stream --table`, writing the points of a cached point table.
+fn handle_stream_table(table: &Path, binary: bool) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    ignore_broken_pipe(cmd::stream_table(table, binary, &mut out))
+}
+
+/// Treat a reader closing the pipe early as success.
+fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(err)
+            if err
+                .downcast_ref::<io::Error>()
+                .is_some_and(|err| err.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Handle the `table` subcommand.
+fn handle_table(pattern: &'static CurveEntry, axes: u32, side: u32, output: &Path) -> Result<()> {
+    let curve = registry::construct_entry(pattern, axes, side)?;
+    table::save(&*curve, output)?;
+    let bytes = fs::metadata(output)?.len();
+    println!(
+        "{pattern} ({axes}D, side {side}): {} points in {bytes} bytes, written to {}",
+        curve.length(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// One line of the `verify` table: `fail` plus the first offending index
+/// when `tally` recorded anything.
+fn verify_line(check: &str, tally: &Tally, what: &str) {
+    match tally.first {
+        None => println!("  {check:<12} ok"),
+        Some(first) => println!(
+            "  {check:<12} fail — {} {what}, first at index {first}",
+            tally.count
+        ),
+    }
+}
+
+/// Handle the `verify` subcommand, returning whether the curve passed.
+///
+/// Continuity is reported but does not fail the check: jumping curves like
+/// Z-order are valid traversals.
+fn handle_verify(
+    pattern: &'static CurveEntry,
+    axes: u32,
+    side: u32,
+    per_index: bool,
+) -> Result<bool> {
+    let curve = registry::construct_entry(pattern, axes, side)?;
+    let report = verify::full_report(&*curve)?;
+    println!(
+        "{pattern} ({axes}D, side {side}): {} points over {} cells",
+        report.length, report.cells
+    );
+    verify_line("bounds", &report.out_of_bounds, "points outside the grid");
+    verify_line("round trip", &report.round_trip, "indices not recovered");
+    verify_line("duplicates", &report.duplicates, "cells revisited");
+    if report.is_surjective() {
+        println!("  {:<12} ok", "coverage");
+    } else {
+        println!(
+            "  {:<12} fail — {} cells never visited",
+            "coverage", report.unvisited
+        );
+    }
+    match report.jumps.first {
+        None => println!("  {:<12} every step is a unit move", "continuity"),
+        Some(first) => println!(
+            "  {:<12} {} jumps, first at index {first}",
+            "continuity", report.jumps.count
+        ),
+    }
+    match (report.claims_cyclic, report.closes) {
+        (true, true) => println!("  {:<12} ok (closed loop)", "cycle"),
+        (true, false) => println!(
+            "  {:<12} fail — reports cyclic but the last point is not next to the first",
+            "cycle"
+        ),
+        (false, true) => println!("  {:<12} ok (closes, but not reported cyclic)", "cycle"),
+        (false, false) => println!("  {:<12} ok (open)", "cycle"),
+    }
+    if per_index {
+        let stretch = metrics::dilation_map(&*curve);
+        let (worst, max) =
+            stretch
+                .iter()
+                .copied()
+                .enumerate()
+                .fold(
+                    (0, 0.0_f32),
+                    |best, (i, s)| if s > best.1 { (i, s) } else { best },
+                );
+        let mean = stretch.iter().map(|&s| f64::from(s)).sum::<f64>() / stretch.len() as f64;
+        println!(
+            "  {:<12} mean {mean:.3}, max {max:.3} at index {worst} (radius {})",
+            "dilation",
+            metrics::DILATION_RADIUS
+        );
+        println!();
+        ignore_broken_pipe(write_dilation(&stretch))?;
+    }
+    Ok(report.is_valid())
+}
+
+/// Write one `index stretch` line per entry of a dilation map to stdout.
+fn write_dilation(stretch: &[f32]) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    for (index, s) in stretch.iter().enumerate() {
+        writeln!(out, "{index} {s}")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Handle the `permute` subcommand.
+fn handle_permute(
+    input: &Path,
+    output: &Path,
+    pattern: Option<&'static CurveEntry>,
+    inverse: bool,
+    length: Option<usize>,
+) -> Result<()> {
+    let pattern = pattern.unwrap_or(CurveId::Hilbert.entry());
+    let result = cmd::permute(input, pattern, inverse, length)?;
+    fs::write(output, &result.bytes)?;
+    if result.padded > 0 {
+        eprintln!(
+            "Padded {} bytes to fill a {side}×{side} grid; restore with --inverse --length {}.",
+            result.padded,
+            result.bytes.len() - result.padded,
+            side = result.side
+        );
+    }
+    Ok(())
+}
+
+/// Tell the user the requested grid side was rounded up to one the curve accepts.
+fn warn_adjusted(requested: u32, pattern: &'static CurveEntry, side: u32) {
+    eprintln!(
+        "Requested curve dimension {requested} is not valid for pattern '{pattern}'; using {side} instead."
+    );
+}
+
+/// Parameters supplied by the CLI for the `map` subcommand.
+#[derive(Clone, Copy)]
+struct MapInput<'a> {
+    /// Requested output size in pixels (defaults to 512 when `None`).
+    size: Option<u32>,
+    /// Requested curve dimension (defaults to 16 when `None`).
+    curve_dimension: Option<u32>,
+    /// Curve pattern.
+    pattern: &'static CurveEntry,
+    /// Optional output path; a viewer opens when `None`.
+    output: Option<&'a Path>,
+    /// Optional path for the per-cell curve index map.
+    index_map: Option<&'a Path>,
+    /// Optional offset range limiting the drawn segment.
+    chunk: Option<ChunkOffsets>,
+    /// Stroke styling options.
+    stroke: map::StrokeOptions,
+    /// Title and caption overlay.
+    labels: &'a LabelArgs,
+}
+
+/// Handle the `map` subcommand.
+fn handle_map(input: MapInput<'_>, opts: RenderOptions) -> Result<()> {
+    let MapInput {
+        size,
+        curve_dimension,
+        pattern,
+        output,
+        index_map,
+        chunk,
+        stroke,
+        labels,
+    } = input;
+
+    let size = size.unwrap_or(512);
+    let estimate = Estimate::image(size, size);
+    // Default keeps behaviour similar to the previous 16×16 grid (256 points).
+    let requested_dimension = curve_dimension.unwrap_or(16);
+    if opts.dry_run {
+        let (side, adjusted) = cmd::resolve_curve_dimension(pattern, requested_dimension)?;
+        if adjusted {
+            warn_adjusted(requested_dimension, pattern, side);
+        }
+        if let Some(chunk) = chunk {
+            cmd::check_chunk(&chunk.into_range(), u64::from(side).pow(2), pattern)?;
+        }
+        return Plan::new("map", estimate)
+            .curve(pattern, 2, side)
+            .output(output)
+            .output(index_map)
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let mut render = cmd::map(
+        size,
+        requested_dimension,
+        pattern,
         chunk.map(ChunkOffsets::into_range),
         stroke,
     )?;
     if render.adjusted {
-        eprintln!(
-            "Requested curve dimension {} is not valid for pattern '{}'; using {} instead.",
-            requested_dimension, pattern, render.side
-        );
+        warn_adjusted(requested_dimension, pattern, render.side);
     }
-    deliver_image(render.image, output, &format!("map: {pattern}"))
+    if let Some(path) = index_map {
+        cmd::write_index_map(pattern, render.side, path)?;
+    }
+    draw_labels(&mut render.image, labels)?;
+    let metadata = opts.metadata().map(|m| {
+        let m = m
+            .with("Curve", pattern.key)
+            .with("Size", size)
+            .with("Grid", render.side);
+        match chunk {
+            Some(chunk) => m.with("Chunk", format!("{}:{}", chunk.start, chunk.end)),
+            None => m,
+        }
+    });
+    deliver_image(
+        render.image,
+        output,
+        &format!("map: {pattern}"),
+        metadata.as_ref(),
+        opts.language(),
+    )
 }
 
 /// Parameters supplied by the CLI for the `snake` subcommand.
@@ -492,8 +1961,8 @@ struct SnakeInput<'a> {
     size: Option<u32>,
     /// Requested curve dimension (defaults to 16 when `None`).
     curve_dimension: Option<u32>,
-    /// Curve pattern name.
-    pattern: &'a str,
+    /// Curve pattern.
+    pattern: &'static CurveEntry,
     /// Offset range for the animated segment.
     chunk: ChunkOffsets,
     /// Destination GIF path.
@@ -504,6 +1973,12 @@ struct SnakeInput<'a> {
     stroke: map::StrokeOptions,
     /// Optional colour for the static full-curve layer.
     full_curve: Option<Rgba<u8>>,
+    /// Steps of fading trail behind the snake.
+    trail: u32,
+    /// Frames per curve step.
+    substeps: u32,
+    /// Global flags (confirmation, dry run).
+    opts: RenderOptions,
 }
 
 /// Handle the `snake` subcommand.
@@ -517,54 +1992,472 @@ fn handle_snake(input: SnakeInput<'_>) -> Result<()> {
         fps,
         stroke,
         full_curve,
+        trail,
+        substeps,
+        opts,
     } = input;
 
     let size = size.unwrap_or(512);
     let requested_dimension = curve_dimension.unwrap_or(16);
+    // The snake takes `substeps` frames per curve point.
+    let (side, adjusted) = cmd::resolve_curve_dimension(pattern, requested_dimension)?;
+    let estimate = Estimate::animation(size, u64::from(side).pow(2) * u64::from(substeps));
+    if opts.dry_run {
+        if adjusted {
+            warn_adjusted(requested_dimension, pattern, side);
+        }
+        cmd::check_gif_size(size)?;
+        cmd::check_snake_chunk(&chunk.into_range(), u64::from(side).pow(2), pattern)?;
+        return Plan::new("snake", estimate)
+            .curve(pattern, 2, side)
+            .output(Some(output))
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
     let render = cmd::snake(cmd::SnakeOptions {
         size,
         curve_dimension: requested_dimension,
-        pattern_name: pattern,
+        pattern_entry: pattern,
         chunk: chunk.into_range(),
         fps,
         stroke,
         output,
         full_curve,
+        trail,
+        substeps,
     })?;
 
     if render.adjusted {
+        warn_adjusted(requested_dimension, pattern, render.side);
+    }
+    Ok(())
+}
+
+/// Parameters supplied by the CLI for the `morph` subcommand.
+#[derive(Clone, Copy)]
+struct MorphInput<'a> {
+    /// Requested output size in pixels (defaults to 512 when `None`).
+    size: Option<u32>,
+    /// Requested curve dimension (defaults to 16 when `None`).
+    curve_dimension: Option<u32>,
+    /// Curve the animation starts from.
+    from: &'static CurveEntry,
+    /// Curve the animation ends on.
+    to: &'static CurveEntry,
+    /// Number of frames.
+    frames: u32,
+    /// Frames per second.
+    fps: u16,
+    /// Stroke styling options.
+    stroke: map::StrokeOptions,
+    /// Destination GIF path.
+    output: &'a Path,
+    /// Global flags (confirmation, dry run).
+    opts: RenderOptions,
+}
+
+/// Parameters supplied by the CLI for the `record` subcommand.
+#[derive(Clone, Copy)]
+struct RecordInput<'a> {
+    /// Pane to record.
+    pane: RecordPane,
+    /// Length of the recording in seconds.
+    seconds: u32,
+    /// Frames per second.
+    fps: u16,
+    /// Curve to show; the GUI default when `None`.
+    curve: Option<&'static CurveEntry>,
+    /// Grid size of the recorded pane; the GUI default when `None`.
+    size: Option<u32>,
+    /// 3D rotation speed (0-100); the GUI default when `None`.
+    spin: Option<f32>,
+    /// Snake overlay visibility; the GUI default when `None`.
+    snake: Option<bool>,
+    /// Destination video or GIF path.
+    output: &'a Path,
+    /// Global flags (confirmation, dry run).
+    opts: RenderOptions,
+}
+
+/// Handle the `record` subcommand.
+fn handle_record(input: RecordInput<'_>) -> Result<()> {
+    let RecordInput {
+        pane,
+        seconds,
+        fps,
+        curve,
+        size,
+        spin,
+        snake,
+        output,
+        opts,
+    } = input;
+
+    let (pane, dimension) = match pane {
+        RecordPane::TwoD => (Pane::TwoD, 2),
+        RecordPane::ThreeD => (Pane::ThreeD, 3),
+    };
+    let frames = seconds.saturating_mul(u32::from(fps));
+    let [width, height] = record::frame_size();
+    let estimate = Estimate {
+        frames: u64::from(frames),
+        ..Estimate::image(width, height)
+    };
+    let id = curve.unwrap_or(CurveId::Hilbert.entry());
+    if opts.dry_run {
+        let plan = Plan::new("record", estimate).output(Some(output));
+        return match size {
+            Some(side) => plan.curve(id, dimension, side).run(opts.language()),
+            None => plan.run(opts.language()),
+        };
+    }
+    if let Some(side) = size {
+        registry::validate_entry(id, dimension, side)?;
+    }
+    confirm(estimate, opts.yes)?;
+    record::record(RecordOptions {
+        state: StateSpec {
+            curve: curve.map(|entry| entry.key.to_string()),
+            pane: Some(pane),
+            size: size.filter(|_| pane == Pane::TwoD),
+            size3d: size.filter(|_| pane == Pane::ThreeD),
+            snake,
+            spin,
+            ..StateSpec::default()
+        },
+        frames,
+        fps,
+        output,
+    })
+}
+
+/// Parameters supplied by the CLI for the `sweep` subcommand.
+#[derive(Clone, Copy)]
+struct SweepInput<'a> {
+    /// Size of each map in pixels.
+    size: u32,
+    /// Requested grid sides, in order.
+    sides: &'a [u32],
+    /// Curve pattern.
+    pattern: &'static CurveEntry,
+    /// Frames per second when writing a GIF.
+    fps: u16,
+    /// Stroke styling options.
+    stroke: map::StrokeOptions,
+    /// Destination GIF, image or directory path.
+    output: &'a Path,
+    /// Treatment of existing outputs in a directory.
+    batch: BatchArgs,
+    /// Global flags (confirmation, dry run).
+    opts: RenderOptions,
+}
+
+/// Handle the `sweep` subcommand.
+fn handle_sweep(input: SweepInput<'_>) -> Result<()> {
+    let SweepInput {
+        size,
+        sides,
+        pattern,
+        fps,
+        stroke,
+        output,
+        batch,
+        opts,
+    } = input;
+
+    let directory = output.is_dir() || output.to_string_lossy().ends_with(path::is_separator);
+    if directory {
+        return sweep_into_directory(input);
+    }
+    if batch.any() {
+        bail!("--skip-existing and --force apply when sweeping into a directory");
+    }
+    let animate = output
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+    let tiles = sides.len() as u32;
+    let estimate = if animate {
+        Estimate::animation(size, u64::from(tiles))
+    } else {
+        Estimate::image(size.saturating_mul(tiles), size)
+    };
+    if opts.dry_run {
+        let mut plan = Plan::new("sweep", estimate).output(Some(output));
+        for &requested in sides {
+            let (side, adjusted) = cmd::resolve_curve_dimension(pattern, requested)?;
+            if adjusted {
+                warn_adjusted(requested, pattern, side);
+            }
+            plan = plan.curve(pattern, 2, side);
+        }
+        if animate {
+            cmd::check_gif_size(size)?;
+        }
+        return plan.run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let render = cmd::sweep(size, sides, pattern, stroke)?;
+    for (&requested, &side) in sides.iter().zip(&render.sides) {
+        if requested != side {
+            warn_adjusted(requested, pattern, side);
+        }
+    }
+    if animate {
+        return cmd::write_gif(render.tiles, fps, output);
+    }
+    let image = cmd::montage(&render.tiles, stroke.palette.background);
+    let metadata = opts.metadata().map(|m| {
+        let sides: Vec<String> = render.sides.iter().map(u32::to_string).collect();
+        m.with("Curve", pattern.key)
+            .with("Size", size)
+            .with("Grids", sides.join(","))
+    });
+    save_image(&image, output, metadata.as_ref())
+}
+
+/// Write one PNG per grid side of a sweep into the `output` directory,
+/// named after the curve and side, e.g. `hilbert-16.png`.
+fn sweep_into_directory(input: SweepInput<'_>) -> Result<()> {
+    let SweepInput {
+        size,
+        sides,
+        pattern,
+        stroke,
+        output,
+        batch,
+        opts,
+        ..
+    } = input;
+
+    let mut items: Vec<(u32, PathBuf)> = Vec::with_capacity(sides.len());
+    for &requested in sides {
+        let (side, adjusted) = cmd::resolve_curve_dimension(pattern, requested)?;
+        if adjusted {
+            warn_adjusted(requested, pattern, side);
+        }
+        if items.iter().all(|&(done, _)| done != side) {
+            items.push((side, output.join(format!("{}-{side}.png", pattern.key))));
+        }
+    }
+    let (pending, skipped) = batch.select(items)?;
+    let estimate = Estimate::image(size, size.saturating_mul(pending.len() as u32));
+    if opts.dry_run {
+        let mut plan = Plan::new("sweep", estimate);
+        for (side, path) in &pending {
+            plan = plan.curve(pattern, 2, *side).output(Some(path));
+        }
+        return plan.run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    fs::create_dir_all(output)?;
+    for (side, path) in &pending {
+        let render = cmd::sweep(size, &[*side], pattern, stroke)?;
+        let metadata = opts.metadata().map(|m| {
+            m.with("Curve", pattern.key)
+                .with("Size", size)
+                .with("Grids", side)
+        });
+        save_image(&render.tiles[0], path, metadata.as_ref())?;
+    }
+    println!(
+        "{}",
+        batch::summary(opts.language(), pending.len(), skipped)
+    );
+    Ok(())
+}
+
+/// Parameters supplied by the CLI for the `wallpaper` subcommand.
+#[derive(Clone, Copy)]
+struct WallpaperInput<'a> {
+    /// Canvas width and height in pixels.
+    resolution: (u32, u32),
+    /// Curves to draw, in tile order.
+    curves: &'a [&'static CurveEntry],
+    /// Requested side length of every curve grid.
+    curve_dimension: u32,
+    /// Tile arrangement.
+    layout: WallpaperLayout,
+    /// Pixels around and between tiles.
+    padding: u32,
+    /// Stroke styling options.
+    stroke: map::StrokeOptions,
+    /// Destination image path.
+    output: &'a Path,
+    /// Global flags (confirmation, dry run).
+    opts: RenderOptions,
+}
+
+/// Handle the `wallpaper` subcommand.
+fn handle_wallpaper(input: WallpaperInput<'_>) -> Result<()> {
+    let WallpaperInput {
+        resolution: (width, height),
+        curves,
+        curve_dimension,
+        layout,
+        padding,
+        stroke,
+        output,
+        opts,
+    } = input;
+
+    let estimate = Estimate::image(width, height);
+    if opts.dry_run {
+        cmd::wallpaper_tiles(width, height, curves.len() as u32, layout, padding)?;
+        let mut plan = Plan::new("wallpaper", estimate).output(Some(output));
+        for &pattern in curves {
+            let (side, adjusted) = cmd::resolve_curve_dimension(pattern, curve_dimension)?;
+            if adjusted {
+                warn_adjusted(curve_dimension, pattern, side);
+            }
+            plan = plan.curve(pattern, 2, side);
+        }
+        return plan.run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let render = cmd::wallpaper(
+        width,
+        height,
+        curves,
+        curve_dimension,
+        layout,
+        padding,
+        stroke,
+    )?;
+    for (&pattern, &side) in curves.iter().zip(&render.sides) {
+        if side != curve_dimension {
+            warn_adjusted(curve_dimension, pattern, side);
+        }
+    }
+    let metadata = opts.metadata().map(|m| {
+        let keys: Vec<&str> = curves.iter().map(|id| id.key).collect();
+        let sides: Vec<String> = render.sides.iter().map(u32::to_string).collect();
+        m.with("Curves", keys.join(","))
+            .with("Resolution", format!("{width}x{height}"))
+            .with("Layout", layout.key())
+            .with("Grids", sides.join(","))
+    });
+    save_image(&render.image, output, metadata.as_ref())
+}
+
+/// Handle the `morph` subcommand.
+fn handle_morph(input: MorphInput<'_>) -> Result<()> {
+    let MorphInput {
+        size,
+        curve_dimension,
+        from,
+        to,
+        frames,
+        fps,
+        stroke,
+        output,
+        opts,
+    } = input;
+
+    let size = size.unwrap_or(512);
+    let requested_dimension = curve_dimension.unwrap_or(16);
+    let warn = |side: u32| {
         eprintln!(
-            "Requested curve dimension {} is not valid for pattern '{}'; using {} instead.",
-            requested_dimension, pattern, render.side
+            "Requested curve dimension {requested_dimension} is not valid for both '{from}' and '{to}'; using {side} instead."
         );
+    };
+    let estimate = Estimate::animation(size, u64::from(frames));
+    if opts.dry_run {
+        let (side, adjusted) = cmd::resolve_shared_dimension(from, to, requested_dimension)?;
+        if adjusted {
+            warn(side);
+        }
+        cmd::check_gif_size(size)?;
+        return Plan::new("morph", estimate)
+            .curve(from, 2, side)
+            .curve(to, 2, side)
+            .output(Some(output))
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let render = cmd::morph(cmd::MorphOptions {
+        size,
+        curve_dimension: requested_dimension,
+        from,
+        to,
+        frames,
+        fps,
+        stroke,
+        output,
+    })?;
+
+    if render.adjusted {
+        warn(render.side);
     }
     Ok(())
 }
 
 /// Handle the `allrgb` subcommand.
-fn handle_allrgb(pattern: &str, colormap: Option<&str>, output: Option<&Path>) -> Result<()> {
+fn handle_allrgb(
+    pattern: &'static CurveEntry,
+    colormap: Option<&'static CurveEntry>,
+    output: Option<&Path>,
+    labels: &LabelArgs,
+    opts: RenderOptions,
+) -> Result<()> {
     let colormap = colormap.unwrap_or(pattern);
-    let image = cmd::allrgb(pattern, colormap)?;
-    deliver_image(image, output, &format!("allrgb: {pattern}/{colormap}"))
+    let estimate = Estimate::image(4096, 4096);
+    if opts.dry_run {
+        return Plan::new("allrgb", estimate)
+            .curve(pattern, 2, 4096)
+            .curve(colormap, 3, 256)
+            .output(output)
+            .run(opts.language());
+    }
+    confirm(estimate, opts.yes)?;
+    let mut image = cmd::allrgb(pattern, colormap)?;
+    draw_labels(&mut image, labels)?;
+    let metadata = opts.metadata().map(|m| {
+        m.with("Curve", pattern.key)
+            .with("Colormap", colormap.key)
+            .with("Size", image.width())
+    });
+    deliver_image(
+        image,
+        output,
+        &format!("allrgb: {pattern}/{colormap}"),
+        metadata.as_ref(),
+        opts.language(),
+    )
 }
 
 /// Handle the `gui` subcommand.
-fn handle_gui(dev: bool) {
+fn handle_gui(
+    dev: bool,
+    demo: bool,
+    state: Option<StateSpec>,
+    screenshot_dir: Option<PathBuf>,
+    fixed_fps: Option<u32>,
+    lang: Language,
+) {
     report_ok(
         scurve_gui::gui_with_options(scurve_gui::GuiOptions {
             include_experimental_curves: dev,
             show_dev_overlay: dev,
+            demo,
+            state,
+            screenshot_dir,
+            clock: fixed_fps.map_or(
+                scurve_gui::AnimationClock::Wall,
+                scurve_gui::AnimationClock::Fixed,
+            ),
             ..scurve_gui::GuiOptions::default()
         }),
-        "OK!",
+        tr(lang, Msg::Ok),
     );
 }
 
 #[cfg(feature = "screenshot")]
-/// Handle the `screenshot` subcommand when the feature is enabled.
-fn handle_screenshot(pane: ScreenshotPane, output: PathBuf) {
-    use scurve_gui::{ScreenshotConfig, ScreenshotTarget};
-
+/// Check the requested view and build the GUI's screenshot configuration.
+fn screenshot_config(
+    pane: ScreenshotPane,
+    view: ScreenshotView,
+    output: PathBuf,
+) -> Result<ScreenshotConfig> {
     let target = match pane {
         ScreenshotPane::TwoD => ScreenshotTarget::TwoD,
         ScreenshotPane::ThreeD => ScreenshotTarget::ThreeD,
@@ -573,20 +2466,51 @@ fn handle_screenshot(pane: ScreenshotPane, output: PathBuf) {
         ScreenshotPane::Settings3D => ScreenshotTarget::Settings3D,
     };
 
-    let config = ScreenshotConfig {
-        target,
-        output_path: output,
+    if let Some(size) = view.size {
+        let dimension = if matches!(pane, ScreenshotPane::ThreeD | ScreenshotPane::Settings3D) {
+            3
+        } else {
+            2
+        };
+        let curve = view.curve.unwrap_or(CurveId::Hilbert.entry());
+        registry::validate_entry(curve, dimension, size)?;
+    }
+
+    Ok(ScreenshotConfig {
+        curve: view.curve,
+        size: view.size,
+        snake: view.snake,
+        opacity: view.opacity,
+        rotation: view.rotation,
+        ..ScreenshotConfig::new(target, output)
+    })
+}
+
+#[cfg(feature = "screenshot")]
+/// Handle the `screenshot` subcommand when the feature is enabled.
+fn handle_screenshot(pane: ScreenshotPane, view: ScreenshotView, output: PathBuf, lang: Language) {
+    let config = match screenshot_config(pane, view, output) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}: {err}", tr(lang, Msg::Error));
+            process::exit(1);
+        }
     };
 
     report_ok(
         scurve_gui::gui_with_screenshot(Some(config)),
-        "Screenshot saved!",
+        tr(lang, Msg::SavedScreenshot),
     );
 }
 
 #[cfg(not(feature = "screenshot"))]
 /// Handle the `screenshot` subcommand when the feature is disabled.
-fn handle_screenshot(_pane: ScreenshotPane, _output: PathBuf) {
+fn handle_screenshot(
+    _pane: ScreenshotPane,
+    _view: ScreenshotView,
+    _output: PathBuf,
+    _lang: Language,
+) {
     eprintln!("Screenshot feature not enabled. Rebuild with: cargo build --features screenshot",);
     process::exit(1);
 }
@@ -602,18 +2526,141 @@ fn handle_list_curves() {
     }
 }
 
+/// Handle the `bench` subcommand.
+fn handle_bench(pattern: Option<&'static CurveEntry>) {
+    let entries = pattern.map_or_else(|| registry::entries(true), |entry| vec![entry]);
+    for entry in entries {
+        println!("{} ({}) — {}", entry.display, entry.key, entry.complexity);
+        println!(
+            "  {:>10}  {:>12}  {:>12}",
+            "size", "point (ns)", "index (ns)"
+        );
+        for c in metrics::calibrate_entry(entry, 2, &metrics::CALIBRATION_SIZES) {
+            println!(
+                "  {:>10}  {:>12.1}  {:>12.1}",
+                format!("{0}×{0}", c.size),
+                c.point_ns,
+                c.index_ns
+            );
+        }
+    }
+}
+
 fn main() {
-    let cli = Cli::parse();
+    let args: Vec<_> = env::args_os().collect();
+    let lang = i18n::cli_language(&args);
+    let matches = i18n::localize(Cli::command(), lang).get_matches_from(&args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     match cli.command {
         Commands::Vis {
             input,
             output,
             width,
+            mode,
+            colormap,
+            mark_duplicates,
+            diff,
+            report,
+            report_region,
+            pattern,
+            labels,
+            render,
+        } => {
+            let opts = cli.global.png(render);
+            report_rendered(
+                vis_coloring(mode, colormap).and_then(|coloring| {
+                    handle_vis(
+                        VisInput {
+                            input: &input,
+                            output: output.as_deref(),
+                            width,
+                            pattern,
+                            coloring,
+                            mark_duplicates,
+                            diff: diff.as_deref(),
+                            report: report.as_deref().map(|path| (path, report_region)),
+                            labels: &labels,
+                        },
+                        opts,
+                    )
+                }),
+                opts,
+                Msg::Ok,
+            );
+        }
+        Commands::VisSimilarity {
+            pattern,
+            width,
+            json,
+            heat,
+            a,
+            b,
+            render,
+        } => {
+            if let Err(e) = handle_vis_similarity(
+                &a,
+                &b,
+                pattern,
+                width,
+                json,
+                heat.as_deref(),
+                cli.global.png(render),
+            ) {
+                eprintln!("{}: {e}", tr(lang, Msg::Error));
+                process::exit(1);
+            }
+        }
+        Commands::Stream {
+            side,
+            axes,
+            binary,
+            table,
+            pattern,
+        } => {
+            let result = match (table, pattern) {
+                (Some(table), _) => handle_stream_table(&table, binary),
+                (None, Some(pattern)) => handle_stream(pattern, axes, side, binary),
+                (None, None) => unreachable!("clap requires a pattern without --table"),
+            };
+            if let Err(e) = result {
+                eprintln!("{}: {e}", tr(lang, Msg::Error));
+                process::exit(1);
+            }
+        }
+        Commands::Table {
+            side,
+            axes,
+            pattern,
+            output,
+        } => {
+            if let Err(e) = handle_table(pattern, axes, side, &output) {
+                eprintln!("{}: {e}", tr(lang, Msg::Error));
+                process::exit(1);
+            }
+        }
+        Commands::Verify {
+            side,
+            axes,
+            per_index,
             pattern,
+        } => match handle_verify(pattern, axes, side, per_index) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("{}: {e}", tr(lang, Msg::Error));
+                process::exit(1);
+            }
+        },
+        Commands::Permute {
+            pattern,
+            inverse,
+            length,
+            input,
+            output,
         } => report_ok(
-            handle_vis(&input, output.as_deref(), width, pattern.as_deref()),
-            "OK!",
+            handle_permute(&input, &output, pattern, inverse, length),
+            tr(lang, Msg::Ok),
         ),
         Commands::Map {
             pattern,
@@ -623,34 +2670,61 @@ fn main() {
             output,
             foreground,
             background,
+            theme,
             chunk,
             long_edges,
-        } => report_ok(
-            handle_map(
-                size,
-                curve_dimension,
-                &pattern,
-                output.as_deref(),
-                chunk,
-                map::StrokeOptions {
-                    line_width,
-                    long_edges,
-                    palette: MapPalette {
-                        foreground,
-                        background,
+            index_map,
+            labels,
+            render,
+        } => {
+            let opts = cli.global.png(render);
+            report_rendered(
+                handle_map(
+                    MapInput {
+                        size,
+                        curve_dimension,
+                        pattern,
+                        output: output.as_deref(),
+                        index_map: index_map.as_deref(),
+                        chunk,
+                        stroke: map::StrokeOptions {
+                            line_width,
+                            adjacency: long_edges.unwrap_or_default(),
+                            palette: MapPalette {
+                                foreground: themed(
+                                    foreground,
+                                    theme.map(|t| t.curve.to_array()),
+                                    DEFAULT_FOREGROUND,
+                                ),
+                                background: themed(
+                                    background,
+                                    theme.map(|t| t.background.to_array()),
+                                    DEFAULT_BACKGROUND,
+                                ),
+                            },
+                        },
+                        labels: &labels,
                     },
-                },
-            ),
-            "OK!",
-        ),
+                    opts,
+                ),
+                opts,
+                Msg::Ok,
+            );
+        }
         Commands::Allrgb {
             pattern,
             colormap,
             output,
-        } => report_ok(
-            handle_allrgb(&pattern, colormap.as_deref(), output.as_deref()),
-            "OK!",
-        ),
+            labels,
+            render,
+        } => {
+            let opts = cli.global.png(render);
+            report_rendered(
+                handle_allrgb(pattern, colormap, output.as_deref(), &labels, opts),
+                opts,
+                Msg::Ok,
+            );
+        }
         Commands::Snake {
             pattern,
             size,
@@ -659,39 +2733,269 @@ fn main() {
             output,
             foreground,
             background,
+            theme,
             chunk,
             fps,
+            trail,
+            substeps,
             long_edges,
             full,
-        } => report_ok(
-            handle_snake(SnakeInput {
-                size,
-                curve_dimension,
-                pattern: &pattern,
-                chunk,
-                output: &output,
-                fps,
-                stroke: map::StrokeOptions {
-                    line_width,
-                    long_edges,
-                    palette: MapPalette {
-                        foreground,
-                        background,
+            render,
+        } => {
+            let opts = cli.global.render(render);
+            report_rendered(
+                handle_snake(SnakeInput {
+                    size,
+                    curve_dimension,
+                    pattern,
+                    chunk,
+                    output: &output,
+                    fps,
+                    stroke: map::StrokeOptions {
+                        line_width,
+                        adjacency: long_edges.unwrap_or_default(),
+                        palette: MapPalette {
+                            // The snake takes the accent, as over the GUI's curve.
+                            foreground: themed(
+                                foreground,
+                                theme.map(|t| t.accent.to_array()),
+                                DEFAULT_FOREGROUND,
+                            ),
+                            background: themed(
+                                background,
+                                theme.map(|t| t.background.to_array()),
+                                DEFAULT_BACKGROUND,
+                            ),
+                        },
                     },
-                },
-                full_curve: full,
-            }),
-            "Saved snake GIF!",
-        ),
-        Commands::Gui { dev } => handle_gui(dev),
-        Commands::Screenshot { pane, output } => handle_screenshot(pane, output),
+                    full_curve: full.or(theme.map(|t| Rgba(t.curve.to_array()))),
+                    trail,
+                    substeps,
+                    opts,
+                }),
+                opts,
+                Msg::SavedSnake,
+            );
+        }
+        Commands::Morph {
+            size,
+            curve_dimension,
+            line_width,
+            foreground,
+            background,
+            theme,
+            long_edges,
+            frames,
+            fps,
+            from,
+            to,
+            output,
+            render,
+        } => {
+            let opts = cli.global.render(render);
+            report_rendered(
+                handle_morph(MorphInput {
+                    size,
+                    curve_dimension,
+                    from,
+                    to,
+                    frames,
+                    fps,
+                    stroke: map::StrokeOptions {
+                        line_width,
+                        adjacency: long_edges.unwrap_or_default(),
+                        palette: MapPalette {
+                            foreground: themed(
+                                foreground,
+                                theme.map(|t| t.curve.to_array()),
+                                DEFAULT_FOREGROUND,
+                            ),
+                            background: themed(
+                                background,
+                                theme.map(|t| t.background.to_array()),
+                                DEFAULT_BACKGROUND,
+                            ),
+                        },
+                    },
+                    output: &output,
+                    opts,
+                }),
+                opts,
+                Msg::SavedMorph,
+            );
+        }
+        Commands::Sweep {
+            size,
+            sides,
+            line_width,
+            foreground,
+            background,
+            theme,
+            long_edges,
+            fps,
+            pattern,
+            output,
+            batch,
+            render,
+        } => {
+            let opts = cli.global.png(render);
+            report_rendered(
+                handle_sweep(SweepInput {
+                    size,
+                    sides: &sides,
+                    pattern,
+                    fps,
+                    stroke: map::StrokeOptions {
+                        line_width,
+                        adjacency: long_edges.unwrap_or_default(),
+                        palette: MapPalette {
+                            foreground: themed(
+                                foreground,
+                                theme.map(|t| t.curve.to_array()),
+                                DEFAULT_FOREGROUND,
+                            ),
+                            background: themed(
+                                background,
+                                theme.map(|t| t.background.to_array()),
+                                DEFAULT_BACKGROUND,
+                            ),
+                        },
+                    },
+                    output: &output,
+                    batch,
+                    opts,
+                }),
+                opts,
+                Msg::SavedSweep,
+            );
+        }
+        Commands::Wallpaper {
+            resolution,
+            curves,
+            curve_dimension,
+            layout,
+            padding,
+            line_width,
+            foreground,
+            background,
+            theme,
+            output,
+            render,
+        } => {
+            let opts = cli.global.png(render);
+            report_rendered(
+                handle_wallpaper(WallpaperInput {
+                    resolution,
+                    curves: &curves,
+                    curve_dimension,
+                    layout,
+                    padding,
+                    stroke: map::StrokeOptions {
+                        line_width,
+                        adjacency: Adjacency::UNIT,
+                        palette: MapPalette {
+                            foreground: themed(
+                                foreground,
+                                theme.map(|t| t.curve.to_array()),
+                                DEFAULT_FOREGROUND,
+                            ),
+                            background: themed(
+                                background,
+                                theme.map(|t| t.background.to_array()),
+                                DEFAULT_BACKGROUND,
+                            ),
+                        },
+                    },
+                    output: &output,
+                    opts,
+                }),
+                opts,
+                Msg::SavedWallpaper,
+            );
+        }
+        Commands::Gui {
+            dev,
+            demo,
+            state,
+            screenshot_dir,
+            fixed_fps,
+        } => handle_gui(dev, demo, state, screenshot_dir, fixed_fps, lang),
+        Commands::Record {
+            pane,
+            seconds,
+            fps,
+            curve,
+            size,
+            spin,
+            snake,
+            output,
+            render,
+        } => {
+            let opts = cli.global.render(render);
+            report_rendered(
+                handle_record(RecordInput {
+                    pane,
+                    seconds,
+                    fps,
+                    curve,
+                    size,
+                    spin,
+                    snake,
+                    output: &output,
+                    opts,
+                }),
+                opts,
+                Msg::SavedRecording,
+            );
+        }
+        Commands::Screenshot { pane, view, output } => {
+            handle_screenshot(pane, view, output, lang);
+        }
         Commands::ListCurves => handle_list_curves(),
+        Commands::Bench { pattern } => handle_bench(pattern),
+        Commands::Tui { pattern } => {
+            if let Err(e) = tui::run(pattern.unwrap_or(CurveId::Hilbert.entry())) {
+                eprintln!("{}: {e}", tr(lang, Msg::Error));
+                process::exit(1);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ChunkOffsets;
+    use clap::Parser;
+    use spacecurve::{
+        registry::{self, CurveEntry, CurveId},
+        segment::{Adjacency, Metric},
+    };
+
+    #[cfg(feature = "screenshot")]
+    use scurve_gui::ScreenshotTarget;
+
+    #[cfg(feature = "screenshot")]
+    use super::screenshot_config;
+    use super::{
+        ChunkOffsets, Cli, Commands, parse_curve_name, parse_long_edges, parse_opacity,
+        parse_resolution,
+    };
+
+    #[test]
+    fn curve_names_include_registered_curves() {
+        let scan = CurveId::Scan.entry();
+        registry::register(CurveEntry {
+            id: None,
+            key: "cli-scan",
+            display: "CLI scan",
+            experimental: false,
+            ..*scan
+        })
+        .unwrap();
+        assert_eq!(parse_curve_name("hilbert").unwrap().key, "hilbert");
+        assert_eq!(parse_curve_name("cli-scan").unwrap().key, "cli-scan");
+        let err = parse_curve_name("hilbret").unwrap_err();
+        assert!(err.contains("cli-scan"), "{err}");
+    }
 
     #[test]
     fn parses_chunk_offsets() {
@@ -705,4 +3009,102 @@ mod tests {
         assert!("abc".parse::<ChunkOffsets>().is_err());
         assert!("1:".parse::<ChunkOffsets>().is_err());
     }
+
+    #[test]
+    fn parses_long_edge_rules() {
+        assert_eq!(parse_long_edges("any").unwrap(), Adjacency::ANY);
+        assert_eq!(
+            parse_long_edges("chebyshev").unwrap(),
+            Adjacency::new(Metric::Chebyshev, 1.0)
+        );
+        assert_eq!(
+            parse_long_edges("euclidean:2.5").unwrap(),
+            Adjacency::new(Metric::Euclidean, 2.5)
+        );
+        assert!(parse_long_edges("manhattan:-1").is_err());
+        assert!(parse_long_edges("taxicab:2").is_err());
+    }
+
+    #[test]
+    fn parses_screenshot_view() {
+        let cli = Cli::try_parse_from([
+            "scurve",
+            "screenshot",
+            "--pane",
+            "3d",
+            "--curve",
+            "zorder",
+            "--size",
+            "8",
+            "--snake",
+            "off",
+            "--opacity",
+            "0.5",
+            "--rotation",
+            "-30",
+            "out.png",
+        ])
+        .unwrap();
+        let Commands::Screenshot { view, .. } = cli.command else {
+            panic!("expected the screenshot command");
+        };
+        assert_eq!(view.curve.map(|entry| entry.key), Some("zorder"));
+        assert_eq!(view.size, Some(8));
+        assert_eq!(view.snake, Some(false));
+        assert_eq!(view.opacity, Some(0.5));
+        assert_eq!(view.rotation, Some(-30.0));
+    }
+
+    #[cfg(feature = "screenshot")]
+    #[test]
+    fn screenshot_config_checks_the_size() {
+        let parse = |args: &[&str]| {
+            let cli = Cli::try_parse_from(["scurve", "screenshot"].iter().chain(args)).unwrap();
+            let Commands::Screenshot { pane, view, output } = cli.command else {
+                panic!("expected the screenshot command");
+            };
+            screenshot_config(pane, view, output)
+        };
+
+        let config = parse(&[
+            "--pane", "3d", "--curve", "zorder", "--size", "8", "out.png",
+        ])
+        .unwrap();
+        assert!(matches!(config.target, ScreenshotTarget::ThreeD));
+        assert_eq!(config.curve.map(|entry| entry.key), Some("zorder"));
+        assert_eq!(config.size, Some(8));
+
+        // Hilbert only takes powers of two, and the default curve is Hilbert.
+        assert!(parse(&["--size", "6", "out.png"]).is_err());
+        assert!(parse(&["--curve", "scan", "--size", "6", "out.png"]).is_ok());
+        // The Sierpiński curve has no 3D form.
+        assert!(
+            parse(&[
+                "--pane",
+                "3d",
+                "--curve",
+                "sierpinski",
+                "--size",
+                "8",
+                "out.png"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_resolutions() {
+        assert_eq!(parse_resolution("5120x1440").unwrap(), (5120, 1440));
+        assert_eq!(parse_resolution("800×600").unwrap(), (800, 600));
+        assert!(parse_resolution("0x100").is_err());
+        assert!(parse_resolution("1920").is_err());
+        assert!(parse_resolution("wide x tall").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_opacity() {
+        assert!(parse_opacity("1.5").is_err());
+        assert!(parse_opacity("-0.1").is_err());
+        assert!(parse_opacity("abc").is_err());
+    }
 }
@@ -3,10 +3,13 @@
 //! This module includes small drawing primitives and the function that renders
 //! a sampled map for a given space‑filling curve.
 
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
-use image::{Rgba, RgbaImage};
-use spacecurve::SpaceCurve;
+use image::{Pixel, Rgba, RgbaImage};
+use spacecurve::{
+    SpaceCurve,
+    segment::{Adjacency, Segment},
+};
 
 /// Colors used when rendering a map image.
 #[derive(Clone, Copy, Debug)]
@@ -22,33 +25,71 @@ pub struct MapPalette {
 pub struct StrokeOptions {
     /// Stroke width in pixels.
     pub line_width: u32,
-    /// Whether to render non-adjacent edges (Manhattan distance > 1).
-    pub long_edges: bool,
+    /// Which edges to draw; steps that are not adjacent under this rule are
+    /// skipped, and [`Adjacency::ANY`] draws every edge.
+    pub adjacency: Adjacency,
     /// Colors for foreground/background.
     pub palette: MapPalette,
 }
 
 /// Convert a map coordinate to image space.
-fn scale(v: u32, margin: u32, side: u32, innerw: f64) -> f64 {
+fn scale(v: f64, margin: u32, side: u32, innerw: f64) -> f64 {
     if side <= 1 {
         return f64::from(margin);
     }
 
     let sc = innerw / f64::from(side - 1);
-    f64::from(margin) + (f64::from(v) * sc)
+    f64::from(margin) + (v * sc)
 }
 
-/// Put a pixel if the coordinates are inside the image bounds.
-fn put_pixel_safe(img: &mut RgbaImage, x: i64, y: i64, col: image::Rgba<u8>) {
-    let w = i64::from(img.width());
-    let h = i64::from(img.height());
-    if x >= 0 && y >= 0 && x < w && y < h {
-        img.put_pixel(x as u32, y as u32, col);
+/// Placement of a `side×side` curve grid inside a square image.
+struct Projection {
+    /// Pixels between the image edge and the outermost cells.
+    margin: u32,
+    /// Side length of the curve grid.
+    side: u32,
+    /// Distance in pixels between the outermost cell centres.
+    innerw: f64,
+}
+
+impl Projection {
+    /// Fit a grid into a `size`-pixel image, leaving room for `stroke_width`.
+    fn new(size: u32, side: u32, stroke_width: u32) -> Self {
+        let margin = 10_u32.saturating_add(stroke_width / 2);
+        let innerw = f64::from(size.saturating_sub(margin.saturating_mul(2))).max(1.0);
+        Self {
+            margin,
+            side,
+            innerw,
+        }
+    }
+
+    /// Pixel position of the cell at `p`.
+    fn project(&self, p: &[u32]) -> (i64, i64) {
+        self.project_coords(&[f64::from(p[0]), f64::from(p[1])])
+    }
+
+    /// Pixel position of fractional grid coordinates `p`.
+    fn project_coords(&self, p: &[f64]) -> (i64, i64) {
+        (
+            scale(p[0], self.margin, self.side, self.innerw).round() as i64,
+            scale(p[1], self.margin, self.side, self.innerw).round() as i64,
+        )
+    }
+
+    /// Pixel position a fraction `t` of the way from the cell at `a` to the cell at `b`.
+    fn project_between(&self, a: &[u32], b: &[u32], t: f64) -> (i64, i64) {
+        let lerp = |axis: usize| {
+            let from = scale(f64::from(a[axis]), self.margin, self.side, self.innerw);
+            let to = scale(f64::from(b[axis]), self.margin, self.side, self.innerw);
+            (from + (to - from) * t).round() as i64
+        };
+        (lerp(0), lerp(1))
     }
 }
 
-/// Stamp a filled square centered on `(cx, cy)` with a given side length.
-fn stamp_square(img: &mut RgbaImage, cx: i64, cy: i64, size: u32, col: image::Rgba<u8>) {
+/// Visit each pixel of a filled square centered on `(cx, cy)` with a given side length.
+fn square_pixels(cx: i64, cy: i64, size: u32, visit: &mut impl FnMut(i64, i64)) {
     let radius = (i64::from(size) - 1) / 2;
     let extra = if size.is_multiple_of(2) { 1 } else { 0 };
     let x_start = cx - radius;
@@ -58,20 +99,19 @@ fn stamp_square(img: &mut RgbaImage, cx: i64, cy: i64, size: u32, col: image::Rg
 
     for y in y_start..=y_end {
         for x in x_start..=x_end {
-            put_pixel_safe(img, x, y, col);
+            visit(x, y);
         }
     }
 }
 
-/// Draw a 4‑connected Bresenham line into `img` with color `col`.
-fn draw_line(
-    img: &mut RgbaImage,
-    mut x0: i64,
-    mut y0: i64,
-    x1: i64,
-    y1: i64,
-    col: image::Rgba<u8>,
+/// Visit the pixels of a 4‑connected Bresenham line stroked `line_width` wide.
+///
+/// Neighbouring stamps overlap, so a pixel may be visited more than once.
+fn line_pixels(
+    (mut x0, mut y0): (i64, i64),
+    (x1, y1): (i64, i64),
     line_width: u32,
+    mut visit: impl FnMut(i64, i64),
 ) {
     let dx = (x1 - x0).abs();
     let sx = if x0 < x1 { 1 } else { -1 };
@@ -79,7 +119,7 @@ fn draw_line(
     let sy = if y0 < y1 { 1 } else { -1 };
     let mut err = dx + dy;
     loop {
-        stamp_square(img, x0, y0, line_width, col);
+        square_pixels(x0, y0, line_width, &mut visit);
         if x0 == x1 && y0 == y1 {
             break;
         }
@@ -95,6 +135,69 @@ fn draw_line(
     }
 }
 
+/// Rasterizes curve steps as 4‑connected lines stamped with square brushes.
+///
+/// Every map, trail and morph image is drawn through this type, so its pixel
+/// output is what the golden-image fixtures pin down.
+pub struct Renderer {
+    /// Placement of the curve grid in the image.
+    projection: Projection,
+    /// Side of the square brush in pixels.
+    stroke_width: u32,
+    /// Stroke color.
+    color: Rgba<u8>,
+}
+
+impl Renderer {
+    /// Renderer for a `side×side` grid in a `size×size` image styled by `stroke`.
+    pub fn new(size: u32, side: u32, stroke: &StrokeOptions) -> Self {
+        let stroke_width = stroke.line_width.max(1);
+        Self {
+            projection: Projection::new(size, side, stroke_width),
+            stroke_width,
+            color: stroke.palette.foreground,
+        }
+    }
+
+    /// Pixel position of the cell at `p`.
+    pub fn project(&self, p: &[u32]) -> (i64, i64) {
+        self.projection.project(p)
+    }
+
+    /// Pixel position of fractional grid coordinates `p`, as from
+    /// [`SpaceCurve::point_at`].
+    pub fn project_coords(&self, p: &[f64]) -> (i64, i64) {
+        self.projection.project_coords(p)
+    }
+
+    /// Pixel position a fraction `t` of the way from the cell at `a` to the cell at `b`.
+    pub fn project_between(&self, a: &[u32], b: &[u32], t: f64) -> (i64, i64) {
+        self.projection.project_between(a, b, t)
+    }
+
+    /// Visit the pixels of a stroke between two pixel positions.
+    ///
+    /// Neighbouring stamps overlap, so a pixel may be visited more than once.
+    pub fn stroke_pixels(&self, from: (i64, i64), to: (i64, i64), visit: impl FnMut(i64, i64)) {
+        line_pixels(from, to, self.stroke_width, visit);
+    }
+
+    /// Stroke between two pixel positions, clipped to the image.
+    pub fn stroke(&self, img: &mut RgbaImage, from: (i64, i64), to: (i64, i64)) {
+        let (width, height) = (i64::from(img.width()), i64::from(img.height()));
+        self.stroke_pixels(from, to, |x, y| {
+            if x >= 0 && y >= 0 && x < width && y < height {
+                img.put_pixel(x as u32, y as u32, self.color);
+            }
+        });
+    }
+
+    /// Stroke the step between the cells at `a` and `b`.
+    pub fn step(&self, img: &mut RgbaImage, a: &[u32], b: &[u32]) {
+        self.stroke(img, self.project(a), self.project(b));
+    }
+}
+
 /// Render a square `size×size` image showing a sampled map of `pattern`.
 ///
 /// `side` controls the logical grid size of the pattern (e.g. 16 for a 16×16 Hilbert
@@ -104,7 +207,7 @@ fn draw_line(
 pub fn render_map_image(
     size: u32,
     side: u32,
-    chunk: Range<u32>,
+    chunk: Range<u64>,
     stroke: StrokeOptions,
     pattern: &dyn SpaceCurve,
 ) -> RgbaImage {
@@ -127,14 +230,12 @@ fn draw_chunk(
     img: &mut RgbaImage,
     size: u32,
     side: u32,
-    start: u32,
-    len: u32,
+    start: u64,
+    len: u64,
     stroke: StrokeOptions,
     pattern: &dyn SpaceCurve,
 ) {
-    let stroke_width = stroke.line_width.max(1);
-    let margin = 10_u32.saturating_add(stroke_width / 2);
-    let innerw = f64::from(size.saturating_sub(margin.saturating_mul(2))).max(1.0);
+    let renderer = Renderer::new(size, side, &stroke);
 
     let total_points = pattern.length();
     let len = len.min(total_points);
@@ -145,24 +246,30 @@ fn draw_chunk(
         return;
     }
 
-    let mut prev = pattern.point(start % total_points);
-    for step in 1..len {
-        let idx = (start + step) % total_points;
-        let next = pattern.point(idx);
-        if !stroke.long_edges {
-            let dx = (prev[0] as i64 - next[0] as i64).abs();
-            let dy = (prev[1] as i64 - next[1] as i64).abs();
-            if dx + dy > 1 {
-                prev = next;
-                continue;
-            }
+    // A chunk running past the last point continues from the start of the curve.
+    let (head, wrapped) = pattern.cyclic_range(start, len).split();
+    let junction = (!wrapped.is_empty()).then(|| {
+        Segment::with_adjacency(
+            total_points - 1,
+            pattern.point(total_points - 1),
+            pattern.point(0),
+            stroke.adjacency,
+        )
+    });
+    let segments = pattern
+        .segments_in(head)
+        .with_adjacency(stroke.adjacency)
+        .chain(junction)
+        .chain(
+            pattern
+                .segments_in(wrapped)
+                .with_adjacency(stroke.adjacency),
+        );
+    for segment in segments {
+        if !segment.adjacent {
+            continue;
         }
-        let x0 = scale(prev[0], margin, side, innerw).round() as i64;
-        let y0 = scale(prev[1], margin, side, innerw).round() as i64;
-        let x1 = scale(next[0], margin, side, innerw).round() as i64;
-        let y1 = scale(next[1], margin, side, innerw).round() as i64;
-        draw_line(img, x0, y0, x1, y1, stroke.palette.foreground, stroke_width);
-        prev = next;
+        renderer.step(img, &segment.a, &segment.b);
     }
 }
 
@@ -173,8 +280,8 @@ fn draw_chunk(
 pub fn render_chunk_image(
     size: u32,
     side: u32,
-    start: u32,
-    len: u32,
+    start: u64,
+    len: u64,
     stroke: StrokeOptions,
     pattern: &dyn SpaceCurve,
 ) -> RgbaImage {
@@ -190,14 +297,121 @@ pub fn draw_chunk_overlay(
     img: &mut RgbaImage,
     size: u32,
     side: u32,
-    start: u32,
-    len: u32,
+    start: u64,
+    len: u64,
     stroke: StrokeOptions,
     pattern: &dyn SpaceCurve,
 ) {
     draw_chunk(img, size, side, start, len, stroke, pattern);
 }
 
+/// Draw the part of the step from index `index` to the next index between
+/// the fractions `span` of the way along it.
+///
+/// The step after the last index leads back to index 0. Steps that are not
+/// adjacent under `stroke.adjacency` are skipped, as in a full chunk. The
+/// snake animation uses this to draw its head and tail between cells.
+pub fn draw_partial_step(
+    img: &mut RgbaImage,
+    size: u32,
+    side: u32,
+    index: u64,
+    span: Range<f64>,
+    stroke: StrokeOptions,
+    pattern: &dyn SpaceCurve,
+) {
+    let (a, b) = (
+        pattern.point(index),
+        pattern.point(pattern.advance(index, 1)),
+    );
+    if !stroke.adjacency.contains(&a, &b) {
+        return;
+    }
+    let renderer = Renderer::new(size, side, &stroke);
+    renderer.stroke(
+        img,
+        renderer.project_coords(&a.lerp(&b, span.start)),
+        renderer.project_coords(&a.lerp(&b, span.end)),
+    );
+}
+
+/// Draw the `len` steps leading up to `start` as a trail that fades with distance.
+///
+/// The step ending at `start` takes most of the foreground alpha and each
+/// earlier one less. Trail pixels are alpha-composited onto `img`, each once at
+/// the strongest step covering it, so overlapping stamps do not build up.
+pub fn draw_trail(
+    img: &mut RgbaImage,
+    size: u32,
+    side: u32,
+    start: u64,
+    len: u64,
+    stroke: StrokeOptions,
+    pattern: &dyn SpaceCurve,
+) {
+    let renderer = Renderer::new(size, side, &stroke);
+    let len = len.min(pattern.length().saturating_sub(1));
+    let (width, height) = img.dimensions();
+
+    let mut coverage: HashMap<(u32, u32), f64> = HashMap::new();
+    for step in 1..=len {
+        let from = pattern.advance(start, -(step as i64));
+        let (a, b) = (pattern.point(from), pattern.point(pattern.advance(from, 1)));
+        if !stroke.adjacency.contains(&a, &b) {
+            continue;
+        }
+        let strength = (len + 1 - step) as f64 / (len + 1) as f64;
+        renderer.stroke_pixels(renderer.project(&a), renderer.project(&b), |x, y| {
+            if let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y))
+                && x < width
+                && y < height
+            {
+                let cell = coverage.entry((x, y)).or_default();
+                *cell = cell.max(strength);
+            }
+        });
+    }
+
+    for ((x, y), strength) in coverage {
+        let mut color = stroke.palette.foreground;
+        color.0[3] = (f64::from(color.0[3]) * strength).round() as u8;
+        img.get_pixel_mut(x, y).blend(&color);
+    }
+}
+
+/// Render one frame of a morph from curve `from` to curve `to`.
+///
+/// Every curve index travels a fraction `t` of the way from its cell in `from`
+/// to its cell in `to`, and consecutive indices are joined by strokes. Which
+/// steps are drawn follows the curve nearer in time, so `t = 0` and `t = 1`
+/// match [`render_map_image`] of the two curves. Both curves must share `side`.
+pub fn render_morph_image(
+    size: u32,
+    side: u32,
+    t: f64,
+    stroke: StrokeOptions,
+    from: &dyn SpaceCurve,
+    to: &dyn SpaceCurve,
+) -> RgbaImage {
+    let renderer = Renderer::new(size, side, &stroke);
+    let mut img: RgbaImage = image::ImageBuffer::from_pixel(size, size, stroke.palette.background);
+
+    let positions: Vec<(i64, i64)> = from
+        .points()
+        .zip(to.points())
+        .map(|(a, b)| renderer.project_between(&a, &b, t))
+        .collect();
+    let nearer = if t < 0.5 { from } else { to };
+    for segment in nearer.segments().with_adjacency(stroke.adjacency) {
+        if !segment.adjacent {
+            continue;
+        }
+        let index = segment.index as usize;
+        renderer.stroke(&mut img, positions[index], positions[index + 1]);
+    }
+    img
+}
+
 #[cfg(test)]
 mod tests {
     use image::Rgba;
@@ -229,19 +443,19 @@ mod tests {
             "stub"
         }
 
-        fn index(&self, p: &Point) -> u32 {
+        fn index(&self, p: &Point) -> u64 {
             self.points
                 .iter()
                 .position(|candidate| candidate == p)
-                .expect("point not found") as u32
+                .expect("point not found") as u64
         }
 
-        fn point(&self, index: u32) -> Point {
+        fn point(&self, index: u64) -> Point {
             self.points[index as usize].clone()
         }
 
-        fn length(&self) -> u32 {
-            self.points.len() as u32
+        fn length(&self) -> u64 {
+            self.points.len() as u64
         }
 
         fn dimensions(&self) -> u32 {
@@ -255,7 +469,7 @@ mod tests {
 
         let stroke = StrokeOptions {
             line_width: 1,
-            long_edges: true,
+            adjacency: Adjacency::ANY,
             palette: MapPalette {
                 foreground: Rgba([1, 2, 3, 255]),
                 background: Rgba([0, 0, 0, 0]),
@@ -276,7 +490,7 @@ mod tests {
 
         let stroke = StrokeOptions {
             line_width: 1,
-            long_edges: true,
+            adjacency: Adjacency::ANY,
             palette: MapPalette {
                 foreground: Rgba([9, 9, 9, 255]),
                 background: Rgba([0, 0, 0, 0]),
@@ -295,14 +509,14 @@ mod tests {
         let pattern = StubPattern::new(vec![[0, 0], [2, 0]]);
         let stroke_short = StrokeOptions {
             line_width: 1,
-            long_edges: false,
+            adjacency: Adjacency::UNIT,
             palette: MapPalette {
                 foreground: Rgba([50, 60, 70, 255]),
                 background: Rgba([0, 0, 0, 0]),
             },
         };
         let stroke_long = StrokeOptions {
-            long_edges: true,
+            adjacency: Adjacency::ANY,
             ..stroke_short
         };
 
@@ -315,4 +529,76 @@ mod tests {
         let mid_pixel_long = with_long.get_pixel(32, 10);
         assert_eq!(mid_pixel_long, &stroke_short.palette.foreground);
     }
+
+    #[test]
+    fn trail_fades_behind_start() {
+        let pattern = StubPattern::new(vec![[0, 0], [1, 0], [1, 1], [0, 1]]);
+        let stroke = StrokeOptions {
+            line_width: 1,
+            adjacency: Adjacency::ANY,
+            palette: MapPalette {
+                foreground: Rgba([255, 0, 0, 255]),
+                background: Rgba([0, 0, 255, 255]),
+            },
+        };
+        let mut image = RgbaImage::from_pixel(32, 32, stroke.palette.background);
+
+        // Steps 1 → 2 (strength 2/3) and 0 → 1 (1/3) lead up to index 2.
+        draw_trail(&mut image, 32, 2, 2, 2, stroke, &pattern);
+
+        let near = image.get_pixel(22, 16);
+        let far = image.get_pixel(16, 10);
+        assert_eq!(near.0[3], 255);
+        assert!(near.0[0] > far.0[0] && far.0[0] > 0, "{near:?} {far:?}");
+        assert!(near.0[2] < far.0[2]);
+        // The shared corner takes the stronger step, not both stacked.
+        assert_eq!(image.get_pixel(22, 10), near);
+        assert_eq!(image.get_pixel(10, 22), &stroke.palette.background);
+    }
+
+    #[test]
+    fn partial_step_covers_only_its_span() {
+        let pattern = StubPattern::new(vec![[0, 0], [1, 0], [1, 1], [0, 1]]);
+        let stroke = StrokeOptions {
+            line_width: 1,
+            adjacency: Adjacency::UNIT,
+            palette: MapPalette {
+                foreground: Rgba([9, 9, 9, 255]),
+                background: Rgba([0, 0, 0, 0]),
+            },
+        };
+        let mut image = RgbaImage::from_pixel(32, 32, stroke.palette.background);
+
+        // The second half of step 0 → 1 and the first half of 3 → 0.
+        draw_partial_step(&mut image, 32, 2, 0, 0.5..1.0, stroke, &pattern);
+        draw_partial_step(&mut image, 32, 2, 3, 0.0..0.5, stroke, &pattern);
+
+        assert_eq!(image.get_pixel(19, 10), &stroke.palette.foreground);
+        assert_eq!(image.get_pixel(13, 10), &stroke.palette.background);
+        assert_eq!(image.get_pixel(10, 19), &stroke.palette.foreground);
+        assert_eq!(image.get_pixel(10, 13), &stroke.palette.background);
+    }
+
+    #[test]
+    fn morph_ends_match_both_curves() {
+        let from = StubPattern::new(vec![[0, 0], [1, 0], [1, 1], [0, 1]]);
+        let to = StubPattern::new(vec![[0, 0], [0, 1], [1, 1], [1, 0]]);
+        let stroke = StrokeOptions {
+            line_width: 1,
+            adjacency: Adjacency::UNIT,
+            palette: MapPalette {
+                foreground: Rgba([9, 9, 9, 255]),
+                background: Rgba([0, 0, 0, 0]),
+            },
+        };
+
+        for (t, pattern) in [(0.0, &from), (1.0, &to)] {
+            let morph = render_morph_image(32, 2, t, stroke, &from, &to);
+            assert_eq!(morph, render_map_image(32, 2, 0..4, stroke, pattern));
+        }
+
+        // Halfway, index 1 sits between (1, 0) and (0, 1), at the centre.
+        let halfway = render_morph_image(32, 2, 0.5, stroke, &from, &to);
+        assert_eq!(halfway.get_pixel(16, 16), &stroke.palette.foreground);
+    }
 }
@@ -0,0 +1,109 @@
+//! Reproduction details embedded in PNG outputs.
+//!
+//! Every PNG the CLI writes carries text chunks with the command line, the
+//! crate version and the render parameters, so a shared image can be
+//! regenerated exactly. Other image formats are saved without them.
+
+use std::{borrow::Cow, env, fmt::Display, fs::File, io::BufWriter, path::Path};
+
+use anyhow::Result;
+use image::RgbaImage;
+
+/// Key/value pairs written as PNG text chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngMetadata {
+    /// Entries in write order.
+    entries: Vec<(&'static str, String)>,
+}
+
+impl PngMetadata {
+    /// Start with the software version and the command line of this process.
+    pub fn new() -> Self {
+        let command = env::args_os()
+            .map(|arg| shell_quote(&arg.to_string_lossy()).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            entries: vec![
+                ("Software", format!("scurve {}", env!("CARGO_PKG_VERSION"))),
+                ("Command", command),
+            ],
+        }
+    }
+
+    /// Add an entry.
+    pub fn with(mut self, key: &'static str, value: impl Display) -> Self {
+        self.entries.push((key, value.to_string()));
+        self
+    }
+
+    /// Entries in write order.
+    pub fn entries(&self) -> &[(&'static str, String)] {
+        &self.entries
+    }
+}
+
+impl Default for PngMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Save `image` to `path`, embedding `metadata` when the path names a PNG.
+pub fn save_image(image: &RgbaImage, path: &Path, metadata: Option<&PngMetadata>) -> Result<()> {
+    let is_png = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    match metadata {
+        Some(metadata) if is_png => save_png(image, path, metadata),
+        _ => Ok(image.save(path)?),
+    }
+}
+
+/// Encode `image` as an RGBA PNG with `metadata` as text chunks.
+///
+/// ASCII values go into `tEXt` chunks; anything else (e.g. non-ASCII paths on
+/// the command line) uses UTF-8 `iTXt`.
+fn save_png(image: &RgbaImage, path: &Path, metadata: &PngMetadata) -> Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    for (key, value) in metadata.entries() {
+        if value.is_ascii() {
+            encoder.add_text_chunk((*key).to_string(), value.clone())?;
+        } else {
+            encoder.add_itxt_chunk((*key).to_string(), value.clone())?;
+        }
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(image.as_raw())?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Quote `arg` for a POSIX shell when it contains anything beyond safe characters.
+fn shell_quote(arg: &str) -> Cow<'_, str> {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./:=,+@%#".contains(c));
+    if safe {
+        Cow::Borrowed(arg)
+    } else {
+        Cow::Owned(format!("'{}'", arg.replace('\'', r"'\''")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_unsafe_arguments() {
+        assert_eq!(shell_quote("--fg=#8080ff"), "--fg=#8080ff");
+        assert_eq!(shell_quote("my file.png"), "'my file.png'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
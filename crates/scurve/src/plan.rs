@@ -0,0 +1,182 @@
+//! Validation summaries for `--dry-run`.
+//!
+//! A [`Plan`] describes what a rendering command would do. Running it builds
+//! the curves, checks that inputs are readable and outputs writable, and
+//! prints the expected output size, without rendering anything.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::ErrorKind,
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use scurve_gui::i18n::Language;
+use spacecurve::registry::{self, CurveEntry};
+
+use crate::{
+    confirm::{Estimate, format_bytes, format_duration},
+    i18n::{Msg, tr},
+};
+
+/// A curve a render would build.
+#[derive(Debug, Clone, Copy)]
+pub struct CurveSpec {
+    /// Curve to build.
+    pub entry: &'static CurveEntry,
+    /// Number of dimensions.
+    pub dimensions: u32,
+    /// Side length of the grid.
+    pub side: u32,
+}
+
+/// What a rendering command would read, build and write.
+#[derive(Debug, Clone)]
+pub struct Plan<'a> {
+    /// Subcommand name.
+    pub command: &'static str,
+    /// Curves the render builds.
+    pub curves: Vec<CurveSpec>,
+    /// Files the render reads.
+    pub inputs: Vec<&'a Path>,
+    /// Files the render writes; empty when the result opens in a viewer.
+    pub outputs: Vec<&'a Path>,
+    /// Size of the render.
+    pub estimate: Estimate,
+}
+
+impl<'a> Plan<'a> {
+    /// Plan for `command` rendering `estimate`, with nothing else attached yet.
+    pub fn new(command: &'static str, estimate: Estimate) -> Self {
+        Self {
+            command,
+            curves: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            estimate,
+        }
+    }
+
+    /// Add a curve of `dimensions` dimensions on a grid of `side` cells per axis.
+    pub fn curve(mut self, entry: &'static CurveEntry, dimensions: u32, side: u32) -> Self {
+        self.curves.push(CurveSpec {
+            entry,
+            dimensions,
+            side,
+        });
+        self
+    }
+
+    /// Add an input file.
+    pub fn input(mut self, path: &'a Path) -> Self {
+        self.inputs.push(path);
+        self
+    }
+
+    /// Add an output file, if there is one.
+    pub fn output(mut self, path: Option<&'a Path>) -> Self {
+        self.outputs.extend(path);
+        self
+    }
+
+    /// Validate everything and describe the render, one line per item.
+    pub fn validate(&self) -> Result<Vec<String>> {
+        let estimate = self.estimate;
+        let mut lines = vec![format!(
+            "{}: {} frame(s) of {} pixels, about {} in memory and {} to render",
+            self.command,
+            estimate.frames,
+            estimate.pixels,
+            format_bytes(estimate.memory_bytes()),
+            format_duration(estimate.seconds()),
+        )];
+        for spec in &self.curves {
+            let curve = registry::construct_entry(spec.entry, spec.dimensions, spec.side)?;
+            lines.push(format!(
+                "curve: {} in {}D, side {} ({} points)",
+                spec.entry.key,
+                spec.dimensions,
+                spec.side,
+                curve.length()
+            ));
+        }
+        for path in &self.inputs {
+            let len = fs::File::open(path)
+                .and_then(|file| file.metadata())
+                .with_context(|| format!("cannot read {}", path.display()))?
+                .len();
+            lines.push(format!("input: {} ({len} bytes)", path.display()));
+        }
+        for path in &self.outputs {
+            check_writable(path)?;
+            lines.push(format!("output: {}", path.display()));
+        }
+        if self.outputs.is_empty() {
+            lines.push("output: viewer window".to_string());
+        }
+        Ok(lines)
+    }
+
+    /// Validate and print the summary, headed in `lang`.
+    pub fn run(&self, lang: Language) -> Result<()> {
+        println!("{}", tr(lang, Msg::NothingRendered));
+        for line in self.validate()? {
+            println!("  {line}");
+        }
+        Ok(())
+    }
+}
+
+/// Check that `path` can be written, leaving any existing file untouched.
+///
+/// A missing file is created and removed again, which also catches missing
+/// directories and names the file system rejects.
+fn check_writable(path: &Path) -> Result<()> {
+    let context = || format!("cannot write {}", path.display());
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(file) => {
+            drop(file);
+            fs::remove_file(path).with_context(context)
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map(drop)
+            .with_context(context),
+        Err(e) => Err(e).with_context(context),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spacecurve::registry::CurveId;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn validates_without_leaving_files() {
+        let td = tempdir().unwrap();
+        let input = td.path().join("in.bin");
+        fs::write(&input, [1, 2, 3]).unwrap();
+        let output = td.path().join("out.png");
+
+        let plan = Plan::new("vis", Estimate::image(64, 64))
+            .curve(CurveId::Hilbert.entry(), 2, 64)
+            .input(&input)
+            .output(Some(&output));
+        let lines = plan.validate().unwrap();
+        assert!(lines[1].contains("hilbert in 2D, side 64 (4096 points)"));
+        assert!(lines[2].ends_with("(3 bytes)"));
+        assert!(!output.exists());
+
+        let missing = td.path().join("missing").join("out.png");
+        assert!(plan.clone().output(Some(&missing)).validate().is_err());
+        assert!(
+            Plan::new("map", Estimate::image(64, 64))
+                .curve(CurveId::Hilbert.entry(), 2, 3)
+                .validate()
+                .is_err()
+        );
+    }
+}
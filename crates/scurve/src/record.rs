@@ -0,0 +1,164 @@
+//! Scripted GUI recordings for the `record` subcommand.
+//!
+//! The app runs headlessly on a fixed animation clock, so a recording depends
+//! only on its settings: the 3D view spins and the snake advances by the same
+//! amount every frame. GIFs are encoded in-process; any other extension is
+//! piped to `ffmpeg` as raw RGBA frames.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    process::{Child, Command, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+use gif::{Encoder, Frame, Repeat};
+use scurve_gui::{AnimationClock, GuiOptions, share::StateSpec, test_harness::Harness, theme};
+
+use crate::cmd::frame_delay_from_fps;
+
+/// Parameters for [`record`].
+pub struct RecordOptions<'a> {
+    /// Pane, curve and display settings applied before the first frame.
+    pub state: StateSpec,
+    /// Number of frames to capture.
+    pub frames: u32,
+    /// Frames per second of both the animation clock and the output.
+    pub fps: u16,
+    /// Destination video or GIF.
+    pub output: &'a Path,
+}
+
+/// Window size of recordings, in pixels.
+pub fn frame_size() -> [u32; 2] {
+    theme::window::DEFAULT_SIZE.map(|v| v as u32)
+}
+
+/// Render `frames` frames of the GUI and encode them to `output`.
+pub fn record(options: RecordOptions<'_>) -> Result<()> {
+    let RecordOptions {
+        state,
+        frames,
+        fps,
+        output,
+    } = options;
+
+    let mut harness = Harness::new(
+        GuiOptions {
+            state: Some(state),
+            clock: AnimationClock::Fixed(u32::from(fps)),
+            ..GuiOptions::default()
+        },
+        theme::window::DEFAULT_SIZE,
+    );
+    let mut sink = Sink::open(output, frame_size(), fps)?;
+
+    let mut pb = pbr::ProgressBar::new(u64::from(frames));
+    pb.format("╢▌▌░╟");
+    for _ in 0..frames {
+        let mut image = harness.step(Vec::new());
+        sink.write(image.as_raw_mut())?;
+        pb.inc();
+    }
+    pb.finish();
+    sink.finish()
+}
+
+/// Encoder receiving recorded frames.
+enum Sink {
+    /// In-process animated GIF.
+    Gif {
+        /// GIF encoder writing to the output file.
+        encoder: Encoder<BufWriter<File>>,
+        /// Frame width and height in pixels.
+        size: [u16; 2],
+        /// Delay between frames, in hundredths of a second.
+        delay: u16,
+    },
+    /// `ffmpeg` process reading raw RGBA frames on stdin.
+    Ffmpeg(Child),
+}
+
+impl Sink {
+    /// Start encoding `size` frames at `fps` to `path`, picking the encoder
+    /// from the file extension.
+    fn open(path: &Path, size: [u32; 2], fps: u16) -> Result<Self> {
+        let [width, height] = size;
+        let is_gif = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"));
+        if is_gif {
+            let size = [width as u16, height as u16];
+            let file = BufWriter::new(File::create(path)?);
+            let mut encoder = Encoder::new(file, size[0], size[1], &[])?;
+            encoder.set_repeat(Repeat::Infinite)?;
+            return Ok(Self::Gif {
+                encoder,
+                size,
+                delay: frame_delay_from_fps(fps),
+            });
+        }
+
+        let child = Command::new("ffmpeg")
+            .args([
+                "-y",
+                "-loglevel",
+                "error",
+                "-f",
+                "rawvideo",
+                "-pix_fmt",
+                "rgba",
+            ])
+            .args(["-s", &format!("{width}x{height}"), "-r", &fps.to_string()])
+            .args(["-i", "-", "-pix_fmt", "yuv420p"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .spawn();
+        match child {
+            Ok(child) => Ok(Self::Ffmpeg(child)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => bail!(
+                "encoding {} needs ffmpeg on the PATH; record to a .gif instead",
+                path.display()
+            ),
+            Err(err) => Err(err).context("failed to start ffmpeg"),
+        }
+    }
+
+    /// Encode one RGBA frame.
+    fn write(&mut self, rgba: &mut [u8]) -> Result<()> {
+        match self {
+            Self::Gif {
+                encoder,
+                size: [width, height],
+                delay,
+            } => {
+                let mut frame = Frame::from_rgba_speed(*width, *height, rgba, 10);
+                frame.delay = *delay;
+                encoder.write_frame(&frame)?;
+            }
+            Self::Ffmpeg(child) => {
+                let stdin = child.stdin.as_mut().context("ffmpeg stdin is closed")?;
+                stdin
+                    .write_all(rgba)
+                    .context("ffmpeg stopped reading frames")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush the output and wait for the encoder to finish.
+    fn finish(self) -> Result<()> {
+        match self {
+            Self::Gif { encoder, .. } => encoder.into_inner()?.flush()?,
+            Self::Ffmpeg(mut child) => {
+                drop(child.stdin.take());
+                let status = child.wait()?;
+                if !status.success() {
+                    bail!("ffmpeg failed ({status})");
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+//! Terminal curve explorer behind `scurve tui`.
+//!
+//! Draws a 2D curve with braille characters so it can be browsed over SSH
+//! without a display. Arrow keys switch curve and size; the status line
+//! summarises the jumps along the curve.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::Color,
+    symbols::Marker,
+    text::Line,
+    widgets::{
+        Block, Paragraph,
+        canvas::{self, Canvas},
+    },
+};
+use spacecurve::{
+    SpaceCurve, error,
+    registry::{self, CurveEntry},
+    segment::Metric,
+};
+
+/// Side lengths offered by the explorer, smallest first.
+const SIZES: [u32; 7] = [2, 4, 8, 16, 32, 64, 128];
+
+/// How long to wait for input before redrawing.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Key bindings shown in the status line.
+const HELP: &str = "←/→ curve  ↑/↓ size  l long jumps  q quit";
+
+/// Explorer state: the selected curve and size plus display toggles.
+#[derive(Debug, Clone)]
+pub struct Explorer {
+    /// Curves to browse: the built-in ones, then any registered at runtime.
+    curves: Vec<&'static CurveEntry>,
+    /// Index of the selected curve in `curves`.
+    curve_index: usize,
+    /// Index of the selected side length in [`SIZES`].
+    size_index: usize,
+    /// Whether non-adjacent steps are drawn.
+    long_jumps: bool,
+}
+
+/// What a key press asks the event loop to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Keep running and redraw.
+    Continue,
+    /// Leave the explorer.
+    Quit,
+}
+
+/// Jump statistics for the status line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Number of points on the curve.
+    pub length: u64,
+    /// Steps between cells that are not neighbours.
+    pub long_jumps: usize,
+    /// Longest step, as a straight-line distance in cells.
+    pub longest: f64,
+}
+
+impl Explorer {
+    /// Start on `curve` at a 16×16 grid with long jumps hidden.
+    pub fn new(curve: &CurveEntry) -> Self {
+        let curves = registry::entries(true);
+        Self {
+            curve_index: curves.iter().position(|c| c.key == curve.key).unwrap_or(0),
+            curves,
+            size_index: SIZES.iter().position(|&s| s == 16).unwrap_or(0),
+            long_jumps: false,
+        }
+    }
+
+    /// Currently selected curve.
+    pub fn curve(&self) -> &'static CurveEntry {
+        self.curves[self.curve_index]
+    }
+
+    /// Currently selected side length.
+    pub fn size(&self) -> u32 {
+        SIZES[self.size_index]
+    }
+
+    /// Apply a key press.
+    pub fn handle_key(&mut self, key: KeyCode) -> Action {
+        let curves = self.curves.len();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Right => self.curve_index = (self.curve_index + 1) % curves,
+            KeyCode::Left => self.curve_index = (self.curve_index + curves - 1) % curves,
+            KeyCode::Up => self.size_index = (self.size_index + 1).min(SIZES.len() - 1),
+            KeyCode::Down => self.size_index = self.size_index.saturating_sub(1),
+            KeyCode::Char('l') => self.long_jumps = !self.long_jumps,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Build the selected curve in 2D.
+    pub fn build(&self) -> error::Result<Box<dyn SpaceCurve>> {
+        registry::construct_entry(self.curve(), 2, self.size())
+    }
+}
+
+/// Count and measure the long jumps of `curve`.
+pub fn summarize(curve: &dyn SpaceCurve) -> Summary {
+    let mut summary = Summary {
+        length: curve.length(),
+        long_jumps: 0,
+        longest: 0.0,
+    };
+    for segment in curve.segments() {
+        if !segment.adjacent {
+            summary.long_jumps += 1;
+        }
+        summary.longest = summary
+            .longest
+            .max(Metric::Euclidean.distance(&segment.a, &segment.b));
+    }
+    summary
+}
+
+/// Run the explorer until the user quits, restoring the terminal afterwards.
+pub fn run(curve: &CurveEntry) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, Explorer::new(curve));
+    ratatui::restore();
+    result
+}
+
+/// Redraw and dispatch key presses until [`Action::Quit`].
+fn event_loop(terminal: &mut DefaultTerminal, mut explorer: Explorer) -> Result<()> {
+    loop {
+        let curve = explorer.build()?;
+        let summary = summarize(&*curve);
+        terminal.draw(|frame| draw(frame, &explorer, &*curve, summary))?;
+
+        if !event::poll(POLL_INTERVAL)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && explorer.handle_key(key.code) == Action::Quit
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// Render the curve canvas and the status line.
+fn draw(frame: &mut Frame<'_>, explorer: &Explorer, curve: &dyn SpaceCurve, summary: Summary) {
+    let [canvas_area, status_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+
+    let entry = explorer.curve();
+    let side = f64::from(explorer.size());
+    let long_jumps = explorer.long_jumps;
+    let canvas = Canvas::default()
+        .block(Block::bordered().title(format!(" {} ", entry.display)))
+        .marker(Marker::Braille)
+        .x_bounds([-0.5, side - 0.5])
+        .y_bounds([-0.5, side - 0.5])
+        .paint(|ctx| {
+            for segment in curve.segments() {
+                if !segment.adjacent && !long_jumps {
+                    continue;
+                }
+                // Canvas y grows upwards; flip so index 0 sits top-left as in the GUI.
+                ctx.draw(&canvas::Line {
+                    x1: f64::from(segment.a[0]),
+                    y1: side - 1.0 - f64::from(segment.a[1]),
+                    x2: f64::from(segment.b[0]),
+                    y2: side - 1.0 - f64::from(segment.b[1]),
+                    color: if segment.adjacent {
+                        Color::Cyan
+                    } else {
+                        Color::DarkGray
+                    },
+                });
+            }
+        });
+    frame.render_widget(canvas, canvas_area);
+
+    let status = format!(
+        "{}×{} · {} points · {} long jumps (longest {:.1}) · {HELP}",
+        explorer.size(),
+        explorer.size(),
+        summary.length,
+        summary.long_jumps,
+        summary.longest,
+    );
+    frame.render_widget(Paragraph::new(Line::from(status)), status_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use spacecurve::{curve_from_name, registry::CurveId};
+
+    use super::*;
+
+    #[test]
+    fn keys_cycle_curves_and_clamp_sizes() {
+        let mut explorer = Explorer::new(CurveId::ALL[0].entry());
+        explorer.handle_key(KeyCode::Left);
+        assert_eq!(
+            explorer.curve().key,
+            registry::entries(true).last().unwrap().key
+        );
+        explorer.handle_key(KeyCode::Right);
+        assert_eq!(explorer.curve().key, CurveId::ALL[0].key());
+
+        for _ in 0..SIZES.len() {
+            explorer.handle_key(KeyCode::Up);
+        }
+        assert_eq!(explorer.size(), 128);
+        for _ in 0..SIZES.len() {
+            explorer.handle_key(KeyCode::Down);
+        }
+        assert_eq!(explorer.size(), 2);
+
+        assert_eq!(explorer.handle_key(KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn every_curve_builds_at_every_size() {
+        let mut explorer = Explorer::new(CurveId::ALL[0].entry());
+        for _ in registry::entries(true) {
+            for (size_index, size) in SIZES.into_iter().enumerate() {
+                explorer.size_index = size_index;
+                assert!(explorer.build().is_ok(), "{} at {size}", explorer.curve());
+            }
+            explorer.handle_key(KeyCode::Right);
+        }
+    }
+
+    #[test]
+    fn summary_counts_long_jumps() {
+        let zorder = curve_from_name("zorder", 2, 4).unwrap();
+        let summary = summarize(&*zorder);
+        assert_eq!(summary.length, 16);
+        assert_eq!(summary.long_jumps, 7);
+        assert!((summary.longest - 10f64.sqrt()).abs() < 1e-9);
+    }
+}
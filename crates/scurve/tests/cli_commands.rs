@@ -1,8 +1,16 @@
-//! Integration tests for CLI commands: vis, map, allrgb, and error handling.
+//! Integration tests for CLI commands: vis, map, allrgb, permute, and error handling.
 
 #![allow(missing_docs, clippy::tests_outside_test_module, deprecated)]
 
-use std::{fs, fs::File, io::Write, path::PathBuf, process::Command};
+use std::{
+    fs,
+    fs::File,
+    io::{BufReader, Write},
+    ops::Range,
+    path::PathBuf,
+    process::Command,
+    str,
+};
 
 use assert_cmd::{
     assert::{Assert, OutputAssertExt},
@@ -183,6 +191,339 @@ fn vis_produces_valid_png() {
     assert_eq!(img.height(), 16);
 }
 
+#[test]
+#[allow(deprecated)]
+fn vis_value_mode_colors_bytes_through_colormap() {
+    let td = tempdir().expect("tmp");
+    let input = td.path().join("data.bin");
+    write_bytes(&input, &[0x00, 0x40, 0x80, 0xff]);
+    let output = td.path().join("out.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "-p", "scan", "-w", "2", "--mode", "value"])
+        .arg(&input)
+        .arg(&output);
+    cmd.assert().success();
+    let img = read_image(&output).to_rgba8();
+    // Scan visits (0,0), (1,0), (1,1), (0,1); viridis runs dark purple to yellow.
+    assert_eq!(img.get_pixel(0, 0).0, [0x44, 0x01, 0x54, 0xff]);
+    assert_eq!(img.get_pixel(0, 1).0, [0xfd, 0xe7, 0x25, 0xff]);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "-p", "scan", "-w", "2", "--mode", "value"])
+        .args(["--colormap", "gray"])
+        .arg(&input)
+        .arg(&output);
+    cmd.assert().success();
+    let img = read_image(&output).to_rgba8();
+    assert_eq!(img.get_pixel(1, 0).0, [0x40, 0x40, 0x40, 0xff]);
+    assert_eq!(img.get_pixel(1, 1).0, [0x80, 0x80, 0x80, 0xff]);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "--colormap", "magma"])
+        .arg(&input)
+        .arg(&output);
+    let assert = cmd.assert().failure();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr);
+    assert!(stderr.contains("--mode value"), "{stderr}");
+}
+
+#[test]
+fn vis_diff_paints_changed_bytes() {
+    let td = tempdir().expect("tmp");
+    let before = td.path().join("before.bin");
+    let after = td.path().join("after.bin");
+    let data: Vec<u8> = (0..16).collect();
+    write_bytes(&before, &data);
+    let mut changed = data;
+    changed[5] = 0xff;
+    write_bytes(&after, &changed);
+    let plain = td.path().join("plain.png");
+    let diffed = td.path().join("diff.png");
+
+    run_vis(&before, &plain, 4, "scan").success();
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "-p", "scan", "-w", "4", "--diff"])
+        .arg(&after)
+        .arg(&before)
+        .arg(&diffed);
+    cmd.assert().success();
+
+    let plain = read_image(&plain).to_rgba8();
+    let diffed = read_image(&diffed).to_rgba8();
+    // The scan snakes, so byte 5 lands at (2, 1); it takes the neon accent.
+    assert_eq!(diffed.get_pixel(2, 1).0, [0xff, 0x4d, 0xf6, 0xff]);
+    let unchanged = plain
+        .enumerate_pixels()
+        .filter(|&(x, y, p)| (x, y) != (2, 1) && diffed.get_pixel(x, y) == p)
+        .count();
+    assert_eq!(unchanged, 15);
+}
+
+#[test]
+#[allow(deprecated)]
+fn vis_tints_repeated_blocks() {
+    let td = tempdir().expect("tmp");
+    let input = td.path().join("data.bin");
+    // Four 4-byte blocks: the first and third are identical.
+    let data: Vec<u8> = [[1, 2, 3, 4], [5, 6, 7, 8], [1, 2, 3, 4], [9, 9, 9, 9]].concat();
+    write_bytes(&input, &data);
+    let plain = td.path().join("plain.png");
+    let marked = td.path().join("marked.png");
+
+    run_vis(&input, &plain, 4, "scan").success();
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "-p", "scan", "-w", "4", "--mark-duplicates", "4"])
+        .arg(&input)
+        .arg(&marked);
+    cmd.assert().success();
+
+    let plain = read_image(&plain).to_rgba8();
+    let marked = read_image(&marked).to_rgba8();
+    // Scan lays one block out per row.
+    for x in 0..4 {
+        assert_ne!(plain.get_pixel(x, 0), marked.get_pixel(x, 0));
+        assert_eq!(plain.get_pixel(x, 1), marked.get_pixel(x, 1));
+        assert_ne!(plain.get_pixel(x, 2), marked.get_pixel(x, 2));
+        assert_eq!(plain.get_pixel(x, 3), marked.get_pixel(x, 3));
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn vis_report_describes_regions() {
+    let td = tempdir().expect("tmp");
+    let input = td.path().join("data.bin");
+    let output = td.path().join("out.png");
+    let report = td.path().join("report.json");
+    // Zeros, then two regions holding every byte value equally often, then text.
+    let mut data = vec![0u8; 4096];
+    data.extend((0..8192).map(|i| (i * 7) as u8));
+    data.extend(b"A".repeat(100));
+    write_bytes(&input, &data);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "-w", "16", "--report"])
+        .arg(&report)
+        .arg(&input)
+        .arg(&output);
+    cmd.assert().success();
+    assert!(output.exists());
+
+    let json = fs::read_to_string(&report).expect("report written");
+    assert!(json.starts_with(r#"{"pattern":"hilbert","offset":0,"len":12388,"#));
+    assert!(json.contains(r#""region_len":4096"#));
+    assert!(json.contains(r#""high_entropy_runs":[[4096,12288]]"#));
+    assert!(
+        json.contains(r#"{"offset":0,"len":4096,"entropy":0.000000,"classes":{"zero":1.000000,"#)
+    );
+    assert!(json.contains(r#"{"offset":4096,"len":4096,"entropy":8.000000,"#));
+    assert!(json.contains(r#""printable":1.000000,"other":0.000000}}]}"#));
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "--report-region", "16"])
+        .arg(&input)
+        .arg(&output);
+    cmd.assert().failure();
+}
+
+#[test]
+#[allow(deprecated)]
+fn stream_accepts_transform_presets() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd
+        .args(["stream", "-d", "3", "scan-columns"])
+        .assert()
+        .success();
+    let text = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[..4], ["0 0 0", "1 0 1", "2 0 2", "3 1 2"]);
+}
+
+#[test]
+#[allow(deprecated)]
+fn stream_writes_text_and_binary_records() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd
+        .args(["stream", "-d", "4", "hilbert"])
+        .assert()
+        .success();
+    let text = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 16);
+    assert_eq!(lines[..3], ["0 0 0", "1 0 1", "2 1 1"]);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd
+        .args(["stream", "-d", "2", "--axes", "3", "--binary", "zorder"])
+        .assert()
+        .success();
+    // Each record is a u64 index followed by three u32 coordinates.
+    let records: Vec<(u64, Vec<u32>)> = assert
+        .get_output()
+        .stdout
+        .chunks_exact(20)
+        .map(|r| {
+            let index = u64::from_le_bytes(r[..8].try_into().unwrap());
+            let coords = r[8..]
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+                .collect();
+            (index, coords)
+        })
+        .collect();
+    assert_eq!(records.len(), 8);
+    assert_eq!(records[0], (0, vec![0, 0, 0]));
+    assert_eq!(records[1], (1, vec![1, 0, 0]));
+    assert_eq!(records[7].0, 7);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["stream", "-d", "3", "hilbert"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn table_streams_like_the_curve() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("hilbert.table");
+    let path_arg = path.to_str().expect("utf8 path");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd
+        .args(["table", "-d", "64", "hilbert", path_arg])
+        .assert()
+        .success();
+    let text = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(text.contains("4096 points"), "{text}");
+
+    let stream = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone()
+    };
+    assert_eq!(
+        stream(&["stream", "--table", path_arg]),
+        stream(&["stream", "-d", "64", "hilbert"])
+    );
+
+    fs::write(&path, b"not a table").expect("write");
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["stream", "--table", path_arg]).assert().failure();
+}
+
+#[test]
+fn verify_reports_checks() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd.args(["verify", "-d", "8", "zorder"]).assert().success();
+    let text = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(text.contains("64 points over 64 cells"), "{text}");
+    assert!(text.contains("31 jumps, first at index 2"), "{text}");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["verify", "-d", "3", "hilbert"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn verify_prints_per_index_dilation() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    let assert = cmd
+        .args(["verify", "-d", "4", "--per-index", "hilbert"])
+        .assert()
+        .success();
+    let text = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(text.contains("dilation"), "{text}");
+    let (_, values) = text.split_once("\n\n").expect("blank line before values");
+    let lines: Vec<(u64, f32)> = values
+        .lines()
+        .map(|line| {
+            let (index, stretch) = line.split_once(' ').expect("index and stretch");
+            (index.parse().unwrap(), stretch.parse().unwrap())
+        })
+        .collect();
+    assert_eq!(lines.len(), 16);
+    assert!(lines.iter().zip(0..).all(|(&(index, _), i)| index == i));
+    assert!(lines.iter().all(|&(_, stretch)| stretch >= 1.0), "{text}");
+}
+
+#[test]
+fn permute_roundtrips_through_inverse() {
+    let td = tempdir().expect("tmp");
+    let input = td.path().join("data.bin");
+    let data: Vec<u8> = (0..50u8).collect();
+    write_bytes(&input, &data);
+    let permuted = td.path().join("permuted.bin");
+    let restored = td.path().join("restored.bin");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["permute", "-p", "hilbert"])
+        .arg(&input)
+        .arg(&permuted);
+    cmd.assert().success();
+    let shuffled = fs::read(&permuted).expect("read permuted");
+    assert_eq!(shuffled.len(), 64);
+    assert_ne!(shuffled[..50], data[..]);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["permute", "-p", "hilbert", "--inverse", "--length", "50"])
+        .arg(&permuted)
+        .arg(&restored);
+    cmd.assert().success();
+    assert_eq!(fs::read(&restored).expect("read restored"), data);
+}
+
+#[test]
+fn vis_similarity_scores_changed_blocks() {
+    let td = tempdir().expect("tmp");
+    let a = td.path().join("a.bin");
+    let b = td.path().join("b.bin");
+    let heat = td.path().join("heat.png");
+    let data: Vec<u8> = (0..1024u32).map(|i| (i * 7) as u8).collect();
+    let mut patched = data.clone();
+    // Invert the first quarter: 64 of the 256 four-byte blocks differ entirely.
+    for byte in &mut patched[..256] {
+        *byte = !*byte;
+    }
+    write_bytes(&a, &data);
+    write_bytes(&b, &patched);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args([
+        "vis-similarity",
+        "-p",
+        "hilbert",
+        "-w",
+        "16",
+        "--json",
+        "--heat",
+    ])
+    .arg(&heat)
+    .arg(&a)
+    .arg(&b);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let json = String::from_utf8(output).expect("utf8");
+    assert!(json.contains(r#""block_len":4"#), "{json}");
+    assert!(json.contains(r#""similarity":0.750000"#), "{json}");
+    assert!(json.contains(r#""blocks":[0.0000,"#), "{json}");
+
+    let img = read_image(&heat).to_rgba8();
+    assert_eq!(img.dimensions(), (16, 16));
+    // Hilbert starts at (0,0) in the patched region and ends at (15,0) outside it.
+    assert_eq!(img.get_pixel(0, 0).0, [0xe4, 0x1a, 0x1c, 0xff]);
+    assert_eq!(img.get_pixel(15, 0).0, [0xff, 0xff, 0xff, 0xff]);
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis-similarity", "-w", "16"]).arg(&a).arg(&a);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    assert!(String::from_utf8_lossy(&output).starts_with("Similarity 100.00%"));
+}
+
 #[test]
 fn vis_works_with_scan_pattern() {
     let td = tempdir().expect("tmp");
@@ -367,6 +708,185 @@ fn map_respects_custom_colors() {
     assert!(has_fg, "foreground colour appears in rendered map");
 }
 
+fn png_text(path: &PathBuf) -> Vec<(String, String)> {
+    let file = BufReader::new(File::open(path).expect("open png"));
+    let reader = png::Decoder::new(file).read_info().expect("png header");
+    reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .map(|chunk| (chunk.keyword.clone(), chunk.text.clone()))
+        .collect()
+}
+
+#[test]
+fn map_embeds_reproduction_metadata() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("map_meta.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "-s", "64", "-d", "8", "--chunk", "2:40", "hilbert"])
+        .arg(&output);
+    cmd.assert().success();
+
+    let text = png_text(&output);
+    let get = |key: &str| {
+        text.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or_else(|| panic!("missing {key}: {text:?}"))
+    };
+    assert!(get("Software").starts_with("scurve "));
+    assert!(get("Command").contains("map -s 64 -d 8 --chunk 2:40 hilbert"));
+    assert_eq!(get("Curve"), "hilbert");
+    assert_eq!(get("Size"), "64");
+    assert_eq!(get("Grid"), "8");
+    assert_eq!(get("Chunk"), "2:40");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "-s", "64", "-d", "8", "--no-metadata", "hilbert"])
+        .arg(&output);
+    cmd.assert().success();
+    assert!(png_text(&output).is_empty());
+}
+
+#[test]
+fn map_writes_index_maps() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("map.png");
+    let npy = td.path().join("indices.npy");
+    let png16 = td.path().join("indices.png16");
+
+    for index_map in [&npy, &png16] {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(["map", "-s", "64", "-d", "8", "hilbert"])
+            .arg(&output)
+            .arg("--index-map")
+            .arg(index_map);
+        cmd.assert().success();
+    }
+
+    let bytes = fs::read(&npy).expect("read npy");
+    assert!(bytes.starts_with(b"\x93NUMPY\x01\x00"));
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let header = str::from_utf8(&bytes[10..10 + header_len]).expect("ascii header");
+    assert!(header.contains("'descr': '<u2'") && header.contains("'shape': (8, 8)"));
+    assert_eq!((10 + header_len) % 64, 0);
+    let from_npy: Vec<u16> = bytes[10 + header_len..]
+        .chunks(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+
+    let mut reader = png::Decoder::new(BufReader::new(File::open(&png16).expect("open")))
+        .read_info()
+        .expect("png header");
+    assert_eq!(reader.info().bit_depth, png::BitDepth::Sixteen);
+    let mut buf = vec![0; reader.output_buffer_size().expect("size")];
+    reader.next_frame(&mut buf).expect("decode");
+    let from_png: Vec<u16> = buf
+        .chunks(2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .collect();
+
+    assert_eq!(from_npy, from_png);
+    let mut sorted = from_npy;
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..64).collect::<Vec<u16>>());
+}
+
+#[test]
+fn dry_run_validates_without_rendering() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("dry.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "--dry-run", "-d", "3", "--chunk", "0:10", "hilbert"])
+        .arg(&output);
+    let assert = cmd.assert().success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    assert!(stdout.contains("side 4 (16 points)"), "{stdout}");
+    assert!(!stdout.contains("OK!"), "{stdout}");
+    assert!(stderr.contains("using 4 instead"), "{stderr}");
+    assert!(!output.exists());
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["verify", "--dry-run", "hilbert"]);
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "--dry-run", "-d", "4", "--chunk", "0:20", "hilbert"])
+        .arg(&output);
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["vis", "--dry-run"])
+        .arg(td.path().join("missing.bin"))
+        .arg(&output);
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["snake", "--dry-run", "--chunk", "0:4", "hilbert"])
+        .arg(td.path().join("no-such-dir").join("snake.gif"));
+    cmd.assert().failure();
+}
+
+#[test]
+fn map_theme_sets_colors_unless_overridden() {
+    let td = tempdir().expect("tmp");
+    let themed = td.path().join("map_paper.png");
+    let overridden = td.path().join("map_paper_bg.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "-s", "64", "-d", "8", "--theme", "paper", "hilbert"])
+        .arg(&themed);
+    cmd.assert().success();
+    let img = read_image(&themed).to_rgba8();
+    assert_eq!(img.get_pixel(0, 0).0, rgba_from_hex("#fbf8f1"));
+    assert!(img.pixels().any(|p| p.0 == rgba_from_hex("#1f2a44")));
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "-s", "64", "-d", "8", "--theme", "PAPER"])
+        .args(["--bg", "black", "hilbert"])
+        .arg(&overridden);
+    cmd.assert().success();
+    let img = read_image(&overridden).to_rgba8();
+    assert_eq!(img.get_pixel(0, 0).0, rgba_from_name("black"));
+    assert!(img.pixels().any(|p| p.0 == rgba_from_hex("#1f2a44")));
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["map", "--theme", "sepia", "hilbert"]);
+    cmd.assert().failure();
+}
+
+#[test]
+fn map_labels_draw_in_the_chosen_corner() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("map_labelled.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args([
+        "map", "-s", "256", "-d", "2", "--bg", "white", "--fg", "white",
+    ])
+    .args(["--title", "Hilbert", "--caption", "order 1"])
+    .args(["--label-corner", "top-right", "hilbert"])
+    .arg(&output);
+    cmd.assert().success();
+
+    let img = read_image(&output).to_rgba8();
+    let inked = |xs: Range<u32>, ys: Range<u32>| {
+        ys.flat_map(|y| xs.clone().map(move |x| (x, y)))
+            .filter(|&(x, y)| img.get_pixel(x, y).0 != rgba_from_name("white"))
+            .count()
+    };
+    assert!(
+        inked(128..256, 0..128) > 0,
+        "labels in the top-right quadrant"
+    );
+    assert_eq!(inked(0..128, 0..256), 0, "left half untouched");
+    assert_eq!(inked(128..256, 128..256), 0, "bottom-right untouched");
+}
+
 #[test]
 fn map_accepts_named_colors() {
     let td = tempdir().expect("tmp");
@@ -436,6 +956,34 @@ fn map_respects_line_width() {
     );
 }
 
+#[test]
+fn map_long_edge_metric_limits_jumps() {
+    let td = tempdir().expect("tmp");
+    let fg_expected = rgba_from_hex("#8080ff");
+    let foreground = |long: Option<&str>| {
+        let output = td
+            .path()
+            .join(format!("zorder_{}.png", long.unwrap_or("none")));
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.arg("map").arg("-s").arg("128").arg("-d").arg("8");
+        if let Some(long) = long {
+            cmd.arg(long);
+        }
+        cmd.arg("zorder").arg(&output).assert().success();
+        read_image(&output)
+            .to_rgba8()
+            .pixels()
+            .filter(|p| p.0 == fg_expected)
+            .count()
+    };
+
+    let unit = foreground(None);
+    let diagonal = foreground(Some("--long=chebyshev:1"));
+    let all = foreground(Some("--long"));
+    assert!(unit < diagonal, "chebyshev adds diagonal steps");
+    assert!(diagonal < all, "bare --long adds every jump");
+}
+
 #[test]
 fn snake_produces_gif() {
     let td = tempdir().expect("tmp");
@@ -456,6 +1004,251 @@ fn snake_produces_gif() {
     assert_eq!(img.height(), 32);
 }
 
+#[test]
+fn huge_snake_requires_confirmation() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("huge.gif");
+
+    // 64×64 points means 4096 frames, above the 2000-frame threshold; stdin
+    // is not a terminal, so the render is refused rather than prompted for.
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["snake", "-s", "8", "-d", "64", "--chunk", "0:4", "hilbert"])
+        .arg(&output);
+    let stderr = cmd.assert().failure().get_output().stderr.clone();
+    assert!(String::from_utf8_lossy(&stderr).contains("--yes"));
+    assert!(!output.exists());
+}
+
+#[test]
+fn snake_trail_blends_fading_steps() {
+    let td = tempdir().expect("tmp");
+    let plain = td.path().join("plain.gif");
+    let trailed = td.path().join("trail.gif");
+
+    for (output, extra) in [(&plain, None), (&trailed, Some("12"))] {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(["snake", "-s", "64", "-d", "8", "--chunk", "0:4"]);
+        if let Some(trail) = extra {
+            cmd.args(["--trail", trail]);
+        }
+        cmd.arg("hilbert").arg(output);
+        cmd.assert().success();
+    }
+
+    // Only foreground and background appear without a trail; the trail adds
+    // blended shades between them.
+    let colors = |path: &PathBuf| {
+        let mut seen: Vec<[u8; 4]> = read_image(path).to_rgba8().pixels().map(|p| p.0).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        seen.len()
+    };
+    assert_eq!(colors(&plain), 2);
+    assert!(colors(&trailed) > 2);
+}
+
+#[test]
+fn snake_substeps_glide_between_cells() {
+    let td = tempdir().expect("tmp");
+    let read_frames = |path: &PathBuf| {
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::RGBA);
+        let mut reader = decoder
+            .read_info(File::open(path).expect("open gif"))
+            .expect("read gif");
+        let mut frames = Vec::new();
+        while let Some(frame) = reader.read_next_frame().expect("frame") {
+            frames.push(frame.buffer.to_vec());
+        }
+        frames
+    };
+
+    let mut outputs = Vec::new();
+    for substeps in ["1", "4"] {
+        let output = td.path().join(format!("snake-{substeps}.gif"));
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(["snake", "-s", "64", "-d", "4", "--chunk", "0:4"])
+            .args(["--substeps", substeps, "hilbert"])
+            .arg(&output);
+        cmd.assert().success();
+        outputs.push(read_frames(&output));
+    }
+    let (whole, glided) = (&outputs[0], &outputs[1]);
+
+    // Every fourth frame lands on a cell and matches the plain snake; the
+    // frames between differ from both cells they lie between.
+    assert_eq!(whole.len(), 16);
+    assert_eq!(glided.len(), 64);
+    for (step, frame) in whole.iter().enumerate() {
+        assert_eq!(&glided[step * 4], frame, "step {step}");
+    }
+    assert_ne!(glided[2], glided[0]);
+    assert_ne!(glided[2], glided[4]);
+}
+
+#[test]
+fn morph_animates_between_two_curves() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("morph.gif");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["morph", "-s", "64", "-d", "3", "--frames", "6"])
+        .args(["hilbert", "zorder"])
+        .arg(&output);
+    cmd.assert()
+        .success()
+        .stderr("Requested curve dimension 3 is not valid for both 'hilbert' and 'zorder'; using 4 instead.\n");
+
+    let mut decoder = gif::DecodeOptions::new();
+    decoder.set_color_output(gif::ColorOutput::RGBA);
+    let mut reader = decoder
+        .read_info(File::open(&output).expect("open gif"))
+        .expect("read gif");
+    let mut frames = Vec::new();
+    while let Some(frame) = reader.read_next_frame().expect("frame") {
+        frames.push(frame.buffer.to_vec());
+    }
+    assert_eq!(frames.len(), 6);
+    assert_ne!(
+        frames[0], frames[5],
+        "first and last frames show different curves"
+    );
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["morph", "--frames", "1", "hilbert", "zorder"])
+        .arg(td.path().join("short.gif"));
+    cmd.assert().failure();
+}
+
+#[test]
+fn sweep_writes_montage_or_animation() {
+    let td = tempdir().expect("tmp");
+    let montage = td.path().join("sweep.png");
+    let animation = td.path().join("sweep.gif");
+
+    for output in [&montage, &animation] {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(["sweep", "-s", "64", "--sizes", "2,4,8", "hilbert"])
+            .arg(output);
+        cmd.assert().success();
+    }
+
+    // Three 64px tiles with two 4px gaps between them.
+    let img = read_image(&montage);
+    assert_eq!((img.width(), img.height()), (64 * 3 + 4 * 2, 64));
+
+    let decoder = gif::DecodeOptions::new();
+    let mut reader = decoder
+        .read_info(File::open(&animation).expect("open gif"))
+        .expect("read gif");
+    let mut frames = 0;
+    while reader.read_next_frame().expect("frame").is_some() {
+        frames += 1;
+    }
+    assert_eq!(frames, 3);
+}
+
+#[test]
+fn sweep_into_directory_resumes_with_skip_existing() {
+    let td = tempdir().expect("tmp");
+    let dir = td.path().join("frames");
+    fs::create_dir(&dir).expect("create dir");
+    let sweep = |extra: &[&str]| {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        cmd.args(["sweep", "-s", "16", "--sizes", "2,4,8", "hilbert"])
+            .arg(&dir)
+            .args(extra);
+        cmd.assert()
+    };
+    let stdout = |assert: Assert| String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+
+    // An interrupted run that only got as far as the first size.
+    fs::write(dir.join("hilbert-2.png"), b"partial").expect("write");
+    sweep(&[]).failure();
+
+    let out = stdout(sweep(&["--skip-existing"]).success());
+    assert!(out.contains("Rendered: 2, skipped (already existed): 1"));
+    assert_eq!(
+        fs::read(dir.join("hilbert-2.png")).expect("read"),
+        b"partial"
+    );
+    assert_eq!(read_image(&dir.join("hilbert-8.png")).width(), 16);
+
+    let out = stdout(sweep(&["--force"]).success());
+    assert!(out.contains("Rendered: 3, skipped (already existed): 0"));
+    assert_eq!(read_image(&dir.join("hilbert-2.png")).width(), 16);
+
+    sweep(&["--force", "--skip-existing"]).failure();
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["sweep", "--force", "hilbert"])
+        .arg(td.path().join("sweep.png"));
+    cmd.assert().failure();
+}
+
+#[test]
+fn wallpaper_lays_tiles_out_on_the_canvas() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("wallpaper.png");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args([
+        "wallpaper",
+        "--resolution",
+        "400x120",
+        "--curves",
+        "hilbert,gray",
+        "-d",
+        "8",
+        "--padding",
+        "10",
+        "--bg",
+        "000",
+        "--fg",
+        "fff",
+    ])
+    .arg(&output);
+    cmd.assert().success();
+
+    // Two 100px tiles, each centred in a 185px cell of a 400px row.
+    let img = read_image(&output).to_rgba8();
+    assert_eq!(img.dimensions(), (400, 120));
+    let lit = |x0: u32, x1: u32| {
+        (x0..x1).any(|x| (0..120).any(|y| img.get_pixel(x, y).0[..3] != [0, 0, 0]))
+    };
+    assert!(lit(52, 152) && lit(247, 347));
+    assert!(!lit(0, 52) && !lit(152, 247) && !lit(347, 400));
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["wallpaper", "--resolution", "100x100", "--padding", "60"])
+        .arg(td.path().join("cramped.png"));
+    cmd.assert().failure();
+}
+
+#[test]
+fn record_spins_the_3d_view() {
+    let td = tempdir().expect("tmp");
+    let output = td.path().join("record.gif");
+
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["record", "--pane", "3d", "--seconds", "1", "--fps", "3"])
+        .args(["--curve", "hilbert", "--size", "2", "--snake", "off"])
+        .arg(&output);
+    cmd.assert().success();
+
+    let mut decoder = gif::DecodeOptions::new();
+    decoder.set_color_output(gif::ColorOutput::RGBA);
+    let mut reader = decoder
+        .read_info(File::open(&output).expect("open gif"))
+        .expect("read gif");
+    assert_eq!((reader.width(), reader.height()), (800, 600));
+    let mut frames = Vec::new();
+    while let Some(frame) = reader.read_next_frame().expect("frame") {
+        frames.push(frame.buffer.to_vec());
+    }
+    assert_eq!(frames.len(), 3);
+    assert_ne!(frames[0], frames[2], "the 3D view rotates between frames");
+}
+
 #[test]
 fn snake_respects_fps_setting() {
     let td = tempdir().expect("tmp");
@@ -622,3 +1415,33 @@ fn allrgb_rejects_invalid_colormap() {
         .arg(&output);
     cmd.assert().failure();
 }
+
+#[test]
+#[allow(deprecated)]
+fn help_follows_locale() {
+    let help = |args: &[&str], lang: &str| {
+        let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+        let assert = cmd
+            .args(args)
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env("LANG", lang)
+            .assert()
+            .success();
+        String::from_utf8_lossy(&assert.get_output().stdout).into_owned()
+    };
+
+    let german = help(&["--help"], "de_DE.UTF-8");
+    assert!(german.contains("Karte eines Musters erzeugen"), "{german}");
+    let english = help(&["--locale", "en", "--help"], "de_DE.UTF-8");
+    assert!(english.contains("Generate a map of a pattern"), "{english}");
+    let flag = help(&["map", "--locale=de", "--help"], "C");
+    assert!(flag.contains("Ohne Rückfrage rendern"), "{flag}");
+}
+
+#[test]
+fn rejects_unsupported_locale() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["--locale", "fr", "list-curves"]);
+    cmd.assert().failure();
+}
@@ -14,3 +14,15 @@ fn list_curves_prints_expected_entries() {
     assert!(stdout.contains("hilbert"));
     assert!(stdout.contains("Z-order (Morton)"));
 }
+
+#[test]
+#[allow(deprecated)]
+fn bench_prints_complexity_and_sizes() {
+    let mut cmd = Command::cargo_bin("scurve").expect("binary exists");
+    cmd.args(["bench", "zorder"]);
+    let assert = cmd.assert().success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Z-order (Morton) (zorder) — O(D·bitwidth)"));
+    assert!(stdout.contains("1024×1024"));
+    assert!(!stdout.contains("Hilbert"));
+}
@@ -0,0 +1,158 @@
+#![allow(missing_docs, clippy::tests_outside_test_module)]
+
+//! Golden-image regression tests for the map renderer.
+//!
+//! Each case renders a small map and compares it with a PNG under
+//! `tests/fixtures/map` by perceptual hash, so a stroke nudged by a pixel
+//! passes while missing, extra or misplaced strokes fail.
+//! After an intended rendering change, rewrite the fixtures with
+//! `cargo test -p scurve --test map_golden -- --ignored` and review the PNGs.
+
+use std::{fs, path::PathBuf};
+
+use image::{
+    Rgba, RgbaImage,
+    imageops::{self, FilterType},
+};
+use scurve::map::{MapPalette, StrokeOptions, render_chunk_image, render_map_image};
+use spacecurve::{curve_from_name, segment::Adjacency};
+
+/// Image side of every fixture, in pixels.
+const SIZE: u32 = 128;
+
+/// Side of the grid the perceptual hash averages the image down to.
+const HASH_SIDE: u32 = 16;
+
+/// Hash bits that may differ before a render counts as changed.
+const TOLERANCE: u32 = 6;
+
+/// Colors shared by all fixtures.
+const PALETTE: MapPalette = MapPalette {
+    foreground: Rgba([0x20, 0x20, 0x80, 0xff]),
+    background: Rgba([0xff, 0xff, 0xff, 0xff]),
+};
+
+/// A named render to compare with its fixture.
+struct Case {
+    /// Fixture file stem.
+    name: &'static str,
+    /// Produce the image.
+    render: fn() -> RgbaImage,
+}
+
+/// Stroke options with the fixture palette.
+fn stroke(line_width: u32, adjacency: Adjacency) -> StrokeOptions {
+    StrokeOptions {
+        line_width,
+        adjacency,
+        palette: PALETTE,
+    }
+}
+
+/// Render `len` points of a 2D curve from `start`, wrapping past the end.
+fn chunk(name: &str, side: u32, start: u64, len: u64, stroke: StrokeOptions) -> RgbaImage {
+    let curve = curve_from_name(name, 2, side).expect("curve builds");
+    render_chunk_image(SIZE, side, start, len, stroke, &*curve)
+}
+
+/// Render a whole 2D curve.
+fn full(name: &str, side: u32, stroke: StrokeOptions) -> RgbaImage {
+    let curve = curve_from_name(name, 2, side).expect("curve builds");
+    render_map_image(SIZE, side, 0..curve.length(), stroke, &*curve)
+}
+
+/// Every golden case.
+const CASES: &[Case] = &[
+    Case {
+        name: "hilbert_width_1",
+        render: || full("hilbert", 8, stroke(1, Adjacency::UNIT)),
+    },
+    Case {
+        name: "hilbert_width_5",
+        render: || full("hilbert", 8, stroke(5, Adjacency::UNIT)),
+    },
+    Case {
+        name: "hilbert_chunk",
+        render: || chunk("hilbert", 8, 10, 30, stroke(3, Adjacency::UNIT)),
+    },
+    Case {
+        name: "hcurve_wraparound",
+        render: || chunk("hcurve", 8, 48, 32, stroke(3, Adjacency::UNIT)),
+    },
+    Case {
+        name: "zorder_skip_long_edges",
+        render: || full("zorder", 8, stroke(2, Adjacency::UNIT)),
+    },
+    Case {
+        name: "zorder_long_edges",
+        render: || full("zorder", 8, stroke(2, Adjacency::ANY)),
+    },
+];
+
+/// Path of the fixture for `name`.
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/map")
+        .join(format!("{name}.png"))
+}
+
+/// Average hash: one bit per cell of a `HASH_SIDE²` grayscale thumbnail,
+/// set where the cell is darker than the thumbnail's mean.
+fn perceptual_hash(image: &RgbaImage) -> Vec<bool> {
+    let gray = imageops::grayscale(image);
+    let thumb = imageops::resize(&gray, HASH_SIDE, HASH_SIDE, FilterType::Triangle);
+    let mean = thumb.pixels().map(|p| u32::from(p.0[0])).sum::<u32>() / (HASH_SIDE * HASH_SIDE);
+    thumb.pixels().map(|p| u32::from(p.0[0]) < mean).collect()
+}
+
+/// Number of differing bits between two hashes.
+fn hash_distance(a: &[bool], b: &[bool]) -> u32 {
+    a.iter().zip(b).filter(|(x, y)| x != y).count() as u32
+}
+
+#[test]
+fn renders_match_golden_fixtures() {
+    for case in CASES {
+        let actual = (case.render)();
+        let path = fixture_path(case.name);
+        let expected = image::open(&path)
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+            .to_rgba8();
+        assert_eq!(actual.dimensions(), expected.dimensions(), "{}", case.name);
+        let distance = hash_distance(&perceptual_hash(&actual), &perceptual_hash(&expected));
+        assert!(
+            distance <= TOLERANCE,
+            "{} differs from its fixture by {distance} hash bits",
+            case.name
+        );
+    }
+}
+
+#[test]
+fn hash_tells_cases_apart() {
+    // The tolerance must not hide a missing stroke or the long-edge setting.
+    let hashes: Vec<_> = CASES
+        .iter()
+        .map(|case| perceptual_hash(&(case.render)()))
+        .collect();
+    for (i, a) in hashes.iter().enumerate() {
+        for (j, b) in hashes.iter().enumerate().skip(i + 1) {
+            assert!(
+                hash_distance(a, b) > TOLERANCE,
+                "{} and {} hash alike",
+                CASES[i].name,
+                CASES[j].name
+            );
+        }
+    }
+}
+
+#[test]
+#[ignore = "rewrites the golden fixtures"]
+fn regenerate_golden_fixtures() {
+    for case in CASES {
+        let path = fixture_path(case.name);
+        fs::create_dir_all(path.parent().expect("fixture dir")).expect("create fixture dir");
+        (case.render)().save(&path).expect("write fixture");
+    }
+}
@@ -3,7 +3,9 @@
 use std::hint::black_box;
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use spacecurve::ops::{deinterleave_lsb, interleave_lsb};
+use spacecurve::ops::{
+    deinterleave_lsb, deinterleave_many, interleave_lsb, interleave_many, interleave_many_soa,
+};
 
 /// Benchmark the `interleave_lsb` operation.
 fn bench_interleave(c: &mut Criterion) {
@@ -43,10 +45,60 @@ fn bench_deinterleave(c: &mut Criterion) {
     group.finish();
 }
 
+/// Number of points in each batch benchmark.
+const BATCH_LEN: u32 = 1 << 16;
+
+/// Compare per-point and batch interleaving over a large 2D dataset.
+fn bench_interleave_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interleave_many");
+    let points: Vec<[u32; 2]> = (0..BATCH_LEN).map(|i| [i & 0xff, i >> 8]).collect();
+    let xs: Vec<u32> = points.iter().map(|p| p[0]).collect();
+    let ys: Vec<u32> = points.iter().map(|p| p[1]).collect();
+    let mut out = Vec::with_capacity(points.len());
+
+    group.bench_function("2D scalar", |b| {
+        b.iter(|| {
+            out.clear();
+            out.extend(points.iter().map(|p| interleave_lsb(black_box(p), 8)));
+            black_box(&out);
+        })
+    });
+    group.bench_function("2D AoS", |b| {
+        b.iter(|| {
+            out.clear();
+            interleave_many(black_box(&points), 8, &mut out);
+            black_box(&out);
+        })
+    });
+    group.bench_function("2D SoA", |b| {
+        b.iter(|| {
+            out.clear();
+            interleave_many_soa(&[black_box(&xs), black_box(&ys)], 8, &mut out);
+            black_box(&out);
+        })
+    });
+
+    let mut decoded: Vec<[u32; 2]> = Vec::with_capacity(points.len());
+    group.bench_function("2D deinterleave", |b| {
+        b.iter(|| {
+            decoded.clear();
+            deinterleave_many(8, black_box(0..u64::from(BATCH_LEN)), &mut decoded);
+            black_box(&decoded);
+        })
+    });
+
+    group.finish();
+}
+
 #[allow(missing_docs, clippy::missing_docs_in_private_items)]
 mod bench_defs {
     use super::*;
-    criterion_group!(benches, bench_interleave, bench_deinterleave);
+    criterion_group!(
+        benches,
+        bench_interleave,
+        bench_deinterleave,
+        bench_interleave_many
+    );
 }
 pub use bench_defs::benches;
 criterion_main!(benches);
@@ -23,10 +23,14 @@ fn bench_configs() -> Vec<(&'static str, u32, u32)> {
         ("scan", 2, 16),
         ("scan", 3, 4),
         // Onion
+        #[cfg(feature = "experimental-curves")]
         ("onion", 2, 16),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 3, 4),
         // Hairy Onion
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 2, 16),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 3, 4),
         // Gray code
         ("gray", 2, 16),
@@ -66,6 +70,35 @@ fn bench_index(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark `index_many` against per-point `index` calls through `dyn SpaceCurve`.
+fn bench_index_many(c: &mut Criterion) {
+    let mut group = c.benchmark_group("index_many");
+
+    for (name, dim, size) in [("hilbert", 2, 256), ("zorder", 2, 256), ("zorder", 3, 32)] {
+        let curve = curve_from_name(name, dim, size).expect("valid curve");
+        let points: Vec<_> = curve.points().collect();
+        let id = format!("{dim}d-{size}");
+        let mut out = Vec::with_capacity(points.len());
+
+        group.bench_function(BenchmarkId::new(format!("{name} per point"), &id), |b| {
+            b.iter(|| {
+                out.clear();
+                out.extend(black_box(&points).iter().map(|p| curve.index(p)));
+                black_box(out.len())
+            })
+        });
+        group.bench_function(BenchmarkId::new(format!("{name} batch"), &id), |b| {
+            b.iter(|| {
+                out.clear();
+                curve.index_many(black_box(&points), &mut out);
+                black_box(out.len())
+            })
+        });
+    }
+
+    group.finish();
+}
+
 /// Compare 2D optimized Hilbert vs N-D Hilbert at various sizes.
 fn bench_hilbert_2d_vs_nd(c: &mut Criterion) {
     let mut group = c.benchmark_group("hilbert_2d_vs_nd");
@@ -90,6 +123,27 @@ fn bench_hilbert_2d_vs_nd(c: &mut Criterion) {
     group.finish();
 }
 
+/// Measure the 2D H-curve kernel at sizes the GUI renders.
+fn bench_hcurve_2d(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hcurve_2d");
+
+    for size in [16, 128, 1024] {
+        let curve = curve_from_name("hcurve", 2, size).expect("hcurve 2d");
+        let midpoint = curve.length() / 2;
+        let pt = curve.point(midpoint);
+
+        group.bench_function(BenchmarkId::new("point", format!("2d-{size}")), |b| {
+            b.iter(|| curve.point(black_box(midpoint)))
+        });
+
+        group.bench_function(BenchmarkId::new("index", format!("2d-{size}")), |b| {
+            b.iter(|| curve.index(black_box(&pt)))
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark scaling behavior: how performance changes with curve size.
 fn bench_scaling(c: &mut Criterion) {
     let mut group = c.benchmark_group("scaling");
@@ -122,7 +176,9 @@ mod bench_defs {
         benches,
         bench_point,
         bench_index,
+        bench_index_many,
         bench_hilbert_2d_vs_nd,
+        bench_hcurve_2d,
         bench_scaling
     );
 }
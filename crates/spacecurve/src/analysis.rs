@@ -0,0 +1,221 @@
+//! Whole-curve locality metrics for comparing traversals.
+//!
+//! [`locality`] condenses how well a curve preserves neighbourhoods into four
+//! standard numbers: the mean index distance between neighbouring cells, the
+//! dilation factor, the clustering number of random query boxes and the count
+//! of long jumps. Lower is better for all of them. Unlike the per-point
+//! [`local_stretch`](crate::metrics::local_stretch), these summarise a curve
+//! in a single row, so curves can be ranked side by side.
+//!
+//! [`locality`]: crate::analysis::locality
+
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+
+use crate::{error, registry, registry::CurveId, spacecurve::SpaceCurve};
+
+/// Settings for [`locality`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalityOptions {
+    /// Largest index gap considered for the dilation factor; the factor is
+    /// exact when this is at least the curve length.
+    pub dilation_window: u64,
+    /// Number of random boxes averaged for the clustering number.
+    pub queries: u32,
+    /// Side length of each query box, clamped to the grid.
+    pub query_side: u32,
+    /// Seed for placing the query boxes, so results are reproducible.
+    pub seed: u64,
+}
+
+impl Default for LocalityOptions {
+    fn default() -> Self {
+        Self {
+            dilation_window: 1024,
+            queries: 256,
+            query_side: 4,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// Locality metrics of one curve; lower values mean better locality.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Locality {
+    /// Mean index distance between cells one step apart along an axis.
+    pub neighbor_distance: f64,
+    /// Largest `d(p_i, p_j)^D / (j - i)` over point pairs within the dilation
+    /// window, with `d` the Euclidean distance and `D` the dimension.
+    pub dilation: f64,
+    /// Mean number of contiguous index ranges needed to cover a query box.
+    pub clustering: f64,
+    /// Steps between consecutive points that are not unit steps.
+    pub long_jumps: u64,
+}
+
+/// Measure the locality of `curve`.
+///
+/// Query boxes are placed inside the curve's [`extents`](SpaceCurve::extents).
+/// Every point is held in memory, so this suits the grid sizes used for
+/// comparing curves rather than the largest ones a curve supports.
+pub fn locality(curve: &dyn SpaceCurve, options: &LocalityOptions) -> Locality {
+    let points: Vec<SmallVec<[u32; 8]>> = curve.points().map(|p| p.0).collect();
+    Locality {
+        neighbor_distance: neighbor_distance(&points),
+        dilation: dilation(&points, options.dilation_window),
+        clustering: clustering(curve, options),
+        long_jumps: curve.segments().filter(|s| !s.adjacent).count() as u64,
+    }
+}
+
+/// Measure the locality of the curve `id` at `size` in `dimension`.
+///
+/// Returns an error if the curve rejects the combination.
+pub fn locality_of(
+    id: CurveId,
+    dimension: u32,
+    size: u32,
+    options: &LocalityOptions,
+) -> error::Result<Locality> {
+    let curve = registry::construct_id(id, dimension, size)?;
+    Ok(locality(&*curve, options))
+}
+
+/// Mean index distance over every pair of points one step apart on an axis.
+fn neighbor_distance(points: &[SmallVec<[u32; 8]>]) -> f64 {
+    let lookup: HashMap<&[u32], usize> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.as_slice(), i))
+        .collect();
+    let (mut total, mut pairs) = (0u64, 0u64);
+    for (i, p) in points.iter().enumerate() {
+        let mut q = p.clone();
+        for axis in 0..p.len() {
+            let Some(next) = p[axis].checked_add(1) else {
+                continue;
+            };
+            q[axis] = next;
+            if let Some(&j) = lookup.get(q.as_slice()) {
+                total += i.abs_diff(j) as u64;
+                pairs += 1;
+            }
+            q[axis] = p[axis];
+        }
+    }
+    if pairs == 0 {
+        0.0
+    } else {
+        total as f64 / pairs as f64
+    }
+}
+
+/// Largest `d^D / gap` over point pairs at most `window` indices apart.
+fn dilation(points: &[SmallVec<[u32; 8]>], window: u64) -> f64 {
+    let window = usize::try_from(window).unwrap_or(usize::MAX);
+    let mut worst: f64 = 0.0;
+    for (i, p) in points.iter().enumerate() {
+        let end = i.saturating_add(window).min(points.len() - 1);
+        for (gap, q) in (1..).zip(&points[i + 1..=end]) {
+            let squared: u64 = p
+                .iter()
+                .zip(q)
+                .map(|(&a, &b)| u64::from(a.abs_diff(b)).pow(2))
+                .sum();
+            let volume = (squared as f64).powf(p.len() as f64 / 2.0);
+            worst = worst.max(volume / f64::from(gap));
+        }
+    }
+    worst
+}
+
+/// Mean number of index ranges covering [`LocalityOptions::queries`] random
+/// boxes of side [`LocalityOptions::query_side`].
+fn clustering(curve: &dyn SpaceCurve, options: &LocalityOptions) -> f64 {
+    if curve.length() == 0 || options.queries == 0 {
+        return 0.0;
+    }
+    let extent = curve.extents();
+    let mut rng = SplitMix64(options.seed);
+    let mut total = 0usize;
+    for _ in 0..options.queries {
+        let (lo, hi): (SmallVec<[u32; 8]>, SmallVec<[u32; 8]>) = extent
+            .iter()
+            .map(|&side| {
+                let span = options.query_side.clamp(1, side);
+                let lo = (rng.next() % u64::from(side - span + 1)) as u32;
+                (lo, lo + span - 1)
+            })
+            .unzip();
+        total += curve.ranges_for_box(&lo, &hi).len();
+    }
+    total as f64 / f64::from(options.queries)
+}
+
+/// Small deterministic generator for placing query boxes.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Next pseudo-random value.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_from_name;
+
+    #[test]
+    fn scan_has_known_metrics() {
+        // Boustrophedon on 4×4: rows of unit steps joined by unit turns.
+        let curve = curve_from_name("scan", 2, 4).unwrap();
+        let options = LocalityOptions {
+            queries: 0,
+            ..LocalityOptions::default()
+        };
+        let metrics = locality(&*curve, &options);
+        assert_eq!(metrics.long_jumps, 0);
+        assert_eq!(metrics.clustering, 0.0);
+        // Row neighbours are 1 apart; column neighbours 1, 3, 5 or 7 apart.
+        assert_eq!(metrics.neighbor_distance, (12.0 + 48.0) / 24.0);
+        assert!(metrics.dilation >= 1.0);
+    }
+
+    #[test]
+    fn hilbert_beats_zorder() {
+        let options = LocalityOptions::default();
+        let hilbert = locality_of(CurveId::Hilbert, 2, 32, &options).unwrap();
+        let zorder = locality_of(CurveId::ZOrder, 2, 32, &options).unwrap();
+        assert_eq!(hilbert.long_jumps, 0);
+        assert!(zorder.long_jumps > 0);
+        assert!(hilbert.dilation < zorder.dilation);
+        assert!(hilbert.clustering < zorder.clustering);
+        assert!(hilbert.clustering >= 1.0);
+    }
+
+    #[test]
+    fn shuffle_is_the_baseline() {
+        let options = LocalityOptions::default();
+        let zorder = locality_of(CurveId::ZOrder, 2, 32, &options).unwrap();
+        let shuffle = locality_of(CurveId::Shuffle, 2, 32, &options).unwrap();
+        assert!(shuffle.neighbor_distance > zorder.neighbor_distance);
+        assert!(shuffle.clustering > zorder.clustering);
+        assert!(shuffle.long_jumps > zorder.long_jumps);
+    }
+
+    #[test]
+    fn results_are_reproducible() {
+        let options = LocalityOptions::default();
+        let a = locality_of(CurveId::HCurve, 2, 16, &options).unwrap();
+        let b = locality_of(CurveId::HCurve, 2, 16, &options).unwrap();
+        assert_eq!(a, b);
+        assert!(locality_of(CurveId::Hilbert, 2, 5, &options).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Curves restricted to a rectangular part of their grid.
+//!
+//! Square curves cover a cube of side `size`. A [`Clipped`] curve keeps only
+//! the points inside a smaller box anchored at the origin and renumbers them
+//! consecutively, so a square traversal can fill a wide or tall extent. Points
+//! keep their relative order; steps across the removed region become long
+//! jumps.
+//!
+//! [`Clipped`]: crate::clip::Clipped
+
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+
+use smallvec::SmallVec;
+
+use crate::{error, point::Point, spacecurve::SpaceCurve};
+
+/// A curve visiting only the points of `inner` that lie inside `extent`.
+#[derive(Debug)]
+pub struct Clipped {
+    /// The full curve being clipped.
+    inner: Box<dyn SpaceCurve>,
+    /// Exclusive upper bound per axis.
+    extent: Vec<u32>,
+    /// Indices on `inner` of the retained points, ascending.
+    indices: Vec<u64>,
+}
+
+impl Clipped {
+    /// Keep the points of `inner` with every coordinate below `extent`.
+    ///
+    /// `extent` needs one entry per dimension of `inner`, each at least 1.
+    pub fn new(inner: Box<dyn SpaceCurve>, extent: &[u32]) -> error::Result<Self> {
+        if extent.len() != inner.dimensions() as usize {
+            return Err(error::Error::Shape(format!(
+                "clip extent has {} axes but the curve has {} dimensions",
+                extent.len(),
+                inner.dimensions()
+            )));
+        }
+        if extent.contains(&0) {
+            return Err(error::Error::Size(
+                "clip extent must be at least 1 on every axis".to_string(),
+            ));
+        }
+        let indices = (0..inner.length())
+            .filter(|&index| {
+                inner
+                    .point(index)
+                    .iter()
+                    .zip(extent)
+                    .all(|(coord, bound)| coord < bound)
+            })
+            .collect();
+        Ok(Self {
+            inner,
+            extent: extent.to_vec(),
+            indices,
+        })
+    }
+
+    /// Exclusive upper bound of the retained points on each axis.
+    pub fn extent(&self) -> &[u32] {
+        &self.extent
+    }
+}
+
+impl SpaceCurve for Clipped {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn info(&self) -> &'static str {
+        self.inner.info()
+    }
+
+    /// Index of `p`, which must lie inside the extent.
+    fn index(&self, p: &Point) -> u64 {
+        let inner = self.inner.index(p);
+        self.indices
+            .binary_search(&inner)
+            .expect("point lies outside the clip extent") as u64
+    }
+
+    fn point(&self, index: u64) -> Point {
+        self.inner.point(self.indices[index as usize])
+    }
+
+    fn length(&self) -> u64 {
+        self.indices.len() as u64
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        SmallVec::from_slice(&self.extent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_from_name;
+
+    #[test]
+    fn keeps_points_inside_the_extent_in_order() {
+        let full = curve_from_name("hilbert", 2, 8).unwrap();
+        let clipped = Clipped::new(curve_from_name("hilbert", 2, 8).unwrap(), &[8, 3]).unwrap();
+        assert_eq!(clipped.length(), 24);
+        assert_eq!(clipped.extent(), &[8, 3]);
+
+        let mut previous = None;
+        for index in 0..clipped.length() {
+            let p = clipped.point(index);
+            assert!(p[0] < 8 && p[1] < 3, "{p:?}");
+            assert_eq!(clipped.index(&p), index);
+            let on_full = full.index(&p);
+            assert!(previous.is_none_or(|prev| prev < on_full));
+            previous = Some(on_full);
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_extents() {
+        let curve = || curve_from_name("zorder", 2, 4).unwrap();
+        assert!(Clipped::new(curve(), &[4]).is_err());
+        assert!(Clipped::new(curve(), &[4, 0]).is_err());
+        assert_eq!(Clipped::new(curve(), &[4, 4]).unwrap().length(), 16);
+    }
+}
@@ -0,0 +1,247 @@
+//! Curves made of several traversals walked one after another.
+//!
+//! A [`Concatenated`] curve visits every point of its first part, then every
+//! point of the next, and so on. Each part can be scaled up by a whole factor,
+//! so a coarse traversal can run over every `n`th cell of the same grid a fine
+//! one covers later; a coarse 8×8 Hilbert pass at scale 8 followed by the full
+//! 64×64 curve gives a quick preview that progressive renderers then refine.
+//!
+//! Parts may visit the same cell, so unlike other curves a concatenation need
+//! not be a bijection: [`SpaceCurve::index`] returns the first visit. Scaled
+//! parts can also leave cells of the grid unvisited; for those, `index`
+//! returns a nearby point of the finest part instead.
+//!
+//! [`Concatenated`]: crate::concat::Concatenated
+
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use core::ops::Range;
+
+use smallvec::SmallVec;
+
+use crate::{error, point::Point, spacecurve::SpaceCurve};
+
+/// Several curves, optionally scaled, traversed in order.
+///
+/// [`extents`](SpaceCurve::extents) covers every scaled part, so it can
+/// include cells that no part visits, e.g. the cells between the points of a
+/// coarse pass when no later part fills them in. [`index`](SpaceCurve::index)
+/// maps such a cell to the point of the finest-scaled part nearest to it,
+/// rounding each coordinate to that part's lattice.
+#[derive(Debug)]
+pub struct Concatenated {
+    /// Curves walked in order, with the factor applied to their coordinates.
+    parts: Vec<(Box<dyn SpaceCurve>, u32)>,
+    /// Index of the first point of each part.
+    starts: Vec<u64>,
+    /// Total number of points.
+    length: u64,
+    /// Side length of the grid covering every scaled part.
+    extents: SmallVec<[u32; 8]>,
+}
+
+impl Concatenated {
+    /// Walk `parts` one after another at their own coordinates.
+    pub fn new(parts: Vec<Box<dyn SpaceCurve>>) -> error::Result<Self> {
+        Self::scaled(parts.into_iter().map(|part| (part, 1)).collect())
+    }
+
+    /// Walk `parts` one after another, multiplying each part's coordinates by
+    /// its factor.
+    ///
+    /// There must be at least one part, all with the same number of
+    /// dimensions and a factor of at least 1, and the result must fit `u32`
+    /// coordinates and `u64` indices.
+    pub fn scaled(parts: Vec<(Box<dyn SpaceCurve>, u32)>) -> error::Result<Self> {
+        let Some((first, _)) = parts.first() else {
+            return Err(error::Error::Shape(
+                "a concatenation needs at least one curve".to_string(),
+            ));
+        };
+        let dimension = first.dimensions();
+        let mut extents: SmallVec<[u32; 8]> = SmallVec::from_elem(0, dimension as usize);
+        let mut starts = Vec::with_capacity(parts.len());
+        let mut length = 0u64;
+        for (part, scale) in &parts {
+            if part.dimensions() != dimension {
+                return Err(error::Error::Shape(format!(
+                    "cannot concatenate a {}D curve with a {}D curve",
+                    dimension,
+                    part.dimensions()
+                )));
+            }
+            if *scale == 0 {
+                return Err(error::Error::Size(
+                    "concatenated parts need a scale of at least 1".to_string(),
+                ));
+            }
+            for (extent, &side) in extents.iter_mut().zip(&part.extents()) {
+                let scaled = (side - 1)
+                    .checked_mul(*scale)
+                    .and_then(|last| last.checked_add(1))
+                    .ok_or_else(|| {
+                        error::Error::Size("concatenated grid exceeds u32 coordinates".to_string())
+                    })?;
+                *extent = (*extent).max(scaled);
+            }
+            starts.push(length);
+            length = length.checked_add(part.length()).ok_or_else(|| {
+                error::Error::Size("concatenated curve length exceeds u64 bounds".to_string())
+            })?;
+        }
+        Ok(Self {
+            parts,
+            starts,
+            length,
+            extents,
+        })
+    }
+
+    /// Number of parts.
+    pub fn num_parts(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Part containing `index`, which must be below the curve length.
+    pub fn part_of(&self, index: u64) -> usize {
+        debug_assert!(index < self.length, "index out of bounds");
+        self.starts.partition_point(|&start| start <= index) - 1
+    }
+
+    /// Indices of the points of part `k`.
+    pub fn part_range(&self, k: usize) -> Range<u64> {
+        self.starts[k]..self.starts[k] + self.parts[k].0.length()
+    }
+
+    /// Index of the first visit to `p`, if some part visits it.
+    fn first_visit(&self, p: &Point) -> Option<u64> {
+        self.parts
+            .iter()
+            .zip(&self.starts)
+            .find_map(|((part, scale), start)| {
+                let extents = part.extents();
+                let local: Option<SmallVec<[u32; 8]>> = p
+                    .iter()
+                    .zip(&extents)
+                    .map(|(&c, &side)| (c % scale == 0 && c / scale < side).then_some(c / scale))
+                    .collect();
+                Some(start + part.index(&Point::new(local?)))
+            })
+    }
+
+    /// Index of the point of the finest-scaled part nearest to `p`, for a
+    /// cell that no part visits.
+    fn nearest_visit(&self, p: &Point) -> u64 {
+        let (k, (part, scale)) = self
+            .parts
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, scale))| *scale)
+            .expect("a concatenation has at least one part");
+        let local: SmallVec<[u32; 8]> = p
+            .iter()
+            .zip(&part.extents())
+            .map(|(&c, &side)| (c / scale + u32::from(c % scale > scale / 2)).min(side - 1))
+            .collect();
+        self.starts[k] + part.index(&Point::new(local))
+    }
+}
+
+impl SpaceCurve for Concatenated {
+    fn name(&self) -> &'static str {
+        "Concatenated"
+    }
+
+    fn info(&self) -> &'static str {
+        "Several curves traversed one after another."
+    }
+
+    /// Index of the first visit to `p`, or for a cell no part visits, of the
+    /// nearest point of the finest-scaled part.
+    fn index(&self, p: &Point) -> u64 {
+        self.first_visit(p).unwrap_or_else(|| self.nearest_visit(p))
+    }
+
+    fn point(&self, index: u64) -> Point {
+        let k = self.part_of(index);
+        let (part, scale) = &self.parts[k];
+        let mut p = part.point(index - self.starts[k]);
+        for c in p.0.iter_mut() {
+            *c *= scale;
+        }
+        p
+    }
+
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.extents.len() as u32
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::curve_from_name;
+
+    #[test]
+    fn walks_parts_in_order() {
+        let coarse = curve_from_name("hilbert", 2, 4).unwrap();
+        let fine = curve_from_name("hilbert", 2, 16).unwrap();
+        let (coarse_points, fine_points): (Vec<_>, Vec<_>) =
+            (coarse.points().collect(), fine.points().collect());
+        let curve = Concatenated::scaled(vec![(coarse, 5), (fine, 1)]).unwrap();
+        assert_eq!(curve.name(), "Concatenated");
+        assert_eq!(curve.num_parts(), 2);
+        assert_eq!(curve.length(), 16 + 256);
+        assert_eq!(curve.extents().as_slice(), &[16, 16]);
+        assert_eq!(curve.part_range(0), 0..16);
+        assert_eq!(curve.part_range(1), 16..272);
+        assert_eq!((curve.part_of(15), curve.part_of(16)), (0, 1));
+        for (index, p) in (0..).zip(&coarse_points) {
+            assert_eq!(curve.point(index).as_slice(), [p[0] * 5, p[1] * 5]);
+        }
+        for (index, expected) in (0..).zip(&fine_points) {
+            let p = curve.point(16 + index);
+            assert_eq!(&p, expected);
+            // Cells on the coarse grid were first visited by the coarse pass.
+            let first = curve.index(&p);
+            assert_eq!(curve.point(first), p);
+            assert!(first == 16 + index || (first < 16 && p.iter().all(|c| c % 5 == 0)));
+        }
+    }
+
+    #[test]
+    fn unvisited_cells_map_to_the_nearest_point() {
+        let curve =
+            Concatenated::scaled(vec![(curve_from_name("hilbert", 2, 4).unwrap(), 5)]).unwrap();
+        assert_eq!(curve.extents().as_slice(), &[16, 16]);
+        for (cell, nearest) in [([1, 2], [0, 0]), ([3, 7], [5, 5]), ([15, 14], [15, 15])] {
+            let index = curve.index(&Point::new(cell.to_vec()));
+            assert_eq!(curve.point(index).as_slice(), nearest, "{cell:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        assert!(Concatenated::new(Vec::new()).is_err());
+        assert!(
+            Concatenated::new(vec![
+                curve_from_name("hilbert", 2, 4).unwrap(),
+                curve_from_name("hilbert", 3, 4).unwrap(),
+            ])
+            .is_err()
+        );
+        assert!(Concatenated::scaled(vec![(curve_from_name("scan", 2, 4).unwrap(), 0)]).is_err());
+        assert!(
+            Concatenated::scaled(vec![(curve_from_name("scan", 2, 4).unwrap(), u32::MAX)]).is_err()
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use smallvec::SmallVec;
+
+use crate::{curves::scan::Scan, error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
+
+/// Rectangular spiral across the first two axes, reflected across the rest.
+///
+/// Each plane spanned by axes 0 and 1 is walked as an inward spiral: the
+/// outer ring counter-clockwise from the origin, then the next ring inside
+/// it, down to the center. The planes are visited in [`Scan`] order over the
+/// remaining axes, and every other plane runs its spiral backwards, outward
+/// from the center, so in 3D the layers stack into a coil. Every step is a
+/// unit move for any number of dimensions and any side lengths.
+#[derive(Debug)]
+pub struct Coil {
+    /// Side length along each axis.
+    extents: SmallVec<[u32; 8]>,
+    /// Total number of points.
+    length: u64,
+    /// Order of the planes, over axes 2 and up; `None` below three dimensions.
+    planes: Option<Scan>,
+}
+
+impl Coil {
+    /// Construct a `Coil` curve for the given dimensions and side length.
+    pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::new(dimension, size)?)
+    }
+
+    /// Construct a `Coil` curve over a grid with its own side length on each
+    /// axis.
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::from_extents(extents)?)
+    }
+
+    /// Construct a `Coil` curve over a validated grid.
+    fn from_spec(spec: &GridSpec) -> error::Result<Self> {
+        let extents = spec.extents();
+        let planes = match extents.get(2..) {
+            Some(rest) if !rest.is_empty() => Some(Scan::from_extents(rest)?),
+            _ => None,
+        };
+        Ok(Self {
+            extents: extents.into(),
+            length: spec.length(),
+            planes,
+        })
+    }
+
+    /// Width and height of each plane; a 1D grid is a single row.
+    fn plane(&self) -> (u64, u64) {
+        (
+            u64::from(self.extents[0]),
+            self.extents.get(1).map_or(1, |&h| u64::from(h)),
+        )
+    }
+}
+
+/// Cells of a `width`×`height` spiral before ring `ring` starts.
+fn ring_start(width: u64, height: u64, ring: u64) -> u64 {
+    2 * ring * (width + height - 2 * ring)
+}
+
+/// Cell at position `offset` of the inward spiral over a `width`×`height`
+/// plane.
+fn spiral_point(width: u64, height: u64, offset: u64) -> (u64, u64) {
+    // The last ring starting at or before `offset`; ring starts grow with the
+    // ring number for every ring that exists.
+    let (mut lo, mut hi) = (0, width.min(height).div_ceil(2) - 1);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if ring_start(width, height, mid) <= offset {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let ring = lo;
+    let along = offset - ring_start(width, height, ring);
+    let (w, h) = (width - 2 * ring, height - 2 * ring);
+    let (x, y) = if w == 1 || h == 1 {
+        // A single row or column left in the middle.
+        if h == 1 { (along, 0) } else { (0, along) }
+    } else if along < w {
+        (along, 0)
+    } else if along < w + h - 1 {
+        (w - 1, along - (w - 1))
+    } else if along < 2 * w + h - 2 {
+        (w - 1 - (along - (w + h - 2)), h - 1)
+    } else {
+        (0, h - 1 - (along - (2 * w + h - 3)))
+    };
+    (ring + x, ring + y)
+}
+
+/// Position of cell `cell` in the inward spiral over a `width`×`height`
+/// plane.
+fn spiral_index(width: u64, height: u64, cell: (u64, u64)) -> u64 {
+    let ring = cell
+        .0
+        .min(cell.1)
+        .min(width - 1 - cell.0)
+        .min(height - 1 - cell.1);
+    let (w, h) = (width - 2 * ring, height - 2 * ring);
+    let (x, y) = (cell.0 - ring, cell.1 - ring);
+    let along = if w == 1 || h == 1 || y == 0 {
+        x + y
+    } else if x == w - 1 {
+        (w - 1) + y
+    } else if y == h - 1 {
+        (w - 1) + (h - 1) + (w - 1 - x)
+    } else {
+        2 * (w - 1) + (h - 1) + (h - 1 - y)
+    };
+    ring_start(width, height, ring) + along
+}
+
+impl SpaceCurve for Coil {
+    fn name(&self) -> &'static str {
+        "Coil"
+    }
+
+    fn info(&self) -> &'static str {
+        "Inward spiral in each plane, reversed on every other plane so 3D\n\
+        layers stack into a coil. Continuous for any dimension and size;\n\
+        a simple reference curve alongside the scan."
+    }
+
+    #[inline]
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    #[inline]
+    fn dimensions(&self) -> u32 {
+        self.extents.len() as u32
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+
+    fn point(&self, index: u64) -> Point {
+        debug_assert!(index < self.length, "index out of bounds");
+        let (width, height) = self.plane();
+        let area = width * height;
+        let (plane, offset) = (index / area, index % area);
+        let offset = if plane % 2 == 1 {
+            area - 1 - offset
+        } else {
+            offset
+        };
+        let (x, y) = spiral_point(width, height, offset);
+        let mut coordinates: SmallVec<[u32; 8]> = SmallVec::with_capacity(self.extents.len());
+        coordinates.push(x as u32);
+        if self.extents.len() > 1 {
+            coordinates.push(y as u32);
+        }
+        if let Some(planes) = &self.planes {
+            coordinates.extend(planes.point(plane).iter().copied());
+        }
+        Point::new_with_dimension(self.dimensions(), coordinates)
+    }
+
+    fn index(&self, point: &Point) -> u64 {
+        debug_assert_eq!(point.len(), self.extents.len(), "point dimension mismatch");
+        debug_assert!(
+            point.iter().zip(&self.extents).all(|(c, e)| c < e),
+            "point coordinate out of bounds"
+        );
+        let (width, height) = self.plane();
+        let area = width * height;
+        let plane = self.planes.as_ref().map_or(0, |planes| {
+            planes.index(&Point::new_with_dimension(
+                planes.dimensions(),
+                point[2..].iter().copied().collect::<SmallVec<[u32; 8]>>(),
+            ))
+        });
+        let cell = (
+            u64::from(point[0]),
+            point.get(1).map_or(0, |&y| u64::from(y)),
+        );
+        let offset = spiral_index(width, height, cell);
+        let offset = if plane % 2 == 1 {
+            area - 1 - offset
+        } else {
+            offset
+        };
+        plane * area + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spirals_inward_in_the_plane() {
+        let c = Coil::from_dimensions(2, 3).unwrap();
+        let expected = [
+            [0, 0],
+            [1, 0],
+            [2, 0],
+            [2, 1],
+            [2, 2],
+            [1, 2],
+            [0, 2],
+            [0, 1],
+            [1, 1],
+        ];
+        for (idx, coords) in expected.iter().enumerate() {
+            assert_eq!(c.point(idx as u64).as_slice(), coords);
+            assert_eq!(c.index(&Point::new(coords.to_vec())), idx as u64);
+        }
+    }
+
+    #[test]
+    fn reverses_every_other_plane() {
+        let c = Coil::from_dimensions(3, 3).unwrap();
+        // The second layer starts above the center where the first one ended.
+        assert_eq!(c.point(8).as_slice(), [1, 1, 0]);
+        assert_eq!(c.point(9).as_slice(), [1, 1, 1]);
+        assert_eq!(c.point(17).as_slice(), [0, 0, 1]);
+        assert_eq!(c.point(18).as_slice(), [0, 0, 2]);
+    }
+
+    #[test]
+    fn roundtrips_on_any_grid() {
+        for extents in [
+            &[1][..],
+            &[5],
+            &[4, 1],
+            &[1, 4],
+            &[6, 3],
+            &[3, 7],
+            &[4, 4, 2],
+            &[3, 2, 2, 3],
+        ] {
+            let c = Coil::from_extents(extents).unwrap();
+            for idx in 0..c.length() {
+                let p = c.point(idx);
+                assert!(p.iter().zip(extents).all(|(c, e)| c < e));
+                assert_eq!(c.index(&p), idx, "roundtrip failed on {extents:?} at {idx}");
+                if idx > 0 {
+                    let q = c.point(idx - 1);
+                    let step: u32 = p.iter().zip(q.iter()).map(|(a, b)| a.abs_diff(*b)).sum();
+                    assert_eq!(step, 1, "jump on {extents:?} at {idx}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn guard_matches_registry() {
+        assert!(Coil::from_dimensions(0, 3).is_err());
+        assert!(Coil::from_dimensions(2, 0).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::string::ToString;
+
+use smallvec::{SmallVec, smallvec};
+
+use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
+
+/// Generalized Hilbert ("gilbert") curve over an arbitrary `width × height`
+/// rectangle.
+///
+/// The rectangle is split recursively along its major axis, as in Jakub
+/// Červený's gilbert2d: thin rectangles are halved, others are cut into a
+/// short leg, a long leg and a return leg, with odd splits nudged so each part
+/// stays traversable. On power-of-two squares this is a Hilbert curve. Every
+/// step is a unit step except for a single diagonal step that may occur when
+/// the longer side is odd and the shorter side even.
+#[derive(Debug)]
+pub struct Gilbert {
+    /// Number of columns.
+    width: u32,
+    /// Number of rows.
+    height: u32,
+    /// Cached total number of points (`width * height`).
+    length: u64,
+}
+
+impl Gilbert {
+    /// Construct a curve covering a `width × height` grid.
+    pub fn new(width: u32, height: u32) -> error::Result<Self> {
+        let spec = GridSpec::from_extents(&[width, height])?;
+        Ok(Self {
+            width,
+            height,
+            length: spec.length(),
+        })
+    }
+
+    /// Construct a curve covering a square grid, for parity with the other
+    /// curves. Only two dimensions are supported.
+    pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
+        if dimension != 2 {
+            return Err(error::Error::Shape(
+                "Gilbert requires dimension 2".to_string(),
+            ));
+        }
+        Self::new(size, size)
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// A sub-rectangle in the recursion, traversed from `(x, y)` along the major
+/// axis `a` while sweeping across the minor axis `b`.
+///
+/// `a` and `b` are axis-aligned, signed vectors whose lengths are the
+/// rectangle's extents.
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    /// Start column.
+    x: i64,
+    /// Start row.
+    y: i64,
+    /// Major axis, x component.
+    ax: i64,
+    /// Major axis, y component.
+    ay: i64,
+    /// Minor axis, x component.
+    bx: i64,
+    /// Minor axis, y component.
+    by: i64,
+}
+
+impl Region {
+    /// The whole grid, with the major axis along its longer side.
+    fn root(width: u32, height: u32) -> Self {
+        let (width, height) = (i64::from(width), i64::from(height));
+        if width >= height {
+            Self::new(0, 0, width, 0, 0, height)
+        } else {
+            Self::new(0, 0, 0, height, width, 0)
+        }
+    }
+
+    /// A region from its start and axes.
+    fn new(x: i64, y: i64, ax: i64, ay: i64, bx: i64, by: i64) -> Self {
+        Self {
+            x,
+            y,
+            ax,
+            ay,
+            bx,
+            by,
+        }
+    }
+
+    /// Extent along the major axis.
+    fn major(&self) -> i64 {
+        (self.ax + self.ay).abs()
+    }
+
+    /// Extent along the minor axis.
+    fn minor(&self) -> i64 {
+        (self.bx + self.by).abs()
+    }
+
+    /// Number of points in the region.
+    fn len(&self) -> u64 {
+        self.major().unsigned_abs() * self.minor().unsigned_abs()
+    }
+
+    /// Whether `(x, y)` lies inside the region.
+    fn contains(&self, x: i64, y: i64) -> bool {
+        let far_x = self.x + self.ax + self.bx - self.ax.signum() - self.bx.signum();
+        let far_y = self.y + self.ay + self.by - self.ay.signum() - self.by.signum();
+        (self.x.min(far_x)..=self.x.max(far_x)).contains(&x)
+            && (self.y.min(far_y)..=self.y.max(far_y)).contains(&y)
+    }
+
+    /// Whether the region is a single row or column, walked straight through.
+    fn is_line(&self) -> bool {
+        self.major() == 1 || self.minor() == 1
+    }
+
+    /// Unit step of a line region.
+    fn line_step(&self) -> (i64, i64) {
+        if self.minor() == 1 {
+            (self.ax.signum(), self.ay.signum())
+        } else {
+            (self.bx.signum(), self.by.signum())
+        }
+    }
+
+    /// The sub-regions of a region that is not a line, in traversal order.
+    fn split(&self) -> SmallVec<[Self; 3]> {
+        let Self {
+            x,
+            y,
+            ax,
+            ay,
+            bx,
+            by,
+        } = *self;
+        let (dax, day) = (ax.signum(), ay.signum());
+        let (dbx, dby) = (bx.signum(), by.signum());
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+        let (major, minor) = (self.major(), self.minor());
+
+        if 2 * major > 3 * minor {
+            // Long and thin: halve the major axis, preferring an even first half.
+            if (ax2 + ay2).abs() % 2 == 1 && major > 2 {
+                ax2 += dax;
+                ay2 += day;
+            }
+            return smallvec![
+                Self::new(x, y, ax2, ay2, bx, by),
+                Self::new(x + ax2, y + ay2, ax - ax2, ay - ay2, bx, by),
+            ];
+        }
+
+        // Up half the minor axis, along the full major axis, and back down.
+        if (bx2 + by2).abs() % 2 == 1 && minor > 2 {
+            bx2 += dbx;
+            by2 += dby;
+        }
+        smallvec![
+            Self::new(x, y, bx2, by2, ax2, ay2),
+            Self::new(x + bx2, y + by2, ax, ay, bx - bx2, by - by2),
+            Self::new(
+                x + (ax - dax) + (bx2 - dbx),
+                y + (ay - day) + (by2 - dby),
+                -bx2,
+                -by2,
+                -(ax - ax2),
+                -(ay - ay2),
+            ),
+        ]
+    }
+}
+
+impl SpaceCurve for Gilbert {
+    fn name(&self) -> &'static str {
+        "Gilbert"
+    }
+
+    fn info(&self) -> &'static str {
+        "Generalized Hilbert curve for any width × height rectangle.\n\
+        Keeps Hilbert-like locality on grids that are not powers of two,\n\
+        at the cost of one diagonal step on some odd-by-even shapes."
+    }
+    #[inline]
+    fn length(&self) -> u64 {
+        self.length
+    }
+    #[inline]
+    fn dimensions(&self) -> u32 {
+        2
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        smallvec![self.width, self.height]
+    }
+
+    fn point(&self, index: u64) -> Point {
+        debug_assert!(index < self.length, "index out of bounds");
+        let mut region = Region::root(self.width, self.height);
+        let mut offset = index;
+        while !region.is_line() {
+            for part in region.split() {
+                if offset < part.len() {
+                    region = part;
+                    break;
+                }
+                offset -= part.len();
+            }
+        }
+        let (dx, dy) = region.line_step();
+        let step = offset as i64;
+        Point::new_with_dimension(
+            2,
+            smallvec![(region.x + dx * step) as u32, (region.y + dy * step) as u32],
+        )
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        debug_assert_eq!(p.len(), 2, "point dimension mismatch");
+        debug_assert!(
+            p[0] < self.width && p[1] < self.height,
+            "point coordinate out of bounds"
+        );
+        let (x, y) = (i64::from(p[0]), i64::from(p[1]));
+        let mut region = Region::root(self.width, self.height);
+        let mut index = 0;
+        while !region.is_line() {
+            for part in region.split() {
+                if part.contains(x, y) {
+                    region = part;
+                    break;
+                }
+                index += part.len();
+            }
+        }
+        index + ((x - region.x).abs() + (y - region.y).abs()) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn covers_rectangles_with_unit_steps() {
+        for width in 1..=24 {
+            for height in 1..=24 {
+                let curve = Gilbert::new(width, height).unwrap();
+                let mut seen = vec![false; curve.length() as usize];
+                let mut diagonal = 0;
+                let mut previous: Option<Point> = None;
+                for i in 0..curve.length() {
+                    let p = curve.point(i);
+                    assert!(p[0] < width && p[1] < height, "{width}x{height} at {i}");
+                    assert_eq!(curve.index(&p), i, "{width}x{height} roundtrip at {i}");
+                    seen[(p[1] * width + p[0]) as usize] = true;
+                    if let Some(prev) = previous {
+                        let dx = prev[0].abs_diff(p[0]);
+                        let dy = prev[1].abs_diff(p[1]);
+                        assert!(dx <= 1 && dy <= 1, "{width}x{height} jumps at {i}");
+                        if dx + dy == 2 {
+                            diagonal += 1;
+                        }
+                    }
+                    previous = Some(p);
+                }
+                assert!(seen.iter().all(|&s| s), "{width}x{height} misses points");
+                let (long, short) = (width.max(height), width.min(height));
+                if long % 2 == 0 || short % 2 == 1 {
+                    assert_eq!(diagonal, 0, "{width}x{height}");
+                } else {
+                    assert!(diagonal <= 1, "{width}x{height}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn power_of_two_squares_match_hilbert_shape() {
+        let curve = Gilbert::new(4, 4).unwrap();
+        let points: Vec<Vec<u32>> = (0..4).map(|i| curve.point(i).to_vec()).collect();
+        assert_eq!(points, vec![vec![0, 0], vec![1, 0], vec![1, 1], vec![0, 1]]);
+        assert_eq!(curve.point(15).to_vec(), vec![3, 0]);
+    }
+
+    #[test]
+    fn roundtrips_grids_beyond_u32_indices() {
+        let curve = Gilbert::new(u32::MAX, 3 << 20).unwrap();
+        assert!(curve.length() > u64::from(u32::MAX));
+        for i in [0, 1 << 32, curve.length() / 3, curve.length() - 1] {
+            let p = curve.point(i);
+            assert_eq!(curve.index(&p), i);
+        }
+    }
+
+    #[test]
+    fn rejects_other_dimensions() {
+        assert!(Gilbert::from_dimensions(3, 4).is_err());
+        assert!(Gilbert::new(0, 4).is_err());
+        assert!(Gilbert::from_dimensions(2, 5).is_ok());
+    }
+}
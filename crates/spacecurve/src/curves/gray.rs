@@ -1,3 +1,5 @@
+use smallvec::{SmallVec, smallvec};
+
 use crate::{error, ops, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
 
 /// Gray-code based hypercube traversal (BRGC).
@@ -5,12 +7,14 @@ use crate::{error, ops, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
 pub struct Gray {
     /// Number of dimensions in the grid.
     dimension: u32,
-    /// Side length per dimension.
+    /// Side length per dimension; the largest extent on rectangular grids.
     size: u32,
     /// Cached bit width per coordinate (size is always a power of two).
     bits_per_axis: u32,
+    /// Bit width of each axis when they differ, `None` for hypercubes.
+    axis_bits: Option<SmallVec<[u32; 4]>>,
     /// Cached total number of points in the curve.
-    length: u32,
+    length: u64,
 }
 
 impl Gray {
@@ -20,13 +24,27 @@ impl Gray {
     /// power of two so the Binary Reflected Gray Code remains bijective across
     /// the hypercube.
     pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
-        let spec = GridSpec::power_of_two(dimension, size)?;
-        spec.require_index_bits_lt(32)?;
+        Self::from_spec(&GridSpec::power_of_two(dimension, size)?)
+    }
+
+    /// Construct a `Gray` curve over a box with its own power-of-two side
+    /// length on each axis.
+    ///
+    /// Code bits are spread over the axes as in
+    /// [`ZOrder::from_extents`](super::zorder::ZOrder::from_extents).
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::power_of_two_extents(extents)?)
+    }
+
+    /// Construct a `Gray` curve over a validated power-of-two grid.
+    fn from_spec(spec: &GridSpec) -> error::Result<Self> {
+        spec.require_index_bits_lt(64)?;
 
         Ok(Self {
             dimension: spec.dimension(),
             size: spec.size(),
             bits_per_axis: spec.bits_per_axis().unwrap(),
+            axis_bits: (!spec.is_square()).then(|| spec.axis_bits().unwrap()),
             length: spec.length(),
         })
     }
@@ -42,42 +60,78 @@ impl SpaceCurve for Gray {
         indices differ by one bit. Requires power-of-two side lengths; fast,\n\
         but spatial locality is weaker than Hilbert/H-curve."
     }
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
 
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimension
     }
 
-    fn point(&self, index: u32) -> Point {
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        match &self.axis_bits {
+            Some(bits) => bits.iter().map(|&b| 1 << b).collect(),
+            None => smallvec![self.size; self.dimension as usize],
+        }
+    }
+
+    #[inline]
+    fn point(&self, index: u64) -> Point {
         debug_assert!(index < self.length, "index out of range");
 
         // Convert the linear index to Gray code, then deinterleave the bits
-        // across coordinates using the same bit layout as Morton order.
+        // across coordinates using the same bit layout as Morton order. In 2D
+        // `deinterleave_lsb` and `interleave_lsb` already dispatch to the
+        // const-generic Morton kernels, and the call is dominated by building
+        // the `Point`; a dedicated 2D Gray kernel measured no faster, so
+        // there is none (unlike Hilbert and the H-curve).
         let gray_index = ops::graycode(index);
-        Point::new_with_dimension(
-            self.dimension,
-            ops::deinterleave_lsb(self.dimension, self.bits_per_axis, gray_index),
-        )
+        let coords = match &self.axis_bits {
+            Some(bits) => ops::deinterleave_uneven(bits, gray_index),
+            None => ops::deinterleave_lsb(self.dimension, self.bits_per_axis, gray_index),
+        };
+        Point::new_with_dimension(self.dimension, coords)
     }
 
-    fn index(&self, p: &Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &Point) -> u64 {
         debug_assert_eq!(p.len(), self.dimension as usize, "point dimension mismatch");
         debug_assert!(
-            p.iter().all(|&coord| coord < self.size),
+            p.iter()
+                .zip(self.extents())
+                .all(|(&coord, side)| coord < side),
             "point coordinate out of bounds"
         );
 
-        let gray_index = ops::interleave_lsb(&p[..], self.bits_per_axis);
+        let gray_index = match &self.axis_bits {
+            Some(bits) => ops::interleave_uneven(p, bits),
+            None => ops::interleave_lsb(&p[..], self.bits_per_axis),
+        };
         let binary_index = ops::igraycode(gray_index);
         debug_assert!(binary_index < self.length, "index conversion overflowed");
         binary_index
     }
+
+    #[inline]
+    fn advance_point(&self, index: u64, point: &mut Point) {
+        debug_assert!(index + 1 < self.length, "index out of range");
+        if self.axis_bits.is_some() {
+            *point = self.point(index + 1);
+            return;
+        }
+        // Consecutive Gray codes differ in the lowest set bit of the next
+        // index, and interleaving sends code bit `b` to axis `b % dimension`.
+        let bit = (index + 1).trailing_zeros();
+        point.0[(bit % self.dimension) as usize] ^= 1 << (bit / self.dimension);
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::{vec, vec::Vec};
+
     use super::*;
 
     fn assert_roundtrip(dimension: u32, size: u32) {
@@ -149,12 +203,37 @@ mod tests {
         assert_roundtrip(2, 4);
     }
 
+    #[test]
+    fn test_gray_rectangular_roundtrip() {
+        let gray = Gray::from_extents(&[2, 8, 4]).unwrap();
+        assert_eq!(gray.length(), 64);
+        assert_eq!(gray.extents().as_slice(), [2, 8, 4]);
+        for i in 0..gray.length() {
+            let point = gray.point(i);
+            assert_eq!(gray.index(&point), i);
+        }
+        assert!(Gray::from_extents(&[2, 6]).is_err());
+    }
+
+    #[test]
+    fn test_gray_beyond_u32_indices() {
+        let gray = Gray::from_dimensions(2, 1 << 30).unwrap();
+        assert_eq!(gray.length(), 1 << 60);
+        for i in [(1 << 32) - 1, 1 << 32, (1 << 59) + 7, gray.length() - 2] {
+            let point = gray.point(i);
+            assert_eq!(gray.index(&point), i);
+            let mut next = point.clone();
+            gray.advance_point(i, &mut next);
+            assert_eq!(next, gray.point(i + 1));
+        }
+    }
+
     #[test]
     fn test_gray_neighbourhood() {
         let gray2 = Gray::from_dimensions(2, 2).unwrap();
         let expected = [vec![0, 0], vec![1, 0], vec![1, 1], vec![0, 1]];
         for (idx, coords) in expected.iter().enumerate() {
-            assert_eq!(Vec::<u32>::from(gray2.point(idx as u32)), *coords);
+            assert_eq!(Vec::<u32>::from(gray2.point(idx as u64)), *coords);
         }
 
         // For 3D hypercube with size 2 ensure adjacency differs by one coordinate.
@@ -1,10 +1,11 @@
 use crate::{
-    curves::onion::{onion_index_2d, onion_point_2d},
+    curves::onion::{self, onion_index_2d, onion_point_2d},
     error,
     point::Point,
     spacecurve::SpaceCurve,
     spec::GridSpec,
 };
+use alloc::{vec, vec::Vec};
 
 /// A continuous N-dimensional generalization of the Onion Curve.
 /// It relaxes strict layering constraints (impossible for N>=3) by tiling the space
@@ -16,13 +17,16 @@ pub struct HairyOnionCurve {
     /// Side length per dimension.
     side_length: u32,
     /// Total number of points (L^N).
-    length: u32,
+    length: u64,
 }
 
 impl HairyOnionCurve {
     /// Construct a new Hairy Onion curve for `dimensions` and `side_length`.
+    ///
+    /// As with [`onion::OnionCurve`], `L^N` must fit in a `u32`.
     pub fn new(dimensions: u32, side_length: u32) -> error::Result<Self> {
         let spec = GridSpec::new(dimensions, side_length)?;
+        spec.require_length_le(u64::from(u32::MAX))?;
         Ok(Self {
             dimensions: spec.dimension(),
             side_length: spec.size(),
@@ -39,15 +43,18 @@ impl SpaceCurve for HairyOnionCurve {
     fn info(&self) -> &'static str {
         "A stacked variant of the Onion curve."
     }
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimensions
     }
 
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
 
-    fn index(&self, p: &Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &Point) -> u64 {
         debug_assert_eq!(
             p.len(),
             self.dimensions as usize,
@@ -57,17 +64,34 @@ impl SpaceCurve for HairyOnionCurve {
             p.iter().all(|&c| c < self.side_length),
             "point coordinate out of bounds"
         );
-        hairy_onion_index_recursive(self.dimensions, self.side_length, p)
+        u64::from(hairy_onion_index_recursive(
+            self.dimensions,
+            self.side_length,
+            p,
+        ))
     }
 
-    fn point(&self, index: u32) -> Point {
+    #[inline]
+    fn point(&self, index: u64) -> Point {
         debug_assert!(index < self.length, "index out of bounds");
-        let coords =
-            hairy_onion_point_recursive(self.dimensions, self.side_length, index % self.length);
+        let index = (index % self.length) as u32;
+        let coords = hairy_onion_point_recursive(self.dimensions, self.side_length, index);
         Point::new_with_dimension(self.dimensions, coords)
     }
 }
 
+/// Index of the 2D onion shell containing `point` within its tile, counted
+/// from the outside (0 is the outermost shell).
+///
+/// Every tile spans the first two axes, so only those coordinates decide the
+/// shell; in 1D every point is on shell 0.
+pub fn shell_level(side: u32, point: &[u32]) -> u32 {
+    if point.len() < 2 {
+        return 0;
+    }
+    onion::shell_level(side, &point[..2])
+}
+
 // --- Generalized N-D Hairy Onion Implementation (Tiled 2D Onion) ---
 
 // Helper function to calculate the index recursively.
@@ -172,6 +196,8 @@ mod tests {
         assert!(HairyOnionCurve::new(2, 0).is_err());
         // N==0 rejected
         assert!(HairyOnionCurve::new(0, 4).is_err());
+        // L^N beyond u32 rejected
+        assert!(HairyOnionCurve::new(3, 1 << 11).is_err());
         // Valid shapes
         let c = HairyOnionCurve::new(2, 3).unwrap();
         assert_eq!(c.length(), 9);
@@ -193,4 +219,14 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn shell_levels_follow_tiles() {
+        let curve = HairyOnionCurve::new(3, 5).unwrap();
+        let tile: Vec<u32> = (0..25).map(|i| shell_level(5, &curve.point(i))).collect();
+        assert!(tile.windows(2).all(|w| w[0] <= w[1]));
+        let next: Vec<u32> = (25..50).map(|i| shell_level(5, &curve.point(i))).collect();
+        assert!(next.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(shell_level(5, &[3]), 0);
+    }
 }
@@ -8,9 +8,16 @@ Cyclic space-filling curves and their clustering property, Igor V. Netay.
 The original C implementation by Netay contained an error in Grey/InvGrey usage
 for D>=3, leading to discontinuities, which is fixed here.
 */
+use alloc::{string::ToString, vec, vec::Vec};
+
 use smallvec::SmallVec;
 
-use crate::{error, ops, point, spacecurve::SpaceCurve, spec::GridSpec};
+use crate::{
+    curves::hcurve2::{self, SHIFT_SLOTS},
+    error, ops, point,
+    spacecurve::SpaceCurve,
+    spec::GridSpec,
+};
 
 // Convention used in low-level functions:
 // d: Dimension
@@ -41,32 +48,32 @@ and grey_inverse_fast implemented Grey). We rename them here for clarity.
 // Retrieves Grey(val) from cache (lower half of corners[0]).
 // Corresponds to C: grey_inverse_fast.
 /// Cached Gray code from the precomputed corner tables.
-fn cached_grey(_d: u32, val: u32, corners: &[Vec<u32>]) -> u32 {
-    corners[0][val as usize]
+fn cached_grey(_d: u32, val: u32, corners: &[Vec<u64>]) -> u32 {
+    corners[0][val as usize] as u32
 }
 
 // Retrieves InverseGrey(val) from cache (upper half of corners[0]).
 // Corresponds to C: grey_fast.
 /// Cached inverse Gray code from the precomputed corner tables.
-fn cached_inv_grey(d: u32, val: u32, corners: &[Vec<u32>]) -> u32 {
+fn cached_inv_grey(d: u32, val: u32, corners: &[Vec<u64>]) -> u32 {
     // Assumes d < 32.
-    corners[0][(val + (1 << d)) as usize]
+    corners[0][(val + (1 << d)) as usize] as u32
 }
 
 // d=Dimension, n=Order.
 /// Precompute corner-index tables for dimension `d` and order `n`.
-fn corner_indexes(d: u32, n: u32) -> Vec<Vec<u32>> {
+fn corner_indexes(d: u32, n: u32) -> Vec<Vec<u64>> {
     // Assumes d < 32.
     let size = 1u32 << d;
-    let mut v = vec![vec![0u32; (size * 2) as usize]; (n + 1) as usize];
+    let mut v = vec![vec![0u64; (size * 2) as usize]; (n + 1) as usize];
 
     // Initialize Grey codes cache (n=0 case).
     for i in 0..size {
         let g = grey(d, i);
         // Store Inverse Grey in the upper half
-        v[0][(g + size) as usize] = i;
+        v[0][(g + size) as usize] = u64::from(i);
         // Store Grey in the lower half
-        v[0][i as usize] = g;
+        v[0][i as usize] = u64::from(g);
     }
 
     // Build the rest of the tables recursively.
@@ -85,9 +92,8 @@ fn corner_indexes(d: u32, n: u32) -> Vec<Vec<u32>> {
 }
 
 // encode_h in C. (Point to Index)
-// Uses u64 for internal calculations (r) to prevent overflow during intermediate steps.
 /// Compute H-curve index from alpha vectors.
-fn h_index_alphas(d: u32, n: u32, alphas: &[u32], corners: &[Vec<u32>]) -> u32 {
+fn h_index_alphas(d: u32, n: u32, alphas: &[u32], corners: &[Vec<u64>]) -> u64 {
     debug_assert_eq!(alphas.len(), n as usize);
     if alphas.len() != n as usize {
         return 0;
@@ -108,7 +114,7 @@ fn h_index_alphas(d: u32, n: u32, alphas: &[u32], corners: &[Vec<u32>]) -> u32 {
         let index = (alpha_inv + two_power_d * need_to_change_last) as usize;
         // k = n - 1 - i (depth)
         let k = (n - 1 - i) as usize;
-        let r_shift = corners[k][index] as u64;
+        let r_shift = corners[k][index];
 
         let mut current_r_shift = r_shift;
 
@@ -134,12 +140,12 @@ fn h_index_alphas(d: u32, n: u32, alphas: &[u32], corners: &[Vec<u32>]) -> u32 {
         // 4. Combine. r = r0*S + r_transformed.
         r += r0 * sub_cell_size;
     }
-    r as u32
+    r
 }
 
 // d=Dimension, n=Order.
 /// Point to index mapping for the H-curve.
-fn h_index(d: u32, n: u32, p: &[u32], corners: &[Vec<u32>]) -> u32 {
+fn h_index(d: u32, n: u32, p: &[u32], corners: &[Vec<u64>]) -> u64 {
     debug_assert_eq!(p.len(), d as usize);
     if p.len() != d as usize {
         return 0;
@@ -151,13 +157,14 @@ fn h_index(d: u32, n: u32, p: &[u32], corners: &[Vec<u32>]) -> u32 {
 
 // decode_h in C. (Index to Point)
 /// Index to point mapping for the H-curve.
-fn h_point(d: u32, n: u32, idx: u32, corners: &[Vec<u32>]) -> SmallVec<[u32; 8]> {
+fn h_point(d: u32, n: u32, idx: u64, corners: &[Vec<u64>]) -> SmallVec<[u32; 8]> {
     let mut alphas = vec![0; n as usize];
     let two_power_d = 1u32 << d;
     let two_power_d_64 = 1u64 << d;
 
-    // r must be u64 as intermediate values during decoding can exceed 2^(D*N).
-    let mut r: u64 = idx as u64;
+    // Wrapping arithmetic keeps intermediate values in u64 even when they
+    // would exceed 2^(D*N).
+    let mut r: u64 = idx;
 
     // Iterate from most significant alpha (i=0) to least significant (i=n-1).
     for i in 0..n {
@@ -179,7 +186,7 @@ fn h_point(d: u32, n: u32, idx: u32, corners: &[Vec<u32>]) -> SmallVec<[u32; 8]>
         let need_to_change_last = 1 ^ parity(alpha_inv);
         let index = (alpha_inv + two_power_d * need_to_change_last) as usize;
 
-        let mut r_shift = corners[k as usize][index] as u64;
+        let mut r_shift = corners[k as usize][index];
 
         // Condition of reversal.
         if d % 2 == 1 && n == 1 {
@@ -196,6 +203,50 @@ fn h_point(d: u32, n: u32, idx: u32, corners: &[Vec<u32>]) -> SmallVec<[u32; 8]>
     ops::bit_transpose(d, &alphas)
 }
 
+/// Internal dispatcher selecting the 2D or N-D H-curve core.
+#[derive(Debug)]
+enum HCurveImpl {
+    /// Specialised 2D kernel with the corner tables reduced to one shift per
+    /// level and digit.
+    TwoD(Vec<[u64; 4]>),
+    /// Generic N-dimensional mapping over the full corner index tables.
+    Nd(Vec<Vec<u64>>),
+}
+
+impl HCurveImpl {
+    /// Precompute the tables for dimension `d` and order `n`.
+    fn new(d: u32, n: u32) -> Self {
+        let corners = corner_indexes(d, n);
+        if d == 2 {
+            Self::TwoD(
+                corners
+                    .iter()
+                    .take(n as usize)
+                    .map(|level| SHIFT_SLOTS.map(|slot| level[slot]))
+                    .collect(),
+            )
+        } else {
+            Self::Nd(corners)
+        }
+    }
+
+    /// Compute an H-curve index using the chosen implementation.
+    fn index(&self, d: u32, n: u32, p: &[u32]) -> u64 {
+        match self {
+            Self::TwoD(shifts) => hcurve2::h_index(n, p, shifts),
+            Self::Nd(corners) => h_index(d, n, p, corners),
+        }
+    }
+
+    /// Compute coordinates from an index using the chosen implementation.
+    fn point(&self, d: u32, n: u32, idx: u64) -> SmallVec<[u32; 8]> {
+        match self {
+            Self::TwoD(shifts) => hcurve2::h_point(n, idx, shifts),
+            Self::Nd(corners) => h_point(d, n, idx, corners),
+        }
+    }
+}
+
 /// An implementation of the H curve generalization.
 #[derive(Debug)]
 pub struct HCurve {
@@ -203,8 +254,8 @@ pub struct HCurve {
     pub order: u32,
     /// The dimension of the H curve (D).
     pub dimension: u32,
-    /// Precomputed corner index tables used by point/index mapping.
-    corners: Vec<Vec<u32>>,
+    /// Precomputed tables and the 2D fast path or generic N-D logic using them.
+    mapper: HCurveImpl,
 }
 
 impl HCurve {
@@ -217,23 +268,20 @@ impl HCurve {
         let spec = GridSpec::power_of_two(dimension, size)?;
         let order = spec.order().unwrap();
 
-        // Enforce constraints required by the implementation (u32 limits and bit shifts).
+        // Enforce constraints required by the implementation (u64 limits and bit shifts).
         if dimension >= 32 {
             return Err(error::Error::Shape("Dimension must be < 32".to_string()));
         }
-        if (order as u64) * (dimension as u64) >= 32 {
+        if (order as u64) * (dimension as u64) >= 64 {
             return Err(error::Error::Size(
-                "Curve size exceeds u32 limits (D*O must be < 32)".to_string(),
+                "Curve size exceeds u64 limits (D*O must be < 64)".to_string(),
             ));
         }
 
-        // Precompute corner index tables once per instance.
-        let corners = corner_indexes(dimension, order);
-
         Ok(Self {
             dimension,
             order,
-            corners,
+            mapper: HCurveImpl::new(dimension, order),
         })
     }
 }
@@ -249,24 +297,32 @@ impl SpaceCurve for HCurve {
         Continuous on 2^n grids and often offering strong locality with\n\
         relatively simple bit operations."
     }
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         // Calculate 2^(D*O). Safe due to constructor checks.
-        1u32 << (self.order * self.dimension)
+        1u64 << (self.order * self.dimension)
     }
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimension
     }
-    fn point(&self, index: u32) -> point::Point {
+    /// Every H-curve with more than one point closes into a loop.
+    fn is_cyclic(&self) -> bool {
+        self.length() > 1
+    }
+    #[inline]
+    fn point(&self, index: u64) -> point::Point {
         let d = self.dimension;
         let n = self.order;
-        let hpoint = h_point(d, n, index, &self.corners);
+        let hpoint = self.mapper.point(d, n, index);
         point::Point::new_with_dimension(self.dimension, hpoint)
     }
 
-    fn index(&self, p: &point::Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &point::Point) -> u64 {
         let d = self.dimension;
         let n = self.order;
-        h_index(d, n, &p[..], &self.corners)
+        self.mapper.index(d, n, &p[..])
     }
 }
 
@@ -284,6 +340,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn single_cell_is_not_cyclic() -> error::Result<()> {
+        assert!(!HCurve::from_dimensions(2, 1)?.is_cyclic());
+        assert!(HCurve::from_dimensions(2, 2)?.is_cyclic());
+        Ok(())
+    }
+
+    #[test]
+    fn two_d_kernel_matches_generic() {
+        for n in 1..=5 {
+            let corners = corner_indexes(2, n);
+            let fast = HCurveImpl::new(2, n);
+            for idx in 0..1u64 << (2 * n) {
+                let p = h_point(2, n, idx, &corners);
+                assert_eq!(
+                    fast.point(2, n, idx),
+                    p,
+                    "point mismatch at order {n} index {idx}"
+                );
+                assert_eq!(
+                    fast.index(2, n, &p),
+                    idx,
+                    "index mismatch at order {n} index {idx}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn roundtrip_beyond_u32_indices() -> error::Result<()> {
+        for (dimension, order) in [(2, 24), (3, 20)] {
+            let curve = HCurve::from_dimensions(dimension, 1 << order)?;
+            let last = curve.length() - 1;
+            for idx in [1 << 32, last / 3, last] {
+                let point = curve.point(idx);
+                assert_eq!(curve.index(&point), idx, "{dimension}D mismatch at {idx}");
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn roundtrip_3d_order1() -> error::Result<()> {
         let curve = HCurve::from_dimensions(3, 2)?;
@@ -0,0 +1,41 @@
+//! 2D kernel for the H-curve.
+//!
+//! [`HCurve`](super::hcurve::HCurve) picks these functions at construction for
+//! 2D grids. Each level's corner tables reduce to one sub-curve shift per
+//! 2-bit digit, and coordinate bits are read directly, so `point` and `index`
+//! run without the N-D path's allocations and bit transposes.
+
+use smallvec::{SmallVec, smallvec};
+
+/// 2-bit Binary Reflected Gray Code; in two bits it is its own inverse.
+const GRAY2: [u32; 4] = [0, 1, 3, 2];
+
+/// Corner-table slot holding the sub-curve shift for each 2-bit digit `alpha`:
+/// the inverted digit, offset by 4 when that has even parity.
+pub const SHIFT_SLOTS: [usize; 4] = [7, 2, 1, 4];
+
+/// 2D H-curve index for `point`, given per-level sub-curve shifts indexed by digit.
+pub fn h_index(order: u32, point: &[u32], shifts: &[[u64; 4]]) -> u64 {
+    let mut r = 0u64;
+    // Walk from the least significant level (k = 0) up, matching the N-D encoder.
+    for (k, level) in shifts.iter().enumerate().take(order as usize) {
+        let alpha = ((point[0] >> k) & 1) << 1 | ((point[1] >> k) & 1);
+        let sub_cell_mask = (1u64 << (2 * k)) - 1;
+        r = r.wrapping_sub(level[alpha as usize]) & sub_cell_mask;
+        r |= u64::from(GRAY2[alpha as usize]) << (2 * k);
+    }
+    r
+}
+
+/// 2D H-curve point for `index`, given per-level sub-curve shifts indexed by digit.
+pub fn h_point(order: u32, index: u64, shifts: &[[u64; 4]]) -> SmallVec<[u32; 8]> {
+    let mut r = index;
+    let (mut x, mut y) = (0u32, 0u32);
+    for k in (0..order as usize).rev() {
+        let alpha = GRAY2[((r >> (2 * k)) & 3) as usize];
+        x |= (alpha >> 1) << k;
+        y |= (alpha & 1) << k;
+        r = r.wrapping_add(shifts[k][alpha as usize]);
+    }
+    smallvec![x, y]
+}
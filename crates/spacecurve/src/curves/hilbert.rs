@@ -1,8 +1,11 @@
+use alloc::{string::ToString, vec::Vec};
+use core::ops::Range;
+
 use smallvec::SmallVec;
 
 use crate::{
     curves::{hilbert2, hilbertn},
-    error, point,
+    error, point, query,
     spacecurve::SpaceCurve,
     spec::GridSpec,
 };
@@ -18,7 +21,7 @@ enum HilbertImpl {
 
 impl HilbertImpl {
     /// Compute a Hilbert index using the chosen implementation.
-    fn index(&self, dimension: u32, order: u32, point: &[u32]) -> u32 {
+    fn index(&self, dimension: u32, order: u32, point: &[u32]) -> u64 {
         match self {
             Self::TwoD => hilbert2::hilbert_index(order, point),
             Self::Nd => hilbertn::hilbert_index(dimension, order, point),
@@ -26,7 +29,7 @@ impl HilbertImpl {
     }
 
     /// Compute coordinates from an index using the chosen implementation.
-    fn point(&self, dimension: u32, order: u32, index: u32) -> SmallVec<[u32; 8]> {
+    fn point(&self, dimension: u32, order: u32, index: u64) -> SmallVec<[u32; 8]> {
         match self {
             Self::TwoD => hilbert2::hilbert_point(order, index),
             Self::Nd => hilbertn::hilbert_point(dimension, order, index),
@@ -44,7 +47,7 @@ pub struct Hilbert {
     pub dimension: u32,
     /// Cached total number of points (`2^(order * dimension)`), computed once
     /// at construction with checked math to avoid overflow in debug/release.
-    length: u32,
+    length: u64,
     /// Chooses between the 2D fast path and the generic N-D logic.
     mapper: HilbertImpl,
 }
@@ -55,7 +58,12 @@ impl Hilbert {
     /// a power of two (`size == 2^order`) or the result is an error.
     pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
         let spec = GridSpec::power_of_two(dimension, size)?;
-        spec.require_index_bits_lt(32)?;
+        spec.require_index_bits_lt(64)?;
+        if size > 1 && dimension >= 32 {
+            return Err(error::Error::Shape(
+                "Hilbert requires dimension < 32".to_string(),
+            ));
+        }
 
         Ok(Self {
             dimension: spec.dimension(),
@@ -80,13 +88,16 @@ impl SpaceCurve for Hilbert {
         Defined recursively via rotations/reflections; widely used in GIS,\n\
         image storage, and indexing; typically clusters better than Z-order."
     }
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimension
     }
-    fn index(&self, p: &point::Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &point::Point) -> u64 {
         debug_assert_eq!(p.len(), self.dimension as usize, "point dimension mismatch");
         let side = 1u32 << self.order;
         debug_assert!(
@@ -95,7 +106,8 @@ impl SpaceCurve for Hilbert {
         );
         self.mapper.index(self.dimension, self.order, p)
     }
-    fn point(&self, index: u32) -> point::Point {
+    #[inline]
+    fn point(&self, index: u64) -> point::Point {
         let len = self.length;
         debug_assert!(index < len, "index out of bounds");
         point::Point::new_with_dimension(
@@ -103,6 +115,35 @@ impl SpaceCurve for Hilbert {
             self.mapper.point(self.dimension, self.order, index % len),
         )
     }
+    #[inline]
+    fn advance_point(&self, index: u64, point: &mut point::Point) {
+        match self.mapper {
+            HilbertImpl::TwoD => {
+                let (dx, dy) = hilbert2::hilbert_step(self.order, index);
+                point.0[0] = point.0[0].wrapping_add_signed(dx);
+                point.0[1] = point.0[1].wrapping_add_signed(dy);
+            }
+            HilbertImpl::Nd => *point = self.point(index + 1),
+        }
+    }
+    fn index_many(&self, points: &[point::Point], out: &mut Vec<u64>) {
+        out.reserve(points.len());
+        match self.mapper {
+            HilbertImpl::TwoD => out.extend(
+                points
+                    .iter()
+                    .map(|p| hilbert2::hilbert_index_xy(self.order, p[0], p[1])),
+            ),
+            HilbertImpl::Nd => out.extend(
+                points
+                    .iter()
+                    .map(|p| hilbertn::hilbert_index(self.dimension, self.order, p)),
+            ),
+        }
+    }
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        query::block_ranges(self, self.order, lo, hi)
+    }
 }
 
 #[cfg(test)]
@@ -123,11 +164,31 @@ mod tests {
             panic!("expected error")
         }
 
-        // Guard: 2D order 16 (size 2^16) would produce length 2^32 → reject
-        assert!(Hilbert::from_dimensions(2, 1u32 << 16).is_err());
-        // 2D order 15 → ok
-        assert!(Hilbert::from_dimensions(2, 1u32 << 15).is_ok());
+        // Guard: 3D order 22 would need 66 index bits → reject
+        assert!(Hilbert::from_dimensions(3, 1u32 << 22).is_err());
+        // 3D order 21 (63 bits) and every 2D power of two → ok
+        assert!(Hilbert::from_dimensions(3, 1u32 << 21).is_ok());
+        assert_eq!(Hilbert::from_dimensions(2, 1u32 << 31)?.length(), 1 << 62);
+        assert!(Hilbert::from_dimensions(40, 2).is_err());
+
+        Ok(())
+    }
 
+    #[test]
+    fn roundtrip_beyond_u32_indices() -> error::Result<()> {
+        for (dimension, order) in [(2, 24), (2, 31), (3, 21), (5, 12)] {
+            let h = Hilbert::from_dimensions(dimension, 1 << order)?;
+            let last = h.length() - 1;
+            for i in [0, 1 << 33, last / 3, last - 1, last] {
+                let p = h.point(i);
+                assert_eq!(h.index(&p), i, "{dimension}D order {order} at {i}");
+                if i < last {
+                    let mut next = p.clone();
+                    h.advance_point(i, &mut next);
+                    assert_eq!(next, h.point(i + 1), "{dimension}D order {order} at {i}");
+                }
+            }
+        }
         Ok(())
     }
 }
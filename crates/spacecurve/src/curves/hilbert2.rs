@@ -1,10 +1,16 @@
 use smallvec::{SmallVec, smallvec};
 
 use super::hilbert_common::{gray2, rot2};
+use crate::ops::part1by1;
+
+/// The low bit of every base-4 digit of an index.
+const EVEN_BITS: u64 = 0x5555_5555_5555_5555;
+/// The low 32 bits, the lanes used by [`hilbert_index_xy`].
+const LOW_32: u64 = 0xFFFF_FFFF;
 
 /// 2D Hilbert index for a point `p` at a given `order`.
-pub fn hilbert_index(order: u32, point: &[u32]) -> u32 {
-    let mut index_acc = 0;
+pub fn hilbert_index(order: u32, point: &[u32]) -> u64 {
+    let mut index_acc = 0u64;
     let mut entry_state = 0;
     let mut direction_state = 0;
     for step in 0..order {
@@ -19,7 +25,7 @@ pub fn hilbert_index(order: u32, point: &[u32]) -> u32 {
         if word == 3 {
             entry_state = 3 - entry_state;
         }
-        index_acc = (index_acc << 2) | word;
+        index_acc = (index_acc << 2) | u64::from(word);
         if word == 0 || word == 3 {
             direction_state ^= 1;
         }
@@ -27,8 +33,49 @@ pub fn hilbert_index(order: u32, point: &[u32]) -> u32 {
     index_acc
 }
 
+/// Branch-free 2D Hilbert index of `(x, y)` at a given `order`.
+///
+/// Computes the same value as [`hilbert_index`] without a per-level loop:
+/// the orientation of every level is found with a parallel prefix scan over
+/// all coordinate bits at once (after the public-domain construction by
+/// rawrunprotected), so batches of points compile to straight-line code.
+pub fn hilbert_index_xy(order: u32, x: u32, y: u32) -> u64 {
+    if order == 0 {
+        return 0;
+    }
+    // The construction takes the axes in the opposite order to `hilbert_index`.
+    let first = u64::from(y) << (32 - order);
+    let second = u64::from(x) << (32 - order);
+
+    // Initial round, primed with the coordinates.
+    let diff = first ^ second;
+    let same = LOW_32 ^ diff;
+    let neither = LOW_32 ^ (first | second);
+    let only_first = first & (second ^ LOW_32);
+    let mut ta = diff | (same >> 1);
+    let mut tb = (diff >> 1) ^ diff;
+    let mut tc = ((neither >> 1) ^ (same & (only_first >> 1))) ^ neither;
+    let mut td = ((diff & (neither >> 1)) ^ (only_first >> 1)) ^ only_first;
+
+    // Combine the per-level transforms over doubling spans.
+    for shift in [2, 4, 8, 16] {
+        let (pa, pb, pc, pd) = (ta, tb, tc, td);
+        ta = (pa & (pa >> shift)) ^ (pb & (pb >> shift));
+        tb = (pa & (pb >> shift)) ^ (pb & ((pa ^ pb) >> shift));
+        tc ^= (pa & (pc >> shift)) ^ (pb & (pd >> shift));
+        td ^= (pb & (pc >> shift)) ^ ((pa ^ pb) & (pd >> shift));
+    }
+
+    // Undo the prefix scan and recover the index bits.
+    let flip = tc ^ (tc >> 1);
+    let swap = td ^ (td >> 1);
+    let low = diff;
+    let high = swap | (LOW_32 ^ (low | flip));
+    ((part1by1(high as u32) << 1) | part1by1(low as u32)) >> (64 - 2 * order)
+}
+
 /// 2D Hilbert point for a given `order` and `index`.
-pub fn hilbert_point(order: u32, index: u32) -> SmallVec<[u32; 8]> {
+pub fn hilbert_point(order: u32, index: u64) -> SmallVec<[u32; 8]> {
     let hwidth = order * 2;
     let mut entry_state = 0;
     let mut direction_state = 0;
@@ -37,7 +84,7 @@ pub fn hilbert_point(order: u32, index: u32) -> SmallVec<[u32; 8]> {
     let mut y_coord: u32 = 0;
     for step in 0..order {
         // Extract 2 bits from the index
-        let word = (index >> (hwidth - (step * 2) - 2)) & 3;
+        let word = ((index >> (hwidth - (step * 2) - 2)) & 3) as u32;
 
         let label = match direction_state {
             0 => rot2(gray2(word)) ^ entry_state,
@@ -63,6 +110,32 @@ pub fn hilbert_point(order: u32, index: u32) -> SmallVec<[u32; 8]> {
     smallvec![x_coord, y_coord]
 }
 
+/// Unit step `(dx, dy)` from the point at `index` to the point at `index + 1`.
+///
+/// The step is fixed by the lowest base-4 digit of `index` that is not 3: the
+/// sub-curve at that level moves on to its next quadrant. The frame of that
+/// level is set by the digits above it, and since the digit transforms
+/// (transpose for 0, anti-transpose for 3) commute, only the parity of their
+/// counts matters, which two popcounts give in constant time.
+pub fn hilbert_step(order: u32, index: u64) -> (i32, i32) {
+    debug_assert!(index + 1 < 1 << (2 * order), "no next point");
+    let level = index.trailing_ones() / 2;
+    let digit = (index >> (2 * level)) & 3;
+    let above = ((1u64 << (2 * order)) - (1u64 << (2 * (level + 1)))) & EVEN_BITS;
+    let (low, high) = (index & EVEN_BITS, (index >> 1) & EVEN_BITS);
+    let zeros = (!low & !high & above).count_ones();
+    let threes = (low & high & above).count_ones();
+
+    let (mut dx, mut dy) = [(1, 0), (0, 1), (-1, 0)][digit as usize];
+    if zeros % 2 == 1 {
+        (dx, dy) = (dy, dx);
+    }
+    if threes % 2 == 1 {
+        (dx, dy) = (-dy, -dx);
+    }
+    (dx, dy)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,16 +153,57 @@ mod tests {
         assert_eq!(gray2(3), 2);
     }
 
+    #[test]
+    fn branch_free_index_matches_the_loop() {
+        for order in 0u32..=6 {
+            for x in 0..1u32 << order {
+                for y in 0..1u32 << order {
+                    assert_eq!(
+                        hilbert_index_xy(order, x, y),
+                        hilbert_index(order, &[x, y]),
+                        "{order} {x} {y}"
+                    );
+                }
+            }
+        }
+        for order in [16, 24, 31] {
+            let side = 1u32 << order;
+            for (x, y) in [
+                (0, side - 1),
+                (side - 1, 0),
+                (side / 3, side / 7),
+                (12345, side - 99),
+            ] {
+                assert_eq!(
+                    hilbert_index_xy(order, x, y),
+                    hilbert_index(order, &[x, y]),
+                    "{order} {x} {y}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_index() {
         assert!(hilbert_index(3, &[5, 6]) == 45);
         assert!(hilbert_point(3, 45).as_slice() == [5, 6]);
     }
 
+    #[test]
+    fn steps_match_consecutive_points() {
+        for order in 1u32..6 {
+            for i in 0..(1 << (2 * order)) - 1 {
+                let (a, b) = (hilbert_point(order, i), hilbert_point(order, i + 1));
+                let step = (b[0] as i32 - a[0] as i32, b[1] as i32 - a[1] as i32);
+                assert_eq!(hilbert_step(order, i), step, "order {order} index {i}");
+            }
+        }
+    }
+
     #[test]
     fn test_symmetry() {
         for m in 2u32..5u32 {
-            for i in 0u32..2u32.pow(2 * m) {
+            for i in 0u64..2u64.pow(2 * m) {
                 let p = hilbert_point(m, i);
                 let r = hilbert_index(m, &p);
                 assert!(i == r);
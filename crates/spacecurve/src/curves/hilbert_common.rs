@@ -89,5 +89,5 @@ pub fn rot2(label: u32) -> u32 {
 /// Gray code limited to the low two bits.
 #[inline]
 pub fn gray2(word: u32) -> u32 {
-    ops::graycode(word) & 3
+    (ops::graycode(u64::from(word)) & 3) as u32
 }
@@ -35,24 +35,25 @@ fn direction(x: u32, n: u32) -> u32 {
 fn entry(x: u32) -> u32 {
     match x {
         0 => 0,
-        _ => ops::graycode(2 * ((x - 1) / 2)),
+        _ => gray(2 * ((x - 1) / 2)),
     }
 }
 
+/// Gray code of a `dimension`-bit label.
+fn gray(x: u32) -> u32 {
+    ops::graycode(u64::from(x)) as u32
+}
+
 /// N‑D Hilbert: compute point coordinates for `index`.
-pub fn hilbert_point(dimension: u32, order: u32, index: u32) -> SmallVec<[u32; 8]> {
-    let hwidth = order * dimension;
+pub fn hilbert_point(dimension: u32, order: u32, index: u64) -> SmallVec<[u32; 8]> {
     let mut entry_state = 0;
     let mut direction_state = 0;
     let mut point = smallvec![0; dimension as usize];
     for order_idx in 0..order {
-        let word = bitrange(
-            index,
-            hwidth,
-            order_idx * dimension,
-            order_idx * dimension + dimension,
-        );
-        let mut label = ops::graycode(word);
+        // Digits are read from the most significant end of the index.
+        let shift = (order - order_idx - 1) * dimension;
+        let word = ((index >> shift) as u32) & bitmask(dimension);
+        let mut label = gray(word);
         label = itransform(entry_state, direction_state, dimension, label);
         for coord in 0..dimension {
             let bit_val = bitrange(label, dimension, coord, coord + 1);
@@ -65,8 +66,8 @@ pub fn hilbert_point(dimension: u32, order: u32, index: u32) -> SmallVec<[u32; 8
 }
 
 /// N‑D Hilbert: compute linear index for `point`.
-pub fn hilbert_index(dimension: u32, order: u32, point: &[u32]) -> u32 {
-    let mut index_acc = 0;
+pub fn hilbert_index(dimension: u32, order: u32, point: &[u32]) -> u64 {
+    let mut index_acc = 0u64;
     let mut entry_state = 0;
     let mut direction_state = 0;
     for order_idx in 0..order {
@@ -82,10 +83,10 @@ pub fn hilbert_index(dimension: u32, order: u32, point: &[u32]) -> u32 {
         }
         label = transform(entry_state, direction_state, dimension, label);
 
-        let word = ops::igraycode(label);
+        let word = ops::igraycode(u64::from(label)) as u32;
         entry_state ^= lrot(entry(word), direction_state + 1, dimension);
         direction_state = (direction_state + direction(word, dimension) + 1) % dimension;
-        index_acc = (index_acc << dimension) | word;
+        index_acc = (index_acc << dimension) | u64::from(word);
     }
     index_acc
 }
@@ -98,7 +99,7 @@ mod tests {
     fn test_hilbert() {
         let m = 3;
         for n in 2..5 {
-            for i in 0..2u32.pow(n * m) {
+            for i in 0..2u64.pow(n * m) {
                 let v = hilbert_point(n, m, i);
                 assert_eq!(i, hilbert_index(n, m, &v));
             }
@@ -1,11 +1,18 @@
 //! Modules implementing individual curve families.
 
+/// Coil: planar spirals reflected across the remaining axes.
+pub mod coil;
+/// Generalized Hilbert curve over arbitrary rectangles.
+pub mod gilbert;
 /// Gray-code based traversal over a hyper-rectangular grid.
 pub mod gray;
 /// Hairy Onion: tiled 2D onion spirals connected in higher dimensions.
+#[cfg(feature = "experimental-curves")]
 pub mod hairyonion;
 /// H-curve: a Hilbert-like family using BRGC and orientation transforms.
 pub mod hcurve;
+/// Internal 2D H-curve helpers.
+mod hcurve2;
 /// Classic Hilbert curves and utilities.
 pub mod hilbert;
 /// Internal 2D Hilbert helpers.
@@ -15,8 +22,13 @@ mod hilbert_common;
 /// Internal N-D Hilbert helpers.
 mod hilbertn;
 /// Onion curve family operating on L∞ shells (single consolidated module).
+#[cfg(feature = "experimental-curves")]
 pub mod onion;
 /// Simple serpentine scan (boustrophedon) traversal.
 pub mod scan;
+/// Keyed pseudo-random permutation of the grid, a locality baseline.
+pub mod shuffle;
+/// Sierpiński curve built from recursively bisected triangles.
+pub mod sierpinski;
 /// Z-order (Morton) bit-interleaving.
 pub mod zorder;
@@ -1,3 +1,4 @@
+use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
 /// The Onion Curve is a space-filling curve named after the core concept of "peeling" an
 /// N-dimensional hypercube layer by layer, like an onion.
 ///
@@ -18,7 +19,8 @@
 /// The outer shell has 26 cells (even). The center cell is White, hence the shell
 /// must end on White; any continuous traversal into the next shell would need to
 /// enter a Black cell, contradiction.
-use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
 
 /// Onion curve operating on L∞ shells in N‑D.
 #[derive(Debug)]
@@ -28,19 +30,16 @@ pub struct OnionCurve {
     /// Side length per dimension.
     side_length: u32,
     /// Total number of points (L^N).
-    length: u32,
+    length: u64,
 }
 
 impl OnionCurve {
     /// Construct a new Onion curve for `dimensions` and `side_length`.
+    ///
+    /// The shell arithmetic works in `u32`, so `L^N` must fit in a `u32`.
     pub fn new(dimensions: u32, side_length: u32) -> error::Result<Self> {
         let spec = GridSpec::new(dimensions, side_length)?;
-        // Special-case overflow guard retained for L=2 where 2^N grows quickly.
-        if side_length == 2 && dimensions > 31 {
-            return Err(error::Error::Size(
-                "For L=2, dimensions must be <= 31 (2^N must fit in u32)".to_string(),
-            ));
-        }
+        spec.require_length_le(u64::from(u32::MAX))?;
 
         Ok(Self {
             dimensions: spec.dimension(),
@@ -48,6 +47,34 @@ impl OnionCurve {
             length: spec.length(),
         })
     }
+
+    /// Number of L∞ shells (layers) the curve peels, `ceil(L / 2)`.
+    pub fn num_layers(&self) -> u32 {
+        self.side_length.div_ceil(2)
+    }
+
+    /// Layer containing the point at curve `index`, counted from the outside
+    /// (0 is the outermost shell).
+    ///
+    /// Layers are visited in order, so this never decreases along the curve
+    /// and agrees with [`shell_level`] of the point.
+    pub fn layer_of(&self, index: u64) -> u32 {
+        debug_assert!(index < self.length, "index out of bounds");
+        let index = (index % self.length) as u32;
+        shell_for_index(self.dimensions, self.side_length, index).level
+    }
+
+    /// Curve indices covering layer `k`, or an empty range at the end of the
+    /// curve when `k` is not below [`Self::num_layers`].
+    pub fn layer_range(&self, k: u32) -> Range<u64> {
+        let mut side = self.side_length;
+        let mut start = 0;
+        for _ in 0..k.min(self.num_layers()) {
+            start += u64::from(shell_size(self.dimensions, side));
+            side = side.saturating_sub(2);
+        }
+        start..start + u64::from(shell_size(self.dimensions, side))
+    }
 }
 
 impl SpaceCurve for OnionCurve {
@@ -59,15 +86,18 @@ impl SpaceCurve for OnionCurve {
         "Peels L∞ layers. L=2 uses Gray-code generalisation (continuous); N>2,L>2 is discontinuous."
     }
 
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimensions
     }
 
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
 
-    fn index(&self, p: &Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &Point) -> u64 {
         debug_assert_eq!(
             p.len(),
             self.dimensions as usize,
@@ -77,16 +107,31 @@ impl SpaceCurve for OnionCurve {
             p.iter().all(|&c| c < self.side_length),
             "point coordinate out of bounds"
         );
-        onion_index_nd(self.dimensions, self.side_length, p)
+        u64::from(onion_index_nd(self.dimensions, self.side_length, p))
     }
 
-    fn point(&self, index: u32) -> Point {
+    #[inline]
+    fn point(&self, index: u64) -> Point {
         debug_assert!(index < self.length, "index out of bounds");
-        let coords = onion_point_nd(self.dimensions, self.side_length, index % self.length);
+        let index = (index % self.length) as u32;
+        let coords = onion_point_nd(self.dimensions, self.side_length, index);
         Point::new_with_dimension(self.dimensions, coords)
     }
 }
 
+/// Index of the L∞ shell containing `point` in a cube of side `side`,
+/// counted from the outside (0 is the outermost shell).
+///
+/// The onion traversal visits shells in this order, so shell levels never
+/// decrease along the curve.
+pub fn shell_level(side: u32, point: &[u32]) -> u32 {
+    point
+        .iter()
+        .map(|&c| c.min(side - 1 - c))
+        .min()
+        .unwrap_or(0)
+}
+
 /// Describes a single L∞ shell within the onion traversal.
 #[derive(Clone, Copy, Debug)]
 struct Shell {
@@ -139,11 +184,7 @@ fn shell_for_index(dimension: u32, side: u32, mut index: u32) -> Shell {
 
 /// Locate the shell and offset for a given point.
 fn shell_for_point(dimension: u32, side: u32, point: &[u32]) -> Shell {
-    let level = point
-        .iter()
-        .map(|&c| c.min(side - 1 - c))
-        .min()
-        .unwrap_or(0);
+    let level = shell_level(side, point);
     let mut side_at_level = side;
     let mut offset = 0;
     for _ in 0..level {
@@ -861,6 +902,9 @@ mod tests {
         assert!(OnionCurve::new(2, 0).is_err());
         // N==0 rejected
         assert!(OnionCurve::new(0, 4).is_err());
+        // L^N beyond u32 rejected
+        assert!(OnionCurve::new(2, 1 << 16).is_err());
+        assert!(OnionCurve::new(32, 2).is_err());
         // Valid small shapes
         let c = OnionCurve::new(2, 3).unwrap();
         assert_eq!(c.length(), 9);
@@ -882,4 +926,39 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn shell_levels_never_decrease() {
+        for dim in 2..=3 {
+            let curve = OnionCurve::new(dim, 7).unwrap();
+            let levels: Vec<u32> = (0..curve.length())
+                .map(|i| shell_level(7, &curve.point(i)))
+                .collect();
+            assert!(levels.windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(levels.first(), Some(&0));
+            assert_eq!(levels.last(), Some(&3));
+        }
+    }
+
+    #[test]
+    fn layer_ranges_tile_the_curve() {
+        for (dim, size) in [(2, 1), (2, 6), (2, 7), (3, 5), (4, 4)] {
+            let curve = OnionCurve::new(dim, size).unwrap();
+            let layers = curve.num_layers();
+            let mut next = 0;
+            for k in 0..layers {
+                let range = curve.layer_range(k);
+                assert_eq!(range.start, next);
+                assert!(!range.is_empty());
+                for idx in range.clone() {
+                    assert_eq!(curve.layer_of(idx), k);
+                    assert_eq!(shell_level(size, &curve.point(idx)), k);
+                }
+                next = range.end;
+            }
+            assert_eq!(next, curve.length());
+            assert!(curve.layer_range(layers).is_empty());
+            assert_eq!(curve.layer_range(layers + 5).start, curve.length());
+        }
+    }
 }
@@ -1,6 +1,6 @@
-use std::iter::Iterator;
+use core::iter::Iterator;
 
-use smallvec::smallvec;
+use smallvec::{SmallVec, smallvec};
 
 use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
 
@@ -9,19 +9,29 @@ use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
 pub struct Scan {
     /// Number of dimensions in the grid.
     dimension: u32,
-    /// Side length per dimension.
-    size: u32,
+    /// Side length along each axis.
+    extents: SmallVec<[u32; 8]>,
     /// Cached total number of points in the scan.
-    length: u32,
+    length: u64,
 }
 
 impl Scan {
     /// Construct a `Scan` curve for the given dimensions and side length.
     pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
-        let spec = GridSpec::new(dimension, size)?;
+        Self::from_spec(&GridSpec::new(dimension, size)?)
+    }
+
+    /// Construct a `Scan` curve over a grid with its own side length on each
+    /// axis.
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::from_extents(extents)?)
+    }
+
+    /// Construct a `Scan` curve over a validated grid.
+    fn from_spec(spec: &GridSpec) -> error::Result<Self> {
         Ok(Self {
             dimension: spec.dimension(),
-            size: spec.size(),
+            extents: spec.extents().into(),
             length: spec.length(),
         })
     }
@@ -37,33 +47,44 @@ impl SpaceCurve for Scan {
         Continuous with minimal turning, but locality drops at row boundaries.\n\
         Useful as a simple, predictable baseline traversal."
     }
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimension
     }
 
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+
     /// Convert a 1D index into N-dimensional coordinates.
     ///
     /// The scan performs a boustrophedon (ox-turning) traversal. This means
     /// every other row/plane is traversed in reverse order to maintain
     /// continuity between lines.
-    fn point(&self, index: u32) -> Point {
+    #[inline]
+    fn point(&self, index: u64) -> Point {
         debug_assert!(index < self.length, "index out of bounds");
         // Tracks whether the current dimension should be traversed in reverse.
         let mut should_reverse_direction = false;
         let mut coordinates = smallvec![0; self.dimension as usize];
         let mut remaining_index = index;
+        // Points per step along the current axis: the product of the extents
+        // below it.
+        let mut stride = self.length;
 
         // Iterate dimensions from highest to lowest (e.g., Z -> Y -> X)
         for dim_idx in (0..self.dimension).rev() {
-            let stride = self.size.pow(dim_idx);
-            let raw_coordinate = remaining_index / stride;
+            let extent = self.extents[dim_idx as usize];
+            stride /= u64::from(extent);
+            let raw_coordinate = (remaining_index / stride) as u32;
 
             // If we are in a reversed section, invert the coordinate
             coordinates[dim_idx as usize] = if should_reverse_direction {
-                self.size - raw_coordinate - 1
+                extent - raw_coordinate - 1
             } else {
                 raw_coordinate
             };
@@ -75,36 +96,38 @@ impl SpaceCurve for Scan {
                 should_reverse_direction = !should_reverse_direction;
             }
 
-            remaining_index -= raw_coordinate * stride;
+            remaining_index -= u64::from(raw_coordinate) * stride;
         }
         Point::new_with_dimension(self.dimension, coordinates)
     }
 
     /// Convert N-dimensional coordinates into a 1D index.
-    fn index(&self, point: &Point) -> u32 {
+    #[inline]
+    fn index(&self, point: &Point) -> u64 {
         debug_assert_eq!(
             point.len(),
             self.dimension as usize,
             "point dimension mismatch"
         );
         debug_assert!(
-            point.iter().all(|&c| c < self.size),
+            point.iter().zip(&self.extents).all(|(c, e)| c < e),
             "point coordinate out of bounds"
         );
         let mut should_reverse_direction = false;
         let mut index_accumulator = 0;
+        let mut stride = self.length;
 
         // Iterate dimensions from highest to lowest to reconstruct the index
-        for (dim_idx, &coordinate) in point.iter().enumerate().rev() {
-            let stride = self.size.pow(dim_idx as u32);
+        for (&coordinate, &extent) in point.iter().zip(&self.extents).rev() {
+            stride /= u64::from(extent);
 
             let actual_value = if should_reverse_direction {
-                self.size - coordinate - 1
+                extent - coordinate - 1
             } else {
                 coordinate
             };
 
-            index_accumulator += actual_value * stride;
+            index_accumulator += u64::from(actual_value) * stride;
 
             // Update direction flip state for the next dimension
             if coordinate % 2 != 0 {
@@ -113,10 +136,36 @@ impl SpaceCurve for Scan {
         }
         index_accumulator
     }
+
+    /// Step to the next point without decoding the index.
+    ///
+    /// Each axis runs backwards when the coordinates above it sum to an odd
+    /// number; the lowest axis that can still move in its direction takes
+    /// the step.
+    #[inline]
+    fn advance_point(&self, index: u64, point: &mut Point) {
+        debug_assert!(index + 1 < self.length, "index out of bounds");
+        let total = point.iter().fold(0, |parity, &c| parity ^ (c & 1));
+        let mut below = 0;
+        for (coordinate, &extent) in point.0.iter_mut().zip(&self.extents) {
+            below ^= *coordinate & 1;
+            if total ^ below == 1 {
+                if *coordinate > 0 {
+                    *coordinate -= 1;
+                    return;
+                }
+            } else if *coordinate + 1 < extent {
+                *coordinate += 1;
+                return;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::{vec, vec::Vec};
+
     use super::*;
 
     #[test]
@@ -160,8 +209,28 @@ mod tests {
             vec![2, 2],
         ];
         for (idx, coords) in expected.iter().enumerate() {
-            assert_eq!(Vec::<u32>::from(s.point(idx as u32)), *coords);
-            assert_eq!(s.index(&Point::new(coords.clone())), idx as u32);
+            assert_eq!(Vec::<u32>::from(s.point(idx as u64)), *coords);
+            assert_eq!(s.index(&Point::new(coords.clone())), idx as u64);
+        }
+    }
+
+    #[test]
+    fn rectangular_grids_snake() {
+        let s = Scan::from_extents(&[3, 2]).unwrap();
+        assert_eq!(s.extents().as_slice(), [3, 2]);
+        let expected = [[0, 0], [1, 0], [2, 0], [2, 1], [1, 1], [0, 1]];
+        for (idx, coords) in expected.iter().enumerate() {
+            assert_eq!(s.point(idx as u64).as_slice(), coords);
+        }
+
+        let s = Scan::from_extents(&[4, 3, 2]).unwrap();
+        let mut point = s.point(0);
+        for idx in 0..s.length() {
+            assert_eq!(point, s.point(idx));
+            assert_eq!(s.index(&point), idx);
+            if idx + 1 < s.length() {
+                s.advance_point(idx, &mut point);
+            }
         }
     }
 
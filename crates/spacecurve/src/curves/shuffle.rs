@@ -0,0 +1,226 @@
+use smallvec::{SmallVec, smallvec};
+
+use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
+
+/// Key used by [`Shuffle`] constructors unless [`Shuffle::with_key`] picks
+/// another.
+pub const DEFAULT_KEY: u64 = 0x5eed_cafe_f00d_d00d;
+
+/// Feistel rounds applied per encryption; six rounds make the permutation
+/// look random to the locality metrics.
+const ROUNDS: u64 = 6;
+
+/// Keyed pseudo-random permutation of the cells of an N‑D grid.
+///
+/// The curve index is encrypted with a small-domain Feistel cipher and the
+/// result read as a row-major cell number. Indices the cipher maps outside
+/// the grid are encrypted again (cycle walking) until they land inside, so
+/// the mapping is a bijection for any grid. It has no locality at all, which
+/// makes it a baseline for the other curves.
+#[derive(Debug)]
+pub struct Shuffle {
+    /// Number of dimensions in the grid.
+    dimension: u32,
+    /// Side length along each axis.
+    extents: SmallVec<[u32; 8]>,
+    /// Total number of points.
+    length: u64,
+    /// Bits in each half of the cipher block.
+    half_bits: u32,
+    /// Cipher key.
+    key: u64,
+}
+
+impl Shuffle {
+    /// Construct a `Shuffle` curve for the given dimensions and side length.
+    pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::new(dimension, size)?)
+    }
+
+    /// Construct a `Shuffle` curve over a grid with its own side length on
+    /// each axis.
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::from_extents(extents)?)
+    }
+
+    /// Construct a `Shuffle` curve over a validated grid.
+    fn from_spec(spec: &GridSpec) -> error::Result<Self> {
+        let bits = u64::BITS - (spec.length() - 1).leading_zeros();
+        Ok(Self {
+            dimension: spec.dimension(),
+            extents: spec.extents().into(),
+            length: spec.length(),
+            half_bits: bits.div_ceil(2),
+            key: DEFAULT_KEY,
+        })
+    }
+
+    /// Use `key` for the permutation; each key gives a different order.
+    pub fn with_key(mut self, key: u64) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Key of the permutation.
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    /// Mask selecting one half of the cipher block.
+    fn half_mask(&self) -> u64 {
+        if self.half_bits == 0 {
+            0
+        } else {
+            u64::MAX >> (u64::BITS - self.half_bits)
+        }
+    }
+
+    /// Round function of round `round` applied to half-block `half`.
+    fn round(&self, round: u64, half: u64) -> u64 {
+        let mut z = self.key ^ half ^ round.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)) & self.half_mask()
+    }
+
+    /// One pass of the Feistel cipher over the full block.
+    fn encrypt(&self, block: u64) -> u64 {
+        let mask = self.half_mask();
+        let (mut left, mut right) = ((block >> self.half_bits) & mask, block & mask);
+        for round in 0..ROUNDS {
+            (left, right) = (right, left ^ self.round(round, right));
+        }
+        (left << self.half_bits) | right
+    }
+
+    /// Inverse of [`Self::encrypt`].
+    fn decrypt(&self, block: u64) -> u64 {
+        let mask = self.half_mask();
+        let (mut left, mut right) = ((block >> self.half_bits) & mask, block & mask);
+        for round in (0..ROUNDS).rev() {
+            (left, right) = (right ^ self.round(round, left), left);
+        }
+        (left << self.half_bits) | right
+    }
+
+    /// Apply `step` until the value falls inside the grid.
+    ///
+    /// The block holds fewer than four times as many values as the grid, so
+    /// this takes a few steps on average.
+    fn walk(&self, mut value: u64, step: impl Fn(u64) -> u64) -> u64 {
+        loop {
+            value = step(value);
+            if value < self.length {
+                return value;
+            }
+        }
+    }
+}
+
+impl SpaceCurve for Shuffle {
+    fn name(&self) -> &'static str {
+        "Shuffle"
+    }
+
+    fn info(&self) -> &'static str {
+        "Keyed pseudo-random permutation of the grid cells (Feistel cipher with cycle walking).\n\
+        Deterministic for a given key, with no locality at all.\n\
+        Useful as a worst-case baseline when comparing curves."
+    }
+
+    #[inline]
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    #[inline]
+    fn dimensions(&self) -> u32 {
+        self.dimension
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+
+    fn point(&self, index: u64) -> Point {
+        debug_assert!(index < self.length, "index out of bounds");
+        let mut cell = self.walk(index, |v| self.encrypt(v));
+        let mut coordinates = smallvec![0; self.dimension as usize];
+        for (coordinate, &extent) in coordinates.iter_mut().zip(&self.extents) {
+            *coordinate = (cell % u64::from(extent)) as u32;
+            cell /= u64::from(extent);
+        }
+        Point::new_with_dimension(self.dimension, coordinates)
+    }
+
+    fn index(&self, point: &Point) -> u64 {
+        debug_assert_eq!(
+            point.len(),
+            self.dimension as usize,
+            "point dimension mismatch"
+        );
+        debug_assert!(
+            point.iter().zip(&self.extents).all(|(c, e)| c < e),
+            "point coordinate out of bounds"
+        );
+        let cell = point
+            .iter()
+            .zip(&self.extents)
+            .rev()
+            .fold(0, |acc, (&c, &extent)| {
+                acc * u64::from(extent) + u64::from(c)
+            });
+        self.walk(cell, |v| self.decrypt(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn permutes_every_grid() {
+        for extents in [
+            &[1][..],
+            &[2, 2],
+            &[5, 3],
+            &[7, 7],
+            &[4, 3, 5],
+            &[2, 2, 2, 2, 2],
+        ] {
+            let curve = Shuffle::from_extents(extents).unwrap();
+            let mut seen = alloc::vec![false; curve.length() as usize];
+            for idx in 0..curve.length() {
+                let p = curve.point(idx);
+                assert_eq!(curve.index(&p), idx, "{extents:?} idx {idx}");
+                let cell = p
+                    .iter()
+                    .zip(extents)
+                    .rev()
+                    .fold(0, |acc, (&c, &e)| acc * e + c) as usize;
+                assert!(!seen[cell], "{extents:?} visits {p:?} twice");
+                seen[cell] = true;
+            }
+        }
+    }
+
+    #[test]
+    fn keys_change_the_order() {
+        let order = |key| {
+            let curve = Shuffle::from_dimensions(2, 16).unwrap().with_key(key);
+            (0..curve.length())
+                .map(|i| curve.point(i))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(order(1), order(1));
+        assert_ne!(order(1), order(2));
+        let scan: Vec<_> = (0..256u32).collect();
+        let shuffled: Vec<u32> = order(DEFAULT_KEY)
+            .iter()
+            .map(|p| p[1] * 16 + p[0])
+            .collect();
+        assert_ne!(shuffled, scan);
+    }
+}
@@ -0,0 +1,247 @@
+use alloc::string::ToString;
+
+use smallvec::smallvec;
+
+use crate::{error, point::Point, spacecurve::SpaceCurve, spec::GridSpec};
+
+/// Sierpiński (Knopp) curve over a square `2^order` grid.
+///
+/// The square is cut along its diagonal into two right isosceles triangles,
+/// and each triangle is bisected recursively through the midpoint of its
+/// hypotenuse, entering at one acute corner and leaving at the other. After
+/// `2 · order` cuts every triangle has the area of one cell and straddles two;
+/// it is assigned to the cell it covers on its exit side, which visits every
+/// cell exactly once. Consecutive cells share an edge or a corner, so about a
+/// third of the steps are diagonal, and the curve closes into a loop.
+#[derive(Debug)]
+pub struct Sierpinski {
+    /// Side length of the grid, a power of two.
+    size: u32,
+    /// Number of bisections below the two root triangles (`2 · order - 1`).
+    depth: u32,
+    /// Cached total number of points (`size²`).
+    length: u64,
+}
+
+impl Sierpinski {
+    /// Construct a curve covering a square grid of side `size`, which must be
+    /// a power of two. Only two dimensions are supported.
+    pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
+        if dimension != 2 {
+            return Err(error::Error::Shape(
+                "Sierpiński requires dimension 2".to_string(),
+            ));
+        }
+        let spec = GridSpec::power_of_two(dimension, size)?;
+        spec.require_index_bits_lt(64)?;
+        Ok(Self {
+            size,
+            depth: (2 * spec.order().unwrap()).saturating_sub(1),
+            length: spec.length(),
+        })
+    }
+
+    /// One of the two halves of the square, in traversal order, with corners
+    /// scaled by `scale`.
+    fn root(&self, second: bool, scale: i64) -> Triangle {
+        let n = i64::from(self.size) * scale;
+        if second {
+            Triangle::new([n, n], [0, n], [0, 0])
+        } else {
+            Triangle::new([0, 0], [n, 0], [n, n])
+        }
+    }
+}
+
+/// A right isosceles triangle traversed from corner `a` to corner `c`, with
+/// its right angle at `b`.
+#[derive(Debug, Clone, Copy)]
+struct Triangle {
+    /// Entry corner.
+    a: [i64; 2],
+    /// Right-angle corner.
+    b: [i64; 2],
+    /// Exit corner.
+    c: [i64; 2],
+}
+
+impl Triangle {
+    /// A triangle from its corners.
+    fn new(a: [i64; 2], b: [i64; 2], c: [i64; 2]) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Midpoint of the hypotenuse.
+    fn midpoint(&self) -> [i64; 2] {
+        [(self.a[0] + self.c[0]) / 2, (self.a[1] + self.c[1]) / 2]
+    }
+
+    /// The first or second half of the triangle, cut through the midpoint of
+    /// its hypotenuse.
+    fn half(&self, second: bool) -> Self {
+        let m = self.midpoint();
+        if second {
+            Self::new(self.b, m, self.c)
+        } else {
+            Self::new(self.a, m, self.b)
+        }
+    }
+
+    /// Whether `p` lies on the second half's side of the cut from `b` to the
+    /// hypotenuse midpoint.
+    fn in_second_half(&self, p: [i64; 2]) -> bool {
+        let m = self.midpoint();
+        // Lattice coordinates reach 2^33, so the cross product needs 128 bits.
+        let side = |q: [i64; 2]| {
+            i128::from(m[0] - self.b[0]) * i128::from(q[1] - self.b[1])
+                - i128::from(m[1] - self.b[1]) * i128::from(q[0] - self.b[0])
+        };
+        side(p).signum() != side(self.a).signum()
+    }
+
+    /// The cell a cell-sized triangle is assigned to: the one it covers next
+    /// to its exit corner.
+    fn cell(&self) -> [i64; 2] {
+        let m = self.midpoint();
+        let far = [self.c[0] + self.b[0] - m[0], self.c[1] + self.b[1] - m[1]];
+        [m[0].min(far[0]), m[1].min(far[1])]
+    }
+}
+
+impl SpaceCurve for Sierpinski {
+    fn name(&self) -> &'static str {
+        "Sierpiński"
+    }
+
+    fn info(&self) -> &'static str {
+        "Triangle-based curve that bisects right triangles recursively.\n\
+        Closes into a loop and keeps neighborhoods compact in every\n\
+        direction, at the cost of frequent diagonal steps."
+    }
+    #[inline]
+    fn length(&self) -> u64 {
+        self.length
+    }
+    #[inline]
+    fn dimensions(&self) -> u32 {
+        2
+    }
+    #[inline]
+    /// Every Sierpiński curve with more than one point closes into a loop.
+    fn is_cyclic(&self) -> bool {
+        self.length > 1
+    }
+
+    fn point(&self, index: u64) -> Point {
+        debug_assert!(index < self.length, "index out of bounds");
+        if self.size == 1 {
+            return Point::new_with_dimension(2, smallvec![0, 0]);
+        }
+        let mut triangle = self.root(index >> self.depth & 1 == 1, 1);
+        for bit in (0..self.depth).rev() {
+            triangle = triangle.half(index >> bit & 1 == 1);
+        }
+        let [x, y] = triangle.cell();
+        Point::new_with_dimension(2, smallvec![x as u32, y as u32])
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        debug_assert_eq!(p.len(), 2, "point dimension mismatch");
+        debug_assert!(
+            p[0] < self.size && p[1] < self.size,
+            "point coordinate out of bounds"
+        );
+        if self.size == 1 {
+            return 0;
+        }
+        // The cell is split between two triangles along one of its diagonals;
+        // probe a point in each half (on a 4× lattice, so no probe ever lies
+        // on a cut) and keep the triangle assigned to this cell.
+        let cell = [i64::from(p[0]), i64::from(p[1])];
+        for dx in [1, 3] {
+            let probe = [4 * cell[0] + dx, 4 * cell[1] + 2];
+            let upper = probe[0] < probe[1];
+            let mut triangle = self.root(upper, 4);
+            let mut index = u64::from(upper);
+            for _ in 0..self.depth {
+                let second = triangle.in_second_half(probe);
+                triangle = triangle.half(second);
+                index = index << 1 | u64::from(second);
+            }
+            if triangle.cell().map(|v| v / 4) == cell {
+                return index;
+            }
+        }
+        unreachable!("every cell is assigned to a triangle covering half of it")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn single_cell_is_not_cyclic() {
+        assert!(!Sierpinski::from_dimensions(2, 1).unwrap().is_cyclic());
+        assert!(Sierpinski::from_dimensions(2, 2).unwrap().is_cyclic());
+    }
+
+    #[test]
+    fn covers_grids_with_king_steps() {
+        for order in 0..=5 {
+            let size = 1 << order;
+            let curve = Sierpinski::from_dimensions(2, size).unwrap();
+            let mut seen = vec![false; curve.length() as usize];
+            let mut previous: Option<Point> = None;
+            for i in 0..curve.length() {
+                let p = curve.point(i);
+                assert!(p[0] < size && p[1] < size, "{size} at {i}");
+                assert_eq!(curve.index(&p), i, "{size} roundtrip at {i}");
+                seen[(p[1] * size + p[0]) as usize] = true;
+                if let Some(prev) = previous {
+                    let step = prev[0].abs_diff(p[0]).max(prev[1].abs_diff(p[1]));
+                    assert_eq!(step, 1, "{size} jumps at {i}");
+                }
+                previous = Some(p);
+            }
+            assert!(seen.iter().all(|&s| s), "{size} misses points");
+        }
+    }
+
+    #[test]
+    fn small_grid_order() {
+        let curve = Sierpinski::from_dimensions(2, 4).unwrap();
+        let points: Vec<Vec<u32>> = (0..6).map(|i| curve.point(i).to_vec()).collect();
+        assert_eq!(
+            points,
+            vec![
+                vec![1, 0],
+                vec![1, 1],
+                vec![2, 0],
+                vec![3, 0],
+                vec![3, 1],
+                vec![2, 1]
+            ]
+        );
+        assert_eq!(curve.point(15).to_vec(), vec![0, 0]);
+    }
+
+    #[test]
+    fn roundtrips_large_grids() {
+        let curve = Sierpinski::from_dimensions(2, 1 << 31).unwrap();
+        assert_eq!(curve.length(), 1 << 62);
+        for i in [0, 1 << 32, (1 << 61) + 99, curve.length() - 1] {
+            let p = curve.point(i);
+            assert_eq!(curve.index(&p), i, "at {i}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_shapes() {
+        assert!(Sierpinski::from_dimensions(3, 4).is_err());
+        assert!(Sierpinski::from_dimensions(2, 6).is_err());
+        assert!(Sierpinski::from_dimensions(2, 1).is_ok());
+    }
+}
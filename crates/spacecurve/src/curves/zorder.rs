@@ -1,15 +1,86 @@
-use crate::{error, ops, point, spacecurve::SpaceCurve, spec::GridSpec};
+use alloc::{format, string::ToString, vec::Vec};
+use core::{array, ops::Range};
+
+use smallvec::{SmallVec, smallvec};
+
+use crate::{error, ops, point, query, spacecurve::SpaceCurve, spec::GridSpec};
+
+/// Which axis fills each slot of an interleaved bit group, and which end of
+/// each coordinate is most significant, in a Morton code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitOrder {
+    /// Axes from the lowest slot of each bit group to the highest; the
+    /// standard order is `0, 1, …`, so x varies fastest.
+    pub axes: SmallVec<[u32; 8]>,
+    /// Whether each coordinate's bits are read most-significant first, so its
+    /// high bit varies fastest along the curve.
+    pub reverse_bits: bool,
+}
+
+impl BitOrder {
+    /// The standard Morton order in `dimension` dimensions.
+    pub fn standard(dimension: u32) -> Self {
+        Self {
+            axes: (0..dimension).collect(),
+            reverse_bits: false,
+        }
+    }
+
+    /// Axis priority reversed, so the last axis varies fastest: y-major
+    /// (column-major) order in 2D.
+    pub fn reversed_axes(dimension: u32) -> Self {
+        Self {
+            axes: (0..dimension).rev().collect(),
+            reverse_bits: false,
+        }
+    }
+
+    /// Standard axis priority with each coordinate's bits reversed.
+    pub fn reversed_bits(dimension: u32) -> Self {
+        Self {
+            reverse_bits: true,
+            ..Self::standard(dimension)
+        }
+    }
+
+    /// Whether this is the standard order.
+    pub fn is_standard(&self) -> bool {
+        !self.reverse_bits && self.axes.iter().copied().eq(0..self.axes.len() as u32)
+    }
+}
+
+/// A non-standard [`BitOrder`] with the per-axis bit widths it interleaves.
+#[derive(Debug)]
+struct Reordering {
+    /// The order itself.
+    order: BitOrder,
+    /// Bit width of the axis in each slot, `None` for hypercubes.
+    slot_bits: Option<SmallVec<[u32; 4]>>,
+}
 
 /// An implementation of the Z Order curve.
 #[derive(Debug)]
 pub struct ZOrder {
-    /// The bit width of each co-ordinate
+    /// The bit width of each co-ordinate; the widest axis on rectangular grids
     pub bitwidth: u32,
     /// The number of dimensions
     pub dimension: u32,
+    /// Bit width of each axis when they differ, `None` for hypercubes.
+    axis_bits: Option<SmallVec<[u32; 4]>>,
+    /// Custom bit order, `None` for the standard one.
+    reordering: Option<Reordering>,
     /// Cached total number of points (`2^(bitwidth * dimension)`), computed
     /// once at construction with checked math to avoid overflow.
-    length: u32,
+    length: u64,
+}
+
+/// The low `bits` bits of `value` in reverse order.
+fn reverse_low_bits(value: u32, bits: u32) -> u32 {
+    if bits == 0 {
+        0
+    } else {
+        value.reverse_bits() >> (32 - bits)
+    }
 }
 
 impl ZOrder {
@@ -17,20 +88,170 @@ impl ZOrder {
     /// number of dimensions, and a set size in each dimension. The size must be
     /// a number 2**n, where n is an integer, or the result is an error.
     pub fn from_dimensions(dimension: u32, size: u32) -> error::Result<Self> {
-        let spec = GridSpec::power_of_two(dimension, size)?;
-        spec.require_index_bits_lt(32)?;
-        let bitwidth = spec.bits_per_axis().unwrap();
+        Self::from_spec(&GridSpec::power_of_two(dimension, size)?)
+    }
+
+    /// Construct a Z Order curve over a box with its own power-of-two side
+    /// length on each axis.
+    ///
+    /// Bits are interleaved while every axis has them; once the narrower axes
+    /// run out, the remaining high bits of the wider axes follow in axis
+    /// order.
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        Self::from_spec(&GridSpec::power_of_two_extents(extents)?)
+    }
+
+    /// Construct a Z Order curve over a validated power-of-two grid.
+    fn from_spec(spec: &GridSpec) -> error::Result<Self> {
+        spec.require_index_bits_lt(64)?;
         Ok(Self {
             dimension: spec.dimension(),
-            bitwidth,
+            bitwidth: spec.bits_per_axis().unwrap(),
+            axis_bits: (!spec.is_square()).then(|| spec.axis_bits().unwrap()),
+            reordering: None,
             length: spec.length(),
         })
     }
+
+    /// Interleave bits in `order` instead of the standard Morton order, to
+    /// match keys produced by other systems.
+    ///
+    /// `order.axes` must list every axis exactly once.
+    pub fn with_bit_order(mut self, order: BitOrder) -> error::Result<Self> {
+        if order.axes.len() != self.dimension as usize {
+            return Err(error::Error::Shape(
+                "bit order needs one entry per axis".to_string(),
+            ));
+        }
+        let mut seen: SmallVec<[bool; 8]> = smallvec![false; self.dimension as usize];
+        for &axis in &order.axes {
+            match seen.get_mut(axis as usize) {
+                Some(slot) if !*slot => *slot = true,
+                _ => {
+                    return Err(error::Error::Shape(format!(
+                        "bit order axes {:?} are not a permutation of 0..{}",
+                        order.axes, self.dimension
+                    )));
+                }
+            }
+        }
+        self.reordering = (!order.is_standard()).then(|| Reordering {
+            slot_bits: self
+                .axis_bits
+                .as_ref()
+                .map(|bits| order.axes.iter().map(|&a| bits[a as usize]).collect()),
+            order,
+        });
+        Ok(self)
+    }
+
+    /// The bit order this curve interleaves with.
+    pub fn bit_order(&self) -> BitOrder {
+        self.reordering
+            .as_ref()
+            .map_or_else(|| BitOrder::standard(self.dimension), |r| r.order.clone())
+    }
+
+    /// Bit width of `axis`.
+    fn bits_of(&self, axis: u32) -> u32 {
+        self.axis_bits
+            .as_ref()
+            .map_or(self.bitwidth, |bits| bits[axis as usize])
+    }
+
+    /// Index of `coords` under a custom bit order.
+    fn reordered_index(&self, reordering: &Reordering, coords: &[u32]) -> u64 {
+        let slots: SmallVec<[u32; 8]> = reordering
+            .order
+            .axes
+            .iter()
+            .map(|&axis| {
+                let coord = coords[axis as usize];
+                if reordering.order.reverse_bits {
+                    reverse_low_bits(coord, self.bits_of(axis))
+                } else {
+                    coord
+                }
+            })
+            .collect();
+        match &reordering.slot_bits {
+            Some(bits) => ops::interleave_uneven(&slots, bits),
+            None => ops::interleave_lsb(&slots, self.bitwidth),
+        }
+    }
+
+    /// Coordinates at `index` under a custom bit order.
+    fn reordered_point(&self, reordering: &Reordering, index: u64) -> SmallVec<[u32; 8]> {
+        let slots = match &reordering.slot_bits {
+            Some(bits) => ops::deinterleave_uneven(bits, index),
+            None => ops::deinterleave_lsb(self.dimension, self.bitwidth, index),
+        };
+        let mut coords: SmallVec<[u32; 8]> = smallvec![0; self.dimension as usize];
+        for (&axis, &slot) in reordering.order.axes.iter().zip(&slots) {
+            coords[axis as usize] = if reordering.order.reverse_bits {
+                reverse_low_bits(slot, self.bits_of(axis))
+            } else {
+                slot
+            };
+        }
+        coords
+    }
+
+    /// Append the index of every point in `points` to `out`.
+    ///
+    /// Batch form of [`SpaceCurve::index`] for large datasets; `D` must equal
+    /// the curve's dimension.
+    pub fn index_arrays<const D: usize>(&self, points: &[[u32; D]], out: &mut Vec<u64>) {
+        debug_assert_eq!(D, self.dimension as usize, "point dimension mismatch");
+        if let Some(reordering) = &self.reordering {
+            out.extend(points.iter().map(|p| self.reordered_index(reordering, p)));
+            return;
+        }
+        match &self.axis_bits {
+            Some(bits) => out.extend(points.iter().map(|p| ops::interleave_uneven(p, bits))),
+            None => ops::interleave_many(points, self.bitwidth, out),
+        }
+    }
+
+    /// Append the point for every index in `indices` to `out`.
+    ///
+    /// Batch form of [`SpaceCurve::point`] for large datasets; `D` must equal
+    /// the curve's dimension.
+    pub fn points_into<const D: usize>(&self, indices: Range<u64>, out: &mut Vec<[u32; D]>) {
+        debug_assert_eq!(D, self.dimension as usize, "point dimension mismatch");
+        debug_assert!(indices.end <= self.length, "index out of range");
+        if let Some(reordering) = &self.reordering {
+            out.extend(indices.map(|i| {
+                let coords = self.reordered_point(reordering, i);
+                array::from_fn(|axis| coords[axis])
+            }));
+            return;
+        }
+        match &self.axis_bits {
+            Some(bits) => out.extend(indices.map(|i| {
+                let coords = ops::deinterleave_uneven(bits, i);
+                array::from_fn(|axis| coords[axis])
+            })),
+            None => ops::deinterleave_many(self.bitwidth, indices, out),
+        }
+    }
+
+    /// [`SpaceCurve::index_many`] for a curve of `D` dimensions.
+    fn index_points<const D: usize>(&self, points: &[point::Point], out: &mut Vec<u64>) {
+        out.extend(points.iter().map(|p| {
+            let coords: [u32; D] = array::from_fn(|axis| p[axis]);
+            ops::interleave_lsb_const(&coords, self.bitwidth)
+        }));
+    }
 }
 
 impl SpaceCurve for ZOrder {
     fn name(&self) -> &'static str {
-        "Z-order (Morton)"
+        if self.reordering.is_some() {
+            "Z-order (Morton, reordered bits)"
+        } else {
+            "Z-order (Morton)"
+        }
     }
 
     fn info(&self) -> &'static str {
@@ -38,48 +259,103 @@ impl SpaceCurve for ZOrder {
         Extremely fast and pairs well with quad/oct-trees, but preserves\n\
         neighborhood worse than Hilbert/H-curve and may exhibit long jumps."
     }
-    fn length(&self) -> u32 {
+    #[inline]
+    fn length(&self) -> u64 {
         self.length
     }
+    #[inline]
     fn dimensions(&self) -> u32 {
         self.dimension
     }
-    fn point(&self, index: u32) -> point::Point {
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        match &self.axis_bits {
+            Some(bits) => bits.iter().map(|&b| 1 << b).collect(),
+            None => smallvec![1 << self.bitwidth; self.dimension as usize],
+        }
+    }
+    #[inline]
+    fn point(&self, index: u64) -> point::Point {
         debug_assert!(index < self.length, "index out of range");
-        point::Point::new_with_dimension(
-            self.dimension,
-            ops::deinterleave_lsb(self.dimension, self.bitwidth, index),
-        )
+        let coords = match (&self.reordering, &self.axis_bits) {
+            (Some(reordering), _) => self.reordered_point(reordering, index),
+            (None, Some(bits)) => ops::deinterleave_uneven(bits, index),
+            (None, None) => ops::deinterleave_lsb(self.dimension, self.bitwidth, index),
+        };
+        point::Point::new_with_dimension(self.dimension, coords)
     }
-    fn index(&self, p: &point::Point) -> u32 {
+    #[inline]
+    fn index(&self, p: &point::Point) -> u64 {
         debug_assert_eq!(p.len(), self.dimension as usize, "point dimension mismatch");
-        let side = if self.bitwidth == 0 {
-            1
-        } else {
-            1u32 << self.bitwidth
-        };
         debug_assert!(
-            p.iter().all(|&coord| coord < side),
+            p.iter()
+                .zip(self.extents())
+                .all(|(&coord, side)| u64::from(coord) < u64::from(side)),
             "point coordinate out of bounds"
         );
-        ops::interleave_lsb(&p[..], self.bitwidth)
+        match (&self.reordering, &self.axis_bits) {
+            (Some(reordering), _) => self.reordered_index(reordering, p),
+            (None, Some(bits)) => ops::interleave_uneven(p, bits),
+            (None, None) => ops::interleave_lsb(&p[..], self.bitwidth),
+        }
+    }
+    fn index_many(&self, points: &[point::Point], out: &mut Vec<u64>) {
+        debug_assert!(
+            points.iter().all(|p| p.len() == self.dimension as usize),
+            "point dimension mismatch"
+        );
+        if self.reordering.is_some() || self.axis_bits.is_some() {
+            out.extend(points.iter().map(|p| self.index(p)));
+            return;
+        }
+        // Pick the fixed-width interleave once for the whole batch.
+        out.reserve(points.len());
+        match self.dimension {
+            2 => self.index_points::<2>(points, out),
+            3 => self.index_points::<3>(points, out),
+            4 => self.index_points::<4>(points, out),
+            _ => out.extend(points.iter().map(|p| ops::interleave_lsb(p, self.bitwidth))),
+        }
+    }
+    /// Subdivides aligned blocks on hypercubes, whatever the axis order;
+    /// rectangular grids, whose blocks are not cubes, and reversed bits,
+    /// which scatter blocks, fall back to a scan.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        let reversed = self
+            .reordering
+            .as_ref()
+            .is_some_and(|r| r.order.reverse_bits);
+        if self.axis_bits.is_some() || reversed {
+            return query::scan_ranges(self, lo, hi);
+        }
+        query::block_ranges(self, self.bitwidth, lo, hi)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
     use super::*;
+    use crate::point::Point;
 
     #[test]
     fn from_dimensions_guard() {
-        // 2 * 16 = 32 bits total → reject
-        assert!(ZOrder::from_dimensions(2, 1u32 << 16).is_err());
-        // 2 * 15 = 30 bits total → ok
-        assert!(ZOrder::from_dimensions(2, 1u32 << 15).is_ok());
-        // 4 * 8 = 32 bits total → reject
-        assert!(ZOrder::from_dimensions(4, 1u32 << 8).is_err());
-        // 4 * 7 = 28 bits total → ok
-        assert!(ZOrder::from_dimensions(4, 1u32 << 7).is_ok());
+        // 2 * 31 = 62 bits total → ok
+        assert!(ZOrder::from_dimensions(2, 1u32 << 31).is_ok());
+        // 4 * 16 = 64 bits total → reject
+        assert!(ZOrder::from_dimensions(4, 1u32 << 16).is_err());
+        // 4 * 15 = 60 bits total → ok
+        assert!(ZOrder::from_dimensions(4, 1u32 << 15).is_ok());
+    }
+
+    #[test]
+    fn roundtrip_beyond_u32_indices() {
+        let curve = ZOrder::from_dimensions(2, 1 << 24).unwrap();
+        for i in [1 << 32, (1 << 40) + 12345, curve.length() - 1] {
+            let point = curve.point(i);
+            assert_eq!(curve.index(&point), i);
+        }
+        assert_eq!(curve.point(1 << 47).as_slice(), [0, 1 << 23]);
     }
 
     #[test]
@@ -104,8 +380,29 @@ mod tests {
             vec![3, 3],
         ];
         for (idx, coords) in expected.iter().enumerate() {
-            assert_eq!(Vec::<u32>::from(curve.point(idx as u32)), *coords);
+            assert_eq!(Vec::<u32>::from(curve.point(idx as u64)), *coords);
+        }
+    }
+
+    #[test]
+    fn rectangular_grids_roundtrip() {
+        let curve = ZOrder::from_extents(&[8, 2]).unwrap();
+        assert_eq!(curve.length(), 16);
+        assert_eq!(curve.extents().as_slice(), [8, 2]);
+        // One bit from each axis, then the remaining bits of x.
+        assert_eq!(curve.point(3).as_slice(), [1, 1]);
+        assert_eq!(curve.point(4).as_slice(), [2, 0]);
+        for i in 0..curve.length() {
+            assert_eq!(curve.index(&curve.point(i)), i);
         }
+        let mut points = Vec::new();
+        curve.points_into::<2>(0..curve.length(), &mut points);
+        let mut indices = Vec::new();
+        curve.index_arrays(&points, &mut indices);
+        assert_eq!(indices, (0..curve.length()).collect::<Vec<_>>());
+
+        assert!(ZOrder::from_extents(&[8, 3]).is_err());
+        assert!(ZOrder::from_extents(&[1 << 31, 1 << 31, 4]).is_err());
     }
 
     #[test]
@@ -117,6 +414,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn batch_methods_match_scalar() {
+        let curve = ZOrder::from_dimensions(2, 8).unwrap();
+        let mut points = Vec::new();
+        curve.points_into::<2>(3..curve.length(), &mut points);
+        assert_eq!(points.len(), curve.length() as usize - 3);
+        for (i, p) in (3..).zip(&points) {
+            assert_eq!(Vec::<u32>::from(curve.point(i)), p.to_vec());
+        }
+        let mut indices = Vec::new();
+        curve.index_arrays(&points, &mut indices);
+        assert_eq!(indices, (3..curve.length()).collect::<Vec<_>>());
+    }
+
     #[test]
     fn roundtrip_dims_up_to_four() {
         for dim in 1..=4 {
@@ -131,4 +442,59 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn bit_orders_permute_the_key() {
+        let standard = ZOrder::from_dimensions(2, 4).unwrap();
+        let yx = ZOrder::from_dimensions(2, 4)
+            .unwrap()
+            .with_bit_order(BitOrder::reversed_axes(2))
+            .unwrap();
+        // Column-major: y takes the low bit of each pair.
+        assert_eq!(yx.point(1).as_slice(), [0, 1]);
+        assert_eq!(yx.point(2).as_slice(), [1, 0]);
+        for i in 0..16 {
+            let p = standard.point(i);
+            assert_eq!(yx.index(&Point::new(vec![p[1], p[0]])), i);
+        }
+
+        let reversed = ZOrder::from_dimensions(2, 4)
+            .unwrap()
+            .with_bit_order(BitOrder::reversed_bits(2))
+            .unwrap();
+        // The high bit of each coordinate now varies fastest.
+        assert_eq!(reversed.point(1).as_slice(), [2, 0]);
+        assert_eq!(reversed.point(4).as_slice(), [1, 0]);
+        assert!(!reversed.bit_order().is_standard());
+
+        let uneven = ZOrder::from_extents(&[8, 2])
+            .unwrap()
+            .with_bit_order(BitOrder {
+                axes: smallvec![1, 0],
+                reverse_bits: true,
+            })
+            .unwrap();
+        for curve in [&yx, &reversed, &uneven] {
+            for i in 0..curve.length() {
+                assert_eq!(curve.index(&curve.point(i)), i);
+            }
+            let mut points = Vec::new();
+            curve.points_into::<2>(0..curve.length(), &mut points);
+            let mut indices = Vec::new();
+            curve.index_arrays(&points, &mut indices);
+            assert_eq!(indices, (0..curve.length()).collect::<Vec<_>>());
+        }
+
+        let bad = |axes: SmallVec<[u32; 8]>| {
+            ZOrder::from_dimensions(2, 4)
+                .unwrap()
+                .with_bit_order(BitOrder {
+                    axes,
+                    reverse_bits: false,
+                })
+        };
+        assert!(bad(smallvec![0, 0]).is_err());
+        assert!(bad(smallvec![0, 2]).is_err());
+        assert!(bad(smallvec![0]).is_err());
+    }
 }
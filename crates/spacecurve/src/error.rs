@@ -1,6 +1,7 @@
 //! Error types for the `spacecurve` crate.
 
-use std::result::Result as StdResult;
+use alloc::string::String;
+use core::result::Result as StdResult;
 
 use thiserror::Error;
 
@@ -16,6 +17,9 @@ pub enum Error {
     /// Unknown pattern or identifier error.
     #[error("Unknown: {0}")]
     Unknown(String),
+    /// Malformed or unsupported encoded data.
+    #[error("Format error: {0}")]
+    Format(String),
     /// Other miscellaneous error.
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,212 @@
+//! Render geometry for frontends without a GUI toolkit or allocator.
+//!
+//! A [`Geometry`] walks the segments of a 2D curve that touch a [`Viewport`]
+//! and writes them into caller-provided slices: endpoints normalized so the
+//! viewport spans `0.0..=1.0` on each axis, and one adjacency flag per
+//! segment. The slices set the batch size, so an embedded display can draw a
+//! large curve through a small fixed buffer, one [`Geometry::fill`] at a time.
+//!
+//! [`Geometry`]: crate::geometry::Geometry
+//! [`Viewport`]: crate::geometry::Viewport
+//! [`Geometry::fill`]: crate::geometry::Geometry::fill
+
+use alloc::format;
+
+use crate::{
+    error,
+    segment::{Adjacency, Segment, Segments},
+    spacecurve::SpaceCurve,
+};
+
+/// Segment endpoints in viewport coordinates, start first.
+pub type Endpoints = [[f32; 2]; 2];
+
+/// Rectangle of grid cells mapped onto the unit square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// Lowest visible cell on each axis.
+    pub origin: [u32; 2],
+    /// Number of visible cells on each axis.
+    pub size: [u32; 2],
+}
+
+impl Viewport {
+    /// The `size` cells on each axis starting at `origin`.
+    pub const fn new(origin: [u32; 2], size: [u32; 2]) -> Self {
+        Self { origin, size }
+    }
+
+    /// The whole grid of a 2D `curve`.
+    pub fn full(curve: &dyn SpaceCurve) -> error::Result<Self> {
+        check_2d(curve)?;
+        let extents = curve.extents();
+        Ok(Self::new([0, 0], [extents[0], extents[1]]))
+    }
+
+    /// Position of the center of cell `p`, with the viewport spanning
+    /// `0.0..=1.0`. Cells outside the viewport map outside that range.
+    pub fn normalize(&self, p: [u32; 2]) -> [f32; 2] {
+        [0, 1]
+            .map(|axis| (p[axis] as f32 - self.origin[axis] as f32 + 0.5) / self.size[axis] as f32)
+    }
+
+    /// Whether the bounding box of the step from `a` to `b` overlaps the
+    /// viewport.
+    fn touches(&self, a: [u32; 2], b: [u32; 2]) -> bool {
+        (0..2).all(|axis| {
+            let end = u64::from(self.origin[axis]) + u64::from(self.size[axis]);
+            u64::from(a[axis].min(b[axis])) < end && a[axis].max(b[axis]) >= self.origin[axis]
+        })
+    }
+}
+
+/// Reject curves that are not 2D.
+fn check_2d(curve: &dyn SpaceCurve) -> error::Result<()> {
+    if curve.dimensions() != 2 {
+        return Err(error::Error::Shape(format!(
+            "render geometry needs a 2D curve, got {} dimensions",
+            curve.dimensions()
+        )));
+    }
+    Ok(())
+}
+
+/// Batched walk over the segments of a 2D curve that touch a viewport.
+pub struct Geometry<'a> {
+    /// Remaining segments of the curve.
+    segments: Segments<'a>,
+    /// Region the endpoints are normalized to.
+    viewport: Viewport,
+}
+
+impl<'a> Geometry<'a> {
+    /// Walk the segments of `curve` that touch `viewport`, classified with
+    /// [`Adjacency::UNIT`].
+    ///
+    /// `curve` must be 2D and `viewport` at least one cell on each axis.
+    pub fn new(curve: &'a dyn SpaceCurve, viewport: Viewport) -> error::Result<Self> {
+        check_2d(curve)?;
+        if viewport.size.contains(&0) {
+            return Err(error::Error::Size(format!(
+                "viewport must be at least 1 cell on each axis, got {:?}",
+                viewport.size
+            )));
+        }
+        Ok(Self {
+            segments: curve.segments(),
+            viewport,
+        })
+    }
+
+    /// Classify segments with `adjacency` instead of [`Adjacency::UNIT`].
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.segments = self.segments.with_adjacency(adjacency);
+        self
+    }
+
+    /// Region the endpoints are normalized to.
+    pub fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+
+    /// Write the next visible segments into `ends` and their adjacency flags
+    /// into `adjacent`, returning how many were written.
+    ///
+    /// At most the shorter slice's length is written; a return value of 0
+    /// means the curve is exhausted.
+    pub fn fill(&mut self, ends: &mut [Endpoints], adjacent: &mut [bool]) -> usize {
+        let capacity = ends.len().min(adjacent.len());
+        let mut written = 0;
+        while written < capacity {
+            let Some(Segment {
+                a,
+                b,
+                adjacent: adj,
+                ..
+            }) = self.segments.next()
+            else {
+                break;
+            };
+            let (a, b) = ([a[0], a[1]], [b[0], b[1]]);
+            if self.viewport.touches(a, b) {
+                ends[written] = [self.viewport.normalize(a), self.viewport.normalize(b)];
+                adjacent[written] = adj;
+                written += 1;
+            }
+        }
+        written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::curve_from_name;
+
+    /// Every visible segment, gathered in batches of `batch`.
+    fn collect(geometry: &mut Geometry<'_>, batch: usize) -> Vec<(Endpoints, bool)> {
+        let (mut ends, mut adjacent) =
+            (alloc::vec![[[0.0; 2]; 2]; batch], alloc::vec![false; batch]);
+        let mut out = Vec::new();
+        loop {
+            let n = geometry.fill(&mut ends, &mut adjacent);
+            if n == 0 {
+                return out;
+            }
+            out.extend(ends[..n].iter().copied().zip(adjacent[..n].iter().copied()));
+        }
+    }
+
+    #[test]
+    fn fills_the_whole_curve_in_batches() {
+        let curve = curve_from_name("zorder", 2, 4).unwrap();
+        let all = collect(
+            &mut Geometry::new(&*curve, Viewport::full(&*curve).unwrap()).unwrap(),
+            64,
+        );
+        assert_eq!(all.len(), 15);
+        assert_eq!(all[0].0, [[0.125, 0.125], [0.375, 0.125]]);
+        assert!(all[0].1);
+        assert!(!all[1].1);
+        assert!(
+            all.iter()
+                .flat_map(|(ends, _)| ends.iter().flatten())
+                .all(|&c| (0.0..=1.0).contains(&c))
+        );
+        let batched = collect(
+            &mut Geometry::new(&*curve, Viewport::full(&*curve).unwrap()).unwrap(),
+            4,
+        );
+        assert_eq!(batched, all);
+    }
+
+    #[test]
+    fn viewport_culls_segments() {
+        let curve = curve_from_name("hilbert", 2, 8).unwrap();
+        let viewport = Viewport::new([2, 2], [2, 2]);
+        let visible = collect(&mut Geometry::new(&*curve, viewport).unwrap(), 3);
+        let expected = curve
+            .segments()
+            .filter(|s| {
+                let inside = |p: &[u32]| p.iter().all(|c| (2..4).contains(c));
+                inside(&s.a) || inside(&s.b)
+            })
+            .count();
+        assert_eq!(visible.len(), expected);
+        assert_eq!(viewport.normalize([2, 3]), [0.25, 0.75]);
+        assert_eq!(viewport.normalize([0, 0]), [-0.75, -0.75]);
+    }
+
+    #[test]
+    fn rejects_other_dimensions_and_empty_viewports() {
+        let cube = curve_from_name("hilbert", 3, 4).unwrap();
+        assert!(Geometry::new(&*cube, Viewport::new([0, 0], [4, 4])).is_err());
+        assert!(Viewport::full(&*cube).is_err());
+        let line = curve_from_name("scan", 1, 8).unwrap();
+        assert!(Viewport::full(&*line).is_err());
+        let square = curve_from_name("hilbert", 2, 4).unwrap();
+        assert!(Geometry::new(&*square, Viewport::new([0, 0], [0, 4])).is_err());
+    }
+}
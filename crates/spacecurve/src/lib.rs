@@ -6,27 +6,88 @@
 //! # Supported Curves
 //!
 //! - Hilbert
-//! - Z-order (Morton)
+//! - Z-order (Morton), with column-major and reversed-bit variants
 //! - Gray Code
 //! - H-curve
+//! - Gilbert (generalized Hilbert for any 2D rectangle)
+//! - Sierpiński (triangle-based, 2D)
 //! - Scan (Boustrophedon)
+//! - Coil (planar spiral reversed on alternate planes)
+//! - Shuffle (keyed pseudo-random permutation, a locality baseline)
 //! - Onion / Hairy Onion (experimental)
+//!
+//! Scan, Coil, Shuffle, Z-order, Gray and Gilbert also cover rectangular grids
+//! with a different size per axis; see [`curve_from_sizes`].
+//!
+//! # `no_std`
+//!
+//! The curves only need `alloc`. Disabling the default `std` feature builds
+//! the crate as `no_std`, without the [`analysis`] and [`metrics`] modules.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::boxed::Box;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+/// Whole-curve locality metrics for comparing traversals.
+#[cfg(feature = "std")]
+pub mod analysis;
+/// Curves restricted to a rectangular part of their grid.
+pub mod clip;
+/// Curves made of several traversals walked one after another.
+pub mod concat;
 /// Implementations of specific space‑filling curves.
 pub mod curves;
 /// Error types used across the crate.
 pub mod error;
+/// Normalized segment geometry for rendering into fixed-size buffers.
+pub mod geometry;
+/// Reordering byte buffers along a curve.
+pub mod mapping;
+/// Runtime cost calibration and locality metrics for registered curves.
+#[cfg(feature = "std")]
+pub mod metrics;
+/// Grid neighbours of curve points.
+pub mod neighbor;
+/// Curves started part way along their traversal.
+pub mod offset;
 /// Internal bit operations shared by curve implementations.
 #[doc(hidden)]
 pub mod ops;
 /// N‑dimensional points and helpers.
 pub mod point;
+/// Index ranges covering axis-aligned boxes.
+pub mod query;
+/// Walking `image` crate buffers along curves.
+#[cfg(feature = "image")]
+pub mod raster;
+/// Segments between consecutive curve points and their adjacency.
+pub mod segment;
 /// The `SpaceCurve` trait and related utilities.
 mod spacecurve;
 /// Grid specification helpers shared across curves.
 pub mod spec;
+/// Compact point tables for caching precomputed curves.
+pub mod table;
+/// Curves derived from others by reversal, symmetry, translation or tiling.
+pub mod transform;
+/// Exhaustive validity checks for curve implementations.
+pub mod verify;
+/// Index arithmetic with wraparound at the curve length.
+pub mod wrap;
 
 pub use crate::spacecurve::SpaceCurve;
+// Concrete curve types, for generic code that wants static dispatch (see
+// [`registry::with_curve`]).
+pub use crate::curves::{
+    coil::Coil, gilbert::Gilbert, gray::Gray, hcurve::HCurve, hilbert::Hilbert, scan::Scan,
+    shuffle::Shuffle, sierpinski::Sierpinski, zorder::ZOrder,
+};
+#[cfg(feature = "experimental-curves")]
+pub use crate::curves::{hairyonion::HairyOnionCurve, onion::OnionCurve};
 
 /// Central registry of curve metadata and constructors.
 pub mod registry;
@@ -41,3 +102,48 @@ pub fn curve_from_name(
 ) -> error::Result<Box<dyn SpaceCurve + 'static>> {
     registry::construct(name, dimension, size)
 }
+
+/// Construct a curve by name over a grid with the given side length per axis.
+///
+/// `&[width, height]` requests a rectangle and `&[512, 256, 64]` a box. Scan
+/// takes any sizes, Z-order and Gray any powers of two, and Gilbert any 2D
+/// rectangle; curves that only cover square grids reject unequal sizes.
+pub fn curve_from_sizes(name: &str, sizes: &[u32]) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    registry::construct_spec(name, sizes)
+}
+
+/// Construct a curve by typed identifier with the requested dimensionality and size.
+///
+/// Returns an error if the combination is invalid for the curve.
+pub fn curve_from_id(
+    id: registry::CurveId,
+    dimension: u32,
+    size: u32,
+) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    registry::construct_id(id, dimension, size)
+}
+
+/// Construct a curve by name as a shared handle for use across threads.
+///
+/// Returns an error if the combination is invalid or the name is unknown.
+#[cfg(target_has_atomic = "ptr")]
+pub fn shared_curve_from_name(
+    name: &str,
+    dimension: u32,
+    size: u32,
+) -> error::Result<Arc<dyn SpaceCurve + 'static>> {
+    curve_from_name(name, dimension, size).map(Arc::from)
+}
+
+/// Construct a curve by typed identifier as a shared handle for use across
+/// threads.
+///
+/// Returns an error if the combination is invalid for the curve.
+#[cfg(target_has_atomic = "ptr")]
+pub fn shared_curve_from_id(
+    id: registry::CurveId,
+    dimension: u32,
+    size: u32,
+) -> error::Result<Arc<dyn SpaceCurve + 'static>> {
+    curve_from_id(id, dimension, size).map(Arc::from)
+}
@@ -0,0 +1,224 @@
+//! Reorder flat byte buffers along a curve.
+//!
+//! A buffer is read as a row-major grid over the curve's extents (first
+//! coordinate varying fastest) with one byte per cell, so rectangular grids
+//! and clipped curves work as well as cubes. [`permute_bytes`] emits the cells in curve order,
+//! and [`unpermute_bytes`] restores the original layout, which makes it easy
+//! to compare how well curve-ordered data compresses. [`index_grid`] gives
+//! the curve index of every cell in the same layout.
+//!
+//! [`permute_bytes`]: crate::mapping::permute_bytes
+//! [`unpermute_bytes`]: crate::mapping::unpermute_bytes
+//! [`index_grid`]: crate::mapping::index_grid
+
+use crate::{error, spacecurve::SpaceCurve};
+use alloc::{format, vec, vec::Vec};
+use smallvec::SmallVec;
+
+/// How to handle input whose length differs from the curve length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fit {
+    /// Require the input to cover the grid exactly.
+    #[default]
+    Exact,
+    /// Pad short input with the given byte; longer input is an error.
+    Pad(u8),
+    /// Drop bytes beyond the grid; shorter input is an error.
+    Truncate,
+}
+
+/// Reorder `data` from row-major grid order into curve order.
+///
+/// The result always has `curve.length()` bytes; `fit` decides how inputs of
+/// a different length are brought to that size.
+pub fn permute_bytes<C: SpaceCurve + ?Sized>(
+    curve: &C,
+    data: &[u8],
+    fit: Fit,
+) -> error::Result<Vec<u8>> {
+    let strides = strides(curve)?;
+    let length = curve.length() as usize;
+    let grid = match fit {
+        Fit::Exact if data.len() != length => {
+            return Err(error::Error::Size(format!(
+                "input has {} bytes but the curve covers {length}",
+                data.len()
+            )));
+        }
+        Fit::Pad(_) if data.len() > length => {
+            return Err(error::Error::Size(format!(
+                "input has {} bytes, more than the {length} the curve covers",
+                data.len()
+            )));
+        }
+        Fit::Truncate if data.len() < length => {
+            return Err(error::Error::Size(format!(
+                "input has {} bytes, fewer than the {length} the curve covers",
+                data.len()
+            )));
+        }
+        Fit::Pad(byte) => {
+            let mut grid = data.to_vec();
+            grid.resize(length, byte);
+            grid
+        }
+        Fit::Exact | Fit::Truncate => data[..length].to_vec(),
+    };
+
+    let mut permuted = Vec::with_capacity(length);
+    for_each_point(curve, |_, p| permuted.push(grid[scan_offset(p, &strides)]));
+    Ok(permuted)
+}
+
+/// Undo [`permute_bytes`], returning the first `len` bytes of the grid.
+///
+/// `permuted` must hold exactly `curve.length()` bytes; `len` is the original
+/// input length, so padding added by [`Fit::Pad`] is dropped again.
+pub fn unpermute_bytes<C: SpaceCurve + ?Sized>(
+    curve: &C,
+    permuted: &[u8],
+    len: usize,
+) -> error::Result<Vec<u8>> {
+    let strides = strides(curve)?;
+    let length = curve.length() as usize;
+    if permuted.len() != length {
+        return Err(error::Error::Size(format!(
+            "permuted data has {} bytes but the curve covers {length}",
+            permuted.len()
+        )));
+    }
+    if len > length {
+        return Err(error::Error::Size(format!(
+            "requested {len} bytes, more than the {length} the curve covers"
+        )));
+    }
+
+    let mut grid = vec![0u8; length];
+    for_each_point(curve, |i, p| {
+        grid[scan_offset(p, &strides)] = permuted[i as usize]
+    });
+    grid.truncate(len);
+    Ok(grid)
+}
+
+/// Curve index of every grid cell, in row-major order (first coordinate fastest).
+pub fn index_grid<C: SpaceCurve + ?Sized>(curve: &C) -> error::Result<Vec<u64>> {
+    let strides = strides(curve)?;
+    let mut grid = vec![0u64; curve.length() as usize];
+    for_each_point(curve, |i, p| grid[scan_offset(p, &strides)] = i);
+    Ok(grid)
+}
+
+/// Points decoded per batch by [`for_each_point`].
+const BATCH: u64 = 4096;
+
+/// Call `f` with every index of `curve` and its coordinates, in order.
+///
+/// Points are decoded in batches with [`SpaceCurve::points_range`], so curves
+/// with an incremental step avoid a full decode per point.
+pub(crate) fn for_each_point<C: SpaceCurve + ?Sized>(curve: &C, mut f: impl FnMut(u64, &[u32])) {
+    let mut batch = Vec::with_capacity(BATCH as usize);
+    for start in (0..curve.length()).step_by(BATCH as usize) {
+        batch.clear();
+        curve.points_range(start..start + BATCH, &mut batch);
+        for (i, p) in (start..).zip(&batch) {
+            f(i, p);
+        }
+    }
+}
+
+/// Row-major offset of `coords` given the `strides` of each axis.
+fn scan_offset(coords: &[u32], strides: &[usize]) -> usize {
+    coords
+        .iter()
+        .zip(strides)
+        .map(|(&c, &stride)| c as usize * stride)
+        .sum()
+}
+
+/// Row-major stride of each axis of the curve's extents, checking that the
+/// curve visits every cell of that grid.
+fn strides<C: SpaceCurve + ?Sized>(curve: &C) -> error::Result<SmallVec<[usize; 8]>> {
+    let extents = curve.extents();
+    let mut strides = SmallVec::with_capacity(extents.len());
+    let mut cells = 1u64;
+    for &extent in &extents {
+        strides.push(cells as usize);
+        cells = cells.saturating_mul(u64::from(extent));
+    }
+    if cells != curve.length() {
+        return Err(error::Error::Shape(format!(
+            "curve length {} does not cover its {extents:?} grid",
+            curve.length()
+        )));
+    }
+    Ok(strides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clip::Clipped, curve_from_name, curve_from_sizes};
+
+    #[test]
+    fn permutes_into_curve_order() {
+        let curve = curve_from_name("zorder", 2, 4).unwrap();
+        let data: Vec<u8> = (0..16).collect();
+        let permuted = permute_bytes(&*curve, &data, Fit::Exact).unwrap();
+        // Z-order visits (0,0), (1,0), (0,1), (1,1), (2,0), ...
+        assert_eq!(permuted[..5], [0, 1, 4, 5, 2]);
+        assert_eq!(unpermute_bytes(&*curve, &permuted, 16).unwrap(), data);
+    }
+
+    #[test]
+    fn padding_roundtrips_in_three_dimensions() {
+        let curve = curve_from_name("hilbert", 3, 4).unwrap();
+        let data: Vec<u8> = (0..50).map(|i| i * 3).collect();
+        let permuted = permute_bytes(&*curve, &data, Fit::Pad(0xff)).unwrap();
+        assert_eq!(permuted.len(), 64);
+        assert_eq!(permuted.iter().filter(|&&b| b == 0xff).count(), 14);
+        assert_eq!(unpermute_bytes(&*curve, &permuted, 50).unwrap(), data);
+    }
+
+    #[test]
+    fn index_grid_inverts_curve_order() {
+        let curve = curve_from_name("hilbert", 2, 8).unwrap();
+        let grid = index_grid(&*curve).unwrap();
+        for i in 0..curve.length() {
+            let p = curve.point(i);
+            assert_eq!(grid[(p[1] * 8 + p[0]) as usize], i);
+        }
+    }
+
+    #[test]
+    fn rectangles_and_clipped_curves_use_their_extents() {
+        let curve = curve_from_sizes("gilbert", &[6, 3]).unwrap();
+        let grid = index_grid(&*curve).unwrap();
+        for i in 0..curve.length() {
+            let p = curve.point(i);
+            assert_eq!(grid[(p[1] * 6 + p[0]) as usize], i);
+        }
+        let data: Vec<u8> = (0..18).collect();
+        let permuted = permute_bytes(&*curve, &data, Fit::Exact).unwrap();
+        assert_eq!(unpermute_bytes(&*curve, &permuted, 18).unwrap(), data);
+
+        let clipped = Clipped::new(curve_from_name("hilbert", 2, 8).unwrap(), &[5, 3]).unwrap();
+        let data: Vec<u8> = (0..15).collect();
+        let permuted = permute_bytes(&clipped, &data, Fit::Exact).unwrap();
+        assert_eq!(unpermute_bytes(&clipped, &permuted, 15).unwrap(), data);
+    }
+
+    #[test]
+    fn length_policies_are_enforced() {
+        let curve = curve_from_name("hilbert", 2, 4).unwrap();
+        let long = [7u8; 20];
+        assert!(permute_bytes(&*curve, &long[..15], Fit::Exact).is_err());
+        assert!(permute_bytes(&*curve, &long, Fit::Pad(0)).is_err());
+        assert!(permute_bytes(&*curve, &long[..15], Fit::Truncate).is_err());
+        assert_eq!(
+            permute_bytes(&*curve, &long, Fit::Truncate).unwrap(),
+            [7u8; 16]
+        );
+        assert!(unpermute_bytes(&*curve, &long[..16], 17).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Runtime cost calibration and locality metrics.
+//!
+//! The registry's `complexity` notes describe asymptotic costs; [`calibrate`]
+//! complements them with measured per-call latency of `point` and `index` at
+//! a handful of grid sizes. [`local_stretch`] measures how well a traversal
+//! keeps spatial neighbours close together in index order, and
+//! [`dilation_map`] applies it to every index of a curve. [`arc_distance`]
+//! and [`stretch`] compare a single pair of points.
+//!
+//! [`calibrate`]: crate::metrics::calibrate
+//! [`local_stretch`]: crate::metrics::local_stretch
+//! [`dilation_map`]: crate::metrics::dilation_map
+//! [`arc_distance`]: crate::metrics::arc_distance
+//! [`stretch`]: crate::metrics::stretch
+
+use std::{collections::HashMap, hint::black_box, num::NonZeroUsize, thread, time::Duration};
+
+use smallvec::SmallVec;
+use web_time::Instant;
+
+use crate::{
+    point::Point,
+    registry::{self, CurveEntry, CurveId},
+    spacecurve::SpaceCurve,
+};
+
+/// 2D side lengths measured by [`calibrate`].
+pub const CALIBRATION_SIZES: [u32; 4] = [16, 64, 256, 1024];
+
+/// Maximum number of indices timed per size.
+const SAMPLES: u64 = 4096;
+
+/// Timed passes per size; the fastest one is reported.
+const ROUNDS: usize = 3;
+
+/// Measured per-call latency at one grid size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    /// Side length of the grid.
+    pub size: u32,
+    /// Number of points on the curve.
+    pub length: u64,
+    /// Mean nanoseconds per `point` call.
+    pub point_ns: f64,
+    /// Mean nanoseconds per `index` call.
+    pub index_ns: f64,
+}
+
+/// Measure a 2D curve at every size in [`CALIBRATION_SIZES`] it accepts.
+pub fn calibrate(id: CurveId) -> Vec<Calibration> {
+    calibrate_sizes(id, 2, &CALIBRATION_SIZES)
+}
+
+/// Measure a curve in `dimension` at each of `sizes`, skipping sizes it rejects.
+pub fn calibrate_sizes(id: CurveId, dimension: u32, sizes: &[u32]) -> Vec<Calibration> {
+    calibrate_entry(id.entry(), dimension, sizes)
+}
+
+/// [`calibrate_sizes`] for any registry entry, including curves added with
+/// [`registry::register`].
+pub fn calibrate_entry(entry: &CurveEntry, dimension: u32, sizes: &[u32]) -> Vec<Calibration> {
+    sizes
+        .iter()
+        .filter_map(|&size| {
+            let curve = registry::construct_entry(entry, dimension, size).ok()?;
+            Some(measure(&*curve, size))
+        })
+        .collect()
+}
+
+/// Time `point` and `index` over evenly spaced indices of `curve`.
+fn measure(curve: &dyn SpaceCurve, size: u32) -> Calibration {
+    let length = curve.length();
+    let samples = length.min(SAMPLES);
+    let indices: Vec<u64> = (0..samples)
+        .map(|k| (u128::from(k) * u128::from(length) / u128::from(samples)) as u64)
+        .collect();
+    let points: Vec<Point> = indices.iter().map(|&i| curve.point(i)).collect();
+
+    let point_time = fastest(|| {
+        for &i in &indices {
+            black_box(curve.point(black_box(i)));
+        }
+    });
+    let index_time = fastest(|| {
+        for p in &points {
+            black_box(curve.index(black_box(p)));
+        }
+    });
+    let per_call = |elapsed: Duration| elapsed.as_secs_f64() * 1e9 / samples.max(1) as f64;
+    Calibration {
+        size,
+        length,
+        point_ns: per_call(point_time),
+        index_ns: per_call(index_time),
+    }
+}
+
+/// Run `pass` [`ROUNDS`] times and return the shortest duration.
+fn fastest(mut pass: impl FnMut()) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            pass();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Mean stretch of each point's axis-aligned neighbourhood.
+///
+/// For every point in `points` (in curve order), looks at the cells up to
+/// `radius` steps away along each axis that are also on the curve, and
+/// averages the ratio of index distance to spatial distance over them. Values
+/// near 1 mean spatial neighbours are also close along the curve; large values
+/// mark seams where the traversal separates adjacent cells. Points without any
+/// neighbour on the curve get a stretch of 0.
+pub fn local_stretch<const D: usize>(points: &[[u32; D]], radius: u32) -> Vec<f64> {
+    let lookup: HashMap<[u32; D], usize> =
+        points.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            stretch_at(i, p, radius, |q| {
+                lookup.get(<&[u32; D]>::try_from(q).ok()?).copied()
+            })
+        })
+        .collect()
+}
+
+/// Neighbourhood radius, in cells, used by [`dilation_map`].
+pub const DILATION_RADIUS: u32 = 2;
+
+/// Curves with at least this many points are measured on several threads.
+const PARALLEL_POINTS: usize = 1 << 16;
+
+/// [`local_stretch`] of every index of `curve`, with [`DILATION_RADIUS`].
+///
+/// Works for any dimension and for clipped curves. Large curves are split
+/// across the available cores; where threads are unavailable (e.g. on the
+/// web) the map is computed on the calling thread.
+pub fn dilation_map<C: SpaceCurve + ?Sized>(curve: &C) -> Vec<f32> {
+    let length = curve.length();
+    let mut points: Vec<Point> = Vec::new();
+    curve.points_range(0..length, &mut points);
+    let lookup: HashMap<&[u32], usize> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.as_slice(), i))
+        .collect();
+    let stretch_of =
+        |i: usize| stretch_at(i, &points[i], DILATION_RADIUS, |q| lookup.get(q).copied()) as f32;
+
+    let workers = if points.len() < PARALLEL_POINTS {
+        1
+    } else {
+        thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    };
+    if workers == 1 {
+        return (0..points.len()).map(stretch_of).collect();
+    }
+    let chunk = points.len().div_ceil(workers);
+    thread::scope(|scope| {
+        let parts: Vec<_> = (0..points.len())
+            .step_by(chunk)
+            .map(|start| {
+                let end = (start + chunk).min(points.len());
+                scope.spawn(move || (start..end).map(stretch_of).collect::<Vec<_>>())
+            })
+            .collect();
+        parts
+            .into_iter()
+            .flat_map(|part| part.join().expect("dilation worker panicked"))
+            .collect()
+    })
+}
+
+/// Number of steps along `curve` between points `a` and `b`.
+pub fn arc_distance<C: SpaceCurve + ?Sized>(curve: &C, a: &Point, b: &Point) -> u64 {
+    curve.index(a).abs_diff(curve.index(b))
+}
+
+/// Ratio of the [`arc_distance`] between `a` and `b` to their Euclidean
+/// distance.
+///
+/// 1 for neighbouring cells that are also consecutive on the curve; larger
+/// values mean the curve separates points that are close in space. The
+/// stretch of a point with itself is 0.
+pub fn stretch<C: SpaceCurve + ?Sized>(curve: &C, a: &Point, b: &Point) -> f64 {
+    let distance = a.distance(b);
+    if distance == 0.0 {
+        0.0
+    } else {
+        arc_distance(curve, a, b) as f64 / distance
+    }
+}
+
+/// Stretch of the point `p` at index `i`, finding neighbours with `lookup`.
+fn stretch_at(i: usize, p: &[u32], radius: u32, lookup: impl Fn(&[u32]) -> Option<usize>) -> f64 {
+    let (mut total, mut count) = (0.0, 0u32);
+    let mut q: SmallVec<[u32; 8]> = SmallVec::from_slice(p);
+    for axis in 0..p.len() {
+        for step in 1..=radius {
+            let below = p[axis].checked_sub(step);
+            let above = p[axis].checked_add(step);
+            for coord in [below, above].into_iter().flatten() {
+                q[axis] = coord;
+                if let Some(j) = lookup(&q) {
+                    total += i.abs_diff(j) as f64 / f64::from(step);
+                    count += 1;
+                }
+            }
+        }
+        q[axis] = p[axis];
+    }
+    if count == 0 {
+        0.0
+    } else {
+        total / f64::from(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibrates_every_accepted_size() {
+        let results = calibrate(CurveId::Hilbert);
+        let sizes: Vec<u32> = results.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, CALIBRATION_SIZES);
+        for c in &results {
+            assert_eq!(c.length, u64::from(c.size * c.size));
+            assert!(c.point_ns >= 0.0 && c.index_ns >= 0.0);
+        }
+    }
+
+    #[test]
+    fn skips_rejected_sizes() {
+        let results = calibrate_sizes(CurveId::ZOrder, 2, &[3, 8]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].size, 8);
+    }
+
+    #[test]
+    fn stretch_highlights_seams() {
+        // A 2×2 "U": (0,0) and (1,0) are neighbours three steps apart.
+        let stretch = local_stretch(&[[0, 0], [0, 1], [1, 1], [1, 0]], 1);
+        assert_eq!(stretch, vec![2.0, 1.0, 1.0, 2.0]);
+        assert_eq!(local_stretch(&[[0u32, 0]], 1), vec![0.0]);
+    }
+
+    #[test]
+    fn pair_metrics_compare_index_and_space() {
+        // The 4×4 Hilbert curve starts (0,0) (0,1) (1,1) (1,0) and ends at (0,3).
+        let curve = registry::construct("hilbert", 2, 4).unwrap();
+        let (start, corner, end) = (
+            Point::new(vec![0, 0]),
+            Point::new(vec![1, 0]),
+            Point::new(vec![0, 3]),
+        );
+        assert_eq!(arc_distance(&*curve, &start, &corner), 3);
+        assert_eq!(arc_distance(&*curve, &end, &start), 15);
+        assert_eq!(stretch(&*curve, &start, &corner), 3.0);
+        assert_eq!(stretch(&*curve, &start, &end), 5.0);
+        assert_eq!(stretch(&*curve, &end, &end), 0.0);
+    }
+
+    /// 2D points of `curve` as arrays, for comparing with [`local_stretch`].
+    fn stretch_2d(curve: &dyn SpaceCurve) -> Vec<f32> {
+        let points: Vec<[u32; 2]> = curve.points().map(|p| [p[0], p[1]]).collect();
+        local_stretch(&points, DILATION_RADIUS)
+            .into_iter()
+            .map(|s| s as f32)
+            .collect()
+    }
+
+    #[test]
+    fn dilation_map_matches_local_stretch() {
+        for name in ["hilbert", "zorder", "scan"] {
+            let curve = registry::construct(name, 2, 16).unwrap();
+            assert_eq!(dilation_map(&*curve), stretch_2d(&*curve), "{name}");
+        }
+        // Large enough to be split across threads.
+        let curve = registry::construct("hilbert", 2, 512).unwrap();
+        assert_eq!(dilation_map(&*curve), stretch_2d(&*curve));
+    }
+}
@@ -0,0 +1,108 @@
+//! Grid neighbours of curve points.
+//!
+//! A point's neighbours are the cells one step away along a single axis. The
+//! curve indices of those cells are what flood fills and locality experiments
+//! need, and comparing them with the point's own index shows how far apart
+//! the curve places adjacent cells.
+
+use smallvec::SmallVec;
+
+use crate::{point::Point, spacecurve::SpaceCurve};
+
+/// Iterator over the curve indices of a point's grid neighbours, returned by
+/// `neighbors()` on a `dyn SpaceCurve`.
+pub struct Neighbors<'a> {
+    /// Curve whose cells are indexed.
+    curve: &'a dyn SpaceCurve,
+    /// The point whose neighbours are visited; restored after each lookup.
+    point: Point,
+    /// Side length of the grid along each axis.
+    extents: SmallVec<[u32; 8]>,
+    /// Next candidate, as `axis * 2` plus 1 for the upper cell.
+    next: usize,
+}
+
+impl<'a> Neighbors<'a> {
+    /// Visit the neighbours of the point at `index` on `curve`.
+    pub(crate) fn new(curve: &'a dyn SpaceCurve, index: u64) -> Self {
+        Self {
+            curve,
+            point: curve.point(index),
+            extents: curve.extents(),
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for Neighbors<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.next < self.point.len() * 2 {
+            let (axis, upper) = (self.next / 2, self.next % 2 == 1);
+            self.next += 1;
+            let coord = self.point[axis];
+            let moved = if upper {
+                coord.checked_add(1).filter(|&c| c < self.extents[axis])
+            } else {
+                coord.checked_sub(1)
+            };
+            if let Some(moved) = moved {
+                self.point.0[axis] = moved;
+                let index = self.curve.index(&self.point);
+                self.point.0[axis] = coord;
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.point.len() * 2 - self.next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+    use crate::{curve_from_name, curve_from_sizes};
+
+    #[test]
+    fn neighbors_are_unit_steps_away() {
+        for name in ["hilbert", "zorder", "scan", "hcurve", "gray"] {
+            let curve = curve_from_name(name, 3, 4).unwrap();
+            for index in 0..curve.length() {
+                let p = curve.point(index);
+                let neighbors: Vec<u64> = curve.neighbors(index).collect();
+                for &n in &neighbors {
+                    assert_eq!(p.distance(&curve.point(n)), 1.0, "{name} {index}");
+                }
+                let inner = p.iter().filter(|&&c| c > 0 && c < 3).count();
+                assert_eq!(neighbors.len(), 3 + inner, "{name} {index}");
+            }
+        }
+    }
+
+    #[test]
+    fn continuous_curves_neighbor_their_successors() {
+        let curve = curve_from_name("hilbert", 2, 8).unwrap();
+        assert_eq!(curve.neighbors(0).count(), 2);
+        for index in 0..curve.length() - 1 {
+            assert!(curve.neighbors(index).any(|n| n == index + 1), "{index}");
+        }
+    }
+
+    #[test]
+    fn rectangles_stay_inside_their_extents() {
+        let curve = curve_from_sizes("gilbert", &[5, 3]).unwrap();
+        for index in 0..curve.length() {
+            for n in curve.neighbors(index) {
+                assert!(n < curve.length());
+            }
+        }
+        let corner = curve.index(&Point::new(vec![4, 2]));
+        assert_eq!(curve.neighbors(corner).count(), 2);
+    }
+}
@@ -0,0 +1,151 @@
+//! Curves whose first point is moved along the traversal.
+//!
+//! An [`Offset`] curve visits the same points as its inner curve in the same
+//! cyclic order, but starts `offset` steps in: index `i` is index
+//! `i + offset` of the inner curve, wrapping past the end. Aligning the starts
+//! of two curves this way keeps snake animations and chunk renderings in step
+//! when they are compared side by side.
+//!
+//! [`Offset`]: crate::offset::Offset
+
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::Range;
+
+use smallvec::SmallVec;
+
+use crate::{point::Point, query, spacecurve::SpaceCurve, wrap};
+
+/// A curve that starts `offset` points into `inner` and wraps to its start.
+#[derive(Debug)]
+pub struct Offset {
+    /// The curve being rotated.
+    inner: Box<dyn SpaceCurve>,
+    /// Index on `inner` of the first point, below its length.
+    offset: u64,
+}
+
+impl Offset {
+    /// Start `inner` at its point `offset`, taken modulo the curve length.
+    pub fn new(inner: Box<dyn SpaceCurve>, offset: u64) -> Self {
+        let offset = offset.checked_rem(inner.length()).unwrap_or(0);
+        Self { inner, offset }
+    }
+
+    /// Index on the inner curve of the first point.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Index on the inner curve of this curve's point `index`.
+    fn inner_index(&self, index: u64) -> u64 {
+        let length = u128::from(self.inner.length());
+        ((u128::from(index) + u128::from(self.offset)) % length) as u64
+    }
+}
+
+impl SpaceCurve for Offset {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn info(&self) -> &'static str {
+        self.inner.info()
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        wrap::offset_between(self.inner.length(), self.offset, self.inner.index(p))
+    }
+
+    fn point(&self, index: u64) -> Point {
+        self.inner.point(self.inner_index(index))
+    }
+
+    fn length(&self) -> u64 {
+        self.inner.length()
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.inner.extents()
+    }
+
+    fn advance_point(&self, index: u64, point: &mut Point) {
+        let inner = self.inner_index(index);
+        if inner + 1 == self.inner.length() {
+            *point = self.inner.point(0);
+        } else {
+            self.inner.advance_point(inner, point);
+        }
+    }
+
+    /// The inner curve's ranges, rotated; a range across the inner curve's
+    /// start and end is split in two.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        let (length, offset) = (self.inner.length(), self.offset);
+        let inner = self.inner.ranges_for_box(lo, hi);
+        let mut ranges = Vec::with_capacity(inner.len() + 1);
+        for r in &inner {
+            if r.end > offset {
+                query::push_range(&mut ranges, r.start.max(offset) - offset..r.end - offset);
+            }
+        }
+        for r in &inner {
+            if r.start < offset {
+                let shift = length - offset;
+                query::push_range(&mut ranges, r.start + shift..r.end.min(offset) + shift);
+            }
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve_from_name;
+
+    #[test]
+    fn rotates_the_index_space() {
+        let full = curve_from_name("hilbert", 2, 8).unwrap();
+        let curve = Offset::new(curve_from_name("hilbert", 2, 8).unwrap(), 70);
+        assert_eq!(curve.offset(), 6);
+        assert_eq!(curve.length(), 64);
+        assert_eq!(curve.point(0), full.point(6));
+        assert_eq!(curve.point(57), full.point(63));
+        assert_eq!(curve.point(58), full.point(0));
+        for index in 0..curve.length() {
+            assert_eq!(curve.index(&curve.point(index)), index);
+        }
+
+        let dyn_curve: &dyn SpaceCurve = &curve;
+        let walked: Vec<Point> = dyn_curve.points().collect();
+        for (index, p) in walked.iter().enumerate() {
+            assert_eq!(*p, curve.point(index as u64), "{index}");
+        }
+    }
+
+    #[test]
+    fn box_ranges_match_a_scan() {
+        for offset in [0, 1, 13, 32, 63] {
+            let curve = Offset::new(curve_from_name("zorder", 2, 8).unwrap(), offset);
+            for (lo, hi) in [([0, 0], [7, 7]), ([2, 1], [5, 6]), ([0, 0], [0, 0])] {
+                assert_eq!(
+                    curve.ranges_for_box(&lo, &hi),
+                    query::scan_ranges(&curve, &lo, &hi),
+                    "{offset} {lo:?} {hi:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_cyclic_curves_cyclic() {
+        let curve = Offset::new(curve_from_name("hcurve", 2, 8).unwrap(), 17);
+        assert!(curve.is_cyclic());
+        let last = curve.point(curve.length() - 1);
+        assert_eq!(last.distance(&curve.point(0)), 1.0);
+    }
+}
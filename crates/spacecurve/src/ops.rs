@@ -1,14 +1,16 @@
 //! Support operations for curve calculation.
 
+use alloc::vec::Vec;
+
 use smallvec::{SmallVec, smallvec};
 
 /// Convert a binary index to its Binary Reflected Gray Code (BRGC) form.
-pub fn graycode(x: u32) -> u32 {
+pub fn graycode(x: u64) -> u64 {
     x ^ (x >> 1)
 }
 
 /// Inverse Gray code: recover binary from a BRGC value `x`.
-pub fn igraycode(x: u32) -> u32 {
+pub fn igraycode(x: u64) -> u64 {
     let mut g = x;
     let mut b = x;
     loop {
@@ -45,64 +47,68 @@ pub fn bit_transpose(d: u32, v: &[u32]) -> SmallVec<[u32; 8]> {
     ret
 }
 
-/// Spreads bits of a 16-bit number so that there is 1 zero between each bit.
+/// Spreads bits of a 32-bit number so that there is 1 zero between each bit.
 /// (e.g., 1011 -> 1000101)
 /// Used for 2D Morton codes.
-fn part1by1(mut n: u32) -> u32 {
-    n &= 0x0000ffff;
-    n = (n ^ (n << 8)) & 0x00ff00ff;
-    n = (n ^ (n << 4)) & 0x0f0f0f0f;
-    n = (n ^ (n << 2)) & 0x33333333;
-    n = (n ^ (n << 1)) & 0x55555555;
+pub(crate) fn part1by1(n: u32) -> u64 {
+    let mut n = u64::from(n);
+    n = (n ^ (n << 16)) & 0x0000_ffff_0000_ffff;
+    n = (n ^ (n << 8)) & 0x00ff_00ff_00ff_00ff;
+    n = (n ^ (n << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    n = (n ^ (n << 2)) & 0x3333_3333_3333_3333;
+    n = (n ^ (n << 1)) & 0x5555_5555_5555_5555;
     n
 }
 
-/// Compresses bits of a 32-bit number, selecting every other bit.
+/// Compresses bits of a 64-bit number, selecting every other bit.
 /// Inverse of part1by1.
-fn compact1by1(mut n: u32) -> u32 {
-    n &= 0x55555555;
-    n = (n ^ (n >> 1)) & 0x33333333;
-    n = (n ^ (n >> 2)) & 0x0f0f0f0f;
-    n = (n ^ (n >> 4)) & 0x00ff00ff;
-    n = (n ^ (n >> 8)) & 0x0000ffff;
-    n
+fn compact1by1(mut n: u64) -> u32 {
+    n &= 0x5555_5555_5555_5555;
+    n = (n ^ (n >> 1)) & 0x3333_3333_3333_3333;
+    n = (n ^ (n >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    n = (n ^ (n >> 4)) & 0x00ff_00ff_00ff_00ff;
+    n = (n ^ (n >> 8)) & 0x0000_ffff_0000_ffff;
+    n = (n ^ (n >> 16)) & 0x0000_0000_ffff_ffff;
+    n as u32
 }
 
-/// Spreads bits of a 10-bit number so that there are 2 zeroes between each bit.
+/// Spreads bits of a 21-bit number so that there are 2 zeroes between each bit.
 /// (e.g., 1011 -> 1001001)
 /// Used for 3D Morton codes.
-fn part1by2(mut n: u32) -> u32 {
-    n &= 0x000003ff;
-    n = (n ^ (n << 16)) & 0xff0000ff;
-    n = (n ^ (n << 8)) & 0x0300f00f;
-    n = (n ^ (n << 4)) & 0x030c30c3;
-    n = (n ^ (n << 2)) & 0x09249249;
+fn part1by2(n: u32) -> u64 {
+    let mut n = u64::from(n) & 0x1f_ffff;
+    n = (n ^ (n << 32)) & 0x001f_0000_0000_ffff;
+    n = (n ^ (n << 16)) & 0x001f_0000_ff00_00ff;
+    n = (n ^ (n << 8)) & 0x100f_00f0_0f00_f00f;
+    n = (n ^ (n << 4)) & 0x10c3_0c30_c30c_30c3;
+    n = (n ^ (n << 2)) & 0x1249_2492_4924_9249;
     n
 }
 
-/// Compresses bits of a 32-bit number, selecting every third bit.
+/// Compresses bits of a 64-bit number, selecting every third bit.
 /// Inverse of part1by2.
-fn compact1by2(mut n: u32) -> u32 {
-    n &= 0x09249249;
-    n = (n ^ (n >> 2)) & 0x030c30c3;
-    n = (n ^ (n >> 4)) & 0x0300f00f;
-    n = (n ^ (n >> 8)) & 0xff0000ff;
-    n = (n ^ (n >> 16)) & 0x000003ff;
-    n
+fn compact1by2(mut n: u64) -> u32 {
+    n &= 0x1249_2492_4924_9249;
+    n = (n ^ (n >> 2)) & 0x10c3_0c30_c30c_30c3;
+    n = (n ^ (n >> 4)) & 0x100f_00f0_0f00_f00f;
+    n = (n ^ (n >> 8)) & 0x001f_0000_ff00_00ff;
+    n = (n ^ (n >> 16)) & 0x001f_0000_0000_ffff;
+    n = (n ^ (n >> 32)) & 0x1f_ffff;
+    n as u32
 }
 
 #[inline]
-fn interleave_lsb_const<const D: usize>(coords: &[u32; D], bits_per_axis: u32) -> u32 {
+pub(crate) fn interleave_lsb_const<const D: usize>(coords: &[u32; D], bits_per_axis: u32) -> u64 {
     if D == 0 || bits_per_axis == 0 {
         return 0;
     }
 
     match D {
-        2 if bits_per_axis <= 16 => {
+        2 if bits_per_axis <= 32 => {
             let mask = bitmask(bits_per_axis);
             return part1by1(coords[0] & mask) | (part1by1(coords[1] & mask) << 1);
         }
-        3 if bits_per_axis <= 10 => {
+        3 if bits_per_axis <= 21 => {
             let mask = bitmask(bits_per_axis);
             return part1by2(coords[0] & mask)
                 | (part1by2(coords[1] & mask) << 1)
@@ -111,10 +117,10 @@ fn interleave_lsb_const<const D: usize>(coords: &[u32; D], bits_per_axis: u32) -
         _ => {}
     }
 
-    let mut value = 0u32;
+    let mut value = 0u64;
     for bit in 0..bits_per_axis {
         for (dim, coord) in coords.iter().enumerate() {
-            let bit_val = (coord >> bit) & 1;
+            let bit_val = u64::from((coord >> bit) & 1);
             value |= bit_val << (bit * (D as u32) + dim as u32);
         }
     }
@@ -122,20 +128,20 @@ fn interleave_lsb_const<const D: usize>(coords: &[u32; D], bits_per_axis: u32) -
 }
 
 #[inline]
-fn deinterleave_lsb_const<const D: usize>(bits_per_axis: u32, value: u32) -> [u32; D] {
+fn deinterleave_lsb_const<const D: usize>(bits_per_axis: u32, value: u64) -> [u32; D] {
     let mut coords = [0u32; D];
     if D == 0 || bits_per_axis == 0 {
         return coords;
     }
 
     match D {
-        2 if bits_per_axis <= 16 => {
+        2 if bits_per_axis <= 32 => {
             let mask = bitmask(bits_per_axis);
             coords[0] = compact1by1(value) & mask;
             coords[1] = compact1by1(value >> 1) & mask;
             return coords;
         }
-        3 if bits_per_axis <= 10 => {
+        3 if bits_per_axis <= 21 => {
             let mask = bitmask(bits_per_axis);
             coords[0] = compact1by2(value) & mask;
             coords[1] = compact1by2(value >> 1) & mask;
@@ -148,7 +154,7 @@ fn deinterleave_lsb_const<const D: usize>(bits_per_axis: u32, value: u32) -> [u3
     for bit in 0..bits_per_axis {
         for (dim, coord) in coords.iter_mut().enumerate() {
             let bit_index = bit * (D as u32) + dim as u32;
-            let bit_val = (value >> bit_index) & 1;
+            let bit_val = ((value >> bit_index) & 1) as u32;
             *coord |= bit_val << bit;
         }
     }
@@ -160,7 +166,7 @@ fn deinterleave_lsb_const<const D: usize>(bits_per_axis: u32, value: u32) -> [u3
 /// `bits_per_axis` defines how many bits should be read from every coordinate.
 /// Bits are interleaved from least-significant to most-significant order to
 /// match the conventional Morton/Z-order encoding.
-pub fn interleave_lsb(coords: &[u32], bits_per_axis: u32) -> u32 {
+pub fn interleave_lsb(coords: &[u32], bits_per_axis: u32) -> u64 {
     if coords.is_empty() || bits_per_axis == 0 {
         return 0;
     }
@@ -176,12 +182,12 @@ pub fn interleave_lsb(coords: &[u32], bits_per_axis: u32) -> u32 {
     }
 }
 
-fn interleave_generic(coords: &[u32], bits_per_axis: u32) -> u32 {
+fn interleave_generic(coords: &[u32], bits_per_axis: u32) -> u64 {
     let dimension = coords.len();
-    let mut value = 0u32;
+    let mut value = 0u64;
     for bit in 0..bits_per_axis {
         for (dim, coord) in coords.iter().enumerate() {
-            let bit_val = (coord >> bit) & 1;
+            let bit_val = u64::from((coord >> bit) & 1);
             value |= bit_val << (bit * (dimension as u32) + dim as u32);
         }
     }
@@ -189,7 +195,7 @@ fn interleave_generic(coords: &[u32], bits_per_axis: u32) -> u32 {
 }
 
 /// Deinterleave a Morton/Z-order code into coordinate components.
-pub fn deinterleave_lsb(dimension: u32, bits_per_axis: u32, value: u32) -> SmallVec<[u32; 8]> {
+pub fn deinterleave_lsb(dimension: u32, bits_per_axis: u32, value: u64) -> SmallVec<[u32; 8]> {
     if dimension == 0 {
         return smallvec![];
     }
@@ -220,20 +226,147 @@ pub fn deinterleave_lsb(dimension: u32, bits_per_axis: u32, value: u32) -> Small
     deinterleave_generic(dimension, bits_per_axis, value)
 }
 
-fn deinterleave_generic(dimension: u32, bits_per_axis: u32, value: u32) -> SmallVec<[u32; 8]> {
+fn deinterleave_generic(dimension: u32, bits_per_axis: u32, value: u64) -> SmallVec<[u32; 8]> {
     let mut coords = smallvec![0u32; dimension as usize];
     for bit in 0..bits_per_axis {
         for dim in 0..dimension {
             let bit_index = bit * dimension + dim;
-            let bit_val = (value >> bit_index) & 1;
+            let bit_val = ((value >> bit_index) & 1) as u32;
             coords[dim as usize] |= bit_val << bit;
         }
     }
     coords
 }
 
+/// Interleave coordinates whose axes have different bit widths.
+///
+/// Bit `b` of every axis wider than `b` bits is taken in axis order, from
+/// least-significant to most-significant; axes that run out of bits drop
+/// out. With equal widths this matches [`interleave_lsb`].
+pub fn interleave_uneven(coords: &[u32], axis_bits: &[u32]) -> u64 {
+    debug_assert_eq!(coords.len(), axis_bits.len(), "axis count mismatch");
+    let mut value = 0u64;
+    let mut shift = 0;
+    for bit in 0..axis_bits.iter().copied().max().unwrap_or(0) {
+        for (coord, &bits) in coords.iter().zip(axis_bits) {
+            if bit < bits {
+                value |= u64::from((coord >> bit) & 1) << shift;
+                shift += 1;
+            }
+        }
+    }
+    value
+}
+
+/// Inverse of [`interleave_uneven`].
+pub fn deinterleave_uneven(axis_bits: &[u32], value: u64) -> SmallVec<[u32; 8]> {
+    let mut coords = smallvec![0u32; axis_bits.len()];
+    let mut shift = 0;
+    for bit in 0..axis_bits.iter().copied().max().unwrap_or(0) {
+        for (coord, &bits) in coords.iter_mut().zip(axis_bits) {
+            if bit < bits {
+                *coord |= (((value >> shift) & 1) as u32) << bit;
+                shift += 1;
+            }
+        }
+    }
+    coords
+}
+
+/// Interleave many coordinate tuples, appending one Morton code per tuple to `out`.
+///
+/// Equivalent to calling [`interleave_lsb`] on every element, but the
+/// dimension is a compile-time constant so the per-call dispatch folds away.
+pub fn interleave_many<const D: usize>(
+    coords: &[[u32; D]],
+    bits_per_axis: u32,
+    out: &mut Vec<u64>,
+) {
+    out.extend(
+        coords
+            .iter()
+            .map(|tuple| interleave_lsb_const(tuple, bits_per_axis)),
+    );
+}
+
+/// Interleave coordinates stored as one slice per axis (structure of arrays).
+///
+/// `axes[dim][i]` is coordinate `dim` of tuple `i`, and one code per tuple is
+/// appended to `out`. Each axis is spread in its own straight-line pass over
+/// the output, which lets the compiler vectorise the 2D and 3D cases.
+///
+/// # Panics
+///
+/// Panics if the axis slices differ in length.
+pub fn interleave_many_soa(axes: &[&[u32]], bits_per_axis: u32, out: &mut Vec<u64>) {
+    let Some(first) = axes.first() else {
+        return;
+    };
+    let len = first.len();
+    assert!(
+        axes.iter().all(|axis| axis.len() == len),
+        "axis slices must have equal lengths"
+    );
+    let start = out.len();
+    out.resize(start + len, 0);
+    if bits_per_axis == 0 {
+        return;
+    }
+
+    let codes = &mut out[start..];
+    let dimension = axes.len() as u32;
+    let mask = bitmask(bits_per_axis);
+    for (dim, axis) in axes.iter().enumerate() {
+        let shift = dim as u32;
+        match dimension {
+            2 if bits_per_axis <= 32 => {
+                spread_into(codes, axis, |c| part1by1(c & mask) << shift);
+            }
+            3 if bits_per_axis <= 21 => {
+                spread_into(codes, axis, |c| part1by2(c & mask) << shift);
+            }
+            _ => spread_into(codes, axis, |c| {
+                spread_generic(c, dimension, bits_per_axis) << shift
+            }),
+        }
+    }
+}
+
+/// OR the spread form of every coordinate in `axis` into the matching code.
+#[inline]
+fn spread_into(codes: &mut [u64], axis: &[u32], spread: impl Fn(u32) -> u64) {
+    for (code, &coord) in codes.iter_mut().zip(axis) {
+        *code |= spread(coord);
+    }
+}
+
+/// Place bit `b` of `coord` at position `b * dimension`.
+fn spread_generic(coord: u32, dimension: u32, bits_per_axis: u32) -> u64 {
+    (0..bits_per_axis).fold(0, |acc, bit| {
+        acc | (u64::from((coord >> bit) & 1) << (bit * dimension))
+    })
+}
+
+/// Deinterleave many Morton codes, appending one coordinate tuple per code to `out`.
+///
+/// The batch counterpart of [`deinterleave_lsb`], returning fixed-size arrays
+/// instead of heap-capable vectors.
+pub fn deinterleave_many<const D: usize>(
+    bits_per_axis: u32,
+    values: impl IntoIterator<Item = u64>,
+    out: &mut Vec<[u32; D]>,
+) {
+    out.extend(
+        values
+            .into_iter()
+            .map(|value| deinterleave_lsb_const::<D>(bits_per_axis, value)),
+    );
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
     use super::*;
 
     #[test]
@@ -257,6 +390,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn batch_interleave_matches_scalar() {
+        let coords: Vec<[u32; 3]> = (0..200u32)
+            .map(|i| [i % 7, (i * 5) % 8, (i * 3) % 6])
+            .collect();
+        for bits in [0, 3, 10] {
+            let expected: Vec<u64> = coords.iter().map(|c| interleave_lsb(c, bits)).collect();
+
+            let mut aos = vec![42];
+            interleave_many(&coords, bits, &mut aos);
+            assert_eq!(aos[0], 42);
+            assert_eq!(aos[1..], expected[..]);
+
+            let axes: Vec<Vec<u32>> = (0..3)
+                .map(|dim| coords.iter().map(|c| c[dim]).collect())
+                .collect();
+            let axis_slices: Vec<&[u32]> = axes.iter().map(Vec::as_slice).collect();
+            let mut soa = Vec::new();
+            interleave_many_soa(&axis_slices, bits, &mut soa);
+            assert_eq!(soa, expected);
+
+            let mut decoded = Vec::new();
+            deinterleave_many::<3>(bits, expected.iter().copied(), &mut decoded);
+            let scalar: Vec<[u32; 3]> = expected
+                .iter()
+                .map(|&v| {
+                    let c = deinterleave_lsb(3, bits, v);
+                    [c[0], c[1], c[2]]
+                })
+                .collect();
+            assert_eq!(decoded, scalar);
+        }
+    }
+
+    #[test]
+    fn soa_interleave_handles_other_dimensions() {
+        let xs = [1u32, 2, 3];
+        let ys = [3u32, 2, 1];
+        let zs = [0u32, 1, 2];
+        let ws = [2u32, 3, 0];
+        let mut codes = Vec::new();
+        interleave_many_soa(&[&xs, &ys, &zs, &ws], 2, &mut codes);
+        let expected: Vec<u64> = (0..3)
+            .map(|i| interleave_lsb(&[xs[i], ys[i], zs[i], ws[i]], 2))
+            .collect();
+        assert_eq!(codes, expected);
+
+        codes.clear();
+        interleave_many_soa(&[&xs], 2, &mut codes);
+        assert_eq!(codes, xs.map(u64::from));
+    }
+
+    #[test]
+    fn wide_coordinates_roundtrip() {
+        for (dim, bits) in [(2u32, 31u32), (2, 32), (3, 21), (4, 15), (5, 12)] {
+            let top = bitmask(bits);
+            let coords: Vec<u32> = (0..dim).map(|d| top - d * 0x55).collect();
+            let morton = interleave_lsb(&coords, bits);
+            assert_eq!(deinterleave_lsb(dim, bits, morton).as_slice(), coords);
+            assert_eq!(
+                morton,
+                interleave_generic(&coords, bits),
+                "fast path differs for {dim}D"
+            );
+        }
+    }
+
+    #[test]
+    fn uneven_interleave_roundtrips() {
+        assert_eq!(
+            interleave_uneven(&[5, 6, 3], &[3, 3, 3]),
+            interleave_lsb(&[5, 6, 3], 3)
+        );
+        let axis_bits = [3, 1, 2];
+        let mut seen = [false; 64];
+        for value in 0..64 {
+            let coords = deinterleave_uneven(&axis_bits, value);
+            assert!(coords.iter().zip(&axis_bits).all(|(&c, &b)| c < 1 << b));
+            assert_eq!(interleave_uneven(&coords, &axis_bits), value);
+            seen[(coords[0] + 8 * coords[1] + 16 * coords[2]) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
     #[test]
     fn test_transpose() {
         let v: Vec<u32> = vec![0b00, 0b01, 0b10, 0b11];
@@ -1,9 +1,12 @@
 //! Lightweight N‑dimensional point type used by curve implementations.
 
-use std::{ops::Deref, vec::Vec};
+use alloc::vec::Vec;
+use core::ops::{Deref, Range};
 
 use smallvec::SmallVec;
 
+use crate::spacecurve::SpaceCurve;
+
 /// Compact N‑dimensional point wrapper used by curves.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Point(pub SmallVec<[u32; 8]>);
@@ -47,7 +50,19 @@ impl Point {
             let d = (*a as i128 - *b as i128).abs();
             tot += (d * d) as u128;
         }
-        (tot as f64).sqrt()
+        libm::sqrt(tot as f64)
+    }
+
+    /// Coordinates a fraction `t` of the way from `self` to `to`.
+    ///
+    /// `t = 0` gives `self` and `t = 1` gives `to`; both points must have the
+    /// same dimensionality.
+    pub fn lerp(&self, to: &Self, t: f64) -> SmallVec<[f64; 8]> {
+        debug_assert_eq!(self.len(), to.len(), "Point::lerp dimension mismatch");
+        self.iter()
+            .zip(to.iter())
+            .map(|(&a, &b)| f64::from(a) + (f64::from(b) - f64::from(a)) * t)
+            .collect()
     }
 
     /// Return the point's coordinates as a slice.
@@ -80,10 +95,67 @@ impl Deref for Point {
     }
 }
 
+/// Lazy iterator over the points of a curve, returned by `points()` on a `dyn SpaceCurve`.
+///
+/// Each point after the first is derived from its predecessor with
+/// [`SpaceCurve::advance_point`], so curves with an incremental step avoid a
+/// full decode per point.
+pub struct Points<'a> {
+    /// Curve being walked.
+    curve: &'a dyn SpaceCurve,
+    /// Index of the next point to yield.
+    next: u64,
+    /// One past the last point included.
+    end: u64,
+    /// Previously yielded point, advanced to produce the next one.
+    prev: Option<Point>,
+}
+
+impl<'a> Points<'a> {
+    /// Walk the points of `curve` in `range`, clamped to the curve length.
+    pub(crate) fn new(curve: &'a dyn SpaceCurve, range: Range<u64>) -> Self {
+        Self {
+            curve,
+            next: range.start,
+            end: range.end.min(curve.length()),
+            prev: None,
+        }
+    }
+}
+
+impl Iterator for Points<'_> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.next >= self.end {
+            return None;
+        }
+        let point = match self.prev.take() {
+            Some(mut point) => {
+                self.curve.advance_point(self.next - 1, &mut point);
+                point
+            }
+            None => self.curve.point(self.next),
+        };
+        self.prev = Some(point.clone());
+        self.next += 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.saturating_sub(self.next) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Points<'_> {}
+
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+
     use super::*;
-    use crate::error;
+    use crate::{curve_from_name, error};
 
     #[test]
     fn point() -> error::Result<()> {
@@ -112,4 +184,59 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn points_follow_the_curve() {
+        for name in ["hilbert", "gray", "scan", "zorder"] {
+            let curve = curve_from_name(name, 2, 8).unwrap();
+            let points: Vec<Point> = curve.points().collect();
+            assert_eq!(points.len(), 64, "{name}");
+            for (i, p) in points.iter().enumerate() {
+                assert_eq!(*p, curve.point(i as u64), "{name} {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn points_in_clamps_ranges() {
+        let curve = curve_from_name("hilbert", 2, 4).unwrap();
+        assert_eq!(curve.points_in(3..7).len(), 4);
+        assert_eq!(curve.points_in(14..40).len(), 2);
+        assert_eq!(curve.points_in(5..5).count(), 0);
+        assert_eq!(curve.points_in(3..7).next(), Some(curve.point(3)));
+    }
+
+    #[test]
+    fn points_range_appends_in_order() {
+        for name in ["hilbert", "gray", "scan", "zorder", "hcurve"] {
+            let curve = curve_from_name(name, 2, 8).unwrap();
+            let mut out = vec![Point::new(vec![9, 9])];
+            curve.points_range(10..30, &mut out);
+            assert_eq!(out.len(), 21, "{name}");
+            assert_eq!(out[1..], curve.points_in(10..30).collect::<Vec<_>>()[..]);
+            curve.points_range(60..100, &mut out);
+            assert_eq!(out.len(), 25, "{name}");
+            assert_eq!(out[24], curve.point(63), "{name}");
+            curve.points_range(64..70, &mut out);
+            assert_eq!(out.len(), 25, "{name}");
+        }
+    }
+
+    #[test]
+    fn point_at_interpolates_between_indices() {
+        let curve = curve_from_name("hilbert", 2, 4).unwrap();
+        let last = (curve.length() - 1) as f64;
+        let coords = |p: Point| p.iter().map(|&c| f64::from(c)).collect::<Vec<_>>();
+        assert_eq!(curve.point_at(0.0).to_vec(), coords(curve.point(0)));
+        assert_eq!(curve.point_at(1.0).to_vec(), coords(curve.point(15)));
+        assert_eq!(curve.point_at(7.0 / last).to_vec(), coords(curve.point(7)));
+        assert_eq!(curve.point_at(-3.0), curve.point_at(0.0));
+        assert_eq!(curve.point_at(4.0), curve.point_at(1.0));
+
+        let halfway = curve.point_at(2.5 / last);
+        let expected = curve.point(2).lerp(&curve.point(3), 0.5);
+        for (a, b) in halfway.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-9, "{halfway:?} vs {expected:?}");
+        }
+    }
 }
@@ -0,0 +1,209 @@
+//! Index ranges covering axis-aligned boxes.
+//!
+//! A box query returns the fewest contiguous index ranges whose points are
+//! exactly the cells of the box, so a database keyed by curve index can answer
+//! it with one range scan per entry. Any curve can be queried by visiting
+//! every cell ([`scan_ranges`]); curves whose aligned index blocks fill
+//! aligned subcubes, like Hilbert and Z-order, descend that hierarchy instead
+//! ([`block_ranges`]) and only visit blocks on the box boundary.
+//!
+//! [`scan_ranges`]: crate::query::scan_ranges
+//! [`block_ranges`]: crate::query::block_ranges
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{point::Point, spacecurve::SpaceCurve};
+
+/// Ranges covering the box from `lo` to `hi` (inclusive), found by indexing
+/// every cell in it.
+///
+/// Works for any curve, at a cost proportional to the box volume.
+pub fn scan_ranges<C: SpaceCurve + ?Sized>(curve: &C, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+    debug_assert_eq!(
+        lo.len(),
+        curve.dimensions() as usize,
+        "lo dimension mismatch"
+    );
+    debug_assert_eq!(
+        hi.len(),
+        curve.dimensions() as usize,
+        "hi dimension mismatch"
+    );
+    if lo.iter().zip(hi).any(|(l, h)| l > h) {
+        return Vec::new();
+    }
+    let mut indices = Vec::new();
+    let mut cell = Point::new(lo.to_vec());
+    loop {
+        indices.push(curve.index(&cell));
+        // Step to the next cell like an odometer, first axis fastest.
+        let mut axis = 0;
+        while axis < cell.len() && cell.0[axis] == hi[axis] {
+            cell.0[axis] = lo[axis];
+            axis += 1;
+        }
+        if axis == cell.len() {
+            break;
+        }
+        cell.0[axis] += 1;
+    }
+    indices.sort_unstable();
+    let mut ranges = Vec::new();
+    for index in indices {
+        push_range(&mut ranges, index..index + 1);
+    }
+    ranges
+}
+
+/// Ranges covering the box from `lo` to `hi` (inclusive) on a curve of side
+/// `2^order` whose aligned index blocks fill aligned subcubes.
+///
+/// The curve must map every block of `2^(dimensions·k)` indices starting at a
+/// multiple of its size onto a cube of side `2^k` aligned to that size, as
+/// Hilbert and Z-order do. Blocks inside the box are emitted whole and blocks
+/// outside it are skipped, so only boundary blocks are subdivided.
+pub fn block_ranges<C: SpaceCurve + ?Sized>(
+    curve: &C,
+    order: u32,
+    lo: &[u32],
+    hi: &[u32],
+) -> Vec<Range<u64>> {
+    debug_assert_eq!(
+        lo.len(),
+        curve.dimensions() as usize,
+        "lo dimension mismatch"
+    );
+    debug_assert_eq!(
+        hi.len(),
+        curve.dimensions() as usize,
+        "hi dimension mismatch"
+    );
+    let mut ranges = Vec::new();
+    if lo.iter().zip(hi).all(|(l, h)| l <= h) {
+        descend(curve, 0, order, lo, hi, &mut ranges);
+    }
+    ranges
+}
+
+/// Emit the parts of the block of side `2^level` starting at index `start`
+/// that lie in the box, in index order.
+fn descend<C: SpaceCurve + ?Sized>(
+    curve: &C,
+    start: u64,
+    level: u32,
+    lo: &[u32],
+    hi: &[u32],
+    ranges: &mut Vec<Range<u64>>,
+) {
+    let dimensions = curve.dimensions();
+    let len = 1u64 << (dimensions * level);
+    let first = curve.point(start);
+    let mut inside = true;
+    for (axis, &coord) in first.iter().enumerate() {
+        let min = coord >> level << level;
+        let max = min + ((1u32 << level) - 1);
+        if max < lo[axis] || min > hi[axis] {
+            return;
+        }
+        inside &= lo[axis] <= min && max <= hi[axis];
+    }
+    if inside {
+        push_range(ranges, start..start + len);
+        return;
+    }
+    let child = len >> dimensions;
+    for i in 0..1u64 << dimensions {
+        descend(curve, start + i * child, level - 1, lo, hi, ranges);
+    }
+}
+
+/// Append `range`, extending the last range when they touch.
+pub(crate) fn push_range(ranges: &mut Vec<Range<u64>>, range: Range<u64>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{Hilbert, ZOrder, curve_from_name};
+
+    /// Every box of a small grid, as (lo, hi) corner pairs.
+    fn boxes_2d(side: u32) -> Vec<([u32; 2], [u32; 2])> {
+        let mut boxes = Vec::new();
+        for x0 in 0..side {
+            for x1 in x0..side {
+                for y0 in 0..side {
+                    for y1 in y0..side {
+                        boxes.push(([x0, y0], [x1, y1]));
+                    }
+                }
+            }
+        }
+        boxes
+    }
+
+    #[test]
+    fn scan_merges_contiguous_indices() {
+        let curve = curve_from_name("scan", 2, 4).unwrap();
+        // Rows run back and forth, so two full rows are one range.
+        assert_eq!(curve.ranges_for_box(&[0, 1], &[3, 2]), vec![4..12]);
+        assert_eq!(curve.ranges_for_box(&[1, 0], &[2, 1]), vec![1..3, 5..7]);
+        assert!(curve.ranges_for_box(&[2, 0], &[1, 3]).is_empty());
+    }
+
+    #[test]
+    fn hilbert_quadrants_are_single_ranges() {
+        let curve = curve_from_name("hilbert", 2, 8).unwrap();
+        assert_eq!(curve.ranges_for_box(&[0, 0], &[3, 3]), vec![0..16]);
+        assert_eq!(curve.ranges_for_box(&[0, 0], &[7, 7]), vec![0..64]);
+        assert_eq!(curve.ranges_for_box(&[5, 2], &[5, 2]).len(), 1);
+    }
+
+    #[test]
+    fn fast_paths_match_scans_2d() {
+        let hilbert = Hilbert::from_dimensions(2, 8).unwrap();
+        let zorder = ZOrder::from_dimensions(2, 8).unwrap();
+        for (lo, hi) in boxes_2d(8) {
+            assert_eq!(
+                hilbert.ranges_for_box(&lo, &hi),
+                scan_ranges(&hilbert, &lo, &hi),
+                "hilbert {lo:?}..={hi:?}"
+            );
+            assert_eq!(
+                zorder.ranges_for_box(&lo, &hi),
+                scan_ranges(&zorder, &lo, &hi),
+                "zorder {lo:?}..={hi:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn fast_paths_match_scans_3d() {
+        let hilbert = Hilbert::from_dimensions(3, 8).unwrap();
+        let zorder = ZOrder::from_dimensions(3, 8).unwrap();
+        for (lo, hi) in [
+            ([0, 0, 0], [7, 7, 7]),
+            ([1, 2, 3], [4, 5, 6]),
+            ([0, 0, 0], [3, 3, 3]),
+            ([3, 0, 5], [6, 7, 5]),
+            ([7, 7, 7], [7, 7, 7]),
+        ] {
+            assert_eq!(
+                hilbert.ranges_for_box(&lo, &hi),
+                scan_ranges(&hilbert, &lo, &hi),
+                "hilbert {lo:?}..={hi:?}"
+            );
+            assert_eq!(
+                zorder.ranges_for_box(&lo, &hi),
+                scan_ranges(&zorder, &lo, &hi),
+                "zorder {lo:?}..={hi:?}"
+            );
+        }
+    }
+}
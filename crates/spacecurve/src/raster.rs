@@ -0,0 +1,127 @@
+//! Walking `image` crate buffers along curves.
+//!
+//! A 2D curve whose [`extents`](SpaceCurve::extents) match an image visits
+//! every pixel once, so it orders the pixels as a sequence: pixels can be
+//! painted from their curve index, flattened into curve order, or moved from
+//! their place on one curve to the same index on another. Every function
+//! checks that the curve covers the image exactly.
+
+use image::{ImageBuffer, Pixel};
+
+use crate::{error, mapping::for_each_point, spacecurve::SpaceCurve};
+
+/// An image buffer with `P` pixels.
+pub type Image<P> = ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>;
+
+/// Build an image the size of `curve`'s grid whose pixel at point `i` of
+/// `curve` is `pixel(i)`.
+pub fn paint_along<P, C>(curve: &C, mut pixel: impl FnMut(u64) -> P) -> error::Result<Image<P>>
+where
+    P: Pixel,
+    C: SpaceCurve + ?Sized,
+{
+    let (width, height) = plane(curve)?;
+    let mut image = Image::new(width, height);
+    for_each_point(curve, |i, p| image.put_pixel(p[0], p[1], pixel(i)));
+    Ok(image)
+}
+
+/// The channel bytes of every pixel of `image`, in the order `curve` visits
+/// them.
+pub fn flatten_image_along<P, C>(image: &Image<P>, curve: &C) -> error::Result<Vec<u8>>
+where
+    P: Pixel<Subpixel = u8>,
+    C: SpaceCurve + ?Sized,
+{
+    check_covers(curve, image)?;
+    let mut out = Vec::with_capacity(image.as_raw().len());
+    for_each_point(curve, |_, p| {
+        out.extend_from_slice(image.get_pixel(p[0], p[1]).channels());
+    });
+    Ok(out)
+}
+
+/// Move each pixel of `image` from point `i` of `from` to point `i` of `to`.
+///
+/// Reordering back from `to` to `from` restores the original image.
+pub fn reorder_image<P, A, B>(image: &Image<P>, from: &A, to: &B) -> error::Result<Image<P>>
+where
+    P: Pixel,
+    A: SpaceCurve + ?Sized,
+    B: SpaceCurve + ?Sized,
+{
+    check_covers(from, image)?;
+    check_covers(to, image)?;
+    let mut pixels = Vec::with_capacity(image.len() / usize::from(P::CHANNEL_COUNT));
+    for_each_point(from, |_, p| pixels.push(*image.get_pixel(p[0], p[1])));
+    paint_along(to, |i| pixels[i as usize])
+}
+
+/// Width and height of a 2D `curve`'s grid.
+fn plane<C: SpaceCurve + ?Sized>(curve: &C) -> error::Result<(u32, u32)> {
+    match curve.extents()[..] {
+        [width, height] => Ok((width, height)),
+        _ => Err(error::Error::Shape(format!(
+            "images need a 2D curve, not {}D",
+            curve.dimensions()
+        ))),
+    }
+}
+
+/// Check that `curve`'s grid matches the dimensions of `image`.
+fn check_covers<P: Pixel, C: SpaceCurve + ?Sized>(
+    curve: &C,
+    image: &Image<P>,
+) -> error::Result<()> {
+    let (width, height) = plane(curve)?;
+    if image.dimensions() != (width, height) {
+        return Err(error::Error::Shape(format!(
+            "curve covers {width}×{height} pixels but the image is {}×{}",
+            image.width(),
+            image.height()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Luma, Rgb};
+
+    use super::*;
+    use crate::{clip::Clipped, curve_from_name, curve_from_sizes};
+
+    #[test]
+    fn painting_follows_the_curve() {
+        let curve = curve_from_name("hilbert", 2, 8).unwrap();
+        let image = paint_along(&*curve, |i| Luma([i as u8])).unwrap();
+        for i in 0..curve.length() {
+            let p = curve.point(i);
+            assert_eq!(image.get_pixel(p[0], p[1]).0, [i as u8]);
+        }
+        let flat = flatten_image_along(&image, &*curve).unwrap();
+        assert_eq!(flat, (0..64).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn reordering_round_trips() {
+        let curve = curve_from_sizes("gilbert", &[6, 4]).unwrap();
+        let image = Image::from_fn(6, 4, |x, y| Rgb([x as u8, y as u8, 7]));
+        let other = Clipped::new(curve_from_name("zorder", 2, 8).unwrap(), &[6, 4]).unwrap();
+        let moved = reorder_image(&image, &*curve, &other).unwrap();
+        assert_eq!(
+            flatten_image_along(&moved, &other).unwrap(),
+            flatten_image_along(&image, &*curve).unwrap()
+        );
+        assert_eq!(reorder_image(&moved, &other, &*curve).unwrap(), image);
+    }
+
+    #[test]
+    fn rejects_mismatched_curves() {
+        let image = Image::from_pixel(8, 8, Luma([0u8]));
+        let small = curve_from_name("hilbert", 2, 4).unwrap();
+        assert!(flatten_image_along(&image, &*small).is_err());
+        let cube = curve_from_name("hilbert", 3, 4).unwrap();
+        assert!(paint_along(&*cube, |_| Luma([0u8])).is_err());
+    }
+}
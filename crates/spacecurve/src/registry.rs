@@ -1,42 +1,201 @@
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::{fmt, ops::RangeInclusive, str::FromStr};
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+#[cfg(feature = "experimental-curves")]
+use crate::curves::{hairyonion, onion};
 use crate::{
-    curves::{gray, hairyonion, hcurve, hilbert, onion, scan, zorder},
+    curves::{coil, gilbert, gray, hcurve, hilbert, scan, shuffle, sierpinski, zorder},
     error,
     spacecurve::SpaceCurve,
     spec::GridSpec,
+    transform::PRESETS,
 };
 
 /// Metadata and constructor for a curve type.
+#[derive(Debug)]
 pub struct CurveEntry {
+    /// Typed identifier of a built-in curve; `None` for curves added with
+    /// [`register`].
+    pub id: Option<CurveId>,
     /// Canonical, lowercase key (as accepted by CLI/APIs).
     pub key: &'static str,
     /// Human-friendly display name.
     pub display: &'static str,
     /// Human-friendly constraints summary suitable for help text.
     pub constraints: &'static str,
+    /// Asymptotic time/space cost of a single `point`/`index` call.
+    pub complexity: &'static str,
     /// Whether this curve is experimental and should be hidden in stable UIs.
     pub experimental: bool,
-    /// Build a validated grid specification for this curve.
-    pub build_spec: fn(u32, u32) -> error::Result<GridSpec>,
+    /// Whether the last point is adjacent to the first for every valid grid
+    /// larger than one cell.
+    ///
+    /// This describes the whole family. [`SpaceCurve::is_cyclic`] checks a
+    /// single curve and can also be true for a small grid of a family that
+    /// is not cyclic in general, such as a 2×2 Hilbert curve.
+    pub cyclic: bool,
+    /// Whether every step is a unit move for every valid grid.
+    pub continuous: bool,
+    /// Build a validated grid specification for this curve from the side
+    /// length along each axis.
+    pub build_spec: fn(&[u32]) -> error::Result<GridSpec>,
     /// Construct the curve given a validated grid specification.
     pub ctor: fn(&GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>>,
 }
 
+impl CurveEntry {
+    /// Whether this curve can be built in `dimension` dimensions at all.
+    pub fn accepts_dimension(&self, dimension: u32) -> bool {
+        // Every curve accepts a side of 1 in the dimensions it supports.
+        (self.build_spec)(&square_extents(dimension, 1)).is_ok()
+    }
+
+    /// Side lengths this curve accepts on a square grid in `dimension`
+    /// dimensions.
+    ///
+    /// Derived from the curve's validator, so it cannot drift from what
+    /// [`validate`] and [`construct`] accept.
+    pub fn valid_sizes(&self, dimension: u32) -> SizeRule {
+        let valid = |size: u32| (self.build_spec)(&square_extents(dimension, size)).is_ok();
+        if !valid(1) {
+            return SizeRule::Unsupported;
+        }
+        if valid(3) {
+            // Every limit on an unrestricted size bounds the grid length, so
+            // the accepted sizes are a prefix of 1..=u32::MAX.
+            let (mut lo, mut hi) = (3, u32::MAX);
+            while lo < hi {
+                let mid = lo + (hi - lo).div_ceil(2);
+                if valid(mid) {
+                    lo = mid;
+                } else {
+                    hi = mid - 1;
+                }
+            }
+            return SizeRule::Any { max: lo };
+        }
+        let max = (0..u32::BITS)
+            .map(|bits| 1 << bits)
+            .take_while(|&size| valid(size))
+            .last()
+            .unwrap_or(1);
+        SizeRule::PowerOfTwo { max }
+    }
+
+    /// Nearest sizes below and above `size` that this curve accepts in
+    /// `dimension` dimensions.
+    pub fn nearest_valid_sizes(&self, dimension: u32, size: u32) -> (Option<u32>, Option<u32>) {
+        let rule = self.valid_sizes(dimension);
+        let below = size.checked_sub(1).and_then(|s| rule.at_most(s));
+        let above = size.checked_add(1).and_then(|s| rule.at_least(s));
+        (below, above)
+    }
+}
+
+impl fmt::Display for CurveEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key)
+    }
+}
+
+/// Side lengths a curve accepts on a square grid, from
+/// [`CurveEntry::valid_sizes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeRule {
+    /// The curve cannot be built in this dimension.
+    Unsupported,
+    /// Any side length from 1 to `max`.
+    Any {
+        /// Largest accepted side length.
+        max: u32,
+    },
+    /// Powers of two from 1 to `max`.
+    PowerOfTwo {
+        /// Largest accepted side length.
+        max: u32,
+    },
+}
+
+impl SizeRule {
+    /// Whether `size` is accepted.
+    pub fn allows(self, size: u32) -> bool {
+        match self {
+            Self::Unsupported => false,
+            Self::Any { max } => (1..=max).contains(&size),
+            Self::PowerOfTwo { max } => size.is_power_of_two() && size <= max,
+        }
+    }
+
+    /// Smallest accepted size of at least `size`.
+    pub fn at_least(self, size: u32) -> Option<u32> {
+        let candidate = match self {
+            Self::Unsupported => return None,
+            Self::Any { .. } => size.max(1),
+            Self::PowerOfTwo { .. } => size.checked_next_power_of_two()?,
+        };
+        self.allows(candidate).then_some(candidate)
+    }
+
+    /// Largest accepted size of at most `size`.
+    pub fn at_most(self, size: u32) -> Option<u32> {
+        let candidate = match self {
+            Self::Unsupported => return None,
+            Self::Any { max } => size.min(max),
+            Self::PowerOfTwo { max } => match size {
+                0 => return None,
+                _ => (1 << (u32::BITS - 1 - size.leading_zeros())).min(max),
+            },
+        };
+        self.allows(candidate).then_some(candidate)
+    }
+
+    /// Accepted sizes within `range`, smallest first.
+    pub fn sizes_in(self, range: RangeInclusive<u32>) -> Vec<u32> {
+        range.filter(|&size| self.allows(size)).collect()
+    }
+}
+
+/// Extents of a square grid with `dimension` axes of side `size`.
+fn square_extents(dimension: u32, size: u32) -> Vec<u32> {
+    vec![size; dimension as usize]
+}
+
+/// Dimension and side length of a square grid, rejecting unequal extents.
+fn square(extents: &[u32]) -> error::Result<(u32, u32)> {
+    match extents {
+        [] => Err(error::Error::Shape("dimension must be >= 1".to_string())),
+        [size, rest @ ..] if rest.iter().all(|s| s == size) => Ok((extents.len() as u32, *size)),
+        _ => Err(error::Error::Shape(
+            "curve requires the same size on every axis".to_string(),
+        )),
+    }
+}
+
 // --- Per-curve validators -----------------------------------------------------
 
 /// Hilbert pre-validation aligned with constructor invariants.
-fn v_hilbert(dim: u32, size: u32) -> error::Result<GridSpec> {
+fn v_hilbert(extents: &[u32]) -> error::Result<GridSpec> {
+    let (dim, size) = square(extents)?;
     let spec = GridSpec::power_of_two(dim, size)?;
     let total_bits = (spec.order().unwrap() as u64) * (dim as u64);
-    if total_bits >= 32 {
+    if total_bits >= 64 {
         return Err(error::Error::Size(
-            "Hilbert requires order * dimension < 32 for u32 indices".to_string(),
+            "Hilbert requires order * dimension < 64 for u64 indices".to_string(),
+        ));
+    }
+    if size > 1 && dim >= 32 {
+        return Err(error::Error::Shape(
+            "Hilbert requires dimension < 32".to_string(),
         ));
     }
     Ok(spec)
 }
 
 /// H-curve pre-validation aligned with constructor invariants.
-fn v_hcurve(dim: u32, size: u32) -> error::Result<GridSpec> {
+fn v_hcurve(extents: &[u32]) -> error::Result<GridSpec> {
+    let (dim, size) = square(extents)?;
     if dim < 2 {
         return Err(error::Error::Shape("dimension must be >= 2".to_string()));
     }
@@ -44,44 +203,71 @@ fn v_hcurve(dim: u32, size: u32) -> error::Result<GridSpec> {
     if dim >= 32 {
         return Err(error::Error::Shape("dimension must be < 32".to_string()));
     }
-    if (spec.order().unwrap() as u64) * (dim as u64) >= 32 {
+    if (spec.order().unwrap() as u64) * (dim as u64) >= 64 {
         return Err(error::Error::Size(
-            "Curve size exceeds u32 limits (D*O must be < 32)".to_string(),
+            "Curve size exceeds u64 limits (D*O must be < 64)".to_string(),
         ));
     }
     Ok(spec)
 }
 
-/// Z-order (Morton) pre-validation aligned with constructor invariants.
-fn v_zorder(dim: u32, size: u32) -> error::Result<GridSpec> {
-    let spec = GridSpec::power_of_two(dim, size)?;
-    spec.require_index_bits_lt(32)?;
+/// Z-order (Morton) pre-validation: a power of two on every axis.
+fn v_zorder(extents: &[u32]) -> error::Result<GridSpec> {
+    let spec = GridSpec::power_of_two_extents(extents)?;
+    spec.require_index_bits_lt(64)?;
     Ok(spec)
 }
 
-/// Onion pre-validation: generic shape/length checks.
-fn v_onion(dim: u32, size: u32) -> error::Result<GridSpec> {
-    GridSpec::new(dim, size)
+/// Onion pre-validation: generic shape checks; the length must fit `u32`.
+#[cfg(feature = "experimental-curves")]
+fn v_onion(extents: &[u32]) -> error::Result<GridSpec> {
+    let (dim, size) = square(extents)?;
+    let spec = GridSpec::new(dim, size)?;
+    spec.require_length_le(u64::from(u32::MAX))?;
+    Ok(spec)
 }
 
-/// Hairy Onion pre-validation: generic shape/length checks.
-fn v_hairyonion(dim: u32, size: u32) -> error::Result<GridSpec> {
-    GridSpec::new(dim, size)
+/// Hairy Onion pre-validation: generic shape checks; the length must fit `u32`.
+#[cfg(feature = "experimental-curves")]
+fn v_hairyonion(extents: &[u32]) -> error::Result<GridSpec> {
+    let (dim, size) = square(extents)?;
+    let spec = GridSpec::new(dim, size)?;
+    spec.require_length_le(u64::from(u32::MAX))?;
+    Ok(spec)
 }
 
-/// Scan pre-validation: generic shape/length checks.
-fn v_scan(dim: u32, size: u32) -> error::Result<GridSpec> {
-    GridSpec::new(dim, size)
+/// Scan pre-validation: generic shape/length checks on any box.
+fn v_scan(extents: &[u32]) -> error::Result<GridSpec> {
+    GridSpec::from_extents(extents)
 }
 
-/// Gray pre-validation: generic shape/length checks.
-fn v_gray(dim: u32, size: u32) -> error::Result<GridSpec> {
-    let spec = GridSpec::power_of_two(dim, size)?;
-    if (spec.bits_per_axis().unwrap() as u64) * (dim as u64) >= 32 {
-        return Err(error::Error::Size(
-            "Gray requires bitwidth * dimension < 32 for u32 indices".to_string(),
+/// Gilbert pre-validation: any two-dimensional rectangle.
+fn v_gilbert(extents: &[u32]) -> error::Result<GridSpec> {
+    if extents.len() != 2 {
+        return Err(error::Error::Shape(
+            "Gilbert requires dimension 2".to_string(),
         ));
     }
+    GridSpec::from_extents(extents)
+}
+
+/// Sierpiński pre-validation aligned with constructor invariants.
+fn v_sierpinski(extents: &[u32]) -> error::Result<GridSpec> {
+    let (dim, size) = square(extents)?;
+    if dim != 2 {
+        return Err(error::Error::Shape(
+            "Sierpiński requires dimension 2".to_string(),
+        ));
+    }
+    let spec = GridSpec::power_of_two(dim, size)?;
+    spec.require_index_bits_lt(64)?;
+    Ok(spec)
+}
+
+/// Gray pre-validation: a power of two on every axis.
+fn v_gray(extents: &[u32]) -> error::Result<GridSpec> {
+    let spec = GridSpec::power_of_two_extents(extents)?;
+    spec.require_index_bits_lt(64)?;
     Ok(spec)
 }
 
@@ -103,12 +289,24 @@ fn c_hcurve(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
 }
 /// Construct a boxed Z-order instance.
 fn c_zorder(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
-    Ok(Box::new(zorder::ZOrder::from_dimensions(
-        spec.dimension(),
-        spec.size(),
-    )?))
+    Ok(Box::new(zorder::ZOrder::from_extents(spec.extents())?))
+}
+/// Construct a boxed column-major (y-major) Z-order instance.
+fn c_zorder_yx(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    Ok(Box::new(
+        zorder::ZOrder::from_extents(spec.extents())?
+            .with_bit_order(zorder::BitOrder::reversed_axes(spec.dimension()))?,
+    ))
+}
+/// Construct a boxed Z-order instance with reversed bit significance.
+fn c_zorder_rev(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    Ok(Box::new(
+        zorder::ZOrder::from_extents(spec.extents())?
+            .with_bit_order(zorder::BitOrder::reversed_bits(spec.dimension()))?,
+    ))
 }
 /// Construct a boxed Onion instance.
+#[cfg(feature = "experimental-curves")]
 fn c_onion(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
     Ok(Box::new(onion::OnionCurve::new(
         spec.dimension(),
@@ -116,6 +314,7 @@ fn c_onion(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
     )?))
 }
 /// Construct a boxed Hairy Onion instance.
+#[cfg(feature = "experimental-curves")]
 fn c_hairyonion(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
     Ok(Box::new(hairyonion::HairyOnionCurve::new(
         spec.dimension(),
@@ -124,43 +323,112 @@ fn c_hairyonion(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>>
 }
 /// Construct a boxed Scan instance.
 fn c_scan(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
-    Ok(Box::new(scan::Scan::from_dimensions(
-        spec.dimension(),
-        spec.size(),
-    )?))
+    Ok(Box::new(scan::Scan::from_extents(spec.extents())?))
+}
+/// Construct a boxed Coil instance.
+fn c_coil(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    Ok(Box::new(coil::Coil::from_extents(spec.extents())?))
+}
+/// Construct a boxed Shuffle instance with the default key.
+fn c_shuffle(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    Ok(Box::new(shuffle::Shuffle::from_extents(spec.extents())?))
 }
 /// Construct a boxed Gray instance.
 fn c_gray(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
-    Ok(Box::new(gray::Gray::from_dimensions(
+    Ok(Box::new(gray::Gray::from_extents(spec.extents())?))
+}
+/// Construct a boxed Gilbert instance.
+fn c_gilbert(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    let &[width, height] = spec.extents() else {
+        return Err(error::Error::Shape(
+            "Gilbert requires dimension 2".to_string(),
+        ));
+    };
+    Ok(Box::new(gilbert::Gilbert::new(width, height)?))
+}
+/// Construct a boxed Sierpiński instance.
+fn c_sierpinski(spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    Ok(Box::new(sierpinski::Sierpinski::from_dimensions(
         spec.dimension(),
         spec.size(),
     )?))
 }
 
 /// Generate the registry table and the ordered list of curve keys from one
-/// token list to avoid drift between the two.
+/// token list to avoid drift between the two. Attributes on an entry (such as
+/// a `cfg` gate) apply to everything generated for it.
 macro_rules! define_registry {
     ( $(
+        $(#[$meta:meta])*
         {
+            $id:ident = $code:literal,
             $key:literal,
             $display:literal,
             $constraints:literal,
+            $complexity:literal,
             $experimental:expr,
+            $cyclic:expr,
+            $continuous:expr,
             $validate:ident,
             $ctor:ident
         }
     ),+ $(,)? ) => {
+        /// Typed identifier for every registered curve.
+        ///
+        /// Discriminants are stable across releases: new curves get fresh codes
+        /// and retired codes are never reused, so [`CurveId::code`] can be
+        /// stored or passed across an FFI boundary. See [`CURVE_ID_VERSION`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u8)]
+        pub enum CurveId {
+            $(
+                #[doc = $display]
+                $(#[$meta])*
+                $id = $code,
+            )+
+        }
+
+        impl CurveId {
+            /// All identifiers, in registry order.
+            pub const ALL: &[Self] = &[ $( $(#[$meta])* Self::$id ),+ ];
+
+            /// Canonical lowercase key, as accepted by [`find`] and the CLI.
+            pub const fn key(self) -> &'static str {
+                match self {
+                    $( $(#[$meta])* Self::$id => $key, )+
+                }
+            }
+
+            /// Stable numeric code of this identifier.
+            pub const fn code(self) -> u8 {
+                self as u8
+            }
+
+            /// Look up an identifier by its stable numeric code.
+            pub const fn from_code(code: u8) -> Option<Self> {
+                match code {
+                    $( $(#[$meta])* $code => Some(Self::$id), )+
+                    _ => None,
+                }
+            }
+        }
+
         /// Public list of curve keys accepted by the library and CLI.
-        pub const CURVE_NAMES: &[&str] = &[ $( $key ),+ ];
+        pub const CURVE_NAMES: &[&str] = &[ $( $(#[$meta])* $key ),+ ];
 
         /// Static registry map. Keys must match `CURVE_NAMES` order.
         pub static REGISTRY: &[CurveEntry] = &[
             $(
+                $(#[$meta])*
                 CurveEntry {
+                    id: Some(CurveId::$id),
                     key: $key,
                     display: $display,
                     constraints: $constraints,
+                    complexity: $complexity,
                     experimental: $experimental,
+                    cyclic: $cyclic,
+                    continuous: $continuous,
                     build_spec: $validate,
                     ctor: $ctor,
                 },
@@ -170,34 +438,147 @@ macro_rules! define_registry {
 }
 
 define_registry! {
-    { "hilbert", "Hilbert", "size=2^order; order*dimension < 32 (u32 indices)", false, v_hilbert, c_hilbert },
-    { "scan", "Scan", "any size>=1; any dimension>=1", false, v_scan, c_scan },
-    { "zorder", "Z-order (Morton)", "size=2^bitwidth; bitwidth*dimension < 32 (u32 indices)", false, v_zorder, c_zorder },
-    { "hcurve", "H-curve", "dimension>=2; size=2^order; order*dimension < 32", false, v_hcurve, c_hcurve },
-    { "onion", "Onion", "any size>=1; any dimension>=1; length=size^dimension fits u32", false, v_onion, c_onion },
-    { "hairyonion", "Hairy Onion", "any size>=1; any dimension>=1; length=size^dimension fits u32", true, v_hairyonion, c_hairyonion },
-    { "gray", "Gray (BRGC)", "size=2^bitwidth; bitwidth*dimension < 32 (u32 indices)", false, v_gray, c_gray },
+    { Hilbert = 0, "hilbert", "Hilbert", "size=2^order; order*dimension < 64 (u64 indices)", "O(D·order) time, O(D) space", false, false, true, v_hilbert, c_hilbert },
+    { Scan = 1, "scan", "Scan", "any size>=1 per axis; any dimension>=1", "O(D) time, O(D) space", false, false, true, v_scan, c_scan },
+    { ZOrder = 2, "zorder", "Z-order (Morton)", "size=2^bitwidth per axis; total bitwidth < 64 (u64 indices)", "O(D·bitwidth) time (O(1) for 2D/3D), O(D) space", false, false, false, v_zorder, c_zorder },
+    { HCurve = 3, "hcurve", "H-curve", "dimension>=2; size=2^order; order*dimension < 64", "O(D·order) time, O(D) space", false, true, false, v_hcurve, c_hcurve },
+    #[cfg(feature = "experimental-curves")]
+    { Onion = 4, "onion", "Onion", "any size>=1; any dimension>=1; length=size^dimension fits u32", "O(D²·size) time worst case, O(D) space", false, false, false, v_onion, c_onion },
+    #[cfg(feature = "experimental-curves")]
+    { HairyOnion = 5, "hairyonion", "Hairy Onion", "any size>=1; any dimension>=1; length=size^dimension fits u32", "O(D²·size) time worst case, O(D) space", true, false, true, v_hairyonion, c_hairyonion },
+    { Gray = 6, "gray", "Gray (BRGC)", "size=2^bitwidth per axis; total bitwidth < 64 (u64 indices)", "O(D·bitwidth) time, O(D) space", false, false, false, v_gray, c_gray },
+    { Gilbert = 7, "gilbert", "Gilbert", "dimension=2; any width×height >= 1", "O(log(W·H)) time, O(1) space", false, false, false, v_gilbert, c_gilbert },
+    { Sierpinski = 8, "sierpinski", "Sierpiński", "dimension=2; size=2^order; order < 32 (u64 indices)", "O(order) time, O(1) space", false, true, false, v_sierpinski, c_sierpinski },
+    { ZOrderYx = 9, "zorder-yx", "Z-order (column-major)", "size=2^bitwidth per axis; total bitwidth < 64 (u64 indices)", "O(D·bitwidth) time, O(D) space", false, false, false, v_zorder, c_zorder_yx },
+    { ZOrderRev = 10, "zorder-rev", "Z-order (reversed bits)", "size=2^bitwidth per axis; total bitwidth < 64 (u64 indices)", "O(D·bitwidth) time, O(D) space", false, false, false, v_zorder, c_zorder_rev },
+    { Shuffle = 11, "shuffle", "Shuffle (keyed permutation)", "any size>=1 per axis; any dimension>=1", "O(D) time expected, O(D) space", false, false, false, v_scan, c_shuffle },
+    { Coil = 12, "coil", "Coil (reflected spiral)", "any size>=1 per axis; any dimension>=1", "O(D + log size) time, O(D) space", false, false, true, v_scan, c_coil },
+}
+
+/// Version of the [`CurveId`] code table.
+///
+/// Bumped whenever codes are added; existing codes keep their meaning.
+pub const CURVE_ID_VERSION: u32 = 6;
+
+impl CurveId {
+    /// Registry entry for this curve.
+    pub fn entry(self) -> &'static CurveEntry {
+        REGISTRY
+            .iter()
+            .find(|entry| entry.id == Some(self))
+            .expect("every CurveId has a registry entry")
+    }
+}
+
+impl fmt::Display for CurveId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for CurveId {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        find(s)
+            .and_then(|entry| entry.id)
+            .ok_or_else(|| error::Error::Unknown(format!("unknown pattern: \"{s}\"")))
+    }
+}
+
+/// Curves added with [`register`], in registration order. Entries are leaked
+/// so lookups can hand out `'static` references.
+#[cfg(feature = "std")]
+static REGISTERED: RwLock<Vec<&'static CurveEntry>> = RwLock::new(Vec::new());
+
+/// Call `f` with the curves added with [`register`], under the read lock.
+#[cfg(feature = "std")]
+fn with_registered<R>(f: impl FnOnce(&[&'static CurveEntry]) -> R) -> R {
+    f(&REGISTERED.read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Call `f` with the curves added at runtime; always empty without `std`.
+#[cfg(not(feature = "std"))]
+fn with_registered<R>(f: impl FnOnce(&[&'static CurveEntry]) -> R) -> R {
+    f(&[])
+}
+
+/// Add a curve defined outside this crate to the registry.
+///
+/// Afterwards [`find`], [`curve_names`] and the by-name constructors and
+/// validators accept `entry.key` like a built-in curve. `entry.id` must be
+/// `None` and the key must not be taken. Registered curves have no
+/// [`CurveId`], so they are absent from [`CURVE_NAMES`] and the typed API.
+#[cfg(feature = "std")]
+pub fn register(entry: CurveEntry) -> error::Result<()> {
+    if entry.id.is_some() {
+        return Err(error::Error::Other(format!(
+            "registered curve \"{}\" cannot claim a built-in CurveId",
+            entry.key
+        )));
+    }
+    let mut registered = REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+    if REGISTRY
+        .iter()
+        .chain(PRESETS)
+        .chain(registered.iter().copied())
+        .any(|e| e.key == entry.key)
+    {
+        return Err(error::Error::Other(format!(
+            "curve key \"{}\" is already registered",
+            entry.key
+        )));
+    }
+    registered.push(Box::leak(Box::new(entry)));
+    Ok(())
+}
+
+/// Return registry entries, optionally filtering out experimental ones.
+///
+/// Built-in curves come first, then the transform [`PRESETS`], then those
+/// added with [`register`].
+pub fn entries(include_experimental: bool) -> Vec<&'static CurveEntry> {
+    with_registered(|registered| {
+        REGISTRY
+            .iter()
+            .chain(PRESETS)
+            .chain(registered.iter().copied())
+            .filter(|entry| include_experimental || !entry.experimental)
+            .collect()
+    })
 }
 
 /// Return curve keys, optionally filtering out experimental entries.
+///
+/// Built-in curves come first, then the transform [`PRESETS`], then those
+/// added with [`register`].
 pub fn curve_names(include_experimental: bool) -> Vec<&'static str> {
-    REGISTRY
-        .iter()
-        .filter(|entry| include_experimental || !entry.experimental)
+    entries(include_experimental)
+        .into_iter()
         .map(|entry| entry.key)
         .collect()
 }
 
-/// Look up a registry entry by key (case-sensitive).
+/// Look up a registry entry by key (case-sensitive), including the transform
+/// [`PRESETS`] and curves added with [`register`].
 pub fn find(key: &str) -> Option<&'static CurveEntry> {
-    REGISTRY.iter().find(|e| e.key == key)
+    REGISTRY
+        .iter()
+        .chain(PRESETS)
+        .find(|e| e.key == key)
+        .or_else(|| with_registered(|registered| registered.iter().copied().find(|e| e.key == key)))
 }
 
 /// Validate a curve specification using the registry without constructing it.
 pub fn validate(key: &str, dimension: u32, size: u32) -> error::Result<()> {
+    validate_spec(key, &square_extents(dimension, size))
+}
+
+/// Validate a curve over a grid with the given side length per axis.
+pub fn validate_spec(key: &str, extents: &[u32]) -> error::Result<()> {
     match find(key) {
         Some(entry) => {
-            (entry.build_spec)(dimension, size)?;
+            (entry.build_spec)(extents)?;
             Ok(())
         }
         None => Err(error::Error::Unknown(format!("unknown pattern: \"{key}\""))),
@@ -210,19 +591,332 @@ pub fn construct(
     dimension: u32,
     size: u32,
 ) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    construct_spec(key, &square_extents(dimension, size))
+}
+
+/// Construct a curve by key over a grid with the given side length per axis.
+///
+/// Square-only curves reject unequal extents; see [`CurveEntry::constraints`].
+pub fn construct_spec(key: &str, extents: &[u32]) -> error::Result<Box<dyn SpaceCurve + 'static>> {
     match find(key) {
         Some(entry) => {
-            let spec = (entry.build_spec)(dimension, size)?;
+            let spec = (entry.build_spec)(extents)?;
             (entry.ctor)(&spec)
         }
         None => Err(error::Error::Unknown(format!("unknown pattern: \"{key}\""))),
     }
 }
 
+/// Validate a curve specification by typed identifier without constructing it.
+pub fn validate_id(id: CurveId, dimension: u32, size: u32) -> error::Result<()> {
+    validate_entry(id.entry(), dimension, size)
+}
+
+/// Validate the grid of the curve described by `entry` without constructing
+/// it.
+pub fn validate_entry(entry: &CurveEntry, dimension: u32, size: u32) -> error::Result<()> {
+    (entry.build_spec)(&square_extents(dimension, size))?;
+    Ok(())
+}
+
+/// Construct a curve by typed identifier after validating via the registry.
+pub fn construct_id(
+    id: CurveId,
+    dimension: u32,
+    size: u32,
+) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    construct_entry(id.entry(), dimension, size)
+}
+
+/// Construct the curve described by `entry`, built in or added with
+/// [`register`], after validating the grid.
+pub fn construct_entry(
+    entry: &CurveEntry,
+    dimension: u32,
+    size: u32,
+) -> error::Result<Box<dyn SpaceCurve + 'static>> {
+    let spec = (entry.build_spec)(&square_extents(dimension, size))?;
+    (entry.ctor)(&spec)
+}
+
+/// Operation run on a concretely typed curve by [`with_curve`].
+///
+/// `visit` is monomorphized for every curve type, so hot loops inside it call
+/// `point`/`index` directly instead of through a `dyn SpaceCurve` vtable.
+/// Curves added with [`register`] have no concrete type here and are visited
+/// as `dyn SpaceCurve`; see [`with_entry`].
+pub trait CurveVisitor {
+    /// Value produced by the visit.
+    type Output;
+
+    /// Run the operation on `curve`.
+    fn visit<C: SpaceCurve + ?Sized>(self, curve: &C) -> Self::Output;
+}
+
+/// Construct the curve `id` as its concrete type and hand it to `visitor`.
+///
+/// The generic counterpart of [`construct_id`], for callers whose per-point
+/// work is dominated by trait-object dispatch.
+pub fn with_curve<V: CurveVisitor>(
+    id: CurveId,
+    dimension: u32,
+    size: u32,
+    visitor: V,
+) -> error::Result<V::Output> {
+    let spec = (id.entry().build_spec)(&square_extents(dimension, size))?;
+    let (dimension, size) = (spec.dimension(), spec.size());
+    Ok(match id {
+        CurveId::Hilbert => visitor.visit(&hilbert::Hilbert::from_dimensions(dimension, size)?),
+        CurveId::Scan => visitor.visit(&scan::Scan::from_dimensions(dimension, size)?),
+        CurveId::ZOrder => visitor.visit(&zorder::ZOrder::from_dimensions(dimension, size)?),
+        CurveId::HCurve => visitor.visit(&hcurve::HCurve::from_dimensions(dimension, size)?),
+        #[cfg(feature = "experimental-curves")]
+        CurveId::Onion => visitor.visit(&onion::OnionCurve::new(dimension, size)?),
+        #[cfg(feature = "experimental-curves")]
+        CurveId::HairyOnion => visitor.visit(&hairyonion::HairyOnionCurve::new(dimension, size)?),
+        CurveId::Gray => visitor.visit(&gray::Gray::from_dimensions(dimension, size)?),
+        CurveId::Gilbert => visitor.visit(&gilbert::Gilbert::from_dimensions(dimension, size)?),
+        CurveId::Sierpinski => {
+            visitor.visit(&sierpinski::Sierpinski::from_dimensions(dimension, size)?)
+        }
+        CurveId::ZOrderYx => visitor.visit(
+            &zorder::ZOrder::from_dimensions(dimension, size)?
+                .with_bit_order(zorder::BitOrder::reversed_axes(dimension))?,
+        ),
+        CurveId::ZOrderRev => visitor.visit(
+            &zorder::ZOrder::from_dimensions(dimension, size)?
+                .with_bit_order(zorder::BitOrder::reversed_bits(dimension))?,
+        ),
+        CurveId::Shuffle => visitor.visit(&shuffle::Shuffle::from_dimensions(dimension, size)?),
+        CurveId::Coil => visitor.visit(&coil::Coil::from_dimensions(dimension, size)?),
+    })
+}
+
+/// Hand the curve described by `entry` to `visitor`.
+///
+/// Built-in curves are visited as their concrete type like [`with_curve`];
+/// curves added with [`register`] are visited as `dyn SpaceCurve`.
+pub fn with_entry<V: CurveVisitor>(
+    entry: &CurveEntry,
+    dimension: u32,
+    size: u32,
+    visitor: V,
+) -> error::Result<V::Output> {
+    match entry.id {
+        Some(id) => with_curve(id, dimension, size, visitor),
+        None => Ok(visitor.visit(&*construct_entry(entry, dimension, size)?)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn curve_ids_match_registry() {
+        assert_eq!(CurveId::ALL.len(), REGISTRY.len());
+        for (&id, entry) in CurveId::ALL.iter().zip(REGISTRY) {
+            assert_eq!(entry.id, Some(id));
+            assert_eq!(id.entry().key, entry.key);
+            assert_eq!(id.to_string(), entry.key);
+            assert_eq!(entry.key.parse::<CurveId>().unwrap(), id);
+            assert_eq!(CurveId::from_code(id.code()), Some(id));
+        }
+        assert_eq!(CurveId::ZOrder.code(), 2);
+        assert!("hilbret".parse::<CurveId>().is_err());
+        assert_eq!(CurveId::from_code(200), None);
+    }
+
+    #[test]
+    fn with_curve_matches_boxed_curves() {
+        /// Collects every point of the visited curve.
+        struct Points;
+
+        impl CurveVisitor for Points {
+            type Output = Vec<Vec<u32>>;
+
+            fn visit<C: SpaceCurve + ?Sized>(self, curve: &C) -> Self::Output {
+                (0..curve.length())
+                    .map(|i| curve.point(i).to_vec())
+                    .collect()
+            }
+        }
+
+        for &id in CurveId::ALL {
+            let boxed = construct_id(id, 2, 4).unwrap();
+            let expected: Vec<Vec<u32>> = (0..boxed.length())
+                .map(|i| boxed.point(i).to_vec())
+                .collect();
+            assert_eq!(with_curve(id, 2, 4, Points).unwrap(), expected, "{id}");
+        }
+        assert!(with_curve(CurveId::Hilbert, 2, 5, Points).is_err());
+    }
+
+    #[test]
+    fn extents_cover_every_point() {
+        for &id in CurveId::ALL {
+            for dimension in [2, 3] {
+                let Ok(curve) = construct_id(id, dimension, 4) else {
+                    continue;
+                };
+                let extents = curve.extents();
+                assert_eq!(extents.len(), dimension as usize, "{id}");
+                let cells: u64 = extents.iter().map(|&e| u64::from(e)).product();
+                assert_eq!(cells, curve.length(), "{id} {dimension}");
+            }
+        }
+        let gilbert = construct_spec("gilbert", &[5, 3]).unwrap();
+        assert_eq!(gilbert.extents().as_slice(), [5, 3]);
+    }
+
+    #[test]
+    fn index_many_matches_index() {
+        for &id in CurveId::ALL {
+            for dimension in [2, 3, 5] {
+                let Ok(curve) = construct_id(id, dimension, 4) else {
+                    continue;
+                };
+                let mut points: Vec<_> = curve.points().collect();
+                points.reverse();
+                let mut indices = vec![7];
+                curve.index_many(&points, &mut indices);
+                let expected: Vec<u64> = (0..curve.length()).rev().collect();
+                assert_eq!(indices[0], 7);
+                assert_eq!(indices[1..], expected[..], "{id} {dimension}");
+            }
+        }
+    }
+
+    #[test]
+    fn extents_reach_rectangular_curves_only() {
+        let gilbert = construct_spec("gilbert", &[5, 3]).unwrap();
+        assert_eq!(gilbert.length(), 15);
+        assert_eq!(construct_spec("scan", &[5, 3]).unwrap().length(), 15);
+        assert_eq!(
+            construct_spec("zorder", &[8, 2, 4])
+                .unwrap()
+                .extents()
+                .as_slice(),
+            [8, 2, 4]
+        );
+        assert!(construct_spec("gray", &[8, 2]).is_ok());
+        assert!(construct_spec("zorder", &[8, 3]).is_err());
+        assert!(construct_spec("hilbert", &[8, 4]).is_err());
+        assert!(construct_spec("gilbert", &[]).is_err());
+        assert!(validate_spec("gilbert", &[2, 2, 2]).is_err());
+        assert!(CurveId::Gilbert.entry().accepts_dimension(2));
+        assert!(!CurveId::Gilbert.entry().accepts_dimension(3));
+        assert!(!CurveId::HCurve.entry().accepts_dimension(1));
+    }
+
+    #[test]
+    fn nearest_valid_sizes_bracket_invalid_sizes() {
+        let hilbert = CurveId::Hilbert.entry();
+        assert_eq!(hilbert.nearest_valid_sizes(2, 5), (Some(4), Some(8)));
+        assert_eq!(hilbert.nearest_valid_sizes(2, 8), (Some(4), Some(16)));
+        assert_eq!(hilbert.nearest_valid_sizes(2, 1), (None, Some(2)));
+        assert_eq!(
+            CurveId::Scan.entry().nearest_valid_sizes(2, 5),
+            (Some(4), Some(6))
+        );
+    }
+
+    #[test]
+    fn presets_are_found_by_name() {
+        assert!(curve_names(false).contains(&"hilbert-of-zorder"));
+        let entry = find("scan-columns").unwrap();
+        assert_eq!(entry.id, None);
+        assert!("scan-columns".parse::<CurveId>().is_err());
+        let curve = construct_spec("scan-columns", &[3, 2]).unwrap();
+        assert_eq!(curve.length(), 6);
+        assert_eq!(curve.point(1)[..], [0, 1]);
+        assert!(validate("hilbert-top-right", 3, 8).is_err());
+        assert!(validate("hilbert-of-zorder", 2, 6).is_err());
+        assert!(validate("hilbert-of-zorder", 2, 2).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn registered_curves_are_found_by_name() {
+        use crate::transform::Reversed;
+
+        let entry = || CurveEntry {
+            id: None,
+            key: "reversed-scan",
+            display: "Reversed scan",
+            constraints: "any size>=1 per axis",
+            complexity: "O(D) time, O(D) space",
+            experimental: true,
+            cyclic: false,
+            continuous: true,
+            build_spec: v_scan,
+            ctor: |spec| Ok(Box::new(Reversed::new(c_scan(spec)?))),
+        };
+        register(entry()).unwrap();
+        assert!(register(entry()).is_err());
+        assert!(
+            register(CurveEntry {
+                key: "hilbert",
+                ..entry()
+            })
+            .is_err()
+        );
+        assert!(
+            register(CurveEntry {
+                id: Some(CurveId::Scan),
+                key: "claims-scan",
+                ..entry()
+            })
+            .is_err()
+        );
+
+        assert!(curve_names(true).contains(&"reversed-scan"));
+        assert!(!curve_names(false).contains(&"reversed-scan"));
+        assert_eq!(find("reversed-scan").unwrap().display, "Reversed scan");
+        assert!("reversed-scan".parse::<CurveId>().is_err());
+        let (curve, scan) = (
+            construct("reversed-scan", 2, 3).unwrap(),
+            construct("scan", 2, 3).unwrap(),
+        );
+        assert_eq!(curve.point(0), scan.point(8));
+        assert!(validate("reversed-scan", 2, 0).is_err());
+    }
+
+    #[test]
+    fn size_rules_follow_validators() {
+        let hilbert = CurveId::Hilbert.entry().valid_sizes(2);
+        assert_eq!(hilbert, SizeRule::PowerOfTwo { max: 1 << 31 });
+        assert_eq!(hilbert.sizes_in(3..=40), [4, 8, 16, 32]);
+        assert_eq!(hilbert.at_least(33), Some(64));
+        assert_eq!(hilbert.at_most(33), Some(32));
+        assert_eq!(
+            CurveId::Scan.entry().valid_sizes(2),
+            SizeRule::Any { max: u32::MAX }
+        );
+        assert_eq!(
+            CurveId::Gilbert.entry().valid_sizes(3),
+            SizeRule::Unsupported
+        );
+        assert_eq!(CurveId::Sierpinski.entry().valid_sizes(3).at_least(1), None);
+        for &id in CurveId::ALL {
+            let entry = id.entry();
+            for dimension in 1..=4 {
+                let rule = entry.valid_sizes(dimension);
+                assert_eq!(
+                    rule != SizeRule::Unsupported,
+                    entry.accepts_dimension(dimension)
+                );
+                for size in 1..=70 {
+                    assert_eq!(
+                        rule.allows(size),
+                        validate_id(id, dimension, size).is_ok(),
+                        "{id} {dimension}D size {size}"
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_registry_consistency() {
         let mut registry_keys: Vec<&str> = REGISTRY.iter().map(|e| e.key).collect();
@@ -0,0 +1,275 @@
+//! Segments between consecutive curve points and their adjacency.
+//!
+//! Renderers use adjacency to tell ordinary unit steps apart from the long
+//! jumps some curves make between distant cells. What counts as adjacent is an
+//! [`Adjacency`] rule: a [`Metric`] plus a distance threshold, so that e.g. the
+//! diagonal steps of Z-order can be told apart from its longer jumps.
+//!
+//! [`Adjacency`]: crate::segment::Adjacency
+//! [`Metric`]: crate::segment::Metric
+
+use alloc::format;
+use core::{fmt, ops::Range, str::FromStr};
+
+use crate::{error, point::Point, spacecurve::SpaceCurve};
+
+/// Distance measure between grid points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Metric {
+    /// Sum of per-axis differences.
+    #[default]
+    Manhattan,
+    /// Largest per-axis difference.
+    Chebyshev,
+    /// Straight-line distance.
+    Euclidean,
+}
+
+impl Metric {
+    /// Every metric, in presentation order.
+    pub const ALL: [Self; 3] = [Self::Manhattan, Self::Chebyshev, Self::Euclidean];
+
+    /// Lowercase name accepted by [`FromStr`].
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Manhattan => "manhattan",
+            Self::Chebyshev => "chebyshev",
+            Self::Euclidean => "euclidean",
+        }
+    }
+
+    /// Distance between `a` and `b` under this metric.
+    pub fn distance(self, a: &[u32], b: &[u32]) -> f64 {
+        let deltas = a.iter().zip(b).map(|(&x, &y)| u64::from(x.abs_diff(y)));
+        match self {
+            Self::Manhattan => deltas.sum::<u64>() as f64,
+            Self::Chebyshev => deltas.max().unwrap_or(0) as f64,
+            Self::Euclidean => libm::sqrt(deltas.map(|d| d * d).sum::<u64>() as f64),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for Metric {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|metric| metric.key().eq_ignore_ascii_case(s))
+            .ok_or_else(|| error::Error::Unknown(format!("unknown metric: \"{s}\"")))
+    }
+}
+
+/// Rule deciding which steps count as adjacent: those whose distance under
+/// `metric` is at most `threshold`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adjacency {
+    /// Metric used to measure each step.
+    pub metric: Metric,
+    /// Largest distance still considered adjacent.
+    pub threshold: f64,
+}
+
+impl Adjacency {
+    /// Unit steps between neighbouring cells (Manhattan distance ≤ 1).
+    pub const UNIT: Self = Self::new(Metric::Manhattan, 1.0);
+
+    /// Treats every step as adjacent.
+    pub const ANY: Self = Self::new(Metric::Manhattan, f64::INFINITY);
+
+    /// Build a rule from a metric and threshold.
+    pub const fn new(metric: Metric, threshold: f64) -> Self {
+        Self { metric, threshold }
+    }
+
+    /// Whether the step from `a` to `b` is adjacent under this rule.
+    pub fn contains(&self, a: &[u32], b: &[u32]) -> bool {
+        self.metric.distance(a, b) <= self.threshold
+    }
+}
+
+impl Default for Adjacency {
+    fn default() -> Self {
+        Self::UNIT
+    }
+}
+
+/// Whether `a` and `b` are the same or neighbouring grid cells (Manhattan distance ≤ 1).
+pub fn is_adjacent(a: &[u32], b: &[u32]) -> bool {
+    Adjacency::UNIT.contains(a, b)
+}
+
+/// The step from point `index` to the point after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Curve index of the starting point.
+    pub index: u64,
+    /// Starting point.
+    pub a: Point,
+    /// Ending point.
+    pub b: Point,
+    /// Whether the step is adjacent under the rule it was built with.
+    pub adjacent: bool,
+}
+
+impl Segment {
+    /// Build the segment from `a` (at `index`) to `b`, using [`Adjacency::UNIT`].
+    pub fn new(index: u64, a: Point, b: Point) -> Self {
+        Self::with_adjacency(index, a, b, Adjacency::UNIT)
+    }
+
+    /// Build the segment from `a` (at `index`) to `b`, classified by `adjacency`.
+    pub fn with_adjacency(index: u64, a: Point, b: Point, adjacency: Adjacency) -> Self {
+        let adjacent = adjacency.contains(&a, &b);
+        Self {
+            index,
+            a,
+            b,
+            adjacent,
+        }
+    }
+}
+
+/// Lazy iterator over the segments of a curve, returned by `segments()` on a `dyn SpaceCurve`.
+///
+/// Each point is computed once even though it ends one segment and starts the next.
+pub struct Segments<'a> {
+    /// Curve being walked.
+    curve: &'a dyn SpaceCurve,
+    /// Index of the ending point of the next segment.
+    next: u64,
+    /// One past the last point included.
+    end: u64,
+    /// Ending point of the previous segment, reused as the next start.
+    prev: Option<Point>,
+    /// Rule used to classify each segment.
+    adjacency: Adjacency,
+}
+
+impl<'a> Segments<'a> {
+    /// Walk the segments joining consecutive points in `points`, clamped to
+    /// the curve length.
+    pub(crate) fn new(curve: &'a dyn SpaceCurve, points: Range<u64>) -> Self {
+        Self {
+            curve,
+            next: points.start.saturating_add(1),
+            end: points.end.min(curve.length()),
+            prev: None,
+            adjacency: Adjacency::UNIT,
+        }
+    }
+
+    /// Classify segments with `adjacency` instead of [`Adjacency::UNIT`].
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        if self.next >= self.end {
+            return None;
+        }
+        let a = match self.prev.take() {
+            Some(point) => point,
+            None => self.curve.point(self.next - 1),
+        };
+        let mut b = a.clone();
+        self.curve.advance_point(self.next - 1, &mut b);
+        self.prev = Some(b.clone());
+        self.next += 1;
+        Some(Segment::with_adjacency(self.next - 2, a, b, self.adjacency))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.saturating_sub(self.next) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Segments<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use super::*;
+    use crate::curve_from_name;
+
+    #[test]
+    fn adjacency_uses_manhattan_distance() {
+        assert!(is_adjacent(&[2, 3], &[2, 4]));
+        assert!(is_adjacent(&[2, 3, 1], &[2, 3, 1]));
+        assert!(!is_adjacent(&[2, 3], &[3, 4]));
+        assert!(!is_adjacent(&[0, 0, 0], &[0, 0, 2]));
+    }
+
+    #[test]
+    fn segments_walk_the_curve_once() {
+        let curve = curve_from_name("zorder", 2, 4).unwrap();
+        let segments: Vec<Segment> = curve.segments().collect();
+        assert_eq!(segments.len(), 15);
+        for (i, seg) in segments.iter().enumerate() {
+            assert_eq!(seg.index, i as u64);
+            assert_eq!(seg.a, curve.point(i as u64));
+            assert_eq!(seg.b, curve.point(i as u64 + 1));
+        }
+        // (1,0) → (0,1) is a diagonal jump; (0,0) → (1,0) is a unit step.
+        assert!(segments[0].adjacent);
+        assert!(!segments[1].adjacent);
+        assert!(
+            curve
+                .segments()
+                .all(|s| s.adjacent == is_adjacent(&s.a, &s.b))
+        );
+    }
+
+    #[test]
+    fn segments_in_clamps_ranges() {
+        let curve = curve_from_name("hilbert", 2, 4).unwrap();
+        assert_eq!(curve.segments_in(3..7).len(), 3);
+        assert_eq!(curve.segments_in(14..40).len(), 1);
+        assert_eq!(curve.segments_in(5..6).count(), 0);
+        assert!(curve.segments().all(|s| s.adjacent));
+    }
+
+    #[test]
+    fn metrics_measure_steps() {
+        let (a, b) = ([1, 5, 2], [4, 1, 2]);
+        assert_eq!(Metric::Manhattan.distance(&a, &b), 7.0);
+        assert_eq!(Metric::Chebyshev.distance(&a, &b), 4.0);
+        assert_eq!(Metric::Euclidean.distance(&a, &b), 5.0);
+        for metric in Metric::ALL {
+            assert_eq!(metric.to_string().parse::<Metric>().unwrap(), metric);
+        }
+        assert!("taxicab".parse::<Metric>().is_err());
+    }
+
+    #[test]
+    fn adjacency_rules_classify_zorder_jumps() {
+        let curve = curve_from_name("zorder", 2, 4).unwrap();
+        let count = |adjacency| {
+            curve
+                .segments()
+                .with_adjacency(adjacency)
+                .filter(|s| !s.adjacent)
+                .count()
+        };
+        // Six diagonal steps are long under Manhattan but not Chebyshev; the
+        // (3,1) → (0,2) jump between quadrants is long under both.
+        assert_eq!(count(Adjacency::UNIT), 7);
+        assert_eq!(count(Adjacency::new(Metric::Chebyshev, 1.0)), 1);
+        assert_eq!(count(Adjacency::new(Metric::Euclidean, 3.0)), 1);
+        assert_eq!(count(Adjacency::new(Metric::Euclidean, 3.5)), 0);
+        assert_eq!(count(Adjacency::ANY), 0);
+    }
+}
@@ -1,8 +1,18 @@
 //! The `SpaceCurve` trait describing a family of curves.
 
-use std::fmt;
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
 
-use crate::point;
+use smallvec::{SmallVec, smallvec};
+
+use crate::{
+    neighbor::Neighbors,
+    point::{self, Points},
+    query,
+    segment::{self, Segments},
+    spec,
+    wrap::{self, CyclicRange},
+};
 
 /// SpaceCurve is the core trait for space‑filling curves.
 ///
@@ -10,12 +20,17 @@ use crate::point;
 /// - `dimensions()` is fixed at construction and defines the required point arity.
 /// - `index` expects a [`point::Point`] whose length matches `dimensions()` and whose
 ///   coordinates lie in `[0, size-1]` for the curve.
-/// - `point` expects `index < length()`.
+/// - `point` expects `index < length()`. Indices are `u64`, so curves can hold
+///   more than `2^32` points while coordinates stay `u32`.
 /// - Constructors are responsible for validating dimensionality and bounds (via
 ///   the shared [`spec::GridSpec`] helpers); callers should treat out‑of‑range
 ///   inputs as undefined behaviour. Implementations retain lightweight
 ///   `debug_assert!` guards for development builds.
-pub trait SpaceCurve: fmt::Debug {
+/// - Curves are immutable after construction and must be `Send + Sync`, so one
+///   instance (e.g. an [`Arc`](alloc::sync::Arc) from
+///   [`shared_curve_from_name`](crate::shared_curve_from_name)) can be shared
+///   by worker threads.
+pub trait SpaceCurve: fmt::Debug + Send + Sync {
     /// A short human-friendly name for this curve.
     ///
     /// This is intended for UI display and logs.
@@ -28,12 +43,159 @@ pub trait SpaceCurve: fmt::Debug {
     fn info(&self) -> &'static str;
     /// Calculate the linear index of an N-dimensional point. The dimension of
     /// the point must match that of the curve.
-    fn index(&self, p: &point::Point) -> u32;
+    fn index(&self, p: &point::Point) -> u64;
     /// Calculate the coordinates of a point from a linear index. The returned
     /// point will have a dimension matching that of the curve.
-    fn point(&self, index: u32) -> point::Point;
+    fn point(&self, index: u64) -> point::Point;
     /// What is the maximum linear offset supported by this curve?
-    fn length(&self) -> u32;
+    fn length(&self) -> u64;
     /// How many dimensions does the curve have?
     fn dimensions(&self) -> u32;
+
+    /// Side length of the grid along each axis.
+    ///
+    /// The default is a cube holding `length()` points; curves over other
+    /// shapes, like Gilbert rectangles, must override it.
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        let side = spec::cube_side(self.length(), self.dimensions())
+            .expect("curve length is not a cube; override `extents`");
+        smallvec![side; self.dimensions() as usize]
+    }
+
+    /// Append the index of every point in `points` to `out`, in order.
+    ///
+    /// Each point must satisfy the preconditions of [`index`](Self::index).
+    /// One call covers the whole batch, so indexing many points through a
+    /// `dyn SpaceCurve` dispatches once rather than per point. The default
+    /// indexes each point in turn; Z-order and the 2D Hilbert curve override
+    /// it with loops specialised for their dimension.
+    fn index_many(&self, points: &[point::Point], out: &mut Vec<u64>) {
+        out.reserve(points.len());
+        out.extend(points.iter().map(|p| self.index(p)));
+    }
+
+    /// Replace `point`, the point at `index`, with the point at `index + 1`.
+    ///
+    /// `index + 1` must be below `length()`. The default recomputes the point
+    /// from scratch; curves that can derive it from its predecessor override
+    /// this so full-curve walks avoid a per-point `O(order)` decode.
+    fn advance_point(&self, index: u64, point: &mut point::Point) {
+        *point = self.point(index + 1);
+    }
+
+    /// Coordinates a fraction `t` of the way along the curve.
+    ///
+    /// `t` is clamped to `[0, 1]`; `0` is the first point, `1` the last, and
+    /// positions between two indices interpolate linearly between their
+    /// points. Animations use this to place things between cells, and
+    /// because every curve of a given size spans the same range of `t`, two
+    /// curves can be blended at equal `t` for smooth morphs.
+    fn point_at(&self, t: f64) -> SmallVec<[f64; 8]> {
+        let last = self.length().saturating_sub(1);
+        let position = t.clamp(0.0, 1.0) * last as f64;
+        // Truncation is floor here: `position` is non-negative.
+        let index = (position as u64).min(last);
+        let from = self.point(index);
+        if index == last {
+            return from.iter().map(|&c| f64::from(c)).collect();
+        }
+        from.lerp(&self.point(index + 1), position - index as f64)
+    }
+
+    /// Append the points with indices in `range` to `out`, in order.
+    ///
+    /// The range is clamped to `length()`. The first point is decoded and the
+    /// rest are stepped with [`advance_point`](Self::advance_point), so batch
+    /// fills of caches and maps get each curve's incremental step; generic
+    /// code can use this where `points()` on a `dyn SpaceCurve` is unavailable.
+    fn points_range(&self, range: Range<u64>, out: &mut Vec<point::Point>) {
+        let end = range.end.min(self.length());
+        if range.start >= end {
+            return;
+        }
+        out.reserve((end - range.start) as usize);
+        let mut point = self.point(range.start);
+        for index in range.start..end - 1 {
+            let next = point.clone();
+            out.push(point);
+            point = next;
+            self.advance_point(index, &mut point);
+        }
+        out.push(point);
+    }
+
+    /// The fewest contiguous index ranges covering exactly the cells of the
+    /// axis-aligned box from `lo` to `hi`, both inclusive, in index order.
+    ///
+    /// `lo` and `hi` need one in-bounds coordinate per dimension; a box with
+    /// `lo > hi` on any axis is empty. The default indexes every cell in the
+    /// box; Hilbert and Z-order descend their block hierarchy instead.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        query::scan_ranges(self, lo, hi)
+    }
+
+    /// Whether the last point is adjacent to the first, so the curve closes
+    /// into a loop and wrapping animations never jump. A single point is not
+    /// a loop.
+    ///
+    /// The default decodes both end points and compares them; curves that
+    /// know the answer without decoding override it.
+    fn is_cyclic(&self) -> bool {
+        let length = self.length();
+        length > 1 && segment::is_adjacent(&self.point(length - 1), &self.point(0))
+    }
+
+    /// Move `index` by `delta` steps along the curve, wrapping past either end.
+    fn advance(&self, index: u64, delta: i64) -> u64 {
+        wrap::advance(self.length(), index, delta)
+    }
+
+    /// Number of forward steps from index `from` to index `to`, wrapping past
+    /// the end.
+    fn offset_between(&self, from: u64, to: u64) -> u64 {
+        wrap::offset_between(self.length(), from, to)
+    }
+
+    /// The `len` consecutive indices from `start`, continuing from index 0
+    /// after the last point.
+    fn cyclic_range(&self, start: u64, len: u64) -> CyclicRange {
+        CyclicRange::new(self.length(), start, len)
+    }
+}
+
+impl dyn SpaceCurve + '_ {
+    /// Iterate over every point of the curve, in order.
+    pub fn points(&self) -> Points<'_> {
+        self.points_in(0..self.length())
+    }
+
+    /// Iterate over the points with indices in `indices`.
+    ///
+    /// The range is clamped to the curve length.
+    pub fn points_in(&self, indices: Range<u64>) -> Points<'_> {
+        Points::new(self, indices)
+    }
+
+    /// Iterate over the curve indices of the cells one step along an axis
+    /// from the point at `index`, lowest axis first and the lower cell before
+    /// the upper one.
+    ///
+    /// Cells outside [`extents`](SpaceCurve::extents) are skipped, so corner
+    /// points have `dimensions()` neighbours and interior points twice that.
+    pub fn neighbors(&self, index: u64) -> Neighbors<'_> {
+        Neighbors::new(self, index)
+    }
+
+    /// Iterate over every segment of the curve, in order.
+    pub fn segments(&self) -> Segments<'_> {
+        self.segments_in(0..self.length())
+    }
+
+    /// Iterate over the segments joining consecutive points in `points`.
+    ///
+    /// The range is clamped to the curve length; fewer than two points yield
+    /// no segments.
+    pub fn segments_in(&self, points: Range<u64>) -> Segments<'_> {
+        Segments::new(self, points)
+    }
 }
@@ -1,19 +1,27 @@
 //! Grid specification helpers used by curve constructors and registry validation.
 
+use alloc::{format, string::ToString};
+
+use smallvec::{SmallVec, smallvec};
+
 use crate::{error, error::Error};
 
-/// Describes the dimensionality and side length of a grid along with derived values.
+/// Describes the dimensionality and extents of a grid along with derived values.
 ///
-/// The helper centralizes guard logic (non‑zero sizes, power‑of‑two checks, overflow checks)
-/// so curve constructors can focus on their own algorithmic invariants.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Grids are square (every axis has the same side length) unless built with
+/// [`GridSpec::from_extents`] or [`GridSpec::power_of_two_extents`]. The helper centralizes guard logic (non‑zero sizes,
+/// power‑of‑two checks, overflow checks) so curve constructors can focus on their
+/// own algorithmic invariants.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GridSpec {
     /// Number of dimensions in the grid.
     dimension: u32,
-    /// Side length per dimension.
+    /// Side length per dimension; the largest extent for rectangular grids.
     size: u32,
+    /// Side length along each axis.
+    extents: SmallVec<[u32; 4]>,
     /// Total number of points (`size^dimension`).
-    length: u32,
+    length: u64,
     /// Order (bits per axis) when `size` is a power of two.
     order: Option<u32>,
     /// Bit width per axis when `size` is a power of two.
@@ -25,7 +33,7 @@ impl GridSpec {
     ///
     /// - `dimension` must be >= 1
     /// - `size` must be >= 1
-    /// - `size.pow(dimension)` must fit inside `u32`
+    /// - `size.pow(dimension)` must fit inside `u64`
     pub fn new(dimension: u32, size: u32) -> error::Result<Self> {
         if dimension == 0 {
             return Err(Error::Shape("dimension must be >= 1".to_string()));
@@ -34,13 +42,45 @@ impl GridSpec {
             return Err(Error::Size("size must be >= 1".to_string()));
         }
 
-        let length = size.checked_pow(dimension).ok_or_else(|| {
-            Error::Size("curve length (size^dimension) exceeds u32 bounds".to_string())
+        let length = u64::from(size).checked_pow(dimension).ok_or_else(|| {
+            Error::Size("curve length (size^dimension) exceeds u64 bounds".to_string())
         })?;
 
         Ok(Self {
             dimension,
             size,
+            extents: smallvec![size; dimension as usize],
+            length,
+            order: None,
+            bits_per_axis: None,
+        })
+    }
+
+    /// Construct a spec for a grid with its own side length on each axis.
+    ///
+    /// - `extents` needs at least one axis, each at least 1
+    /// - the product of `extents` must fit inside `u64`
+    pub fn from_extents(extents: &[u32]) -> error::Result<Self> {
+        let Some(&size) = extents.iter().max() else {
+            return Err(Error::Shape("dimension must be >= 1".to_string()));
+        };
+        if extents.contains(&0) {
+            return Err(Error::Size("every extent must be >= 1".to_string()));
+        }
+
+        let length = extents
+            .iter()
+            .try_fold(1u64, |length, &extent| {
+                length.checked_mul(u64::from(extent))
+            })
+            .ok_or_else(|| {
+                Error::Size("curve length (product of extents) exceeds u64 bounds".to_string())
+            })?;
+
+        Ok(Self {
+            dimension: extents.len() as u32,
+            size,
+            extents: extents.into(),
             length,
             order: None,
             bits_per_axis: None,
@@ -64,33 +104,77 @@ impl GridSpec {
         Ok(spec)
     }
 
+    /// Construct a spec for a grid with its own side length on each axis,
+    /// requiring every extent to be a positive power of two.
+    ///
+    /// `order` and `bits_per_axis` describe the largest axis; see
+    /// [`GridSpec::axis_bits`] for the width of each.
+    pub fn power_of_two_extents(extents: &[u32]) -> error::Result<Self> {
+        if !extents.iter().all(|extent| extent.is_power_of_two()) {
+            return Err(Error::Size(
+                "every extent must be a positive power of two".to_string(),
+            ));
+        }
+
+        let mut spec = Self::from_extents(extents)?;
+        let order = spec.size.trailing_zeros();
+        spec.order = Some(order);
+        spec.bits_per_axis = Some(order);
+        Ok(spec)
+    }
+
     /// Require that the total number of index bits is strictly less than `limit`.
     ///
-    /// Useful for curves that encode indices into `u32` using `bits_per_axis * dimension`.
+    /// Useful for curves that encode indices into `u64` using `bits_per_axis` bits
+    /// from each axis.
     pub fn require_index_bits_lt(&self, limit: u32) -> error::Result<()> {
-        if let Some(bits) = self.bits_per_axis {
-            let total_bits = (bits as u64) * (self.dimension as u64);
+        if let Some(bits) = self.axis_bits() {
+            let total_bits: u64 = bits.iter().map(|&b| u64::from(b)).sum();
             if total_bits >= limit as u64 {
                 return Err(Error::Size(format!(
-                    "index requires {total_bits} bits; must be < {limit} for u32 indices"
+                    "index requires {total_bits} bits; must be < {limit}"
                 )));
             }
         }
         Ok(())
     }
 
+    /// Require that the grid holds at most `limit` points.
+    ///
+    /// Useful for curves whose internal arithmetic is narrower than the `u64`
+    /// index type.
+    pub fn require_length_le(&self, limit: u64) -> error::Result<()> {
+        if self.length > limit {
+            return Err(Error::Size(format!(
+                "curve length {} exceeds the supported maximum of {limit}",
+                self.length
+            )));
+        }
+        Ok(())
+    }
+
     /// Dimension count.
     pub fn dimension(&self) -> u32 {
         self.dimension
     }
 
-    /// Side length.
+    /// Side length; the largest extent for rectangular grids.
     pub fn size(&self) -> u32 {
         self.size
     }
 
-    /// Total number of points in the grid (`size^dimension`).
-    pub fn length(&self) -> u32 {
+    /// Side length along each axis.
+    pub fn extents(&self) -> &[u32] {
+        &self.extents
+    }
+
+    /// Whether every axis has the same side length.
+    pub fn is_square(&self) -> bool {
+        self.extents.iter().all(|&extent| extent == self.size)
+    }
+
+    /// Total number of points in the grid (product of the extents).
+    pub fn length(&self) -> u64 {
         self.length
     }
 
@@ -99,8 +183,33 @@ impl GridSpec {
         self.order
     }
 
-    /// Bit width per coordinate for power‑of‑two grids (when available).
+    /// Bit width per coordinate for power‑of‑two grids (when available); the
+    /// width of the largest axis for rectangular grids.
     pub fn bits_per_axis(&self) -> Option<u32> {
         self.bits_per_axis
     }
+
+    /// Bit width of each axis for power‑of‑two grids (when available).
+    pub fn axis_bits(&self) -> Option<SmallVec<[u32; 4]>> {
+        self.order?;
+        Some(self.extents.iter().map(|e| e.trailing_zeros()).collect())
+    }
+}
+
+/// Side length of a cube holding `length` cells in `dimension` dimensions, or
+/// `None` when `length` is not a perfect power.
+pub fn cube_side(length: u64, dimension: u32) -> Option<u32> {
+    if dimension == 0 {
+        return None;
+    }
+    // Binary search for the largest side whose power does not exceed `length`.
+    let (mut lo, mut hi) = (0u64, u64::from(u32::MAX));
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        match mid.checked_pow(dimension) {
+            Some(v) if v <= length => lo = mid,
+            _ => hi = mid - 1,
+        }
+    }
+    (lo.pow(dimension) == length).then_some(lo as u32)
 }
@@ -0,0 +1,434 @@
+//! Compact point tables for caching precomputed curves.
+//!
+//! Consecutive points of a continuous curve differ by one step along one
+//! axis, so a table stores each step as a move code of 2 bits (up to two
+//! dimensions) or 4 bits (up to eight) instead of a full point. Steps that
+//! are not unit moves, like the jumps of Z-order, are stored as full points
+//! in a jump list, and every [`KEYFRAME_INTERVAL`]th point is stored in full
+//! so decoding can start near any index.
+//!
+//! All values are little-endian. The layout is a 32-byte header (magic
+//! `SCPT`, version, dimension, bits per move, a reserved byte, the point
+//! count and the jump count as `u64`, the keyframe interval as `u32` and four
+//! reserved bytes), followed by the keyframes, the jumps (the `u64` index of the point
+//! reached, then the point) and the packed move codes, lowest bits first.
+//!
+//! [`encode`] writes a table and [`PointTable::parse`] reads one in place, so
+//! a table can be decoded straight from a memory-mapped file. With `std`,
+//! [`save`] and [`load`] store and read tables as files.
+//!
+//! [`KEYFRAME_INTERVAL`]: crate::table::KEYFRAME_INTERVAL
+//! [`encode`]: crate::table::encode
+//! [`PointTable::parse`]: crate::table::PointTable::parse
+//! [`save`]: crate::table::save
+//! [`load`]: crate::table::load
+
+use alloc::{format, string::ToString, vec::Vec};
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+use smallvec::SmallVec;
+
+use crate::{error, point::Point, spacecurve::SpaceCurve};
+
+/// File magic at the start of every table.
+const MAGIC: &[u8; 4] = b"SCPT";
+/// Format version written by [`encode`].
+const VERSION: u8 = 2;
+/// Size of the fixed header in bytes.
+const HEADER_LEN: usize = 32;
+/// Number of points between full keyframes.
+pub const KEYFRAME_INTERVAL: u32 = 4096;
+/// Largest dimension a move code can describe.
+pub const MAX_DIMENSION: u32 = 8;
+
+/// Bits per move code for a curve of `dimension` axes.
+fn move_bits(dimension: u32) -> u32 {
+    if dimension <= 2 { 2 } else { 4 }
+}
+
+/// Move code of the unit step from `a` to `b`, or `None` for any other step.
+fn move_code(a: &[u32], b: &[u32]) -> Option<u8> {
+    let mut code = None;
+    for (axis, (&x, &y)) in a.iter().zip(b).enumerate() {
+        if x == y {
+            continue;
+        }
+        if code.is_some() || x.abs_diff(y) != 1 {
+            return None;
+        }
+        code = Some(axis as u8 * 2 + u8::from(y > x));
+    }
+    code
+}
+
+/// Append `point` to `out` as little-endian `u32` coordinates.
+fn put_point(out: &mut Vec<u8>, point: &[u32]) {
+    for &coord in point {
+        out.extend_from_slice(&coord.to_le_bytes());
+    }
+}
+
+/// Encode every point of `curve` as a point table.
+///
+/// Fails for curves of more than [`MAX_DIMENSION`] dimensions.
+pub fn encode<C: SpaceCurve + ?Sized>(curve: &C) -> error::Result<Vec<u8>> {
+    let dimension = curve.dimensions();
+    if dimension == 0 || dimension > MAX_DIMENSION {
+        return Err(error::Error::Shape(format!(
+            "point tables support 1 to {MAX_DIMENSION} dimensions, not {dimension}"
+        )));
+    }
+    let length = curve.length();
+    let bits = move_bits(dimension);
+    let interval = u64::from(KEYFRAME_INTERVAL);
+
+    let mut keyframes = Vec::new();
+    let mut jumps = Vec::new();
+    let mut jump_count: u64 = 0;
+    let mut moves =
+        Vec::with_capacity((length.saturating_sub(1) * u64::from(bits)).div_ceil(8) as usize);
+    let mut packed = 0u8;
+    let mut filled = 0;
+    let mut batch = Vec::new();
+    let mut prev: Option<Point> = None;
+    let mut start = 0;
+    while start < length {
+        batch.clear();
+        curve.points_range(start..start.saturating_add(interval), &mut batch);
+        for (index, point) in (start..).zip(batch.drain(..)) {
+            if index % interval == 0 {
+                put_point(&mut keyframes, &point);
+            }
+            if let Some(prev) = &prev {
+                let code = move_code(prev, &point).unwrap_or_else(|| {
+                    jumps.extend_from_slice(&index.to_le_bytes());
+                    put_point(&mut jumps, &point);
+                    jump_count += 1;
+                    0
+                });
+                packed |= code << filled;
+                filled += bits;
+                if filled == 8 {
+                    moves.push(packed);
+                    (packed, filled) = (0, 0);
+                }
+            }
+            prev = Some(point);
+        }
+        start += interval;
+    }
+    if filled > 0 {
+        moves.push(packed);
+    }
+
+    let mut out = Vec::with_capacity(HEADER_LEN + keyframes.len() + jumps.len() + moves.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[VERSION, dimension as u8, bits as u8, 0]);
+    out.extend_from_slice(&length.to_le_bytes());
+    out.extend_from_slice(&jump_count.to_le_bytes());
+    out.extend_from_slice(&KEYFRAME_INTERVAL.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&keyframes);
+    out.extend_from_slice(&jumps);
+    out.extend_from_slice(&moves);
+    Ok(out)
+}
+
+/// Encode every point of `curve` and write the table to `path`.
+#[cfg(feature = "std")]
+pub fn save<C: SpaceCurve + ?Sized>(curve: &C, path: &Path) -> error::Result<()> {
+    fs::write(path, encode(curve)?)
+        .map_err(|e| error::Error::Other(format!("failed to write {}: {e}", path.display())))
+}
+
+/// Read the table stored at `path`, checking it with [`PointTable::parse`].
+///
+/// Returns the encoded bytes for [`PointTable::parse`] to read in place.
+#[cfg(feature = "std")]
+pub fn load(path: &Path) -> error::Result<Vec<u8>> {
+    let data = fs::read(path)
+        .map_err(|e| error::Error::Other(format!("failed to read {}: {e}", path.display())))?;
+    PointTable::parse(&data)?;
+    Ok(data)
+}
+
+/// A point table read in place from its encoded bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct PointTable<'a> {
+    /// The whole encoded table.
+    data: &'a [u8],
+    /// Number of axes.
+    dimension: u32,
+    /// Bits per move code.
+    bits: u32,
+    /// Number of points.
+    length: u64,
+    /// Points between keyframes.
+    interval: u64,
+    /// Number of entries in the jump list.
+    jumps: usize,
+    /// Byte offset of the jump list.
+    jumps_at: usize,
+    /// Byte offset of the packed move codes.
+    moves_at: usize,
+}
+
+impl<'a> PointTable<'a> {
+    /// Read the table encoded in `data`, checking its header and size.
+    pub fn parse(data: &'a [u8]) -> error::Result<Self> {
+        let bad = |msg: &str| error::Error::Format(msg.to_string());
+        if data.len() < HEADER_LEN || &data[..4] != MAGIC {
+            return Err(bad("not a point table"));
+        }
+        if data[4] != VERSION {
+            return Err(error::Error::Format(format!(
+                "unsupported point table version {}",
+                data[4]
+            )));
+        }
+        let dimension = u32::from(data[5]);
+        let bits = u32::from(data[6]);
+        if dimension == 0 || dimension > MAX_DIMENSION || bits != move_bits(dimension) {
+            return Err(bad("invalid dimension or move width"));
+        }
+        let length = read_u64(data, 8);
+        let jumps = usize::try_from(read_u64(data, 16))
+            .map_err(|_| bad("jump count does not fit in memory"))?;
+        let interval = u64::from(read_u32(data, 24));
+        if interval == 0 {
+            return Err(bad("keyframe interval must be at least 1"));
+        }
+
+        // Sizes are summed in u128 so no header can overflow them.
+        let point_len = u128::from(dimension) * 4;
+        let keyframes_len = u128::from(length.div_ceil(interval)) * point_len;
+        let jumps_len = jumps as u128 * (8 + point_len);
+        let moves_len = (u128::from(length.saturating_sub(1)) * u128::from(bits)).div_ceil(8);
+        if HEADER_LEN as u128 + keyframes_len + jumps_len + moves_len != data.len() as u128 {
+            return Err(bad("point table size does not match its header"));
+        }
+        let jumps_at = HEADER_LEN + keyframes_len as usize;
+        let moves_at = jumps_at + jumps_len as usize;
+
+        let table = Self {
+            data,
+            dimension,
+            bits,
+            length,
+            interval,
+            jumps,
+            jumps_at,
+            moves_at,
+        };
+        let mut previous = 0;
+        for jump in 0..jumps {
+            let index = table.jump_index(jump);
+            if index <= previous || index >= length {
+                return Err(bad("jump indices must be ascending and inside the table"));
+            }
+            previous = index;
+        }
+        Ok(table)
+    }
+
+    /// Number of points in the table.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Number of coordinates per point.
+    pub fn dimensions(&self) -> u32 {
+        self.dimension
+    }
+
+    /// The point at `index`, which must be below [`length`](Self::length).
+    pub fn point(&self, index: u64) -> Point {
+        let mut out = Vec::with_capacity(1);
+        self.points_range(index..index + 1, &mut out);
+        out.pop().expect("point index out of range")
+    }
+
+    /// Append the points with indices in `range` to `out`, in order.
+    ///
+    /// The range is clamped to the table length. Decoding starts at the
+    /// keyframe at or before `range.start`.
+    pub fn points_range(&self, range: Range<u64>, out: &mut Vec<Point>) {
+        let end = range.end.min(self.length);
+        if range.start >= end {
+            return;
+        }
+        out.reserve((end - range.start) as usize);
+        let mut index = range.start / self.interval * self.interval;
+        let mut point = self.keyframe(index / self.interval);
+        let mut jump = self.first_jump_after(index);
+        loop {
+            if index >= range.start {
+                out.push(point.clone());
+            }
+            index += 1;
+            if index == end {
+                break;
+            }
+            if jump < self.jumps && self.jump_index(jump) == index {
+                point = self.jump_point(jump);
+                jump += 1;
+            } else {
+                let code = self.move_at(index - 1);
+                let axis = usize::from(code / 2);
+                if let Some(coord) = point.0.get_mut(axis) {
+                    *coord = if code % 2 == 1 {
+                        coord.wrapping_add(1)
+                    } else {
+                        coord.wrapping_sub(1)
+                    };
+                }
+            }
+        }
+    }
+
+    /// Decode the point stored at byte `at`.
+    fn point_at(&self, at: usize) -> Point {
+        let coords: SmallVec<[u32; 8]> = (0..self.dimension as usize)
+            .map(|axis| read_u32(self.data, at + axis * 4))
+            .collect();
+        Point(coords)
+    }
+
+    /// Keyframe number `keyframe`.
+    fn keyframe(&self, keyframe: u64) -> Point {
+        self.point_at(HEADER_LEN + keyframe as usize * self.dimension as usize * 4)
+    }
+
+    /// Byte offset of jump number `jump`.
+    fn jump_offset(&self, jump: usize) -> usize {
+        self.jumps_at + jump * (8 + self.dimension as usize * 4)
+    }
+
+    /// Index of the point reached by jump number `jump`.
+    fn jump_index(&self, jump: usize) -> u64 {
+        read_u64(self.data, self.jump_offset(jump))
+    }
+
+    /// Point reached by jump number `jump`.
+    fn jump_point(&self, jump: usize) -> Point {
+        self.point_at(self.jump_offset(jump) + 8)
+    }
+
+    /// Number of the first jump reaching an index above `index`.
+    fn first_jump_after(&self, index: u64) -> usize {
+        let (mut lo, mut hi) = (0, self.jumps);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.jump_index(mid) <= index {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Move code of the step from point `step` to point `step + 1`.
+    fn move_at(&self, step: u64) -> u8 {
+        let bit = step * u64::from(self.bits);
+        let byte = self.data[self.moves_at + (bit / 8) as usize];
+        (byte >> (bit % 8)) & ((1 << self.bits) - 1)
+    }
+}
+
+/// Read a little-endian `u32` at byte `at`.
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[at..at + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+/// Read a little-endian `u64` at byte `at`.
+fn read_u64(data: &[u8], at: usize) -> u64 {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&data[at..at + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{curve_from_name, curve_from_sizes};
+
+    fn assert_round_trip(curve: &dyn SpaceCurve) -> Vec<u8> {
+        let bytes = encode(curve).unwrap();
+        let table = PointTable::parse(&bytes).unwrap();
+        assert_eq!(table.length(), curve.length());
+        assert_eq!(table.dimensions(), curve.dimensions());
+        let mut decoded = Vec::new();
+        table.points_range(0..table.length(), &mut decoded);
+        assert_eq!(decoded, curve.points().collect::<Vec<_>>());
+        bytes
+    }
+
+    #[test]
+    fn continuous_curves_pack_two_bits_per_point() {
+        let curve = curve_from_name("hilbert", 2, 128).unwrap();
+        let bytes = assert_round_trip(&*curve);
+        // Four keyframes of 8 bytes and no jumps.
+        assert_eq!(bytes.len(), HEADER_LEN + 4 * 8 + (16384 - 1) * 2 / 8 + 1);
+    }
+
+    #[test]
+    fn jumps_and_higher_dimensions_round_trip() {
+        assert_round_trip(&*curve_from_name("zorder", 3, 16).unwrap());
+        assert_round_trip(&*curve_from_name("scan", 1, 5).unwrap());
+        assert_round_trip(&*curve_from_sizes("gilbert", &[70, 90]).unwrap());
+    }
+
+    #[test]
+    fn ranges_start_anywhere() {
+        let curve = curve_from_name("zorder", 2, 128).unwrap();
+        let bytes = encode(&*curve).unwrap();
+        let table = PointTable::parse(&bytes).unwrap();
+        for index in [0, 1, 4095, 4096, 4097, 9000, 16383] {
+            assert_eq!(table.point(index), curve.point(index), "{index}");
+        }
+        let mut out = Vec::new();
+        table.points_range(4090..4100, &mut out);
+        table.points_range(16380..20000, &mut out);
+        assert_eq!(out.len(), 14);
+        assert_eq!(out[13], curve.point(16383));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn tables_round_trip_through_files() {
+        use std::{env, process};
+
+        let dir = env::temp_dir().join(format!("spacecurve-table-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hilbert.scpt");
+        let curve = curve_from_name("hilbert", 2, 16).unwrap();
+        save(&*curve, &path).unwrap();
+        let data = load(&path).unwrap();
+        let table = PointTable::parse(&data).unwrap();
+        assert_eq!(table.point(100), curve.point(100));
+
+        fs::write(&path, &data[..data.len() - 1]).unwrap();
+        assert!(load(&path).is_err());
+        assert!(load(&dir.join("missing.scpt")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_damaged_tables() {
+        let bytes = encode(&*curve_from_name("hilbert", 2, 8).unwrap()).unwrap();
+        assert!(PointTable::parse(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PointTable::parse(&bytes[1..]).is_err());
+        let mut too_many_jumps = bytes.clone();
+        too_many_jumps[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(PointTable::parse(&too_many_jumps).is_err());
+        let mut wrong_version = bytes;
+        wrong_version[4] = 9;
+        assert!(PointTable::parse(&wrong_version).is_err());
+        assert!(encode(&*curve_from_name("scan", 9, 2).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,711 @@
+//! Curves derived from others by reordering or moving their points.
+//!
+//! Each wrapper takes any curve and is itself a [`SpaceCurve`], so variants
+//! like "Hilbert entered from the top-right corner" need no new curve type:
+//!
+//! - [`Reversed`] walks the inner curve from its last point to its first.
+//! - [`Rotated`] applies a symmetry of the grid (axis permutations and
+//!   reflections) to every point.
+//! - [`Translated`] shifts every point, wrapping around the grid edges.
+//! - [`Composed`] tiles the grid with blocks visited in the order of one
+//!   curve, each traversed by another.
+//!
+//! [`Offset`](crate::offset::Offset) rotates the index space instead, and
+//! [`PRESETS`] registers a few useful combinations as named curves.
+//!
+//! [`Reversed`]: crate::transform::Reversed
+//! [`Rotated`]: crate::transform::Rotated
+//! [`Translated`]: crate::transform::Translated
+//! [`Composed`]: crate::transform::Composed
+//! [`PRESETS`]: crate::transform::PRESETS
+
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::{mem, ops::Range};
+
+use smallvec::SmallVec;
+
+use crate::{
+    curve_from_name, error,
+    point::Point,
+    query,
+    registry::{self, CurveEntry, CurveId},
+    spacecurve::SpaceCurve,
+    spec::GridSpec,
+};
+
+/// Inclusive coordinate intervals covering one axis of a box.
+type Pieces = SmallVec<[(u32, u32); 2]>;
+
+/// Sort possibly unordered, disjoint `ranges` and merge the ones that touch.
+fn merge_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_unstable_by_key(|r| r.start);
+    let mut merged = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        query::push_range(&mut merged, r);
+    }
+    merged
+}
+
+/// A curve visiting the points of `inner` in the opposite order.
+#[derive(Debug)]
+pub struct Reversed {
+    /// The curve being reversed.
+    inner: Box<dyn SpaceCurve>,
+}
+
+impl Reversed {
+    /// Walk `inner` from its last point back to its first.
+    pub fn new(inner: Box<dyn SpaceCurve>) -> Self {
+        Self { inner }
+    }
+}
+
+impl SpaceCurve for Reversed {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn info(&self) -> &'static str {
+        self.inner.info()
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        self.inner.length() - 1 - self.inner.index(p)
+    }
+
+    fn point(&self, index: u64) -> Point {
+        self.inner.point(self.inner.length() - 1 - index)
+    }
+
+    fn length(&self) -> u64 {
+        self.inner.length()
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.inner.extents()
+    }
+
+    /// The inner curve's ranges, mirrored.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        let length = self.inner.length();
+        let inner = self.inner.ranges_for_box(lo, hi);
+        inner
+            .iter()
+            .rev()
+            .map(|r| length - r.end..length - r.start)
+            .collect()
+    }
+
+    fn is_cyclic(&self) -> bool {
+        self.inner.is_cyclic()
+    }
+}
+
+/// A curve whose points are those of `inner` under a symmetry of the grid.
+///
+/// Output axis `k` carries inner axis `axes[k]`, mirrored when `flips[k]` is
+/// set. Every rotation and reflection of the grid is such a map, and steps
+/// keep their length, so continuity and cycles carry over from `inner`.
+#[derive(Debug)]
+pub struct Rotated {
+    /// The curve being transformed.
+    inner: Box<dyn SpaceCurve>,
+    /// Inner axis shown on each output axis.
+    axes: SmallVec<[usize; 8]>,
+    /// Whether each output axis is mirrored.
+    flips: SmallVec<[bool; 8]>,
+    /// Side length along each output axis.
+    extents: SmallVec<[u32; 8]>,
+}
+
+impl Rotated {
+    /// Put inner axis `axes[k]` on output axis `k`, mirrored when `flips[k]`.
+    ///
+    /// `axes` must be a permutation of the inner curve's axes and `flips`
+    /// needs one entry per axis.
+    pub fn new(inner: Box<dyn SpaceCurve>, axes: &[usize], flips: &[bool]) -> error::Result<Self> {
+        let dimension = inner.dimensions() as usize;
+        let mut seen: SmallVec<[bool; 8]> = SmallVec::from_elem(false, dimension);
+        let is_permutation = axes.len() == dimension
+            && axes
+                .iter()
+                .all(|&axis| axis < dimension && !mem::replace(&mut seen[axis], true));
+        if !is_permutation || flips.len() != dimension {
+            return Err(error::Error::Shape(format!(
+                "a {dimension}D symmetry needs a permutation of {dimension} axes and {dimension} flips"
+            )));
+        }
+        let inner_extents = inner.extents();
+        let extents = axes.iter().map(|&axis| inner_extents[axis]).collect();
+        Ok(Self {
+            inner,
+            axes: axes.into(),
+            flips: flips.into(),
+            extents,
+        })
+    }
+
+    /// Turn a 2D curve `turns` quarter turns counter-clockwise (with `y`
+    /// pointing up).
+    pub fn quarter_turns(inner: Box<dyn SpaceCurve>, turns: u32) -> error::Result<Self> {
+        if inner.dimensions() != 2 {
+            return Err(error::Error::Shape(
+                "quarter turns need a 2D curve".to_string(),
+            ));
+        }
+        let (axes, flips) = match turns % 4 {
+            0 => ([0, 1], [false, false]),
+            1 => ([1, 0], [true, false]),
+            2 => ([0, 1], [true, true]),
+            _ => ([1, 0], [false, true]),
+        };
+        Self::new(inner, &axes, &flips)
+    }
+
+    /// Mirror `inner` along `axis`.
+    pub fn reflected(inner: Box<dyn SpaceCurve>, axis: usize) -> error::Result<Self> {
+        let dimension = inner.dimensions() as usize;
+        if axis >= dimension {
+            return Err(error::Error::Shape(format!(
+                "cannot reflect axis {axis} of a {dimension}D curve"
+            )));
+        }
+        let axes: SmallVec<[usize; 8]> = (0..dimension).collect();
+        let flips: SmallVec<[bool; 8]> = (0..dimension).map(|a| a == axis).collect();
+        Self::new(inner, &axes, &flips)
+    }
+
+    /// Output point for the inner point `q`.
+    fn forward(&self, q: &Point) -> Point {
+        Point::new(
+            (0..self.axes.len())
+                .map(|k| {
+                    let c = q[self.axes[k]];
+                    if self.flips[k] {
+                        self.extents[k] - 1 - c
+                    } else {
+                        c
+                    }
+                })
+                .collect::<SmallVec<[u32; 8]>>(),
+        )
+    }
+
+    /// Inner point for the output point `p`.
+    fn backward(&self, p: &Point) -> Point {
+        let mut q: SmallVec<[u32; 8]> = SmallVec::from_elem(0, self.axes.len());
+        for (k, &c) in p.iter().enumerate() {
+            q[self.axes[k]] = if self.flips[k] {
+                self.extents[k] - 1 - c
+            } else {
+                c
+            };
+        }
+        Point::new(q)
+    }
+}
+
+impl SpaceCurve for Rotated {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn info(&self) -> &'static str {
+        self.inner.info()
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        self.inner.index(&self.backward(p))
+    }
+
+    fn point(&self, index: u64) -> Point {
+        self.forward(&self.inner.point(index))
+    }
+
+    fn length(&self) -> u64 {
+        self.inner.length()
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+
+    fn advance_point(&self, index: u64, point: &mut Point) {
+        let mut inner = self.backward(point);
+        self.inner.advance_point(index, &mut inner);
+        *point = self.forward(&inner);
+    }
+
+    /// The inner curve's ranges for the box mapped back onto the inner grid.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        if lo.iter().zip(hi).any(|(l, h)| l > h) {
+            return Vec::new();
+        }
+        let (a, b) = (
+            self.backward(&Point::new(lo)),
+            self.backward(&Point::new(hi)),
+        );
+        // Mirrored axes swap which corner is low.
+        let (inner_lo, inner_hi): (SmallVec<[u32; 8]>, SmallVec<[u32; 8]>) = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&a, &b)| (a.min(b), a.max(b)))
+            .unzip();
+        self.inner.ranges_for_box(&inner_lo, &inner_hi)
+    }
+
+    fn is_cyclic(&self) -> bool {
+        self.inner.is_cyclic()
+    }
+}
+
+/// A curve whose points are those of `inner` shifted by a fixed amount,
+/// wrapping around the grid edges.
+///
+/// Every cell is still visited once, but steps across a wrapped edge become
+/// long jumps unless the shift is zero on that axis.
+#[derive(Debug)]
+pub struct Translated {
+    /// The curve being shifted.
+    inner: Box<dyn SpaceCurve>,
+    /// Shift along each axis, below the extent.
+    shift: SmallVec<[u32; 8]>,
+    /// Side length along each axis.
+    extents: SmallVec<[u32; 8]>,
+}
+
+impl Translated {
+    /// Shift `inner` by `shift`, taken modulo the extent on each axis.
+    pub fn new(inner: Box<dyn SpaceCurve>, shift: &[u32]) -> error::Result<Self> {
+        if shift.len() != inner.dimensions() as usize {
+            return Err(error::Error::Shape(format!(
+                "shift has {} axes but the curve has {} dimensions",
+                shift.len(),
+                inner.dimensions()
+            )));
+        }
+        let extents = inner.extents();
+        let shift = shift.iter().zip(&extents).map(|(s, e)| s % e).collect();
+        Ok(Self {
+            inner,
+            shift,
+            extents,
+        })
+    }
+
+    /// Shift along each axis, reduced below the extent.
+    pub fn shift(&self) -> &[u32] {
+        &self.shift
+    }
+}
+
+impl SpaceCurve for Translated {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn info(&self) -> &'static str {
+        self.inner.info()
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        let q: SmallVec<[u32; 8]> = p
+            .iter()
+            .zip(&self.shift)
+            .zip(&self.extents)
+            .map(|((&c, &s), &e)| if c >= s { c - s } else { c + (e - s) })
+            .collect();
+        self.inner.index(&Point::new(q))
+    }
+
+    fn point(&self, index: u64) -> Point {
+        let mut p = self.inner.point(index);
+        for ((c, &s), &e) in p.0.iter_mut().zip(&self.shift).zip(&self.extents) {
+            // `c + s` can overflow u32 on the widest grids.
+            *c = ((u64::from(*c) + u64::from(s)) % u64::from(e)) as u32;
+        }
+        p
+    }
+
+    fn length(&self) -> u64 {
+        self.inner.length()
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.inner.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+
+    /// The box split where it wraps on the inner grid (at most two pieces
+    /// per axis), each queried on the inner curve.
+    fn ranges_for_box(&self, lo: &[u32], hi: &[u32]) -> Vec<Range<u64>> {
+        if lo.iter().zip(hi).any(|(l, h)| l > h) {
+            return Vec::new();
+        }
+        let unshift = |c: u32, axis: usize| {
+            let (s, e) = (self.shift[axis], self.extents[axis]);
+            if c >= s { c - s } else { c + (e - s) }
+        };
+        // Inner intervals covering the box on each axis.
+        let pieces: SmallVec<[Pieces; 8]> = (0..lo.len())
+            .map(|axis| {
+                let (a, b) = (unshift(lo[axis], axis), unshift(hi[axis], axis));
+                if a <= b {
+                    SmallVec::from_slice(&[(a, b)])
+                } else {
+                    SmallVec::from_slice(&[(a, self.extents[axis] - 1), (0, b)])
+                }
+            })
+            .collect();
+        let mut ranges = Vec::new();
+        let mut choice: SmallVec<[usize; 8]> = SmallVec::from_elem(0, lo.len());
+        loop {
+            let (piece_lo, piece_hi): (SmallVec<[u32; 8]>, SmallVec<[u32; 8]>) =
+                choice.iter().zip(&pieces).map(|(&c, p)| p[c]).unzip();
+            ranges.extend(self.inner.ranges_for_box(&piece_lo, &piece_hi));
+            let mut axis = 0;
+            while axis < choice.len() && choice[axis] + 1 == pieces[axis].len() {
+                choice[axis] = 0;
+                axis += 1;
+            }
+            if axis == choice.len() {
+                break;
+            }
+            choice[axis] += 1;
+        }
+        merge_ranges(ranges)
+    }
+}
+
+/// A curve over a grid of blocks: `outer` orders the blocks and `inner`
+/// traverses each one.
+///
+/// The grid is `outer`'s extents times `inner`'s on each axis. Index `i`
+/// lies in block `i / inner.length()`, at position `i % inner.length()` of
+/// the inner curve.
+#[derive(Debug)]
+pub struct Composed {
+    /// Curve over the blocks.
+    outer: Box<dyn SpaceCurve>,
+    /// Curve within each block.
+    inner: Box<dyn SpaceCurve>,
+    /// Side length of a block along each axis.
+    block: SmallVec<[u32; 8]>,
+    /// Side length of the whole grid along each axis.
+    extents: SmallVec<[u32; 8]>,
+}
+
+impl Composed {
+    /// Visit the blocks in `outer` order, traversing each with `inner`.
+    ///
+    /// Both curves need the same number of dimensions, and the composed grid
+    /// must fit `u32` coordinates and `u64` indices.
+    pub fn new(outer: Box<dyn SpaceCurve>, inner: Box<dyn SpaceCurve>) -> error::Result<Self> {
+        if outer.dimensions() != inner.dimensions() {
+            return Err(error::Error::Shape(format!(
+                "cannot compose a {}D curve with a {}D curve",
+                outer.dimensions(),
+                inner.dimensions()
+            )));
+        }
+        outer.length().checked_mul(inner.length()).ok_or_else(|| {
+            error::Error::Size("composed curve length exceeds u64 bounds".to_string())
+        })?;
+        let block = inner.extents();
+        let extents = outer
+            .extents()
+            .iter()
+            .zip(&block)
+            .map(|(o, b)| o.checked_mul(*b))
+            .collect::<Option<_>>()
+            .ok_or_else(|| {
+                error::Error::Size("composed grid exceeds u32 coordinates".to_string())
+            })?;
+        Ok(Self {
+            outer,
+            inner,
+            block,
+            extents,
+        })
+    }
+}
+
+impl SpaceCurve for Composed {
+    fn name(&self) -> &'static str {
+        self.outer.name()
+    }
+
+    fn info(&self) -> &'static str {
+        "Blocks visited in the order of one curve, each traversed by another."
+    }
+
+    fn index(&self, p: &Point) -> u64 {
+        let (outer, inner): (SmallVec<[u32; 8]>, SmallVec<[u32; 8]>) = p
+            .iter()
+            .zip(&self.block)
+            .map(|(&c, &b)| (c / b, c % b))
+            .unzip();
+        self.outer.index(&Point::new(outer)) * self.inner.length()
+            + self.inner.index(&Point::new(inner))
+    }
+
+    fn point(&self, index: u64) -> Point {
+        let length = self.inner.length();
+        let outer = self.outer.point(index / length);
+        let mut p = self.inner.point(index % length);
+        for ((c, &o), &b) in p.0.iter_mut().zip(outer.iter()).zip(&self.block) {
+            *c += o * b;
+        }
+        p
+    }
+
+    fn length(&self) -> u64 {
+        self.outer.length() * self.inner.length()
+    }
+
+    fn dimensions(&self) -> u32 {
+        self.outer.dimensions()
+    }
+
+    fn extents(&self) -> SmallVec<[u32; 8]> {
+        self.extents.clone()
+    }
+}
+
+/// Validate `extents` for the built-in curve `id`.
+fn spec_for(id: CurveId, extents: &[u32]) -> error::Result<GridSpec> {
+    (id.entry().build_spec)(extents)
+}
+
+/// Construct the built-in curve `id` from a grid validated for it.
+fn curve_for(id: CurveId, spec: &GridSpec) -> error::Result<Box<dyn SpaceCurve>> {
+    (id.entry().ctor)(spec)
+}
+
+/// Fail unless `extents` describes a 2D grid.
+fn require_2d(key: &str, extents: &[u32]) -> error::Result<()> {
+    if extents.len() != 2 {
+        return Err(error::Error::Shape(format!("{key} is 2D only")));
+    }
+    Ok(())
+}
+
+/// Largest side of the Z-order blocks in the `hilbert-of-zorder` preset.
+const PRESET_BLOCK: u32 = 4;
+
+/// Outer Hilbert side and Z-order block side of `hilbert-of-zorder` on a grid
+/// of side `size`; grids smaller than a block are a single block.
+fn hilbert_of_zorder_sides(size: u32) -> (u32, u32) {
+    let block = size.min(PRESET_BLOCK);
+    (size / block.max(1), block)
+}
+
+/// Useful transformed curves, as registry entries without a [`CurveId`].
+///
+/// [`registry::find`], [`registry::entries`] and the by-name constructors
+/// include them after the built-in curves, so the CLI and GUI offer them
+/// like any other curve.
+pub static PRESETS: &[CurveEntry] = &[
+    CurveEntry {
+        id: None,
+        key: "hilbert-reversed",
+        display: "Hilbert (reversed)",
+        constraints: "as hilbert",
+        complexity: "as hilbert",
+        experimental: false,
+        cyclic: false,
+        continuous: true,
+        build_spec: |extents| spec_for(CurveId::Hilbert, extents),
+        ctor: |spec| Ok(Box::new(Reversed::new(curve_for(CurveId::Hilbert, spec)?))),
+    },
+    CurveEntry {
+        id: None,
+        key: "hilbert-top-right",
+        display: "Hilbert (from top right)",
+        constraints: "dimension=2; as hilbert",
+        complexity: "as hilbert",
+        experimental: false,
+        cyclic: false,
+        continuous: true,
+        build_spec: |extents| {
+            require_2d("hilbert-top-right", extents)?;
+            spec_for(CurveId::Hilbert, extents)
+        },
+        ctor: |spec| {
+            Ok(Box::new(Rotated::reflected(
+                curve_for(CurveId::Hilbert, spec)?,
+                0,
+            )?))
+        },
+    },
+    CurveEntry {
+        id: None,
+        key: "scan-columns",
+        display: "Scan (columns)",
+        constraints: "dimension=2; any width×height >= 1",
+        complexity: "as scan",
+        experimental: false,
+        cyclic: false,
+        continuous: true,
+        // The columns of a w×h grid are the rows of an h×w scan with its axes
+        // swapped, so the spec describes the scan before the swap.
+        build_spec: |extents| {
+            require_2d("scan-columns", extents)?;
+            spec_for(CurveId::Scan, &[extents[1], extents[0]])
+        },
+        ctor: |spec| {
+            Ok(Box::new(Rotated::new(
+                curve_for(CurveId::Scan, spec)?,
+                &[1, 0],
+                &[false, false],
+            )?))
+        },
+    },
+    CurveEntry {
+        id: None,
+        key: "hilbert-of-zorder",
+        display: "Hilbert of Z-order blocks",
+        constraints: "size=2^order; Hilbert order over 4×4 blocks, each filled in Z-order",
+        complexity: "as hilbert plus zorder",
+        experimental: false,
+        cyclic: false,
+        continuous: false,
+        build_spec: |extents| {
+            let spec = GridSpec::power_of_two_extents(extents)?;
+            if !spec.is_square() {
+                return Err(error::Error::Shape(
+                    "hilbert-of-zorder requires the same size on every axis".to_string(),
+                ));
+            }
+            let (outer, block) = hilbert_of_zorder_sides(spec.size());
+            let dimension = spec.dimension() as usize;
+            spec_for(CurveId::Hilbert, &vec![outer; dimension])?;
+            spec_for(CurveId::ZOrder, &vec![block; dimension])?;
+            Ok(spec)
+        },
+        ctor: |spec| {
+            let (outer, block) = hilbert_of_zorder_sides(spec.size());
+            Ok(Box::new(Composed::new(
+                curve_from_name("hilbert", spec.dimension(), outer)?,
+                curve_from_name("zorder", spec.dimension(), block)?,
+            )?))
+        },
+    },
+];
+
+/// Build the preset `key` in `dimension` dimensions with side `size`.
+pub fn preset(key: &str, dimension: u32, size: u32) -> error::Result<Box<dyn SpaceCurve>> {
+    let entry = PRESETS
+        .iter()
+        .find(|entry| entry.key == key)
+        .ok_or_else(|| error::Error::Unknown(format!("unknown preset: \"{key}\"")))?;
+    registry::construct_entry(entry, dimension, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify;
+
+    fn hilbert(size: u32) -> Box<dyn SpaceCurve> {
+        curve_from_name("hilbert", 2, size).unwrap()
+    }
+
+    /// Assert that box queries on `curve` agree with a scan of every cell.
+    fn assert_box_ranges(curve: &dyn SpaceCurve) {
+        let extents = curve.extents();
+        let hi: Vec<u32> = extents.iter().map(|e| e - 1).collect();
+        let mid: Vec<u32> = extents.iter().map(|e| e / 2).collect();
+        let low: Vec<u32> = extents.iter().map(|e| e / 4).collect();
+        for (lo, hi) in [(&low, &mid), (&mid, &hi), (&low, &hi)] {
+            assert_eq!(
+                curve.ranges_for_box(lo, hi),
+                query::scan_ranges(curve, lo, hi),
+                "{lo:?} {hi:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reversal_walks_backwards() {
+        let curve = Reversed::new(hilbert(8));
+        assert_eq!(curve.point(0), hilbert(8).point(63));
+        assert_eq!(curve.point(63).as_slice(), [0, 0]);
+        let report = verify::full_report(&curve).unwrap();
+        assert!(report.is_valid() && report.is_continuous());
+        assert_box_ranges(&curve);
+    }
+
+    #[test]
+    fn rotations_keep_the_curve_continuous() {
+        for turns in 0..4 {
+            let curve = Rotated::quarter_turns(hilbert(8), turns).unwrap();
+            let report = verify::full_report(&curve).unwrap();
+            assert!(report.is_valid() && report.is_continuous(), "{turns}");
+            assert_box_ranges(&curve);
+        }
+        let quarter = Rotated::quarter_turns(hilbert(8), 1).unwrap();
+        let end = hilbert(8).point(63);
+        assert_eq!(quarter.point(63).as_slice(), [7 - end[1], end[0]]);
+
+        let gilbert = Rotated::new(
+            crate::curve_from_sizes("gilbert", &[6, 3]).unwrap(),
+            &[1, 0],
+            &[false, true],
+        )
+        .unwrap();
+        assert_eq!(gilbert.extents().as_slice(), [3, 6]);
+        assert!(verify::full_report(&gilbert).unwrap().is_valid());
+
+        assert!(Rotated::new(hilbert(8), &[0, 0], &[false, false]).is_err());
+        assert!(Rotated::reflected(hilbert(8), 2).is_err());
+    }
+
+    #[test]
+    fn translation_wraps_around_the_grid() {
+        let curve = Translated::new(hilbert(8), &[3, 13]).unwrap();
+        assert_eq!(curve.shift(), [3, 5]);
+        assert_eq!(curve.point(0).as_slice(), [3, 5]);
+        let report = verify::full_report(&curve).unwrap();
+        assert!(report.is_valid());
+        assert!(!report.is_continuous());
+        assert_box_ranges(&curve);
+
+        let cycle = Translated::new(curve_from_name("hcurve", 2, 8).unwrap(), &[0, 0]).unwrap();
+        assert!(cycle.is_cyclic());
+    }
+
+    #[test]
+    fn composition_tiles_blocks() {
+        let curve = Composed::new(hilbert(4), curve_from_name("gray", 2, 2).unwrap()).unwrap();
+        assert_eq!(curve.extents().as_slice(), [8, 8]);
+        assert_eq!(curve.point(4).as_slice(), [0, 2]);
+        assert!(verify::full_report(&curve).unwrap().is_valid());
+        assert_box_ranges(&curve);
+        assert!(Composed::new(hilbert(4), curve_from_name("hilbert", 3, 2).unwrap()).is_err());
+    }
+
+    #[test]
+    fn presets_build_valid_curves() {
+        for p in PRESETS {
+            let curve = preset(p.key, 2, 16).unwrap();
+            let report = verify::full_report(&*curve).unwrap();
+            assert!(report.is_valid(), "{}: {report:?}", p.key);
+        }
+        let top_right = preset("hilbert-top-right", 2, 8).unwrap();
+        assert_eq!(top_right.point(0).as_slice(), [7, 0]);
+        assert!(preset("hilbert-top-right", 3, 8).is_err());
+        assert!(preset("missing", 2, 8).is_err());
+    }
+}
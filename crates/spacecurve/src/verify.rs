@@ -0,0 +1,266 @@
+//! Exhaustive checks of a curve implementation.
+//!
+//! [`full_report`] walks every index of a curve once and checks the
+//! properties the rest of the crate relies on: `point` and `index` invert
+//! each other, every cell of the grid is visited exactly once, and a curve
+//! that reports itself cyclic really closes into a loop. It also counts the
+//! steps that are not unit moves, which is how continuous curves are told
+//! apart from jumping ones like Z-order. New curves can be checked before any
+//! tests are written for them.
+//!
+//! [`full_report`]: crate::verify::full_report
+
+use alloc::{format, vec, vec::Vec};
+
+use crate::{error, point::Point, spacecurve::SpaceCurve};
+
+/// Largest grid [`full_report`] will check; the visited set takes one bit
+/// per cell.
+pub const MAX_CELLS: u64 = 1 << 32;
+
+/// Occurrences of one kind of failure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Number of indices affected.
+    pub count: u64,
+    /// The lowest affected index.
+    pub first: Option<u64>,
+}
+
+impl Tally {
+    /// Record a failure at `index`; indices arrive in ascending order.
+    fn add(&mut self, index: u64) {
+        self.count += 1;
+        self.first.get_or_insert(index);
+    }
+
+    /// Whether nothing was recorded.
+    pub fn is_clear(&self) -> bool {
+        self.count == 0
+    }
+}
+
+/// Result of [`full_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Number of points, from `length()`.
+    pub length: u64,
+    /// Number of grid cells, the product of `extents()`.
+    pub cells: u64,
+    /// Indices whose point has the wrong arity or lies outside the extents.
+    pub out_of_bounds: Tally,
+    /// Indices `i` with `index(point(i)) != i`.
+    pub round_trip: Tally,
+    /// Indices whose point was already visited at a lower index.
+    pub duplicates: Tally,
+    /// Grid cells no index visits.
+    pub unvisited: u64,
+    /// Indices `i` whose step from point `i - 1` is not a unit move.
+    pub jumps: Tally,
+    /// Whether the last point is a unit move from the first; a single point
+    /// trivially closes.
+    pub closes: bool,
+    /// What the curve reports from `is_cyclic()`.
+    pub claims_cyclic: bool,
+}
+
+impl Report {
+    /// Every index maps to its own in-bounds point and back.
+    pub fn is_bijective(&self) -> bool {
+        self.out_of_bounds.is_clear() && self.round_trip.is_clear() && self.duplicates.is_clear()
+    }
+
+    /// Every grid cell is visited.
+    pub fn is_surjective(&self) -> bool {
+        self.unvisited == 0 && self.length == self.cells
+    }
+
+    /// Every step is a unit move.
+    pub fn is_continuous(&self) -> bool {
+        self.jumps.is_clear()
+    }
+
+    /// A curve reporting `is_cyclic()` really closes. The converse is not
+    /// checked: curves may close without reporting it.
+    pub fn cycle_consistent(&self) -> bool {
+        !self.claims_cyclic || self.closes
+    }
+
+    /// The curve is a valid traversal: bijective, surjective and consistent
+    /// about cycles. Continuity is reported but not required.
+    pub fn is_valid(&self) -> bool {
+        self.is_bijective() && self.is_surjective() && self.cycle_consistent()
+    }
+}
+
+/// Whether `a` and `b` differ by one along exactly one axis.
+fn unit_step(a: &[u32], b: &[u32]) -> bool {
+    let mut moved = 0;
+    for (&x, &y) in a.iter().zip(b) {
+        match x.abs_diff(y) {
+            0 => {}
+            1 => moved += 1,
+            _ => return false,
+        }
+    }
+    moved == 1
+}
+
+/// Check every index of `curve`.
+///
+/// Fails when the grid has more than [`MAX_CELLS`] cells.
+pub fn full_report<C: SpaceCurve + ?Sized>(curve: &C) -> error::Result<Report> {
+    let extents = curve.extents();
+    let cells = extents
+        .iter()
+        .try_fold(1u64, |acc, &e| acc.checked_mul(u64::from(e)))
+        .filter(|&cells| cells <= MAX_CELLS)
+        .ok_or_else(|| {
+            error::Error::Size(format!(
+                "verification is limited to {MAX_CELLS} cells, grid is {extents:?}"
+            ))
+        })?;
+    let length = curve.length();
+
+    let mut visited = vec![0u64; cells.div_ceil(64) as usize];
+    let mut report = Report {
+        length,
+        cells,
+        out_of_bounds: Tally::default(),
+        round_trip: Tally::default(),
+        duplicates: Tally::default(),
+        unvisited: cells,
+        jumps: Tally::default(),
+        closes: false,
+        claims_cyclic: curve.is_cyclic(),
+    };
+
+    let mut batch = Vec::new();
+    let mut prev: Option<Point> = None;
+    let mut first: Option<Point> = None;
+    for start in (0..length).step_by(4096) {
+        batch.clear();
+        curve.points_range(start..start.saturating_add(4096), &mut batch);
+        for (index, point) in (start..).zip(&batch) {
+            let in_bounds =
+                point.len() == extents.len() && point.iter().zip(&extents).all(|(c, e)| c < e);
+            if in_bounds {
+                let cell = point
+                    .iter()
+                    .zip(&extents)
+                    .rev()
+                    .fold(0u64, |acc, (&c, &e)| acc * u64::from(e) + u64::from(c));
+                let (word, bit) = ((cell / 64) as usize, 1 << (cell % 64));
+                if visited[word] & bit == 0 {
+                    visited[word] |= bit;
+                    report.unvisited -= 1;
+                } else {
+                    report.duplicates.add(index);
+                }
+                if curve.index(point) != index {
+                    report.round_trip.add(index);
+                }
+            } else {
+                report.out_of_bounds.add(index);
+            }
+            if let Some(prev) = &prev
+                && !unit_step(prev, point)
+            {
+                report.jumps.add(index);
+            }
+            if first.is_none() {
+                first = Some(point.clone());
+            }
+            prev = Some(point.clone());
+        }
+    }
+    if let (Some(first), Some(last)) = (&first, &prev) {
+        report.closes = length == 1 || unit_step(last, first);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::SmallVec;
+
+    use super::*;
+    use crate::{curve_from_id, curve_from_name, curve_from_sizes, registry::CurveId};
+
+    #[test]
+    fn registry_curves_are_valid() {
+        for &id in CurveId::ALL {
+            for dimension in [2, 3] {
+                let Ok(curve) = curve_from_id(id, dimension, 8) else {
+                    continue;
+                };
+                let report = full_report(&*curve).unwrap();
+                assert!(report.is_valid(), "{id} {dimension}: {report:?}");
+            }
+        }
+        let hilbert = full_report(&*curve_from_name("hilbert", 2, 8).unwrap()).unwrap();
+        assert!(hilbert.is_continuous());
+        assert!(!hilbert.closes);
+        let zorder = full_report(&*curve_from_name("zorder", 2, 8).unwrap()).unwrap();
+        assert!(!zorder.is_continuous());
+        assert_eq!(zorder.jumps.first, Some(2));
+    }
+
+    /// A scan over a 2×2 grid that visits `(0, 0)` twice and never `(1, 1)`.
+    #[derive(Debug)]
+    struct Broken;
+
+    impl SpaceCurve for Broken {
+        fn name(&self) -> &'static str {
+            "broken"
+        }
+        fn info(&self) -> &'static str {
+            ""
+        }
+        fn index(&self, p: &Point) -> u64 {
+            u64::from(p[1] * 2 + p[0])
+        }
+        fn point(&self, index: u64) -> Point {
+            let cells: [[u32; 2]; 4] = [[0, 0], [1, 0], [0, 1], [0, 0]];
+            Point::new(SmallVec::from_slice(&cells[index as usize]))
+        }
+        fn length(&self) -> u64 {
+            4
+        }
+        fn dimensions(&self) -> u32 {
+            2
+        }
+        fn is_cyclic(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn single_cells_are_valid() {
+        for &id in CurveId::ALL {
+            for dimension in [1, 2, 3] {
+                let Ok(curve) = curve_from_id(id, dimension, 1) else {
+                    continue;
+                };
+                let report = full_report(&*curve).unwrap();
+                assert!(report.closes);
+                assert!(report.is_valid(), "{id} {dimension}: {report:?}");
+            }
+        }
+        let sierpinski = curve_from_sizes("sierpinski", &[1, 1]).unwrap();
+        assert!(full_report(&*sierpinski).unwrap().is_valid());
+    }
+
+    #[test]
+    fn reports_broken_curves() {
+        let report = full_report(&Broken).unwrap();
+        assert_eq!(report.duplicates.first, Some(3));
+        assert_eq!(report.round_trip.first, Some(3));
+        assert_eq!(report.unvisited, 1);
+        assert_eq!(report.jumps.first, Some(2));
+        assert!(!report.cycle_consistent());
+        assert!(!report.is_bijective());
+        assert!(!report.is_surjective());
+        assert!(!report.is_valid());
+    }
+}
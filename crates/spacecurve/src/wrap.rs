@@ -0,0 +1,216 @@
+//! Index arithmetic on curves treated as cycles.
+//!
+//! Animations and chunked rendering walk a curve past its last point and back
+//! to the start. These helpers take the curve length directly so callers that
+//! only hold cached points can share them; [`SpaceCurve`] exposes the same
+//! operations as provided methods.
+//!
+//! Indices are taken modulo the curve length, so the point after the last one
+//! is index 0 and the point before index 0 is the last one. A run that crosses
+//! that boundary is a [`CyclicRange`]: it never holds an index twice, and
+//! [`CyclicRange::split`] turns it back into at most two ordinary ranges for
+//! code that works on contiguous slices. Every helper treats an empty curve
+//! as having no indices at all.
+//!
+//! [`SpaceCurve`]: crate::spacecurve::SpaceCurve
+//!
+//! [`CyclicRange`]: crate::wrap::CyclicRange
+//! [`CyclicRange::split`]: crate::wrap::CyclicRange::split
+
+use core::ops::Range;
+
+/// Move `index` by `delta` steps (negative moves backwards), wrapping at `length`.
+///
+/// Returns 0 for an empty curve.
+pub fn advance(length: u64, index: u64, delta: i64) -> u64 {
+    if length == 0 {
+        return 0;
+    }
+    let length = i128::from(length);
+    (i128::from(index) + i128::from(delta).rem_euclid(length)).rem_euclid(length) as u64
+}
+
+/// Number of forward steps from `from` to `to`, wrapping at `length`.
+///
+/// Returns 0 for an empty curve.
+pub fn offset_between(length: u64, from: u64, to: u64) -> u64 {
+    if length == 0 {
+        return 0;
+    }
+    let length = u128::from(length);
+    ((u128::from(to) % length + length - u128::from(from) % length) % length) as u64
+}
+
+/// A run of consecutive indices on a curve of `length` points that continues
+/// from index 0 after the last point.
+///
+/// The range starts at `start` (reduced modulo `length`) and covers `len`
+/// indices, capped at `length` so no index appears twice. An empty curve has
+/// only the empty range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CyclicRange {
+    /// Curve length the indices wrap at.
+    length: u64,
+    /// First index in the range.
+    start: u64,
+    /// Number of indices in the range.
+    len: u64,
+}
+
+impl CyclicRange {
+    /// The `len` indices from `start` on a curve of `length` points.
+    pub fn new(length: u64, start: u64, len: u64) -> Self {
+        Self {
+            length,
+            start: if length == 0 { 0 } else { start % length },
+            len: len.min(length),
+        }
+    }
+
+    /// First index in the range.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Number of indices in the range.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the range holds no indices.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the range runs past the last point back to index 0.
+    pub fn wraps(&self) -> bool {
+        u128::from(self.start) + u128::from(self.len) > u128::from(self.length)
+    }
+
+    /// Whether `index` lies in the range.
+    pub fn contains(&self, index: u64) -> bool {
+        index < self.length && offset_between(self.length, self.start, index) < self.len
+    }
+
+    /// Split the range into the run up to the end of the curve and the run
+    /// continuing from index 0, which is empty when nothing wraps.
+    pub fn split(&self) -> (Range<u64>, Range<u64>) {
+        let head_len = self.len.min(self.length - self.start);
+        (self.start..self.start + head_len, 0..self.len - head_len)
+    }
+
+    /// Iterate over the indices in order.
+    pub fn iter(&self) -> CyclicIter {
+        CyclicIter { remaining: *self }
+    }
+}
+
+impl IntoIterator for CyclicRange {
+    type Item = u64;
+    type IntoIter = CyclicIter;
+
+    fn into_iter(self) -> CyclicIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the indices of a [`CyclicRange`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicIter {
+    /// Indices not yet yielded.
+    remaining: CyclicRange,
+}
+
+impl Iterator for CyclicIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let index = self.remaining.start;
+        self.remaining.start = if index + 1 == self.remaining.length {
+            0
+        } else {
+            index + 1
+        };
+        self.remaining.len -= 1;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining.len as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for CyclicIter {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn advance_wraps_in_both_directions() {
+        assert_eq!(advance(10, 7, 5), 2);
+        assert_eq!(advance(10, 2, -5), 7);
+        assert_eq!(advance(10, 3, 40), 3);
+        assert_eq!(advance(10, 0, i64::MIN), 2);
+        assert_eq!(advance(0, 3, 1), 0);
+        assert_eq!(advance(1 << 40, 0, -1), (1 << 40) - 1);
+        assert_eq!(advance(u64::MAX, u64::MAX - 1, 1), 0);
+    }
+
+    #[test]
+    fn offsets_count_forward_steps() {
+        assert_eq!(offset_between(10, 2, 7), 5);
+        assert_eq!(offset_between(10, 7, 2), 5);
+        assert_eq!(offset_between(10, 4, 4), 0);
+        assert_eq!(advance(10, 8, offset_between(10, 8, 1) as i64), 1);
+    }
+
+    #[test]
+    fn cyclic_ranges_visit_each_index_once() {
+        let iter = |r: CyclicRange| r.into_iter().collect::<Vec<_>>();
+        assert_eq!(iter(CyclicRange::new(6, 4, 4)), [4, 5, 0, 1]);
+        assert_eq!(iter(CyclicRange::new(6, 10, 3)), [4, 5, 0]);
+        assert_eq!(iter(CyclicRange::new(6, 0, 6)), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(CyclicRange::new(6, 1, 9).iter().len(), 6);
+        assert_eq!(CyclicRange::new(0, 1, 9).iter().count(), 0);
+        assert!(CyclicRange::new(0, 1, 9).is_empty());
+    }
+
+    #[test]
+    fn cyclic_ranges_split_at_the_wrap() {
+        // Ending exactly on the last point does not wrap.
+        let to_end = CyclicRange::new(6, 4, 2);
+        assert!(!to_end.wraps());
+        assert_eq!(to_end.split(), (4..6, 0..0));
+
+        let across = CyclicRange::new(6, 4, 4);
+        assert!(across.wraps());
+        assert_eq!(across.split(), (4..6, 0..2));
+        assert_eq!(CyclicRange::new(6, 1, 3).split(), (1..4, 0..0));
+
+        // A full-length range from a non-zero start covers both runs.
+        assert_eq!(CyclicRange::new(6, 5, 6).split(), (5..6, 0..5));
+        assert_eq!(CyclicRange::new(0, 0, 3).split(), (0..0, 0..0));
+
+        let mut partial = across.iter();
+        partial.next();
+        assert_eq!(partial.remaining.split(), (5..6, 0..2));
+    }
+
+    #[test]
+    fn cyclic_ranges_contain_wrapped_indices() {
+        let across = CyclicRange::new(6, 4, 4);
+        let members: Vec<u64> = (0..8).filter(|&i| across.contains(i)).collect();
+        assert_eq!(members, [0, 1, 4, 5]);
+        assert!(!CyclicRange::new(6, 2, 0).contains(2));
+        assert!((0..6).all(|i| CyclicRange::new(6, 3, 6).contains(i)));
+        assert!(!CyclicRange::new(6, 3, 6).contains(6));
+        assert!(!CyclicRange::new(0, 0, 0).contains(0));
+    }
+}
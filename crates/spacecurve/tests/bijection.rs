@@ -30,12 +30,18 @@ fn curve_configs() -> Vec<(&'static str, u32, u32, u32)> {
         ("hcurve", 2, 8, 64),
         ("hcurve", 4, 2, 16),
         // Onion (any size)
+        #[cfg(feature = "experimental-curves")]
         ("onion", 2, 5, 25),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 2, 8, 64),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 3, 4, 64),
         // Hairy Onion (any size)
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 2, 5, 25),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 2, 8, 64),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 3, 4, 64),
         // Gray (power-of-two)
         ("gray", 2, 4, 16),
@@ -49,7 +55,7 @@ proptest! {
 
     /// Test bijection property for Hilbert 2D curves.
     #[test]
-    fn bijection_hilbert_2d(index in 0u32..256) {
+    fn bijection_hilbert_2d(index in 0u64..256) {
         let curve = curve_from_name("hilbert", 2, 16).expect("hilbert 2d 16");
         if index < curve.length() {
             let point = curve.point(index);
@@ -60,7 +66,7 @@ proptest! {
 
     /// Test bijection property for Hilbert 3D curves.
     #[test]
-    fn bijection_hilbert_3d(index in 0u32..64) {
+    fn bijection_hilbert_3d(index in 0u64..64) {
         let curve = curve_from_name("hilbert", 3, 4).expect("hilbert 3d 4");
         if index < curve.length() {
             let point = curve.point(index);
@@ -71,7 +77,7 @@ proptest! {
 
     /// Test bijection property for Scan curves.
     #[test]
-    fn bijection_scan(index in 0u32..100) {
+    fn bijection_scan(index in 0u64..100) {
         let curve = curve_from_name("scan", 2, 10).expect("scan 2d 10");
         if index < curve.length() {
             let point = curve.point(index);
@@ -82,7 +88,7 @@ proptest! {
 
     /// Test bijection property for Z-order curves.
     #[test]
-    fn bijection_zorder(index in 0u32..256) {
+    fn bijection_zorder(index in 0u64..256) {
         let curve = curve_from_name("zorder", 2, 16).expect("zorder 2d 16");
         if index < curve.length() {
             let point = curve.point(index);
@@ -93,7 +99,7 @@ proptest! {
 
     /// Test bijection property for H-curve.
     #[test]
-    fn bijection_hcurve(index in 0u32..64) {
+    fn bijection_hcurve(index in 0u64..64) {
         let curve = curve_from_name("hcurve", 2, 8).expect("hcurve 2d 8");
         if index < curve.length() {
             let point = curve.point(index);
@@ -104,7 +110,8 @@ proptest! {
 
     /// Test bijection property for Onion curves.
     #[test]
-    fn bijection_onion(index in 0u32..64) {
+    #[cfg(feature = "experimental-curves")]
+    fn bijection_onion(index in 0u64..64) {
         let curve = curve_from_name("onion", 2, 8).expect("onion 2d 8");
         if index < curve.length() {
             let point = curve.point(index);
@@ -115,7 +122,8 @@ proptest! {
 
     /// Test bijection property for Hairy Onion curves.
     #[test]
-    fn bijection_hairyonion(index in 0u32..64) {
+    #[cfg(feature = "experimental-curves")]
+    fn bijection_hairyonion(index in 0u64..64) {
         let curve = curve_from_name("hairyonion", 2, 8).expect("hairyonion 2d 8");
         if index < curve.length() {
             let point = curve.point(index);
@@ -126,7 +134,7 @@ proptest! {
 
     /// Test bijection property for Gray code curves.
     #[test]
-    fn bijection_gray(index in 0u32..256) {
+    fn bijection_gray(index in 0u64..256) {
         let curve = curve_from_name("gray", 2, 16).expect("gray 2d 16");
         if index < curve.length() {
             let point = curve.point(index);
@@ -197,7 +205,9 @@ fn exhaustive_bijection_small_curves() {
         ("scan", 3, 3),
         ("zorder", 2, 4),
         ("hcurve", 2, 4),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 2, 4),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 2, 4),
         ("gray", 2, 4),
     ];
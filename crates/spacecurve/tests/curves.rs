@@ -1,7 +1,13 @@
-//! Integration tests checking reflection and continuity properties.
+//! Integration tests checking reflection, continuity and closure properties.
 #[cfg(test)]
 mod tests {
-    use spacecurve::{SpaceCurve, curve_from_name, curves::onion::OnionCurve, error, point::Point};
+    use std::thread;
+
+    use spacecurve::{
+        SpaceCurve, curve_from_name, curve_from_sizes, error, registry, shared_curve_from_name,
+    };
+    #[cfg(feature = "experimental-curves")]
+    use spacecurve::{curves::onion::OnionCurve, point::Point};
 
     fn pattern_reflects(pattern_name: &str, p: &dyn SpaceCurve) {
         for off in 0..p.length() {
@@ -31,11 +37,22 @@ mod tests {
         }
     }
 
+    fn pattern_closes(pattern_name: &str, p: &dyn SpaceCurve) {
+        let first = p.point(0);
+        let last = p.point(p.length() - 1);
+        assert_eq!(
+            first.distance(&last),
+            1.0,
+            "Pattern {pattern_name} claims to be cyclic but its last point {last:?} is not adjacent to {first:?}"
+        );
+    }
+
     macro_rules! curve_tests {
-        ($(($pattern:expr, $dims:expr, $size:expr, $reflection:expr, $continuous:expr)),* $(,)?) => {
+        ($($(#[$meta:meta])* ($pattern:expr, $dims:expr, $size:expr, $reflection:expr, $continuous:expr)),* $(,)?) => {
             $(
                 paste::paste! {
                     #[test]
+                    $(#[$meta])*
                     fn [<$pattern _reflection_ $dims d_ $size>]() -> error::Result<()> {
                         if $reflection {
                             let curve = curve_from_name($pattern, $dims, $size)?;
@@ -45,13 +62,28 @@ mod tests {
                     }
 
                     #[test]
+                    $(#[$meta])*
                     fn [<$pattern _continuous_ $dims d_ $size>]() -> error::Result<()> {
+                        let entry = registry::find($pattern).expect("registered pattern");
+                        assert!($continuous || !entry.continuous, "{} continuous flag disagrees with the test table", $pattern);
                         if $continuous {
                             let curve = curve_from_name($pattern, $dims, $size)?;
                             pattern_continuous(&format!("{}({},{})", $pattern, $dims, $size), curve.as_ref());
                         }
                         Ok(())
                     }
+
+                    #[test]
+                    $(#[$meta])*
+                    fn [<$pattern _cyclic_ $dims d_ $size>]() -> error::Result<()> {
+                        let curve = curve_from_name($pattern, $dims, $size)?;
+                        let entry = registry::find($pattern).expect("registered pattern");
+                        assert!(!entry.cyclic || curve.is_cyclic(), "{} is registered cyclic but does not close", $pattern);
+                        if curve.is_cyclic() {
+                            pattern_closes(&format!("{}({},{})", $pattern, $dims, $size), curve.as_ref());
+                        }
+                        Ok(())
+                    }
                 }
             )*
         };
@@ -65,29 +97,45 @@ mod tests {
         // ("hcurve", 3, 4, true, true),
         // ("hcurve", 3, 8, true, true),
         ("hcurve", 4, 2, true, true),
+        ("hcurve", 2, 16, true, true),
+        ("hcurve", 3, 8, true, false),
         ("scan", 2, 4, true, true),
         ("scan", 3, 4, true, true),
         ("scan", 4, 2, true, true),
         ("zorder", 2, 4, true, false),
         ("zorder", 3, 4, true, false),
         ("zorder", 4, 2, true, false),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 2, 4, true, true),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 3, 4, true, false),
+        #[cfg(feature = "experimental-curves")]
         ("onion", 4, 2, true, false),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 2, 4, true, true),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 3, 4, true, true),
+        #[cfg(feature = "experimental-curves")]
         ("hairyonion", 4, 2, true, true),
         ("gray", 2, 4, true, false),
         ("gray", 3, 4, true, false),
         ("gray", 4, 2, true, false),
+        ("sierpinski", 2, 8, true, false),
+        ("sierpinski", 2, 32, true, false),
+        ("shuffle", 2, 5, true, false),
+        ("shuffle", 3, 4, true, false),
+        ("coil", 2, 5, true, true),
+        ("coil", 3, 3, true, true),
+        ("coil", 4, 3, true, true),
     }
 
     #[test]
+    #[cfg(feature = "experimental-curves")]
     fn onion_3d_outer_faces_follow_plane_order() -> error::Result<()> {
         let face = OnionCurve::new(2, 5)?;
         let cube = OnionCurve::new(3, 5)?;
 
-        for idx in 0..25u32 {
+        for idx in 0..25u64 {
             let p3 = cube.point(idx);
             assert_eq!(p3[0], 0, "Index {} should be on the x=0 face", idx);
 
@@ -102,6 +150,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "experimental-curves")]
     fn onion_3d_initial_edge_sequence_matches_definition() -> error::Result<()> {
         let cube = OnionCurve::new(3, 5)?;
 
@@ -115,11 +164,67 @@ mod tests {
         assert_eq!(cube.point(52).as_slice(), &[3, 0, 0]);
 
         // Confirm index lookups for the same coordinates.
-        for (expected, coords) in [(50u32, [1, 0, 0]), (51, [2, 0, 0]), (52, [3, 0, 0])] {
+        for (expected, coords) in [(50u64, [1, 0, 0]), (51, [2, 0, 0]), (52, [3, 0, 0])] {
             let pt = Point::new(coords.to_vec());
             assert_eq!(cube.index(&pt), expected);
         }
 
         Ok(())
     }
+
+    #[test]
+    fn small_grids_that_close_are_cyclic() -> error::Result<()> {
+        assert!(curve_from_name("hilbert", 2, 2)?.is_cyclic());
+        assert!(curve_from_name("gray", 2, 2)?.is_cyclic());
+        assert!(curve_from_sizes("scan", &[8, 2])?.is_cyclic());
+        assert!(!curve_from_name("hilbert", 2, 4)?.is_cyclic());
+        assert!(!curve_from_sizes("scan", &[8, 3])?.is_cyclic());
+        assert!(!curve_from_name("scan", 2, 1)?.is_cyclic());
+        Ok(())
+    }
+
+    #[test]
+    fn shared_curves_serve_worker_threads() -> error::Result<()> {
+        for &name in registry::CURVE_NAMES {
+            let curve = shared_curve_from_name(name, 2, 8)?;
+            let expected: Vec<_> = (0..curve.length()).map(|i| curve.point(i)).collect();
+            let chunks: Vec<_> = thread::scope(|scope| {
+                let workers: Vec<_> = (0..4)
+                    .map(|part| {
+                        let curve = curve.clone();
+                        scope.spawn(move || {
+                            let start = part * curve.length() / 4;
+                            let end = (part + 1) * curve.length() / 4;
+                            (start..end).map(|i| curve.point(i)).collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+                workers.into_iter().map(|w| w.join().unwrap()).collect()
+            });
+            assert_eq!(chunks.concat(), expected, "{name}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn advance_point_matches_point() -> error::Result<()> {
+        for &name in registry::CURVE_NAMES {
+            for (dimension, size) in [(2, 5), (2, 8), (2, 16), (3, 3), (3, 4), (4, 2)] {
+                if registry::validate(name, dimension, size).is_err() {
+                    continue;
+                }
+                let curve = curve_from_name(name, dimension, size)?;
+                let mut point = curve.point(0);
+                for i in 1..curve.length() {
+                    curve.advance_point(i - 1, &mut point);
+                    assert_eq!(
+                        point,
+                        curve.point(i),
+                        "{name} {dimension}D size {size} at {i}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,184 @@
+//! Curve gallery images for the documentation.
+//!
+//! `cargo xtask render-gallery` renders every curve in the registry with the
+//! `scurve` CLI into `assets/curves/`: 2D maps at each standard grid size the
+//! curve accepts, an animated snake, and a spinning 3D view for curves that
+//! extend to three dimensions. A new registry entry only needs a re-run to
+//! appear in the gallery.
+//!
+//! Like `scurve sweep` into a directory, the run refuses to overwrite images
+//! that already exist: `--skip-existing` renders only the missing ones, which
+//! resumes an interrupted run, and `--force` clears the directory and renders
+//! everything again.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use spacecurve::registry::{CurveEntry, REGISTRY};
+use xshell::{Shell, cmd};
+
+use crate::{RepoPaths, repo_shell};
+
+/// Grid sides of the 2D maps.
+const MAP_SIDES: &[u32] = &[8, 16, 32];
+/// Pixel size of the 2D maps.
+const MAP_PIXELS: &str = "512";
+/// Stroke width of the 2D maps.
+const MAP_LINE_WIDTH: &str = "2";
+
+/// Grid side of the snake animations; the snake walks the whole curve.
+const SNAKE_SIDE: u32 = 16;
+/// Pixel size of the snake animations.
+const SNAKE_PIXELS: &str = "256";
+/// Trail length of the snake animations.
+const SNAKE_TRAIL: &str = "32";
+/// Color of the full curve drawn behind the snake.
+const SNAKE_FULL: &str = "d0d0e8";
+
+/// Grid side of the 3D views.
+const VIEW_3D_SIDE: u32 = 4;
+/// Length of the 3D views in seconds.
+const VIEW_3D_SECONDS: &str = "4";
+/// Frame rate of the 3D views.
+const VIEW_3D_FPS: &str = "15";
+
+/// Treatment of gallery images that already exist.
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct GalleryArgs {
+    /// Keep existing images and render only the missing ones.
+    #[arg(long, conflicts_with = "force")]
+    pub skip_existing: bool,
+    /// Clear the gallery and render every image again.
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// One gallery image of a curve.
+#[derive(Clone, Copy, Debug)]
+enum Image {
+    /// 2D map at the given grid side.
+    Map(u32),
+    /// Snake animation over the whole 16×16 curve.
+    Snake,
+    /// Spinning 3D view.
+    View3d,
+}
+
+impl Image {
+    /// Images the gallery shows for `entry`.
+    fn all_for(entry: &CurveEntry) -> Vec<Self> {
+        let mut images: Vec<Self> = MAP_SIDES
+            .iter()
+            .filter(|&&side| accepts(entry, &[side, side]))
+            .map(|&side| Self::Map(side))
+            .collect();
+        if accepts(entry, &[SNAKE_SIDE, SNAKE_SIDE]) {
+            images.push(Self::Snake);
+        }
+        if accepts(entry, &[VIEW_3D_SIDE; 3]) {
+            images.push(Self::View3d);
+        }
+        images
+    }
+
+    /// File name of this image of curve `key`.
+    fn file_name(self, key: &str) -> String {
+        match self {
+            Self::Map(side) => format!("{key}-{side}.png"),
+            Self::Snake => format!("{key}-snake.gif"),
+            Self::View3d => format!("{key}-3d.gif"),
+        }
+    }
+
+    /// Render this image of curve `key` to `path` with the `scurve` binary.
+    fn render(self, sh: &Shell, scurve: &Path, key: &str, path: &Path) -> Result<()> {
+        match self {
+            Self::Map(side) => {
+                let side = side.to_string();
+                cmd!(
+                    sh,
+                    "{scurve} map -s {MAP_PIXELS} -w {MAP_LINE_WIDTH} -d {side} {key} {path}"
+                )
+                .run()
+                .with_context(|| format!("rendering the {key} map"))?;
+            }
+            Self::Snake => {
+                let side = SNAKE_SIDE.to_string();
+                let chunk = format!("0:{}", SNAKE_SIDE * SNAKE_SIDE);
+                cmd!(
+                    sh,
+                    "{scurve} snake -s {SNAKE_PIXELS} -d {side} --chunk {chunk} --trail {SNAKE_TRAIL} --full {SNAKE_FULL} {key} {path}"
+                )
+                .run()
+                .with_context(|| format!("rendering the {key} snake"))?;
+            }
+            Self::View3d => {
+                let side = VIEW_3D_SIDE.to_string();
+                cmd!(
+                    sh,
+                    "{scurve} record -p 3d --seconds {VIEW_3D_SECONDS} --fps {VIEW_3D_FPS} -c {key} -s {side} {path}"
+                )
+                .run()
+                .with_context(|| format!("rendering the {key} 3D view"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Render the gallery into `assets/curves/`.
+pub fn render_gallery(paths: &RepoPaths, args: GalleryArgs) -> Result<()> {
+    let out = paths.root.join("assets").join("curves");
+    if args.force && out.exists() {
+        fs::remove_dir_all(&out).with_context(|| format!("failed to clear {}", out.display()))?;
+    }
+    fs::create_dir_all(&out).with_context(|| format!("failed to create {}", out.display()))?;
+
+    let images: Vec<(&CurveEntry, Image, PathBuf)> = REGISTRY
+        .iter()
+        .flat_map(|entry| {
+            let out = &out;
+            Image::all_for(entry)
+                .into_iter()
+                .map(move |image| (entry, image, out.join(image.file_name(entry.key))))
+        })
+        .collect();
+    let total = images.len();
+    let (existing, pending): (Vec<_>, Vec<_>) =
+        images.into_iter().partition(|(_, _, path)| path.exists());
+    if let Some((_, _, first)) = existing.first()
+        && !args.skip_existing
+    {
+        bail!(
+            "{} of {total} gallery images already exist (first: {}); pass --skip-existing to render only the missing ones or --force to render them all again",
+            existing.len(),
+            first.display()
+        );
+    }
+
+    if !pending.is_empty() {
+        let sh = repo_shell(paths)?;
+        cmd!(sh, "cargo build -q --release -p scurve").run()?;
+        let scurve = paths.root.join("target").join("release").join("scurve");
+        for (entry, image, path) in &pending {
+            image.render(&sh, &scurve, entry.key, path)?;
+        }
+    }
+
+    println!(
+        "Rendered: {}, skipped (already existed): {} in {}",
+        pending.len(),
+        existing.len(),
+        out.display()
+    );
+    Ok(())
+}
+
+/// Whether `entry` can be built over a grid with the given `extents`.
+fn accepts(entry: &CurveEntry, extents: &[u32]) -> bool {
+    (entry.build_spec)(extents).is_ok()
+}
@@ -0,0 +1,162 @@
+//! Dependency license table for the About dialog.
+//!
+//! `cargo xtask licenses` resolves the key dependencies of `scurve-gui` with
+//! `cargo metadata` and writes their versions and licenses to
+//! `crates/scurve-gui/src/dependency_licenses.rs`, which is checked in. Re-run
+//! it after changing dependencies; `--check` only reports whether the table is
+//! up to date.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+};
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use xshell::cmd;
+
+use crate::{RepoPaths, repo_shell};
+
+/// Package whose dependency graph is searched.
+const ROOT_PACKAGE: &str = "scurve-gui";
+
+/// Dependencies listed in the About dialog, in display order.
+const KEY_DEPENDENCIES: &[&str] = &[
+    "egui",
+    "eframe",
+    "egui_commonmark",
+    "webbrowser",
+    "png",
+    "anyhow",
+    "smallvec",
+    "thiserror",
+];
+
+/// A resolved package from `cargo metadata`.
+#[derive(Clone, Copy)]
+struct Package<'a> {
+    /// Crate name.
+    name: &'a str,
+    /// Resolved version.
+    version: &'a str,
+    /// SPDX license expression, if the manifest declares one.
+    license: Option<&'a str>,
+}
+
+/// Regenerate the license table, or with `check` fail if it is stale.
+pub fn licenses(paths: &RepoPaths, check: bool) -> Result<()> {
+    let sh = repo_shell(paths)?;
+    let metadata = cmd!(sh, "cargo metadata --format-version 1 --locked").read()?;
+    let metadata: Value =
+        serde_json::from_str(&metadata).context("failed to parse cargo metadata output")?;
+    let table = render(&key_packages(&metadata)?);
+
+    let path = paths
+        .root
+        .join("crates")
+        .join("scurve-gui")
+        .join("src")
+        .join("dependency_licenses.rs");
+    if check {
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        if current != table {
+            bail!(
+                "{} is out of date; run `cargo xtask licenses`",
+                path.display()
+            );
+        }
+        println!("{} is up to date", path.display());
+        return Ok(());
+    }
+    fs::write(&path, table).with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// The package of each of [`KEY_DEPENDENCIES`], in order.
+///
+/// A crate that appears in several versions resolves to the one nearest to
+/// [`ROOT_PACKAGE`] in the dependency graph, so a direct dependency wins over
+/// an older copy pulled in further down.
+fn key_packages(metadata: &Value) -> Result<Vec<Package<'_>>> {
+    let packages: HashMap<&str, Package<'_>> = metadata["packages"]
+        .as_array()
+        .context("cargo metadata has no packages")?
+        .iter()
+        .filter_map(|package| {
+            Some((
+                str_field(package, "id")?,
+                Package {
+                    name: str_field(package, "name")?,
+                    version: str_field(package, "version")?,
+                    license: str_field(package, "license"),
+                },
+            ))
+        })
+        .collect();
+    let dependencies: HashMap<&str, Vec<&str>> = metadata["resolve"]["nodes"]
+        .as_array()
+        .context("cargo metadata has no resolved dependency graph")?
+        .iter()
+        .filter_map(|node| {
+            let deps = node["dependencies"].as_array()?;
+            Some((
+                str_field(node, "id")?,
+                deps.iter().filter_map(Value::as_str).collect(),
+            ))
+        })
+        .collect();
+    let root = packages
+        .iter()
+        .find(|(_, package)| package.name == ROOT_PACKAGE)
+        .map(|(&id, _)| id)
+        .with_context(|| format!("{ROOT_PACKAGE} is not in the workspace"))?;
+
+    let mut nearest: HashMap<&str, &Package<'_>> = HashMap::new();
+    let mut seen = HashSet::from([root]);
+    let mut queue = VecDeque::from([root]);
+    while let Some(id) = queue.pop_front() {
+        for &dep in dependencies.get(id).into_iter().flatten() {
+            if !seen.insert(dep) {
+                continue;
+            }
+            if let Some(package) = packages.get(dep) {
+                nearest.entry(package.name).or_insert(package);
+            }
+            queue.push_back(dep);
+        }
+    }
+
+    KEY_DEPENDENCIES
+        .iter()
+        .map(|&name| {
+            let package = nearest
+                .get(name)
+                .with_context(|| format!("{name} is not a dependency of {ROOT_PACKAGE}"))?;
+            Ok(**package)
+        })
+        .collect()
+}
+
+/// String field `key` of a JSON object.
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Rust source of the license table.
+fn render(packages: &[Package<'_>]) -> String {
+    let mut table = String::from(
+        "// Generated by `cargo xtask licenses`; do not edit by hand.\n\n\
+         /// Key dependencies as `(name, version, license)`.\n\
+         const DEPENDENCY_LICENSES: &[(&str, &str, &str)] = &[\n",
+    );
+    for package in packages {
+        let license = package.license.unwrap_or("unknown");
+        table.push_str(&format!(
+            "    ({:?}, {:?}, {license:?}),\n",
+            package.name, package.version
+        ));
+    }
+    table.push_str("];\n");
+    table
+}
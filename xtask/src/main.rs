@@ -16,6 +16,10 @@ use clap::{Parser, Subcommand};
 use tiny_http::{Header, Response, Server, StatusCode};
 use xshell::{Shell, cmd};
 
+mod gallery;
+mod licenses;
+mod ui_diff;
+
 /// Bind address used by `serve-dist`.
 const DIST_HOST: Ipv4Addr = Ipv4Addr::new(127, 0, 0, 1);
 /// Default port used by `serve-dist`.
@@ -40,6 +44,23 @@ enum CommandName {
     /// Web build and serve tasks.
     #[command(subcommand)]
     Web(WebCommand),
+    /// Render the curve gallery into `assets/curves/`.
+    RenderGallery(gallery::GalleryArgs),
+    /// Regenerate the dependency license table shown in the GUI About dialog.
+    Licenses {
+        /// Only check that the checked-in table is up to date.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Compare GUI screenshots with the committed baselines.
+    UiDiff {
+        /// Rewrite the baselines from fresh captures instead of comparing.
+        #[arg(long)]
+        update: bool,
+        /// Fraction of pixels that may differ before a screenshot counts as changed.
+        #[arg(long, default_value_t = ui_diff::DEFAULT_THRESHOLD)]
+        threshold: f64,
+    },
 }
 
 /// Web build and serve commands.
@@ -119,14 +140,21 @@ fn run() -> Result<()> {
             WebCommand::Build => web_build(&paths),
             WebCommand::ServeDist { port } => web_serve_dist(&paths, port),
         },
+        CommandName::RenderGallery(args) => gallery::render_gallery(&paths, args),
+        CommandName::Licenses { check } => licenses::licenses(&paths, check),
+        CommandName::UiDiff { update, threshold } => ui_diff::ui_diff(&paths, update, threshold),
     }
 }
 
-/// Run `cargo fmt` and the workspace linter.
+/// Run `cargo fmt` and the workspace linter, then check that every feature
+/// combination still builds cleanly.
 fn tidy(paths: &RepoPaths) -> Result<()> {
     format_workspace(paths)?;
     lint_workspace(paths)?;
     format_workspace(paths)?;
+    check_all_features(paths)?;
+    check_no_std(paths)?;
+    check_docs(paths)?;
     Ok(())
 }
 
@@ -162,6 +190,46 @@ fn lint_workspace(paths: &RepoPaths) -> Result<()> {
     Ok(())
 }
 
+/// Build and lint with every feature enabled, denying warnings, so code
+/// behind optional features such as `screenshot` cannot silently break.
+fn check_all_features(paths: &RepoPaths) -> Result<()> {
+    let sh = repo_shell(paths)?;
+    cmd!(sh, "cargo build -q --all --all-targets --all-features").run()?;
+    cmd!(
+        sh,
+        "cargo clippy -q --all --all-targets --all-features -- -D warnings"
+    )
+    .run()?;
+    Ok(())
+}
+
+/// Lint and test `spacecurve` without its default `std` feature, so the
+/// `no_std` build and its tests keep compiling against `alloc` alone.
+fn check_no_std(paths: &RepoPaths) -> Result<()> {
+    let sh = repo_shell(paths)?;
+    cmd!(
+        sh,
+        "cargo clippy -q -p spacecurve --no-default-features --all-targets -- -D warnings"
+    )
+    .run()?;
+    cmd!(
+        sh,
+        "cargo test -q -p spacecurve --no-default-features --lib"
+    )
+    .run()?;
+    Ok(())
+}
+
+/// Build the workspace docs with warnings denied, so broken intra-doc links
+/// fail the check.
+fn check_docs(paths: &RepoPaths) -> Result<()> {
+    let sh = repo_shell(paths)?;
+    cmd!(sh, "cargo doc -q --workspace --no-deps")
+        .env("RUSTDOCFLAGS", "-D warnings")
+        .run()?;
+    Ok(())
+}
+
 /// Create a verbose shell rooted at the repository root.
 fn repo_shell(paths: &RepoPaths) -> Result<Shell> {
     let sh = Shell::new()?;
@@ -482,19 +550,38 @@ fn production_index_html() -> &'static str {
   <style>
     html, body { margin: 0; padding: 0; height: 100%; overflow: hidden; background: #2c3e50; font-family: Arial, sans-serif; }
     canvas { display: block; width: 100vw; height: 100vh; border: 2px solid #34495e; border-radius: 8px; box-shadow: 0 4px 8px rgba(0,0,0,.3); }
-    .loading { position: absolute; bottom: 16px; left: 50%; transform: translateX(-50%); color: white; font-size: 1.1em; }
+    .loading { position: absolute; bottom: 16px; left: 50%; transform: translateX(-50%); max-width: 80vw; text-align: center; color: white; font-size: 1.1em; }
   </style>
 </head>
 <body>
   <canvas id="bevy"></canvas>
   <div class="loading" id="loading">Loading...</div>
 
+  <!-- Reports download progress; the app reports the rest of startup. -->
   <script type="module">
     import init from './scurve-web.js';
-    init().then(() => {
-      const loading = document.getElementById('loading');
-      if (loading) loading.style.display = 'none';
-    }).catch(console.error);
+    const status = document.getElementById('loading');
+    async function fetchWithProgress(url) {
+      const response = await fetch(url);
+      const total = Number(response.headers.get('content-length'));
+      if (!response.body || !total) return response;
+      const reader = response.body.getReader();
+      let loaded = 0;
+      const body = new ReadableStream({
+        async pull(controller) {
+          const { done, value } = await reader.read();
+          if (done) return controller.close();
+          loaded += value.length;
+          status.textContent = `Loading… ${Math.min(100, Math.round(100 * loaded / total))}%`;
+          controller.enqueue(value);
+        },
+      });
+      return new Response(body, { headers: response.headers });
+    }
+    init({ module_or_path: fetchWithProgress('./scurve-web_bg.wasm') }).catch((err) => {
+      status.textContent = `Failed to load spacecurve: ${err}`;
+      console.error(err);
+    });
   </script>
 </body>
 </html>
@@ -0,0 +1,353 @@
+//! Golden screenshot comparison for the GUI.
+//!
+//! `cargo xtask ui-diff` captures every entry of [`SHOTS`] with
+//! `scurve screenshot` and compares it with the committed baseline under
+//! `crates/scurve-gui/tests/fixtures/ui`. Pixels are compared by perceived
+//! brightness and color (the YIQ distance used by pixelmatch), so subpixel
+//! antialiasing noise stays below the per-pixel threshold while moved or
+//! recolored widgets do not. Captures, diff images and an HTML report of the
+//! changed shots are written to `target/ui-diff`.
+//!
+//! A shot without a baseline does not fail the run: the capture is reported as
+//! new, and `cargo xtask ui-diff --update` records it as the first baseline.
+//! This is how the baselines are bootstrapped on a machine with a display.
+
+use std::{
+    fs,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use anyhow::{Context, Result, bail};
+use xshell::cmd;
+
+use crate::{RepoPaths, repo_shell};
+
+/// Default fraction of pixels that may differ before a shot counts as changed.
+pub const DEFAULT_THRESHOLD: f64 = 0.001;
+
+/// Normalized YIQ distance above which two pixels count as different.
+const PIXEL_THRESHOLD: f64 = 0.1;
+
+/// Largest possible YIQ distance between two colors.
+const MAX_DELTA: f64 = 35215.0;
+
+/// A screenshot in the standard matrix.
+struct Shot {
+    /// File stem of the baseline and capture.
+    name: &'static str,
+    /// Arguments to `scurve screenshot`, before the output path.
+    args: &'static [&'static str],
+}
+
+/// The standard screenshot matrix: every capture target, plus curves and
+/// settings that exercise the heavier drawing paths.
+const SHOTS: &[Shot] = &[
+    Shot {
+        name: "2d",
+        args: &["-p", "2d"],
+    },
+    Shot {
+        name: "3d",
+        args: &["-p", "3d", "--rotation", "30"],
+    },
+    Shot {
+        name: "about",
+        args: &["-p", "about"],
+    },
+    Shot {
+        name: "settings",
+        args: &["-p", "settings"],
+    },
+    Shot {
+        name: "settings-3d",
+        args: &["-p", "settings-3d"],
+    },
+    Shot {
+        name: "2d-zorder-opaque",
+        args: &[
+            "-p",
+            "2d",
+            "--curve",
+            "zorder",
+            "--size",
+            "16",
+            "--opacity",
+            "1",
+        ],
+    },
+    Shot {
+        name: "3d-hilbert-no-snake",
+        args: &[
+            "-p", "3d", "--curve", "hilbert", "--size", "4", "--snake", "off",
+        ],
+    },
+];
+
+/// How a capture compares with its baseline.
+enum Outcome {
+    /// Within the threshold.
+    Unchanged,
+    /// Differs in this fraction of pixels, or in size (fraction 1).
+    Changed(f64),
+    /// No baseline has been committed yet.
+    New,
+}
+
+/// An RGBA8 image.
+struct Rgba {
+    /// Width in pixels.
+    width: u32,
+    /// Height in pixels.
+    height: u32,
+    /// Row-major RGBA bytes.
+    data: Vec<u8>,
+}
+
+/// Capture the matrix and compare it with the baselines, or rewrite the
+/// baselines when `update` is set.
+///
+/// Fails when any shot changed by more than `threshold` (a fraction of
+/// pixels). Shots without a baseline are listed but do not fail the run.
+pub fn ui_diff(paths: &RepoPaths, update: bool, threshold: f64) -> Result<()> {
+    let baselines = paths
+        .root
+        .join("crates")
+        .join("scurve-gui")
+        .join("tests")
+        .join("fixtures")
+        .join("ui");
+    let out = paths.root.join("target").join("ui-diff");
+    if out.exists() {
+        fs::remove_dir_all(&out).with_context(|| format!("failed to clear {}", out.display()))?;
+    }
+    let captures = out.join("current");
+    let copies = out.join("baseline");
+    for dir in [&captures, &copies] {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+
+    let sh = repo_shell(paths)?;
+    cmd!(sh, "cargo build -q -p scurve --features screenshot").run()?;
+    for shot in SHOTS {
+        let path = captures.join(format!("{}.png", shot.name));
+        let args = shot.args;
+        cmd!(
+            sh,
+            "cargo run -q -p scurve --features screenshot -- screenshot {args...} {path}"
+        )
+        .run()
+        .with_context(|| format!("capturing {} (screenshots need a display)", shot.name))?;
+    }
+
+    if update {
+        fs::create_dir_all(&baselines)
+            .with_context(|| format!("failed to create {}", baselines.display()))?;
+        for shot in SHOTS {
+            let file = format!("{}.png", shot.name);
+            fs::copy(captures.join(&file), baselines.join(&file))
+                .with_context(|| format!("failed to update baseline {file}"))?;
+        }
+        println!(
+            "Updated {} baselines in {}",
+            SHOTS.len(),
+            baselines.display()
+        );
+        return Ok(());
+    }
+
+    let mut rows = Vec::new();
+    let mut failures = 0;
+    let mut missing = 0;
+    for shot in SHOTS {
+        let file = format!("{}.png", shot.name);
+        let current = read_png(&captures.join(&file))?;
+        let baseline_path = baselines.join(&file);
+        let outcome = if baseline_path.is_file() {
+            fs::copy(&baseline_path, copies.join(&file))
+                .with_context(|| format!("failed to copy baseline {file}"))?;
+            let baseline = read_png(&baseline_path)?;
+            let (fraction, diff) = compare(&baseline, &current);
+            if let Some(diff) = diff {
+                write_png(&out.join(format!("{}-diff.png", shot.name)), &diff)?;
+            }
+            if fraction > threshold {
+                Outcome::Changed(fraction)
+            } else {
+                Outcome::Unchanged
+            }
+        } else {
+            Outcome::New
+        };
+        let status = match outcome {
+            Outcome::Unchanged => {
+                println!("ok       {}", shot.name);
+                continue;
+            }
+            Outcome::Changed(fraction) => {
+                let status = format!("{:.2}% of pixels changed", fraction * 100.0);
+                println!("CHANGED  {} ({status})", shot.name);
+                status
+            }
+            Outcome::New => {
+                println!("NEW      {} (no baseline)", shot.name);
+                missing += 1;
+                rows.push(report_row(shot.name, "no baseline"));
+                continue;
+            }
+        };
+        failures += 1;
+        rows.push(report_row(shot.name, &status));
+    }
+
+    let report = out.join("report.html");
+    fs::write(&report, report_html(&rows.concat(), failures, missing))
+        .with_context(|| format!("failed to write {}", report.display()))?;
+    println!("Report: {}", report.display());
+    if missing > 0 {
+        println!(
+            "{missing} of {} screenshots have no baseline yet; check their captures in {} \
+             and run `cargo xtask ui-diff --update` to record them in {}",
+            SHOTS.len(),
+            captures.display(),
+            baselines.display()
+        );
+    }
+    if failures > 0 {
+        bail!(
+            "{failures} of {} screenshots changed; review the report and run \
+             `cargo xtask ui-diff --update` if the changes are intended",
+            SHOTS.len()
+        );
+    }
+    Ok(())
+}
+
+/// Fraction of pixels whose YIQ distance exceeds [`PIXEL_THRESHOLD`], and a
+/// diff image marking them in red over a faded copy of `baseline`.
+///
+/// Images of different sizes differ everywhere and get no diff image.
+fn compare(baseline: &Rgba, current: &Rgba) -> (f64, Option<Rgba>) {
+    if (baseline.width, baseline.height) != (current.width, current.height) {
+        return (1.0, None);
+    }
+    let mut diff = Vec::with_capacity(baseline.data.len());
+    let mut changed = 0usize;
+    for (a, b) in baseline
+        .data
+        .chunks_exact(4)
+        .zip(current.data.chunks_exact(4))
+    {
+        if yiq_delta(a, b) > PIXEL_THRESHOLD * PIXEL_THRESHOLD * MAX_DELTA {
+            changed += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let gray = (255.0 - 0.1 * (255.0 - luma(a))) as u8;
+            diff.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+    let pixels = (baseline.width as usize * baseline.height as usize).max(1);
+    let image = Rgba {
+        width: baseline.width,
+        height: baseline.height,
+        data: diff,
+    };
+    (changed as f64 / pixels as f64, Some(image))
+}
+
+/// Squared YIQ distance between two RGBA pixels, blended over white.
+fn yiq_delta(first: &[u8], second: &[u8]) -> f64 {
+    let (ya, ia, qa) = yiq(first);
+    let (yb, ib, qb) = yiq(second);
+    0.5053 * (ya - yb).powi(2) + 0.299 * (ia - ib).powi(2) + 0.1957 * (qa - qb).powi(2)
+}
+
+/// YIQ components of an RGBA pixel blended over white.
+fn yiq(pixel: &[u8]) -> (f64, f64, f64) {
+    let alpha = f64::from(pixel[3]) / 255.0;
+    let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(|c| 255.0 + (f64::from(c) - 255.0) * alpha);
+    (
+        r * 0.298_895_31 + g * 0.586_622_47 + b * 0.114_482_23,
+        r * 0.595_977_99 - g * 0.274_176_00 - b * 0.321_801_99,
+        r * 0.211_470_17 - g * 0.522_617_02 + b * 0.311_146_85,
+    )
+}
+
+/// Brightness of an RGBA pixel, for the faded diff background.
+fn luma(pixel: &[u8]) -> f64 {
+    yiq(pixel).0
+}
+
+/// A report row showing the baseline, capture and diff of a shot.
+fn report_row(name: &str, status: &str) -> String {
+    format!(
+        "<tr><th>{name}<br><small>{status}</small></th>\
+         <td><img src=\"baseline/{name}.png\" alt=\"no baseline\"></td>\
+         <td><img src=\"current/{name}.png\"></td>\
+         <td><img src=\"{name}-diff.png\" alt=\"no diff\"></td></tr>\n"
+    )
+}
+
+/// The HTML report for the changed and new shots in `rows`.
+fn report_html(rows: &str, failures: usize, missing: usize) -> String {
+    let mut summary = if failures == 0 {
+        format!("No screenshots changed out of {}.", SHOTS.len())
+    } else {
+        format!("{failures} of {} screenshots changed.", SHOTS.len())
+    };
+    if missing > 0 {
+        summary.push_str(&format!(
+            " {missing} have no baseline yet; run <code>cargo xtask ui-diff --update</code> to record them."
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>spacecurve UI diff</title>\n<style>\n\
+         body {{ font-family: sans-serif; background: #222; color: #eee; }}\n\
+         td, th {{ padding: 8px; vertical-align: top; text-align: left; }}\n\
+         img {{ max-width: 400px; border: 1px solid #555; }}\n\
+         </style>\n</head>\n<body>\n<h1>UI diff</h1>\n<p>{summary}</p>\n\
+         <table>\n<tr><th></th><th>Baseline</th><th>Current</th><th>Diff</th></tr>\n\
+         {rows}</table>\n</body>\n</html>\n"
+    )
+}
+
+/// Decode an 8-bit RGB or RGBA PNG into RGBA.
+fn read_png(path: &Path) -> Result<Rgba> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let mut buf = vec![0; reader.output_buffer_size().context("PNG too large")?];
+    let info = reader
+        .next_frame(&mut buf)
+        .with_context(|| format!("failed to decode {}", path.display()))?;
+    buf.truncate(info.buffer_size());
+    let data = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        other => bail!("{}: unsupported PNG color type {other:?}", path.display()),
+    };
+    Ok(Rgba {
+        width: info.width,
+        height: info.height,
+        data,
+    })
+}
+
+/// Encode an RGBA image as a PNG.
+fn write_png(path: &Path, image: &Rgba) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&image.data)?;
+    Ok(())
+}